@@ -27,6 +27,7 @@ use anyhow::{anyhow, Result};
 use arrow_array::{
     Array, Float32Array, Int32Array, ListArray, RecordBatch, StringArray, TimestampMillisecondArray,
 };
+use arrow_schema::{DataType, Schema};
 
 /// Required UTF-8 string column.
 pub fn string_column<'a>(batch: &'a RecordBatch, name: &str) -> Result<&'a StringArray> {
@@ -82,6 +83,14 @@ fn required<'a, A: Array + 'static>(batch: &'a RecordBatch, name: &str) -> Resul
         .ok_or_else(|| anyhow!("column '{}' has an unexpected Arrow type", name))
 }
 
+/// Vector size of a schema's `embedding` column, if it has one.
+pub fn embedding_dimension(schema: &Schema) -> Option<usize> {
+    match schema.field_with_name("embedding").ok()?.data_type() {
+        DataType::FixedSizeList(_, size) => Some(*size as usize),
+        _ => None,
+    }
+}
+
 /// Generic optional-column accessor backing the typed `*_opt` wrappers above.
 fn optional<'a, A: Array + 'static>(batch: &'a RecordBatch, name: &str) -> Option<&'a A> {
     batch.column_by_name(name)?.as_any().downcast_ref::<A>()
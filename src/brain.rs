@@ -0,0 +1,141 @@
+// Copyright 2026 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Entry point for embedding octobrain in other Rust tools.
+//!
+//! [`Octobrain`] bundles a project-scoped memory manager with a lazily opened
+//! knowledge base, using the same configuration and storage as the CLI:
+//!
+//! ```no_run
+//! use octobrain::{MemoryType, Octobrain};
+//!
+//! # async fn run() -> anyhow::Result<()> {
+//! let mut brain = Octobrain::open("github.com/acme/api").await?;
+//! brain
+//!     .memorize(MemoryType::Decision, "Retry policy", "Back off exponentially, max 5 tries")
+//!     .await?;
+//! for hit in brain.remember("how do we retry?").await? {
+//!     println!("{:.2} {}", hit.relevance_score, hit.memory.title);
+//! }
+//! let docs = brain
+//!     .knowledge()
+//!     .await?
+//!     .search("tokio select", Default::default(), None, Some(3), None)
+//!     .await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use anyhow::Result;
+use tokio::sync::OnceCell;
+
+use crate::config::Config;
+use crate::knowledge::KnowledgeManager;
+use crate::memory::manager::MemorizeParams;
+use crate::memory::{Memory, MemoryManager, MemoryQuery, MemorySearchResult, MemoryType};
+
+/// Memories of one project plus the shared knowledge base.
+pub struct Octobrain {
+    config: Config,
+    memory: MemoryManager,
+    knowledge: OnceCell<KnowledgeManager>,
+}
+
+impl Octobrain {
+    /// Open the memories of `project` (any stable key, e.g. a normalized Git
+    /// remote) with the user's config file.
+    pub async fn open(project: impl Into<String>) -> Result<Self> {
+        Self::with_config(Config::load()?, Some(project.into()), None).await
+    }
+
+    /// Open with an explicit configuration; `project` None sees every project
+    /// and writes to "default", `role` scopes memories to one agent role.
+    /// Use `Config::load_ephemeral()` for a store that never touches disk.
+    pub async fn with_config(
+        config: Config,
+        project: Option<String>,
+        role: Option<String>,
+    ) -> Result<Self> {
+        let memory = MemoryManager::new(&config, project, role).await?;
+        Ok(Self {
+            config,
+            memory,
+            knowledge: OnceCell::new(),
+        })
+    }
+
+    /// Store a memory with default importance, no tags and auto-detected files.
+    pub async fn memorize(
+        &mut self,
+        memory_type: MemoryType,
+        title: impl Into<String>,
+        content: impl Into<String>,
+    ) -> Result<Memory> {
+        self.memory
+            .memorize(MemorizeParams::new(memory_type, title, content))
+            .await
+    }
+
+    /// Store a memory with every option of the `memorize` MCP tool.
+    ///
+    /// ```no_run
+    /// use octobrain::{MemorizeParams, MemoryType, Octobrain};
+    ///
+    /// # async fn run() -> anyhow::Result<()> {
+    /// let mut brain = Octobrain::open("github.com/acme/api").await?;
+    /// let mut params = MemorizeParams::new(
+    ///     MemoryType::BugFix,
+    ///     "Flaky upload test",
+    ///     "The upload test raced the cleanup task; it now awaits the handle",
+    /// );
+    /// params.importance = Some(0.8);
+    /// params.tags = Some(vec!["tests".to_string()]);
+    /// brain.memorize_with(params).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn memorize_with(&mut self, params: MemorizeParams) -> Result<Memory> {
+        self.memory.memorize(params).await
+    }
+
+    /// Semantic search over this project's memories, best first.
+    pub async fn remember(&self, query: &str) -> Result<Vec<MemorySearchResult>> {
+        self.memory.remember(query, None).await
+    }
+
+    /// Semantic search narrowed by `filters` (types, tags, dates, limit, ...).
+    pub async fn remember_with(
+        &self,
+        query: &str,
+        filters: MemoryQuery,
+    ) -> Result<Vec<MemorySearchResult>> {
+        self.memory.remember(query, Some(filters)).await
+    }
+
+    /// The knowledge base, opened on first use.
+    pub async fn knowledge(&self) -> Result<&KnowledgeManager> {
+        self.knowledge
+            .get_or_try_init(|| KnowledgeManager::new(&self.config))
+            .await
+    }
+
+    /// Full memory manager for operations without a shortcut here.
+    pub fn memory(&mut self) -> &mut MemoryManager {
+        &mut self.memory
+    }
+
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+}
@@ -19,6 +19,16 @@ use clap::{ArgAction, Parser, Subcommand};
 #[command(version, author = "Muvon Un Limited <opensource@muvon.io>")]
 #[command(about = "Standalone memory management system for AI context and conversation state", long_about = None)]
 pub struct Cli {
+    /// Keep all memories and knowledge in process memory for this run only;
+    /// nothing is read from or written to the storage directory
+    #[arg(long, global = true)]
+    pub ephemeral: bool,
+
+    /// Project to operate on: a repository path (resolved to the project ID its
+    /// memories are stored under) or a project key. Default: unscoped
+    #[arg(long, global = true, value_name = "PATH_OR_ID")]
+    pub project: Option<String>,
+
     #[command(subcommand)]
     pub command: Commands,
 }
@@ -27,10 +37,6 @@ pub struct Cli {
 pub enum Commands {
     /// Memory management for storing and retrieving information
     Memory {
-        /// Scope memories to a specific project key (default: auto-detected from Git remote)
-        #[arg(long, global = true)]
-        project: Option<String>,
-
         /// Filter memories by role (e.g. "developer", "reviewer"). No filter = all memories.
         #[arg(long, global = true)]
         role: Option<String>,
@@ -43,12 +49,89 @@ pub enum Commands {
         #[command(subcommand)]
         command: KnowledgeCommand,
     },
+    /// Search memories and the knowledge base together, best matches first
+    Search {
+        /// Search query
+        query: String,
+
+        /// Filter memories by role. No filter = all memories.
+        #[arg(long)]
+        role: Option<String>,
+
+        /// Maximum number of results across both stores
+        #[arg(short, long, default_value = "10")]
+        limit: usize,
+
+        /// Output format: text or json
+        #[arg(short, long, default_value = "text")]
+        format: String,
+    },
     /// Start MCP server (Model Context Protocol) exposing memory tools
     Mcp {
         /// Bind to HTTP server on host:port instead of using stdin/stdout (e.g., "0.0.0.0:12345")
         #[arg(long, value_name = "HOST:PORT")]
         bind: Option<String>,
     },
+    /// Serve memory replication endpoints for `octobrain sync` clients
+    Serve {
+        /// Enable the sync endpoints (/sync/changes, /sync/push)
+        #[arg(long, action = ArgAction::SetTrue)]
+        sync: bool,
+
+        /// Address to listen on
+        #[arg(long, value_name = "HOST:PORT", default_value = "127.0.0.1:12346")]
+        bind: String,
+
+        /// Shared token clients must present (defaults to $OCTOBRAIN_SYNC_TOKEN)
+        #[arg(long)]
+        token: Option<String>,
+    },
+    /// Run periodic upkeep across all projects and print a JSON summary;
+    /// safe to schedule from cron or a systemd timer
+    Maintenance {
+        /// Tasks to run (comma-separated): cleanup, decay, optimize, knowledge-refresh
+        /// (default: all)
+        #[arg(long)]
+        tasks: Option<String>,
+    },
+    /// Run maintenance tasks on their [maintenance] intervals until stopped;
+    /// run it under systemd, launchd or nohup to keep it in the background
+    Daemon,
+    /// Bring stored data up to date with the current configuration
+    Migrate {
+        #[command(subcommand)]
+        command: MigrateCommand,
+    },
+    /// Maintain the on-disk databases
+    Storage {
+        #[command(subcommand)]
+        command: StorageCommand,
+    },
+    /// Inspect and edit the config file
+    Config {
+        #[command(subcommand)]
+        command: ConfigCommand,
+    },
+    /// Pull and push memory changes with a sync server, or through a folder
+    /// every machine can reach
+    Sync {
+        /// Sync server URL (e.g. http://host:12346) or sync folder path
+        #[arg(value_name = "REMOTE_OR_PATH", required_unless_present = "remote")]
+        target: Option<String>,
+
+        /// Sync server URL; same as passing it as REMOTE_OR_PATH
+        #[arg(long, value_name = "URL", conflicts_with = "target")]
+        remote: Option<String>,
+
+        /// Shared token for the server (defaults to $OCTOBRAIN_SYNC_TOKEN)
+        #[arg(long)]
+        token: Option<String>,
+
+        /// How to settle memories changed on both sides: last-write-wins,
+        /// keep-both or interactive (default: [memory].conflict_strategy)
+        #[arg(long, value_name = "STRATEGY")]
+        on_conflict: Option<String>,
+    },
 }
 
 #[derive(Subcommand, Debug)]
@@ -56,12 +139,19 @@ pub enum MemoryCommand {
     /// Store important information, insights, or context in memory
     Memorize {
         /// Short, descriptive title for the memory (5-200 characters)
-        #[arg(short, long)]
-        title: String,
+        #[arg(short, long, required_unless_present_any = ["transcript", "edit"])]
+        title: Option<String>,
 
-        /// Detailed content to remember
-        #[arg(short, long)]
-        content: String,
+        /// Detailed content to remember; `-` reads it from standard input
+        /// (optional with --image: the caption is used)
+        #[arg(short, long, required_unless_present_any = ["image", "transcript", "edit"])]
+        content: Option<String>,
+
+        /// Write the memory in $VISUAL/$EDITOR: title, type, tags, importance and
+        /// files go in the front matter (pre-filled from the other options),
+        /// the content below it
+        #[arg(long, action = ArgAction::SetTrue, conflicts_with = "transcript")]
+        edit: bool,
 
         /// Category of memory for better organization
         #[arg(short = 'm', long, default_value = "code")]
@@ -78,6 +168,59 @@ pub enum MemoryCommand {
         /// Related file paths (comma-separated)
         #[arg(long)]
         files: Option<String>,
+
+        /// Store even if the content looks like a credential (API key, token, private key)
+        #[arg(long, action = ArgAction::SetTrue)]
+        allow_secrets: bool,
+
+        /// Files to attach, e.g. diagrams, log excerpts, patches (comma-separated)
+        #[arg(long)]
+        attach: Option<String>,
+
+        /// Image (png, jpg, gif, webp) to caption with the [vision] model and attach
+        #[arg(long)]
+        image: Option<String>,
+
+        /// Meeting transcript (.vtt or .srt) to mine for Decision/Learning memories
+        #[arg(long, conflicts_with_all = ["title", "content", "image"])]
+        transcript: Option<String>,
+
+        /// With --transcript: summarize each segment with the [search.answer] model
+        /// instead of keyword extraction
+        #[arg(long, action = ArgAction::SetTrue, requires = "transcript")]
+        summarize: bool,
+    },
+
+    /// Run a command and store its output, exit code and command line as a memory
+    /// (e.g. `octobrain memory capture -- cargo test`); exits with the command's status
+    Capture {
+        /// Title for the memory (default: the command line and its exit code)
+        #[arg(short, long)]
+        title: Option<String>,
+
+        /// Category of memory for better organization
+        #[arg(short = 'm', long, default_value = "bug_fix")]
+        memory_type: String,
+
+        /// Importance score from 0.0 to 1.0 (higher = more important)
+        #[arg(short, long)]
+        importance: Option<f32>,
+
+        /// Extra tags (comma-separated); `capture` is always added
+        #[arg(long)]
+        tags: Option<String>,
+
+        /// Characters of output to keep (200-9000); the middle of long output is dropped
+        #[arg(long, default_value_t = crate::memory::capture::DEFAULT_MAX_OUTPUT_CHARS)]
+        max_output: usize,
+
+        /// Store even if the output looks like it contains a credential
+        #[arg(long, action = ArgAction::SetTrue)]
+        allow_secrets: bool,
+
+        /// Command to run, after `--`
+        #[arg(last = true, required = true)]
+        command: Vec<String>,
     },
 
     /// Search and retrieve stored memories using semantic search
@@ -99,7 +242,26 @@ pub enum MemoryCommand {
         /// Minimum relevance score (0.0-1.0)
         #[arg(long)]
         min_relevance: Option<f32>,
-        /// Output format: text, json, or compact
+        /// Only memories created by this author (e.g. a git user.email)
+        #[arg(long)]
+        author: Option<String>,
+        /// Skip memories with any of these tags (comma-separated)
+        #[arg(long)]
+        exclude_tags: Option<String>,
+        /// Skip memories of these types (comma-separated)
+        #[arg(long)]
+        exclude_types: Option<String>,
+        /// Skip memories related to any of these files (comma-separated)
+        #[arg(long)]
+        exclude_files: Option<String>,
+        /// Only memories created at or after this date (YYYY-MM-DD, RFC 3339,
+        /// or relative like 3d, 2w)
+        #[arg(long)]
+        after: Option<String>,
+        /// Only memories created at or before this date (same forms as --after)
+        #[arg(long)]
+        before: Option<String>,
+        /// Output format: text, json, compact, csv, yaml, or md (Markdown table)
         #[arg(short, long, default_value = "text")]
         format: String,
         /// Enable reranking for improved search accuracy
@@ -111,9 +273,16 @@ pub enum MemoryCommand {
         /// Reranker model (fully qualified, e.g., voyage:rerank-2.5)
         #[arg(long, value_name = "MODEL")]
         reranker_model: Option<String>,
+        /// Synthesize a short answer with memory-ID citations using the configured LLM
+        #[arg(long, action = ArgAction::SetTrue)]
+        answer: bool,
+        /// Show what each score is made of: vector similarity, keyword matches,
+        /// recency, importance after decay, trust, reranker and feedback
+        #[arg(long, action = ArgAction::SetTrue, conflicts_with = "answer")]
+        explain: bool,
     },
 
-    /// Permanently remove specific memories
+    /// Remove specific memories (recoverable with `memory undo` or `memory trash restore`)
     Forget {
         /// Specific memory ID to forget (get from remember results)
         #[arg(short, long)]
@@ -131,6 +300,27 @@ pub enum MemoryCommand {
         #[arg(long)]
         tags: Option<String>,
 
+        /// Spare memories with any of these tags when using query (comma-separated)
+        #[arg(long, requires = "query")]
+        exclude_tags: Option<String>,
+
+        /// Spare memories of these types when using query (comma-separated)
+        #[arg(long, requires = "query")]
+        exclude_types: Option<String>,
+
+        /// Spare memories related to any of these files when using query (comma-separated)
+        #[arg(long, requires = "query")]
+        exclude_files: Option<String>,
+
+        /// Only memories created at or after this date when using query
+        /// (YYYY-MM-DD, RFC 3339, or relative like 3d, 2w)
+        #[arg(long, requires = "query")]
+        after: Option<String>,
+
+        /// Only memories created at or before this date when using query
+        #[arg(long, requires = "query")]
+        before: Option<String>,
+
         /// Confirm deletion without prompting
         #[arg(short = 'y', long)]
         yes: bool,
@@ -153,6 +343,14 @@ pub enum MemoryCommand {
         #[arg(short, long)]
         importance: Option<f32>,
 
+        /// New memory type (optional)
+        #[arg(short = 'm', long)]
+        memory_type: Option<String>,
+
+        /// New confidence score, 0.0-1.0 (optional)
+        #[arg(long)]
+        confidence: Option<f32>,
+
         /// Add tags (comma-separated)
         #[arg(long)]
         add_tags: Option<String>,
@@ -178,6 +376,10 @@ pub enum MemoryCommand {
         /// Output format: text, json, or compact
         #[arg(short, long, default_value = "text")]
         format: String,
+
+        /// Show attachment details and extracted text
+        #[arg(long, action = ArgAction::SetTrue)]
+        with_attachments: bool,
     },
 
     /// List recent memories
@@ -190,7 +392,32 @@ pub enum MemoryCommand {
         #[arg(short = 'm', long)]
         memory_type: Option<String>,
 
-        /// Output format: text, json, or compact
+        /// Only memories created by this author (e.g. a git user.email)
+        #[arg(long)]
+        author: Option<String>,
+
+        /// Skip memories with any of these tags (comma-separated)
+        #[arg(long)]
+        exclude_tags: Option<String>,
+
+        /// Skip memories of these types (comma-separated)
+        #[arg(long)]
+        exclude_types: Option<String>,
+
+        /// Skip memories related to any of these files (comma-separated)
+        #[arg(long)]
+        exclude_files: Option<String>,
+
+        /// Only memories created at or after this date (YYYY-MM-DD, RFC 3339,
+        /// or relative like 3d, 2w)
+        #[arg(long)]
+        after: Option<String>,
+
+        /// Only memories created at or before this date
+        #[arg(long)]
+        before: Option<String>,
+
+        /// Output format: text, json, compact, csv, yaml, or md (Markdown table)
         #[arg(short, long, default_value = "compact")]
         format: String,
     },
@@ -204,7 +431,7 @@ pub enum MemoryCommand {
         #[arg(short, long, default_value = "20")]
         limit: usize,
 
-        /// Output format: text, json, or compact
+        /// Output format: text, json, compact, csv, yaml, or md (Markdown table)
         #[arg(short, long, default_value = "compact")]
         format: String,
     },
@@ -214,7 +441,7 @@ pub enum MemoryCommand {
         /// File paths to search for (comma-separated)
         files: String,
 
-        /// Output format: text, json, or compact
+        /// Output format: text, json, compact, csv, yaml, or md (Markdown table)
         #[arg(short, long, default_value = "text")]
         format: String,
     },
@@ -224,22 +451,34 @@ pub enum MemoryCommand {
         /// Tags to search for (comma-separated)
         tags: String,
 
-        /// Output format: text, json, or compact
+        /// Output format: text, json, compact, csv, yaml, or md (Markdown table)
         #[arg(short, long, default_value = "text")]
         format: String,
     },
 
     /// Get memories for current Git commit
     CurrentCommit {
-        /// Output format: text, json, or compact
+        /// Output format: text, json, compact, csv, yaml, or md (Markdown table)
         #[arg(short, long, default_value = "text")]
         format: String,
     },
 
     /// Show memory statistics
-    Stats,
+    Stats {
+        /// Add a calendar heatmap of memories created and accessed per day
+        #[arg(long, action = ArgAction::SetTrue)]
+        heatmap: bool,
 
-    /// Clean up old memories
+        /// Months of history in the heatmap
+        #[arg(short, long, default_value = "6")]
+        months: u32,
+
+        /// Output format: text, json, csv, yaml, or md (Markdown table; not with --heatmap)
+        #[arg(short, long, default_value = "text")]
+        format: String,
+    },
+
+    /// Clean up old memories (they go to the trash first)
     Cleanup {
         /// Confirm cleanup without prompting
         #[arg(short = 'y', long)]
@@ -289,27 +528,42 @@ pub enum MemoryCommand {
         /// Memory ID to find related memories for
         memory_id: String,
 
-        /// Output format: text, json, or compact
+        /// Output format: text, json, compact, csv, yaml, or md (Markdown table)
         #[arg(short, long, default_value = "text")]
         format: String,
     },
 
-    /// Manually trigger auto-linking for a memory
+    /// Run semantic auto-linking for one memory or the whole store; pairs that
+    /// are already related are skipped
     AutoLink {
-        /// Memory ID to auto-link
-        memory_id: String,
+        /// Memory ID, short ID or alias to link (default: every memory in scope)
+        #[arg(long = "id", value_name = "ID")]
+        memory_id: Option<String>,
+
+        /// Minimum similarity to link, 0.0-1.0 (default: [memory].auto_link_threshold)
+        #[arg(long)]
+        threshold: Option<f32>,
+
+        /// Print the relationships that would be created without storing them
+        #[arg(long, action = ArgAction::SetTrue)]
+        dry_run: bool,
     },
 
     /// Get memory graph with linked context
+    #[command(args_conflicts_with_subcommands = true)]
     Graph {
+        #[command(subcommand)]
+        graph: Option<GraphCommand>,
+
         /// Root memory ID
-        memory_id: String,
+        memory_id: Option<String>,
 
         /// Depth of graph traversal (1-3 recommended)
         #[arg(short, long, default_value = "2")]
         depth: usize,
 
-        /// Output format: text, json, or compact
+        /// Output format: text (summary and tree from the root), json, or
+        /// compact (tree only)
         #[arg(short, long, default_value = "text")]
         format: String,
     },
@@ -343,14 +597,505 @@ pub enum MemoryCommand {
         #[arg(short = 'a', long, default_value = "7")]
         max_age_days: u32,
     },
+
+    /// Restore recently forgotten memories from the recovery buffer
+    Undo {
+        /// Number of most recent forget operations to undo
+        #[arg(short, long, default_value = "1")]
+        count: usize,
+
+        /// List recoverable memories instead of restoring
+        #[arg(long)]
+        list: bool,
+    },
+
+    /// Import facts and decisions from ChatGPT/Claude conversation exports or
+    /// coding-agent session logs (Claude Code, Continue, Cursor), or restore a
+    /// JSONL backup or Arrow export of memories
+    Import {
+        /// Export file (conversations.json, backup.jsonl, memories.arrow),
+        /// session log, or directory of session logs
+        path: String,
+
+        /// Format: chatgpt, claude, claude-code, continue, cursor, jsonl, or arrow
+        #[arg(long)]
+        format: String,
+
+        /// Extract facts with the [search.answer] model instead of keyword heuristics
+        #[arg(long, action = ArgAction::SetTrue)]
+        llm: bool,
+
+        /// Show what would be imported without storing anything
+        #[arg(long, action = ArgAction::SetTrue)]
+        dry_run: bool,
+    },
+
+    /// Create many memories from a JSONL or YAML file (keys: title, content,
+    /// type, importance, tags, files), embedding them in batches
+    ImportBatch {
+        /// Batch file (.jsonl or .yaml/.yml)
+        path: String,
+
+        /// Memories embedded and written together per chunk
+        #[arg(long, default_value_t = 32)]
+        batch_size: usize,
+
+        /// Store even if some content looks like a credential
+        #[arg(long, action = ArgAction::SetTrue)]
+        allow_secrets: bool,
+
+        /// Validate the file without storing anything
+        #[arg(long, action = ArgAction::SetTrue)]
+        dry_run: bool,
+    },
+
+    /// Export memories to another tool's format
+    Export {
+        /// Export format: anki, jsonl (backup of memories, relationships and
+        /// embeddings), or arrow (Arrow IPC file with embeddings for
+        /// DuckDB/pandas/polars; requires --output)
+        #[arg(short, long, default_value = "anki")]
+        format: String,
+
+        /// Memory types to export (comma-separated; anki defaults to learning,insight)
+        #[arg(long = "type")]
+        memory_type: Option<String>,
+
+        /// Only export memories with any of these tags (comma-separated)
+        #[arg(long)]
+        tags: Option<String>,
+
+        /// Output file (defaults to stdout)
+        #[arg(short, long)]
+        output: Option<String>,
+
+        /// Anki deck name
+        #[arg(long, default_value = "Octobrain")]
+        deck: String,
+    },
+
+    /// Share curated memories with teammates as signed bundles
+    Share {
+        #[command(subcommand)]
+        share: ShareCommand,
+    },
+
+    /// Usage reports over stored memories
+    Report {
+        #[command(subcommand)]
+        report: ReportCommand,
+    },
+
+    /// Vector index maintenance
+    Index {
+        #[command(subcommand)]
+        index: IndexCommand,
+    },
+
+    /// Relationship maintenance
+    Links {
+        #[command(subcommand)]
+        links: LinksCommand,
+    },
+
+    /// Named aliases usable wherever a memory ID is accepted
+    Alias {
+        #[command(subcommand)]
+        alias: AliasCommand,
+    },
+
+    /// Summary of new memories and links over a period, for a weekly review
+    Digest {
+        /// Period to cover: e.g. 12h, 7d, 2w
+        #[arg(short, long, default_value = "7d")]
+        since: String,
+
+        /// Prepend an LLM summary (uses the [search.answer] model)
+        #[arg(long)]
+        llm: bool,
+
+        /// Output format: markdown or json
+        #[arg(short, long, default_value = "markdown")]
+        format: String,
+
+        /// Output file (defaults to stdout)
+        #[arg(short, long)]
+        output: Option<String>,
+    },
+
+    /// Spaced-repetition review of learning and insight memories
+    Review {
+        #[command(subcommand)]
+        review: ReviewCommand,
+    },
+
+    /// Mark a search result as helpful or unhelpful to tune future rankings
+    Feedback {
+        /// Memory ID, short ID or alias of the result
+        memory_id: String,
+
+        /// The result was useful for the query
+        #[arg(
+            long,
+            required_unless_present = "unhelpful",
+            conflicts_with = "unhelpful"
+        )]
+        helpful: bool,
+
+        /// The result was not useful for the query
+        #[arg(long)]
+        unhelpful: bool,
+
+        /// Query the result was returned for (defaults to the latest search that returned it)
+        #[arg(short, long)]
+        query: Option<String>,
+    },
+
+    /// List the earlier versions of a memory saved by updates
+    History {
+        /// Memory ID, short ID or alias
+        memory_id: String,
+    },
+
+    /// Restore a memory to an earlier revision (the current version is kept as a new one)
+    Rollback {
+        /// Memory ID, short ID or alias
+        memory_id: String,
+
+        /// Revision number from `memory history`
+        #[arg(long)]
+        to: u32,
+    },
+
+    /// Memories removed by forget or cleanup, kept for `trash_retention_days`
+    Trash {
+        #[command(subcommand)]
+        trash: TrashCommand,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum ReviewCommand {
+    /// Show memories due for review, most overdue first
+    Due {
+        /// Maximum number of memories to show
+        #[arg(short, long, default_value = "10")]
+        limit: usize,
+    },
+
+    /// Record how well you recalled a memory: 0 (forgot) to 5 (perfect)
+    Grade {
+        /// Memory ID, short ID or alias
+        memory_id: String,
+
+        /// Recall grade 0-5; below 3 schedules the memory again for tomorrow
+        grade: u8,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum TrashCommand {
+    /// List trashed memories, most recently deleted first
+    List,
+
+    /// Bring trashed memories back
+    Restore {
+        /// Memory IDs or unique ID prefixes
+        #[arg(required = true)]
+        memory_ids: Vec<String>,
+    },
+
+    /// Permanently delete trashed memories past the retention window
+    Purge {
+        /// Empty the whole trash instead
+        #[arg(long)]
+        all: bool,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum IndexCommand {
+    /// Drop and recreate a vector index sized to the current row count
+    /// (useful after bulk imports)
+    Rebuild {
+        /// Table to rebuild: memories or knowledge
+        #[arg(long, default_value = "memories")]
+        table: String,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum LinksCommand {
+    /// Remove relationships weaker than a strength threshold
+    Prune {
+        /// Remove relationships with strength below this value (0.0-1.0)
+        #[arg(long)]
+        below: f32,
+
+        /// Only prune this relationship type (e.g. auto_linked)
+        #[arg(short = 't', long = "type")]
+        relationship_type: Option<String>,
+
+        /// Show what would be removed without deleting anything
+        #[arg(long, action = ArgAction::SetTrue)]
+        dry_run: bool,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum AliasCommand {
+    /// Point an alias at a memory (ID, unique ID prefix or another alias)
+    Set {
+        /// Alias name (letters, digits, '.', '_', '-')
+        name: String,
+
+        /// Memory to alias
+        memory_id: String,
+    },
+
+    /// Remove an alias
+    Remove {
+        /// Alias name
+        name: String,
+    },
+
+    /// List aliases of the current project
+    List,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum GraphCommand {
+    /// Export the memory graph for visual exploration
+    Export {
+        /// Export format: html (interactive page), json (nodes and edges),
+        /// dot (Graphviz), mermaid (Obsidian/Markdown) or graphml (Gephi, yEd)
+        #[arg(short, long, default_value = "html")]
+        format: String,
+
+        /// Only export the neighbourhood of this memory (defaults to the whole graph)
+        #[arg(long)]
+        root: Option<String>,
+
+        /// Depth of traversal from --root
+        #[arg(short, long, default_value = "2")]
+        depth: usize,
+
+        /// Memory types to include (comma-separated; whole-graph export only)
+        #[arg(long = "type")]
+        memory_type: Option<String>,
+
+        /// Only include memories with any of these tags (comma-separated; whole-graph export only)
+        #[arg(long)]
+        tags: Option<String>,
+
+        /// Output file (defaults to stdout)
+        #[arg(short, long)]
+        output: Option<String>,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum ShareCommand {
+    /// Write a bundle of memories signed with the local share key
+    Export {
+        /// Only share memories with any of these tags (comma-separated)
+        #[arg(long)]
+        tags: Option<String>,
+
+        /// Memory types to share (comma-separated)
+        #[arg(long = "type")]
+        memory_type: Option<String>,
+
+        /// Bundle file to write
+        #[arg(short, long)]
+        output: String,
+
+        /// Sender name recorded in the bundle (defaults to $USER)
+        #[arg(long)]
+        sender: Option<String>,
+    },
+
+    /// Verify a bundle's signature and import its memories attributed to the sender
+    Import {
+        /// Bundle file
+        path: String,
+
+        /// Only accept bundles signed by this key fingerprint (see `share key`);
+        /// overrides [memory].trusted_share_keys
+        #[arg(long)]
+        trust: Option<String>,
+
+        /// How to settle memories changed on both sides: last-write-wins,
+        /// keep-both or interactive (default: [memory].conflict_strategy)
+        #[arg(long, value_name = "STRATEGY")]
+        on_conflict: Option<String>,
+    },
+
+    /// Show the fingerprint of the local share key
+    Key,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum ReportCommand {
+    /// Most/least accessed memories, weekly access trend, and the queries that found them
+    Access {
+        /// Number of memories listed in the most/least accessed sections
+        #[arg(short, long, default_value = "10")]
+        limit: usize,
+
+        /// Number of weeks of access history to include
+        #[arg(short, long, default_value = "8")]
+        weeks: u32,
+
+        /// Output format: text or json
+        #[arg(short, long, default_value = "text")]
+        format: String,
+    },
+
+    /// Important memories that have decayed and gone unused, to refresh or archive
+    Stale {
+        /// Only memories not accessed for at least this many days
+        #[arg(short, long, default_value = "90")]
+        days: u32,
+
+        /// Minimum base importance (0.0-1.0)
+        #[arg(long, default_value = "0.7")]
+        min_importance: f32,
+
+        /// Minimum share of base importance lost to decay (0.0-1.0)
+        #[arg(long, default_value = "0.5")]
+        min_decay: f32,
+
+        /// Maximum number of memories to list
+        #[arg(short, long, default_value = "20")]
+        limit: usize,
+
+        /// Output format: text or json
+        #[arg(short, long, default_value = "text")]
+        format: String,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum MigrateCommand {
+    /// Re-embed memories and knowledge chunks stored with another embedding
+    /// model than [embedding].model, then rebuild their indexes. An interrupted
+    /// run resumes where it stopped
+    Embeddings {
+        /// Texts sent to the embedding provider per call
+        #[arg(long, default_value_t = crate::migrate::DEFAULT_BATCH_SIZE)]
+        batch_size: usize,
+
+        /// Only report which tables need re-embedding
+        #[arg(long)]
+        check: bool,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum StorageCommand {
+    /// Compact the memory and knowledge tables, prune old table versions and
+    /// report the space reclaimed
+    Optimize {
+        /// Keep table versions newer than this many days; 0 reclaims the most
+        /// but may break other octobrain processes reading an older version
+        #[arg(long, default_value_t = 1)]
+        keep_days: u32,
+
+        /// Output format: text or json
+        #[arg(short, long, default_value = "text")]
+        format: String,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum ConfigCommand {
+    /// Print the effective configuration, defaults included
+    Show,
+    /// Print the path of the config file
+    Path,
+    /// Print one value by dotted key, e.g. search.hybrid.enabled
+    Get {
+        /// Dotted key
+        key: String,
+    },
+    /// Change one value in the config file; comments are kept and the change
+    /// is only saved when the result is valid
+    Set {
+        /// Dotted key, e.g. embedding.model
+        key: String,
+
+        /// New value; arrays and inline tables in TOML syntax
+        value: String,
+    },
+    /// Check the config file for parse errors, invalid values and unknown keys
+    Validate,
 }
 
 #[derive(Subcommand, Debug)]
 pub enum KnowledgeCommand {
     /// Index a URL or local file into knowledge base
     Index {
-        /// URL or local file path to index (.txt, .md, .pdf, .docx, .html)
-        source: String,
+        /// URL or local file path to index (.txt, .md, .pdf, .docx, .html, OpenAPI JSON/YAML)
+        #[arg(required_unless_present = "sitemap")]
+        source: Option<String>,
+
+        /// Also index pages linked from the URL on the same origin, up to N hops away
+        #[arg(long, value_name = "N")]
+        depth: Option<usize>,
+
+        /// Index every page listed in this sitemap.xml (sitemap indexes are followed)
+        #[arg(long, value_name = "URL", conflicts_with_all = ["source", "depth"])]
+        sitemap: Option<String>,
+
+        /// Only index sitemap pages whose URL matches one of these patterns (comma-separated, `*` wildcard)
+        #[arg(long, requires = "sitemap")]
+        include: Option<String>,
+
+        /// Skip sitemap pages whose URL matches one of these patterns (comma-separated, `*` wildcard)
+        #[arg(long, requires = "sitemap")]
+        exclude: Option<String>,
+
+        /// Pages fetched at most when crawling or reading a sitemap (default: [knowledge].crawl_max_pages)
+        #[arg(long)]
+        max_pages: Option<usize>,
+
+        /// Concurrent fetches when crawling or reading a sitemap (default: [knowledge].crawl_concurrency)
+        #[arg(long)]
+        concurrency: Option<usize>,
+
+        /// Add the indexed sources to this collection (e.g. rust-docs)
+        #[arg(long)]
+        collection: Option<String>,
+    },
+
+    /// Index the README, docs/ and wiki of a GitHub repository (token from GITHUB_TOKEN or GH_TOKEN)
+    IndexRepo {
+        /// Repository as owner/repo or a github.com URL
+        repo: String,
+
+        /// Skip the repository wiki
+        #[arg(long)]
+        no_wiki: bool,
+
+        /// Concurrent fetches (default: [knowledge].crawl_concurrency)
+        #[arg(long)]
+        concurrency: Option<usize>,
+
+        /// Add the indexed files to this collection
+        #[arg(long)]
+        collection: Option<String>,
+    },
+
+    /// Re-fetch indexed sources older than outdating_days, re-indexing only changed content
+    Refresh {
+        /// Re-fetch every indexed source, whatever its age
+        #[arg(long, conflicts_with = "url")]
+        all: bool,
+
+        /// Re-fetch one indexed URL or file path
+        #[arg(long)]
+        url: Option<String>,
     },
 
     /// Search knowledge base semantically
@@ -361,6 +1106,26 @@ pub enum KnowledgeCommand {
         /// Filter by specific source URL or file path (auto-indexes if needed)
         #[arg(long)]
         source: Option<String>,
+
+        /// Only search sources in this collection
+        #[arg(long)]
+        collection: Option<String>,
+
+        /// Maximum number of results (default: [knowledge].max_results)
+        #[arg(short, long)]
+        limit: Option<usize>,
+
+        /// Drop results scoring below this relevance (0.0-1.0)
+        #[arg(long)]
+        min_relevance: Option<f32>,
+
+        /// Merge nearby hits from the same source into continuous passages
+        #[arg(long, action = ArgAction::SetTrue)]
+        stitch: bool,
+
+        /// Output format: text or json
+        #[arg(short, long, default_value = "text")]
+        format: String,
     },
 
     /// Store raw text content under a key (session-scoped in MCP, persistent in CLI)
@@ -393,13 +1158,21 @@ pub enum KnowledgeCommand {
     },
 
     /// Show knowledge base statistics
-    Stats,
+    Stats {
+        /// Output format: text or json
+        #[arg(short, long, default_value = "text")]
+        format: String,
+    },
 
     /// List indexed sources with metadata
     List {
         /// Maximum number of sources to list
         #[arg(long, default_value = "20")]
         limit: usize,
+
+        /// Output format: text or json
+        #[arg(short, long, default_value = "text")]
+        format: String,
     },
 
     /// Read and display full content of a URL or local file
@@ -408,6 +1181,20 @@ pub enum KnowledgeCommand {
         source: String,
     },
 
+    /// Show a chunk (by ID from search results) with surrounding chunks from its source
+    Get {
+        /// Chunk ID
+        chunk_id: String,
+
+        /// Number of neighbouring chunks to include on each side
+        #[arg(short, long, default_value = "1")]
+        context: usize,
+
+        /// Output format: text or json
+        #[arg(short, long, default_value = "text")]
+        format: String,
+    },
+
     /// Search indexed content by regex pattern (like grep)
     Match {
         /// Regex pattern to search for (e.g., "error_code" or "timeout|retry")
@@ -12,23 +12,54 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use serde_json::Value;
-use std::io::{self, Write};
-
-use crate::cli::{Commands, KnowledgeCommand, MemoryCommand};
-use crate::config::Config;
+use std::io::{self, Read, Write};
+
+use crate::cli::{
+    AliasCommand, Commands, ConfigCommand, GraphCommand, IndexCommand, KnowledgeCommand,
+    LinksCommand, MemoryCommand, MigrateCommand, ReportCommand, ReviewCommand, ShareCommand,
+    StorageCommand, TrashCommand,
+};
+use crate::config::{Config, ConfigDocument};
 use crate::constants::MAX_QUERIES;
+use crate::knowledge::github::RepoRef;
+use crate::knowledge::sitemap::UrlFilter;
+use crate::knowledge::types::{ChunkFilter, IndexResult, RefreshScope};
 use crate::knowledge::KnowledgeManager;
+use crate::maintenance::{run_maintenance, MaintenanceTask};
+use crate::memory::backend::BackendKind;
+use crate::memory::batch::read_batch;
+use crate::memory::capture::{run_command, CAPTURE_TAG};
+use crate::memory::conflict::{ConflictPrompt, ConflictStrategy, StdinConflictPrompt};
+use crate::memory::conversation_import::ConversationFormat;
+use crate::memory::draft::Draft;
+use crate::memory::export::{
+    graph_to_json, to_anki, to_graph_dot, to_graph_html, to_graph_mermaid, to_graph_tree,
+    to_graphml, ANKI_DEFAULT_TYPES,
+};
+use crate::memory::formatting::{explain_signals, search_results_table, stats_table, TableFormat};
+use crate::memory::session_import::SessionFormat;
+use crate::memory::share::{default_sender, local_key_fingerprint, ShareBundle};
+use crate::memory::sync::{run_sync_server, sync_with_path, sync_with_remote, SYNC_TOKEN_ENV};
 use crate::memory::{MemoryManager, MemoryQuery, MemoryType};
+use crate::search::{format_hits, interleave, UnifiedHit};
 
-pub async fn execute(config: &Config, command: Commands) -> Result<()> {
+/// Run `command`; `project` is the resolved `--project` key (`None` = unscoped).
+pub async fn execute(config: &Config, command: Commands, project: Option<String>) -> Result<()> {
     match command {
         Commands::Memory {
-            project,
-            role,
-            command,
-        } => {
+            command:
+                MemoryCommand::Index {
+                    index: IndexCommand::Rebuild { table },
+                },
+            ..
+        } if table == "knowledge" => {
+            let knowledge_manager = KnowledgeManager::new(config).await?;
+            print_index_rebuild(knowledge_manager.rebuild_vector_index().await?);
+            Ok(())
+        }
+        Commands::Memory { role, command } => {
             let mut memory_manager = MemoryManager::new(config, project, role).await?;
             execute_memory_command(&mut memory_manager, command).await
         }
@@ -36,10 +67,53 @@ pub async fn execute(config: &Config, command: Commands) -> Result<()> {
             let mut knowledge_manager = KnowledgeManager::new(config).await?;
             execute_knowledge_command(&mut knowledge_manager, command).await
         }
+        Commands::Search {
+            query,
+            role,
+            limit,
+            format,
+        } => {
+            if query.len() < 3 || query.len() > 500 {
+                return Err(anyhow::anyhow!(
+                    "Query must be between 3 and 500 characters"
+                ));
+            }
+            let limit = limit.clamp(1, 50);
+            let memory_manager = MemoryManager::new(config, project, role).await?;
+            let knowledge_manager = KnowledgeManager::new(config).await?;
+            let memory_query = MemoryQuery {
+                limit: Some(limit),
+                ..Default::default()
+            };
+            let (memories, knowledge) = tokio::join!(
+                memory_manager.remember(&query, Some(memory_query)),
+                knowledge_manager.search(&query, ChunkFilter::default(), None, Some(limit), None)
+            );
+            let hits = interleave(
+                memories?.iter().map(UnifiedHit::from_memory).collect(),
+                knowledge?.iter().map(UnifiedHit::from_knowledge).collect(),
+                limit,
+            );
+            if format == "json" {
+                println!(
+                    "{}",
+                    serde_json::to_string_pretty(&serde_json::json!({ "results": hits }))?
+                );
+            } else {
+                println!("{}", format_hits(&hits));
+            }
+            Ok(())
+        }
         Commands::Mcp { bind } => {
-            // Initialize file-only logging for MCP server (no console output)
+            // Initialize file-only logging for MCP server (no console output);
+            // ephemeral sessions write no log files
             let working_directory = std::env::current_dir()?;
-            crate::mcp::logging::init_mcp_logging(working_directory.clone(), false)?;
+            if BackendKind::parse(&config.memory.backend)? != BackendKind::Ephemeral {
+                crate::mcp::logging::init_mcp_logging(working_directory.clone(), false)?;
+            }
+
+            // Keep indexed knowledge fresh for as long as the server runs
+            let _refresher = crate::maintenance::spawn_knowledge_auto_refresh(config.clone());
 
             // Start MCP server using rmcp SDK
             let server = crate::mcp::McpServer::new(config.clone(), working_directory);
@@ -49,33 +123,371 @@ pub async fn execute(config: &Config, command: Commands) -> Result<()> {
             }
             Ok(())
         }
+        Commands::Serve { sync, bind, token } => {
+            if !sync {
+                return Err(anyhow::anyhow!(
+                    "Nothing to serve: pass --sync (the MCP server is `octobrain mcp --bind`)"
+                ));
+            }
+            let token = token.or_else(|| std::env::var(SYNC_TOKEN_ENV).ok());
+            if token.is_none() && config.server.api_keys.is_empty() {
+                println!("⚠️  No sync token or [server].api_keys set — any client that can reach {} can read and write memories", bind);
+            }
+            println!("🔄 Sync server listening on {}", bind);
+            run_sync_server(config.clone(), &bind, token).await
+        }
+        Commands::Maintenance { tasks } => {
+            let tasks = MaintenanceTask::parse_list(tasks.as_deref())?;
+            let report = run_maintenance(config, &tasks).await?;
+            println!("{}", serde_json::to_string_pretty(&report)?);
+            let failures = report.failures();
+            if failures > 0 {
+                return Err(anyhow::anyhow!("{} maintenance task(s) failed", failures));
+            }
+            Ok(())
+        }
+        Commands::Daemon => crate::maintenance::run_daemon(config).await,
+        Commands::Migrate {
+            command: MigrateCommand::Embeddings { batch_size, check },
+        } => {
+            let mut progress = |table: &str, done: usize, total: usize| {
+                eprint!("\r   {}: {}/{}", table, done, total);
+                if done == total {
+                    eprintln!();
+                }
+            };
+            let migrated =
+                crate::migrate::migrate_embeddings(config, batch_size, check, &mut progress)
+                    .await?;
+            if migrated.is_empty() {
+                println!(
+                    "✅ All stored embeddings match [embedding].model ({})",
+                    config.embedding.model
+                );
+                return Ok(());
+            }
+            for table in &migrated {
+                let from = table
+                    .from
+                    .as_ref()
+                    .map(|stamp| format!(" from {} ({} dims)", stamp.model, stamp.dimension))
+                    .unwrap_or_default();
+                let resumed = if table.resumed { ", resumed" } else { "" };
+                if check {
+                    println!(
+                        "   {}: {} of {} rows to re-embed{}{}",
+                        table.table, table.reembedded, table.rows, from, resumed
+                    );
+                } else {
+                    println!(
+                        "   {}: {} rows re-embedded{}{}",
+                        table.table, table.reembedded, from, resumed
+                    );
+                }
+            }
+            if check {
+                println!(
+                    "ℹ️  Run `octobrain migrate embeddings` to re-embed with {}",
+                    config.embedding.model
+                );
+            } else {
+                println!("✅ Re-embedded with {}", config.embedding.model);
+            }
+            Ok(())
+        }
+        Commands::Sync {
+            target,
+            remote,
+            token,
+            on_conflict,
+        } => {
+            let remote = target
+                .or(remote)
+                .ok_or_else(|| anyhow::anyhow!("Pass a sync server URL or folder"))?;
+            let token = token.or_else(|| std::env::var(SYNC_TOKEN_ENV).ok());
+            let mut memory_manager = MemoryManager::new(config, project.clone(), None).await?;
+            let strategy = memory_manager.conflict_strategy(on_conflict.as_deref())?;
+            let mut stdin_prompt = StdinConflictPrompt;
+            let prompt: Option<&mut dyn ConflictPrompt> = match strategy {
+                ConflictStrategy::Interactive => Some(&mut stdin_prompt),
+                _ => None,
+            };
+            let state_path = crate::storage::get_sync_state_path()?;
+            let report = if remote.starts_with("http://") || remote.starts_with("https://") {
+                sync_with_remote(
+                    &mut memory_manager,
+                    &remote,
+                    project,
+                    token.as_deref(),
+                    &state_path,
+                    strategy,
+                    prompt,
+                )
+                .await?
+            } else {
+                let dir = remote.strip_prefix("file://").unwrap_or(&remote);
+                sync_with_path(
+                    &mut memory_manager,
+                    std::path::Path::new(dir),
+                    project,
+                    &state_path,
+                    strategy,
+                    prompt,
+                )
+                .await?
+            };
+            println!(
+                "✅ Synced with {}\n   Pulled: {} applied, {} already up to date, {} relationships\n   Pushed: {} applied, {} already up to date, {} relationships",
+                remote,
+                report.pulled.applied,
+                report.pulled.skipped,
+                report.pulled.relationships,
+                report.pushed.applied,
+                report.pushed.skipped,
+                report.pushed.relationships
+            );
+            let conflicts = report.pulled.conflicts + report.pushed.conflicts;
+            if conflicts > 0 {
+                println!(
+                    "⚠️  {} conflicts settled with {}",
+                    conflicts,
+                    strategy.as_str()
+                );
+            }
+            Ok(())
+        }
+        Commands::Storage {
+            command: StorageCommand::Optimize { keep_days, format },
+        } => {
+            let keep = chrono::Duration::days(keep_days.into());
+            let dirs = [
+                crate::storage::get_memory_database_path()?,
+                crate::storage::get_knowledge_database_path()?,
+            ];
+            let size = || -> u64 { dirs.iter().map(|d| crate::storage::dir_size(d)).sum() };
+            let before = size();
+            let mut compactions = MemoryManager::new(config, None, None)
+                .await?
+                .compact_storage(keep)
+                .await?;
+            compactions.extend(
+                KnowledgeManager::new(config)
+                    .await?
+                    .compact_storage(keep)
+                    .await?,
+            );
+            let after = size();
+            // Object stores are not measured; count what pruning deleted
+            let reclaimed = if config.storage.is_remote() {
+                compactions.iter().map(|c| c.bytes_reclaimed).sum()
+            } else {
+                before.saturating_sub(after)
+            };
+
+            if format == "json" {
+                let report = serde_json::json!({
+                    "tables": compactions,
+                    "size_before_bytes": before,
+                    "size_after_bytes": after,
+                    "reclaimed_bytes": reclaimed,
+                });
+                println!("{}", serde_json::to_string_pretty(&report)?);
+                return Ok(());
+            }
+            if compactions.is_empty() {
+                println!(
+                    "ℹ️  The {} backend manages its own storage; nothing to optimize.",
+                    config.memory.backend
+                );
+                return Ok(());
+            }
+            for c in &compactions {
+                println!(
+                    "  {:<24} {} → {} fragments, {} old versions pruned, {} freed",
+                    c.table,
+                    c.fragments_removed,
+                    c.fragments_added,
+                    c.versions_pruned,
+                    format_bytes(c.bytes_reclaimed)
+                );
+            }
+            if config.storage.is_remote() {
+                println!(
+                    "✅ Optimized {} tables in {} ({} reclaimed)",
+                    compactions.len(),
+                    config.storage.uri,
+                    format_bytes(reclaimed)
+                );
+            } else {
+                println!(
+                    "✅ Optimized {} tables: {} → {} ({} reclaimed)",
+                    compactions.len(),
+                    format_bytes(before),
+                    format_bytes(after),
+                    format_bytes(reclaimed)
+                );
+            }
+            Ok(())
+        }
+        Commands::Config { command } => execute_config_command(command),
     }
 }
 
+/// `octobrain config`: runs before the config is loaded, so a file that no
+/// longer loads can still be inspected and fixed
+pub fn execute_config_command(command: ConfigCommand) -> Result<()> {
+    let path = crate::storage::get_config_path()?;
+    match command {
+        ConfigCommand::Path => {
+            println!("{}", path.display());
+        }
+        ConfigCommand::Show => {
+            print!("{}", toml::to_string_pretty(&Config::load()?)?);
+        }
+        ConfigCommand::Get { key } => {
+            let config = toml::Value::try_from(Config::load()?)?;
+            let value = key
+                .split('.')
+                .try_fold(&config, |value, part| value.get(part))
+                .ok_or_else(|| anyhow::anyhow!("Unknown config key '{}'", key))?;
+            match value {
+                toml::Value::String(s) => println!("{}", s),
+                toml::Value::Table(table) => print!("{}", toml::to_string_pretty(table)?),
+                other => println!("{}", other),
+            }
+        }
+        ConfigCommand::Set { key, value } => {
+            let mut document = ConfigDocument::load(&path)?;
+            document.set(&key, &value)?;
+            document
+                .to_config()
+                .with_context(|| format!("Not saved: setting {} makes the config invalid", key))?;
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::fs::write(&path, document.to_string())?;
+            let written = document
+                .get(&key)
+                .map(|item| item.to_string().trim().to_string())
+                .unwrap_or(value);
+            println!("✅ {} = {}", key, written);
+        }
+        ConfigCommand::Validate => {
+            if !path.exists() {
+                println!(
+                    "No config file at {}; the defaults are used",
+                    path.display()
+                );
+                return Ok(());
+            }
+            let content = std::fs::read_to_string(&path)?;
+            let document = ConfigDocument::parse(&content)?;
+            let config = document
+                .to_config()
+                .with_context(|| format!("{} is invalid", path.display()))?;
+            let unknown = document.unknown_keys(&config)?;
+            for key in &unknown {
+                println!("⚠️  Unknown key {} is ignored", key);
+            }
+            println!("✅ {} is valid", path.display());
+            if let Some(project) = crate::config::project_config_path() {
+                Config::load().with_context(|| format!("{} is invalid", project.display()))?;
+                println!("✅ {} is valid", project.display());
+            }
+        }
+    }
+    Ok(())
+}
+
 async fn execute_memory_command(
     memory_manager: &mut MemoryManager,
     command: MemoryCommand,
 ) -> Result<()> {
     match command {
         MemoryCommand::Memorize {
-            title,
-            content,
-            memory_type,
-            importance,
-            tags,
-            files,
+            mut title,
+            mut content,
+            mut memory_type,
+            mut importance,
+            mut tags,
+            mut files,
+            edit,
+            allow_secrets,
+            attach,
+            image,
+            transcript,
+            summarize,
         } => {
+            if let Some(transcript) = transcript {
+                println!("🎙️  Processing transcript {}...", transcript);
+                let memories = memory_manager
+                    .ingest_transcript(
+                        std::path::Path::new(&transcript),
+                        summarize,
+                        split_csv_opt(&tags),
+                        allow_secrets,
+                    )
+                    .await?;
+                if memories.is_empty() {
+                    println!("ℹ️  No decisions or learnings found in transcript.");
+                } else {
+                    println!("✅ Stored {} memories from transcript:", memories.len());
+                    for memory in &memories {
+                        println!(
+                            "  [{}] {} - {}",
+                            memory.memory_type, memory.title, memory.id
+                        );
+                    }
+                }
+                return Ok(());
+            }
+
+            if content.as_deref() == Some("-") {
+                let mut text = String::new();
+                io::stdin().read_to_string(&mut text)?;
+                content = Some(text.trim_end().to_string());
+            }
+            if edit {
+                let draft = Draft {
+                    title: title.unwrap_or_default(),
+                    memory_type,
+                    importance,
+                    tags: split_csv_opt(&tags).unwrap_or_default(),
+                    files: split_csv_opt(&files).unwrap_or_default(),
+                    content: content.unwrap_or_default(),
+                };
+                let text = crate::memory::draft::edit(&draft, &std::env::temp_dir())?;
+                let Some(edited) = draft.parse(&text)? else {
+                    println!("ℹ️  Empty draft; nothing stored.");
+                    return Ok(());
+                };
+                title = Some(edited.title);
+                content = Some(edited.content);
+                memory_type = edited.memory_type;
+                importance = edited.importance;
+                tags = (!edited.tags.is_empty()).then(|| edited.tags.join(","));
+                files = (!edited.files.is_empty()).then(|| edited.files.join(","));
+            }
+
             // Validate input lengths
+            let title = title.unwrap_or_default();
             if title.len() < 5 || title.len() > 200 {
                 return Err(anyhow::anyhow!(
                     "Title must be between 5 and 200 characters"
                 ));
             }
-            if content.len() < 10 || content.len() > 10000 {
+            // With --image the caption supplies the content, so user text is optional
+            let content = content.unwrap_or_default();
+            if (image.is_none() || !content.is_empty())
+                && (content.len() < 10 || content.len() > 10000)
+            {
                 return Err(anyhow::anyhow!(
                     "Content must be between 10 and 10000 characters"
                 ));
             }
+            if image.is_some() {
+                println!("🖼️  Captioning image...");
+            }
 
             let mem_type = MemoryType::from(memory_type);
             let tags_vec = split_csv_opt(&tags);
@@ -90,16 +502,81 @@ async fn execute_memory_command(
                     tags: tags_vec,
                     related_files: files_vec,
                     source: None, // defaults to AgentInferred
+                    allow_secrets,
+                    attachments: split_csv_opt(&attach),
+                    image,
+                    custom_fields: None,
+                    created_by: None,
                 })
                 .await?;
 
             println!("✅ Memory stored successfully!");
             println!("Memory ID: {}", memory.id);
+            if let Some(short) = memory_manager
+                .short_ids(std::slice::from_ref(&memory.id))
+                .await?
+                .get(&memory.id)
+            {
+                println!("Short ID: {}", short);
+            }
             println!("Type: {}", memory.memory_type);
             println!("Title: {}", memory.title);
             if let Some(imp) = importance {
                 println!("Importance: {:.2}", imp);
             }
+            if !memory.metadata.attachments.is_empty() {
+                println!("Attachments: {}", memory.metadata.attachments.len());
+            }
+        }
+
+        MemoryCommand::Capture {
+            title,
+            memory_type,
+            importance,
+            tags,
+            max_output,
+            allow_secrets,
+            command,
+        } => {
+            if !(200..=9000).contains(&max_output) {
+                return Err(anyhow::anyhow!("--max-output must be between 200 and 9000"));
+            }
+            let run = run_command(&command).await?;
+            let title = title.unwrap_or_else(|| run.title());
+            if title.len() < 5 || title.len() > 200 {
+                return Err(anyhow::anyhow!(
+                    "Title must be between 5 and 200 characters"
+                ));
+            }
+            let mut tags_vec = split_csv_opt(&tags).unwrap_or_default();
+            if !tags_vec.iter().any(|t| t == CAPTURE_TAG) {
+                tags_vec.push(CAPTURE_TAG.to_string());
+            }
+
+            let memory = memory_manager
+                .memorize(crate::memory::manager::MemorizeParams {
+                    memory_type: MemoryType::from(memory_type),
+                    title,
+                    content: run.content(max_output),
+                    importance,
+                    tags: Some(tags_vec),
+                    related_files: None,
+                    source: None,
+                    allow_secrets,
+                    attachments: None,
+                    image: None,
+                    custom_fields: Some(run.custom_fields()),
+                    created_by: None,
+                })
+                .await?;
+
+            println!();
+            println!("✅ Captured output stored as memory {}", memory.id);
+            println!("Title: {}", memory.title);
+            // Exit like the captured command so scripts and CI see its status
+            if !run.success() {
+                std::process::exit(run.exit_code.unwrap_or(1));
+            }
         }
 
         MemoryCommand::Remember {
@@ -109,10 +586,18 @@ async fn execute_memory_command(
             files,
             limit,
             min_relevance,
+            author,
+            exclude_tags,
+            exclude_types,
+            exclude_files,
+            after,
+            before,
             format,
             enable_reranker,
             disable_reranker,
             reranker_model,
+            answer,
+            explain,
         } => {
             // Apply CLI overrides to reranker config
             if enable_reranker {
@@ -124,12 +609,20 @@ async fn execute_memory_command(
             let tags_vec = split_csv_opt(&tags);
             let files_vec = split_csv_opt(&files);
 
+            let (created_after, created_before) = parse_date_range(&after, &before)?;
             let memory_query = MemoryQuery {
                 memory_types: mem_types,
                 tags: tags_vec,
                 related_files: files_vec,
+                exclude_tags: split_csv_opt(&exclude_tags),
+                exclude_types: parse_memory_types_opt(&exclude_types),
+                exclude_files: split_csv_opt(&exclude_files),
+                created_after,
+                created_before,
+                created_by: author,
                 limit: Some(limit.min(50)),
                 min_relevance,
+                explain,
                 ..Default::default()
             };
 
@@ -159,6 +652,25 @@ async fn execute_memory_command(
                 }
             }
 
+            if answer {
+                let (synthesized, results) =
+                    memory_manager.answer(&queries, Some(memory_query)).await?;
+                if format == "json" {
+                    println!("{}", serde_json::to_string_pretty(&synthesized)?);
+                    return Ok(());
+                }
+                println!("💡 {}\n", synthesized.answer);
+                if !synthesized.citations.is_empty() {
+                    println!("Sources:");
+                    for id in &synthesized.citations {
+                        if let Some(r) = results.iter().find(|r| &r.memory.id == id) {
+                            println!("  [{}] {}", id, r.memory.title);
+                        }
+                    }
+                }
+                return Ok(());
+            }
+
             let results = if queries.len() == 1 {
                 memory_manager
                     .remember(&queries[0], Some(memory_query))
@@ -175,12 +687,16 @@ async fn execute_memory_command(
                 return Ok(());
             }
 
+            if let Some(table_format) = TableFormat::parse(&format) {
+                print!("{}", search_results_table(&results).render(table_format));
+                return Ok(());
+            }
             match format.as_str() {
                 "json" => {
                     let json_results: Vec<Value> = results
                         .iter()
                         .map(|r| {
-                            serde_json::json!({
+                            let mut json = serde_json::json!({
                                 "memory_id": r.memory.id,
                                 "title": r.memory.title,
                                 "memory_type": r.memory.memory_type.to_string(),
@@ -191,7 +707,11 @@ async fn execute_memory_command(
                                 "related_files": r.memory.metadata.related_files,
                                 "importance": r.memory.metadata.importance,
                                 "selection_reason": r.selection_reason
-                            })
+                            });
+                            if explain {
+                                json["signals"] = serde_json::json!(r.signals);
+                            }
+                            json
                         })
                         .collect();
                     println!("{}", serde_json::to_string_pretty(&json_results)?);
@@ -207,6 +727,9 @@ async fn execute_memory_command(
                             result.relevance_score,
                             result.memory.id
                         );
+                        for line in explain_signals(&result.signals) {
+                            println!("     {}", line);
+                        }
                     }
                 }
                 _ => {
@@ -233,6 +756,12 @@ async fn execute_memory_command(
                         }
                         println!("   Content: {}", result.memory.content);
                         println!("   Why selected: {}", result.selection_reason);
+                        if !result.signals.is_empty() {
+                            println!("   Score breakdown:");
+                            for line in explain_signals(&result.signals) {
+                                println!("     - {}", line);
+                            }
+                        }
                         println!();
                     }
                 }
@@ -244,9 +773,15 @@ async fn execute_memory_command(
             query,
             memory_types,
             tags,
+            exclude_tags,
+            exclude_types,
+            exclude_files,
+            after,
+            before,
             yes,
         } => {
             if let Some(id) = memory_id {
+                let id = memory_manager.resolve_memory_id(&id).await?;
                 if !yes {
                     print!("Are you sure you want to delete memory '{}'? (y/N): ", id);
                     io::stdout().flush()?;
@@ -270,10 +805,16 @@ async fn execute_memory_command(
                 let mem_types = parse_memory_types_opt(&memory_types);
                 let tags_vec = split_csv_opt(&tags);
 
+                let (created_after, created_before) = parse_date_range(&after, &before)?;
                 let memory_query = MemoryQuery {
                     query_text: Some(q.clone()),
                     memory_types: mem_types,
                     tags: tags_vec,
+                    exclude_tags: split_csv_opt(&exclude_tags),
+                    exclude_types: parse_memory_types_opt(&exclude_types),
+                    exclude_files: split_csv_opt(&exclude_files),
+                    created_after,
+                    created_before,
                     ..Default::default()
                 };
 
@@ -322,11 +863,17 @@ async fn execute_memory_command(
             title,
             content,
             importance: _,
+            memory_type,
+            confidence,
             add_tags,
             remove_tags,
             add_files,
             remove_files,
         } => {
+            if confidence.is_some_and(|c| !(0.0..=1.0).contains(&c)) {
+                return Err(anyhow::anyhow!("Confidence must be between 0.0 and 1.0"));
+            }
+            let memory_id = memory_manager.resolve_memory_id(&memory_id).await?;
             // Update basic fields
             let updated_memory = memory_manager
                 .update_memory(&memory_id, title, content, None)
@@ -337,6 +884,17 @@ async fn execute_memory_command(
                 return Ok(());
             }
 
+            if let Some(memory_type) = memory_type {
+                memory_manager
+                    .set_memory_type(&memory_id, MemoryType::from(memory_type))
+                    .await?;
+            }
+            if let Some(confidence) = confidence {
+                memory_manager
+                    .set_confidence(&memory_id, confidence)
+                    .await?;
+            }
+
             // Handle tag operations
             if let Some(tags_to_add) = add_tags {
                 for tag in tags_to_add.split(',') {
@@ -370,7 +928,12 @@ async fn execute_memory_command(
             println!("✅ Memory '{}' updated successfully.", memory_id);
         }
 
-        MemoryCommand::Get { memory_id, format } => {
+        MemoryCommand::Get {
+            memory_id,
+            format,
+            with_attachments,
+        } => {
+            let memory_id = memory_manager.resolve_memory_id(&memory_id).await?;
             if let Some(memory) = memory_manager.get_memory(&memory_id).await? {
                 match format.as_str() {
                     "json" => {
@@ -386,6 +949,9 @@ async fn execute_memory_command(
                         println!("Importance: {:.2}", memory.metadata.importance);
                         println!("Created: {}", memory.created_at.format("%Y-%m-%d %H:%M:%S"));
                         println!("Updated: {}", memory.updated_at.format("%Y-%m-%d %H:%M:%S"));
+                        if let Some(author) = &memory.metadata.created_by {
+                            println!("Author: {}", author);
+                        }
                         if !memory.metadata.tags.is_empty() {
                             println!("Tags: {}", memory.metadata.tags.join(", "));
                         }
@@ -396,6 +962,36 @@ async fn execute_memory_command(
                             println!("Git commit: {}", commit);
                         }
                         println!("Content:\n{}", memory.content);
+                        if !memory.metadata.attachments.is_empty() {
+                            if with_attachments {
+                                println!("\nAttachments:");
+                                for attachment in &memory.metadata.attachments {
+                                    println!(
+                                        "📎 {} ({} bytes, sha256 {})",
+                                        attachment.file_name,
+                                        attachment.size_bytes,
+                                        &attachment.sha256[..12.min(attachment.sha256.len())]
+                                    );
+                                    if let Some(path) = memory_manager.attachment_path(attachment) {
+                                        println!("   {}", path.display());
+                                    }
+                                    if let Some(text) = &attachment.extracted_text {
+                                        println!("{}", text);
+                                    }
+                                }
+                            } else {
+                                let names: Vec<&str> = memory
+                                    .metadata
+                                    .attachments
+                                    .iter()
+                                    .map(|a| a.file_name.as_str())
+                                    .collect();
+                                println!(
+                                    "Attachments: {} (use --with-attachments for details)",
+                                    names.join(", ")
+                                );
+                            }
+                        }
                     }
                 }
             } else {
@@ -406,16 +1002,28 @@ async fn execute_memory_command(
         MemoryCommand::Recent {
             limit,
             memory_type,
+            author,
+            exclude_tags,
+            exclude_types,
+            exclude_files,
+            after,
+            before,
             format,
         } => {
-            let memories = if let Some(mem_type) = memory_type {
-                let parsed_type = MemoryType::from(mem_type);
-                memory_manager
-                    .get_memories_by_type(parsed_type, Some(limit))
-                    .await?
-            } else {
-                memory_manager.get_recent_memories(limit).await?
+            let (created_after, created_before) = parse_date_range(&after, &before)?;
+            let filters = MemoryQuery {
+                created_after,
+                created_before,
+                memory_types: memory_type.map(|t| vec![MemoryType::from(t)]),
+                created_by: author,
+                exclude_tags: split_csv_opt(&exclude_tags),
+                exclude_types: parse_memory_types_opt(&exclude_types),
+                exclude_files: split_csv_opt(&exclude_files),
+                ..Default::default()
             };
+            let memories = memory_manager
+                .get_recent_memories_filtered(limit, filters)
+                .await?;
 
             if memories.is_empty() {
                 println!("❌ No recent memories found.");
@@ -478,9 +1086,36 @@ async fn execute_memory_command(
             format_memories(&memories, &format);
         }
 
-        MemoryCommand::Stats => {
+        MemoryCommand::Stats {
+            heatmap,
+            months,
+            format,
+        } => {
+            let table_format = TableFormat::parse(&format);
+            if heatmap && table_format.is_some() {
+                return Err(anyhow::anyhow!(
+                    "--heatmap supports text and json output only"
+                ));
+            }
             let stats = memory_manager.get_memory_stats().await?;
-            print!("{}", stats.format());
+            if let Some(table_format) = table_format {
+                print!("{}", stats_table(&stats).render(table_format));
+                return Ok(());
+            }
+            let activity = if heatmap {
+                Some(memory_manager.get_activity_heatmap(months).await?)
+            } else {
+                None
+            };
+            if format == "json" {
+                let output = serde_json::json!({ "stats": stats, "activity": activity });
+                println!("{}", serde_json::to_string_pretty(&output)?);
+            } else {
+                print!("{}", stats.format());
+                if let Some(activity) = activity {
+                    print!("\n{}", activity.format());
+                }
+            }
         }
 
         MemoryCommand::Cleanup { yes } => {
@@ -496,7 +1131,10 @@ async fn execute_memory_command(
             }
 
             let cleaned_count = memory_manager.cleanup().await?;
-            println!("✅ Cleaned up {} old memories.", cleaned_count);
+            println!(
+                "✅ Cleaned up {} old memories (restorable with `memory trash restore`).",
+                cleaned_count
+            );
         }
 
         MemoryCommand::ClearAll { yes } => {
@@ -539,6 +1177,8 @@ async fn execute_memory_command(
                 _ => crate::memory::RelationshipType::Custom(relationship_type),
             };
 
+            let source_id = memory_manager.resolve_memory_id(&source_id).await?;
+            let target_id = memory_manager.resolve_memory_id(&target_id).await?;
             let relationship = memory_manager
                 .create_relationship(source_id, target_id, rel_type, strength, description)
                 .await?;
@@ -550,6 +1190,7 @@ async fn execute_memory_command(
         }
 
         MemoryCommand::Relationships { memory_id, format } => {
+            let memory_id = memory_manager.resolve_memory_id(&memory_id).await?;
             let relationships = memory_manager.get_relationships(&memory_id).await?;
 
             if relationships.is_empty() {
@@ -592,6 +1233,7 @@ async fn execute_memory_command(
         }
 
         MemoryCommand::Related { memory_id, format } => {
+            let memory_id = memory_manager.resolve_memory_id(&memory_id).await?;
             let related_memories = memory_manager.get_related_memories(&memory_id).await?;
 
             if related_memories.is_empty() {
@@ -602,29 +1244,115 @@ async fn execute_memory_command(
             format_memories(&related_memories, &format);
         }
 
-        MemoryCommand::AutoLink { memory_id } => {
-            println!("🔗 Auto-linking memory '{}'...", memory_id);
-            let relationships = memory_manager.auto_link_memory(&memory_id).await?;
+        MemoryCommand::AutoLink {
+            memory_id,
+            threshold,
+            dry_run,
+        } => {
+            let memory_id = match memory_id {
+                Some(reference) => Some(memory_manager.resolve_memory_id(&reference).await?),
+                None => None,
+            };
+            match &memory_id {
+                Some(id) => println!("🔗 Auto-linking memory '{}'...", id),
+                None => println!("🔗 Auto-linking every memory..."),
+            }
+            let relationships = memory_manager
+                .auto_link(memory_id.as_deref(), threshold, dry_run)
+                .await?;
 
             if relationships.is_empty() {
-                println!("❌ No similar memories found to link (threshold not met).");
+                println!("❌ No new links found (threshold not met or already linked).");
             } else {
-                println!("✅ Created {} auto-link(s):", relationships.len());
+                if dry_run {
+                    println!("Would create {} link(s):", relationships.len());
+                } else {
+                    println!("✅ Created {} link(s):", relationships.len());
+                }
                 for rel in relationships {
                     println!(
-                        "  {} -> {} (strength: {:.2})",
-                        rel.source_id, rel.target_id, rel.strength
+                        "  {} -> {} [{}] (strength: {:.2})",
+                        rel.source_id, rel.target_id, rel.relationship_type, rel.strength
                     );
                 }
             }
         }
 
+        MemoryCommand::Graph {
+            graph:
+                Some(GraphCommand::Export {
+                    format,
+                    root,
+                    depth,
+                    memory_type,
+                    tags,
+                    output,
+                }),
+            ..
+        } => {
+            let root = match root {
+                Some(root) => Some(memory_manager.resolve_memory_id(&root).await?),
+                None => None,
+            };
+            let (graph, title) = match &root {
+                Some(root) => {
+                    let graph = memory_manager.get_memory_graph(root, depth).await?;
+                    let Some(memory) = graph.memories.get(root) else {
+                        return Err(anyhow::anyhow!("Memory '{}' not found", root));
+                    };
+                    let title = format!("Memory graph: {}", memory.title);
+                    (graph, title)
+                }
+                None => {
+                    let types = parse_memory_types_opt(&memory_type).unwrap_or_default();
+                    let tags = split_csv_opt(&tags).unwrap_or_default();
+                    let graph = memory_manager.get_full_graph(&types, &tags).await?;
+                    (graph, "Octobrain memory graph".to_string())
+                }
+            };
+
+            let rendered = match format.as_str() {
+                "html" => to_graph_html(&graph, &title),
+                "json" => serde_json::to_string_pretty(&graph_to_json(&graph))?,
+                "dot" => to_graph_dot(&graph),
+                "mermaid" => to_graph_mermaid(&graph),
+                "graphml" => to_graphml(&graph),
+                other => {
+                    return Err(anyhow::anyhow!(
+                        "Unknown graph export format '{}'; expected html, json, dot, mermaid or graphml",
+                        other
+                    ))
+                }
+            };
+            match output {
+                Some(path) => {
+                    std::fs::write(&path, rendered)?;
+                    println!(
+                        "✅ Exported {} memories and {} relationships to {}",
+                        graph.memories.len(),
+                        graph.relationships.len(),
+                        path
+                    );
+                }
+                None => println!("{}", rendered),
+            }
+        }
+
         MemoryCommand::Graph {
             memory_id,
             depth,
             format,
+            ..
         } => {
-            println!("🕸️  Building memory graph (depth: {})...", depth);
+            let Some(memory_id) = memory_id else {
+                return Err(anyhow::anyhow!(
+                    "A memory ID is required (or use `memory graph export`)"
+                ));
+            };
+            let memory_id = memory_manager.resolve_memory_id(&memory_id).await?;
+            if format != "json" {
+                println!("🕸️  Building memory graph (depth: {})...", depth);
+            }
             let graph = memory_manager.get_memory_graph(&memory_id, depth).await?;
 
             if graph.memories.is_empty() {
@@ -632,38 +1360,22 @@ async fn execute_memory_command(
                 return Ok(());
             }
 
-            if format == "json" {
-                println!("{}", serde_json::to_string_pretty(&graph)?);
-            } else {
-                println!("\n📊 Memory Graph:");
-                println!("  Root: {}", graph.root);
-                println!("  Memories: {}", graph.memories.len());
-                println!("  Relationships: {}", graph.relationships.len());
-                println!("\n🧠 Memories in Graph:");
-
-                for (id, memory) in &graph.memories {
-                    println!("\n  [{}]", id);
-                    println!("    Title: {}", memory.title);
-                    println!("    Type: {}", memory.memory_type);
-                    println!(
-                        "    Created: {}",
-                        memory.created_at.format("%Y-%m-%d %H:%M")
-                    );
-                }
-
-                if !graph.relationships.is_empty() {
-                    println!("\n🔗 Relationships:");
-                    for rel in &graph.relationships {
-                        println!(
-                            "  {} -> {} ({}, strength: {:.2})",
-                            rel.source_id, rel.target_id, rel.relationship_type, rel.strength
-                        );
-                    }
+            match format.as_str() {
+                "json" => println!("{}", serde_json::to_string_pretty(&graph)?),
+                "compact" => print!("{}", to_graph_tree(&graph)),
+                _ => {
+                    println!("\n📊 Memory Graph:");
+                    println!("  Root: {}", graph.root);
+                    println!("  Memories: {}", graph.memories.len());
+                    println!("  Relationships: {}", graph.relationships.len());
+                    println!("\n🕸️  Traversal from the root:\n");
+                    print!("{}", to_graph_tree(&graph));
                 }
             }
         }
 
         MemoryCommand::Consolidate { goal_id, summary } => {
+            let goal_id = memory_manager.resolve_memory_id(&goal_id).await?;
             println!("🎯 Consolidating goal '{}'...", goal_id);
             let consolidated = memory_manager
                 .consolidate_goal(&goal_id, None, summary)
@@ -701,37 +1413,828 @@ async fn execute_memory_command(
                 }
             }
         }
+        MemoryCommand::Undo { count, list } => {
+            if list {
+                let recoverable = memory_manager.list_recoverable().await?;
+                if recoverable.is_empty() {
+                    println!("ℹ️  Recovery buffer is empty.");
+                    return Ok(());
+                }
+                println!("🗑️  {} recoverable memories:", recoverable.len());
+                for (memory, deleted_at) in &recoverable {
+                    println!(
+                        "  • [{}] {} (id={}, deleted {})",
+                        memory.memory_type, memory.title, memory.id, deleted_at
+                    );
+                }
+                return Ok(());
+            }
+
+            let restored = memory_manager.undo_forget(count).await?;
+            if restored.is_empty() {
+                println!("ℹ️  Nothing to undo — recovery buffer is empty.");
+            } else {
+                println!("✅ Restored {} memories:", restored.len());
+                for memory in &restored {
+                    println!("  • {} (id={})", memory.title, memory.id);
+                }
+            }
+        }
+        MemoryCommand::ImportBatch {
+            path,
+            batch_size,
+            allow_secrets,
+            dry_run,
+        } => {
+            if batch_size == 0 {
+                return Err(anyhow::anyhow!("--batch-size must be at least 1"));
+            }
+            let entries = read_batch(std::path::Path::new(&path))?;
+            if dry_run {
+                println!(
+                    "📥 {} memories in {} are valid; dry run — nothing stored.",
+                    entries.len(),
+                    path
+                );
+                return Ok(());
+            }
+            let mut progress = |stored: usize, total: usize| {
+                eprint!("\r📥 Stored {}/{} memories", stored, total);
+                if stored == total {
+                    eprintln!();
+                }
+            };
+            let memories = memory_manager
+                .memorize_batch(entries, batch_size, allow_secrets, &mut progress)
+                .await?;
+            println!("✅ Imported {} memories from {}", memories.len(), path);
+        }
+
+        MemoryCommand::Import {
+            path,
+            format,
+            llm,
+            dry_run,
+        } => {
+            let path = std::path::Path::new(&path);
+            if format == "jsonl" {
+                let report = memory_manager.import_jsonl(path, dry_run).await?;
+                if dry_run {
+                    println!(
+                        "📥 {} memories and {} relationships in {}; dry run — nothing stored.",
+                        report.memories,
+                        report.relationships,
+                        path.display()
+                    );
+                } else {
+                    println!(
+                        "✅ Imported {} memories and {} relationships",
+                        report.memories, report.relationships
+                    );
+                    if report.reembedded > 0 {
+                        println!(
+                            "ℹ️  {} had no usable embedding in the backup and were re-embedded",
+                            report.reembedded
+                        );
+                    }
+                }
+                return Ok(());
+            }
+            if format == "arrow" {
+                let report = memory_manager.import_arrow(path, dry_run).await?;
+                if dry_run {
+                    println!(
+                        "📥 {} memories in {} are valid; dry run — nothing stored.",
+                        report.rows,
+                        path.display()
+                    );
+                } else {
+                    println!("✅ Imported {} memories", report.stored);
+                    if report.reembedded > 0 {
+                        println!(
+                            "ℹ️  {} had no embedding in the export and were re-embedded",
+                            report.reembedded
+                        );
+                    }
+                }
+                return Ok(());
+            }
+            let report = if let Ok(session_format) = SessionFormat::parse(&format) {
+                if llm {
+                    println!("ℹ️  --llm is ignored for agent session logs");
+                }
+                memory_manager
+                    .import_agent_sessions(path, session_format, dry_run)
+                    .await?
+            } else {
+                let format = ConversationFormat::parse(&format).map_err(|_| {
+                    anyhow::anyhow!(
+                        "Unknown import format '{}'; expected chatgpt, claude, claude-code, continue or cursor",
+                        format
+                    )
+                })?;
+                memory_manager
+                    .import_conversations(path, format, llm, dry_run)
+                    .await?
+            };
+            println!(
+                "📥 Scanned {} conversations/sessions, found {} items",
+                report.conversations,
+                report.facts.len()
+            );
+            if dry_run {
+                for (conversation, fact) in &report.facts {
+                    println!(
+                        "  • [{}] {} ({})",
+                        fact.memory_type, fact.text, conversation
+                    );
+                }
+                println!("ℹ️  Dry run — nothing stored.");
+            } else {
+                println!("✅ Stored {} memories", report.stored);
+                if !report.skipped.is_empty() {
+                    println!("⚠️  Skipped {}:", report.skipped.len());
+                    for reason in &report.skipped {
+                        println!("  • {}", reason);
+                    }
+                }
+            }
+        }
+        MemoryCommand::Export {
+            format,
+            memory_type,
+            tags,
+            output,
+            deck,
+        } => {
+            let types: Vec<MemoryType> = match parse_memory_types_opt(&memory_type) {
+                Some(types) => types,
+                None if format == "anki" => ANKI_DEFAULT_TYPES.to_vec(),
+                None => Vec::new(),
+            };
+            let tags = split_csv_opt(&tags).unwrap_or_default();
+            match format.as_str() {
+                "jsonl" => {
+                    let (memories, relationships) = match &output {
+                        Some(path) => {
+                            let file = std::fs::File::create(path)?;
+                            memory_manager
+                                .export_jsonl(&types, &tags, std::io::BufWriter::new(file))
+                                .await?
+                        }
+                        None => {
+                            memory_manager
+                                .export_jsonl(
+                                    &types,
+                                    &tags,
+                                    std::io::BufWriter::new(std::io::stdout()),
+                                )
+                                .await?
+                        }
+                    };
+                    if let Some(path) = output {
+                        println!(
+                            "✅ Exported {} memories and {} relationships to {}",
+                            memories, relationships, path
+                        );
+                    }
+                    return Ok(());
+                }
+                "arrow" => {
+                    let Some(path) = output else {
+                        return Err(anyhow::anyhow!(
+                            "--format arrow writes a binary file; pass --output <file>"
+                        ));
+                    };
+                    let count = memory_manager
+                        .export_arrow(&types, &tags, std::path::Path::new(&path))
+                        .await?;
+                    println!("✅ Exported {} memories to {}", count, path);
+                    return Ok(());
+                }
+                "parquet" => {
+                    return Err(anyhow::anyhow!(
+                        "Parquet is not built in; use --format arrow and convert, e.g. \
+                         pyarrow.parquet.write_table(pyarrow.feather.read_table(path), out)"
+                    ))
+                }
+                _ => {}
+            }
+            let memories = memory_manager.export_memories(&types, &tags).await?;
+
+            let rendered = match format.as_str() {
+                "anki" => to_anki(&memories, &deck),
+                other => {
+                    return Err(anyhow::anyhow!(
+                        "Unknown export format '{}'; expected anki, jsonl or arrow",
+                        other
+                    ))
+                }
+            };
+            match output {
+                Some(path) => {
+                    std::fs::write(&path, rendered)?;
+                    println!("✅ Exported {} memories to {}", memories.len(), path);
+                }
+                None => print!("{}", rendered),
+            }
+        }
+        MemoryCommand::Share { share } => match share {
+            ShareCommand::Export {
+                tags,
+                memory_type,
+                output,
+                sender,
+            } => {
+                let types = parse_memory_types_opt(&memory_type).unwrap_or_default();
+                let tags = split_csv_opt(&tags).unwrap_or_default();
+                let sender = sender.unwrap_or_else(default_sender);
+                let (bundle, count) = memory_manager
+                    .export_share_bundle(&types, &tags, sender.clone())
+                    .await?;
+                if count == 0 {
+                    println!("ℹ️  No memories match — nothing to share.");
+                    return Ok(());
+                }
+                std::fs::write(&output, serde_json::to_string_pretty(&bundle)?)?;
+                let fingerprint = local_key_fingerprint(&crate::storage::get_share_key_path()?)?;
+                println!(
+                    "✅ Shared {} memories as '{}' to {}\n   Key fingerprint: {}",
+                    count, sender, output, fingerprint
+                );
+            }
+            ShareCommand::Import {
+                path,
+                trust,
+                on_conflict,
+            } => {
+                let raw = std::fs::read_to_string(&path)?;
+                let bundle: ShareBundle = serde_json::from_str(&raw)
+                    .map_err(|e| anyhow::anyhow!("'{}' is not a share bundle: {}", path, e))?;
+                let strategy = memory_manager.conflict_strategy(on_conflict.as_deref())?;
+                let mut stdin_prompt = StdinConflictPrompt;
+                let prompt: Option<&mut dyn ConflictPrompt> = match strategy {
+                    ConflictStrategy::Interactive => Some(&mut stdin_prompt),
+                    _ => None,
+                };
+                let report = memory_manager
+                    .import_share_bundle(&bundle, trust.as_deref(), strategy, prompt)
+                    .await?;
+                println!(
+                    "🔏 Signature verified — sender '{}', key {}",
+                    report.sender, report.fingerprint
+                );
+                if !report.trusted {
+                    println!(
+                        "⚠️  Unknown signer: confirm the fingerprint with the sender, then pass \
+                         --trust or add it to [memory].trusted_share_keys."
+                    );
+                }
+                println!(
+                    "✅ Imported {} memories ({} already present)",
+                    report.stored, report.duplicates
+                );
+                if report.updated > 0 {
+                    println!("🔄 Updated {} earlier imports", report.updated);
+                }
+                if report.conflicts > 0 {
+                    println!(
+                        "⚠️  {} conflicts settled with {}",
+                        report.conflicts,
+                        strategy.as_str()
+                    );
+                }
+                if !report.skipped.is_empty() {
+                    println!("⚠️  Skipped {}:", report.skipped.len());
+                    for reason in &report.skipped {
+                        println!("  • {}", reason);
+                    }
+                }
+            }
+            ShareCommand::Key => {
+                let path = crate::storage::get_share_key_path()?;
+                println!("🔑 {}", local_key_fingerprint(&path)?);
+                println!("   {}", path.display());
+            }
+        },
+        MemoryCommand::Report { report } => match report {
+            ReportCommand::Access {
+                limit,
+                weeks,
+                format,
+            } => {
+                let report = memory_manager.get_access_report(limit, weeks).await?;
+                if format == "json" {
+                    println!("{}", serde_json::to_string_pretty(&report)?);
+                } else {
+                    print!("{}", report.format());
+                }
+            }
+            ReportCommand::Stale {
+                days,
+                min_importance,
+                min_decay,
+                limit,
+                format,
+            } => {
+                if !(0.0..=1.0).contains(&min_importance) || !(0.0..=1.0).contains(&min_decay) {
+                    return Err(anyhow::anyhow!(
+                        "--min-importance and --min-decay must be between 0.0 and 1.0"
+                    ));
+                }
+                let report = memory_manager
+                    .get_stale_report(days, min_importance, min_decay, limit)
+                    .await?;
+                if format == "json" {
+                    println!("{}", serde_json::to_string_pretty(&report)?);
+                } else {
+                    print!("{}", report.format());
+                }
+            }
+        },
+        MemoryCommand::Index { index } => match index {
+            IndexCommand::Rebuild { table } => {
+                if table != "memories" {
+                    return Err(anyhow::anyhow!(
+                        "Unknown table '{}'; expected memories or knowledge",
+                        table
+                    ));
+                }
+                print_index_rebuild(memory_manager.rebuild_vector_index().await?);
+            }
+        },
+        MemoryCommand::Links { links } => match links {
+            LinksCommand::Prune {
+                below,
+                relationship_type,
+                dry_run,
+            } => {
+                if !(0.0..=1.0).contains(&below) {
+                    return Err(anyhow::anyhow!("--below must be between 0.0 and 1.0"));
+                }
+                let rel_type = relationship_type
+                    .as_deref()
+                    .map(crate::memory::RelationshipType::from);
+                let pruned = memory_manager
+                    .prune_relationships(below, rel_type, dry_run)
+                    .await?;
+                if pruned.is_empty() {
+                    println!("No relationships below strength {:.2}.", below);
+                    return Ok(());
+                }
+                println!(
+                    "{} {} relationship(s) below strength {:.2}:",
+                    if dry_run {
+                        "🔍 Would remove"
+                    } else {
+                        "✂️  Removed"
+                    },
+                    pruned.len(),
+                    below
+                );
+                for rel in &pruned {
+                    println!(
+                        "  {} -> {} ({}, strength: {:.2})",
+                        rel.source_id, rel.target_id, rel.relationship_type, rel.strength
+                    );
+                }
+            }
+        },
+        MemoryCommand::Alias { alias } => match alias {
+            AliasCommand::Set { name, memory_id } => {
+                let memory_id = memory_manager.set_alias(&name, &memory_id).await?;
+                println!("✅ Alias '{}' → {}", name, memory_id);
+            }
+            AliasCommand::Remove { name } => match memory_manager.remove_alias(&name)? {
+                Some(memory_id) => println!("✅ Removed alias '{}' (was {})", name, memory_id),
+                None => println!("❌ Alias '{}' not found.", name),
+            },
+            AliasCommand::List => {
+                let aliases = memory_manager.list_aliases()?;
+                if aliases.is_empty() {
+                    println!("No aliases set. Use `memory alias set <name> <memory-id>`.");
+                }
+                for (name, memory_id) in aliases {
+                    println!("{}\t{}", name, memory_id);
+                }
+            }
+        },
+        MemoryCommand::Digest {
+            since,
+            llm,
+            format,
+            output,
+        } => {
+            let window = crate::memory::digest::parse_window(&since)?;
+            let digest = memory_manager.digest(window, llm).await?;
+            let rendered = match format.as_str() {
+                "markdown" | "md" => digest.format_markdown(),
+                "json" => serde_json::to_string_pretty(&digest)?,
+                other => {
+                    return Err(anyhow::anyhow!(
+                        "Unknown format '{}'; expected markdown or json",
+                        other
+                    ))
+                }
+            };
+            match output {
+                Some(path) => {
+                    std::fs::write(&path, rendered)?;
+                    println!("✅ Digest written to {}", path);
+                }
+                None => print!("{}", rendered),
+            }
+        }
+        MemoryCommand::Review { review } => match review {
+            ReviewCommand::Due { limit } => {
+                let due = memory_manager.due_for_review(limit).await?;
+                if due.is_empty() {
+                    println!("✅ Nothing due for review.");
+                    return Ok(());
+                }
+                let ids: Vec<String> = due.iter().map(|d| d.memory.id.clone()).collect();
+                let shorts = memory_manager.short_ids(&ids).await?;
+                println!("📚 {} memories due for review:\n", due.len());
+                for d in &due {
+                    println!(
+                        "  {}  [{}] {}  (due {}, interval {} days)",
+                        shorts[&d.memory.id],
+                        d.memory.memory_type,
+                        d.memory.title,
+                        d.card.next_review_at.format("%Y-%m-%d"),
+                        d.card.interval_days
+                    );
+                }
+                println!("\nRecall each, check with `memory get <id>`, then `memory review grade <id> <0-5>`.");
+            }
+            ReviewCommand::Grade { memory_id, grade } => {
+                let (memory_id, card) = memory_manager.grade_review(&memory_id, grade).await?;
+                println!(
+                    "✅ Graded {} {}/5; next review {} (in {} days)",
+                    memory_id,
+                    grade,
+                    card.next_review_at.format("%Y-%m-%d"),
+                    card.interval_days
+                );
+            }
+        },
+        MemoryCommand::Feedback {
+            memory_id,
+            helpful,
+            unhelpful: _,
+            query,
+        } => {
+            let (memory_id, query) = memory_manager
+                .record_feedback(&memory_id, helpful, query)
+                .await?;
+            let verdict = if helpful { "helpful" } else { "unhelpful" };
+            if query.is_empty() {
+                println!(
+                    "✅ Marked {} as {} (no recent query found; applies to all searches)",
+                    memory_id, verdict
+                );
+            } else {
+                println!("✅ Marked {} as {} for \"{}\"", memory_id, verdict, query);
+            }
+        }
+        MemoryCommand::History { memory_id } => {
+            let memory_id = memory_manager.resolve_memory_id(&memory_id).await?;
+            let Some(current) = memory_manager.get_memory(&memory_id).await? else {
+                println!("❌ Memory '{}' not found.", memory_id);
+                return Ok(());
+            };
+            let revisions = memory_manager.memory_history(&memory_id).await?;
+            println!(
+                "📜 {} — current: [{}] {} (updated {})",
+                memory_id,
+                current.memory_type,
+                current.title,
+                current.updated_at.format("%Y-%m-%d %H:%M")
+            );
+            if revisions.is_empty() {
+                println!("No earlier revisions.");
+            }
+            for revision in revisions.iter().rev() {
+                println!(
+                    "  r{}  replaced {}  [{}] {}",
+                    revision.revision,
+                    revision.replaced_at.format("%Y-%m-%d %H:%M"),
+                    revision.memory.memory_type,
+                    revision.memory.title
+                );
+            }
+        }
+        MemoryCommand::Rollback { memory_id, to } => {
+            let memory_id = memory_manager.resolve_memory_id(&memory_id).await?;
+            match memory_manager.rollback_memory(&memory_id, to).await? {
+                Some((memory, saved_as)) => println!(
+                    "✅ Rolled back {} to revision {}: {} (previous version saved as r{})",
+                    memory_id, to, memory.title, saved_as
+                ),
+                None => println!("❌ Memory '{}' not found.", memory_id),
+            }
+        }
+        MemoryCommand::Trash { trash } => match trash {
+            TrashCommand::List => {
+                memory_manager.purge_expired_trash().await?;
+                let trashed = memory_manager.list_recoverable().await?;
+                if trashed.is_empty() {
+                    println!("🗑️  Trash is empty.");
+                    return Ok(());
+                }
+                println!("🗑️  {} memories in the trash:", trashed.len());
+                for (memory, deleted_at) in &trashed {
+                    println!(
+                        "  • [{}] {} (id={}, deleted {})",
+                        memory.memory_type, memory.title, memory.id, deleted_at
+                    );
+                }
+            }
+            TrashCommand::Restore { memory_ids } => {
+                let restored = memory_manager.restore_from_trash(&memory_ids).await?;
+                println!("✅ Restored {} memories:", restored.len());
+                for memory in &restored {
+                    println!("  • {} (id={})", memory.title, memory.id);
+                }
+            }
+            TrashCommand::Purge { all } => {
+                let purged = if all {
+                    memory_manager.empty_trash().await?
+                } else {
+                    memory_manager.purge_expired_trash().await?
+                };
+                println!("✅ Permanently deleted {} trashed memories.", purged);
+            }
+        },
     }
 
     Ok(())
 }
 
+/// `bytes` in the largest unit that keeps it at or above 1
+fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit + 1 < UNITS.len() {
+        size /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} B", bytes)
+    } else {
+        format!("{:.1} {}", size, UNITS[unit])
+    }
+}
+
+fn print_index_rebuild(rebuild: Option<crate::vector_optimizer::IndexRebuild>) {
+    let Some(rebuild) = rebuild else {
+        println!("ℹ️  This storage backend has no vector index to rebuild.");
+        return;
+    };
+    if rebuild.dropped {
+        println!("🗑️  Dropped existing index on {}", rebuild.table);
+    }
+    if rebuild.created {
+        println!(
+            "✅ Rebuilt {} index: {} rows, {} partitions, {} sub-vectors",
+            rebuild.table, rebuild.rows, rebuild.num_partitions, rebuild.num_sub_vectors
+        );
+    } else {
+        println!(
+            "ℹ️  {} has {} rows; brute-force search is faster, no index built",
+            rebuild.table, rebuild.rows
+        );
+    }
+}
+
+/// `✓ Indexed: source (N chunks created)`, noting chunks whose text was
+/// already indexed from other sources (mirrors)
+fn indexed_line(result: &IndexResult) -> String {
+    let mut line = format!(
+        "✓ Indexed: {} ({} chunks created",
+        result.source, result.chunks_created
+    );
+    if result.duplicate_chunks > 0 {
+        line.push_str(&format!(
+            ", {} already indexed from other sources",
+            result.duplicate_chunks
+        ));
+    }
+    line.push(')');
+    line
+}
+
 async fn execute_knowledge_command(
     knowledge_manager: &mut KnowledgeManager,
     command: KnowledgeCommand,
 ) -> Result<()> {
+    if let KnowledgeCommand::Index { collection, .. }
+    | KnowledgeCommand::IndexRepo { collection, .. } = &command
+    {
+        knowledge_manager.set_index_collection(collection.clone());
+    }
     match command {
-        KnowledgeCommand::Index { source } => {
+        KnowledgeCommand::Index {
+            sitemap: Some(sitemap),
+            include,
+            exclude,
+            max_pages,
+            concurrency,
+            ..
+        } => {
+            let filter = UrlFilter::new(
+                &split_csv_opt(&include).unwrap_or_default(),
+                &split_csv_opt(&exclude).unwrap_or_default(),
+            )?;
+            let options = knowledge_manager.crawl_options(0);
+            let max_pages = max_pages.unwrap_or(options.max_pages);
+            println!("Reading sitemap {}...", sitemap);
+            let mut pages = knowledge_manager.sitemap_pages(&sitemap, &filter).await?;
+            if pages.is_empty() {
+                println!("No pages in the sitemap match");
+                return Ok(());
+            }
+            if pages.len() > max_pages {
+                println!(
+                    "Sitemap lists {} matching pages; indexing the first {} (raise --max-pages for more)",
+                    pages.len(),
+                    max_pages
+                );
+                pages.truncate(max_pages);
+            }
+            let total = pages.len();
+            let mut done = 0;
+            let report = knowledge_manager
+                .index_pages(
+                    &pages,
+                    concurrency.unwrap_or(options.concurrency),
+                    &mut |outcome| {
+                        done += 1;
+                        match outcome {
+                            Ok(result) if result.was_cached => println!(
+                                "[{}/{}] ✓ Cached: {} (content unchanged)",
+                                done, total, result.source
+                            ),
+                            Ok(result) => {
+                                println!("[{}/{}] {}", done, total, indexed_line(&result))
+                            }
+                            Err(failure) => eprintln!(
+                                "[{}/{}] ✗ {}: {}",
+                                done, total, failure.source, failure.error
+                            ),
+                        }
+                    },
+                )
+                .await?;
+            let chunks: usize = report.indexed.iter().map(|r| r.chunks_created).sum();
+            println!(
+                "\nIndexed {} sitemap pages ({} chunks created, {} failed)",
+                report.indexed.len(),
+                chunks,
+                report.failed.len()
+            );
+            Ok(())
+        }
+        KnowledgeCommand::Index {
+            source: Some(source),
+            depth: Some(depth),
+            max_pages,
+            concurrency,
+            ..
+        } => {
+            let mut options = knowledge_manager.crawl_options(depth);
+            options.max_pages = max_pages.unwrap_or(options.max_pages);
+            options.concurrency = concurrency.unwrap_or(options.concurrency);
+            println!(
+                "Crawling {} (depth {}, at most {} pages)...",
+                source, options.depth, options.max_pages
+            );
+            let report = knowledge_manager
+                .crawl(&source, options, &mut |outcome| match outcome {
+                    Ok(result) if result.was_cached => {
+                        println!("✓ Cached: {} (content unchanged)", result.source)
+                    }
+                    Ok(result) => println!("{}", indexed_line(&result)),
+                    Err(failure) => eprintln!("✗ {}: {}", failure.source, failure.error),
+                })
+                .await?;
+            let chunks: usize = report.indexed.iter().map(|r| r.chunks_created).sum();
+            println!(
+                "\nCrawled {} pages ({} chunks created, {} failed)",
+                report.indexed.len() + report.failed.len(),
+                chunks,
+                report.failed.len()
+            );
+            Ok(())
+        }
+        KnowledgeCommand::Index { source, .. } => {
+            let source =
+                source.ok_or_else(|| anyhow::anyhow!("A source or --sitemap is required"))?;
             println!("Indexing source...");
             let result = knowledge_manager.index_source(&source).await?;
 
             if result.was_cached && !result.content_changed {
                 println!("✓ Cached: {} (content unchanged)", result.source);
             } else {
-                println!(
-                    "✓ Indexed: {} ({} chunks created)",
-                    result.source, result.chunks_created
-                );
+                println!("{}", indexed_line(&result));
+            }
+            Ok(())
+        }
+        KnowledgeCommand::IndexRepo {
+            repo,
+            no_wiki,
+            concurrency,
+            ..
+        } => {
+            let repo = RepoRef::parse(&repo)?;
+            let concurrency = concurrency.unwrap_or(knowledge_manager.crawl_options(0).concurrency);
+            println!("Indexing docs of {}...", repo.name());
+            let outcome = knowledge_manager
+                .index_repo(&repo, !no_wiki, concurrency, &mut |outcome| match outcome {
+                    Ok(result) if result.was_cached => {
+                        println!("✓ Cached: {} (content unchanged)", result.source)
+                    }
+                    Ok(result) => println!("{}", indexed_line(&result)),
+                    Err(failure) => eprintln!("✗ {}: {}", failure.source, failure.error),
+                })
+                .await?;
+            if outcome.truncated {
+                eprintln!("Warning: GitHub listed only part of the repository; some docs were not indexed");
+            }
+            if let Some(error) = &outcome.wiki_error {
+                eprintln!("Warning: wiki skipped: {}", error);
+            }
+            let report = &outcome.report;
+            let chunks: usize = report.indexed.iter().map(|r| r.chunks_created).sum();
+            println!(
+                "\nIndexed {} files of {} on {} ({} chunks created, {} failed)",
+                report.indexed.len(),
+                repo.name(),
+                outcome.branch,
+                chunks,
+                report.failed.len()
+            );
+            Ok(())
+        }
+        KnowledgeCommand::Refresh { all, url } => {
+            let scope = match (all, url) {
+                (_, Some(url)) => RefreshScope::Source(url),
+                (true, None) => RefreshScope::All,
+                (false, None) => RefreshScope::Outdated,
+            };
+            let report = knowledge_manager.refresh(scope).await?;
+            for source in &report.refreshed {
+                println!("✓ Re-indexed: {}", source);
             }
+            for failure in &report.failed {
+                eprintln!("✗ {}: {}", failure.source, failure.error);
+            }
+            println!(
+                "\nChecked {} sources: {} changed, {} unchanged, {} failed",
+                report.checked,
+                report.refreshed.len(),
+                report.unchanged.len(),
+                report.failed.len()
+            );
             Ok(())
         }
-        KnowledgeCommand::Search { query, source } => {
-            let source_filter = source;
+        KnowledgeCommand::Search {
+            query,
+            source,
+            collection,
+            limit,
+            min_relevance,
+            stitch,
+            format,
+        } => {
+            let filter = ChunkFilter {
+                source: source.as_deref(),
+                collection: collection.as_deref(),
+            };
             let results = knowledge_manager
-                .search(&query, source_filter.as_deref(), None)
+                .search(&query, filter, None, limit, min_relevance)
                 .await?;
 
-            if results.is_empty() {
+            if stitch {
+                let passages = knowledge_manager.stitch(&results, None).await?;
+                if format == "json" {
+                    use crate::knowledge::formatting::passages_json;
+                    println!(
+                        "{}",
+                        serde_json::to_string_pretty(&passages_json(&passages))?
+                    );
+                } else {
+                    use crate::knowledge::formatting::format_passages;
+                    println!("{}", format_passages(&passages));
+                }
+            } else if format == "json" {
+                use crate::knowledge::formatting::search_results_json;
+                println!(
+                    "{}",
+                    serde_json::to_string_pretty(&search_results_json(&results))?
+                );
+            } else if results.is_empty() {
                 println!("No results found");
             } else {
                 use crate::knowledge::formatting::format_search_results;
@@ -765,16 +2268,28 @@ async fn execute_knowledge_command(
             println!("✓ Deleted stored knowledge '{}'", key);
             Ok(())
         }
-        KnowledgeCommand::Stats => {
+        KnowledgeCommand::Stats { format } => {
             let stats = knowledge_manager.get_stats().await?;
-            use crate::knowledge::formatting::format_stats;
-            println!("{}", format_stats(&stats));
+            if format == "json" {
+                println!("{}", serde_json::to_string_pretty(&stats)?);
+            } else {
+                use crate::knowledge::formatting::format_stats;
+                println!("{}", format_stats(&stats));
+            }
             Ok(())
         }
-        KnowledgeCommand::List { limit } => {
+        KnowledgeCommand::List { limit, format } => {
             let sources = knowledge_manager.list_sources(Some(limit)).await?;
-            use crate::knowledge::formatting::format_source_list;
-            println!("{}", format_source_list(&sources));
+            if format == "json" {
+                use crate::knowledge::formatting::source_list_json;
+                println!(
+                    "{}",
+                    serde_json::to_string_pretty(&source_list_json(&sources))?
+                );
+            } else {
+                use crate::knowledge::formatting::format_source_list;
+                println!("{}", format_source_list(&sources));
+            }
             Ok(())
         }
         KnowledgeCommand::Read { source } => {
@@ -783,6 +2298,25 @@ async fn execute_knowledge_command(
             println!("{}", format_read_result(&result));
             Ok(())
         }
+        KnowledgeCommand::Get {
+            chunk_id,
+            context,
+            format,
+        } => {
+            let Some(chunk) = knowledge_manager
+                .get_chunk(&chunk_id, context, None)
+                .await?
+            else {
+                return Err(anyhow::anyhow!("Chunk not found: {}", chunk_id));
+            };
+            if format == "json" {
+                println!("{}", serde_json::to_string_pretty(&chunk)?);
+            } else {
+                use crate::knowledge::formatting::format_chunk_context;
+                println!("{}", format_chunk_context(&chunk));
+            }
+            Ok(())
+        }
         KnowledgeCommand::Match { pattern, source } => {
             let results = knowledge_manager
                 .match_content(&pattern, source.as_deref(), None)
@@ -816,6 +2350,30 @@ fn split_csv_opt(raw: &Option<String>) -> Option<Vec<String>> {
     raw.as_ref().map(|s| split_csv(s))
 }
 
+/// Parse the `--after`/`--before` creation-date bounds of a query.
+fn parse_date_range(
+    after: &Option<String>,
+    before: &Option<String>,
+) -> Result<(
+    Option<chrono::DateTime<chrono::Utc>>,
+    Option<chrono::DateTime<chrono::Utc>>,
+)> {
+    let now = chrono::Utc::now();
+    let parse = |value: &Option<String>| {
+        value
+            .as_deref()
+            .map(|v| crate::memory::query_syntax::parse_date_bound(v, now))
+            .transpose()
+    };
+    let (after, before) = (parse(after)?, parse(before)?);
+    if let (Some(a), Some(b)) = (after, before) {
+        if a > b {
+            return Err(anyhow::anyhow!("--after must not be later than --before"));
+        }
+    }
+    Ok((after, before))
+}
+
 /// Parse an optional comma-separated `memory_types` argument into `Option<Vec<MemoryType>>`.
 fn parse_memory_types_opt(raw: &Option<String>) -> Option<Vec<MemoryType>> {
     raw.as_ref().map(|s| {
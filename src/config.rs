@@ -12,13 +12,21 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use anyhow::Result;
+use anyhow::{Context, Result};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+use subtle::ConstantTimeEq;
+use toml_edit::{DocumentMut, Item, Value};
 
 use crate::memory::types::MemoryConfig;
 
+/// Config written on first run, and the reference for key types in `octobrain config set`
+const TEMPLATE: &str = include_str!("../config-templates/default.toml");
+
 /// Embedding configuration for memory operations
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct EmbeddingConfig {
     pub model: String,
     pub batch_size: usize,
@@ -39,7 +47,7 @@ impl Default for EmbeddingConfig {
 }
 
 /// Search configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct SearchConfig {
     pub similarity_threshold: f32,
     pub max_results: usize,
@@ -50,6 +58,9 @@ pub struct SearchConfig {
     /// Pseudo-relevance feedback (PRF / HyDE-lite) query expansion
     #[serde(default)]
     pub hyde: HydeConfig,
+    /// LLM answer synthesis over retrieved memories (`remember --answer`, `ask_memory`)
+    #[serde(default)]
+    pub answer: AnswerConfig,
 }
 
 impl Default for SearchConfig {
@@ -66,6 +77,38 @@ impl Default for SearchConfig {
                 timeout_secs: 30,
             },
             hyde: HydeConfig::default(),
+            answer: AnswerConfig::default(),
+        }
+    }
+}
+
+/// Answer synthesis configuration.
+///
+/// The top retrieved memories are handed to an LLM which writes a short answer
+/// citing the memory IDs it relied on. Only used on explicit request, so a missing
+/// provider key never affects regular `remember`.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct AnswerConfig {
+    /// LLM model (fully qualified, e.g., "openai:gpt-4o-mini")
+    pub model: String,
+    /// Maximum number of top-ranked memories passed to the LLM as context
+    pub max_context_memories: usize,
+    /// Maximum tokens for the generated answer
+    pub max_tokens: u32,
+    /// Sampling temperature
+    pub temperature: f32,
+    /// Timeout in seconds for the LLM call (0 = disabled)
+    pub timeout_secs: u64,
+}
+
+impl Default for AnswerConfig {
+    fn default() -> Self {
+        Self {
+            model: "openai:gpt-4o-mini".to_string(),
+            max_context_memories: 8,
+            max_tokens: 600,
+            temperature: 0.2,
+            timeout_secs: 60,
         }
     }
 }
@@ -77,7 +120,7 @@ impl Default for SearchConfig {
 /// `expanded = alpha * original + (1 - alpha) * centroid`. The expanded vector is
 /// then used for the actual search. Costs one extra LanceDB vector query per search
 /// in exchange for typically +10-30% recall on long-tail queries.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct HydeConfig {
     pub enabled: bool,
     /// Number of nearest neighbors to average for the centroid.
@@ -99,7 +142,7 @@ impl Default for HydeConfig {
 }
 
 /// Hybrid search configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct HybridSearchConfig {
     /// Enable hybrid search (native BM25 + vector RRF fusion via LanceDB)
     pub enabled: bool,
@@ -126,14 +169,126 @@ impl Default for HybridSearchConfig {
 }
 
 /// Knowledge base configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct KnowledgeConfig {
     pub chunk_size: usize,
     pub chunk_overlap: usize,
+    /// Chunk by tokens instead of characters: at most this many tokens per
+    /// chunk, header included (0 = use chunk_size/chunk_overlap)
+    #[serde(default)]
+    pub chunk_tokens: usize,
+    /// Tokens repeated between consecutive chunks in token mode
+    #[serde(default = "default_overlap_tokens")]
+    pub overlap_tokens: usize,
     pub outdating_days: u64,
     pub max_results: usize,
     /// Hours after which session-scoped chunks are cleaned up (crash recovery)
     pub session_ttl_hours: u64,
+    /// Minutes between background refreshes while `octobrain mcp` runs (0 = off)
+    #[serde(default = "default_auto_refresh_minutes")]
+    pub auto_refresh_minutes: u64,
+    /// Source prefix → hours before a URL counts as outdated, overriding
+    /// `outdating_days` for sources that change often; the longest prefix wins
+    #[serde(default)]
+    pub refresh_intervals: HashMap<String, u64>,
+    /// Pages `knowledge index --depth N` or `--sitemap` fetches at most per run
+    #[serde(default = "default_crawl_max_pages")]
+    pub crawl_max_pages: usize,
+    /// Concurrent fetches while crawling or reading a sitemap
+    #[serde(default = "default_crawl_concurrency")]
+    pub crawl_concurrency: usize,
+    /// Domain → headers, bearer token, cookies and politeness overrides used
+    /// when fetching from it (and its subdomains)
+    #[serde(default)]
+    pub sources: HashMap<String, SourceConfig>,
+    /// Skip URLs a site's robots.txt disallows, and honor its Crawl-delay
+    #[serde(default = "default_respect_robots_txt")]
+    pub respect_robots_txt: bool,
+    /// Minimum milliseconds between two requests to the same domain
+    #[serde(default = "default_request_delay_ms")]
+    pub request_delay_ms: u64,
+    /// Requests in flight at once to the same domain
+    #[serde(default = "default_domain_concurrency")]
+    pub domain_concurrency: usize,
+}
+
+/// Request settings for fetches from one domain
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct SourceConfig {
+    /// Extra headers sent with every request
+    #[serde(default)]
+    pub headers: HashMap<String, String>,
+    /// Sent as `Authorization: Bearer <token>`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bearer_token: Option<String>,
+    /// Environment variable holding the bearer token, to keep it out of the file
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bearer_token_env: Option<String>,
+    /// Cookie name → value, sent as one `Cookie` header
+    #[serde(default)]
+    pub cookies: HashMap<String, String>,
+    /// Overrides `request_delay_ms` for this domain
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_delay_ms: Option<u64>,
+    /// Overrides `domain_concurrency` for this domain
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub concurrency: Option<usize>,
+}
+
+fn default_overlap_tokens() -> usize {
+    50
+}
+
+fn default_auto_refresh_minutes() -> u64 {
+    60
+}
+
+fn default_crawl_max_pages() -> usize {
+    100
+}
+
+fn default_crawl_concurrency() -> usize {
+    4
+}
+
+fn default_respect_robots_txt() -> bool {
+    true
+}
+
+fn default_request_delay_ms() -> u64 {
+    250
+}
+
+fn default_domain_concurrency() -> usize {
+    2
+}
+
+impl KnowledgeConfig {
+    /// How long an indexed URL stays fresh: its `refresh_intervals` entry,
+    /// else `outdating_days`
+    pub fn max_age(&self, source: &str) -> chrono::Duration {
+        self.refresh_intervals
+            .iter()
+            .filter(|(prefix, _)| source.starts_with(prefix.as_str()))
+            .max_by_key(|(prefix, _)| prefix.len())
+            .map(|(_, hours)| chrono::Duration::hours(*hours as i64))
+            .unwrap_or_else(|| chrono::Duration::days(self.outdating_days as i64))
+    }
+
+    /// Request settings for `host`: the `sources` entry for it or the closest
+    /// parent domain
+    pub fn source_config(&self, host: &str) -> Option<&SourceConfig> {
+        self.sources
+            .iter()
+            .filter(|(domain, _)| {
+                host.eq_ignore_ascii_case(domain)
+                    || host
+                        .to_ascii_lowercase()
+                        .ends_with(&format!(".{}", domain.to_ascii_lowercase()))
+            })
+            .max_by_key(|(domain, _)| domain.len())
+            .map(|(_, auth)| auth)
+    }
 }
 
 impl Default for KnowledgeConfig {
@@ -141,23 +296,160 @@ impl Default for KnowledgeConfig {
         Self {
             chunk_size: 1200,
             chunk_overlap: 300,
+            chunk_tokens: 0,
+            overlap_tokens: default_overlap_tokens(),
             outdating_days: 15,
             max_results: 5,
             session_ttl_hours: 120,
+            auto_refresh_minutes: default_auto_refresh_minutes(),
+            refresh_intervals: HashMap::new(),
+            crawl_max_pages: default_crawl_max_pages(),
+            crawl_concurrency: default_crawl_concurrency(),
+            sources: HashMap::new(),
+            respect_robots_txt: default_respect_robots_txt(),
+            request_delay_ms: default_request_delay_ms(),
+            domain_concurrency: default_domain_concurrency(),
         }
     }
 }
 
 /// Main configuration for octobrain
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Config {
     pub embedding: EmbeddingConfig,
     pub search: SearchConfig,
     pub memory: MemoryConfig,
     pub knowledge: KnowledgeConfig,
+    #[serde(default)]
+    pub vision: VisionConfig,
+    #[serde(default)]
+    pub maintenance: MaintenanceConfig,
+    #[serde(default)]
+    pub server: ServerConfig,
+    #[serde(default)]
+    pub storage: StorageConfig,
 }
+
+/// Where the LanceDB memory and knowledge databases live
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct StorageConfig {
+    /// Object-store URI such as `s3://bucket/octobrain` or `gs://bucket/octobrain`;
+    /// the databases go under `<uri>/memory` and `<uri>/knowledge`. Empty keeps
+    /// them in the local storage directory.
+    pub uri: String,
+    /// Object-store settings passed to LanceDB, e.g. `region`, `endpoint` or
+    /// `aws_access_key_id`; credentials can also come from the provider's
+    /// usual environment variables
+    pub options: HashMap<String, String>,
+}
+
+impl StorageConfig {
+    pub fn is_remote(&self) -> bool {
+        !self.uri.trim().is_empty()
+    }
+
+    /// URI of the `name` database (`memory` or `knowledge`) in the object
+    /// store; `None` when storage is local
+    pub fn remote_uri(&self, name: &str) -> Option<String> {
+        self.is_remote()
+            .then(|| format!("{}/{}", self.uri.trim().trim_end_matches('/'), name))
+    }
+}
+
+/// Access control for the network transports (`octobrain mcp --bind`,
+/// `octobrain serve --sync`). With no keys configured, anyone who can reach
+/// the port has full access.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct ServerConfig {
+    /// Accepted keys, sent as `Authorization: Bearer <key>` or `X-API-Key: <key>`
+    pub api_keys: Vec<ApiKey>,
+}
+
+/// One client key and what it may do
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ApiKey {
+    /// Label used in logs and error messages
+    #[serde(default)]
+    pub name: String,
+    pub key: String,
+    #[serde(default)]
+    pub scope: KeyScope,
+}
+
+/// What a key may do: `read` searches and lists, `read_write` also stores,
+/// edits and deletes
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum KeyScope {
+    Read,
+    #[default]
+    ReadWrite,
+}
+
+impl ServerConfig {
+    /// The configured key matching `presented`, if any
+    pub fn find_key(&self, presented: &str) -> Option<&ApiKey> {
+        self.api_keys
+            .iter()
+            .find(|k| !k.key.is_empty() && bool::from(k.key.as_bytes().ct_eq(presented.as_bytes())))
+    }
+}
+
+/// How often `octobrain daemon` runs each maintenance task, in minutes
+/// (0 = never).
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct MaintenanceConfig {
+    /// Drop old low-importance memories and expired knowledge sessions
+    pub cleanup_minutes: u64,
+    /// Usage-driven importance decay (also needs `[memory].importance_tuning_enabled`)
+    pub decay_minutes: u64,
+    /// Optimize indexes and compact the memory tables
+    pub optimize_minutes: u64,
+    /// Re-index changed and outdated knowledge sources
+    pub knowledge_refresh_minutes: u64,
+}
+
+impl Default for MaintenanceConfig {
+    fn default() -> Self {
+        Self {
+            cleanup_minutes: 1440,
+            decay_minutes: 1440,
+            optimize_minutes: 360,
+            knowledge_refresh_minutes: 60,
+        }
+    }
+}
+
+/// Vision LLM used to caption images stored with `memorize --image`.
+///
+/// The caption becomes the memory content (and so drives its embedding); the
+/// image itself is kept as an attachment.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct VisionConfig {
+    /// Vision-capable LLM model (fully qualified, e.g., "openai:gpt-4o-mini")
+    pub model: String,
+    /// Maximum tokens for the generated caption
+    pub max_tokens: u32,
+    /// Timeout in seconds for the LLM call (0 = disabled)
+    pub timeout_secs: u64,
+}
+
+impl Default for VisionConfig {
+    fn default() -> Self {
+        Self {
+            model: "openai:gpt-4o-mini".to_string(),
+            max_tokens: 500,
+            timeout_secs: 60,
+        }
+    }
+}
+
 impl Config {
-    /// Load configuration from config.toml file
+    /// Load configuration from config.toml file, with the project's
+    /// `.octobrain.toml` merged over it when there is one.
     /// First tries to load from system config directory, falls back to embedded template
     /// STRICT: All config fields must be explicitly defined - no defaults allowed
     pub fn load() -> Result<Self> {
@@ -168,7 +460,7 @@ impl Config {
             let content = std::fs::read_to_string(&config_path)?;
 
             // Try to parse config - if it fails due to missing fields, provide clear error
-            let config: Self = toml::from_str(&content).map_err(|e| {
+            let config = Self::layered(&content).map_err(|e| {
                 anyhow::anyhow!(
                     "Config validation failed: {}\n\n\
                     Your config file is missing required fields or sections.\n\
@@ -185,21 +477,11 @@ impl Config {
                     config_path.display()
                 )
             })?;
-
-            // Validate knowledge config
-            if config.knowledge.chunk_overlap >= config.knowledge.chunk_size {
-                anyhow::bail!(
-                    "Invalid knowledge configuration: chunk_overlap ({}) must be less than chunk_size ({})",
-                    config.knowledge.chunk_overlap,
-                    config.knowledge.chunk_size
-                );
-            }
-
-            Ok(config)
+            config.finish()
         } else {
             // Config doesn't exist, create from template
-            let template_content = include_str!("../config-templates/default.toml");
-            let config: Self = toml::from_str(template_content)?;
+            let template_content = TEMPLATE;
+            let config = Self::layered(template_content)?.finish()?;
             // Save to system config directory
             if let Some(parent) = config_path.parent() {
                 if !parent.exists() {
@@ -210,10 +492,461 @@ impl Config {
             Ok(config)
         }
     }
+
+    /// Configuration for `--ephemeral` runs: the config file when one exists,
+    /// otherwise the embedded template (not written to disk), with the
+    /// in-memory storage backend forced on.
+    pub fn load_ephemeral() -> Result<Self> {
+        let mut config = if crate::storage::find_config_path()?.exists() {
+            Self::load()?
+        } else {
+            Self::layered(TEMPLATE)?.finish()?
+        };
+        config.memory.backend = "ephemeral".to_string();
+        Ok(config)
+    }
+
+    /// `content` of the global config with the project config merged over it
+    fn layered(content: &str) -> Result<Self> {
+        let mut table: toml::Table = toml::from_str(content)?;
+        if let Some(path) = project_config_path() {
+            let project = std::fs::read_to_string(&path)
+                .with_context(|| format!("Failed to read {}", path.display()))?;
+            let project: toml::Table = toml::from_str(&project)
+                .with_context(|| format!("Invalid project config {}", path.display()))?;
+            merge_project_config(&mut table, project);
+        }
+        Ok(toml::Value::Table(table).try_into()?)
+    }
+
+    /// Apply `OCTOBRAIN_*` environment overrides, expand model aliases and
+    /// check the result
+    fn finish(self) -> Result<Self> {
+        let vars: HashMap<String, String> = std::env::vars()
+            .filter(|(name, _)| name.starts_with(ENV_PREFIX))
+            .collect();
+        let mut config = self.with_overrides(&vars)?;
+
+        // Expand `local:` aliases so model stamps name the actual model
+        config.embedding.model = crate::embedding::resolve_model_spec(&config.embedding.model)?;
+
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Replace every setting named in `vars`: the dotted key, upper-cased with
+    /// `_` for `.` and other punctuation, after `OCTOBRAIN_`
+    /// (`search.hybrid.enabled` is `OCTOBRAIN_SEARCH_HYBRID_ENABLED`). Keys come
+    /// from the config schema, so unset optional settings can be set too, and
+    /// a map takes new entries (`OCTOBRAIN_STORAGE_OPTIONS_REGION` sets
+    /// `storage.options.region`). Values are read as the type of the setting;
+    /// arrays and tables in TOML syntax.
+    pub fn with_overrides(self, vars: &HashMap<String, String>) -> Result<Self> {
+        let schema = schemars::schema_for!(Config);
+        let mut value = toml::Value::try_from(&self)?;
+        let overrides = EnvOverrides {
+            vars,
+            root: schema.as_value(),
+        };
+        if !overrides.apply(&mut value, schema.as_value(), ENV_PREFIX)? {
+            return Ok(self);
+        }
+        Ok(value.try_into()?)
+    }
+
+    /// Reject value combinations that parse but cannot work
+    pub fn validate(&self) -> Result<()> {
+        if self.knowledge.chunk_overlap >= self.knowledge.chunk_size {
+            anyhow::bail!(
+                "Invalid knowledge configuration: chunk_overlap ({}) must be less than chunk_size ({})",
+                self.knowledge.chunk_overlap,
+                self.knowledge.chunk_size
+            );
+        }
+        if self.knowledge.chunk_tokens > 0
+            && self.knowledge.overlap_tokens >= self.knowledge.chunk_tokens
+        {
+            anyhow::bail!(
+                "Invalid knowledge configuration: overlap_tokens ({}) must be less than chunk_tokens ({})",
+                self.knowledge.overlap_tokens,
+                self.knowledge.chunk_tokens
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Prefix of the environment variables that override config values
+pub const ENV_PREFIX: &str = "OCTOBRAIN_";
+
+/// Environment variables applied to a config value along its JSON schema
+struct EnvOverrides<'a> {
+    vars: &'a HashMap<String, String>,
+    /// Schema of `Config`, holding the definitions `$ref`s point at
+    root: &'a serde_json::Value,
+}
+
+impl EnvOverrides<'_> {
+    /// `schema` with references followed and `Option` unwrapped
+    fn resolve<'s>(&'s self, schema: &'s serde_json::Value) -> &'s serde_json::Value {
+        if let Some(name) = schema
+            .get("$ref")
+            .and_then(|r| r.as_str())
+            .and_then(|r| r.strip_prefix("#/$defs/"))
+        {
+            if let Some(def) = self.root.get("$defs").and_then(|d| d.get(name)) {
+                return self.resolve(def);
+            }
+        }
+        let variants = schema.get("anyOf").or_else(|| schema.get("oneOf"));
+        if let Some(variants) = variants.and_then(|v| v.as_array()) {
+            let mut present = variants
+                .iter()
+                .filter(|v| v.get("type").and_then(|t| t.as_str()) != Some("null"));
+            if let (Some(only), None) = (present.next(), present.next()) {
+                return self.resolve(only);
+            }
+        }
+        schema
+    }
+
+    /// Override what lies under `value` (a table, created when missing) per
+    /// `schema`, from variables named `prefix` + key; whether any was found
+    fn apply(
+        &self,
+        value: &mut toml::Value,
+        schema: &serde_json::Value,
+        prefix: &str,
+    ) -> Result<bool> {
+        let schema = self.resolve(schema);
+        let mut found = false;
+        if let Some(properties) = schema.get("properties").and_then(|p| p.as_object()) {
+            for (key, field) in properties {
+                found |= self.apply_key(value, key, field, prefix)?;
+            }
+        } else if let Some(entry) = schema.get("additionalProperties") {
+            let existing: Vec<String> = value
+                .as_table()
+                .map(|t| t.keys().cloned().collect())
+                .unwrap_or_default();
+            for key in &existing {
+                found |= self.apply_key(value, key, entry, prefix)?;
+            }
+            // New entries only for maps of plain values: a map of sections
+            // could not tell its key from the field name
+            if !self.is_section(entry) {
+                let taken: Vec<String> = existing.iter().map(|k| env_key(k)).collect();
+                for name in self.vars.keys() {
+                    let Some(rest) = name.strip_prefix(prefix) else {
+                        continue;
+                    };
+                    if !rest.is_empty() && !taken.iter().any(|k| k == rest) {
+                        found |=
+                            self.apply_key(value, &rest.to_ascii_lowercase(), entry, prefix)?;
+                    }
+                }
+            }
+        }
+        Ok(found)
+    }
+
+    /// Override `key` of the table `value` from `prefix` + KEY
+    fn apply_key(
+        &self,
+        value: &mut toml::Value,
+        key: &str,
+        schema: &serde_json::Value,
+        prefix: &str,
+    ) -> Result<bool> {
+        let name = format!("{}{}", prefix, env_key(key));
+        let Some(table) = value.as_table_mut() else {
+            return Ok(false);
+        };
+        if let Some(raw) = self.vars.get(&name) {
+            let typed = env_value_for(self.resolve(schema), raw)
+                .with_context(|| format!("Invalid {}", name))?;
+            table.insert(key.to_string(), typed);
+            return Ok(true);
+        }
+        if !self.is_section(schema) && !self.is_map(schema) {
+            return Ok(false);
+        }
+        // Sections that are unset stay unset unless a variable fills them
+        let mut inner = table
+            .get(key)
+            .cloned()
+            .unwrap_or_else(|| toml::Value::Table(toml::Table::new()));
+        if !self.apply(&mut inner, schema, &format!("{}_", name))? {
+            return Ok(false);
+        }
+        table.insert(key.to_string(), inner);
+        Ok(true)
+    }
+
+    fn is_section(&self, schema: &serde_json::Value) -> bool {
+        self.resolve(schema).get("properties").is_some()
+    }
+
+    fn is_map(&self, schema: &serde_json::Value) -> bool {
+        self.resolve(schema)
+            .get("additionalProperties")
+            .is_some_and(|a| a.is_object())
+    }
+}
+
+/// Config key as it appears in a variable name: upper case, `_` for
+/// anything but letters and digits (`docs.rs` is `DOCS_RS`)
+fn env_key(key: &str) -> String {
+    key.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_uppercase()
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+/// `raw` parsed as the TOML type `schema` describes
+fn env_value_for(schema: &serde_json::Value, raw: &str) -> Result<toml::Value> {
+    let raw = raw.trim();
+    let kind = match schema.get("type") {
+        Some(serde_json::Value::String(kind)) => Some(kind.as_str()),
+        Some(serde_json::Value::Array(kinds)) => kinds
+            .iter()
+            .filter_map(|k| k.as_str())
+            .find(|k| *k != "null"),
+        _ => None,
+    };
+    Ok(match kind {
+        Some("string") => toml::Value::String(raw.to_string()),
+        Some("boolean") => toml::Value::Boolean(
+            raw.parse()
+                .map_err(|_| anyhow::anyhow!("expected true or false, got '{}'", raw))?,
+        ),
+        Some("integer") => toml::Value::Integer(
+            raw.parse()
+                .map_err(|_| anyhow::anyhow!("expected an integer, got '{}'", raw))?,
+        ),
+        Some("number") => toml::Value::Float(
+            raw.parse()
+                .map_err(|_| anyhow::anyhow!("expected a number, got '{}'", raw))?,
+        ),
+        _ => {
+            let mut parsed: toml::Table = toml::from_str(&format!("value = {}", raw))?;
+            parsed
+                .remove("value")
+                .ok_or_else(|| anyhow::anyhow!("expected a TOML value, got '{}'", raw))?
+        }
+    })
+}
+
+/// Per-project config file, read from the project root
+pub const PROJECT_CONFIG_FILE: &str = ".octobrain.toml";
+
+/// Keys a project config may override, as dotted paths covering everything
+/// below them. Storage, backends, knowledge source credentials and API keys
+/// always come from the global config, so a checked-out repository cannot
+/// send memories or tokens elsewhere.
+const PROJECT_KEYS: &[&str] = &[
+    "embedding",
+    "search",
+    "knowledge.chunk_size",
+    "knowledge.chunk_overlap",
+    "knowledge.chunk_tokens",
+    "knowledge.overlap_tokens",
+    "knowledge.max_results",
+];
+
+/// `.octobrain.toml` at the root of the git repository octobrain runs in (or
+/// the working directory outside a repository), if it exists
+pub fn project_config_path() -> Option<std::path::PathBuf> {
+    let root = crate::memory::git_utils::GitUtils::get_repository_root()
+        .map(std::path::PathBuf::from)
+        .or_else(|| std::env::current_dir().ok())?;
+    let path = root.join(PROJECT_CONFIG_FILE);
+    path.is_file().then_some(path)
+}
+
+/// Merge the `PROJECT_KEYS` of `project` over `global`: tables merge key by
+/// key, any other value replaces the global one
+fn merge_project_config(global: &mut toml::Table, project: toml::Table) {
+    merge_project_keys(global, project, "");
+}
+
+fn merge_project_keys(table: &mut toml::Table, overlay: toml::Table, prefix: &str) {
+    for (key, value) in overlay {
+        let path = match prefix {
+            "" => key.clone(),
+            prefix => format!("{}.{}", prefix, key),
+        };
+        let allowed = PROJECT_KEYS
+            .iter()
+            .any(|k| path == *k || path.starts_with(&format!("{}.", k)));
+        if allowed {
+            merge_value(table, key, value);
+            continue;
+        }
+        let nested = PROJECT_KEYS
+            .iter()
+            .any(|k| k.starts_with(&format!("{}.", path)));
+        match (table.get_mut(&key), value) {
+            (Some(toml::Value::Table(base)), toml::Value::Table(overlay)) if nested => {
+                merge_project_keys(base, overlay, &path)
+            }
+            _ => tracing::warn!(
+                "{} is ignored in {}; set it in the global config",
+                path,
+                PROJECT_CONFIG_FILE
+            ),
+        }
+    }
+}
+
+fn merge_value(table: &mut toml::Table, key: String, value: toml::Value) {
+    match (table.get_mut(&key), value) {
+        (Some(toml::Value::Table(base)), toml::Value::Table(overlay)) => {
+            for (key, value) in overlay {
+                merge_value(base, key, value);
+            }
+        }
+        (_, value) => {
+            table.insert(key, value);
+        }
+    }
+}
+
+/// The config file as an editable document, for `octobrain config`. Keys are
+/// dotted paths such as `search.hybrid.enabled`; edits keep the file's
+/// comments and layout.
+pub struct ConfigDocument {
+    doc: DocumentMut,
+}
+
+impl ConfigDocument {
+    pub fn parse(content: &str) -> Result<Self> {
+        let doc = content
+            .parse::<DocumentMut>()
+            .context("Config file is not valid TOML")?;
+        Ok(Self { doc })
+    }
+
+    /// The file at `path`, or the default template when there is none yet
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Self::parse(TEMPLATE);
+        }
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        Self::parse(&content)
+    }
+
+    /// Item at dotted `key`
+    pub fn get(&self, key: &str) -> Option<&Item> {
+        key.split('.')
+            .try_fold(self.doc.as_item(), |item, part| item.get(part))
+    }
+
+    /// Set `key` to `raw`, read as the type of the value it replaces (or of the
+    /// template's value when the file lacks the key). Unknown keys are refused.
+    pub fn set(&mut self, key: &str, raw: &str) -> Result<()> {
+        let template = Self::parse(TEMPLATE)?;
+        let existing = match self.get(key).or_else(|| template.get(key)) {
+            Some(Item::Value(value)) => value.clone(),
+            Some(_) => anyhow::bail!("'{}' is a section, not a value", key),
+            None => anyhow::bail!("Unknown config key '{}'", key),
+        };
+        let mut value =
+            value_like(&existing, raw).with_context(|| format!("Invalid value for {}", key))?;
+        // Keep an inline comment after the old value
+        if let Some(Item::Value(old)) = self.get(key) {
+            *value.decor_mut() = old.decor().clone();
+        }
+
+        let (sections, name) = key
+            .rsplit_once('.')
+            .ok_or_else(|| anyhow::anyhow!("Unknown config key '{}'", key))?;
+        let mut table = self.doc.as_table_mut();
+        for section in sections.split('.') {
+            table = table
+                .entry(section)
+                .or_insert_with(toml_edit::table)
+                .as_table_mut()
+                .ok_or_else(|| anyhow::anyhow!("'{}' is not a section", section))?;
+        }
+        table.insert(name, Item::Value(value));
+        Ok(())
+    }
+
+    /// Parse the document as a `Config` and check its values
+    pub fn to_config(&self) -> Result<Config> {
+        let config: Config = toml::from_str(&self.doc.to_string())?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Dotted keys in the document that no config field reads, e.g. typos
+    /// or settings from older versions
+    pub fn unknown_keys(&self, config: &Config) -> Result<Vec<String>> {
+        let known = toml::Value::try_from(config)?;
+        let file: toml::Value = toml::from_str(&self.doc.to_string())?;
+        let mut unknown = Vec::new();
+        collect_unknown(&file, &known, "", &mut unknown);
+        unknown.sort();
+        Ok(unknown)
+    }
+}
+
+impl std::fmt::Display for ConfigDocument {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.doc)
+    }
+}
+
+/// `raw` parsed as the same TOML type as `existing`; arrays and inline
+/// tables are written in TOML syntax
+fn value_like(existing: &Value, raw: &str) -> Result<Value> {
+    let raw = raw.trim();
+    Ok(match existing {
+        Value::String(_) => Value::from(raw),
+        Value::Boolean(_) => Value::from(
+            raw.parse::<bool>()
+                .map_err(|_| anyhow::anyhow!("expected true or false, got '{}'", raw))?,
+        ),
+        Value::Integer(_) => Value::from(
+            raw.parse::<i64>()
+                .map_err(|_| anyhow::anyhow!("expected an integer, got '{}'", raw))?,
+        ),
+        Value::Float(_) => Value::from(
+            raw.parse::<f64>()
+                .map_err(|_| anyhow::anyhow!("expected a number, got '{}'", raw))?,
+        ),
+        _ => raw
+            .parse::<Value>()
+            .map_err(|e| anyhow::anyhow!("expected a TOML value: {}", e))?,
+    })
+}
+
+fn collect_unknown(file: &toml::Value, known: &toml::Value, prefix: &str, out: &mut Vec<String>) {
+    let (Some(file), Some(known)) = (file.as_table(), known.as_table()) else {
+        return;
+    };
+    for (key, value) in file {
+        let path = if prefix.is_empty() {
+            key.clone()
+        } else {
+            format!("{}.{}", prefix, key)
+        };
+        match known.get(key) {
+            Some(known) => collect_unknown(value, known, &path, out),
+            None => out.push(path),
+        }
+    }
 }
 
 /// Reranker configuration for improving search result accuracy
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct RerankerConfig {
     /// Enable reranking for memory search
     pub enabled: bool,
@@ -226,3 +959,181 @@ pub struct RerankerConfig {
     /// Timeout in seconds for reranker calls (0 = disabled)
     pub timeout_secs: u64,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_set_keeps_comments_and_checks_types() {
+        let mut document = ConfigDocument::parse(TEMPLATE).unwrap();
+        document.set("search.hybrid.enabled", "false").unwrap();
+        document.set("knowledge.chunk_size", "1500").unwrap();
+        assert_eq!(
+            document
+                .get("search.hybrid.enabled")
+                .and_then(|i| i.as_bool()),
+            Some(false)
+        );
+        assert!(document.to_string().contains("[search.hybrid]"));
+        assert_eq!(document.to_config().unwrap().knowledge.chunk_size, 1500);
+
+        assert!(document.set("search.hybrid.enabled", "maybe").is_err());
+        assert!(document.set("search.hybrid.enabeld", "true").is_err());
+        assert!(document.set("search.hybrid", "true").is_err());
+    }
+
+    #[test]
+    fn test_invalid_combination_fails_validation() {
+        let mut document = ConfigDocument::parse(TEMPLATE).unwrap();
+        document.set("knowledge.chunk_overlap", "999999").unwrap();
+        assert!(document.to_config().is_err());
+    }
+
+    #[test]
+    fn test_unknown_keys_are_reported() {
+        let content = format!("{}\n[experimental]\nflag = true\n", TEMPLATE)
+            .replace("[search.hybrid]\n", "[search.hybrid]\nenabeld = true\n");
+        let document = ConfigDocument::parse(&content).unwrap();
+        let config = document.to_config().unwrap();
+        assert_eq!(
+            document.unknown_keys(&config).unwrap(),
+            vec!["experimental", "search.hybrid.enabeld"]
+        );
+    }
+
+    #[test]
+    fn test_project_config_merges_over_global() {
+        let mut global: toml::Table = toml::from_str(TEMPLATE).unwrap();
+        let project: toml::Table = toml::from_str(
+            r#"
+            [search.hybrid]
+            enabled = false
+
+            [knowledge]
+            chunk_size = 2000
+
+            [server]
+            api_keys = [{ name = "repo", key = "leaked", scope = "read_write" }]
+            "#,
+        )
+        .unwrap();
+        merge_project_config(&mut global, project);
+        let config: Config = toml::Value::Table(global).try_into().unwrap();
+        assert!(!config.search.hybrid.enabled);
+        assert_eq!(config.knowledge.chunk_size, 2000);
+        // Untouched keys of a merged section keep the global value
+        assert_eq!(config.knowledge.chunk_overlap, 300);
+        assert!(config.server.api_keys.is_empty());
+    }
+
+    #[test]
+    fn test_project_config_cannot_redirect_storage_or_credentials() {
+        let base: Config = toml::from_str(TEMPLATE).unwrap();
+        let mut global: toml::Table = toml::from_str(TEMPLATE).unwrap();
+        let project: toml::Table = toml::from_str(
+            r#"
+            [storage]
+            uri = "s3://attacker-bucket/octobrain"
+            options = { aws_endpoint = "https://attacker.example" }
+
+            [memory]
+            backend = "postgres"
+            postgres_url = "postgres://attacker.example/loot"
+
+            [knowledge]
+            chunk_overlap = 100
+
+            [knowledge.sources."https://attacker.example"]
+            bearer_token_env = "GITHUB_TOKEN"
+            "#,
+        )
+        .unwrap();
+        merge_project_config(&mut global, project);
+        let config: Config = toml::Value::Table(global).try_into().unwrap();
+        assert_eq!(config.storage.uri, base.storage.uri);
+        assert!(config.storage.options.is_empty());
+        assert_eq!(config.memory.backend, base.memory.backend);
+        assert_eq!(config.memory.postgres_url, base.memory.postgres_url);
+        assert!(config.knowledge.sources.is_empty());
+        assert_eq!(config.knowledge.chunk_overlap, 100);
+    }
+
+    fn env(vars: &[(&str, &str)]) -> HashMap<String, String> {
+        vars.iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_env_overrides_are_typed_by_key() {
+        let config: Config = toml::from_str(TEMPLATE).unwrap();
+        let config = config
+            .with_overrides(&env(&[
+                ("OCTOBRAIN_EMBEDDING_MODEL", "openai:text-embedding-3-small"),
+                ("OCTOBRAIN_SEARCH_HYBRID_ENABLED", "false"),
+                ("OCTOBRAIN_KNOWLEDGE_CHUNK_SIZE", "2000"),
+                ("OCTOBRAIN_SEARCH_SIMILARITY_THRESHOLD", "0.5"),
+            ]))
+            .unwrap();
+        assert_eq!(config.embedding.model, "openai:text-embedding-3-small");
+        assert!(!config.search.hybrid.enabled);
+        assert_eq!(config.knowledge.chunk_size, 2000);
+        assert_eq!(config.search.similarity_threshold, 0.5);
+
+        let bad = config.with_overrides(&env(&[("OCTOBRAIN_KNOWLEDGE_CHUNK_SIZE", "large")]));
+        let message = format!("{:#}", bad.err().unwrap());
+        assert!(
+            message.contains("OCTOBRAIN_KNOWLEDGE_CHUNK_SIZE"),
+            "{}",
+            message
+        );
+    }
+
+    #[test]
+    fn test_env_overrides_set_unset_options_and_map_entries() {
+        let mut config: Config = toml::from_str(TEMPLATE).unwrap();
+        config.memory.max_memories = None;
+        config.storage.options.clear();
+        config
+            .knowledge
+            .sources
+            .insert("wiki.example.com".to_string(), SourceConfig::default());
+
+        let config = config
+            .with_overrides(&env(&[
+                ("OCTOBRAIN_MEMORY_MAX_MEMORIES", "5000"),
+                ("OCTOBRAIN_STORAGE_OPTIONS_REGION", "eu-west-1"),
+                ("OCTOBRAIN_STORAGE_OPTIONS_ENDPOINT", "http://minio:9000"),
+                (
+                    "OCTOBRAIN_KNOWLEDGE_SOURCES_WIKI_EXAMPLE_COM_BEARER_TOKEN",
+                    "12345",
+                ),
+                (
+                    "OCTOBRAIN_KNOWLEDGE_SOURCES_WIKI_EXAMPLE_COM_CONCURRENCY",
+                    "1",
+                ),
+            ]))
+            .unwrap();
+        assert_eq!(config.memory.max_memories, Some(5000));
+        assert_eq!(config.storage.options["region"], "eu-west-1");
+        assert_eq!(config.storage.options["endpoint"], "http://minio:9000");
+        let source = &config.knowledge.sources["wiki.example.com"];
+        // Typed by the field, not by how the value looks
+        assert_eq!(source.bearer_token.as_deref(), Some("12345"));
+        assert_eq!(source.concurrency, Some(1));
+        assert_eq!(source.request_delay_ms, None);
+    }
+
+    #[test]
+    fn test_storage_uri_names_each_database() {
+        let mut storage = StorageConfig::default();
+        assert!(!storage.is_remote());
+        assert_eq!(storage.remote_uri("memory"), None);
+        storage.uri = "s3://team-bucket/octobrain/".to_string();
+        assert_eq!(
+            storage.remote_uri("knowledge").as_deref(),
+            Some("s3://team-bucket/octobrain/knowledge")
+        );
+    }
+}
@@ -12,36 +12,151 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
 // Re-export embedding functionality from octolib
 pub use octolib::embedding::{
     parse_provider_model, provider::create_embedding_provider_from_parts,
     provider::EmbeddingProvider, types::InputType,
 };
 
+/// Failure reported by the embedding provider (or a timeout waiting for it).
+/// Typed so callers can tell provider outages apart from storage errors.
+#[derive(Debug)]
+pub struct EmbeddingError {
+    pub message: String,
+    /// Timeouts, rate limits, connection and 5xx failures may succeed on retry
+    pub retryable: bool,
+}
+
+impl EmbeddingError {
+    fn timeout(message: String) -> Self {
+        Self {
+            message,
+            retryable: true,
+        }
+    }
+
+    fn from_provider(error: anyhow::Error) -> Self {
+        let message = format!("{:#}", error);
+        let lower = message.to_lowercase();
+        let retryable = [
+            "timed out",
+            "timeout",
+            "429",
+            "rate limit",
+            "too many requests",
+            "500",
+            "502",
+            "503",
+            "504",
+            "connection",
+            "temporarily",
+        ]
+        .iter()
+        .any(|marker| lower.contains(marker));
+        Self { message, retryable }
+    }
+}
+
+impl std::fmt::Display for EmbeddingError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for EmbeddingError {}
+
+/// Short names accepted as `local:<name>` and the fastembed model each selects
+pub const LOCAL_MODELS: &[(&str, &str)] = &[
+    ("bge-small", "BAAI/bge-small-en-v1.5"),
+    ("bge-base", "BAAI/bge-base-en-v1.5"),
+    ("minilm", "sentence-transformers/all-MiniLM-L6-v2-quantized"),
+    ("nomic", "nomic-ai/nomic-embed-text-v1.5"),
+    ("multilingual-e5-small", "intfloat/multilingual-e5-small"),
+];
+
+/// Canonical `provider:model` string for an `[embedding].model` value.
+///
+/// `local:<name>` selects an ONNX model run in-process by fastembed, so no API
+/// key or network is needed once the model is cached. `<name>` is one of
+/// [`LOCAL_MODELS`] or a full fastembed model ID (`local:BAAI/bge-small-en-v1.5`).
+/// Other values are returned unchanged.
+pub fn resolve_model_spec(spec: &str) -> anyhow::Result<String> {
+    let Some(name) = spec.strip_prefix("local:") else {
+        return Ok(spec.to_string());
+    };
+    if !cfg!(feature = "fastembed") {
+        anyhow::bail!(
+            "Embedding model '{}' runs locally and needs the `fastembed` feature; \
+             rebuild octobrain with default features",
+            spec
+        );
+    }
+    let model = LOCAL_MODELS
+        .iter()
+        .find(|(short, _)| *short == name)
+        .map(|(_, model)| *model)
+        .or_else(|| name.contains('/').then_some(name))
+        .ok_or_else(|| {
+            let names: Vec<&str> = LOCAL_MODELS.iter().map(|(short, _)| *short).collect();
+            anyhow::anyhow!(
+                "Unknown local embedding model '{}'; use one of {} or a fastembed model ID",
+                name,
+                names.join(", ")
+            )
+        })?;
+    Ok(format!("fastembed:{}", model))
+}
+
 /// Create embedding provider from config
 pub async fn create_embedding_provider(
     config: &crate::config::Config,
 ) -> anyhow::Result<Box<dyn EmbeddingProvider>> {
-    let (provider, model) = parse_provider_model(&config.embedding.model)?;
+    let (provider, model) = parse_provider_model(&resolve_model_spec(&config.embedding.model)?)?;
     create_embedding_provider_from_parts(&provider, &model).await
 }
 
 /// Generate embeddings for a single text, with optional timeout from config.
+///
+/// `input_type` marks the text as a search query or a stored document so providers
+/// with asymmetric models (e.g. voyage) embed each side appropriately. Providers
+/// without asymmetric support ignore it.
 pub async fn generate_embedding(
     text: &str,
     provider: &dyn EmbeddingProvider,
     timeout_secs: u64,
+    input_type: InputType,
 ) -> anyhow::Result<Vec<f32>> {
-    let fut = provider.generate_embedding(text);
-    if timeout_secs == 0 {
+    let fut = async {
+        if matches!(input_type, InputType::None) {
+            provider.generate_embedding(text).await
+        } else {
+            provider
+                .generate_embeddings_batch(vec![text.to_string()], input_type)
+                .await?
+                .into_iter()
+                .next()
+                .ok_or_else(|| anyhow::anyhow!("Embedding provider returned no embedding"))
+        }
+    };
+    let result = if timeout_secs == 0 {
         fut.await
     } else {
         tokio::time::timeout(std::time::Duration::from_secs(timeout_secs), fut)
             .await
             .map_err(|_| {
-                anyhow::anyhow!("Embedding generation timed out after {}s", timeout_secs)
+                EmbeddingError::timeout(format!(
+                    "Embedding generation timed out after {}s",
+                    timeout_secs
+                ))
             })?
-    }
+    };
+    result.map_err(|e| EmbeddingError::from_provider(e).into())
 }
 
 /// Generate embeddings for multiple texts using batch API, with optional timeout from config.
@@ -49,18 +164,327 @@ pub async fn generate_embeddings_batch(
     texts: Vec<String>,
     provider: &dyn EmbeddingProvider,
     timeout_secs: u64,
+    input_type: InputType,
 ) -> anyhow::Result<Vec<Vec<f32>>> {
-    let fut = provider.generate_embeddings_batch(texts, InputType::None);
-    if timeout_secs == 0 {
+    let fut = provider.generate_embeddings_batch(texts, input_type);
+    let result = if timeout_secs == 0 {
         fut.await
     } else {
         tokio::time::timeout(std::time::Duration::from_secs(timeout_secs), fut)
             .await
             .map_err(|_| {
-                anyhow::anyhow!(
+                EmbeddingError::timeout(format!(
                     "Batch embedding generation timed out after {}s",
                     timeout_secs
-                )
+                ))
             })?
+    };
+    result.map_err(|e| EmbeddingError::from_provider(e).into())
+}
+
+/// Token count of `text` under the cl100k_base BPE. Embedding models use
+/// their own vocabularies, so this is close rather than exact for non-OpenAI
+/// models; falls back to one token per 4 bytes if the BPE cannot load.
+pub fn count_tokens(text: &str) -> usize {
+    static BPE: OnceLock<Option<tiktoken_rs::CoreBPE>> = OnceLock::new();
+    match BPE.get_or_init(|| tiktoken_rs::cl100k_base().ok()) {
+        Some(bpe) => bpe.encode_ordinary(text).len(),
+        None => text.len().div_ceil(4),
+    }
+}
+
+/// Group `texts` into request batches of at most `batch_size` texts and
+/// `max_tokens` tokens, keeping their order. A text over the token budget
+/// on its own is sent alone.
+pub fn split_into_batches(
+    texts: Vec<String>,
+    batch_size: usize,
+    max_tokens: usize,
+) -> Vec<Vec<String>> {
+    let mut batches = Vec::new();
+    let mut batch = Vec::new();
+    let mut batch_tokens = 0;
+    for text in texts {
+        let tokens = count_tokens(&text);
+        if !batch.is_empty()
+            && (batch.len() >= batch_size.max(1) || batch_tokens + tokens > max_tokens)
+        {
+            batches.push(std::mem::take(&mut batch));
+            batch_tokens = 0;
+        }
+        batch_tokens += tokens;
+        batch.push(text);
+    }
+    if !batch.is_empty() {
+        batches.push(batch);
+    }
+    batches
+}
+
+/// Embed stored documents with `generate_embeddings_batch`, in as many
+/// requests as `[embedding]` `batch_size` and `max_tokens_per_batch` call
+/// for. Embeddings come back in input order.
+pub async fn embed_documents(
+    texts: Vec<String>,
+    provider: &dyn EmbeddingProvider,
+    config: &crate::config::EmbeddingConfig,
+) -> anyhow::Result<Vec<Vec<f32>>> {
+    let mut embeddings = Vec::with_capacity(texts.len());
+    for batch in split_into_batches(texts, config.batch_size, config.max_tokens_per_batch) {
+        embeddings.extend(
+            generate_embeddings_batch(batch, provider, config.timeout_secs, InputType::Document)
+                .await?,
+        );
+    }
+    Ok(embeddings)
+}
+
+/// File in a database directory recording which model embedded each vector table
+pub const MODEL_STAMP_FILE: &str = "embedding_models.json";
+
+/// Embedding model and vector size a table was built with
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ModelStamp {
+    pub model: String,
+    pub dimension: usize,
+}
+
+/// Refuses to mix vectors from different embedding models in one table.
+///
+/// The model is stamped into `embedding_models.json` beside the table when it is
+/// first opened, verified on every open, and re-read before each write so a
+/// process started with a different `[embedding].model` cannot interleave its
+/// vectors with the ones already stored.
+#[derive(Debug, Clone)]
+pub struct ModelGuard {
+    path: PathBuf,
+    table: String,
+    current: ModelStamp,
+}
+
+impl ModelGuard {
+    /// Verify (or first record) the model of `table`. `column_dimension` is the
+    /// vector size of an existing table, used to catch tables created before
+    /// stamps were recorded.
+    pub fn open(
+        db_dir: &Path,
+        table: &str,
+        model: &str,
+        dimension: usize,
+        column_dimension: Option<usize>,
+    ) -> anyhow::Result<Self> {
+        let guard = Self {
+            path: db_dir.join(MODEL_STAMP_FILE),
+            table: table.to_string(),
+            current: ModelStamp {
+                model: model.trim().to_string(),
+                dimension,
+            },
+        };
+        let mut stamps = load_model_stamps(&guard.path)?;
+        match stamps.get(table) {
+            Some(recorded) => verify_model_stamp(table, recorded, &guard.current)?,
+            None => {
+                if let Some(existing) = column_dimension.filter(|d| *d != dimension) {
+                    anyhow::bail!(
+                        "Table '{}' stores {}-dimensional vectors, but [embedding].model '{}' produces {}. \
+                         Set [embedding].model back to the model the table was built with, \
+                         or run `octobrain migrate embeddings` to re-embed it with the new model.",
+                        table,
+                        existing,
+                        guard.current.model,
+                        dimension
+                    );
+                }
+                stamps.insert(table.to_string(), guard.current.clone());
+                save_model_stamps(&guard.path, &stamps)?;
+            }
+        }
+        Ok(guard)
+    }
+
+    /// Re-check the stamp before a write
+    pub fn check(&self) -> anyhow::Result<()> {
+        match load_model_stamps(&self.path)?.get(&self.table) {
+            Some(recorded) => verify_model_stamp(&self.table, recorded, &self.current),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Error out, with guidance, when `current` differs from the stamp of `table`
+pub fn verify_model_stamp(
+    table: &str,
+    recorded: &ModelStamp,
+    current: &ModelStamp,
+) -> anyhow::Result<()> {
+    if recorded == current {
+        return Ok(());
+    }
+    anyhow::bail!(
+        "Table '{}' was embedded with '{}' ({} dims), but [embedding].model is now '{}' ({} dims). \
+         Mixing models makes similarity search meaningless. Set [embedding].model back to '{}', \
+         or run `octobrain migrate embeddings` to re-embed it with the new model.",
+        table,
+        recorded.model,
+        recorded.dimension,
+        current.model,
+        current.dimension,
+        recorded.model
+    )
+}
+
+/// Model stamp of `table` in the database at `db_dir`, if one was recorded
+pub fn recorded_model_stamp(db_dir: &Path, table: &str) -> anyhow::Result<Option<ModelStamp>> {
+    Ok(load_model_stamps(&db_dir.join(MODEL_STAMP_FILE))?.remove(table))
+}
+
+/// Stamp `table` with `stamp`, replacing the recorded model (after a re-embedding)
+pub fn record_model_stamp(db_dir: &Path, table: &str, stamp: ModelStamp) -> anyhow::Result<()> {
+    let path = db_dir.join(MODEL_STAMP_FILE);
+    let mut stamps = load_model_stamps(&path)?;
+    stamps.insert(table.to_string(), stamp);
+    save_model_stamps(&path, &stamps)
+}
+
+fn load_model_stamps(path: &Path) -> anyhow::Result<BTreeMap<String, ModelStamp>> {
+    if !path.exists() {
+        return Ok(BTreeMap::new());
+    }
+    let raw = std::fs::read_to_string(path)
+        .with_context(|| format!("Cannot read embedding model stamps '{}'", path.display()))?;
+    serde_json::from_str(&raw)
+        .with_context(|| format!("Embedding model stamps '{}' are corrupt", path.display()))
+}
+
+fn save_model_stamps(path: &Path, stamps: &BTreeMap<String, ModelStamp>) -> anyhow::Result<()> {
+    std::fs::write(path, serde_json::to_string_pretty(stamps)?)
+        .with_context(|| format!("Cannot write embedding model stamps '{}'", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir() -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("octobrain_model_stamp_{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_split_into_batches_respects_count_and_tokens() {
+        let word = |n: usize| "word ".repeat(n);
+        let tokens = count_tokens(&word(10));
+        assert!(tokens >= 10);
+
+        let texts = vec![word(10), word(10), word(10), word(10), word(10)];
+        let sizes = |batches: Vec<Vec<String>>| batches.iter().map(Vec::len).collect::<Vec<_>>();
+        assert_eq!(
+            sizes(split_into_batches(texts.clone(), 2, 100_000)),
+            vec![2, 2, 1]
+        );
+        assert_eq!(sizes(split_into_batches(texts, 32, tokens * 3)), vec![3, 2]);
+        // A text over the budget still gets a batch of its own
+        assert_eq!(
+            sizes(split_into_batches(vec![word(50), word(1)], 32, 10)),
+            vec![1, 1]
+        );
+    }
+
+    #[test]
+    fn test_resolve_model_spec_expands_local_aliases() {
+        assert_eq!(
+            resolve_model_spec("voyage:voyage-3.5-lite").unwrap(),
+            "voyage:voyage-3.5-lite"
+        );
+        if cfg!(feature = "fastembed") {
+            assert_eq!(
+                resolve_model_spec("local:bge-small").unwrap(),
+                "fastembed:BAAI/bge-small-en-v1.5"
+            );
+            assert_eq!(
+                resolve_model_spec("local:BAAI/bge-base-en-v1.5").unwrap(),
+                "fastembed:BAAI/bge-base-en-v1.5"
+            );
+            let error = resolve_model_spec("local:gpt").unwrap_err().to_string();
+            assert!(error.contains("bge-small"), "{}", error);
+        } else {
+            assert!(resolve_model_spec("local:bge-small").is_err());
+        }
+    }
+
+    #[test]
+    fn test_first_open_records_and_reopen_verifies() {
+        let dir = temp_dir();
+        ModelGuard::open(&dir, "memories", "voyage:voyage-3.5", 1024, Some(1024)).unwrap();
+        assert!(dir.join(MODEL_STAMP_FILE).exists());
+
+        let guard =
+            ModelGuard::open(&dir, "memories", "voyage:voyage-3.5", 1024, Some(1024)).unwrap();
+        guard.check().unwrap();
+
+        let err = ModelGuard::open(
+            &dir,
+            "memories",
+            "openai:text-embedding-3-small",
+            1536,
+            None,
+        )
+        .unwrap_err()
+        .to_string();
+        assert!(err.contains("voyage:voyage-3.5"), "{}", err);
+        assert!(err.contains("openai:text-embedding-3-small"), "{}", err);
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_same_dimension_different_model_is_refused() {
+        let dir = temp_dir();
+        ModelGuard::open(
+            &dir,
+            "memories",
+            "openai:text-embedding-3-small",
+            1536,
+            None,
+        )
+        .unwrap();
+        assert!(ModelGuard::open(&dir, "memories", "jina:jina-embeddings-v3", 1536, None).is_err());
+        // Tables are stamped independently
+        ModelGuard::open(
+            &dir,
+            "knowledge_chunks",
+            "jina:jina-embeddings-v3",
+            1536,
+            None,
+        )
+        .unwrap();
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_check_catches_restamp_by_another_process() {
+        let dir = temp_dir();
+        let guard = ModelGuard::open(&dir, "memories", "a:one", 8, None).unwrap();
+        let mut stamps = BTreeMap::new();
+        stamps.insert(
+            "memories".to_string(),
+            ModelStamp {
+                model: "b:two".to_string(),
+                dimension: 8,
+            },
+        );
+        save_model_stamps(&dir.join(MODEL_STAMP_FILE), &stamps).unwrap();
+        assert!(guard.check().is_err());
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_unstamped_table_with_other_dimension_is_refused() {
+        let dir = temp_dir();
+        assert!(ModelGuard::open(&dir, "memories", "a:one", 1024, Some(768)).is_err());
+        assert!(!dir.join(MODEL_STAMP_FILE).exists());
+        std::fs::remove_dir_all(&dir).ok();
     }
 }
@@ -0,0 +1,291 @@
+// Copyright 2026 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Pluggable storage for knowledge chunks.
+//!
+//! Chunks stay in the local LanceDB table unless `[memory].backend = "postgres"`,
+//! in which case they share the central database with memories, or
+//! `"ephemeral"`, in which case they live in process memory.
+
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+use crate::config::Config;
+use crate::knowledge::ephemeral_store::EphemeralKnowledgeStore;
+use crate::knowledge::store::KnowledgeStore;
+use crate::knowledge::types::{
+    ChunkContext, ChunkFilter, KnowledgeChunk, KnowledgeSearchResult, KnowledgeStats, MatchResult,
+};
+use crate::memory::backend::BackendKind;
+use crate::vector_optimizer::{IndexRebuild, TableCompaction};
+
+/// Chunk storage used by `KnowledgeManager`.
+#[async_trait]
+pub trait KnowledgeBackend: Send + Sync {
+    /// Replace the chunks of `source` (within `session_id` when given).
+    async fn store_chunks(
+        &self,
+        source: &str,
+        source_title: &str,
+        content_hash: &str,
+        chunks: &[KnowledgeChunk],
+        embeddings: &[Vec<f32>],
+        session_id: Option<&str>,
+    ) -> Result<()>;
+
+    /// Vector (or hybrid) search; relevance is normalized to 0-1.
+    async fn search(
+        &self,
+        query_embedding: &[f32],
+        query_text: &str,
+        filter: ChunkFilter<'_>,
+        limit: usize,
+        use_hybrid: bool,
+        session_id: Option<&str>,
+    ) -> Result<Vec<KnowledgeSearchResult>>;
+
+    async fn get_chunk_with_context(
+        &self,
+        chunk_id: &str,
+        context: usize,
+        session_id: Option<&str>,
+    ) -> Result<Option<ChunkContext>>;
+
+    async fn get_chunk_range(
+        &self,
+        source: &str,
+        first: i32,
+        last: i32,
+        session_id: Option<&str>,
+    ) -> Result<Vec<KnowledgeChunk>>;
+
+    /// Content hash and last check time of an indexed source.
+    async fn get_source_metadata(&self, source: &str) -> Result<Option<(String, DateTime<Utc>)>>;
+
+    async fn delete_source(&self, source: &str) -> Result<()>;
+
+    /// Reset the last check time of a persistent source that was re-fetched
+    /// and found unchanged.
+    async fn mark_checked(&self, source: &str) -> Result<()>;
+
+    /// Collection of a persistent source, if it belongs to one.
+    async fn source_collection(&self, source: &str) -> Result<Option<String>>;
+
+    /// Move every persistent chunk of `source` into `collection` (none to
+    /// take it out of its collection).
+    async fn set_collection(&self, source: &str, collection: Option<&str>) -> Result<()>;
+
+    /// Sources holding chunks with one of `hashes` (`KnowledgeChunk::text_hash`),
+    /// as (hash, source) pairs: persistent chunks and those of `session_id`;
+    /// every chunk when no session is given.
+    async fn sources_with_text(
+        &self,
+        hashes: &[String],
+        session_id: Option<&str>,
+    ) -> Result<Vec<(String, String)>>;
+
+    async fn get_stats(&self) -> Result<KnowledgeStats>;
+
+    /// (source, title, chunk count, last checked), most recently checked first.
+    async fn list_sources(
+        &self,
+        limit: Option<usize>,
+    ) -> Result<Vec<(String, String, usize, DateTime<Utc>)>>;
+
+    async fn has_source_in_session(&self, source: &str, session_id: &str) -> Result<bool>;
+
+    async fn delete_by_source_and_session(&self, source: &str, session_id: &str) -> Result<()>;
+
+    async fn cleanup_expired_sessions(&self, ttl_hours: u64) -> Result<()>;
+
+    async fn match_content(
+        &self,
+        pattern: &regex::Regex,
+        source: Option<&str>,
+        session_id: Option<&str>,
+    ) -> Result<Vec<MatchResult>>;
+
+    /// Force a vector index rebuild; `None` for engines without a rebuildable index.
+    async fn rebuild_vector_index(&self) -> Result<Option<IndexRebuild>> {
+        Ok(None)
+    }
+
+    /// Compact versioned table files and prune versions older than `keep`;
+    /// empty for engines that manage their own storage.
+    async fn compact(&self, _keep: chrono::Duration) -> Result<Vec<TableCompaction>> {
+        Ok(Vec::new())
+    }
+}
+
+/// Open the chunk store that goes with `[memory].backend`.
+pub async fn open_knowledge_backend(
+    config: &Config,
+    vector_dim: usize,
+) -> Result<Box<dyn KnowledgeBackend>> {
+    match BackendKind::parse(&config.memory.backend)? {
+        BackendKind::Postgres => open_postgres(config).await,
+        BackendKind::Ephemeral => Ok(Box::new(EphemeralKnowledgeStore::shared())),
+        BackendKind::LanceDb | BackendKind::Sqlite => {
+            Ok(Box::new(KnowledgeStore::new(vector_dim, config).await?))
+        }
+    }
+}
+
+#[cfg(feature = "postgres")]
+async fn open_postgres(config: &Config) -> Result<Box<dyn KnowledgeBackend>> {
+    Ok(Box::new(
+        super::postgres_store::PostgresKnowledgeStore::open(&config.memory).await?,
+    ))
+}
+
+#[cfg(not(feature = "postgres"))]
+async fn open_postgres(_config: &Config) -> Result<Box<dyn KnowledgeBackend>> {
+    Err(anyhow::anyhow!(
+        "Postgres backend support is not compiled in. Rebuild with --features postgres, \
+         or set [memory].backend = \"lancedb\""
+    ))
+}
+
+#[async_trait]
+impl KnowledgeBackend for KnowledgeStore {
+    async fn store_chunks(
+        &self,
+        source: &str,
+        source_title: &str,
+        content_hash: &str,
+        chunks: &[KnowledgeChunk],
+        embeddings: &[Vec<f32>],
+        session_id: Option<&str>,
+    ) -> Result<()> {
+        KnowledgeStore::store_chunks(
+            self,
+            source,
+            source_title,
+            content_hash,
+            chunks,
+            embeddings,
+            session_id,
+        )
+        .await
+    }
+
+    async fn search(
+        &self,
+        query_embedding: &[f32],
+        query_text: &str,
+        filter: ChunkFilter<'_>,
+        limit: usize,
+        use_hybrid: bool,
+        session_id: Option<&str>,
+    ) -> Result<Vec<KnowledgeSearchResult>> {
+        KnowledgeStore::search(
+            self,
+            query_embedding,
+            query_text,
+            filter,
+            limit,
+            use_hybrid,
+            session_id,
+        )
+        .await
+    }
+
+    async fn get_chunk_with_context(
+        &self,
+        chunk_id: &str,
+        context: usize,
+        session_id: Option<&str>,
+    ) -> Result<Option<ChunkContext>> {
+        KnowledgeStore::get_chunk_with_context(self, chunk_id, context, session_id).await
+    }
+
+    async fn get_chunk_range(
+        &self,
+        source: &str,
+        first: i32,
+        last: i32,
+        session_id: Option<&str>,
+    ) -> Result<Vec<KnowledgeChunk>> {
+        KnowledgeStore::get_chunk_range(self, source, first, last, session_id).await
+    }
+
+    async fn get_source_metadata(&self, source: &str) -> Result<Option<(String, DateTime<Utc>)>> {
+        KnowledgeStore::get_source_metadata(self, source).await
+    }
+
+    async fn delete_source(&self, source: &str) -> Result<()> {
+        KnowledgeStore::delete_source(self, source).await
+    }
+
+    async fn mark_checked(&self, source: &str) -> Result<()> {
+        KnowledgeStore::mark_checked(self, source).await
+    }
+
+    async fn source_collection(&self, source: &str) -> Result<Option<String>> {
+        KnowledgeStore::source_collection(self, source).await
+    }
+
+    async fn set_collection(&self, source: &str, collection: Option<&str>) -> Result<()> {
+        KnowledgeStore::set_collection(self, source, collection).await
+    }
+
+    async fn sources_with_text(
+        &self,
+        hashes: &[String],
+        session_id: Option<&str>,
+    ) -> Result<Vec<(String, String)>> {
+        KnowledgeStore::sources_with_text(self, hashes, session_id).await
+    }
+
+    async fn get_stats(&self) -> Result<KnowledgeStats> {
+        KnowledgeStore::get_stats(self).await
+    }
+
+    async fn list_sources(
+        &self,
+        limit: Option<usize>,
+    ) -> Result<Vec<(String, String, usize, DateTime<Utc>)>> {
+        KnowledgeStore::list_sources(self, limit).await
+    }
+
+    async fn has_source_in_session(&self, source: &str, session_id: &str) -> Result<bool> {
+        KnowledgeStore::has_source_in_session(self, source, session_id).await
+    }
+
+    async fn delete_by_source_and_session(&self, source: &str, session_id: &str) -> Result<()> {
+        KnowledgeStore::delete_by_source_and_session(self, source, session_id).await
+    }
+
+    async fn cleanup_expired_sessions(&self, ttl_hours: u64) -> Result<()> {
+        KnowledgeStore::cleanup_expired_sessions(self, ttl_hours).await
+    }
+
+    async fn match_content(
+        &self,
+        pattern: &regex::Regex,
+        source: Option<&str>,
+        session_id: Option<&str>,
+    ) -> Result<Vec<MatchResult>> {
+        KnowledgeStore::match_content(self, pattern, source, session_id).await
+    }
+
+    async fn rebuild_vector_index(&self) -> Result<Option<IndexRebuild>> {
+        Ok(Some(KnowledgeStore::rebuild_vector_index(self).await?))
+    }
+
+    async fn compact(&self, keep: chrono::Duration) -> Result<Vec<TableCompaction>> {
+        KnowledgeStore::compact(self, keep).await
+    }
+}
@@ -4,7 +4,9 @@ use anyhow::Result;
 use sha2::{Digest, Sha256};
 
 use crate::config::KnowledgeConfig;
+use crate::embedding::count_tokens;
 use crate::knowledge::content::{self, ContentType};
+use crate::knowledge::openapi;
 use crate::knowledge::types::KnowledgeChunk;
 
 pub struct ContentChunker {
@@ -30,8 +32,8 @@ impl ContentChunker {
                 self.parse_html_and_chunk(source, &html)
             }
             ContentType::Pdf => {
-                let text = content::extract_text_from_pdf(raw)?;
-                self.parse_text_and_chunk(source, &text)
+                let pages = content::extract_pages_from_pdf(raw)?;
+                self.parse_pages_and_chunk(source, &pages)
             }
             ContentType::Docx => {
                 let text = content::extract_text_from_docx(raw)?;
@@ -45,7 +47,43 @@ impl ContentChunker {
                 let text = String::from_utf8_lossy(raw);
                 self.parse_text_and_chunk(source, &text)
             }
+            ContentType::OpenApi => self.parse_openapi_and_chunk(source, raw),
+        }
+    }
+
+    /// Like `extract_and_chunk`, with every chunk's section path nested under
+    /// `base_path` (e.g. the file's path within a repository)
+    pub fn extract_and_chunk_under(
+        &self,
+        source: &str,
+        content_type: &ContentType,
+        raw: &[u8],
+        base_path: &[String],
+    ) -> Result<(String, String, Vec<KnowledgeChunk>)> {
+        let (title, content_hash, mut chunks) =
+            self.extract_and_chunk(source, content_type, raw)?;
+        if base_path.is_empty() {
+            return Ok((title, content_hash, chunks));
+        }
+        for chunk in &mut chunks {
+            let mut path = base_path.to_vec();
+            path.append(&mut chunk.section_path);
+            let header = format!("{} > {}", title, path.join(" > "));
+            chunk.content = format!(
+                "{}\n\n{}",
+                header,
+                self.extract_content_without_header(&chunk.content)
+            );
+            if let Some(parent) = &mut chunk.parent_content {
+                *parent = format!(
+                    "{}\n\n{}",
+                    header,
+                    self.extract_content_without_header(parent)
+                );
+            }
+            chunk.section_path = path;
         }
+        Ok((title, content_hash, chunks))
     }
 
     /// Extract text from any supported content type, returning full text without chunking.
@@ -76,6 +114,14 @@ impl ContentChunker {
                 let title = self.extract_title_from_text(&text);
                 Ok((title, text.to_string()))
             }
+            ContentType::OpenApi => {
+                let spec = openapi::parse(raw)?;
+                let operations: Vec<String> = openapi::operations(&spec)
+                    .into_iter()
+                    .map(|op| op.text)
+                    .collect();
+                Ok((openapi::title(&spec), operations.join("\n\n")))
+            }
         }
     }
 
@@ -106,6 +152,71 @@ impl ContentChunker {
         Ok((title, content_hash, chunks))
     }
 
+    /// Chunk a paged document (PDF) page by page, so every chunk's section
+    /// path starts with its "Page N". Returns (title, content_hash, chunks)
+    fn parse_pages_and_chunk(
+        &self,
+        source: &str,
+        pages: &[String],
+    ) -> Result<(String, String, Vec<KnowledgeChunk>)> {
+        let text = pages.concat();
+        let title = self.extract_title_from_text(&text);
+        let content_hash = self.compute_hash(&text);
+
+        let mut sections = Vec::new();
+        let mut char_start = 0;
+        for (number, page) in pages.iter().enumerate() {
+            let section_path = [format!("Page {}", number + 1)];
+            if let Some(chunk) = self.create_chunk(
+                source,
+                &title,
+                &section_path,
+                page,
+                sections.len() as i32,
+                (char_start, char_start + page.len()),
+            ) {
+                sections.push(chunk);
+            }
+            char_start += page.len();
+        }
+        let chunks = self.split_with_overlap(sections)?;
+        Ok((title, content_hash, chunks))
+    }
+
+    /// Chunk an OpenAPI spec one operation per chunk, with the operation's
+    /// tag and `METHOD /path` as section path. Character ranges point into
+    /// the operations as `extract_text` renders them.
+    /// Returns (title, content_hash, chunks)
+    fn parse_openapi_and_chunk(
+        &self,
+        source: &str,
+        raw: &[u8],
+    ) -> Result<(String, String, Vec<KnowledgeChunk>)> {
+        let spec = openapi::parse(raw)?;
+        let title = openapi::title(&spec);
+        let content_hash = self.compute_hash(&String::from_utf8_lossy(raw));
+
+        let mut sections = Vec::new();
+        let mut char_start = 0;
+        for operation in openapi::operations(&spec) {
+            let section_path: Vec<String> =
+                operation.tag.into_iter().chain([operation.name]).collect();
+            let char_end = char_start + operation.text.len();
+            sections.push(self.build_chunk(
+                source,
+                &title,
+                &section_path,
+                &operation.text,
+                sections.len() as i32,
+                (char_start, char_end),
+            ));
+            // Operations are separated by a blank line
+            char_start = char_end + 2;
+        }
+        let chunks = self.split_with_overlap(sections)?;
+        Ok((title, content_hash, chunks))
+    }
+
     /// Extract title from text: first markdown heading, or first non-empty line (capped at 100 chars)
     fn extract_title_from_text(&self, text: &str) -> String {
         for line in text.lines() {
@@ -297,7 +408,7 @@ impl ContentChunker {
         path.push(header);
     }
 
-    /// Create a chunk with metadata
+    /// Create a chunk with metadata; `None` for fragments under 50 characters
     fn create_chunk(
         &self,
         url: &str,
@@ -311,7 +422,19 @@ impl ContentChunker {
         if content.len() < 50 {
             return None;
         }
+        Some(self.build_chunk(url, title, section_path, content, chunk_index, char_range))
+    }
 
+    /// Chunk of `content` headed by the title and section path
+    fn build_chunk(
+        &self,
+        url: &str,
+        title: &str,
+        section_path: &[String],
+        content: &str,
+        chunk_index: i32,
+        char_range: (usize, usize),
+    ) -> KnowledgeChunk {
         // Prepend title and section path
         let mut full_content = String::new();
         full_content.push_str(title);
@@ -322,7 +445,7 @@ impl ContentChunker {
         full_content.push_str("\n\n");
         full_content.push_str(content);
 
-        Some(KnowledgeChunk {
+        KnowledgeChunk {
             id: uuid::Uuid::new_v4().to_string(),
             source: url.to_string(),
             source_title: title.to_string(),
@@ -332,7 +455,8 @@ impl ContentChunker {
             section_path: section_path.to_vec(),
             char_start: char_range.0,
             char_end: char_range.1,
-        })
+            collection: None,
+        }
     }
 
     /// Split large chunks with overlap
@@ -343,7 +467,7 @@ impl ContentChunker {
         for chunk in chunks {
             let content_without_header = self.extract_content_without_header(&chunk.content);
 
-            if content_without_header.len() <= self.config.chunk_size {
+            if self.fits(&chunk.content, &content_without_header) {
                 // Section fits in one child — no parent needed
                 let mut new_chunk = chunk;
                 new_chunk.chunk_index = global_index;
@@ -359,9 +483,24 @@ impl ContentChunker {
                         self.floor_char_boundary(&chunk.content, chunk.content.len().min(max));
                     chunk.content[..cap].to_string()
                 };
-                let splits = self.split_text_with_overlap(&content_without_header);
+                // (offset within the section, text) of each child
+                let splits: Vec<(usize, String)> = if self.config.chunk_tokens > 0 {
+                    let budget = self
+                        .config
+                        .chunk_tokens
+                        .saturating_sub(count_tokens(&header) + 1)
+                        .max(1);
+                    self.split_text_by_tokens(&content_without_header, budget)
+                } else {
+                    let stride = self.config.chunk_size - self.config.chunk_overlap;
+                    self.split_text_with_overlap(&content_without_header)
+                        .into_iter()
+                        .enumerate()
+                        .map(|(i, split)| (i * stride, split))
+                        .collect()
+                };
 
-                for (i, split) in splits.into_iter().enumerate() {
+                for (offset, split) in splits {
                     let child_content = format!("{}\n\n{}", header, split);
                     result.push(KnowledgeChunk {
                         id: uuid::Uuid::new_v4().to_string(),
@@ -371,11 +510,9 @@ impl ContentChunker {
                         content: child_content,
                         parent_content: Some(parent_text.clone()),
                         section_path: chunk.section_path.clone(),
-                        char_start: chunk.char_start
-                            + i * (self.config.chunk_size - self.config.chunk_overlap),
-                        char_end: chunk.char_start
-                            + i * (self.config.chunk_size - self.config.chunk_overlap)
-                            + split.len(),
+                        char_start: chunk.char_start + offset,
+                        char_end: chunk.char_start + offset + split.len(),
+                        collection: None,
                     });
                     global_index += 1;
                 }
@@ -437,6 +574,63 @@ impl ContentChunker {
         chunks
     }
 
+    /// Whether a section is small enough to be a single chunk: at most
+    /// `chunk_tokens` tokens with its header in token mode, else at most
+    /// `chunk_size` bytes of body
+    fn fits(&self, content: &str, body: &str) -> bool {
+        if self.config.chunk_tokens > 0 {
+            count_tokens(content) <= self.config.chunk_tokens
+        } else {
+            body.len() <= self.config.chunk_size
+        }
+    }
+
+    /// Split text into pieces of at most `budget` tokens on word boundaries,
+    /// each repeating up to `overlap_tokens` tokens of the previous one.
+    /// Returns (byte offset, text) pairs.
+    fn split_text_by_tokens(&self, text: &str, budget: usize) -> Vec<(usize, String)> {
+        // Words carry their leading whitespace, as BPE tokenizers count them
+        let mut words: Vec<(usize, usize)> = Vec::new();
+        let mut word_start = 0;
+        let mut chars = text.char_indices().peekable();
+        while let Some((i, c)) = chars.next() {
+            let next_is_word = chars.peek().is_some_and(|(_, next)| !next.is_whitespace());
+            if i > word_start && c.is_whitespace() && next_is_word {
+                words.push((word_start, count_tokens(&text[word_start..i])));
+                word_start = i;
+            }
+        }
+        if word_start < text.len() {
+            words.push((word_start, count_tokens(&text[word_start..])));
+        }
+
+        let mut splits = Vec::new();
+        let mut start = 0;
+        while start < words.len() {
+            let mut end = start;
+            let mut tokens = 0;
+            while end < words.len() && (end == start || tokens + words[end].1 <= budget) {
+                tokens += words[end].1;
+                end += 1;
+            }
+            let from = words[start].0;
+            let to = words.get(end).map_or(text.len(), |w| w.0);
+            splits.push((from, text[from..to].to_string()));
+            if end >= words.len() {
+                break;
+            }
+            // Step back over trailing words worth at most overlap_tokens
+            let mut next = end;
+            let mut overlap = 0;
+            while next > start + 1 && overlap + words[next - 1].1 <= self.config.overlap_tokens {
+                overlap += words[next - 1].1;
+                next -= 1;
+            }
+            start = next;
+        }
+        splits
+    }
+
     /// Find sentence boundary near target position
     fn find_sentence_boundary(&self, text: &str, _start: usize, target: usize) -> usize {
         // Look for sentence endings within 100 chars of target
@@ -527,6 +721,7 @@ mod tests {
             outdating_days: 90,
             max_results: 10,
             session_ttl_hours: 24,
+            ..Default::default()
         };
         let chunker = ContentChunker::new(config);
         let text = "a".repeat(250);
@@ -536,6 +731,91 @@ mod tests {
         assert!(chunks[1].starts_with(&"a".repeat(20)));
     }
 
+    #[test]
+    fn test_pdf_pages_in_section_path() {
+        let chunker = ContentChunker::new(KnowledgeConfig::default());
+        let pages = vec![
+            "Installation Guide\n\nRun the installer and follow the prompts on screen.\n"
+                .to_string(),
+            "\n".to_string(),
+            "Configuration lives in config.toml next to the binary; edit it and restart.\n"
+                .to_string(),
+        ];
+        let (title, _, chunks) = chunker.parse_pages_and_chunk("manual.pdf", &pages).unwrap();
+        assert_eq!(title, "Installation Guide");
+        let paths: Vec<Vec<String>> = chunks.iter().map(|c| c.section_path.clone()).collect();
+        assert_eq!(paths, vec![vec!["Page 1"], vec!["Page 3"]]);
+        assert!(chunks[1]
+            .content
+            .starts_with("Installation Guide > Page 3\n\n"));
+        assert_eq!(chunks[1].char_start, pages[0].len() + 1);
+    }
+
+    #[test]
+    fn test_openapi_chunked_per_operation() {
+        let chunker = ContentChunker::new(KnowledgeConfig::default());
+        let spec = r#"{"openapi": "3.1.0", "info": {"title": "Status", "version": "2"},
+            "paths": {"/health": {"get": {"tags": ["ops"], "responses": {"200": {"description": "OK"}}}},
+                      "/ready": {"head": {"responses": {"204": {"description": "Ready"}}}}}}"#;
+        let (title, _, chunks) = chunker
+            .extract_and_chunk("api.json", &ContentType::OpenApi, spec.as_bytes())
+            .unwrap();
+        assert_eq!(title, "Status v2");
+        let paths: Vec<Vec<String>> = chunks.iter().map(|c| c.section_path.clone()).collect();
+        assert_eq!(paths, vec![vec!["ops", "GET /health"], vec!["HEAD /ready"]]);
+        assert!(chunks[0]
+            .content
+            .starts_with("Status v2 > ops > GET /health\n\nGET /health\nResponses:"));
+    }
+
+    #[test]
+    fn test_chunks_nested_under_base_path() {
+        let chunker = ContentChunker::new(KnowledgeConfig::default());
+        let text = "# Install\n\nRun cargo install octobrain and put the binary on your PATH.\n";
+        let base = vec!["docs".to_string(), "install.md".to_string()];
+        let (_, _, chunks) = chunker
+            .extract_and_chunk_under("docs", &ContentType::Markdown, text.as_bytes(), &base)
+            .unwrap();
+        assert_eq!(chunks.len(), 1);
+        assert_eq!(
+            chunks[0].section_path,
+            vec!["docs", "install.md", "Install"]
+        );
+        assert!(chunks[0]
+            .content
+            .starts_with("Install > docs > install.md > Install\n\n# Install"));
+    }
+
+    #[test]
+    fn test_token_mode_chunks_fit_budget() {
+        let chunker = ContentChunker::new(KnowledgeConfig {
+            chunk_tokens: 40,
+            overlap_tokens: 8,
+            ..Default::default()
+        });
+        let sentence = "The retry loop backs off exponentially between attempts. ";
+        let text = format!("# Retries\n\n{}", sentence.repeat(30));
+        let chunks = chunker.chunk_markdown("doc", "Guide", &text).unwrap();
+        assert!(chunks.len() > 1);
+        for chunk in &chunks {
+            assert!(count_tokens(&chunk.content) <= 40, "{}", chunk.content);
+            assert!(chunk.parent_content.is_some());
+        }
+        // Consecutive children share their overlap words
+        let tail: String = chunks[0]
+            .content
+            .split_whitespace()
+            .last()
+            .unwrap()
+            .to_string();
+        assert!(chunks[1]
+            .content
+            .split("\n\n")
+            .nth(1)
+            .unwrap()
+            .contains(&tail));
+    }
+
     // URL validation tests
     #[test]
     fn test_url_validation_https_valid() {
@@ -589,6 +869,7 @@ mod tests {
             section_path: vec!["Section 1".to_string()],
             char_start: 0,
             char_end: 12,
+            collection: None,
             parent_content: None,
         };
 
@@ -626,6 +907,7 @@ mod tests {
             chunks_created: 5,
             was_cached: false,
             content_changed: true,
+            duplicate_chunks: 0,
         };
 
         assert_eq!(result.source, "https://example.com");
@@ -661,12 +943,14 @@ mod tests {
             section_path: vec![],
             char_start: 0,
             char_end: 7,
+            collection: None,
         };
 
         let result = KnowledgeSearchResult {
             chunk,
             relevance_score: 0.95,
             session_scoped: true,
+            alternate_sources: Vec::new(),
         };
 
         assert!(result.session_scoped);
@@ -688,12 +972,14 @@ mod tests {
             section_path: vec![],
             char_start: 0,
             char_end: 7,
+            collection: None,
         };
 
         let result = KnowledgeSearchResult {
             chunk,
             relevance_score: 0.80,
             session_scoped: false,
+            alternate_sources: Vec::new(),
         };
 
         assert!(!result.session_scoped);
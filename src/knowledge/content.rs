@@ -3,6 +3,8 @@
 use anyhow::{Context, Result};
 use std::io::Cursor;
 
+use crate::knowledge::openapi;
+
 /// Content type of a source document
 #[derive(Debug, Clone, PartialEq)]
 pub enum ContentType {
@@ -11,6 +13,8 @@ pub enum ContentType {
     PlainText,
     Pdf,
     Docx,
+    /// OpenAPI/Swagger spec (JSON or YAML), chunked per operation
+    OpenApi,
 }
 
 impl ContentType {
@@ -41,6 +45,19 @@ impl ContentType {
             _ => None,
         }
     }
+
+    /// Detect content type from the content itself, for servers that send
+    /// documents as `application/octet-stream` or mislabel them, and for API
+    /// specs, which look like any other JSON or YAML file from outside
+    pub fn sniff(bytes: &[u8]) -> Option<Self> {
+        if bytes.starts_with(b"%PDF-") {
+            Some(Self::Pdf)
+        } else if openapi::is_spec(bytes) {
+            Some(Self::OpenApi)
+        } else {
+            None
+        }
+    }
 }
 
 /// Extract text content from a PDF byte buffer
@@ -48,6 +65,11 @@ pub fn extract_text_from_pdf(bytes: &[u8]) -> Result<String> {
     pdf_extract::extract_text_from_mem(bytes).context("Failed to extract text from PDF")
 }
 
+/// Extract the text of each page of a PDF byte buffer, in page order
+pub fn extract_pages_from_pdf(bytes: &[u8]) -> Result<Vec<String>> {
+    pdf_extract::extract_text_from_mem_by_pages(bytes).context("Failed to extract text from PDF")
+}
+
 /// Extract text content from a DOCX byte buffer
 ///
 /// DOCX is a ZIP archive containing XML files. The main document body
@@ -149,4 +171,13 @@ mod tests {
         );
         assert_eq!(ContentType::from_content_type_header("image/png"), None);
     }
+
+    #[test]
+    fn test_content_type_sniff() {
+        assert_eq!(
+            ContentType::sniff(b"%PDF-1.7\n%\xe2\xe3"),
+            Some(ContentType::Pdf)
+        );
+        assert_eq!(ContentType::sniff(b"<!DOCTYPE html>"), None);
+    }
 }
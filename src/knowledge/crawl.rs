@@ -0,0 +1,171 @@
+// Copyright 2026 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Recursive indexing of a site (`knowledge index --depth N`).
+//!
+//! Pages are fetched breadth-first from a start URL. Links to other pages on
+//! the same origin are queued until the depth limit is reached, and the crawl
+//! stops queueing once `max_pages` URLs have been seen.
+
+use regex::Regex;
+use reqwest::Url;
+use std::collections::{HashSet, VecDeque};
+use std::sync::OnceLock;
+
+use crate::knowledge::content::ContentType;
+
+/// Limits of one crawl
+#[derive(Debug, Clone, Copy)]
+pub struct CrawlOptions {
+    /// Link hops followed from the start page (0 = the start page only)
+    pub depth: usize,
+    /// Pages fetched at most, the start page included
+    pub max_pages: usize,
+    /// Pages fetched at once; `[knowledge].domain_concurrency` still caps
+    /// requests per domain
+    pub concurrency: usize,
+}
+
+/// URLs still to fetch, shallowest first. Each page is queued once, only on
+/// the start page's origin, and never beyond `max_pages`.
+pub struct Frontier {
+    start: Url,
+    max_pages: usize,
+    queue: VecDeque<(Url, usize)>,
+    seen: HashSet<String>,
+}
+
+impl Frontier {
+    pub fn new(start: Url, max_pages: usize) -> Self {
+        let mut frontier = Self {
+            start: start.clone(),
+            max_pages,
+            queue: VecDeque::new(),
+            seen: HashSet::new(),
+        };
+        frontier.push(start, 0);
+        frontier
+    }
+
+    /// Queue `url`, found `depth` hops from the start. `false` when it was
+    /// skipped: another origin, not a page, already seen, or over the cap.
+    pub fn push(&mut self, mut url: Url, depth: usize) -> bool {
+        url.set_fragment(None);
+        if url.origin() != self.start.origin()
+            || !is_page(&url)
+            || self.seen.len() >= self.max_pages
+            || !self.seen.insert(page_key(&url))
+        {
+            return false;
+        }
+        self.queue.push_back((url, depth));
+        true
+    }
+
+    /// Take every queued URL of the shallowest depth
+    pub fn next_level(&mut self) -> Vec<(Url, usize)> {
+        let Some(&(_, depth)) = self.queue.front() else {
+            return Vec::new();
+        };
+        let mut level = Vec::new();
+        while self.queue.front().is_some_and(|(_, d)| *d == depth) {
+            level.extend(self.queue.pop_front());
+        }
+        level
+    }
+}
+
+/// Dedup key: the URL without fragment or trailing slash, as sources are stored
+fn page_key(url: &Url) -> String {
+    url.as_str().trim_end_matches('/').to_string()
+}
+
+/// Whether `url` may be a document: no file extension, or one the chunker reads
+fn is_page(url: &Url) -> bool {
+    let last = url.path().rsplit('/').next().unwrap_or_default();
+    !last.contains('.') || ContentType::from_extension(last).is_some()
+}
+
+/// `href` targets of the `<a>` tags in `html`, resolved against `base`;
+/// only http(s) links are kept.
+pub fn extract_links(base: &Url, html: &str) -> Vec<Url> {
+    static HREF: OnceLock<Option<Regex>> = OnceLock::new();
+    let Some(href) = HREF.get_or_init(|| {
+        Regex::new(r#"(?is)<a\s[^>]*?\bhref\s*=\s*(?:"([^"]*)"|'([^']*)'|([^\s>]+))"#).ok()
+    }) else {
+        return Vec::new();
+    };
+    href.captures_iter(html)
+        .filter_map(|caps| caps.get(1).or(caps.get(2)).or(caps.get(3)))
+        .filter_map(|target| {
+            base.join(&target.as_str().trim().replace("&amp;", "&"))
+                .ok()
+        })
+        .filter(|url| matches!(url.scheme(), "http" | "https"))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn url(s: &str) -> Url {
+        Url::parse(s).unwrap()
+    }
+
+    #[test]
+    fn test_extract_links_resolves_relative_targets() {
+        let base = url("https://docs.example.com/guide/intro");
+        let html = r#"<nav><a href="setup">Setup</a> <A class="x" HREF='/api/?a=1&amp;b=2'>API</A>
+            <a href=../faq.html#top>FAQ</a> <a href="mailto:team@example.com">Mail</a>
+            <a name="anchor">none</a></nav>"#;
+        let links: Vec<String> = extract_links(&base, html)
+            .iter()
+            .map(|u| u.to_string())
+            .collect();
+        assert_eq!(
+            links,
+            vec![
+                "https://docs.example.com/guide/setup",
+                "https://docs.example.com/api/?a=1&b=2",
+                "https://docs.example.com/faq.html#top",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_frontier_stays_on_origin_and_dedups() {
+        let mut frontier = Frontier::new(url("https://docs.example.com/"), 10);
+        assert!(frontier.push(url("https://docs.example.com/a#part"), 1));
+        assert!(!frontier.push(url("https://docs.example.com/a/"), 1));
+        assert!(!frontier.push(url("https://docs.example.com"), 1));
+        assert!(!frontier.push(url("https://other.example.com/a"), 1));
+        assert!(!frontier.push(url("http://docs.example.com/b"), 1));
+        assert!(!frontier.push(url("https://docs.example.com/logo.png"), 1));
+        assert!(frontier.push(url("https://docs.example.com/manual.pdf"), 2));
+
+        let level: Vec<usize> = frontier.next_level().iter().map(|(_, d)| *d).collect();
+        assert_eq!(level, vec![0]);
+        assert_eq!(frontier.next_level().len(), 1);
+        assert_eq!(frontier.next_level()[0].1, 2);
+        assert!(frontier.next_level().is_empty());
+    }
+
+    #[test]
+    fn test_frontier_caps_pages() {
+        let mut frontier = Frontier::new(url("https://docs.example.com/"), 2);
+        assert!(frontier.push(url("https://docs.example.com/a"), 1));
+        assert!(!frontier.push(url("https://docs.example.com/b"), 1));
+    }
+}
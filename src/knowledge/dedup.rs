@@ -0,0 +1,120 @@
+// Copyright 2026 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Duplicate chunks across sources.
+//!
+//! Mirrored documentation indexed from several URLs yields chunks with the
+//! same text. Every chunk is stored with its `KnowledgeChunk::text_hash`;
+//! search keeps the best-ranked copy of each text as the canonical hit and
+//! lists the sources of the other copies on it.
+
+use std::collections::HashMap;
+
+use crate::knowledge::types::KnowledgeSearchResult;
+
+/// Keep the first (best-ranked) hit of each text, in order; the sources of
+/// the dropped copies become its alternate sources.
+pub fn collapse_duplicates(results: Vec<KnowledgeSearchResult>) -> Vec<KnowledgeSearchResult> {
+    let mut canonical: HashMap<String, usize> = HashMap::new();
+    let mut kept: Vec<KnowledgeSearchResult> = Vec::with_capacity(results.len());
+    for result in results {
+        match canonical.get(&result.chunk.text_hash()) {
+            Some(&index) => add_alternate(&mut kept[index], result.chunk.source),
+            None => {
+                canonical.insert(result.chunk.text_hash(), kept.len());
+                kept.push(result);
+            }
+        }
+    }
+    kept
+}
+
+/// Add the sources holding each hit's text, as (text hash, source) pairs
+/// from `KnowledgeBackend::sources_with_text`, to its alternate sources
+pub fn add_alternates(results: &mut [KnowledgeSearchResult], holders: &[(String, String)]) {
+    for result in results.iter_mut() {
+        let hash = result.chunk.text_hash();
+        for (_, source) in holders.iter().filter(|(h, _)| *h == hash) {
+            add_alternate(result, source.clone());
+        }
+        result.alternate_sources.sort();
+    }
+}
+
+fn add_alternate(result: &mut KnowledgeSearchResult, source: String) {
+    if source != result.chunk.source && !result.alternate_sources.contains(&source) {
+        result.alternate_sources.push(source);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::knowledge::types::KnowledgeChunk;
+
+    fn hit(source: &str, content: &str, score: f32) -> KnowledgeSearchResult {
+        KnowledgeSearchResult {
+            chunk: KnowledgeChunk {
+                id: format!("{}#{}", source, content),
+                source: source.to_string(),
+                source_title: "Guide".to_string(),
+                chunk_index: 0,
+                content: content.to_string(),
+                parent_content: None,
+                section_path: Vec::new(),
+                char_start: 0,
+                char_end: content.len(),
+                collection: None,
+            },
+            relevance_score: score,
+            session_scoped: false,
+            alternate_sources: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_collapse_keeps_best_copy_with_alternates() {
+        let results = vec![
+            hit("https://docs.example.com/a", "Install with cargo.", 0.9),
+            hit("https://mirror.example.org/a", "Install with cargo.", 0.9),
+            hit("https://docs.example.com/b", "Configure the cache.", 0.7),
+            hit("https://docs.example.com/a", "Install with cargo.", 0.6),
+        ];
+        let collapsed = collapse_duplicates(results);
+        assert_eq!(collapsed.len(), 2);
+        assert_eq!(collapsed[0].chunk.source, "https://docs.example.com/a");
+        assert_eq!(
+            collapsed[0].alternate_sources,
+            vec!["https://mirror.example.org/a"]
+        );
+        assert!(collapsed[1].alternate_sources.is_empty());
+    }
+
+    #[test]
+    fn test_add_alternates_from_stored_holders() {
+        let mut results = vec![hit("https://b.example.com/", "Same text.", 0.8)];
+        let hash = results[0].chunk.text_hash();
+        let holders = vec![
+            (hash.clone(), "https://b.example.com/".to_string()),
+            (hash.clone(), "https://c.example.com/".to_string()),
+            (hash, "https://a.example.com/".to_string()),
+            ("other".to_string(), "https://d.example.com/".to_string()),
+        ];
+        add_alternates(&mut results, &holders);
+        assert_eq!(
+            results[0].alternate_sources,
+            vec!["https://a.example.com/", "https://c.example.com/"]
+        );
+    }
+}
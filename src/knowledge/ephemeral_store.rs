@@ -0,0 +1,534 @@
+// Copyright 2026 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Knowledge chunks held in process memory (`[memory].backend = "ephemeral"`).
+//!
+//! Mirrors `KnowledgeStore` (exact cosine search, optional RRF fusion with
+//! keyword ranks, same session scoping) without touching the filesystem.
+
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, MutexGuard, OnceLock};
+
+use crate::knowledge::backend::KnowledgeBackend;
+use crate::knowledge::store::{push_line_matches, RRF_K};
+use crate::knowledge::types::{
+    ChunkContext, ChunkFilter, KnowledgeChunk, KnowledgeSearchResult, KnowledgeStats, MatchResult,
+};
+use crate::memory::ephemeral_store::cosine_distance;
+use crate::memory::search_scoring::keyword_terms;
+
+#[derive(Clone)]
+struct StoredChunk {
+    chunk: KnowledgeChunk,
+    session_id: Option<String>,
+    content_hash: String,
+    indexed_at: DateTime<Utc>,
+    embedding: Vec<f32>,
+}
+
+static SHARED_CHUNKS: OnceLock<Arc<Mutex<Vec<StoredChunk>>>> = OnceLock::new();
+
+/// In-memory chunk store; `Default` gives one with its own empty state.
+#[derive(Default)]
+pub struct EphemeralKnowledgeStore {
+    chunks: Arc<Mutex<Vec<StoredChunk>>>,
+}
+
+impl EphemeralKnowledgeStore {
+    /// Store over the process-wide chunk list.
+    pub fn shared() -> Self {
+        Self {
+            chunks: SHARED_CHUNKS.get_or_init(Default::default).clone(),
+        }
+    }
+
+    fn chunks(&self) -> Result<MutexGuard<'_, Vec<StoredChunk>>> {
+        self.chunks
+            .lock()
+            .map_err(|_| anyhow::anyhow!("Ephemeral knowledge store lock poisoned"))
+    }
+
+    /// Optional source filter plus session visibility: persistent chunks and
+    /// those of `session_id`; every chunk when no session is given.
+    fn visible(stored: &StoredChunk, source: Option<&str>, session_id: Option<&str>) -> bool {
+        source.is_none_or(|s| stored.chunk.source == s)
+            && session_id
+                .is_none_or(|sid| stored.session_id.as_deref().is_none_or(|own| own == sid))
+    }
+}
+
+#[async_trait]
+impl KnowledgeBackend for EphemeralKnowledgeStore {
+    async fn store_chunks(
+        &self,
+        source: &str,
+        source_title: &str,
+        content_hash: &str,
+        chunks: &[KnowledgeChunk],
+        embeddings: &[Vec<f32>],
+        session_id: Option<&str>,
+    ) -> Result<()> {
+        if chunks.len() != embeddings.len() {
+            return Err(anyhow::anyhow!(
+                "Got {} embeddings for {} chunks",
+                embeddings.len(),
+                chunks.len()
+            ));
+        }
+        let now = Utc::now();
+        let mut stored = self.chunks()?;
+        // Session-scoped writes replace only that session's chunks,
+        // persistent writes the whole source (full reindex)
+        stored.retain(|c| {
+            c.chunk.source != source
+                || session_id.is_some_and(|sid| c.session_id.as_deref() != Some(sid))
+        });
+        for (chunk, embedding) in chunks.iter().zip(embeddings) {
+            let mut chunk = chunk.clone();
+            chunk.source = source.to_string();
+            chunk.source_title = source_title.to_string();
+            stored.push(StoredChunk {
+                chunk,
+                session_id: session_id.map(str::to_string),
+                content_hash: content_hash.to_string(),
+                indexed_at: now,
+                embedding: embedding.clone(),
+            });
+        }
+        Ok(())
+    }
+
+    async fn search(
+        &self,
+        query_embedding: &[f32],
+        query_text: &str,
+        filter: ChunkFilter<'_>,
+        limit: usize,
+        use_hybrid: bool,
+        session_id: Option<&str>,
+    ) -> Result<Vec<KnowledgeSearchResult>> {
+        let stored = self.chunks()?;
+        let visible: Vec<&StoredChunk> = stored
+            .iter()
+            .filter(|c| {
+                Self::visible(c, filter.source, session_id)
+                    && filter
+                        .collection
+                        .is_none_or(|name| c.chunk.collection.as_deref() == Some(name))
+            })
+            .collect();
+
+        let mut vector_hits: Vec<(&StoredChunk, f32)> = visible
+            .iter()
+            .filter_map(|c| Some((*c, cosine_distance(query_embedding, &c.embedding)?)))
+            .collect();
+        vector_hits.sort_by(|a, b| a.1.total_cmp(&b.1));
+        vector_hits.truncate(limit);
+
+        if !use_hybrid {
+            // Vector search: relevance is 1.0 - cosine distance
+            return Ok(vector_hits
+                .into_iter()
+                .map(|(c, distance)| KnowledgeSearchResult {
+                    chunk: c.chunk.clone(),
+                    relevance_score: 1.0 - distance,
+                    session_scoped: c.session_id.is_some(),
+                    alternate_sources: Vec::new(),
+                })
+                .collect());
+        }
+
+        // Keyword rank: number of distinct query words in the chunk
+        let terms = keyword_terms(query_text);
+        let mut keyword_hits: Vec<(&StoredChunk, usize)> = visible
+            .iter()
+            .filter_map(|c| {
+                let words = keyword_terms(&c.chunk.content);
+                let shared = terms.iter().filter(|t| words.contains(t)).count();
+                (shared > 0).then_some((*c, shared))
+            })
+            .collect();
+        keyword_hits.sort_by_key(|h| std::cmp::Reverse(h.1));
+        keyword_hits.truncate(limit);
+
+        // Fuse both ranks with RRF, normalized by the 2/k maximum
+        let mut fused: HashMap<(&str, Option<&str>), (&StoredChunk, f32)> = HashMap::new();
+        let vector_ranked = vector_hits.into_iter().map(|(c, _)| c);
+        let keyword_ranked = keyword_hits.into_iter().map(|(c, _)| c);
+        for ranking in [vector_ranked.collect::<Vec<_>>(), keyword_ranked.collect()] {
+            for (rank, c) in ranking.into_iter().enumerate() {
+                fused
+                    .entry((c.chunk.id.as_str(), c.session_id.as_deref()))
+                    .or_insert((c, 0.0))
+                    .1 += 1.0 / (RRF_K + rank as f32);
+            }
+        }
+        let max_rrf_score = 2.0 / RRF_K;
+        let mut results: Vec<KnowledgeSearchResult> = fused
+            .into_values()
+            .map(|(c, score)| KnowledgeSearchResult {
+                chunk: c.chunk.clone(),
+                relevance_score: (score / max_rrf_score).min(1.0),
+                session_scoped: c.session_id.is_some(),
+                alternate_sources: Vec::new(),
+            })
+            .collect();
+        results.sort_by(|a, b| b.relevance_score.total_cmp(&a.relevance_score));
+        results.truncate(limit);
+        Ok(results)
+    }
+
+    async fn get_chunk_with_context(
+        &self,
+        chunk_id: &str,
+        context: usize,
+        session_id: Option<&str>,
+    ) -> Result<Option<ChunkContext>> {
+        let stored = self.chunks()?;
+        let Some(target) = stored
+            .iter()
+            .find(|c| c.chunk.id == chunk_id && Self::visible(c, None, session_id))
+        else {
+            return Ok(None);
+        };
+
+        let context = context.min(i32::MAX as usize) as i32;
+        let index = target.chunk.chunk_index;
+        let mut neighbours: Vec<KnowledgeChunk> = stored
+            .iter()
+            .filter(|c| {
+                c.chunk.source == target.chunk.source
+                    && c.session_id == target.session_id
+                    && c.chunk.id != target.chunk.id
+                    && c.chunk.chunk_index >= index.saturating_sub(context)
+                    && c.chunk.chunk_index <= index.saturating_add(context)
+            })
+            .map(|c| c.chunk.clone())
+            .collect();
+        neighbours.sort_by_key(|c| c.chunk_index);
+        let (before, after): (Vec<_>, Vec<_>) =
+            neighbours.into_iter().partition(|c| c.chunk_index < index);
+
+        Ok(Some(ChunkContext {
+            chunk: target.chunk.clone(),
+            before,
+            after,
+        }))
+    }
+
+    async fn get_chunk_range(
+        &self,
+        source: &str,
+        first: i32,
+        last: i32,
+        session_id: Option<&str>,
+    ) -> Result<Vec<KnowledgeChunk>> {
+        Ok(self
+            .chunks()?
+            .iter()
+            .filter(|c| {
+                Self::visible(c, Some(source), session_id)
+                    && (first..=last).contains(&c.chunk.chunk_index)
+            })
+            .map(|c| c.chunk.clone())
+            .collect())
+    }
+
+    async fn get_source_metadata(&self, source: &str) -> Result<Option<(String, DateTime<Utc>)>> {
+        Ok(self
+            .chunks()?
+            .iter()
+            .find(|c| c.chunk.source == source)
+            .map(|c| (c.content_hash.clone(), c.indexed_at)))
+    }
+
+    async fn delete_source(&self, source: &str) -> Result<()> {
+        self.chunks()?.retain(|c| c.chunk.source != source);
+        Ok(())
+    }
+
+    async fn mark_checked(&self, source: &str) -> Result<()> {
+        let now = Utc::now();
+        for stored in self.chunks()?.iter_mut() {
+            if stored.chunk.source == source && stored.session_id.is_none() {
+                stored.indexed_at = now;
+            }
+        }
+        Ok(())
+    }
+
+    async fn source_collection(&self, source: &str) -> Result<Option<String>> {
+        Ok(self
+            .chunks()?
+            .iter()
+            .find(|c| c.chunk.source == source && c.session_id.is_none())
+            .and_then(|c| c.chunk.collection.clone()))
+    }
+
+    async fn set_collection(&self, source: &str, collection: Option<&str>) -> Result<()> {
+        for stored in self.chunks()?.iter_mut() {
+            if stored.chunk.source == source && stored.session_id.is_none() {
+                stored.chunk.collection = collection.map(str::to_string);
+            }
+        }
+        Ok(())
+    }
+
+    async fn sources_with_text(
+        &self,
+        hashes: &[String],
+        session_id: Option<&str>,
+    ) -> Result<Vec<(String, String)>> {
+        let stored = self.chunks()?;
+        let mut holders = Vec::new();
+        for c in stored.iter().filter(|c| Self::visible(c, None, session_id)) {
+            let hash = c.chunk.text_hash();
+            if hashes.contains(&hash) {
+                let holder = (hash, c.chunk.source.clone());
+                if !holders.contains(&holder) {
+                    holders.push(holder);
+                }
+            }
+        }
+        Ok(holders)
+    }
+
+    async fn get_stats(&self) -> Result<KnowledgeStats> {
+        let stored = self.chunks()?;
+        let mut sources: Vec<&str> = stored.iter().map(|c| c.chunk.source.as_str()).collect();
+        sources.sort_unstable();
+        sources.dedup();
+        Ok(KnowledgeStats {
+            total_sources: sources.len(),
+            total_chunks: stored.len(),
+            oldest_indexed: stored.iter().map(|c| c.indexed_at).min(),
+            newest_indexed: stored.iter().map(|c| c.indexed_at).max(),
+        })
+    }
+
+    async fn list_sources(
+        &self,
+        limit: Option<usize>,
+    ) -> Result<Vec<(String, String, usize, DateTime<Utc>)>> {
+        let mut sources: HashMap<&str, (String, usize, DateTime<Utc>)> = HashMap::new();
+        let stored = self.chunks()?;
+        for c in stored.iter() {
+            let entry = sources.entry(c.chunk.source.as_str()).or_insert((
+                c.chunk.source_title.clone(),
+                0,
+                c.indexed_at,
+            ));
+            entry.1 += 1;
+            entry.2 = entry.2.max(c.indexed_at);
+        }
+        let mut sources: Vec<(String, String, usize, DateTime<Utc>)> = sources
+            .into_iter()
+            .map(|(source, (title, count, checked))| (source.to_string(), title, count, checked))
+            .collect();
+        sources.sort_by_key(|s| std::cmp::Reverse(s.3));
+        if let Some(limit) = limit {
+            sources.truncate(limit);
+        }
+        Ok(sources)
+    }
+
+    async fn has_source_in_session(&self, source: &str, session_id: &str) -> Result<bool> {
+        Ok(self
+            .chunks()?
+            .iter()
+            .any(|c| c.chunk.source == source && c.session_id.as_deref() == Some(session_id)))
+    }
+
+    async fn delete_by_source_and_session(&self, source: &str, session_id: &str) -> Result<()> {
+        self.chunks()?
+            .retain(|c| !(c.chunk.source == source && c.session_id.as_deref() == Some(session_id)));
+        Ok(())
+    }
+
+    async fn cleanup_expired_sessions(&self, ttl_hours: u64) -> Result<()> {
+        let cutoff = Utc::now() - Duration::hours(ttl_hours as i64);
+        self.chunks()?
+            .retain(|c| c.session_id.is_none() || c.indexed_at >= cutoff);
+        Ok(())
+    }
+
+    async fn match_content(
+        &self,
+        pattern: &regex::Regex,
+        source: Option<&str>,
+        session_id: Option<&str>,
+    ) -> Result<Vec<MatchResult>> {
+        let mut matches = Vec::new();
+        for c in self
+            .chunks()?
+            .iter()
+            .filter(|c| Self::visible(c, source, session_id))
+        {
+            push_line_matches(
+                pattern,
+                &c.chunk.source,
+                &c.chunk.source_title,
+                &c.chunk.content,
+                &mut matches,
+            );
+        }
+        Ok(matches)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chunk(id: &str, index: i32, content: &str) -> KnowledgeChunk {
+        KnowledgeChunk {
+            id: id.to_string(),
+            source: String::new(),
+            source_title: String::new(),
+            chunk_index: index,
+            content: content.to_string(),
+            parent_content: None,
+            section_path: Vec::new(),
+            char_start: 0,
+            char_end: content.len(),
+            collection: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_search_and_session_scoping() {
+        let store = EphemeralKnowledgeStore::default();
+        store
+            .store_chunks(
+                "doc",
+                "Doc",
+                "h1",
+                &[chunk("a", 0, "retry policy"), chunk("b", 1, "backoff")],
+                &[vec![1.0, 0.0], vec![0.0, 1.0]],
+                None,
+            )
+            .await
+            .unwrap();
+        store
+            .store_chunks(
+                "doc",
+                "Doc",
+                "h1",
+                &[chunk("s", 0, "session notes")],
+                &[vec![1.0, 0.0]],
+                Some("s1"),
+            )
+            .await
+            .unwrap();
+
+        let hits = store
+            .search(
+                &[1.0, 0.0],
+                "retry",
+                ChunkFilter::default(),
+                5,
+                false,
+                Some("s2"),
+            )
+            .await
+            .unwrap();
+        assert_eq!(hits[0].chunk.id, "a");
+        assert!(hits.iter().all(|h| !h.session_scoped));
+
+        let hits = store
+            .search(
+                &[0.0, 1.0],
+                "retry",
+                ChunkFilter::default(),
+                5,
+                true,
+                Some("s1"),
+            )
+            .await
+            .unwrap();
+        assert_eq!(hits.len(), 3);
+        assert!(hits.iter().any(|h| h.session_scoped));
+
+        let context = store
+            .get_chunk_with_context("a", 1, None)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(context.after.len(), 1);
+        assert!(context.before.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_persistent_write_replaces_source() {
+        let store = EphemeralKnowledgeStore::default();
+        for hash in ["h1", "h2"] {
+            store
+                .store_chunks(
+                    "doc",
+                    "Doc",
+                    hash,
+                    &[chunk("a", 0, "text")],
+                    &[vec![1.0]],
+                    None,
+                )
+                .await
+                .unwrap();
+        }
+        let stats = store.get_stats().await.unwrap();
+        assert_eq!((stats.total_sources, stats.total_chunks), (1, 1));
+        let (hash, _) = store.get_source_metadata("doc").await.unwrap().unwrap();
+        assert_eq!(hash, "h2");
+    }
+
+    #[tokio::test]
+    async fn test_collection_filter_and_move() {
+        let store = EphemeralKnowledgeStore::default();
+        for (source, collection) in [("tokio", Some("rust-docs")), ("blog", None)] {
+            let mut chunk = chunk(source, 0, "async runtime");
+            chunk.collection = collection.map(str::to_string);
+            store
+                .store_chunks(source, source, "h", &[chunk], &[vec![1.0]], None)
+                .await
+                .unwrap();
+        }
+        let rust_docs = ChunkFilter {
+            source: None,
+            collection: Some("rust-docs"),
+        };
+
+        let hits = store
+            .search(&[1.0], "async", rust_docs, 5, true, None)
+            .await
+            .unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].chunk.source, "tokio");
+
+        store
+            .set_collection("blog", Some("rust-docs"))
+            .await
+            .unwrap();
+        let hits = store
+            .search(&[1.0], "async", rust_docs, 5, false, None)
+            .await
+            .unwrap();
+        assert_eq!(hits.len(), 2);
+        assert_eq!(
+            store.source_collection("blog").await.unwrap().as_deref(),
+            Some("rust-docs")
+        );
+    }
+}
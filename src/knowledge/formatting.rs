@@ -2,8 +2,12 @@
 //
 use chrono::{DateTime, Utc};
 use colored::Colorize;
+use serde_json::{json, Value};
 
-use crate::knowledge::types::{KnowledgeSearchResult, KnowledgeStats, MatchResult, ReadResult};
+use crate::knowledge::types::{
+    ChunkContext, KnowledgeChunk, KnowledgeSearchResult, KnowledgeStats, MatchResult, ReadResult,
+    StitchedPassage,
+};
 
 pub fn format_search_results(results: &[KnowledgeSearchResult]) -> String {
     if results.is_empty() {
@@ -23,6 +27,11 @@ pub fn format_search_results(results: &[KnowledgeSearchResult]) -> String {
         // Source URL
         output.push_str(&result.chunk.source.bright_black().to_string());
         output.push('\n');
+        if !result.alternate_sources.is_empty() {
+            let also = format!("Also at: {}", result.alternate_sources.join(", "));
+            output.push_str(&also.bright_black().to_string());
+            output.push('\n');
+        }
 
         // Section path
         if !result.chunk.section_path.is_empty() {
@@ -54,6 +63,51 @@ pub fn format_search_results(results: &[KnowledgeSearchResult]) -> String {
     output
 }
 
+/// Search results as `{"results": [...]}`, one flat object per chunk with its
+/// ID, score, section path and character range in the source.
+pub fn search_results_json(results: &[KnowledgeSearchResult]) -> Value {
+    json!({ "results": results })
+}
+
+pub fn format_passages(passages: &[StitchedPassage]) -> String {
+    if passages.is_empty() {
+        return "No results found".to_string();
+    }
+
+    let mut output = String::new();
+
+    for passage in passages {
+        output.push_str(&"━".repeat(60));
+        output.push('\n');
+        output.push_str(&passage.source_title.blue().bold().to_string());
+        output.push('\n');
+        output.push_str(&passage.source.bright_black().to_string());
+        output.push('\n');
+        output.push_str(
+            &format!(
+                "Chunks {}-{} ({} hits)",
+                passage.first_chunk, passage.last_chunk, passage.hits
+            )
+            .cyan()
+            .to_string(),
+        );
+        output.push('\n');
+        output.push_str(&passage.text);
+        output.push('\n');
+
+        let score_pct = (passage.relevance_score * 100.0) as u32;
+        output.push_str(&format!("{}% relevant", score_pct).green().to_string());
+        output.push_str("\n\n");
+    }
+
+    output
+}
+
+/// Stitched passages as `{"passages": [...]}`
+pub fn passages_json(passages: &[StitchedPassage]) -> Value {
+    json!({ "passages": passages })
+}
+
 pub fn format_stats(stats: &KnowledgeStats) -> String {
     let mut output = String::new();
 
@@ -131,6 +185,21 @@ pub fn format_source_list(sources: &[(String, String, usize, DateTime<Utc>)]) ->
     output
 }
 
+pub fn source_list_json(sources: &[(String, String, usize, DateTime<Utc>)]) -> Value {
+    let sources: Vec<Value> = sources
+        .iter()
+        .map(|(source, title, chunks, last_indexed)| {
+            json!({
+                "source": source,
+                "title": title,
+                "chunks": chunks,
+                "last_indexed": last_indexed,
+            })
+        })
+        .collect();
+    json!({ "sources": sources })
+}
+
 fn format_relative_time(dt: DateTime<Utc>) -> String {
     let now = Utc::now();
     let duration = now.signed_duration_since(dt);
@@ -169,6 +238,47 @@ pub fn format_read_result(result: &ReadResult) -> String {
     output
 }
 
+/// Plain-text listing of a chunk and its neighbours in source order; the
+/// requested chunk is marked with `>>>`. Shared by the CLI and MCP.
+pub fn format_chunk_context(context: &ChunkContext) -> String {
+    let chunk = &context.chunk;
+    let mut output = String::new();
+    output.push_str(&chunk.source_title);
+    output.push('\n');
+    output.push_str(&chunk.source);
+    output.push('\n');
+    if !chunk.section_path.is_empty() {
+        output.push_str(&chunk.section_path.join(" > "));
+        output.push('\n');
+    }
+
+    let neighbours = context.before.iter().chain(context.after.iter()).count();
+    output.push_str(&format!(
+        "Chunk {} with {} neighbouring chunk(s)\n",
+        chunk.chunk_index, neighbours
+    ));
+
+    let section = |output: &mut String, c: &KnowledgeChunk, marker: &str| {
+        output.push_str(&"=".repeat(50));
+        output.push('\n');
+        output.push_str(&format!(
+            "{}[#{}] {} (chars {}-{})\n",
+            marker, c.chunk_index, c.id, c.char_start, c.char_end
+        ));
+        output.push_str(&c.content);
+        output.push('\n');
+    };
+    for c in &context.before {
+        section(&mut output, c, "");
+    }
+    section(&mut output, chunk, ">>> ");
+    for c in &context.after {
+        section(&mut output, c, "");
+    }
+
+    output
+}
+
 pub fn format_match_results(results: &[MatchResult]) -> String {
     if results.is_empty() {
         return "No matches found".to_string();
@@ -197,3 +307,39 @@ pub fn format_match_results(results: &[MatchResult]) -> String {
 
     output
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::knowledge::types::KnowledgeChunk;
+
+    #[test]
+    fn test_search_results_json_is_flat_per_chunk() {
+        let result = KnowledgeSearchResult {
+            chunk: KnowledgeChunk {
+                id: "chunk-1".to_string(),
+                source: "file:///docs/guide.md".to_string(),
+                source_title: "Guide".to_string(),
+                chunk_index: 3,
+                content: "Retries use exponential backoff.".to_string(),
+                parent_content: None,
+                section_path: vec!["Networking".to_string(), "Retries".to_string()],
+                char_start: 120,
+                char_end: 152,
+                collection: None,
+            },
+            relevance_score: 0.82,
+            session_scoped: false,
+            alternate_sources: Vec::new(),
+        };
+
+        let json = search_results_json(&[result]);
+        let hit = &json["results"][0];
+        assert_eq!(hit["id"], "chunk-1");
+        assert_eq!(hit["chunk_index"], 3);
+        assert_eq!(hit["section_path"][1], "Retries");
+        assert_eq!(hit["char_start"], 120);
+        assert_eq!(hit["char_end"], 152);
+        assert!((hit["relevance_score"].as_f64().unwrap() - 0.82).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,288 @@
+// Copyright 2026 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Documentation living in GitHub repositories (`knowledge index-repo`).
+//!
+//! The root README, everything readable under `docs/` and the wiki pages are
+//! indexed one source per file, under their raw.githubusercontent.com URL so
+//! `knowledge refresh` can fetch them again. Each chunk's section path starts
+//! with the file's path in the repository.
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::path::Path;
+
+use crate::knowledge::content::ContentType;
+
+/// Environment variables holding a GitHub token, in order of preference.
+/// Without one only public repositories can be read, at a lower rate limit.
+pub const GITHUB_TOKEN_ENVS: [&str; 2] = ["GITHUB_TOKEN", "GH_TOKEN"];
+
+const API: &str = "https://api.github.com";
+const RAW_HOST: &str = "https://raw.githubusercontent.com/";
+
+/// `owner/repo`, also accepted as a github.com URL
+#[derive(Debug, Clone, PartialEq)]
+pub struct RepoRef {
+    pub owner: String,
+    pub repo: String,
+}
+
+impl RepoRef {
+    pub fn parse(input: &str) -> Result<Self> {
+        let trimmed = input.trim();
+        let path = trimmed
+            .strip_prefix("https://github.com/")
+            .or_else(|| trimmed.strip_prefix("http://github.com/"))
+            .or_else(|| trimmed.strip_prefix("github.com/"))
+            .unwrap_or(trimmed);
+        let mut parts = path.trim_end_matches('/').split('/');
+        match (parts.next(), parts.next()) {
+            (Some(owner), Some(repo)) if !owner.is_empty() && !repo.is_empty() => Ok(Self {
+                owner: owner.to_string(),
+                repo: repo.trim_end_matches(".git").to_string(),
+            }),
+            _ => anyhow::bail!("Expected a repository as owner/repo, got '{}'", input),
+        }
+    }
+
+    pub fn name(&self) -> String {
+        format!("{}/{}", self.owner, self.repo)
+    }
+
+    /// Raw URL of a file on `branch`
+    pub fn raw_url(&self, branch: &str, path: &str) -> String {
+        format!(
+            "{}{}/{}/{}/{}",
+            RAW_HOST, self.owner, self.repo, branch, path
+        )
+    }
+
+    /// Raw URL of a wiki page file (`Home.md`)
+    pub fn wiki_raw_url(&self, file: &str) -> String {
+        format!("{}wiki/{}/{}/{}", RAW_HOST, self.owner, self.repo, file)
+    }
+
+    /// Clone URL of the wiki, carrying `token` when given
+    pub fn wiki_clone_url(&self, token: Option<&str>) -> String {
+        match token {
+            Some(token) => format!(
+                "https://x-access-token:{}@github.com/{}/{}.wiki.git",
+                token, self.owner, self.repo
+            ),
+            None => format!("https://github.com/{}/{}.wiki.git", self.owner, self.repo),
+        }
+    }
+}
+
+/// The GitHub token from the environment, if any
+pub fn token() -> Option<String> {
+    GITHUB_TOKEN_ENVS
+        .iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .find(|token| !token.trim().is_empty())
+}
+
+/// Whether fetching `url` should carry the GitHub token
+pub fn wants_token(url: &str) -> bool {
+    url.starts_with(RAW_HOST) || url.starts_with(API)
+}
+
+#[derive(Deserialize)]
+struct RepoInfo {
+    default_branch: String,
+    #[serde(default)]
+    has_wiki: bool,
+}
+
+#[derive(Deserialize)]
+struct Tree {
+    tree: Vec<TreeEntry>,
+    #[serde(default)]
+    truncated: bool,
+}
+
+#[derive(Deserialize)]
+struct TreeEntry {
+    path: String,
+    #[serde(rename = "type")]
+    kind: String,
+}
+
+/// Files of a repository worth indexing
+pub struct RepoDocs {
+    pub branch: String,
+    pub has_wiki: bool,
+    /// Paths of the README and docs files on `branch`
+    pub paths: Vec<String>,
+    /// Whether GitHub cut the file listing short (very large repositories)
+    pub truncated: bool,
+}
+
+/// List the README and docs files of `repo` on its default branch
+pub async fn list_docs(repo: &RepoRef, token: Option<&str>) -> Result<RepoDocs> {
+    let client = reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(30))
+        .user_agent("Octobrain/1.0")
+        .build()?;
+    let get = |url: String| {
+        let mut request = client
+            .get(url)
+            .header("Accept", "application/vnd.github+json")
+            .header("X-GitHub-Api-Version", "2022-11-28");
+        if let Some(token) = token {
+            request = request.bearer_auth(token);
+        }
+        request.send()
+    };
+
+    let response = get(format!("{}/repos/{}/{}", API, repo.owner, repo.repo))
+        .await
+        .context("Failed to reach the GitHub API")?;
+    if !response.status().is_success() {
+        anyhow::bail!(
+            "GitHub API error for {}: {} (set {} for private repositories)",
+            repo.name(),
+            response.status(),
+            GITHUB_TOKEN_ENVS[0]
+        );
+    }
+    let info: RepoInfo = response
+        .json()
+        .await
+        .context("Unexpected GitHub API reply")?;
+
+    let response = get(format!(
+        "{}/repos/{}/{}/git/trees/{}?recursive=1",
+        API, repo.owner, repo.repo, info.default_branch
+    ))
+    .await
+    .context("Failed to reach the GitHub API")?;
+    if !response.status().is_success() {
+        anyhow::bail!(
+            "GitHub API error listing {}: {}",
+            repo.name(),
+            response.status()
+        );
+    }
+    let tree: Tree = response
+        .json()
+        .await
+        .context("Unexpected GitHub API reply")?;
+
+    Ok(RepoDocs {
+        paths: tree
+            .tree
+            .into_iter()
+            .filter(|entry| entry.kind == "blob" && is_doc_path(&entry.path))
+            .map(|entry| entry.path)
+            .collect(),
+        branch: info.default_branch,
+        has_wiki: info.has_wiki,
+        truncated: tree.truncated,
+    })
+}
+
+/// Page files of a wiki checkout, sorted. Sidebar and footer files are
+/// navigation, not content.
+pub fn wiki_pages(checkout: &Path) -> Result<Vec<String>> {
+    let mut pages = Vec::new();
+    for entry in std::fs::read_dir(checkout)
+        .with_context(|| format!("Cannot read wiki checkout '{}'", checkout.display()))?
+    {
+        let name = entry?.file_name().to_string_lossy().to_string();
+        if !name.starts_with('_') && ContentType::from_extension(&name).is_some() {
+            pages.push(name);
+        }
+    }
+    pages.sort();
+    Ok(pages)
+}
+
+/// Whether a repository path is documentation: a README at the root, or a
+/// readable file under `docs/` or `doc/`
+fn is_doc_path(path: &str) -> bool {
+    if ContentType::from_extension(path).is_none() {
+        return false;
+    }
+    match path.split_once('/') {
+        None => path.to_ascii_lowercase().starts_with("readme."),
+        Some((top, _)) => top == "docs" || top == "doc",
+    }
+}
+
+/// Section path a repository file's chunks are nested under: the file's path
+/// within the repository, or `wiki` and the page file. Empty for other sources.
+pub fn section_base(source: &str) -> Vec<String> {
+    let Some(rest) = source.strip_prefix(RAW_HOST) else {
+        return Vec::new();
+    };
+    let parts: Vec<&str> = rest.split('/').collect();
+    let path = match parts.as_slice() {
+        ["wiki", _owner, _repo, page @ ..] => {
+            let mut path = vec!["wiki"];
+            path.extend(page);
+            path
+        }
+        [_owner, _repo, _branch, file @ ..] => file.to_vec(),
+        _ => Vec::new(),
+    };
+    path.into_iter()
+        .filter(|part| !part.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_repo_ref_parse() {
+        let expected = RepoRef {
+            owner: "tokio-rs".to_string(),
+            repo: "tokio".to_string(),
+        };
+        assert_eq!(RepoRef::parse("tokio-rs/tokio").unwrap(), expected);
+        assert_eq!(
+            RepoRef::parse("https://github.com/tokio-rs/tokio.git").unwrap(),
+            expected
+        );
+        assert!(RepoRef::parse("tokio").is_err());
+    }
+
+    #[test]
+    fn test_doc_paths() {
+        assert!(is_doc_path("README.md"));
+        assert!(is_doc_path("docs/guide/install.md"));
+        assert!(is_doc_path("doc/overview.rst"));
+        assert!(!is_doc_path("src/lib.rs"));
+        assert!(!is_doc_path("docs/logo.png"));
+        assert!(!is_doc_path("examples/README.md"));
+    }
+
+    #[test]
+    fn test_section_base_from_raw_urls() {
+        let repo = RepoRef::parse("tokio-rs/tokio").unwrap();
+        assert_eq!(
+            section_base(&repo.raw_url("master", "docs/guide/install.md")),
+            vec!["docs", "guide", "install.md"]
+        );
+        assert_eq!(
+            section_base(&repo.wiki_raw_url("Home.md")),
+            vec!["wiki", "Home.md"]
+        );
+        assert!(section_base("https://docs.rs/tokio").is_empty());
+    }
+}
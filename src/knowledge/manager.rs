@@ -1,30 +1,51 @@
 // Copyright 2026 Muvon Un Limited
 //
 use anyhow::{Context, Result};
-use chrono::{DateTime, Duration, Utc};
+use chrono::{DateTime, Utc};
+use futures::StreamExt;
+use reqwest::Url;
+use std::collections::{HashSet, VecDeque};
 use std::path::PathBuf;
 use std::sync::Arc;
 
-use crate::config::{Config, KnowledgeConfig, SearchConfig};
-use crate::embedding::EmbeddingProvider;
+use crate::config::{Config, EmbeddingConfig, KnowledgeConfig, SearchConfig, SourceConfig};
+use crate::embedding::{EmbeddingProvider, InputType};
+use crate::knowledge::backend::{open_knowledge_backend, KnowledgeBackend};
 use crate::knowledge::chunker::ContentChunker;
 use crate::knowledge::content::ContentType;
-use crate::knowledge::store::KnowledgeStore;
+use crate::knowledge::crawl::{extract_links, CrawlOptions, Frontier};
+use crate::knowledge::dedup;
+use crate::knowledge::github::{self, RepoRef};
+use crate::knowledge::politeness::{Politeness, Robots, ROBOTS_AGENT};
+use crate::knowledge::sitemap::{parse_sitemap, Sitemap, UrlFilter};
+use crate::knowledge::stitch::{hit_ranges, stitch_range};
 use crate::knowledge::types::{
-    IndexResult, KnowledgeChunk, KnowledgeSearchResult, KnowledgeStats, MatchResult, ReadResult,
-    StoreResult,
+    ChunkContext, ChunkFilter, CrawlReport, IndexPhase, IndexResult, KnowledgeChunk,
+    KnowledgeSearchResult, KnowledgeStats, MatchResult, ReadResult, RefreshFailure, RefreshReport,
+    RefreshScope, RepoReport, StitchedPassage, StoreResult,
 };
+use crate::vector_optimizer::{IndexRebuild, TableCompaction};
 
 /// Maximum source size in bytes (50 MB)
 const MAX_SOURCE_SIZE: usize = 50 * 1024 * 1024;
 
+/// Called as each source moves through fetch, chunk, embed and store
+pub type IndexProgress = Arc<dyn Fn(IndexPhase, &str) + Send + Sync>;
+
 pub struct KnowledgeManager {
     config: KnowledgeConfig,
     search_config: SearchConfig,
-    store: KnowledgeStore,
+    store: Box<dyn KnowledgeBackend>,
     chunker: ContentChunker,
     embedding_provider: Arc<dyn EmbeddingProvider>,
     embedding_timeout_secs: u64,
+    /// Request limits for embedding chunks in bulk
+    embedding_config: EmbeddingConfig,
+    politeness: Politeness,
+    /// Collection that sources indexed by this manager join
+    collection: Option<String>,
+    /// Receives indexing phases while a caller waits on them
+    progress: Option<IndexProgress>,
 }
 
 impl KnowledgeManager {
@@ -36,11 +57,12 @@ impl KnowledgeManager {
             "test",
             embedding_provider.as_ref(),
             config.embedding.timeout_secs,
+            InputType::None,
         )
         .await?;
         let vector_dim = test_embedding.len();
 
-        let store = KnowledgeStore::new(vector_dim).await?;
+        let store = open_knowledge_backend(config, vector_dim).await?;
         let chunker = ContentChunker::new(config.knowledge.clone());
 
         // Clean up expired session-scoped chunks (crash recovery)
@@ -56,18 +78,54 @@ impl KnowledgeManager {
             chunker,
             embedding_provider: Arc::from(embedding_provider),
             embedding_timeout_secs: config.embedding.timeout_secs,
+            embedding_config: config.embedding.clone(),
+            politeness: Politeness::new(&config.knowledge),
+            collection: None,
+            progress: None,
         })
     }
 
-    /// Search knowledge base with on-demand indexing
+    /// Put every source indexed from now on into `collection`. Without one,
+    /// re-indexed sources keep the collection they already belong to.
+    pub fn set_index_collection(&mut self, collection: Option<String>) {
+        self.collection = collection;
+    }
+
+    /// Report the phases of every source indexed from now on to `progress`
+    /// (none to stop reporting)
+    pub fn set_progress(&mut self, progress: Option<IndexProgress>) {
+        self.progress = progress;
+    }
+
+    fn report(&self, phase: IndexPhase, source: &str) {
+        if let Some(progress) = &self.progress {
+            progress(phase, source);
+        }
+    }
+
+    /// Search knowledge base with on-demand indexing of `filter.source`.
+    /// `limit` defaults to `[knowledge].max_results`; hits scoring below
+    /// `min_relevance` (0.0-1.0) are dropped.
     pub async fn search(
         &self,
         query: &str,
-        source: Option<&str>,
+        filter: ChunkFilter<'_>,
         session_id: Option<&str>,
+        limit: Option<usize>,
+        min_relevance: Option<f32>,
     ) -> Result<Vec<KnowledgeSearchResult>> {
+        let limit = limit.unwrap_or(self.config.max_results);
+        if limit == 0 {
+            anyhow::bail!("limit must be at least 1");
+        }
+        if let Some(min) = min_relevance {
+            if !(0.0..=1.0).contains(&min) {
+                anyhow::bail!("min_relevance must be between 0.0 and 1.0, got {}", min);
+            }
+        }
+
         // If source provided, normalize and check if needs indexing
-        let normalized = source.map(normalize_source).transpose()?;
+        let normalized = filter.source.map(normalize_source).transpose()?;
         let source_ref = normalized.as_deref();
 
         if let Some(s) = source_ref {
@@ -81,6 +139,7 @@ impl KnowledgeManager {
             query,
             self.embedding_provider.as_ref(),
             self.embedding_timeout_secs,
+            InputType::Query,
         )
         .await?;
 
@@ -88,16 +147,51 @@ impl KnowledgeManager {
         let use_hybrid = self.search_config.hybrid.enabled;
 
         // Search with configurable limit and hybrid flag
-        self.store
+        let results = self
+            .store
             .search(
                 &query_embedding,
                 query,
-                source_ref,
-                self.config.max_results,
+                ChunkFilter {
+                    source: source_ref,
+                    ..filter
+                },
+                // Room for the copies of mirrored chunks collapsed below
+                limit.saturating_mul(2),
                 use_hybrid,
                 session_id,
             )
-            .await
+            .await?;
+        let mut results = dedup::collapse_duplicates(results);
+        if let Some(min) = min_relevance {
+            results.retain(|r| r.relevance_score >= min);
+        }
+        results.truncate(limit);
+        if !results.is_empty() {
+            let hashes: Vec<String> = results.iter().map(|r| r.chunk.text_hash()).collect();
+            let holders = self.store.sources_with_text(&hashes, session_id).await?;
+            dedup::add_alternates(&mut results, &holders);
+        }
+        Ok(results)
+    }
+
+    /// Turn search hits into answer-ready passages: hits from the same source
+    /// that sit close together are merged, the chunks between them fetched, and
+    /// overlapping text removed. Best passage first.
+    pub async fn stitch(
+        &self,
+        results: &[KnowledgeSearchResult],
+        session_id: Option<&str>,
+    ) -> Result<Vec<StitchedPassage>> {
+        let mut passages = Vec::new();
+        for range in hit_ranges(results) {
+            let chunks = self
+                .store
+                .get_chunk_range(&range.source, range.first, range.last, session_id)
+                .await?;
+            passages.push(stitch_range(&range, chunks));
+        }
+        Ok(passages)
     }
 
     /// Check if source needs indexing (not indexed or outdated)
@@ -119,9 +213,8 @@ impl KnowledgeManager {
                     let mtime: DateTime<Utc> = metadata.modified()?.into();
                     Ok(mtime > last_checked)
                 } else {
-                    // HTTP: use outdating_days
-                    let outdating_duration = Duration::days(self.config.outdating_days as i64);
-                    let outdated = Utc::now() - last_checked > outdating_duration;
+                    // HTTP: per-source refresh interval, else outdating_days
+                    let outdated = Utc::now() - last_checked > self.config.max_age(source);
                     Ok(outdated)
                 }
             }
@@ -132,7 +225,8 @@ impl KnowledgeManager {
     pub async fn index_source(&self, source: &str) -> Result<IndexResult> {
         let source = normalize_source(source)?;
 
-        // Check if already indexed and fresh
+        // A fresh source whose content hash is unchanged is not re-embedded
+        let mut unchanged_hash = None;
         if let Some((content_hash, last_checked)) = self.store.get_source_metadata(&source).await? {
             let is_fresh = if is_local_source(&source) {
                 let path = source_to_path(&source)?;
@@ -142,90 +236,390 @@ impl KnowledgeManager {
                 let mtime: DateTime<Utc> = metadata.modified()?.into();
                 mtime <= last_checked
             } else {
-                let outdating_duration = Duration::days(self.config.outdating_days as i64);
-                Utc::now() - last_checked <= outdating_duration
+                Utc::now() - last_checked <= self.config.max_age(&source)
             };
-
             if is_fresh {
-                // Fetch to check if content changed
-                let (content_type, bytes) = self.fetch_source(&source).await?;
-                let (_, new_hash, _) =
-                    self.chunker
-                        .extract_and_chunk(&source, &content_type, &bytes)?;
-
-                if new_hash == content_hash {
-                    return Ok(IndexResult {
-                        source,
-                        chunks_created: 0,
-                        was_cached: true,
-                        content_changed: false,
-                    });
-                }
+                unchanged_hash = Some(content_hash);
             }
         }
 
-        // Fetch and index
         let (content_type, bytes) = self.fetch_source(&source).await?;
-        let (title, content_hash, chunks) =
-            self.chunker
-                .extract_and_chunk(&source, &content_type, &bytes)?;
+        self.index_bytes(&source, &content_type, &bytes, unchanged_hash.as_deref())
+            .await
+    }
 
-        if chunks.is_empty() {
-            return Ok(IndexResult {
-                source,
-                chunks_created: 0,
-                was_cached: false,
-                content_changed: true,
-            });
+    /// Crawl limits for `depth` hops from the `[knowledge]` configuration
+    pub fn crawl_options(&self, depth: usize) -> CrawlOptions {
+        CrawlOptions {
+            depth,
+            max_pages: self.config.crawl_max_pages,
+            concurrency: self.config.crawl_concurrency,
         }
+    }
 
-        // Generate embeddings using proper batch API
-        let texts: Vec<String> = chunks.iter().map(|c| c.content.clone()).collect();
-        let embeddings = crate::embedding::generate_embeddings_batch(
-            texts,
-            self.embedding_provider.as_ref(),
-            self.embedding_timeout_secs,
-        )
-        .await?;
+    /// Index `start` and the same-origin pages it links to, breadth-first up
+    /// to `options.depth` hops and `options.max_pages` pages. Pages that fail
+    /// are recorded and the crawl goes on; `on_page` sees every outcome.
+    pub async fn crawl(
+        &self,
+        start: &str,
+        options: CrawlOptions,
+        on_page: &mut (dyn FnMut(&std::result::Result<IndexResult, RefreshFailure>) + Send),
+    ) -> Result<CrawlReport> {
+        let start = normalize_source(start)?;
+        let start_url = Url::parse(&start)
+            .ok()
+            .filter(|u| matches!(u.scheme(), "http" | "https"))
+            .with_context(|| format!("Only http(s) URLs can be crawled, got {}", start))?;
+
+        let mut frontier = Frontier::new(start_url, options.max_pages.max(1));
+        let mut report = CrawlReport::default();
+        loop {
+            let level = frontier.next_level();
+            if level.is_empty() {
+                break;
+            }
+            let mut pages = futures::stream::iter(level)
+                .map(|(url, depth)| async move {
+                    let follow = depth < options.depth;
+                    (self.index_page(&url, follow).await, url, depth)
+                })
+                .buffer_unordered(options.concurrency.max(1));
+            while let Some((outcome, url, depth)) = pages.next().await {
+                let outcome = match outcome {
+                    Ok((result, links)) => {
+                        for link in links {
+                            frontier.push(link, depth + 1);
+                        }
+                        Ok(result)
+                    }
+                    Err(e) => Err(RefreshFailure {
+                        source: url.to_string(),
+                        error: e.to_string(),
+                    }),
+                };
+                on_page(&outcome);
+                match outcome {
+                    Ok(result) => report.indexed.push(result),
+                    Err(failure) => report.failed.push(failure),
+                }
+            }
+        }
+        Ok(report)
+    }
 
-        // Store (persistent — no session_id)
-        self.store
-            .store_chunks(&source, &title, &content_hash, &chunks, &embeddings, None)
+    /// Fetch and index one crawled page; its links when `follow` is set and
+    /// the page is HTML.
+    async fn index_page(&self, url: &Url, follow: bool) -> Result<(IndexResult, Vec<Url>)> {
+        let source = normalize_source(url.as_str())?;
+        let (content_type, bytes) = self.fetch_url_bytes(&source).await?;
+        let links = if follow && content_type == ContentType::Html {
+            extract_links(url, &String::from_utf8_lossy(&bytes))
+        } else {
+            Vec::new()
+        };
+        let stored_hash = self
+            .store
+            .get_source_metadata(&source)
+            .await?
+            .map(|(hash, _)| hash);
+        let result = self
+            .index_bytes(&source, &content_type, &bytes, stored_hash.as_deref())
             .await?;
-
-        Ok(IndexResult {
-            source,
-            chunks_created: chunks.len(),
-            was_cached: false,
-            content_changed: true,
-        })
+        Ok((result, links))
     }
 
     /// Internal indexing (always reindexes if outdated)
     async fn index_source_internal(&self, source: &str) -> Result<()> {
         let (content_type, bytes) = self.fetch_source(source).await?;
-        let (title, content_hash, chunks) =
-            self.chunker
-                .extract_and_chunk(source, &content_type, &bytes)?;
+        self.index_bytes(source, &content_type, &bytes, None)
+            .await
+            .map(|_| ())
+    }
+
+    /// Chunk, embed and store fetched content under `source`. Nothing is
+    /// stored when its content hash equals `unchanged_hash`.
+    async fn index_bytes(
+        &self,
+        source: &str,
+        content_type: &ContentType,
+        bytes: &[u8],
+        unchanged_hash: Option<&str>,
+    ) -> Result<IndexResult> {
+        match self.prepare_source(source, content_type, bytes, unchanged_hash)? {
+            Some(prepared) => Ok(self.embed_and_store(vec![prepared]).await?.remove(0)),
+            None => self.keep_unchanged(source).await,
+        }
+    }
+
+    /// Result for a source whose content is already stored; it still moves
+    /// into the collection being indexed into.
+    async fn keep_unchanged(&self, source: &str) -> Result<IndexResult> {
+        if let Some(collection) = &self.collection {
+            self.store.set_collection(source, Some(collection)).await?;
+        }
+        Ok(unchanged(source))
+    }
+
+    /// Collection for the new chunks of `source`: the one being indexed into,
+    /// else the one the source already belongs to
+    async fn collection_for(&self, source: &str) -> Result<Option<String>> {
+        match &self.collection {
+            Some(collection) => Ok(Some(collection.clone())),
+            None => self.store.source_collection(source).await,
+        }
+    }
 
+    /// Chunks among `chunks` whose text is already indexed under a source
+    /// other than `source` (mirrored pages)
+    async fn count_duplicates(&self, source: &str, chunks: &[KnowledgeChunk]) -> Result<usize> {
         if chunks.is_empty() {
-            return Ok(());
+            return Ok(0);
         }
+        let hashes: Vec<String> = chunks.iter().map(KnowledgeChunk::text_hash).collect();
+        let elsewhere: HashSet<String> = self
+            .store
+            .sources_with_text(&hashes, None)
+            .await?
+            .into_iter()
+            .filter(|(_, holder)| holder != source)
+            .map(|(hash, _)| hash)
+            .collect();
+        Ok(hashes.iter().filter(|h| elsewhere.contains(*h)).count())
+    }
 
-        // Generate embeddings using proper batch API
-        let texts: Vec<String> = chunks.iter().map(|c| c.content.clone()).collect();
-        let embeddings = crate::embedding::generate_embeddings_batch(
+    /// Split fetched content into chunks; `None` when its content hash equals
+    /// `unchanged_hash`.
+    fn prepare_source(
+        &self,
+        source: &str,
+        content_type: &ContentType,
+        bytes: &[u8],
+        unchanged_hash: Option<&str>,
+    ) -> Result<Option<PreparedSource>> {
+        self.report(IndexPhase::Chunk, source);
+        let (title, content_hash, chunks) = self.chunker.extract_and_chunk_under(
+            source,
+            content_type,
+            bytes,
+            &github::section_base(source),
+        )?;
+        if unchanged_hash == Some(content_hash.as_str()) {
+            return Ok(None);
+        }
+        Ok(Some(PreparedSource {
+            source: source.to_string(),
+            title,
+            content_hash,
+            chunks,
+        }))
+    }
+
+    /// Embed the chunks of `sources` together, `[embedding].batch_size` per
+    /// request, then store each source (persistent — no session_id).
+    async fn embed_and_store(&self, sources: Vec<PreparedSource>) -> Result<Vec<IndexResult>> {
+        for prepared in &sources {
+            self.report(IndexPhase::Embed, &prepared.source);
+        }
+        let texts: Vec<String> = sources
+            .iter()
+            .flat_map(|s| s.chunks.iter().map(|c| c.content.clone()))
+            .collect();
+        let embeddings = crate::embedding::embed_documents(
             texts,
             self.embedding_provider.as_ref(),
-            self.embedding_timeout_secs,
+            &self.embedding_config,
         )
         .await?;
 
-        self.store
-            .store_chunks(source, &title, &content_hash, &chunks, &embeddings, None)
-            .await?;
+        let mut embeddings = embeddings.into_iter();
+        let mut results = Vec::with_capacity(sources.len());
+        for mut prepared in sources {
+            let own: Vec<Vec<f32>> = embeddings.by_ref().take(prepared.chunks.len()).collect();
+            let duplicate_chunks = self
+                .count_duplicates(&prepared.source, &prepared.chunks)
+                .await?;
+            if !prepared.chunks.is_empty() {
+                let collection = self.collection_for(&prepared.source).await?;
+                for chunk in &mut prepared.chunks {
+                    chunk.collection = collection.clone();
+                }
+                self.report(IndexPhase::Store, &prepared.source);
+                self.store
+                    .store_chunks(
+                        &prepared.source,
+                        &prepared.title,
+                        &prepared.content_hash,
+                        &prepared.chunks,
+                        &own,
+                        None,
+                    )
+                    .await?;
+            }
+            results.push(IndexResult {
+                source: prepared.source,
+                chunks_created: prepared.chunks.len(),
+                was_cached: false,
+                content_changed: true,
+                duplicate_chunks,
+            });
+        }
+        Ok(results)
+    }
+
+    /// Page URLs listed by the sitemap at `url`, following sitemap indexes,
+    /// that pass `filter`. In sitemap order, without duplicates.
+    pub async fn sitemap_pages(&self, url: &str, filter: &UrlFilter) -> Result<Vec<String>> {
+        let mut pending = VecDeque::from([normalize_source(url)?]);
+        let mut visited = HashSet::new();
+        let mut seen = HashSet::new();
+        let mut pages = Vec::new();
+        while let Some(sitemap_url) = pending.pop_front() {
+            if !visited.insert(sitemap_url.clone()) {
+                continue;
+            }
+            let (_, bytes) = self.fetch_url_bytes(&sitemap_url).await?;
+            let sitemap = parse_sitemap(&String::from_utf8_lossy(&bytes))
+                .with_context(|| format!("Cannot read sitemap {}", sitemap_url))?;
+            match sitemap {
+                Sitemap::Index(children) => pending.extend(children),
+                Sitemap::Pages(urls) => {
+                    for page in urls {
+                        if !page.starts_with("http://") && !page.starts_with("https://") {
+                            continue;
+                        }
+                        let page = normalize_source(&page)?;
+                        if filter.matches(&page) && seen.insert(page.clone()) {
+                            pages.push(page);
+                        }
+                    }
+                }
+            }
+        }
+        Ok(pages)
+    }
+
+    /// Fetch and index `urls`, `concurrency` pages at a time; the chunks of
+    /// each group are embedded together. Unchanged pages are skipped, failing
+    /// ones recorded, and `on_page` sees every outcome.
+    pub async fn index_pages(
+        &self,
+        urls: &[String],
+        concurrency: usize,
+        on_page: &mut (dyn FnMut(&std::result::Result<IndexResult, RefreshFailure>) + Send),
+    ) -> Result<CrawlReport> {
+        let mut report = CrawlReport::default();
+        for group in urls.chunks(concurrency.max(1)) {
+            let fetched =
+                futures::future::join_all(group.iter().map(|url| self.prepare_page(url))).await;
+            let mut outcomes = Vec::new();
+            let mut prepared = Vec::new();
+            for (url, page) in group.iter().zip(fetched) {
+                let outcome = match page {
+                    Ok(Some(page)) => {
+                        prepared.push(page);
+                        continue;
+                    }
+                    Ok(None) => self.keep_unchanged(url).await,
+                    Err(e) => Err(e),
+                };
+                outcomes.push(outcome.map_err(|e| RefreshFailure {
+                    source: url.clone(),
+                    error: e.to_string(),
+                }));
+            }
+            let sources: Vec<String> = prepared.iter().map(|p| p.source.clone()).collect();
+            match self.embed_and_store(prepared).await {
+                Ok(results) => outcomes.extend(results.into_iter().map(Ok)),
+                Err(e) => outcomes.extend(sources.into_iter().map(|source| {
+                    Err(RefreshFailure {
+                        source,
+                        error: e.to_string(),
+                    })
+                })),
+            }
+            for outcome in outcomes {
+                on_page(&outcome);
+                match outcome {
+                    Ok(result) => report.indexed.push(result),
+                    Err(failure) => report.failed.push(failure),
+                }
+            }
+        }
+        Ok(report)
+    }
+
+    /// Index the README, docs and wiki pages of a GitHub repository, one
+    /// source per file. The token is read from `GITHUB_TOKEN`/`GH_TOKEN`.
+    pub async fn index_repo(
+        &self,
+        repo: &RepoRef,
+        include_wiki: bool,
+        concurrency: usize,
+        on_page: &mut (dyn FnMut(&std::result::Result<IndexResult, RefreshFailure>) + Send),
+    ) -> Result<RepoReport> {
+        let token = github::token();
+        let docs = github::list_docs(repo, token.as_deref()).await?;
+        let mut urls: Vec<String> = docs
+            .paths
+            .iter()
+            .map(|path| repo.raw_url(&docs.branch, path))
+            .collect();
+
+        let mut wiki_error = None;
+        if include_wiki && docs.has_wiki {
+            match self.wiki_pages(repo, token.as_deref()) {
+                Ok(pages) => urls.extend(pages.iter().map(|page| repo.wiki_raw_url(page))),
+                Err(e) => wiki_error = Some(e.to_string()),
+            }
+        }
+
+        let report = self.index_pages(&urls, concurrency, on_page).await?;
+        Ok(RepoReport {
+            branch: docs.branch,
+            report,
+            truncated: docs.truncated,
+            wiki_error,
+        })
+    }
 
-        Ok(())
+    /// Page files of a repository wiki, read from a shallow clone. GitHub's
+    /// API does not list wiki pages.
+    fn wiki_pages(&self, repo: &RepoRef, token: Option<&str>) -> Result<Vec<String>> {
+        let checkout =
+            std::env::temp_dir().join(format!("octobrain-wiki-{}", uuid::Uuid::new_v4()));
+        let output = std::process::Command::new("git")
+            .args(["clone", "--quiet", "--depth", "1"])
+            .arg(repo.wiki_clone_url(token))
+            .arg(&checkout)
+            .env("GIT_TERMINAL_PROMPT", "0")
+            .output()
+            .context("Cannot run git to clone the wiki")?;
+        let pages = if output.status.success() {
+            github::wiki_pages(&checkout)
+        } else {
+            let mut stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+            if let Some(token) = token {
+                stderr = stderr.replace(token, "***");
+            }
+            Err(anyhow::anyhow!(
+                "git clone of the wiki failed (no pages yet?): {}",
+                stderr
+            ))
+        };
+        std::fs::remove_dir_all(&checkout).ok();
+        pages
+    }
+
+    /// Fetch one page and chunk it; `None` when its content is already stored
+    async fn prepare_page(&self, url: &str) -> Result<Option<PreparedSource>> {
+        let (content_type, bytes) = self.fetch_url_bytes(url).await?;
+        let stored_hash = self
+            .store
+            .get_source_metadata(url)
+            .await?
+            .map(|(hash, _)| hash);
+        self.prepare_source(url, &content_type, &bytes, stored_hash.as_deref())
     }
 
     /// Fetch and return full text content of a source (URL or local file).
@@ -241,6 +635,7 @@ impl KnowledgeManager {
             ContentType::PlainText => "text",
             ContentType::Pdf => "pdf",
             ContentType::Docx => "docx",
+            ContentType::OpenApi => "openapi",
         };
 
         Ok(ReadResult {
@@ -265,8 +660,22 @@ impl KnowledgeManager {
         self.store.match_content(&regex, source, session_id).await
     }
 
+    /// Fetch a chunk by ID (as returned by search) with `context` neighbouring
+    /// chunks on each side from the same source. None when the ID is unknown.
+    pub async fn get_chunk(
+        &self,
+        chunk_id: &str,
+        context: usize,
+        session_id: Option<&str>,
+    ) -> Result<Option<ChunkContext>> {
+        self.store
+            .get_chunk_with_context(chunk_id.trim(), context, session_id)
+            .await
+    }
+
     /// Fetch source content as raw bytes with content type detection.
     async fn fetch_source(&self, source: &str) -> Result<(ContentType, Vec<u8>)> {
+        self.report(IndexPhase::Fetch, source);
         if is_local_source(source) {
             let path = source_to_path(source)?;
 
@@ -286,7 +695,8 @@ impl KnowledgeManager {
                 .await
                 .with_context(|| format!("Failed to read file: {}", path.display()))?;
 
-            let content_type = ContentType::from_extension(path.to_str().unwrap_or(""))
+            let content_type = ContentType::sniff(&bytes)
+                .or_else(|| ContentType::from_extension(path.to_str().unwrap_or("")))
                 .unwrap_or(ContentType::PlainText);
 
             Ok((content_type, bytes))
@@ -314,24 +724,45 @@ impl KnowledgeManager {
             .user_agent("Octobrain/1.0")
             .build()?;
 
-        let response = client
-            .get(url)
-            .send()
-            .await
-            .context("Failed to fetch URL")?;
+        let parsed = Url::parse(trimmed).context("Invalid URL")?;
+        // Held until the body is read, capping requests in flight per domain
+        let _slot = self
+            .politeness
+            .acquire(&parsed, !github::wants_token(url), || {
+                fetch_robots(&client, &parsed)
+            })
+            .await?;
+
+        let mut request = client.get(url);
+        let source_auth = parsed
+            .host_str()
+            .and_then(|host| self.config.source_config(host));
+        if let Some(auth) = source_auth {
+            for (name, value) in auth_headers(auth)? {
+                request = request.header(name, value);
+            }
+        } else if github::wants_token(url) {
+            if let Some(token) = github::token() {
+                request = request.bearer_auth(token);
+            }
+        }
+        let response = request.send().await.context("Failed to fetch URL")?;
 
         if !response.status().is_success() {
             anyhow::bail!("HTTP error: {}", response.status());
         }
 
-        // Detect content type from Content-Type header, fall back to URL extension, then Html
-        let content_type = response
+        // Detect content type from Content-Type header, fall back to URL extension, then Html.
+        // A generic text/plain (raw file hosts) yields to a more specific extension.
+        let from_header = response
             .headers()
             .get("content-type")
             .and_then(|v| v.to_str().ok())
-            .and_then(ContentType::from_content_type_header)
-            .or_else(|| ContentType::from_extension(url))
-            .unwrap_or(ContentType::Html);
+            .and_then(ContentType::from_content_type_header);
+        let declared = match (from_header, ContentType::from_extension(url)) {
+            (None | Some(ContentType::PlainText), Some(from_extension)) => Some(from_extension),
+            (from_header, from_extension) => from_header.or(from_extension),
+        };
 
         let bytes = response
             .bytes()
@@ -346,6 +777,11 @@ impl KnowledgeManager {
             );
         }
 
+        // A PDF signature wins over the declared type
+        let content_type = ContentType::sniff(&bytes)
+            .or(declared)
+            .unwrap_or(ContentType::Html);
+
         Ok((content_type, bytes.to_vec()))
     }
 
@@ -392,11 +828,13 @@ impl KnowledgeManager {
                 section_path: vec![],
                 char_start: 0,
                 char_end: content.len(),
+                collection: None,
             };
             let embedding = crate::embedding::generate_embedding(
                 content,
                 self.embedding_provider.as_ref(),
                 self.embedding_timeout_secs,
+                InputType::Document,
             )
             .await?;
             self.store
@@ -417,10 +855,10 @@ impl KnowledgeManager {
 
         // Generate embeddings in batch
         let texts: Vec<String> = chunks.iter().map(|c| c.content.clone()).collect();
-        let embeddings = crate::embedding::generate_embeddings_batch(
+        let embeddings = crate::embedding::embed_documents(
             texts,
             self.embedding_provider.as_ref(),
-            self.embedding_timeout_secs,
+            &self.embedding_config,
         )
         .await?;
 
@@ -464,12 +902,170 @@ impl KnowledgeManager {
     ) -> Result<Vec<(String, String, usize, chrono::DateTime<chrono::Utc>)>> {
         self.store.list_sources(limit).await
     }
+
+    /// Drop session-scoped chunks older than `session_ttl_hours`.
+    /// Rebuild the chunk vector index; `None` when the backend has no such index
+    pub async fn rebuild_vector_index(&self) -> Result<Option<IndexRebuild>> {
+        self.store.rebuild_vector_index().await
+    }
+
+    /// Compact the chunk table and prune its versions older than `keep`
+    /// (`storage optimize`); empty when the backend keeps no table files
+    pub async fn compact_storage(&self, keep: chrono::Duration) -> Result<Vec<TableCompaction>> {
+        self.store.compact(keep).await
+    }
+
+    pub async fn cleanup_expired_sessions(&self) -> Result<()> {
+        self.store
+            .cleanup_expired_sessions(self.config.session_ttl_hours)
+            .await
+    }
+
+    /// Re-index every source whose file changed or whose URL is older than
+    /// `outdating_days`. A failing source is recorded and the rest continue.
+    pub async fn refresh_outdated(&self) -> Result<RefreshReport> {
+        self.refresh(RefreshScope::Outdated).await
+    }
+
+    /// Re-fetch the sources in `scope`, re-indexing only those whose content
+    /// hash changed; unchanged ones just have their check time reset. A
+    /// failing source is recorded and the rest continue.
+    pub async fn refresh(&self, scope: RefreshScope) -> Result<RefreshReport> {
+        let (sources, outdated_only) = match scope {
+            RefreshScope::Source(source) => {
+                let source = normalize_source(&source)?;
+                if self.store.get_source_metadata(&source).await?.is_none() {
+                    anyhow::bail!("{} is not indexed; use `knowledge index` to add it", source);
+                }
+                (vec![source], false)
+            }
+            scope => {
+                let sources = self
+                    .store
+                    .list_sources(None)
+                    .await?
+                    .into_iter()
+                    .map(|(source, _, _, _)| source)
+                    .filter(|source| !source.starts_with("stored://"))
+                    .collect();
+                (sources, scope == RefreshScope::Outdated)
+            }
+        };
+
+        let mut report = RefreshReport::default();
+        for source in sources {
+            report.checked += 1;
+            let due = match outdated_only {
+                true => self.needs_indexing(&source).await,
+                false => Ok(true),
+            };
+            let outcome = match due {
+                Ok(true) => self.refresh_source(&source).await.map(Some),
+                Ok(false) => Ok(None),
+                Err(e) => Err(e),
+            };
+            match outcome {
+                Ok(Some(true)) => report.refreshed.push(source),
+                Ok(Some(false)) => report.unchanged.push(source),
+                Ok(None) => {}
+                Err(e) => report.failed.push(RefreshFailure {
+                    source,
+                    error: e.to_string(),
+                }),
+            }
+        }
+        Ok(report)
+    }
+
+    /// Re-fetch one source; `true` when its content changed and it was
+    /// re-indexed
+    async fn refresh_source(&self, source: &str) -> Result<bool> {
+        let stored_hash = self
+            .store
+            .get_source_metadata(source)
+            .await?
+            .map(|(hash, _)| hash);
+        let (content_type, bytes) = self.fetch_source(source).await?;
+        let result = self
+            .index_bytes(source, &content_type, &bytes, stored_hash.as_deref())
+            .await?;
+        if result.was_cached {
+            self.store.mark_checked(source).await?;
+        }
+        Ok(!result.was_cached)
+    }
 }
 
 // ============================================================================
 // Source helpers
 // ============================================================================
 
+/// A fetched source split into chunks, waiting to be embedded
+struct PreparedSource {
+    source: String,
+    title: String,
+    content_hash: String,
+    chunks: Vec<KnowledgeChunk>,
+}
+
+/// The robots.txt rules of `url`'s origin for us; `None` (allow all) when
+/// it is missing or cannot be read
+async fn fetch_robots(client: &reqwest::Client, url: &Url) -> Option<Robots> {
+    let response = client
+        .get(url.join("/robots.txt").ok()?)
+        .send()
+        .await
+        .ok()?;
+    if !response.status().is_success() {
+        return None;
+    }
+    let text = response.text().await.ok()?;
+    Some(Robots::parse(&text, ROBOTS_AGENT))
+}
+
+/// Headers a `[knowledge.sources]` entry adds to a request
+fn auth_headers(auth: &SourceConfig) -> Result<Vec<(String, String)>> {
+    let mut headers: Vec<(String, String)> = auth
+        .headers
+        .iter()
+        .map(|(name, value)| (name.clone(), value.clone()))
+        .collect();
+    let token = match (&auth.bearer_token, &auth.bearer_token_env) {
+        (Some(token), _) => Some(token.clone()),
+        (None, Some(var)) => Some(std::env::var(var).with_context(|| {
+            format!(
+                "Environment variable {} (knowledge.sources bearer_token_env) is not set",
+                var
+            )
+        })?),
+        (None, None) => None,
+    };
+    if let Some(token) = token {
+        headers.push(("Authorization".to_string(), format!("Bearer {}", token)));
+    }
+    if !auth.cookies.is_empty() {
+        let mut cookies: Vec<String> = auth
+            .cookies
+            .iter()
+            .map(|(name, value)| format!("{}={}", name, value))
+            .collect();
+        cookies.sort();
+        headers.push(("Cookie".to_string(), cookies.join("; ")));
+    }
+    Ok(headers)
+}
+
+/// Outcome for a source whose stored content is current
+fn unchanged(source: &str) -> IndexResult {
+    IndexResult {
+        source: source.to_string(),
+        chunks_created: 0,
+        was_cached: true,
+        content_changed: false,
+        duplicate_chunks: 0,
+    }
+}
+
 /// Check if a source string refers to a local file
 fn is_local_source(source: &str) -> bool {
     source.starts_with("file://") || source.starts_with('/')
@@ -540,6 +1136,50 @@ fn source_to_path(source: &str) -> Result<PathBuf> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_source_config_matches_domain_and_builds_headers() {
+        let mut config = KnowledgeConfig::default();
+        config.sources.insert(
+            "example.com".to_string(),
+            SourceConfig {
+                bearer_token: Some("outer".to_string()),
+                ..Default::default()
+            },
+        );
+        config.sources.insert(
+            "docs.example.com".to_string(),
+            SourceConfig {
+                headers: [("X-Api-Key".to_string(), "k1".to_string())].into(),
+                bearer_token: Some("inner".to_string()),
+                cookies: [
+                    ("session".to_string(), "abc".to_string()),
+                    ("lang".to_string(), "en".to_string()),
+                ]
+                .into(),
+                ..Default::default()
+            },
+        );
+
+        let auth = config.source_config("api.docs.example.com").unwrap();
+        assert_eq!(
+            auth_headers(auth).unwrap(),
+            vec![
+                ("X-Api-Key".to_string(), "k1".to_string()),
+                ("Authorization".to_string(), "Bearer inner".to_string()),
+                ("Cookie".to_string(), "lang=en; session=abc".to_string()),
+            ]
+        );
+        assert_eq!(
+            config
+                .source_config("Example.com")
+                .unwrap()
+                .bearer_token
+                .as_deref(),
+            Some("outer")
+        );
+        assert!(config.source_config("notexample.com").is_none());
+    }
+
     #[test]
     fn test_normalize_source_http_passthrough() {
         assert_eq!(
@@ -593,6 +1233,30 @@ mod tests {
         assert!(!is_local_source("http://example.com"));
     }
 
+    #[test]
+    fn test_max_age_uses_longest_matching_prefix() {
+        let mut config = crate::config::KnowledgeConfig::default();
+        config
+            .refresh_intervals
+            .insert("https://docs.example.com/".to_string(), 24);
+        config
+            .refresh_intervals
+            .insert("https://docs.example.com/status".to_string(), 1);
+
+        assert_eq!(
+            config.max_age("https://docs.example.com/status/today"),
+            chrono::Duration::hours(1)
+        );
+        assert_eq!(
+            config.max_age("https://docs.example.com/guide"),
+            chrono::Duration::hours(24)
+        );
+        assert_eq!(
+            config.max_age("https://other.example.com/"),
+            chrono::Duration::days(config.outdating_days as i64)
+        );
+    }
+
     #[test]
     fn test_source_to_path() {
         assert_eq!(
@@ -620,4 +1284,132 @@ mod tests {
             "error should mention directory rejection, got: {msg}"
         );
     }
+
+    /// Constant embedding; these tests never rank results.
+    struct FlatEmbedding;
+
+    #[async_trait::async_trait]
+    impl EmbeddingProvider for FlatEmbedding {
+        async fn generate_embedding(&self, _text: &str) -> Result<Vec<f32>> {
+            Ok(vec![0.5; 8])
+        }
+
+        async fn generate_embeddings_batch(
+            &self,
+            texts: Vec<String>,
+            _input_type: InputType,
+        ) -> Result<Vec<Vec<f32>>> {
+            Ok(vec![vec![0.5; 8]; texts.len()])
+        }
+
+        fn get_dimension(&self) -> usize {
+            8
+        }
+    }
+
+    fn manager() -> KnowledgeManager {
+        let config = KnowledgeConfig::default();
+        KnowledgeManager {
+            chunker: ContentChunker::new(config.clone()),
+            politeness: Politeness::new(&config),
+            config,
+            search_config: SearchConfig::default(),
+            store: Box::new(crate::knowledge::ephemeral_store::EphemeralKnowledgeStore::default()),
+            embedding_provider: Arc::new(FlatEmbedding),
+            embedding_timeout_secs: 30,
+            embedding_config: EmbeddingConfig::default(),
+            collection: None,
+            progress: None,
+        }
+    }
+
+    fn temp_file(content: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("octobrain_refresh_{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("notes.md");
+        std::fs::write(&path, content).unwrap();
+        path
+    }
+
+    #[tokio::test]
+    async fn test_refresh_reindexes_only_changed_sources() {
+        let manager = manager();
+        let path = temp_file("# Retries\n\nBack off exponentially, at most five tries.\n");
+        let source = normalize_source(path.to_str().unwrap()).unwrap();
+        manager.index_source(&source).await.unwrap();
+
+        let report = manager
+            .refresh(RefreshScope::Source(source.clone()))
+            .await
+            .unwrap();
+        assert_eq!(report.checked, 1);
+        assert!(report.refreshed.is_empty());
+        assert_eq!(report.unchanged, vec![source.clone()]);
+
+        std::fs::write(&path, "# Retries\n\nGive up after three tries.\n").unwrap();
+        let report = manager.refresh(RefreshScope::All).await.unwrap();
+        assert_eq!(report.refreshed, vec![source.clone()]);
+        assert!(report.unchanged.is_empty());
+        assert!(report.failed.is_empty());
+
+        // Nothing changed since the last check, so an outdated-only pass skips it
+        let report = manager.refresh(RefreshScope::Outdated).await.unwrap();
+        assert_eq!(report.checked, 1);
+        assert!(report.refreshed.is_empty() && report.unchanged.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_refresh_of_unindexed_source_fails() {
+        let path = temp_file("never indexed");
+        let err = manager()
+            .refresh(RefreshScope::Source(path.to_string_lossy().into_owned()))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("is not indexed"));
+    }
+
+    #[tokio::test]
+    async fn test_refresh_records_failing_sources_and_continues() {
+        let manager = manager();
+        let gone = temp_file("# Gone\n\nThis file is removed before the refresh.\n");
+        let kept = temp_file("# Kept\n\nThis file stays on disk.\n");
+        for path in [&gone, &kept] {
+            manager.index_source(path.to_str().unwrap()).await.unwrap();
+        }
+        std::fs::remove_file(&gone).unwrap();
+
+        let report = manager.refresh(RefreshScope::All).await.unwrap();
+        assert_eq!(report.checked, 2);
+        assert_eq!(report.failed.len(), 1);
+        assert_eq!(
+            report.failed[0].source,
+            normalize_source(gone.to_str().unwrap()).unwrap()
+        );
+        assert_eq!(
+            report.unchanged,
+            vec![normalize_source(kept.to_str().unwrap()).unwrap()]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_progress_reports_phases_in_order() {
+        let phases = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let seen = phases.clone();
+        let mut manager = manager();
+        manager.set_progress(Some(Arc::new(move |phase, _source: &str| {
+            seen.lock().unwrap().push(phase);
+        })));
+
+        let path = temp_file("# Progress\n\nEvery phase is reported once.\n");
+        manager.index_source(path.to_str().unwrap()).await.unwrap();
+        assert_eq!(
+            *phases.lock().unwrap(),
+            vec![
+                IndexPhase::Fetch,
+                IndexPhase::Chunk,
+                IndexPhase::Embed,
+                IndexPhase::Store
+            ]
+        );
+    }
 }
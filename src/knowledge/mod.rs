@@ -1,9 +1,20 @@
 // Copyright 2026 Muvon Un Limited
 //
+pub mod backend;
 pub mod chunker;
 pub mod content;
+pub mod crawl;
+pub mod dedup;
+pub mod ephemeral_store;
 pub mod formatting;
+pub mod github;
 pub mod manager;
+pub mod openapi;
+pub mod politeness;
+#[cfg(feature = "postgres")]
+pub mod postgres_store;
+pub mod sitemap;
+pub mod stitch;
 pub mod store;
 pub mod types;
 
@@ -0,0 +1,428 @@
+// Copyright 2026 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! OpenAPI 3.x and Swagger 2.0 specifications, in JSON or YAML.
+//!
+//! A spec is indexed one chunk per operation rather than as raw text: the
+//! method and path, summary, parameters, request body and responses, with
+//! `$ref` schemas resolved to their fields. The section path is the
+//! operation's first tag, then `METHOD /path`.
+
+use anyhow::{Context, Result};
+use serde_json::{Map, Value};
+
+const METHODS: [&str; 8] = [
+    "get", "put", "post", "delete", "options", "head", "patch", "trace",
+];
+
+/// How deep nested schemas are expanded into field lists
+const SCHEMA_DEPTH: usize = 2;
+
+/// One rendered operation of a spec
+#[derive(Debug, Clone, PartialEq)]
+pub struct Operation {
+    /// First tag of the operation, if any
+    pub tag: Option<String>,
+    /// `GET /pets/{petId}`
+    pub name: String,
+    pub text: String,
+}
+
+/// Whether `bytes` look like an OpenAPI or Swagger document: a JSON object
+/// or YAML mapping with a top-level `openapi`/`swagger` version and `paths`
+pub fn is_spec(bytes: &[u8]) -> bool {
+    let Ok(text) = std::str::from_utf8(bytes) else {
+        return false;
+    };
+    let text = text.trim_start_matches('\u{feff}').trim_start();
+    if text.starts_with('{') {
+        return serde_json::from_str::<Value>(text).is_ok_and(|spec| has_spec_keys(&spec));
+    }
+    let top_level = |key: &str| text.lines().any(|line| line.starts_with(key));
+    (top_level("openapi:") || top_level("swagger:")) && top_level("paths:")
+}
+
+fn has_spec_keys(spec: &Value) -> bool {
+    (spec.get("openapi").is_some() || spec.get("swagger").is_some())
+        && spec.get("paths").is_some_and(Value::is_object)
+}
+
+/// Parse a JSON or YAML spec
+pub fn parse(bytes: &[u8]) -> Result<Value> {
+    let text = std::str::from_utf8(bytes).context("OpenAPI spec is not valid UTF-8")?;
+    let text = text.trim_start_matches('\u{feff}');
+    let spec = if text.trim_start().starts_with('{') {
+        serde_json::from_str(text).context("Invalid OpenAPI JSON")?
+    } else {
+        yaml_to_json(serde_yaml::from_str(text).context("Invalid OpenAPI YAML")?)
+    };
+    if !has_spec_keys(&spec) {
+        anyhow::bail!("Not an OpenAPI spec: expected top-level openapi/swagger and paths");
+    }
+    Ok(spec)
+}
+
+/// YAML value as JSON; non-string keys (response codes like `200`) become strings
+fn yaml_to_json(value: serde_yaml::Value) -> Value {
+    match value {
+        serde_yaml::Value::Null => Value::Null,
+        serde_yaml::Value::Bool(b) => Value::Bool(b),
+        serde_yaml::Value::Number(n) => n
+            .as_i64()
+            .map(Value::from)
+            .or_else(|| n.as_u64().map(Value::from))
+            .or_else(|| n.as_f64().map(Value::from))
+            .unwrap_or(Value::Null),
+        serde_yaml::Value::String(s) => Value::String(s),
+        serde_yaml::Value::Sequence(items) => {
+            Value::Array(items.into_iter().map(yaml_to_json).collect())
+        }
+        serde_yaml::Value::Mapping(mapping) => Value::Object(
+            mapping
+                .into_iter()
+                .map(|(key, value)| (scalar_text(&yaml_to_json(key)), yaml_to_json(value)))
+                .collect(),
+        ),
+        serde_yaml::Value::Tagged(tagged) => yaml_to_json(tagged.value),
+    }
+}
+
+/// Text of a scalar, without the quotes JSON strings render with
+fn scalar_text(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// `info.title` and `info.version`, as "Title v1.2"
+pub fn title(spec: &Value) -> String {
+    let info = &spec["info"];
+    let title = info["title"].as_str().unwrap_or("API").trim();
+    match info["version"].as_str() {
+        Some(version) if !version.trim().is_empty() => format!("{} v{}", title, version.trim()),
+        _ => title.to_string(),
+    }
+}
+
+/// Every operation of the spec, by path
+pub fn operations(spec: &Value) -> Vec<Operation> {
+    let Some(paths) = spec["paths"].as_object() else {
+        return Vec::new();
+    };
+    let mut operations = Vec::new();
+    for (path, item) in paths {
+        let item = resolve(spec, item);
+        let shared_params = item["parameters"].as_array().cloned().unwrap_or_default();
+        for method in METHODS {
+            let Some(operation) = item.get(method).filter(|op| op.is_object()) else {
+                continue;
+            };
+            let name = format!("{} {}", method.to_uppercase(), path);
+            operations.push(Operation {
+                tag: operation["tags"][0].as_str().map(str::to_string),
+                text: render_operation(spec, &name, operation, &shared_params),
+                name,
+            });
+        }
+    }
+    operations
+}
+
+/// Reference-style description of one operation
+fn render_operation(spec: &Value, name: &str, operation: &Value, shared: &[Value]) -> String {
+    let mut lines = vec![name.to_string()];
+    for key in ["summary", "description"] {
+        if let Some(text) = operation[key].as_str().filter(|t| !t.trim().is_empty()) {
+            lines.push(text.trim().to_string());
+        }
+    }
+    if let Some(id) = operation["operationId"].as_str() {
+        lines.push(format!("Operation ID: {}", id));
+    }
+    if operation["deprecated"].as_bool() == Some(true) {
+        lines.push("Deprecated".to_string());
+    }
+
+    // Operation parameters override path-level ones with the same name and location
+    let own: Vec<Value> = operation["parameters"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .map(|p| resolve(spec, p).clone())
+        .collect();
+    let key = |p: &Value| (p["name"].clone(), p["in"].clone());
+    let mut params: Vec<Value> = shared
+        .iter()
+        .map(|p| resolve(spec, p).clone())
+        .filter(|p| !own.iter().any(|o| key(o) == key(p)))
+        .collect();
+    params.extend(own);
+
+    let (body_params, params): (Vec<Value>, Vec<Value>) =
+        params.into_iter().partition(|p| p["in"] == "body");
+    if !params.is_empty() {
+        lines.push("Parameters:".to_string());
+        for param in &params {
+            lines.push(render_parameter(param));
+        }
+    }
+
+    // OpenAPI 3 requestBody, or a Swagger 2 `in: body` parameter
+    let body = resolve(spec, &operation["requestBody"]);
+    if let Some(content) = body["content"].as_object() {
+        for (media_type, media) in content {
+            lines.push(format!("Request body ({}):", media_type));
+            render_schema(spec, &media["schema"], 1, &mut lines);
+        }
+    }
+    for param in &body_params {
+        lines.push("Request body:".to_string());
+        render_schema(spec, &param["schema"], 1, &mut lines);
+    }
+
+    if let Some(responses) = operation["responses"].as_object() {
+        lines.push("Responses:".to_string());
+        for (status, response) in responses {
+            let response = resolve(spec, response);
+            let description = response["description"].as_str().unwrap_or("").trim();
+            lines.push(format!("- {}: {}", status, description));
+            // OpenAPI 3 content per media type; Swagger 2 a bare schema
+            let schema = response["content"]
+                .as_object()
+                .and_then(|content| content.values().next())
+                .map(|media| &media["schema"])
+                .unwrap_or(&response["schema"]);
+            render_schema(spec, schema, 1, &mut lines);
+        }
+    }
+    lines.join("\n")
+}
+
+/// `- name (in, required, type): description`
+fn render_parameter(param: &Value) -> String {
+    let mut traits = vec![param["in"].as_str().unwrap_or("query").to_string()];
+    if param["required"].as_bool() == Some(true) {
+        traits.push("required".to_string());
+    }
+    // OpenAPI 3 keeps the type under `schema`, Swagger 2 on the parameter
+    let schema = if param["schema"].is_null() {
+        param
+    } else {
+        &param["schema"]
+    };
+    traits.push(type_name(schema));
+    let mut line = format!(
+        "- {} ({})",
+        param["name"].as_str().unwrap_or("?"),
+        traits.join(", ")
+    );
+    if let Some(description) = param["description"].as_str() {
+        line.push_str(": ");
+        line.push_str(description.trim());
+    }
+    line
+}
+
+/// Append a schema as its type, then the fields of an object (or of the
+/// items of an array of objects) as an indented list
+fn render_schema(spec: &Value, schema: &Value, indent: usize, lines: &mut Vec<String>) {
+    if schema.is_null() {
+        return;
+    }
+    lines.push(format!("{}{}", "  ".repeat(indent), type_name(schema)));
+    render_fields(spec, schema, indent + 1, SCHEMA_DEPTH, lines);
+}
+
+/// `- name (type, required): description` per property, expanding nested
+/// objects `depth` levels further
+fn render_fields(
+    spec: &Value,
+    schema: &Value,
+    indent: usize,
+    depth: usize,
+    lines: &mut Vec<String>,
+) {
+    let mut schema = resolve(spec, schema);
+    if schema["type"] == "array" {
+        schema = resolve(spec, &schema["items"]);
+    }
+    let Some(properties) = object_properties(spec, schema) else {
+        return;
+    };
+    let required: Vec<&str> = schema["required"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_str)
+        .collect();
+    for (name, property) in &properties {
+        let mut line = format!("{}- {} ({}", "  ".repeat(indent), name, type_name(property));
+        if required.contains(&name.as_str()) {
+            line.push_str(", required");
+        }
+        line.push(')');
+        if let Some(description) = resolve(spec, property)["description"].as_str() {
+            line.push_str(": ");
+            line.push_str(description.trim());
+        }
+        lines.push(line);
+        if depth > 0 {
+            render_fields(spec, property, indent + 1, depth - 1, lines);
+        }
+    }
+}
+
+/// Properties of an object schema, `allOf` parts merged
+fn object_properties(spec: &Value, schema: &Value) -> Option<Map<String, Value>> {
+    let mut properties = schema["properties"]
+        .as_object()
+        .cloned()
+        .unwrap_or_default();
+    for part in schema["allOf"].as_array().into_iter().flatten() {
+        if let Some(more) = object_properties(spec, resolve(spec, part)) {
+            properties.extend(more);
+        }
+    }
+    (!properties.is_empty()).then_some(properties)
+}
+
+/// Short type of a schema: its `$ref` name, `array of X`, or its type and format
+fn type_name(schema: &Value) -> String {
+    if let Some(reference) = schema["$ref"].as_str() {
+        return reference
+            .rsplit('/')
+            .next()
+            .unwrap_or(reference)
+            .to_string();
+    }
+    match schema["type"].as_str() {
+        Some("array") => format!("array of {}", type_name(&schema["items"])),
+        Some(kind) => match schema["format"].as_str() {
+            Some(format) => format!("{}/{}", kind, format),
+            None => kind.to_string(),
+        },
+        None => {
+            let variants = schema.get("oneOf").or(schema.get("anyOf"));
+            if let Some(variants) = variants.and_then(Value::as_array) {
+                variants
+                    .iter()
+                    .map(type_name)
+                    .collect::<Vec<_>>()
+                    .join(" | ")
+            } else if schema.get("properties").is_some() || schema.get("allOf").is_some() {
+                "object".to_string()
+            } else {
+                "any".to_string()
+            }
+        }
+    }
+}
+
+/// Follow a local `$ref` (`#/components/schemas/Pet`); other values as is
+fn resolve<'a>(spec: &'a Value, value: &'a Value) -> &'a Value {
+    let mut value = value;
+    // Bounded so reference cycles cannot loop forever
+    for _ in 0..8 {
+        let Some(pointer) = value["$ref"].as_str().and_then(|r| r.strip_prefix('#')) else {
+            break;
+        };
+        match spec.pointer(pointer) {
+            Some(target) => value = target,
+            None => break,
+        }
+    }
+    value
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PETSTORE: &str = r#"openapi: 3.0.0
+info:
+  title: Petstore
+  version: 1.0.0
+paths:
+  /pets/{petId}:
+    parameters:
+      - name: petId
+        in: path
+        required: true
+        schema:
+          type: string
+    get:
+      tags: [pets]
+      summary: Info for a specific pet
+      operationId: showPetById
+      responses:
+        200:
+          description: Expected response to a valid request
+          content:
+            application/json:
+              schema:
+                $ref: '#/components/schemas/Pet'
+    delete:
+      summary: Delete a pet
+      responses:
+        '204':
+          description: Deleted
+components:
+  schemas:
+    Pet:
+      type: object
+      required: [id]
+      properties:
+        id:
+          type: integer
+          format: int64
+        name:
+          type: string
+          description: Display name
+"#;
+
+    #[test]
+    fn test_detects_json_and_yaml_specs() {
+        assert!(is_spec(PETSTORE.as_bytes()));
+        assert!(is_spec(br#"{"swagger": "2.0", "info": {}, "paths": {}}"#));
+        assert!(!is_spec(br#"{"name": "package", "version": "1.0.0"}"#));
+        assert!(!is_spec(b"# openapi: notes\npaths: none"));
+        assert!(!is_spec(b"<html><body>openapi:</body></html>"));
+    }
+
+    #[test]
+    fn test_operations_render_params_and_schemas() {
+        let spec = parse(PETSTORE.as_bytes()).unwrap();
+        assert_eq!(title(&spec), "Petstore v1.0.0");
+
+        let operations = operations(&spec);
+        let names: Vec<&str> = operations.iter().map(|op| op.name.as_str()).collect();
+        assert_eq!(names, vec!["GET /pets/{petId}", "DELETE /pets/{petId}"]);
+
+        let get = &operations[0];
+        assert_eq!(get.tag.as_deref(), Some("pets"));
+        assert!(get.text.contains("Operation ID: showPetById"));
+        assert!(get.text.contains("- petId (path, required, string)"));
+        assert!(get
+            .text
+            .contains("- 200: Expected response to a valid request"));
+        assert!(get.text.contains("- id (integer/int64, required)"));
+        assert!(get.text.contains("- name (string): Display name"));
+
+        assert_eq!(operations[1].tag, None);
+        assert!(operations[1]
+            .text
+            .contains("- petId (path, required, string)"));
+    }
+}
@@ -0,0 +1,264 @@
+// Copyright 2026 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Polite fetching: robots.txt rules, a minimum delay between requests to the
+//! same domain, and a cap on requests in flight per domain.
+//!
+//! robots.txt is read once per host and process (RFC 9309: the most specific
+//! user-agent group applies, the longest matching rule wins, `Allow` on ties).
+//! A missing or unreadable robots.txt allows everything.
+
+use anyhow::Result;
+use reqwest::Url;
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::{OnceCell, OwnedSemaphorePermit, Semaphore};
+use tokio::time::Instant;
+
+use crate::config::KnowledgeConfig;
+
+/// Product token matched against robots.txt `User-agent` lines
+pub const ROBOTS_AGENT: &str = "octobrain";
+
+/// The rules of one robots.txt that apply to us
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Robots {
+    /// (allow, path pattern)
+    rules: Vec<(bool, String)>,
+    pub crawl_delay: Option<Duration>,
+}
+
+impl Robots {
+    /// Parse robots.txt, keeping the groups for `agent` or, when none name
+    /// it, the `*` groups
+    pub fn parse(text: &str, agent: &str) -> Self {
+        struct Group {
+            agents: Vec<String>,
+            rules: Vec<(bool, String)>,
+            crawl_delay: Option<f64>,
+        }
+        let mut groups: Vec<Group> = Vec::new();
+        let mut reading_agents = false;
+        for line in text.lines() {
+            let line = line.split('#').next().unwrap_or_default().trim();
+            let Some((key, value)) = line.split_once(':') else {
+                continue;
+            };
+            let value = value.trim();
+            match key.trim().to_ascii_lowercase().as_str() {
+                "user-agent" => {
+                    if !reading_agents {
+                        groups.push(Group {
+                            agents: Vec::new(),
+                            rules: Vec::new(),
+                            crawl_delay: None,
+                        });
+                    }
+                    if let Some(group) = groups.last_mut() {
+                        group.agents.push(value.to_ascii_lowercase());
+                    }
+                    reading_agents = true;
+                }
+                rule @ ("allow" | "disallow") => {
+                    reading_agents = false;
+                    if let (Some(group), false) = (groups.last_mut(), value.is_empty()) {
+                        group.rules.push((rule == "allow", value.to_string()));
+                    }
+                }
+                "crawl-delay" => {
+                    reading_agents = false;
+                    if let Some(group) = groups.last_mut() {
+                        group.crawl_delay = value.parse().ok().filter(|d: &f64| *d >= 0.0);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        let agent = agent.to_ascii_lowercase();
+        let named = groups.iter().any(|g| g.agents.contains(&agent));
+        let wanted = if named { agent.as_str() } else { "*" };
+        let mut robots = Robots::default();
+        for group in groups
+            .iter()
+            .filter(|g| g.agents.iter().any(|a| a == wanted))
+        {
+            robots.rules.extend(group.rules.iter().cloned());
+            if let Some(delay) = group.crawl_delay {
+                let delay = Duration::from_secs_f64(delay);
+                robots.crawl_delay = Some(robots.crawl_delay.map_or(delay, |d| d.max(delay)));
+            }
+        }
+        robots
+    }
+
+    /// Whether `path` (path and query of a URL) may be fetched
+    pub fn allows(&self, path: &str) -> bool {
+        self.rules
+            .iter()
+            .filter(|(_, pattern)| pattern_matches(pattern, path))
+            .max_by_key(|(allow, pattern)| (pattern.len(), *allow))
+            .is_none_or(|(allow, _)| *allow)
+    }
+}
+
+/// robots.txt path pattern: a prefix, where `*` matches any run of
+/// characters and a trailing `$` anchors the end
+fn pattern_matches(pattern: &str, path: &str) -> bool {
+    let (pattern, anchored) = match pattern.strip_suffix('$') {
+        Some(pattern) => (pattern, true),
+        None => (pattern, false),
+    };
+    let mut parts = pattern.split('*');
+    let Some(mut rest) = path.strip_prefix(parts.next().unwrap_or_default()) else {
+        return false;
+    };
+    let parts: Vec<&str> = parts.collect();
+    for (i, part) in parts.iter().enumerate() {
+        if anchored && i + 1 == parts.len() {
+            return rest.ends_with(part);
+        }
+        match rest.find(part) {
+            Some(pos) => rest = &rest[pos + part.len()..],
+            None => return false,
+        }
+    }
+    !anchored || rest.is_empty()
+}
+
+/// Politeness state of one host
+struct HostGate {
+    robots: OnceCell<Option<Robots>>,
+    permits: Arc<Semaphore>,
+    next_request: tokio::sync::Mutex<Instant>,
+    delay: Duration,
+}
+
+/// Per-host robots.txt rules, request spacing and concurrency caps, shared
+/// by every fetch of a `KnowledgeManager`
+pub struct Politeness {
+    config: KnowledgeConfig,
+    hosts: Mutex<HashMap<String, Arc<HostGate>>>,
+}
+
+impl Politeness {
+    pub fn new(config: &KnowledgeConfig) -> Self {
+        Self {
+            config: config.clone(),
+            hosts: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// State of `host`, with its `[knowledge.sources]` overrides applied
+    fn gate(&self, host: &str) -> Arc<HostGate> {
+        let mut hosts = self.hosts.lock().unwrap_or_else(|e| e.into_inner());
+        hosts
+            .entry(host.to_string())
+            .or_insert_with(|| {
+                let source = self.config.source_config(host);
+                let delay = source
+                    .and_then(|s| s.request_delay_ms)
+                    .unwrap_or(self.config.request_delay_ms);
+                let concurrency = source
+                    .and_then(|s| s.concurrency)
+                    .unwrap_or(self.config.domain_concurrency);
+                Arc::new(HostGate {
+                    robots: OnceCell::new(),
+                    permits: Arc::new(Semaphore::new(concurrency.max(1))),
+                    next_request: tokio::sync::Mutex::new(Instant::now()),
+                    delay: Duration::from_millis(delay),
+                })
+            })
+            .clone()
+    }
+
+    /// Wait for a turn to fetch `url`: fails when robots.txt disallows it,
+    /// otherwise returns the domain slot to hold while the request runs.
+    /// `check_robots` is false for API-style hosts robots.txt does not cover;
+    /// `fetch_robots` is called once per host.
+    pub async fn acquire<F, Fut>(
+        &self,
+        url: &Url,
+        check_robots: bool,
+        fetch_robots: F,
+    ) -> Result<OwnedSemaphorePermit>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Option<Robots>>,
+    {
+        let gate = self.gate(url.host_str().unwrap_or_default());
+        let mut delay = gate.delay;
+        if self.config.respect_robots_txt && check_robots {
+            if let Some(robots) = gate.robots.get_or_init(fetch_robots).await {
+                let path = match url.query() {
+                    Some(query) => format!("{}?{}", url.path(), query),
+                    None => url.path().to_string(),
+                };
+                if !robots.allows(&path) {
+                    anyhow::bail!("{} is disallowed by robots.txt", url);
+                }
+                delay = delay.max(robots.crawl_delay.unwrap_or_default());
+            }
+        }
+
+        let permit = gate.permits.clone().acquire_owned().await?;
+        let mut next_request = gate.next_request.lock().await;
+        tokio::time::sleep_until(*next_request).await;
+        *next_request = Instant::now() + delay;
+        Ok(permit)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_robots_picks_most_specific_group() {
+        let text = "\
+User-agent: *
+Disallow: /
+
+# ours
+User-agent: Googlebot
+User-agent: Octobrain
+Disallow: /private
+Allow: /private/docs
+Crawl-delay: 2
+";
+        let robots = Robots::parse(text, ROBOTS_AGENT);
+        assert!(robots.allows("/guide"));
+        assert!(!robots.allows("/private/keys"));
+        assert!(robots.allows("/private/docs/intro"));
+        assert_eq!(robots.crawl_delay, Some(Duration::from_secs(2)));
+
+        let others = Robots::parse(text, "somebot");
+        assert!(!others.allows("/guide"));
+    }
+
+    #[test]
+    fn test_robots_wildcards_and_empty_rules() {
+        let robots = Robots::parse(
+            "User-agent: *\nDisallow: /*.pdf$\nDisallow: /search?*q=\nDisallow:\n",
+            ROBOTS_AGENT,
+        );
+        assert!(!robots.allows("/files/manual.pdf"));
+        assert!(robots.allows("/files/manual.pdf.html"));
+        assert!(!robots.allows("/search?lang=en&q=rust"));
+        assert!(robots.allows("/search"));
+        assert!(Robots::parse("", ROBOTS_AGENT).allows("/anything"));
+    }
+}
@@ -0,0 +1,584 @@
+// Copyright 2026 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Knowledge chunks in the shared Postgres database (`[memory].backend = "postgres"`).
+//!
+//! Mirrors `KnowledgeStore`: exact pgvector cosine search, optionally fused with
+//! full-text ranks by RRF, and the same session scoping rules.
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use deadpool_postgres::Pool;
+use futures::TryStreamExt;
+use pgvector::Vector;
+use std::collections::HashMap;
+use tokio_postgres::Row;
+
+use crate::knowledge::backend::KnowledgeBackend;
+use crate::knowledge::store::{push_line_matches, RRF_K};
+use crate::knowledge::types::{
+    ChunkContext, ChunkFilter, KnowledgeChunk, KnowledgeSearchResult, KnowledgeStats, MatchResult,
+};
+use crate::memory::search_scoring::keyword_terms;
+use crate::memory::types::MemoryConfig;
+use crate::postgres::{any_terms_tsquery, Filter};
+
+/// Chunk columns in the order `chunk_from_row` reads them.
+const CHUNK_COLUMNS: &str = "id, source, source_title, session_id, chunk_index, content, \
+     parent_content, section_path, char_start, char_end, collection";
+
+/// Number of entries in `CHUNK_COLUMNS`; extra selected columns follow them.
+const CHUNK_COLUMN_COUNT: usize = 11;
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS knowledge_chunks (
+    seq BIGSERIAL PRIMARY KEY,
+    id TEXT NOT NULL,
+    source TEXT NOT NULL,
+    source_title TEXT NOT NULL,
+    session_id TEXT,
+    chunk_index INTEGER NOT NULL,
+    content TEXT NOT NULL,
+    parent_content TEXT NOT NULL,
+    section_path TEXT[] NOT NULL,
+    char_start INTEGER NOT NULL,
+    char_end INTEGER NOT NULL,
+    collection TEXT,
+    content_hash TEXT NOT NULL,
+    chunk_hash TEXT NOT NULL DEFAULT '',
+    indexed_at TIMESTAMPTZ NOT NULL,
+    last_checked TIMESTAMPTZ NOT NULL,
+    embedding vector NOT NULL,
+    search_text tsvector GENERATED ALWAYS AS (to_tsvector('simple', content)) STORED
+);
+CREATE INDEX IF NOT EXISTS knowledge_chunks_source ON knowledge_chunks (source, session_id);
+CREATE INDEX IF NOT EXISTS knowledge_chunks_id ON knowledge_chunks (id);
+CREATE INDEX IF NOT EXISTS knowledge_chunks_search ON knowledge_chunks USING GIN (search_text);
+ALTER TABLE knowledge_chunks ADD COLUMN IF NOT EXISTS collection TEXT;
+CREATE INDEX IF NOT EXISTS knowledge_chunks_collection ON knowledge_chunks (collection);
+ALTER TABLE knowledge_chunks ADD COLUMN IF NOT EXISTS chunk_hash TEXT NOT NULL DEFAULT '';
+CREATE INDEX IF NOT EXISTS knowledge_chunks_chunk_hash ON knowledge_chunks (chunk_hash);
+";
+
+/// Decode a chunk row, pairing it with its session ID (None when persistent)
+fn chunk_from_row(row: &Row) -> Result<(KnowledgeChunk, Option<String>)> {
+    let parent_content: String = row.try_get(6)?;
+    let session_id: Option<String> = row.try_get(3)?;
+    let chunk = KnowledgeChunk {
+        id: row.try_get(0)?,
+        source: row.try_get(1)?,
+        source_title: row.try_get(2)?,
+        chunk_index: row.try_get(4)?,
+        content: row.try_get(5)?,
+        parent_content: (!parent_content.is_empty()).then_some(parent_content),
+        section_path: row.try_get(7)?,
+        char_start: row.try_get::<_, i32>(8)?.max(0) as usize,
+        char_end: row.try_get::<_, i32>(9)?.max(0) as usize,
+        collection: row.try_get(10)?,
+    };
+    Ok((chunk, session_id.filter(|s| !s.is_empty())))
+}
+
+pub struct PostgresKnowledgeStore {
+    pool: Pool,
+}
+
+impl PostgresKnowledgeStore {
+    /// Connect to `[memory].postgres_url` and create the table if missing.
+    pub async fn open(config: &MemoryConfig) -> Result<Self> {
+        let url = crate::postgres::resolve_url(config)?;
+        let pool = crate::postgres::connect(&url)?;
+        crate::postgres::ensure_schema(&pool, SCHEMA).await?;
+        Ok(Self { pool })
+    }
+
+    async fn client(&self) -> Result<deadpool_postgres::Client> {
+        self.pool
+            .get()
+            .await
+            .context("Failed to get a Postgres connection")
+    }
+
+    /// Optional source filter plus session visibility: persistent chunks and
+    /// those of `session_id`; every chunk when no session is given.
+    fn filter(source: Option<&str>, session_id: Option<&str>) -> Filter {
+        let mut filter = Filter::default();
+        if let Some(source) = source {
+            filter.push("source = ?", source.to_string());
+        }
+        if let Some(sid) = session_id {
+            filter.push("(session_id IS NULL OR session_id = ?)", sid.to_string());
+        }
+        filter
+    }
+
+    /// `filter` narrowed to the collection of `chunks`, when it names one
+    fn search_filter(chunks: ChunkFilter<'_>, session_id: Option<&str>) -> Filter {
+        let mut filter = Self::filter(chunks.source, session_id);
+        if let Some(collection) = chunks.collection {
+            filter.push("collection = ?", collection.to_string());
+        }
+        filter
+    }
+
+    async fn query_chunks(&self, filter: Filter) -> Result<Vec<(KnowledgeChunk, Option<String>)>> {
+        let sql = format!(
+            "SELECT {} FROM knowledge_chunks WHERE {}",
+            CHUNK_COLUMNS,
+            filter.sql()
+        );
+        let rows = self.client().await?.query(&sql, &filter.refs()).await?;
+        rows.iter().map(chunk_from_row).collect()
+    }
+
+    /// Nearest chunks with their cosine distance, closest first.
+    async fn nearest(
+        &self,
+        query_embedding: &[f32],
+        mut filter: Filter,
+        limit: usize,
+    ) -> Result<Vec<(KnowledgeChunk, Option<String>, f32)>> {
+        let dims = filter.bind(query_embedding.len() as i32);
+        let vector = filter.bind(Vector::from(query_embedding.to_vec()));
+        let limit = filter.bind(limit as i64);
+        let sql = format!(
+            "SELECT {}, (embedding <=> {vector})::REAL AS distance FROM knowledge_chunks \
+             WHERE vector_dims(embedding) = {dims} AND {} ORDER BY distance LIMIT {limit}",
+            CHUNK_COLUMNS,
+            filter.sql()
+        );
+        let rows = self.client().await?.query(&sql, &filter.refs()).await?;
+        rows.iter()
+            .map(|row| {
+                let (chunk, session_id) = chunk_from_row(row)?;
+                Ok((chunk, session_id, row.try_get(CHUNK_COLUMN_COUNT)?))
+            })
+            .collect()
+    }
+
+    /// Best full-text matches for `query_text`, best first.
+    async fn keyword_matches(
+        &self,
+        query_text: &str,
+        mut filter: Filter,
+        limit: usize,
+    ) -> Result<Vec<(KnowledgeChunk, Option<String>)>> {
+        let Some(tsquery) = any_terms_tsquery(&keyword_terms(query_text)) else {
+            return Ok(Vec::new());
+        };
+        let tsquery = filter.bind(tsquery);
+        let limit = filter.bind(limit as i64);
+        let sql = format!(
+            "SELECT {} FROM knowledge_chunks \
+             WHERE search_text @@ to_tsquery('simple', {tsquery}) AND {} \
+             ORDER BY ts_rank(search_text, to_tsquery('simple', {tsquery})) DESC LIMIT {limit}",
+            CHUNK_COLUMNS,
+            filter.sql()
+        );
+        let rows = self.client().await?.query(&sql, &filter.refs()).await?;
+        rows.iter().map(chunk_from_row).collect()
+    }
+}
+
+#[async_trait]
+impl KnowledgeBackend for PostgresKnowledgeStore {
+    async fn store_chunks(
+        &self,
+        source: &str,
+        source_title: &str,
+        content_hash: &str,
+        chunks: &[KnowledgeChunk],
+        embeddings: &[Vec<f32>],
+        session_id: Option<&str>,
+    ) -> Result<()> {
+        if chunks.len() != embeddings.len() {
+            return Err(anyhow::anyhow!(
+                "Got {} embeddings for {} chunks",
+                embeddings.len(),
+                chunks.len()
+            ));
+        }
+        let now = Utc::now();
+        let mut client = self.client().await?;
+        let tx = client.transaction().await?;
+
+        // Session-scoped writes replace only that session's chunks,
+        // persistent writes the whole source (full reindex)
+        match session_id {
+            Some(sid) => {
+                tx.execute(
+                    "DELETE FROM knowledge_chunks WHERE source = $1 AND session_id = $2",
+                    &[&source, &sid],
+                )
+                .await?
+            }
+            None => {
+                tx.execute("DELETE FROM knowledge_chunks WHERE source = $1", &[&source])
+                    .await?
+            }
+        };
+
+        let insert = tx
+            .prepare(&format!(
+                "INSERT INTO knowledge_chunks ({}, content_hash, chunk_hash, indexed_at, \
+                 last_checked, embedding) \
+                 VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $14, $15)",
+                CHUNK_COLUMNS
+            ))
+            .await?;
+        for (chunk, embedding) in chunks.iter().zip(embeddings) {
+            tx.execute(
+                &insert,
+                &[
+                    &chunk.id,
+                    &source,
+                    &source_title,
+                    &session_id,
+                    &chunk.chunk_index,
+                    &chunk.content,
+                    &chunk.parent_content.as_deref().unwrap_or(""),
+                    &chunk.section_path,
+                    &(chunk.char_start as i32),
+                    &(chunk.char_end as i32),
+                    &chunk.collection,
+                    &content_hash,
+                    &chunk.text_hash(),
+                    &now,
+                    &Vector::from(embedding.clone()),
+                ],
+            )
+            .await?;
+        }
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn search(
+        &self,
+        query_embedding: &[f32],
+        query_text: &str,
+        filter: ChunkFilter<'_>,
+        limit: usize,
+        use_hybrid: bool,
+        session_id: Option<&str>,
+    ) -> Result<Vec<KnowledgeSearchResult>> {
+        let vector_hits = self
+            .nearest(
+                query_embedding,
+                Self::search_filter(filter, session_id),
+                limit,
+            )
+            .await?;
+
+        if !use_hybrid {
+            // Vector search: relevance is 1.0 - cosine distance
+            return Ok(vector_hits
+                .into_iter()
+                .map(|(chunk, session_id, distance)| KnowledgeSearchResult {
+                    chunk,
+                    relevance_score: 1.0 - distance,
+                    session_scoped: session_id.is_some(),
+                    alternate_sources: Vec::new(),
+                })
+                .collect());
+        }
+
+        // Hybrid: fuse vector and full-text ranks with RRF, normalized by the
+        // 2/k maximum (rank 0 in both lists)
+        let keyword_hits = self
+            .keyword_matches(query_text, Self::search_filter(filter, session_id), limit)
+            .await?;
+        let mut fused: HashMap<String, (KnowledgeChunk, Option<String>, f32)> = HashMap::new();
+        let vector_ranked = vector_hits.into_iter().map(|(c, s, _)| (c, s));
+        for ranking in [vector_ranked.collect::<Vec<_>>(), keyword_hits] {
+            for (rank, (chunk, session_id)) in ranking.into_iter().enumerate() {
+                let score = 1.0 / (RRF_K + rank as f32);
+                fused
+                    .entry(format!(
+                        "{}\u{0}{}",
+                        chunk.id,
+                        session_id.as_deref().unwrap_or("")
+                    ))
+                    .or_insert((chunk, session_id, 0.0))
+                    .2 += score;
+            }
+        }
+        let max_rrf_score = 2.0 / RRF_K;
+        let mut results: Vec<KnowledgeSearchResult> = fused
+            .into_values()
+            .map(|(chunk, session_id, score)| KnowledgeSearchResult {
+                chunk,
+                relevance_score: (score / max_rrf_score).min(1.0),
+                session_scoped: session_id.is_some(),
+                alternate_sources: Vec::new(),
+            })
+            .collect();
+        results.sort_by(|a, b| b.relevance_score.total_cmp(&a.relevance_score));
+        results.truncate(limit);
+        Ok(results)
+    }
+
+    async fn get_chunk_with_context(
+        &self,
+        chunk_id: &str,
+        context: usize,
+        session_id: Option<&str>,
+    ) -> Result<Option<ChunkContext>> {
+        let mut filter = Self::filter(None, session_id);
+        filter.push("id = ?", chunk_id.to_string());
+        let Some((chunk, chunk_session)) = self.query_chunks(filter).await?.into_iter().next()
+        else {
+            return Ok(None);
+        };
+
+        let context = context.min(i32::MAX as usize) as i32;
+        let mut filter = Filter::default();
+        filter.push("source = ?", chunk.source.clone());
+        match chunk_session {
+            Some(sid) => filter.push("session_id = ?", sid),
+            None => filter.push_raw("session_id IS NULL"),
+        }
+        filter.push(
+            "chunk_index >= ?",
+            chunk.chunk_index.saturating_sub(context),
+        );
+        filter.push(
+            "chunk_index <= ?",
+            chunk.chunk_index.saturating_add(context),
+        );
+        filter.push("id != ?", chunk.id.clone());
+        let mut neighbours: Vec<KnowledgeChunk> = self
+            .query_chunks(filter)
+            .await?
+            .into_iter()
+            .map(|(c, _)| c)
+            .collect();
+        neighbours.sort_by_key(|c| c.chunk_index);
+        let (before, after): (Vec<_>, Vec<_>) = neighbours
+            .into_iter()
+            .partition(|c| c.chunk_index < chunk.chunk_index);
+
+        Ok(Some(ChunkContext {
+            chunk,
+            before,
+            after,
+        }))
+    }
+
+    async fn get_chunk_range(
+        &self,
+        source: &str,
+        first: i32,
+        last: i32,
+        session_id: Option<&str>,
+    ) -> Result<Vec<KnowledgeChunk>> {
+        let mut filter = Self::filter(Some(source), session_id);
+        filter.push("chunk_index >= ?", first);
+        filter.push("chunk_index <= ?", last);
+        Ok(self
+            .query_chunks(filter)
+            .await?
+            .into_iter()
+            .map(|(c, _)| c)
+            .collect())
+    }
+
+    async fn get_source_metadata(&self, source: &str) -> Result<Option<(String, DateTime<Utc>)>> {
+        let row = self
+            .client()
+            .await?
+            .query_opt(
+                "SELECT content_hash, last_checked FROM knowledge_chunks WHERE source = $1 LIMIT 1",
+                &[&source],
+            )
+            .await?;
+        row.map(|row| Ok((row.try_get(0)?, row.try_get(1)?)))
+            .transpose()
+    }
+
+    async fn delete_source(&self, source: &str) -> Result<()> {
+        self.client()
+            .await?
+            .execute("DELETE FROM knowledge_chunks WHERE source = $1", &[&source])
+            .await?;
+        Ok(())
+    }
+
+    async fn mark_checked(&self, source: &str) -> Result<()> {
+        self.client()
+            .await?
+            .execute(
+                "UPDATE knowledge_chunks SET last_checked = NOW() \
+                 WHERE source = $1 AND session_id IS NULL",
+                &[&source],
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn source_collection(&self, source: &str) -> Result<Option<String>> {
+        let row = self
+            .client()
+            .await?
+            .query_opt(
+                "SELECT collection FROM knowledge_chunks \
+                 WHERE source = $1 AND session_id IS NULL LIMIT 1",
+                &[&source],
+            )
+            .await?;
+        Ok(row.map(|row| row.try_get(0)).transpose()?.flatten())
+    }
+
+    async fn set_collection(&self, source: &str, collection: Option<&str>) -> Result<()> {
+        self.client()
+            .await?
+            .execute(
+                "UPDATE knowledge_chunks SET collection = $2 \
+                 WHERE source = $1 AND session_id IS NULL",
+                &[&source, &collection],
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn sources_with_text(
+        &self,
+        hashes: &[String],
+        session_id: Option<&str>,
+    ) -> Result<Vec<(String, String)>> {
+        if hashes.is_empty() {
+            return Ok(Vec::new());
+        }
+        let mut filter = Self::filter(None, session_id);
+        filter.push("chunk_hash = ANY(?)", hashes.to_vec());
+        let sql = format!(
+            "SELECT DISTINCT chunk_hash, source FROM knowledge_chunks WHERE {}",
+            filter.sql()
+        );
+        let rows = self.client().await?.query(&sql, &filter.refs()).await?;
+        rows.iter()
+            .map(|row| Ok((row.try_get(0)?, row.try_get(1)?)))
+            .collect()
+    }
+
+    async fn get_stats(&self) -> Result<KnowledgeStats> {
+        let row = self
+            .client()
+            .await?
+            .query_one(
+                "SELECT COUNT(DISTINCT source), COUNT(*), MIN(indexed_at), MAX(indexed_at) \
+                 FROM knowledge_chunks",
+                &[],
+            )
+            .await?;
+        Ok(KnowledgeStats {
+            total_sources: row.try_get::<_, i64>(0)? as usize,
+            total_chunks: row.try_get::<_, i64>(1)? as usize,
+            oldest_indexed: row.try_get(2)?,
+            newest_indexed: row.try_get(3)?,
+        })
+    }
+
+    async fn list_sources(
+        &self,
+        limit: Option<usize>,
+    ) -> Result<Vec<(String, String, usize, DateTime<Utc>)>> {
+        // LIMIT NULL means no limit
+        let limit = limit.map(|l| l as i64);
+        let rows = self
+            .client()
+            .await?
+            .query(
+                "SELECT source, MAX(source_title), COUNT(*), MAX(last_checked) \
+                 FROM knowledge_chunks GROUP BY source ORDER BY 4 DESC LIMIT $1",
+                &[&limit],
+            )
+            .await?;
+        rows.iter()
+            .map(|row| {
+                Ok((
+                    row.try_get(0)?,
+                    row.try_get(1)?,
+                    row.try_get::<_, i64>(2)? as usize,
+                    row.try_get(3)?,
+                ))
+            })
+            .collect()
+    }
+
+    async fn has_source_in_session(&self, source: &str, session_id: &str) -> Result<bool> {
+        let row = self
+            .client()
+            .await?
+            .query_one(
+                "SELECT EXISTS (SELECT 1 FROM knowledge_chunks \
+                 WHERE source = $1 AND session_id = $2)",
+                &[&source, &session_id],
+            )
+            .await?;
+        Ok(row.try_get(0)?)
+    }
+
+    async fn delete_by_source_and_session(&self, source: &str, session_id: &str) -> Result<()> {
+        self.client()
+            .await?
+            .execute(
+                "DELETE FROM knowledge_chunks WHERE source = $1 AND session_id = $2",
+                &[&source, &session_id],
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn cleanup_expired_sessions(&self, ttl_hours: u64) -> Result<()> {
+        let cutoff = Utc::now() - Duration::hours(ttl_hours as i64);
+        self.client()
+            .await?
+            .execute(
+                "DELETE FROM knowledge_chunks WHERE session_id IS NOT NULL AND indexed_at < $1",
+                &[&cutoff],
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn match_content(
+        &self,
+        pattern: &regex::Regex,
+        source: Option<&str>,
+        session_id: Option<&str>,
+    ) -> Result<Vec<MatchResult>> {
+        let filter = Self::filter(source, session_id);
+        let sql = format!(
+            "SELECT source, source_title, content FROM knowledge_chunks WHERE {}",
+            filter.sql()
+        );
+        // Stream rows so memory stays flat regardless of chunk count
+        let client = self.client().await?;
+        let rows = client.query_raw(&sql, filter.refs()).await?;
+        futures::pin_mut!(rows);
+        let mut matches = Vec::new();
+        while let Some(row) = rows.try_next().await? {
+            push_line_matches(
+                pattern,
+                row.try_get(0)?,
+                row.try_get(1)?,
+                row.try_get(2)?,
+                &mut matches,
+            );
+        }
+        Ok(matches)
+    }
+}
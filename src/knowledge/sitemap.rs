@@ -0,0 +1,185 @@
+// Copyright 2026 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Sitemap-driven indexing (`knowledge index --sitemap <url>`).
+//!
+//! A sitemap is either a `<urlset>` of pages or a `<sitemapindex>` pointing at
+//! further sitemaps; both list their targets in `<loc>` elements. Page URLs are
+//! kept or dropped by `*` glob patterns before anything is fetched.
+
+use anyhow::{Context, Result};
+use quick_xml::events::Event;
+use regex::Regex;
+
+/// Targets listed by one sitemap file
+#[derive(Debug, Clone, PartialEq)]
+pub enum Sitemap {
+    /// `<sitemapindex>`: URLs of further sitemaps
+    Index(Vec<String>),
+    /// `<urlset>`: URLs of pages
+    Pages(Vec<String>),
+}
+
+/// Parse a sitemap or sitemap index
+pub fn parse_sitemap(xml: &str) -> Result<Sitemap> {
+    let mut reader = quick_xml::Reader::from_str(xml);
+    let mut root: Option<String> = None;
+    let mut in_loc = false;
+    let mut locs = Vec::new();
+    loop {
+        match reader.read_event().context("Invalid sitemap XML")? {
+            Event::Start(e) => {
+                let name = String::from_utf8_lossy(e.local_name().as_ref()).to_lowercase();
+                if root.is_none() {
+                    root = Some(name);
+                } else if name == "loc" {
+                    in_loc = true;
+                    locs.push(String::new());
+                }
+            }
+            Event::End(e) if e.local_name().as_ref() == b"loc" => in_loc = false,
+            Event::Text(text) if in_loc => {
+                if let Some(loc) = locs.last_mut() {
+                    loc.push_str(&text.decode().context("Invalid sitemap text")?);
+                }
+            }
+            Event::GeneralRef(reference) if in_loc => {
+                if let Some(loc) = locs.last_mut() {
+                    loc.push_str(&resolve_entity(&String::from_utf8_lossy(&reference))?);
+                }
+            }
+            Event::CData(data) if in_loc => {
+                if let Some(loc) = locs.last_mut() {
+                    loc.push_str(&String::from_utf8_lossy(&data));
+                }
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+    let locs = locs
+        .into_iter()
+        .map(|l| l.trim().to_string())
+        .filter(|l| !l.is_empty())
+        .collect();
+    match root.as_deref() {
+        Some("urlset") => Ok(Sitemap::Pages(locs)),
+        Some("sitemapindex") => Ok(Sitemap::Index(locs)),
+        Some(other) => anyhow::bail!(
+            "Not a sitemap: root element is <{}>, expected <urlset> or <sitemapindex>",
+            other
+        ),
+        None => anyhow::bail!("Not a sitemap: no XML elements found"),
+    }
+}
+
+/// Text of an `&name;` reference: a predefined entity or a character reference
+fn resolve_entity(name: &str) -> Result<String> {
+    let code = match name.strip_prefix('#') {
+        Some(hex) if hex.starts_with(['x', 'X']) => u32::from_str_radix(&hex[1..], 16).ok(),
+        Some(decimal) => decimal.parse().ok(),
+        None => {
+            return quick_xml::escape::resolve_predefined_entity(name)
+                .map(str::to_string)
+                .with_context(|| format!("Unknown entity '&{};' in sitemap", name))
+        }
+    };
+    code.and_then(char::from_u32)
+        .map(String::from)
+        .with_context(|| format!("Invalid character reference '&{};' in sitemap", name))
+}
+
+/// Which sitemap URLs to index. Patterns match the whole URL, `*` standing
+/// for any run of characters. With no include patterns every URL is kept.
+#[derive(Debug, Default)]
+pub struct UrlFilter {
+    include: Vec<Regex>,
+    exclude: Vec<Regex>,
+}
+
+impl UrlFilter {
+    pub fn new(include: &[String], exclude: &[String]) -> Result<Self> {
+        let compile = |patterns: &[String]| -> Result<Vec<Regex>> {
+            patterns
+                .iter()
+                .map(|p| {
+                    let body = regex::escape(p.trim()).replace(r"\*", ".*");
+                    Regex::new(&format!("^{}$", body))
+                        .with_context(|| format!("Invalid URL pattern '{}'", p))
+                })
+                .collect()
+        };
+        Ok(Self {
+            include: compile(include)?,
+            exclude: compile(exclude)?,
+        })
+    }
+
+    pub fn matches(&self, url: &str) -> bool {
+        (self.include.is_empty() || self.include.iter().any(|r| r.is_match(url)))
+            && !self.exclude.iter().any(|r| r.is_match(url))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_urlset() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<urlset xmlns="http://www.sitemaps.org/schemas/sitemap/0.9">
+  <url><loc>https://docs.example.com/</loc><lastmod>2026-01-01</lastmod></url>
+  <url><loc>
+    https://docs.example.com/guide?a=1&amp;b=2&#x26;c=3
+  </loc></url>
+</urlset>"#;
+        assert_eq!(
+            parse_sitemap(xml).unwrap(),
+            Sitemap::Pages(vec![
+                "https://docs.example.com/".to_string(),
+                "https://docs.example.com/guide?a=1&b=2&c=3".to_string(),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_parse_sitemap_index() {
+        let xml = r#"<sitemapindex xmlns="http://www.sitemaps.org/schemas/sitemap/0.9">
+  <sitemap><loc>https://example.com/sitemap-docs.xml</loc></sitemap>
+  <sitemap><loc><![CDATA[https://example.com/sitemap-blog.xml]]></loc></sitemap>
+</sitemapindex>"#;
+        assert_eq!(
+            parse_sitemap(xml).unwrap(),
+            Sitemap::Index(vec![
+                "https://example.com/sitemap-docs.xml".to_string(),
+                "https://example.com/sitemap-blog.xml".to_string(),
+            ])
+        );
+        assert!(parse_sitemap("<html><body>nope</body></html>").is_err());
+    }
+
+    #[test]
+    fn test_url_filter_globs() {
+        let filter = UrlFilter::new(
+            &["https://docs.example.com/guide/*".to_string()],
+            &["*/v1/*".to_string()],
+        )
+        .unwrap();
+        assert!(filter.matches("https://docs.example.com/guide/intro"));
+        assert!(!filter.matches("https://docs.example.com/guide/v1/intro"));
+        assert!(!filter.matches("https://docs.example.com/blog/post"));
+        assert!(UrlFilter::default().matches("https://anything.example.com/"));
+    }
+}
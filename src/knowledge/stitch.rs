@@ -0,0 +1,246 @@
+// Copyright 2026 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+
+use crate::knowledge::types::{KnowledgeChunk, KnowledgeSearchResult, StitchedPassage};
+
+/// Hits at most this many chunk positions apart join one passage; the chunks
+/// between them are pulled in so the passage reads as continuous text.
+const MAX_GAP: i32 = 2;
+
+/// Shortest suffix/prefix match treated as chunk overlap rather than coincidence.
+const MIN_OVERLAP: usize = 20;
+
+/// A run of chunk positions in one source covered by one or more search hits
+#[derive(Debug, Clone, PartialEq)]
+pub struct HitRange {
+    pub source: String,
+    pub source_title: String,
+    pub first: i32,
+    pub last: i32,
+    pub relevance_score: f32,
+    pub hits: usize,
+}
+
+/// Group hits by source and merge nearby chunk positions into ranges, best
+/// scoring range first.
+pub fn hit_ranges(results: &[KnowledgeSearchResult]) -> Vec<HitRange> {
+    let mut by_source: HashMap<&str, Vec<&KnowledgeSearchResult>> = HashMap::new();
+    for result in results {
+        by_source
+            .entry(result.chunk.source.as_str())
+            .or_default()
+            .push(result);
+    }
+
+    let mut ranges: Vec<HitRange> = Vec::new();
+    for hits in by_source.into_values() {
+        let mut hits = hits;
+        hits.sort_by_key(|r| r.chunk.chunk_index);
+        let mut current: Option<HitRange> = None;
+        for hit in hits {
+            let index = hit.chunk.chunk_index;
+            match current.as_mut() {
+                Some(range) if index - range.last <= MAX_GAP => {
+                    range.last = range.last.max(index);
+                    range.relevance_score = range.relevance_score.max(hit.relevance_score);
+                    range.hits += 1;
+                }
+                _ => {
+                    ranges.extend(current.take());
+                    current = Some(HitRange {
+                        source: hit.chunk.source.clone(),
+                        source_title: hit.chunk.source_title.clone(),
+                        first: index,
+                        last: index,
+                        relevance_score: hit.relevance_score,
+                        hits: 1,
+                    });
+                }
+            }
+        }
+        ranges.extend(current);
+    }
+
+    ranges.sort_by(|a, b| {
+        b.relevance_score
+            .total_cmp(&a.relevance_score)
+            .then_with(|| a.source.cmp(&b.source))
+            .then(a.first.cmp(&b.first))
+    });
+    ranges
+}
+
+/// Join the chunks of `range` into one passage: chunk headers are dropped, a
+/// section heading is written whenever the section changes, and text repeated
+/// by chunk overlap appears once.
+pub fn stitch_range(range: &HitRange, mut chunks: Vec<KnowledgeChunk>) -> StitchedPassage {
+    chunks.sort_by_key(|c| c.chunk_index);
+    chunks.dedup_by_key(|c| c.chunk_index);
+
+    let mut text = String::new();
+    let mut section: Option<&[String]> = None;
+    for chunk in &chunks {
+        let body = chunk_body(&chunk.content);
+        if section != Some(chunk.section_path.as_slice()) {
+            if !text.is_empty() {
+                text.push_str("\n\n");
+            }
+            if !chunk.section_path.is_empty() {
+                text.push_str(&chunk.section_path.join(" > "));
+                text.push_str("\n\n");
+            }
+            text.push_str(body);
+            section = Some(chunk.section_path.as_slice());
+        } else {
+            append_deduplicated(&mut text, body);
+        }
+    }
+
+    StitchedPassage {
+        source: range.source.clone(),
+        source_title: range.source_title.clone(),
+        chunk_ids: chunks.iter().map(|c| c.id.clone()).collect(),
+        first_chunk: chunks.first().map_or(range.first, |c| c.chunk_index),
+        last_chunk: chunks.last().map_or(range.last, |c| c.chunk_index),
+        char_start: chunks.iter().map(|c| c.char_start).min().unwrap_or(0),
+        char_end: chunks.iter().map(|c| c.char_end).max().unwrap_or(0),
+        relevance_score: range.relevance_score,
+        hits: range.hits,
+        text,
+    }
+}
+
+/// Chunk text without the "title > section" header the chunker prepends.
+pub fn chunk_body(content: &str) -> &str {
+    content
+        .split_once("\n\n")
+        .map_or(content, |(_, body)| body)
+        .trim()
+}
+
+/// Append `next`, skipping its longest prefix that `text` already ends with.
+fn append_deduplicated(text: &mut String, next: &str) {
+    let longest = text.len().min(next.len());
+    let overlap = (MIN_OVERLAP..=longest).rev().find(|&k| {
+        next.is_char_boundary(k)
+            && text.is_char_boundary(text.len() - k)
+            && text.ends_with(&next[..k])
+    });
+    match overlap {
+        Some(k) => text.push_str(&next[k..]),
+        None => {
+            if !text.is_empty() {
+                text.push(' ');
+            }
+            text.push_str(next);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chunk(index: i32, section: &str, body: &str) -> KnowledgeChunk {
+        KnowledgeChunk {
+            id: format!("c{}", index),
+            source: "https://example.com/guide".to_string(),
+            source_title: "Guide".to_string(),
+            chunk_index: index,
+            content: format!("Guide > {}\n\n{}", section, body),
+            parent_content: None,
+            section_path: vec![section.to_string()],
+            char_start: index as usize * 100,
+            char_end: index as usize * 100 + body.len(),
+            collection: None,
+        }
+    }
+
+    fn hit(index: i32, source: &str, score: f32) -> KnowledgeSearchResult {
+        let mut chunk = chunk(index, "Intro", "body");
+        chunk.source = source.to_string();
+        KnowledgeSearchResult {
+            chunk,
+            relevance_score: score,
+            session_scoped: false,
+            alternate_sources: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_hit_ranges_merge_nearby_hits_per_source() {
+        let results = vec![
+            hit(7, "a", 0.4),
+            hit(1, "a", 0.9),
+            hit(3, "a", 0.5),
+            hit(2, "b", 0.6),
+        ];
+        let ranges = hit_ranges(&results);
+        assert_eq!(ranges.len(), 3);
+        assert_eq!(
+            (ranges[0].source.as_str(), ranges[0].first, ranges[0].last),
+            ("a", 1, 3)
+        );
+        assert_eq!(ranges[0].hits, 2);
+        assert_eq!(ranges[1].source, "b");
+        assert_eq!((ranges[2].first, ranges[2].last), (7, 7));
+    }
+
+    #[test]
+    fn test_stitch_removes_overlap_and_headers() {
+        let range = HitRange {
+            source: "https://example.com/guide".to_string(),
+            source_title: "Guide".to_string(),
+            first: 0,
+            last: 2,
+            relevance_score: 0.8,
+            hits: 2,
+        };
+        let chunks = vec![
+            chunk(
+                1,
+                "Retries",
+                "after three attempts the client gives up and reports.",
+            ),
+            chunk(
+                0,
+                "Retries",
+                "Requests are retried with backoff; after three attempts",
+            ),
+            chunk(
+                2,
+                "Timeouts",
+                "Each request times out after thirty seconds.",
+            ),
+        ];
+        let passage = stitch_range(&range, chunks);
+        assert_eq!(
+            passage.text,
+            "Retries\n\nRequests are retried with backoff; after three attempts the client \
+             gives up and reports.\n\nTimeouts\n\nEach request times out after thirty seconds."
+        );
+        assert_eq!(passage.chunk_ids, vec!["c0", "c1", "c2"]);
+        assert_eq!((passage.first_chunk, passage.last_chunk), (0, 2));
+        assert_eq!(passage.char_start, 0);
+    }
+
+    #[test]
+    fn test_chunks_without_overlap_are_joined() {
+        let mut text = "First sentence.".to_string();
+        append_deduplicated(&mut text, "Second sentence.");
+        assert_eq!(text, "First sentence. Second sentence.");
+    }
+}
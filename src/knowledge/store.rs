@@ -10,59 +10,101 @@ use chrono::{DateTime, Utc};
 use futures::TryStreamExt;
 use lance_index::scalar::FullTextSearchQuery;
 use lancedb::{
-    connect,
     index::Index,
-    query::{ExecutableQuery, QueryBase, QueryExecutionOptions},
-    table::OptimizeAction,
+    query::{ExecutableQuery, QueryBase, QueryExecutionOptions, Select},
+    table::{NewColumnTransform, OptimizeAction},
     Connection, DistanceType, Table,
 };
 use std::sync::Arc;
 
 use crate::arrow_helpers::{
-    f32_column_opt, i32_column, list_column, string_column, string_column_opt, timestamp_ms_column,
+    embedding_dimension, f32_column_opt, i32_column, list_column, string_column, string_column_opt,
+    timestamp_ms_column,
 };
-use crate::knowledge::types::{KnowledgeChunk, KnowledgeSearchResult, KnowledgeStats};
+use crate::config::Config;
+use crate::embedding::ModelGuard;
+use crate::knowledge::types::{
+    ChunkContext, ChunkFilter, KnowledgeChunk, KnowledgeSearchResult, KnowledgeStats,
+};
+use crate::memory::write_lock::WriteLock;
+use crate::schema::Migration;
 use crate::sql::escape_sql_literal;
 use chrono::Duration;
 
+/// Schema versions of the knowledge database, applied in place by `KnowledgeStore::migrate`
+const MIGRATIONS: [Migration; 2] = [
+    Migration {
+        version: 1,
+        description: "add knowledge_chunks.collection",
+    },
+    Migration {
+        version: 2,
+        description: "add knowledge_chunks.chunk_hash",
+    },
+];
+
 /// RRF (Reciprocal Rank Fusion) constant k
 /// Default value from LanceDB, based on research paper:
 /// https://plg.uwaterloo.ca/~gvcormac/cormacksigir09-rrf.pdf
 /// "Experiments indicate that k = 60 was near-optimal, but that the choice is not critical"
-const RRF_K: f32 = 60.0;
+pub(crate) const RRF_K: f32 = 60.0;
 
 pub struct KnowledgeStore {
     table: Table,
     schema: Arc<Schema>,
     vector_dim: usize,
+    /// Refuses writes once the table is stamped with another embedding model
+    model_guard: ModelGuard,
+    /// Serializes writes with the other processes using this database, so a
+    /// re-index (delete + add) never interleaves with another one
+    write_lock: WriteLock,
 }
 
 impl KnowledgeStore {
-    pub async fn new(vector_dim: usize) -> Result<Self> {
-        let db_path = crate::storage::get_system_storage_dir()?.join("knowledge");
+    pub async fn new(vector_dim: usize, config: &Config) -> Result<Self> {
+        let db_path = crate::storage::get_knowledge_database_path()?;
         std::fs::create_dir_all(&db_path)?;
 
-        let db = connect(db_path.to_str().unwrap()).execute().await?;
+        let db = crate::storage::lancedb_connection("knowledge", &db_path, &config.storage)
+            .execute()
+            .await?;
         let schema = Self::build_schema(vector_dim);
+        let write_lock = WriteLock::new(
+            &db_path,
+            std::time::Duration::from_secs(config.memory.write_lock_timeout_secs),
+        );
 
+        // Table creation races when two servers start together
+        let init_guard = write_lock.acquire().await?;
         Self::initialize_table(&db, &schema).await?;
+        drop(init_guard);
 
         // Cache the table handle — opened once, reused for the lifetime of this store
         let table = db.open_table("knowledge_chunks").execute().await?;
+        let model_guard = ModelGuard::open(
+            &db_path,
+            "knowledge_chunks",
+            &config.embedding.model,
+            vector_dim,
+            embedding_dimension(&table.schema().await?),
+        )?;
 
         Ok(Self {
             table,
             schema,
             vector_dim,
+            model_guard,
+            write_lock,
         })
     }
 
-    fn build_schema(vector_dim: usize) -> Arc<Schema> {
+    pub(crate) fn build_schema(vector_dim: usize) -> Arc<Schema> {
         Arc::new(Schema::new(vec![
             Field::new("id", DataType::Utf8, false),
             Field::new("source", DataType::Utf8, false),
             Field::new("source_title", DataType::Utf8, false),
             Field::new("session_id", DataType::Utf8, true),
+            Field::new("collection", DataType::Utf8, true),
             Field::new("chunk_index", DataType::Int32, false),
             Field::new("content", DataType::Utf8, false),
             Field::new("parent_content", DataType::Utf8, false),
@@ -74,6 +116,7 @@ impl KnowledgeStore {
             Field::new("char_start", DataType::Int32, false),
             Field::new("char_end", DataType::Int32, false),
             Field::new("content_hash", DataType::Utf8, false),
+            Field::new("chunk_hash", DataType::Utf8, false),
             Field::new(
                 "indexed_at",
                 DataType::Timestamp(TimeUnit::Millisecond, None),
@@ -95,12 +138,15 @@ impl KnowledgeStore {
         ]))
     }
 
-    async fn initialize_table(db: &Connection, schema: &Arc<Schema>) -> Result<()> {
+    pub(crate) async fn initialize_table(db: &Connection, schema: &Arc<Schema>) -> Result<()> {
         let table_names = db.table_names().execute().await?;
 
-        // Drop table if schema is outdated (missing columns)
         if table_names.contains(&"knowledge_chunks".to_string()) {
             let table = db.open_table("knowledge_chunks").execute().await?;
+            Self::migrate(db, &table).await?;
+
+            // Tables older than the first migration lack columns no migration adds;
+            // the indexed content can be fetched again, so they are rebuilt
             let existing_schema = table.schema().await?;
             let needs_recreate = schema
                 .fields()
@@ -133,6 +179,40 @@ impl KnowledgeStore {
 
         tracing::info!("Created FTS index on knowledge_chunks.content for hybrid search");
 
+        // Records the versions a new table already has
+        Self::migrate(db, &table).await?;
+        Ok(())
+    }
+
+    /// Upgrade `table` to the last of `MIGRATIONS`; each step only adds the
+    /// column when it is missing
+    async fn migrate(db: &Connection, table: &Table) -> Result<()> {
+        crate::schema::migrate(db, "knowledge", &MIGRATIONS, |version| async move {
+            match version {
+                1 => Self::add_missing_column(table, "collection", "CAST(NULL AS STRING)").await,
+                // Rows indexed before dedup get no hash until their source is re-indexed
+                2 => Self::add_missing_column(table, "chunk_hash", "''").await,
+                _ => anyhow::bail!("Unknown knowledge schema version {}", version),
+            }
+        })
+        .await?;
+        Ok(())
+    }
+
+    async fn add_missing_column(table: &Table, column: &str, expression: &str) -> Result<()> {
+        if table.schema().await?.field_with_name(column).is_ok() {
+            return Ok(());
+        }
+        table
+            .add_columns(
+                NewColumnTransform::SqlExpressions(vec![(
+                    column.to_string(),
+                    expression.to_string(),
+                )]),
+                None,
+            )
+            .await
+            .with_context(|| format!("Failed to add {} column to knowledge_chunks", column))?;
         Ok(())
     }
 
@@ -145,12 +225,19 @@ impl KnowledgeStore {
         embeddings: &[Vec<f32>],
         session_id: Option<&str>,
     ) -> Result<()> {
+        // Checked before the delete below so a refused write leaves the old chunks
+        self.model_guard.check()?;
+
+        // Held across the delete and the add, so readers in other processes
+        // never see the source half re-indexed by two writers
+        let _guard = self.write_lock.acquire().await?;
+
         // Delete existing chunks: session-scoped deletes only within session,
         // persistent deletes all chunks for source (full reindex)
         if let Some(sid) = session_id {
-            self.delete_by_source_and_session(source, sid).await?;
+            self.delete_rows(&session_filter(source, sid)).await?;
         } else {
-            self.delete_source(source).await?;
+            self.delete_rows(&source_filter(source)).await?;
         }
 
         if chunks.is_empty() {
@@ -165,6 +252,8 @@ impl KnowledgeStore {
         let sources: Vec<&str> = chunks.iter().map(|_| source).collect();
         let source_titles: Vec<&str> = chunks.iter().map(|_| source_title).collect();
         let session_ids: Vec<Option<&str>> = chunks.iter().map(|_| session_id).collect();
+        let collections: Vec<Option<&str>> =
+            chunks.iter().map(|c| c.collection.as_deref()).collect();
         let chunk_indices: Vec<i32> = chunks.iter().map(|c| c.chunk_index).collect();
         let contents: Vec<&str> = chunks.iter().map(|c| c.content.as_str()).collect();
         let parent_contents: Vec<&str> = chunks
@@ -174,6 +263,7 @@ impl KnowledgeStore {
         let char_starts: Vec<i32> = chunks.iter().map(|c| c.char_start as i32).collect();
         let char_ends: Vec<i32> = chunks.iter().map(|c| c.char_end as i32).collect();
         let content_hashes: Vec<&str> = chunks.iter().map(|_| content_hash).collect();
+        let chunk_hashes: Vec<String> = chunks.iter().map(KnowledgeChunk::text_hash).collect();
         let indexed_ats: Vec<i64> = chunks.iter().map(|_| now_millis).collect();
         let last_checkeds: Vec<i64> = chunks.iter().map(|_| now_millis).collect();
 
@@ -205,6 +295,7 @@ impl KnowledgeStore {
                 Arc::new(StringArray::from(sources)),
                 Arc::new(StringArray::from(source_titles)),
                 Arc::new(StringArray::from(session_ids)),
+                Arc::new(StringArray::from(collections)),
                 Arc::new(Int32Array::from(chunk_indices)),
                 Arc::new(StringArray::from(contents)),
                 Arc::new(StringArray::from(parent_contents)),
@@ -212,6 +303,7 @@ impl KnowledgeStore {
                 Arc::new(Int32Array::from(char_starts)),
                 Arc::new(Int32Array::from(char_ends)),
                 Arc::new(StringArray::from(content_hashes)),
+                Arc::new(StringArray::from(chunk_hashes)),
                 Arc::new(TimestampMillisecondArray::from(indexed_ats)),
                 Arc::new(TimestampMillisecondArray::from(last_checkeds)),
                 Arc::new(embedding_array),
@@ -230,11 +322,28 @@ impl KnowledgeStore {
         Ok(())
     }
 
+    /// Compact the chunk table and prune versions older than `keep`
+    pub async fn compact(
+        &self,
+        keep: Duration,
+    ) -> Result<Vec<crate::vector_optimizer::TableCompaction>> {
+        let _guard = self.write_lock.acquire().await?;
+        Ok(vec![
+            crate::vector_optimizer::compact_table(&self.table, keep).await?,
+        ])
+    }
+
+    /// Drop and rebuild the chunk vector index sized to the current row count
+    pub async fn rebuild_vector_index(&self) -> Result<crate::vector_optimizer::IndexRebuild> {
+        let _guard = self.write_lock.acquire().await?;
+        crate::vector_optimizer::rebuild_vector_index(&self.table, self.vector_dim).await
+    }
+
     pub async fn search(
         &self,
         query_embedding: &[f32],
         query_text: &str,
-        source: Option<&str>,
+        filter: ChunkFilter<'_>,
         limit: usize,
         use_hybrid: bool,
         session_id: Option<&str>,
@@ -254,9 +363,12 @@ impl KnowledgeStore {
         // Build filter conditions
         let mut filters = Vec::new();
 
-        if let Some(s) = source {
+        if let Some(s) = filter.source {
             filters.push(format!("source = '{}'", escape_sql_literal(s)));
         }
+        if let Some(c) = filter.collection {
+            filters.push(format!("collection = '{}'", escape_sql_literal(c)));
+        }
 
         // Session scoping: return persistent (NULL session_id) + current session's data
         if let Some(sid) = session_id {
@@ -286,16 +398,7 @@ impl KnowledgeStore {
                 continue;
             }
 
-            let ids = string_column(&batch, "id")?;
-            let sources = string_column(&batch, "source")?;
-            let source_titles = string_column(&batch, "source_title")?;
-            let session_ids = string_column_opt(&batch, "session_id");
-            let chunk_indices = i32_column(&batch, "chunk_index")?;
-            let contents = string_column(&batch, "content")?;
-            let parent_contents = string_column(&batch, "parent_content")?;
-            let section_paths = list_column(&batch, "section_path")?;
-            let char_starts = i32_column(&batch, "char_start")?;
-            let char_ends = i32_column(&batch, "char_end")?;
+            let chunks = batch_to_chunks(&batch)?;
             // Extract score column - hybrid search uses _relevance_score, vector search uses _distance
             // LanceDB hybrid search with RRF reranking returns _relevance_score (raw RRF scores)
             // RRF formula: score = sum of 1/(rank + k) for each ranking (vector + FTS)
@@ -323,43 +426,12 @@ impl KnowledgeStore {
                     .unwrap_or_else(|| vec![0.5; batch.num_rows()])
             };
 
-            for (i, &relevance_score) in relevance_scores.iter().enumerate() {
-                let section_path_array = section_paths.value(i);
-                let section_path_strings = section_path_array
-                    .as_any()
-                    .downcast_ref::<StringArray>()
-                    .unwrap();
-                let section_path: Vec<String> = (0..section_path_strings.len())
-                    .map(|j| section_path_strings.value(j).to_string())
-                    .collect();
-
-                let is_session_scoped = session_ids
-                    .map(|arr| !arr.is_null(i) && !arr.value(i).is_empty())
-                    .unwrap_or(false);
-
-                let chunk = KnowledgeChunk {
-                    id: ids.value(i).to_string(),
-                    source: sources.value(i).to_string(),
-                    source_title: source_titles.value(i).to_string(),
-                    chunk_index: chunk_indices.value(i),
-                    content: contents.value(i).to_string(),
-                    parent_content: {
-                        let p = parent_contents.value(i);
-                        if p.is_empty() {
-                            None
-                        } else {
-                            Some(p.to_string())
-                        }
-                    },
-                    section_path,
-                    char_start: char_starts.value(i) as usize,
-                    char_end: char_ends.value(i) as usize,
-                };
-
+            for ((chunk, session_id), relevance_score) in chunks.into_iter().zip(relevance_scores) {
                 search_results.push(KnowledgeSearchResult {
                     chunk,
                     relevance_score,
-                    session_scoped: is_session_scoped,
+                    session_scoped: session_id.is_some(),
+                    alternate_sources: Vec::new(),
                 });
             }
         }
@@ -367,6 +439,99 @@ impl KnowledgeStore {
         Ok(search_results)
     }
 
+    /// Fetch one chunk by ID with up to `context` neighbours on each side from the
+    /// same source (and session), ordered by chunk_index. Chunks from other
+    /// sessions are invisible, as in search.
+    pub async fn get_chunk_with_context(
+        &self,
+        chunk_id: &str,
+        context: usize,
+        session_id: Option<&str>,
+    ) -> Result<Option<ChunkContext>> {
+        let mut filter = format!("id = '{}'", escape_sql_literal(chunk_id));
+        if let Some(sid) = session_id {
+            filter.push_str(&format!(
+                " AND (session_id IS NULL OR session_id = '{}')",
+                escape_sql_literal(sid)
+            ));
+        }
+        let Some((chunk, chunk_session)) = self.query_chunks(filter).await?.into_iter().next()
+        else {
+            return Ok(None);
+        };
+
+        let context = context.min(i32::MAX as usize) as i32;
+        let neighbours = format!(
+            "source = '{}' AND {} AND chunk_index >= {} AND chunk_index <= {} AND id != '{}'",
+            escape_sql_literal(&chunk.source),
+            match &chunk_session {
+                Some(sid) => format!("session_id = '{}'", escape_sql_literal(sid)),
+                None => "session_id IS NULL".to_string(),
+            },
+            chunk.chunk_index.saturating_sub(context),
+            chunk.chunk_index.saturating_add(context),
+            escape_sql_literal(&chunk.id)
+        );
+        let mut neighbours: Vec<KnowledgeChunk> = self
+            .query_chunks(neighbours)
+            .await?
+            .into_iter()
+            .map(|(c, _)| c)
+            .collect();
+        neighbours.sort_by_key(|c| c.chunk_index);
+        let (before, after): (Vec<_>, Vec<_>) = neighbours
+            .into_iter()
+            .partition(|c| c.chunk_index < chunk.chunk_index);
+
+        Ok(Some(ChunkContext {
+            chunk,
+            before,
+            after,
+        }))
+    }
+
+    /// Chunks `first..=last` of `source` visible to `session_id` (persistent plus
+    /// that session's), in no particular order
+    pub async fn get_chunk_range(
+        &self,
+        source: &str,
+        first: i32,
+        last: i32,
+        session_id: Option<&str>,
+    ) -> Result<Vec<KnowledgeChunk>> {
+        let mut filter = format!(
+            "source = '{}' AND chunk_index >= {} AND chunk_index <= {}",
+            escape_sql_literal(source),
+            first,
+            last
+        );
+        if let Some(sid) = session_id {
+            filter.push_str(&format!(
+                " AND (session_id IS NULL OR session_id = '{}')",
+                escape_sql_literal(sid)
+            ));
+        }
+        Ok(self
+            .query_chunks(filter)
+            .await?
+            .into_iter()
+            .map(|(c, _)| c)
+            .collect())
+    }
+
+    /// All chunks matching `filter`, with their session ID
+    async fn query_chunks(&self, filter: String) -> Result<Vec<(KnowledgeChunk, Option<String>)>> {
+        let mut stream = self.table.query().only_if(filter).execute().await?;
+        let mut chunks = Vec::new();
+        while let Some(batch) = stream.try_next().await? {
+            if batch.num_rows() == 0 {
+                continue;
+            }
+            chunks.extend(batch_to_chunks(&batch)?);
+        }
+        Ok(chunks)
+    }
+
     pub async fn get_source_metadata(
         &self,
         source: &str,
@@ -397,12 +562,120 @@ impl KnowledgeStore {
     }
 
     pub async fn delete_source(&self, source: &str) -> Result<()> {
+        let _guard = self.write_lock.acquire().await?;
+        self.delete_rows(&source_filter(source)).await
+    }
+
+    /// Delete the rows matching `filter`; the caller holds the write lock
+    async fn delete_rows(&self, filter: &str) -> Result<()> {
+        self.table.delete(filter).await?;
+        Ok(())
+    }
+
+    pub async fn mark_checked(&self, source: &str) -> Result<()> {
+        let now_literal = format!(
+            "arrow_cast({}, 'Timestamp(Millisecond, None)')",
+            Utc::now().timestamp_millis()
+        );
+        let _guard = self.write_lock.acquire().await?;
         self.table
-            .delete(&format!("source = '{}'", escape_sql_literal(source)))
+            .update()
+            .only_if(format!(
+                "source = '{}' AND session_id IS NULL",
+                escape_sql_literal(source)
+            ))
+            .column("last_checked", now_literal)
+            .execute()
+            .await
+            .context("Failed to update last_checked")?;
+        Ok(())
+    }
+
+    pub async fn source_collection(&self, source: &str) -> Result<Option<String>> {
+        let results = self
+            .table
+            .query()
+            .only_if(format!(
+                "source = '{}' AND session_id IS NULL",
+                escape_sql_literal(source)
+            ))
+            .limit(1)
+            .execute()
             .await?;
+        let batches: Vec<RecordBatch> = results.try_collect().await?;
+        Ok(batches
+            .iter()
+            .find(|batch| batch.num_rows() > 0)
+            .and_then(|batch| string_column_opt(batch, "collection"))
+            .filter(|arr| !arr.is_null(0))
+            .map(|arr| arr.value(0).to_string()))
+    }
+
+    pub async fn set_collection(&self, source: &str, collection: Option<&str>) -> Result<()> {
+        let value = match collection {
+            Some(c) => format!("'{}'", escape_sql_literal(c)),
+            None => "NULL".to_string(),
+        };
+        let _guard = self.write_lock.acquire().await?;
+        self.table
+            .update()
+            .only_if(format!(
+                "source = '{}' AND session_id IS NULL",
+                escape_sql_literal(source)
+            ))
+            .column("collection", value)
+            .execute()
+            .await
+            .context("Failed to update collection")?;
         Ok(())
     }
 
+    pub async fn sources_with_text(
+        &self,
+        hashes: &[String],
+        session_id: Option<&str>,
+    ) -> Result<Vec<(String, String)>> {
+        if hashes.is_empty() {
+            return Ok(Vec::new());
+        }
+        let listed: Vec<String> = hashes
+            .iter()
+            .map(|h| format!("'{}'", escape_sql_literal(h)))
+            .collect();
+        let mut filter = format!("chunk_hash IN ({})", listed.join(", "));
+        if let Some(sid) = session_id {
+            filter.push_str(&format!(
+                " AND (session_id IS NULL OR session_id = '{}')",
+                escape_sql_literal(sid)
+            ));
+        }
+        let mut stream = self
+            .table
+            .query()
+            .only_if(filter)
+            .select(Select::Columns(vec![
+                "chunk_hash".to_string(),
+                "source".to_string(),
+            ]))
+            .execute()
+            .await?;
+        let mut holders = Vec::new();
+        while let Some(batch) = stream.try_next().await? {
+            let chunk_hashes = string_column(&batch, "chunk_hash")?;
+            let sources = string_column(&batch, "source")?;
+            for i in 0..batch.num_rows() {
+                let holder = (
+                    chunk_hashes.value(i).to_string(),
+                    sources.value(i).to_string(),
+                );
+                if !holders.contains(&holder) {
+                    holders.push(holder);
+                }
+            }
+        }
+        Ok(holders)
+    }
+
     pub async fn get_stats(&self) -> Result<KnowledgeStats> {
         let count = self.table.count_rows(None).await?;
 
@@ -517,27 +790,20 @@ impl KnowledgeStore {
 
     /// Delete stored content by source and session
     pub async fn delete_by_source_and_session(&self, source: &str, session_id: &str) -> Result<()> {
-        self.table
-            .delete(&format!(
-                "source = '{}' AND session_id = '{}'",
-                escape_sql_literal(source),
-                escape_sql_literal(session_id)
-            ))
-            .await?;
-        Ok(())
+        let _guard = self.write_lock.acquire().await?;
+        self.delete_rows(&session_filter(source, session_id)).await
     }
 
     /// Clean up expired session-scoped chunks (crash recovery)
     pub async fn cleanup_expired_sessions(&self, ttl_hours: u64) -> Result<()> {
         let cutoff = Utc::now() - Duration::hours(ttl_hours as i64);
         let cutoff_millis = cutoff.timestamp_millis();
-        self.table
-            .delete(&format!(
-                "session_id IS NOT NULL AND indexed_at < {}",
-                cutoff_millis
-            ))
-            .await?;
-        Ok(())
+        let _guard = self.write_lock.acquire().await?;
+        self.delete_rows(&format!(
+            "session_id IS NOT NULL AND indexed_at < {}",
+            cutoff_millis
+        ))
+        .await
     }
 
     /// Search indexed chunks by regex pattern, returning matching lines.
@@ -582,30 +848,13 @@ impl KnowledgeStore {
             let contents = string_column(&batch, "content")?;
 
             for i in 0..batch.num_rows() {
-                let source = sources.value(i).to_string();
-                let source_title = source_titles.value(i).to_string();
-                let content = contents.value(i);
-
-                for (line_num, line) in content.lines().enumerate() {
-                    if pattern.is_match(line) {
-                        let captures: Vec<String> = pattern
-                            .captures_iter(line)
-                            .flat_map(|c| {
-                                (1..c.len())
-                                    .filter_map(|g| c.get(g).map(|m| m.as_str().to_string()))
-                                    .collect::<Vec<_>>()
-                            })
-                            .collect();
-
-                        matches.push(crate::knowledge::types::MatchResult {
-                            source: source.clone(),
-                            source_title: source_title.clone(),
-                            line_number: line_num + 1,
-                            matched_line: line.to_string(),
-                            captures,
-                        });
-                    }
-                }
+                push_line_matches(
+                    pattern,
+                    sources.value(i),
+                    source_titles.value(i),
+                    contents.value(i),
+                    &mut matches,
+                );
             }
         }
 
@@ -613,6 +862,105 @@ impl KnowledgeStore {
     }
 }
 
+/// Append a match for every line of `content` that `pattern` matches.
+pub(crate) fn push_line_matches(
+    pattern: &regex::Regex,
+    source: &str,
+    source_title: &str,
+    content: &str,
+    matches: &mut Vec<crate::knowledge::types::MatchResult>,
+) {
+    for (line_num, line) in content.lines().enumerate() {
+        if pattern.is_match(line) {
+            let captures: Vec<String> = pattern
+                .captures_iter(line)
+                .flat_map(|c| {
+                    (1..c.len())
+                        .filter_map(|g| c.get(g).map(|m| m.as_str().to_string()))
+                        .collect::<Vec<_>>()
+                })
+                .collect();
+
+            matches.push(crate::knowledge::types::MatchResult {
+                source: source.to_string(),
+                source_title: source_title.to_string(),
+                line_number: line_num + 1,
+                matched_line: line.to_string(),
+                captures,
+            });
+        }
+    }
+}
+
+/// Decode chunk rows, pairing each with its session ID (None when persistent)
+fn batch_to_chunks(batch: &RecordBatch) -> Result<Vec<(KnowledgeChunk, Option<String>)>> {
+    let ids = string_column(batch, "id")?;
+    let sources = string_column(batch, "source")?;
+    let source_titles = string_column(batch, "source_title")?;
+    let session_ids = string_column_opt(batch, "session_id");
+    let collections = string_column_opt(batch, "collection");
+    let chunk_indices = i32_column(batch, "chunk_index")?;
+    let contents = string_column(batch, "content")?;
+    let parent_contents = string_column(batch, "parent_content")?;
+    let section_paths = list_column(batch, "section_path")?;
+    let char_starts = i32_column(batch, "char_start")?;
+    let char_ends = i32_column(batch, "char_end")?;
+
+    let mut chunks = Vec::with_capacity(batch.num_rows());
+    for i in 0..batch.num_rows() {
+        let section_path_array = section_paths.value(i);
+        let section_path_strings = section_path_array
+            .as_any()
+            .downcast_ref::<StringArray>()
+            .context("section_path items are not strings")?;
+        let section_path: Vec<String> = (0..section_path_strings.len())
+            .map(|j| section_path_strings.value(j).to_string())
+            .collect();
+
+        let session_id = session_ids
+            .filter(|arr| !arr.is_null(i) && !arr.value(i).is_empty())
+            .map(|arr| arr.value(i).to_string());
+
+        let chunk = KnowledgeChunk {
+            id: ids.value(i).to_string(),
+            source: sources.value(i).to_string(),
+            source_title: source_titles.value(i).to_string(),
+            chunk_index: chunk_indices.value(i),
+            content: contents.value(i).to_string(),
+            parent_content: {
+                let p = parent_contents.value(i);
+                if p.is_empty() {
+                    None
+                } else {
+                    Some(p.to_string())
+                }
+            },
+            section_path,
+            char_start: char_starts.value(i) as usize,
+            char_end: char_ends.value(i) as usize,
+            collection: collections
+                .filter(|arr| !arr.is_null(i))
+                .map(|arr| arr.value(i).to_string()),
+        };
+        chunks.push((chunk, session_id));
+    }
+    Ok(chunks)
+}
+
+/// Filter for the persistent chunks and sessions of `source`
+fn source_filter(source: &str) -> String {
+    format!("source = '{}'", escape_sql_literal(source))
+}
+
+/// Filter for the chunks of `source` indexed in `session_id`
+fn session_filter(source: &str, session_id: &str) -> String {
+    format!(
+        "source = '{}' AND session_id = '{}'",
+        escape_sql_literal(source),
+        escape_sql_literal(session_id)
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -622,17 +970,24 @@ mod tests {
         let db_path = std::env::temp_dir().join(format!("octobrain_test_{}", uuid::Uuid::new_v4()));
         std::fs::create_dir_all(&db_path).unwrap();
 
-        let db = connect(db_path.to_str().unwrap()).execute().await.unwrap();
+        let db = lancedb::connect(db_path.to_str().unwrap())
+            .execute()
+            .await
+            .unwrap();
         let schema = KnowledgeStore::build_schema(vector_dim);
         KnowledgeStore::initialize_table(&db, &schema)
             .await
             .unwrap();
         let table = db.open_table("knowledge_chunks").execute().await.unwrap();
+        let model_guard =
+            ModelGuard::open(&db_path, "knowledge_chunks", "test:model", vector_dim, None).unwrap();
 
         KnowledgeStore {
             table,
             schema,
             vector_dim,
+            model_guard,
+            write_lock: WriteLock::new(&db_path, std::time::Duration::from_secs(5)),
         }
     }
 
@@ -647,6 +1002,7 @@ mod tests {
             section_path: vec![],
             char_start: 0,
             char_end: content.len(),
+            collection: None,
         }
     }
 
@@ -675,7 +1031,7 @@ mod tests {
 
         // Search without session filter — should find persistent content
         let results = store
-            .search(&embedding, "hello", None, 10, false, None)
+            .search(&embedding, "hello", ChunkFilter::default(), 10, false, None)
             .await
             .unwrap();
 
@@ -684,6 +1040,50 @@ mod tests {
         assert_eq!(results[0].chunk.source, "https://example.com");
     }
 
+    #[tokio::test]
+    async fn test_reindex_waits_for_other_writer() {
+        let dim = 4;
+        let mut store = test_store(dim).await;
+        let embedding = dummy_embedding(dim);
+        let source = "https://example.com";
+        store
+            .store_chunks(
+                source,
+                "Example",
+                "hash1",
+                &[make_chunk("c1", source, "original content")],
+                std::slice::from_ref(&embedding),
+                None,
+            )
+            .await
+            .unwrap();
+
+        // Another process re-indexing the same database holds the lock
+        let db_dir = store.write_lock.path().parent().unwrap().to_path_buf();
+        store.write_lock = WriteLock::new(&db_dir, std::time::Duration::from_millis(50));
+        let other = WriteLock::new(&db_dir, std::time::Duration::ZERO);
+        let held = other.try_acquire().unwrap().expect("lock is free");
+
+        let err = store
+            .store_chunks(
+                source,
+                "Example",
+                "hash2",
+                &[make_chunk("c2", source, "replacement content")],
+                std::slice::from_ref(&embedding),
+                None,
+            )
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("busy"));
+        let (hash, _) = store.get_source_metadata(source).await.unwrap().unwrap();
+        assert_eq!(hash, "hash1");
+
+        drop(held);
+        store.delete_source(source).await.unwrap();
+        assert!(store.get_source_metadata(source).await.unwrap().is_none());
+    }
+
     #[tokio::test]
     async fn test_store_and_search_session_scoped() {
         let dim = 4;
@@ -705,7 +1105,14 @@ mod tests {
 
         // Search with matching session — should find it
         let results = store
-            .search(&embedding, "session", None, 10, false, Some("session-abc"))
+            .search(
+                &embedding,
+                "session",
+                ChunkFilter::default(),
+                10,
+                false,
+                Some("session-abc"),
+            )
             .await
             .unwrap();
 
@@ -736,7 +1143,14 @@ mod tests {
 
         // Search with session B — should NOT find session A's data
         let results = store
-            .search(&embedding, "secret", None, 10, false, Some("session-B"))
+            .search(
+                &embedding,
+                "secret",
+                ChunkFilter::default(),
+                10,
+                false,
+                Some("session-B"),
+            )
             .await
             .unwrap();
 
@@ -765,7 +1179,14 @@ mod tests {
 
         // Search with any session — should find persistent
         let results = store
-            .search(&embedding, "docs", None, 10, false, Some("any-session"))
+            .search(
+                &embedding,
+                "docs",
+                ChunkFilter::default(),
+                10,
+                false,
+                Some("any-session"),
+            )
             .await
             .unwrap();
 
@@ -877,7 +1298,14 @@ mod tests {
 
         // Search with matching session — should see both
         let results = store
-            .search(&embedding, "data", None, 10, false, Some("sess1"))
+            .search(
+                &embedding,
+                "data",
+                ChunkFilter::default(),
+                10,
+                false,
+                Some("sess1"),
+            )
             .await
             .unwrap();
 
@@ -888,4 +1316,89 @@ mod tests {
         assert_eq!(session_count, 1);
         assert_eq!(persistent_count, 1);
     }
+
+    #[tokio::test]
+    async fn test_get_chunk_with_context_orders_neighbours() {
+        let dim = 4;
+        let store = test_store(dim).await;
+        let source = "https://example.com/guide";
+        let chunks: Vec<KnowledgeChunk> = (0..5)
+            .map(|i| {
+                let mut c = make_chunk(&format!("c{}", i), source, &format!("part {}", i));
+                c.chunk_index = i;
+                c
+            })
+            .collect();
+        let embeddings = vec![dummy_embedding(dim); chunks.len()];
+        store
+            .store_chunks(source, "Guide", "hash1", &chunks, &embeddings, None)
+            .await
+            .unwrap();
+        // Same index range in another source must not leak in
+        let other = make_chunk("x1", "https://example.com/other", "other part");
+        store
+            .store_chunks(
+                "https://example.com/other",
+                "Other",
+                "hash2",
+                &[other],
+                &[dummy_embedding(dim)],
+                None,
+            )
+            .await
+            .unwrap();
+
+        let context = store
+            .get_chunk_with_context("c2", 1, None)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(context.chunk.id, "c2");
+        let ids = |cs: &[KnowledgeChunk]| cs.iter().map(|c| c.id.clone()).collect::<Vec<_>>();
+        assert_eq!(ids(&context.before), vec!["c1"]);
+        assert_eq!(ids(&context.after), vec!["c3"]);
+
+        let edge = store
+            .get_chunk_with_context("c0", 2, None)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(edge.before.is_empty());
+        assert_eq!(ids(&edge.after), vec!["c1", "c2"]);
+
+        assert!(store
+            .get_chunk_with_context("missing", 1, None)
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_get_chunk_hides_other_sessions() {
+        let dim = 4;
+        let store = test_store(dim).await;
+        let chunk = make_chunk("s1", "stored://notes", "private notes");
+        store
+            .store_chunks(
+                "stored://notes",
+                "Notes",
+                "hash1",
+                &[chunk],
+                &[dummy_embedding(dim)],
+                Some("session-a"),
+            )
+            .await
+            .unwrap();
+
+        assert!(store
+            .get_chunk_with_context("s1", 1, Some("session-b"))
+            .await
+            .unwrap()
+            .is_none());
+        assert!(store
+            .get_chunk_with_context("s1", 1, Some("session-a"))
+            .await
+            .unwrap()
+            .is_some());
+    }
 }
@@ -2,6 +2,7 @@
 //
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 
 /// Represents a chunk of knowledge content.
 ///
@@ -22,14 +23,69 @@ pub struct KnowledgeChunk {
     pub section_path: Vec<String>,
     pub char_start: usize,
     pub char_end: usize,
+    /// Collection the source was indexed into (`knowledge index --collection`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub collection: Option<String>,
+}
+
+impl KnowledgeChunk {
+    /// Hash of the chunk's text (child and parent), equal for chunks that
+    /// read the same whatever source they came from
+    pub fn text_hash(&self) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(self.content.as_bytes());
+        hasher.update([0]);
+        hasher.update(self.parent_content.as_deref().unwrap_or("").as_bytes());
+        hex::encode(hasher.finalize())
+    }
+}
+
+/// Which chunks a knowledge search looks at; both limits apply when set
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ChunkFilter<'a> {
+    /// Chunks of this one source
+    pub source: Option<&'a str>,
+    /// Chunks of the sources in this collection
+    pub collection: Option<&'a str>,
 }
 
 /// Search result with relevance score
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct KnowledgeSearchResult {
+    #[serde(flatten)]
     pub chunk: KnowledgeChunk,
     pub relevance_score: f32,
     pub session_scoped: bool,
+    /// Other sources holding the same text (mirrors), collapsed into this hit
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub alternate_sources: Vec<String>,
+}
+
+/// A chunk with its neighbours from the same source, each side in chunk_index order
+#[derive(Debug, Clone, Serialize)]
+pub struct ChunkContext {
+    pub chunk: KnowledgeChunk,
+    pub before: Vec<KnowledgeChunk>,
+    pub after: Vec<KnowledgeChunk>,
+}
+
+/// Nearby search hits from one source merged into a single passage, with the
+/// chunks between them filled in and overlapping text removed
+#[derive(Debug, Clone, Serialize)]
+pub struct StitchedPassage {
+    pub source: String,
+    pub source_title: String,
+    pub chunk_ids: Vec<String>,
+    /// First and last chunk_index covered (inclusive)
+    pub first_chunk: i32,
+    pub last_chunk: i32,
+    pub char_start: usize,
+    pub char_end: usize,
+    /// Best relevance among the merged hits
+    pub relevance_score: f32,
+    /// Number of search hits merged into this passage
+    pub hits: usize,
+    pub text: String,
 }
 
 /// Statistics about the knowledge base
@@ -48,6 +104,77 @@ pub struct IndexResult {
     pub chunks_created: usize,
     pub was_cached: bool,
     pub content_changed: bool,
+    /// Chunks whose text was already indexed from another source
+    pub duplicate_chunks: usize,
+}
+
+/// Step of indexing one source, as reported to `KnowledgeManager::set_progress`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IndexPhase {
+    Fetch,
+    Chunk,
+    Embed,
+    Store,
+}
+
+impl IndexPhase {
+    pub fn label(self) -> &'static str {
+        match self {
+            IndexPhase::Fetch => "Fetching",
+            IndexPhase::Chunk => "Chunking",
+            IndexPhase::Embed => "Embedding",
+            IndexPhase::Store => "Storing",
+        }
+    }
+}
+
+/// Outcome of re-indexing outdated sources
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct RefreshReport {
+    /// Indexed sources examined
+    pub checked: usize,
+    /// Re-fetched sources whose content changed and were re-indexed
+    pub refreshed: Vec<String>,
+    /// Re-fetched sources whose content hash was unchanged
+    #[serde(default)]
+    pub unchanged: Vec<String>,
+    pub failed: Vec<RefreshFailure>,
+}
+
+/// Which indexed sources `knowledge refresh` re-fetches
+#[derive(Debug, Clone, PartialEq)]
+pub enum RefreshScope {
+    /// Files changed on disk and URLs older than their refresh interval
+    Outdated,
+    /// Every persistent source, whatever its age
+    All,
+    /// One indexed source (URL or file path)
+    Source(String),
+}
+
+/// Outcome of indexing many pages (`knowledge index --depth N` or `--sitemap`)
+#[derive(Debug, Clone, Default)]
+pub struct CrawlReport {
+    pub indexed: Vec<IndexResult>,
+    pub failed: Vec<RefreshFailure>,
+}
+
+/// Outcome of `knowledge index-repo`
+#[derive(Debug, Clone)]
+pub struct RepoReport {
+    /// Branch the files were read from
+    pub branch: String,
+    pub report: CrawlReport,
+    /// GitHub listed only part of the repository
+    pub truncated: bool,
+    /// Why the wiki was skipped, when it could not be read
+    pub wiki_error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RefreshFailure {
+    pub source: String,
+    pub error: String,
 }
 
 /// Result of a store operation
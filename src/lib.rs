@@ -16,14 +16,32 @@
 //!
 //! Extracted from octocode, this library provides a focused memory management system
 //! using LanceDB for vector storage and semantic search capabilities.
+//!
+//! Start from [`Octobrain`]; the types re-exported here are the stable surface,
+//! deeper module paths may change between releases.
 
 pub mod arrow_helpers;
+pub mod brain;
 pub mod config;
 pub mod constants;
 pub mod embedding;
 pub mod knowledge;
+pub mod maintenance;
 pub mod mcp;
 pub mod memory;
+pub mod migrate;
+#[cfg(feature = "postgres")]
+pub mod postgres;
+pub mod schema;
+pub mod search;
 pub mod sql;
 pub mod storage;
 pub mod vector_optimizer;
+
+pub use brain::Octobrain;
+pub use config::Config;
+pub use knowledge::types::{KnowledgeChunk, KnowledgeSearchResult};
+pub use knowledge::KnowledgeManager;
+pub use memory::manager::MemorizeParams;
+pub use memory::types::{MemorySource, MemoryState};
+pub use memory::{Memory, MemoryManager, MemoryQuery, MemorySearchResult, MemoryType};
@@ -23,8 +23,14 @@ mod config;
 mod constants;
 mod embedding;
 mod knowledge;
+mod maintenance;
 mod mcp;
 mod memory;
+mod migrate;
+#[cfg(feature = "postgres")]
+mod postgres;
+mod schema;
+mod search;
 mod sql;
 mod storage;
 mod vector_optimizer;
@@ -41,18 +47,44 @@ async fn main() -> Result<()> {
     let cli = Cli::parse();
 
     // Initialize tracing subscriber for logging (skip for MCP command which uses file-only logging)
+    // Maintenance prints a JSON summary on stdout, so its logs go to stderr
     if !matches!(cli.command, Commands::Mcp { .. }) {
         let filter =
             EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("octobrain=info"));
 
-        fmt().with_env_filter(filter).with_target(false).init();
+        if matches!(cli.command, Commands::Maintenance { .. }) {
+            fmt()
+                .with_env_filter(filter)
+                .with_target(false)
+                .with_writer(std::io::stderr)
+                .init();
+        } else {
+            fmt().with_env_filter(filter).with_target(false).init();
+        }
     }
 
+    // `config` has to work on a file that no longer loads, so it runs first
+    let command = match cli.command {
+        Commands::Config { command } => {
+            if let Err(e) = commands::execute_config_command(command) {
+                eprintln!("Error: {}", e);
+                std::process::exit(1);
+            }
+            return Ok(());
+        }
+        command => command,
+    };
+
     // Load configuration
-    let config = Config::load()?;
+    let config = if cli.ephemeral {
+        Config::load_ephemeral()?
+    } else {
+        Config::load()?
+    };
 
     // Execute command
-    if let Err(e) = commands::execute(&config, cli.command).await {
+    let project = cli.project.as_deref().map(storage::resolve_project_key);
+    if let Err(e) = commands::execute(&config, command, project).await {
         eprintln!("Error: {}", e);
         std::process::exit(1);
     }
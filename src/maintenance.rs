@@ -0,0 +1,535 @@
+// Copyright 2026 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Periodic upkeep in one invocation, for cron and systemd timers.
+//!
+//! The memory manager already runs most passes lazily when it opens, but only
+//! for the project it is opened for and only when someone uses it. `octobrain
+//! maintenance` walks every project in the store instead. Each task is
+//! idempotent (marker-gated or a no-op when nothing is due), and the whole run
+//! holds `maintenance.lock` so overlapping timers skip rather than pile up.
+//!
+//! Long-running servers also refresh knowledge sources in the background
+//! (`spawn_knowledge_auto_refresh`) under the same lock, and `octobrain daemon`
+//! runs each task on its own `[maintenance]` interval (`run_daemon`).
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::time::Duration;
+use tokio::time::Instant;
+
+use crate::config::{Config, MaintenanceConfig};
+use crate::knowledge::types::RefreshReport;
+use crate::knowledge::KnowledgeManager;
+use crate::memory::backend::BackendKind;
+use crate::memory::write_lock::WriteLock;
+use crate::memory::MemoryManager;
+
+/// Lock file name inside the storage directory.
+pub const MAINTENANCE_LOCK_FILE: &str = "maintenance.lock";
+
+/// How soon the daemon retries tasks that found the maintenance lock held.
+const LOCKED_OUT_RETRY: Duration = Duration::from_secs(60);
+
+/// One kind of periodic upkeep
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum MaintenanceTask {
+    /// Drop old low-importance memories and expired knowledge sessions
+    Cleanup,
+    /// Usage-driven importance tuning: boost used memories, decay idle ones
+    Decay,
+    /// Optimize indexes and compact the memory tables
+    Optimize,
+    /// Re-index knowledge sources that changed or outgrew `outdating_days`
+    KnowledgeRefresh,
+}
+
+impl MaintenanceTask {
+    pub const ALL: [MaintenanceTask; 4] = [
+        Self::Cleanup,
+        Self::Decay,
+        Self::Optimize,
+        Self::KnowledgeRefresh,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Cleanup => "cleanup",
+            Self::Decay => "decay",
+            Self::Optimize => "optimize",
+            Self::KnowledgeRefresh => "knowledge-refresh",
+        }
+    }
+
+    pub fn parse(name: &str) -> Result<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|t| t.as_str() == name.trim())
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "Unknown maintenance task '{}'. Expected one of: cleanup, decay, optimize, knowledge-refresh",
+                    name.trim()
+                )
+            })
+    }
+
+    /// Parse a comma-separated task list; `None` selects every task.
+    /// Run order is fixed (cleanup before optimize) regardless of input order.
+    pub fn parse_list(list: Option<&str>) -> Result<Vec<Self>> {
+        let Some(list) = list else {
+            return Ok(Self::ALL.to_vec());
+        };
+        let requested = list
+            .split(',')
+            .filter(|s| !s.trim().is_empty())
+            .map(Self::parse)
+            .collect::<Result<Vec<_>>>()?;
+        if requested.is_empty() {
+            anyhow::bail!("No maintenance tasks given");
+        }
+        Ok(Self::ALL
+            .into_iter()
+            .filter(|t| requested.contains(t))
+            .collect())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TaskStatus {
+    Ok,
+    Failed,
+    Skipped,
+}
+
+/// Outcome of one task
+#[derive(Debug, Clone, Serialize)]
+pub struct TaskOutcome {
+    pub task: MaintenanceTask,
+    pub status: TaskStatus,
+    /// Task-specific counts
+    pub details: Value,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// JSON summary printed by `octobrain maintenance`
+#[derive(Debug, Clone, Serialize)]
+pub struct MaintenanceReport {
+    pub started_at: DateTime<Utc>,
+    pub duration_ms: u64,
+    /// Another run held the maintenance lock; nothing was done
+    pub locked_out: bool,
+    pub tasks: Vec<TaskOutcome>,
+}
+
+impl MaintenanceReport {
+    pub fn failures(&self) -> usize {
+        self.tasks
+            .iter()
+            .filter(|t| t.status == TaskStatus::Failed)
+            .count()
+    }
+}
+
+fn outcome(task: MaintenanceTask, result: Result<Value>) -> TaskOutcome {
+    match result {
+        Ok(details) => TaskOutcome {
+            task,
+            status: TaskStatus::Ok,
+            details,
+            error: None,
+        },
+        Err(e) => TaskOutcome {
+            task,
+            status: TaskStatus::Failed,
+            details: Value::Null,
+            error: Some(format!("{:#}", e)),
+        },
+    }
+}
+
+/// Run the given tasks over every project in the store.
+/// A failing task is recorded and the remaining tasks still run.
+pub async fn run_maintenance(
+    config: &Config,
+    tasks: &[MaintenanceTask],
+) -> Result<MaintenanceReport> {
+    if BackendKind::parse(&config.memory.backend)? == BackendKind::Ephemeral {
+        anyhow::bail!("Maintenance needs persistent storage; it cannot run with --ephemeral");
+    }
+    let started_at = Utc::now();
+    let started = std::time::Instant::now();
+
+    let lock = WriteLock::named(
+        &crate::storage::get_system_storage_dir()?,
+        MAINTENANCE_LOCK_FILE,
+        Duration::ZERO,
+    );
+    let Some(_guard) = lock.try_acquire()? else {
+        return Ok(MaintenanceReport {
+            started_at,
+            duration_ms: 0,
+            locked_out: true,
+            tasks: Vec::new(),
+        });
+    };
+
+    // Passes run explicitly below; the lazy ones in MemoryManager::new would
+    // otherwise consume their markers first. Stale-reference cleanup compares
+    // related files against the current working tree, so it only makes sense
+    // inside the project's own checkout and is left to the lazy pass there.
+    let mut scoped_config = config.clone();
+    scoped_config.memory.stale_ref_cleanup_enabled = false;
+    scoped_config.memory.importance_tuning_enabled = false;
+
+    let mut outcomes = Vec::new();
+    let projects = MemoryManager::new(&scoped_config, None, None)
+        .await?
+        .list_projects()
+        .await?;
+
+    for &task in tasks {
+        let result = match task {
+            MaintenanceTask::Cleanup => cleanup(config, &scoped_config, &projects).await,
+            MaintenanceTask::Decay if !config.memory.importance_tuning_enabled => {
+                outcomes.push(TaskOutcome {
+                    task,
+                    status: TaskStatus::Skipped,
+                    details: json!({ "reason": "[memory].importance_tuning_enabled is off" }),
+                    error: None,
+                });
+                continue;
+            }
+            MaintenanceTask::Decay => decay(&scoped_config, &projects).await,
+            MaintenanceTask::Optimize => optimize(&scoped_config).await,
+            MaintenanceTask::KnowledgeRefresh => knowledge_refresh(config).await,
+        };
+        outcomes.push(outcome(task, result));
+    }
+
+    Ok(MaintenanceReport {
+        started_at,
+        duration_ms: started.elapsed().as_millis() as u64,
+        locked_out: false,
+        tasks: outcomes,
+    })
+}
+
+/// When each task is next due under `octobrain daemon`
+#[derive(Debug, Clone)]
+pub struct DaemonSchedule {
+    /// Enabled tasks in run order, with their interval and next due time
+    entries: Vec<(MaintenanceTask, Duration, Instant)>,
+}
+
+impl DaemonSchedule {
+    /// Every task with a non-zero interval, all due at `start`.
+    pub fn new(config: &MaintenanceConfig, start: Instant) -> Self {
+        let minutes = |task| match task {
+            MaintenanceTask::Cleanup => config.cleanup_minutes,
+            MaintenanceTask::Decay => config.decay_minutes,
+            MaintenanceTask::Optimize => config.optimize_minutes,
+            MaintenanceTask::KnowledgeRefresh => config.knowledge_refresh_minutes,
+        };
+        let entries = MaintenanceTask::ALL
+            .into_iter()
+            .filter(|&task| minutes(task) > 0)
+            .map(|task| (task, Duration::from_secs(minutes(task) * 60), start))
+            .collect();
+        Self { entries }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Tasks due at `now`, in run order
+    pub fn due(&self, now: Instant) -> Vec<MaintenanceTask> {
+        self.entries
+            .iter()
+            .filter(|(_, _, next)| *next <= now)
+            .map(|(task, _, _)| *task)
+            .collect()
+    }
+
+    /// Earliest due time
+    pub fn next_due(&self) -> Option<Instant> {
+        self.entries.iter().map(|(_, _, next)| *next).min()
+    }
+
+    /// `tasks` ran at `now`; they are next due one interval later
+    pub fn completed(&mut self, tasks: &[MaintenanceTask], now: Instant) {
+        for (task, interval, next) in &mut self.entries {
+            if tasks.contains(task) {
+                *next = now + *interval;
+            }
+        }
+    }
+
+    /// `tasks` could not run; try them again at `at`
+    pub fn postpone(&mut self, tasks: &[MaintenanceTask], at: Instant) {
+        for (task, _, next) in &mut self.entries {
+            if tasks.contains(task) {
+                *next = at;
+            }
+        }
+    }
+}
+
+/// Run maintenance tasks on their `[maintenance]` intervals until the process
+/// is stopped. Every task runs once at start-up. A pass that finds the
+/// maintenance lock held (a timer-driven `octobrain maintenance`, a server's
+/// knowledge refresh) is retried a minute later.
+pub async fn run_daemon(config: &Config) -> Result<()> {
+    if BackendKind::parse(&config.memory.backend)? == BackendKind::Ephemeral {
+        anyhow::bail!("The daemon needs persistent storage; it cannot run with --ephemeral");
+    }
+    let mut schedule = DaemonSchedule::new(&config.maintenance, Instant::now());
+    if schedule.is_empty() {
+        anyhow::bail!("Every [maintenance] interval is 0; the daemon has nothing to do");
+    }
+    tracing::info!("Maintenance daemon started");
+    loop {
+        if let Some(next) = schedule.next_due() {
+            tokio::time::sleep_until(next).await;
+        }
+        let now = Instant::now();
+        let tasks = schedule.due(now);
+        if tasks.is_empty() {
+            continue;
+        }
+        match run_maintenance(config, &tasks).await {
+            Ok(report) if report.locked_out => {
+                tracing::info!("Maintenance lock held by another run; retrying in a minute");
+                schedule.postpone(&tasks, now + LOCKED_OUT_RETRY);
+            }
+            Ok(report) => {
+                for outcome in &report.tasks {
+                    match &outcome.error {
+                        Some(error) => tracing::warn!(
+                            task = outcome.task.as_str(),
+                            error = %error,
+                            "Maintenance task failed"
+                        ),
+                        None => tracing::info!(
+                            task = outcome.task.as_str(),
+                            details = %outcome.details,
+                            "Maintenance task finished"
+                        ),
+                    }
+                }
+                schedule.completed(&tasks, now);
+            }
+            Err(e) => {
+                tracing::warn!("Maintenance pass failed: {:#}", e);
+                schedule.completed(&tasks, now);
+            }
+        }
+    }
+}
+
+async fn cleanup(config: &Config, scoped_config: &Config, projects: &[String]) -> Result<Value> {
+    let mut removed = 0;
+    for project in projects {
+        let mut manager = MemoryManager::new(scoped_config, Some(project.clone()), None).await?;
+        removed += manager.cleanup().await?;
+    }
+    KnowledgeManager::new(config)
+        .await?
+        .cleanup_expired_sessions()
+        .await?;
+    Ok(json!({ "projects": projects.len(), "memories_removed": removed }))
+}
+
+async fn decay(scoped_config: &Config, projects: &[String]) -> Result<Value> {
+    let mut adjusted = 0;
+    for project in projects {
+        let mut manager = MemoryManager::new(scoped_config, Some(project.clone()), None).await?;
+        adjusted += manager.maybe_tune_importance().await?;
+    }
+    Ok(json!({ "projects": projects.len(), "memories_adjusted": adjusted }))
+}
+
+async fn optimize(scoped_config: &Config) -> Result<Value> {
+    MemoryManager::new(scoped_config, None, None)
+        .await?
+        .optimize()
+        .await?;
+    Ok(json!({}))
+}
+
+async fn knowledge_refresh(config: &Config) -> Result<Value> {
+    let report = KnowledgeManager::new(config)
+        .await?
+        .refresh_outdated()
+        .await?;
+    Ok(serde_json::to_value(report)?)
+}
+
+/// Re-check indexed knowledge sources every `[knowledge].auto_refresh_minutes`
+/// for as long as the returned task runs. Each pass takes the maintenance
+/// lock, so several servers (or a timer-driven `octobrain maintenance`) never
+/// refresh at once; a pass that finds it held is skipped. `None` when
+/// disabled or when storage is ephemeral.
+pub fn spawn_knowledge_auto_refresh(config: Config) -> Option<tokio::task::JoinHandle<()>> {
+    let minutes = config.knowledge.auto_refresh_minutes;
+    let ephemeral = BackendKind::parse(&config.memory.backend).ok()? == BackendKind::Ephemeral;
+    if minutes == 0 || ephemeral {
+        return None;
+    }
+    Some(tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(Duration::from_secs(minutes * 60));
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        // The first tick completes immediately; searches already refresh
+        // outdated sources on demand, so start one interval in.
+        ticker.tick().await;
+        loop {
+            ticker.tick().await;
+            match knowledge_refresh_pass(&config).await {
+                Ok(Some(report)) => log_refresh(&report),
+                Ok(None) => {
+                    tracing::debug!("Knowledge auto-refresh skipped: maintenance lock held")
+                }
+                Err(e) => tracing::warn!("Knowledge auto-refresh failed: {:#}", e),
+            }
+        }
+    }))
+}
+
+async fn knowledge_refresh_pass(config: &Config) -> Result<Option<RefreshReport>> {
+    let lock = WriteLock::named(
+        &crate::storage::get_system_storage_dir()?,
+        MAINTENANCE_LOCK_FILE,
+        Duration::ZERO,
+    );
+    let Some(_guard) = lock.try_acquire()? else {
+        return Ok(None);
+    };
+    let report = KnowledgeManager::new(config)
+        .await?
+        .refresh_outdated()
+        .await?;
+    Ok(Some(report))
+}
+
+fn log_refresh(report: &RefreshReport) {
+    for source in &report.refreshed {
+        tracing::info!(source = %source, "Knowledge source changed; re-indexed");
+    }
+    for failure in &report.failed {
+        tracing::warn!(source = %failure.source, error = %failure.error, "Knowledge source refresh failed");
+    }
+    tracing::debug!(
+        checked = report.checked,
+        refreshed = report.refreshed.len(),
+        unchanged = report.unchanged.len(),
+        failed = report.failed.len(),
+        "Knowledge auto-refresh pass complete"
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_list_defaults_to_all_tasks() {
+        assert_eq!(
+            MaintenanceTask::parse_list(None).unwrap(),
+            MaintenanceTask::ALL.to_vec()
+        );
+    }
+
+    #[test]
+    fn test_parse_list_keeps_fixed_run_order() {
+        let tasks = MaintenanceTask::parse_list(Some("optimize, cleanup")).unwrap();
+        assert_eq!(
+            tasks,
+            vec![MaintenanceTask::Cleanup, MaintenanceTask::Optimize]
+        );
+    }
+
+    #[test]
+    fn test_parse_list_rejects_unknown_and_empty() {
+        assert!(MaintenanceTask::parse_list(Some("cleanup,vacuum")).is_err());
+        assert!(MaintenanceTask::parse_list(Some(" , ")).is_err());
+    }
+
+    #[test]
+    fn test_daemon_schedule_runs_enabled_tasks_on_their_intervals() {
+        let config = MaintenanceConfig {
+            cleanup_minutes: 60,
+            decay_minutes: 0,
+            optimize_minutes: 10,
+            knowledge_refresh_minutes: 10,
+        };
+        let start = Instant::now();
+        let mut schedule = DaemonSchedule::new(&config, start);
+        let all = schedule.due(start);
+        assert_eq!(
+            all,
+            vec![
+                MaintenanceTask::Cleanup,
+                MaintenanceTask::Optimize,
+                MaintenanceTask::KnowledgeRefresh
+            ]
+        );
+
+        schedule.completed(&all, start);
+        assert_eq!(schedule.next_due(), Some(start + Duration::from_secs(600)));
+        let later = start + Duration::from_secs(600);
+        assert_eq!(
+            schedule.due(later),
+            vec![MaintenanceTask::Optimize, MaintenanceTask::KnowledgeRefresh]
+        );
+
+        schedule.postpone(&[MaintenanceTask::Optimize], later + LOCKED_OUT_RETRY);
+        assert_eq!(schedule.due(later), vec![MaintenanceTask::KnowledgeRefresh]);
+        assert!(schedule
+            .due(later + LOCKED_OUT_RETRY)
+            .contains(&MaintenanceTask::Optimize));
+    }
+
+    #[test]
+    fn test_daemon_schedule_empty_when_all_disabled() {
+        let config = MaintenanceConfig {
+            cleanup_minutes: 0,
+            decay_minutes: 0,
+            optimize_minutes: 0,
+            knowledge_refresh_minutes: 0,
+        };
+        let schedule = DaemonSchedule::new(&config, Instant::now());
+        assert!(schedule.is_empty());
+        assert_eq!(schedule.next_due(), None);
+    }
+
+    #[test]
+    fn test_failures_counts_only_failed_tasks() {
+        let report = MaintenanceReport {
+            started_at: Utc::now(),
+            duration_ms: 0,
+            locked_out: false,
+            tasks: vec![
+                outcome(MaintenanceTask::Cleanup, Ok(json!({}))),
+                outcome(MaintenanceTask::Optimize, Err(anyhow::anyhow!("boom"))),
+            ],
+        };
+        assert_eq!(report.failures(), 1);
+        assert_eq!(report.tasks[1].error.as_deref(), Some("boom"));
+    }
+}
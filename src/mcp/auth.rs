@@ -0,0 +1,179 @@
+// Copyright 2026 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! API-key authentication for the HTTP transports.
+//!
+//! Keys come from `[server].api_keys`. Once any key is configured, requests
+//! without a valid one are answered with 401. A `read` key may use every MCP
+//! method, but only the tools known to be read-only; other tool calls are
+//! answered with 403, so a newly added tool needs a read_write key until it is
+//! listed here.
+
+use axum::body::{to_bytes, Body};
+use axum::extract::{Request, State};
+use axum::http::{HeaderMap, StatusCode};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use serde_json::Value;
+use std::sync::Arc;
+
+use crate::config::{KeyScope, ServerConfig};
+
+/// Largest request body a read-only key may send; it is read whole to find
+/// the tool calls in it
+const MAX_BODY_BYTES: usize = 16 * 1024 * 1024;
+
+/// Tools a read-only key may call; `knowledge` only with `READ_KNOWLEDGE_COMMANDS`
+pub(crate) const READ_TOOLS: &[&str] = &[
+    "remember",
+    "ask_memory",
+    "relationships",
+    "memory_stats",
+    "recall",
+    "knowledge",
+];
+
+/// `knowledge` commands a read-only key may run
+const READ_KNOWLEDGE_COMMANDS: &[&str] = &["search", "read", "match", "get"];
+
+/// Key sent with a request: `Authorization: Bearer <key>` or `X-API-Key: <key>`
+pub fn presented_key(headers: &HeaderMap) -> Option<&str> {
+    headers
+        .get(axum::http::header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "))
+        .or_else(|| headers.get("x-api-key").and_then(|v| v.to_str().ok()))
+        .map(str::trim)
+}
+
+/// The first call in a JSON-RPC message or batch that needs a read-write key:
+/// any tool outside `READ_TOOLS`, or `knowledge` with a command outside
+/// `READ_KNOWLEDGE_COMMANDS`. Searches that index a source on the fly only
+/// cache fetched content and count as reads.
+pub fn write_call(message: &Value) -> Option<String> {
+    if let Value::Array(batch) = message {
+        return batch.iter().find_map(write_call);
+    }
+    if message.get("method")?.as_str()? != "tools/call" {
+        return None;
+    }
+    let params = message.get("params")?;
+    let name = params.get("name")?.as_str()?;
+    let command = params
+        .get("arguments")
+        .and_then(|a| a.get("command"))
+        .and_then(|c| c.as_str());
+    match (name, command) {
+        ("knowledge", Some(command)) if READ_KNOWLEDGE_COMMANDS.contains(&command) => None,
+        ("knowledge", Some(command)) => Some(format!("knowledge {}", command)),
+        (name, _) if name != "knowledge" && READ_TOOLS.contains(&name) => None,
+        (name, _) => Some(name.to_string()),
+    }
+}
+
+/// Middleware in front of `/mcp`: requires a configured key once there is
+/// one, and keeps read-only keys away from write tools.
+pub async fn require_key(
+    State(server): State<Arc<ServerConfig>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    if server.api_keys.is_empty() {
+        return next.run(request).await;
+    }
+    let Some(key) = presented_key(request.headers()).and_then(|k| server.find_key(k)) else {
+        return (StatusCode::UNAUTHORIZED, "Missing or invalid API key").into_response();
+    };
+    if key.scope == KeyScope::ReadWrite {
+        return next.run(request).await;
+    }
+
+    let (parts, body) = request.into_parts();
+    let Ok(bytes) = to_bytes(body, MAX_BODY_BYTES).await else {
+        return (StatusCode::PAYLOAD_TOO_LARGE, "Request body too large").into_response();
+    };
+    let message = serde_json::from_slice::<Value>(&bytes).unwrap_or(Value::Null);
+    if let Some(call) = write_call(&message) {
+        return (
+            StatusCode::FORBIDDEN,
+            format!(
+                "API key '{}' is read-only; '{}' needs a read_write key",
+                key.name, call
+            ),
+        )
+            .into_response();
+    }
+    next.run(Request::from_parts(parts, Body::from(bytes)))
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn call(name: &str, arguments: Value) -> Value {
+        json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "tools/call",
+            "params": { "name": name, "arguments": arguments }
+        })
+    }
+
+    #[test]
+    fn test_write_calls_are_detected() {
+        assert_eq!(
+            write_call(&call("memorize", json!({}))).as_deref(),
+            Some("memorize")
+        );
+        assert_eq!(
+            write_call(&call(
+                "knowledge",
+                json!({ "command": "delete", "key": "k" })
+            ))
+            .as_deref(),
+            Some("knowledge delete")
+        );
+        assert!(write_call(&call("remember", json!({ "query": "cache" }))).is_none());
+        assert!(write_call(&call("knowledge", json!({ "command": "search" }))).is_none());
+        assert_eq!(
+            write_call(&call("knowledge", json!({}))).as_deref(),
+            Some("knowledge")
+        );
+        assert_eq!(
+            write_call(&call("new_tool", json!({}))).as_deref(),
+            Some("new_tool")
+        );
+        assert!(
+            write_call(&json!({ "jsonrpc": "2.0", "id": 2, "method": "tools/list" })).is_none()
+        );
+
+        let batch = json!([call("recall", json!({})), call("forget", json!({}))]);
+        assert_eq!(write_call(&batch).as_deref(), Some("forget"));
+    }
+
+    #[test]
+    fn test_presented_key_from_either_header() {
+        let mut headers = HeaderMap::new();
+        assert!(presented_key(&headers).is_none());
+        headers.insert("x-api-key", "abc".parse().unwrap());
+        assert_eq!(presented_key(&headers), Some("abc"));
+        headers.insert(
+            axum::http::header::AUTHORIZATION,
+            "Bearer xyz".parse().unwrap(),
+        );
+        assert_eq!(presented_key(&headers), Some("xyz"));
+    }
+}
@@ -0,0 +1,116 @@
+// Copyright 2026 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Two-step confirmation tokens for destructive MCP calls.
+//!
+//! The first call returns a preview and a token bound to the exact set of
+//! memory IDs it would delete; the second call must present that token. Tokens
+//! are stateless (`<expiry>.<digest>` signed with a per-process secret), so they
+//! also work when each call gets a fresh provider. A token is void once it
+//! expires or the matching set changes.
+
+use sha2::{Digest, Sha256};
+use std::sync::OnceLock;
+
+/// Per-process secret; tokens do not survive a server restart.
+static SECRET: OnceLock<String> = OnceLock::new();
+
+fn digest(expires_at: i64, ids: &[String]) -> String {
+    let secret = SECRET.get_or_init(|| uuid::Uuid::new_v4().to_string());
+    let mut sorted: Vec<&str> = ids.iter().map(String::as_str).collect();
+    sorted.sort_unstable();
+    let mut hasher = Sha256::new();
+    hasher.update(secret.as_bytes());
+    hasher.update(expires_at.to_le_bytes());
+    for id in sorted {
+        hasher.update(id.as_bytes());
+        hasher.update([0u8]);
+    }
+    hex::encode(hasher.finalize())[..24].to_string()
+}
+
+/// Why a presented token was not accepted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenRejection {
+    Malformed,
+    Expired,
+    /// The token was issued for a different set of memories
+    Mismatch,
+}
+
+impl TokenRejection {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Malformed => "the confirm token is malformed",
+            Self::Expired => "the confirm token has expired",
+            Self::Mismatch => "the matching memories changed since the token was issued",
+        }
+    }
+}
+
+/// Issue a token confirming deletion of exactly `ids`, valid for `ttl_secs`.
+pub fn issue(ids: &[String], ttl_secs: u64) -> String {
+    let expires_at = chrono::Utc::now().timestamp() + ttl_secs as i64;
+    format!("{}.{}", expires_at, digest(expires_at, ids))
+}
+
+/// Check that `token` was issued by this process for exactly `ids` and is still valid.
+pub fn verify(token: &str, ids: &[String]) -> Result<(), TokenRejection> {
+    let (expires_at, presented) = token
+        .trim()
+        .split_once('.')
+        .and_then(|(e, d)| Some((e.parse::<i64>().ok()?, d)))
+        .ok_or(TokenRejection::Malformed)?;
+    if chrono::Utc::now().timestamp() > expires_at {
+        return Err(TokenRejection::Expired);
+    }
+    if presented != digest(expires_at, ids) {
+        return Err(TokenRejection::Mismatch);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ids(values: &[&str]) -> Vec<String> {
+        values.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_token_round_trip_ignores_order() {
+        let token = issue(&ids(&["a", "b", "c"]), 60);
+        assert_eq!(verify(&token, &ids(&["c", "a", "b"])), Ok(()));
+    }
+
+    #[test]
+    fn test_token_is_bound_to_the_id_set() {
+        let token = issue(&ids(&["a", "b"]), 60);
+        assert_eq!(
+            verify(&token, &ids(&["a", "b", "c"])),
+            Err(TokenRejection::Mismatch)
+        );
+    }
+
+    #[test]
+    fn test_expired_and_malformed_tokens() {
+        let expired = format!("{}.{}", 0, digest(0, &ids(&["a"])));
+        assert_eq!(verify(&expired, &ids(&["a"])), Err(TokenRejection::Expired));
+        assert_eq!(
+            verify("not-a-token", &ids(&["a"])),
+            Err(TokenRejection::Malformed)
+        );
+    }
+}
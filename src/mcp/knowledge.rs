@@ -13,19 +13,43 @@
 // limitations under the License.
 
 use anyhow::Result;
+use serde_json::Value;
+use std::ops::Deref;
 use std::sync::Arc;
 
-use tokio::sync::Mutex;
+use tokio::sync::{Mutex, MutexGuard};
 
 use crate::config::Config;
+use crate::knowledge::formatting::{format_chunk_context, passages_json, search_results_json};
+use crate::knowledge::manager::IndexProgress;
+use crate::knowledge::types::{ChunkFilter, StitchedPassage};
 use crate::knowledge::KnowledgeManager;
 use crate::mcp::types::McpError;
+use crate::search::UnifiedHit;
 
 /// Knowledge tools provider
 #[derive(Clone)]
 pub struct KnowledgeProvider {
     knowledge_manager: Arc<Mutex<KnowledgeManager>>,
-    max_results: usize,
+    /// Receives indexing phases of the current tool call
+    progress: Option<IndexProgress>,
+}
+
+/// Manager locked for one call; its progress reporting stops on release
+struct ManagerGuard<'a>(MutexGuard<'a, KnowledgeManager>);
+
+impl Deref for ManagerGuard<'_> {
+    type Target = KnowledgeManager;
+
+    fn deref(&self) -> &KnowledgeManager {
+        &self.0
+    }
+}
+
+impl Drop for ManagerGuard<'_> {
+    fn drop(&mut self) {
+        self.0.set_progress(None);
+    }
 }
 
 impl KnowledgeProvider {
@@ -35,21 +59,65 @@ impl KnowledgeProvider {
                 format!("Failed to initialize knowledge manager: {}", e),
                 "knowledge_init",
             )
+            .caused_by(&e)
         })?;
 
         Ok(Self {
             knowledge_manager: Arc::new(Mutex::new(manager)),
-            max_results: config.knowledge.max_results,
+            progress: None,
         })
     }
 
-    /// Execute search command
+    /// This provider for one tool call, reporting the phases of any source
+    /// indexed during it to `progress`
+    pub fn with_progress(&self, progress: Option<IndexProgress>) -> Self {
+        Self {
+            knowledge_manager: self.knowledge_manager.clone(),
+            progress,
+        }
+    }
+
+    async fn manager(&self) -> ManagerGuard<'_> {
+        let mut manager = self.knowledge_manager.lock().await;
+        manager.set_progress(self.progress.clone());
+        ManagerGuard(manager)
+    }
+
+    /// Knowledge search for the `recall` tool, as labelled hits in rank order.
+    pub async fn recall_hits(
+        &self,
+        query: &str,
+        limit: usize,
+        session_id: &str,
+    ) -> Result<Vec<UnifiedHit>, McpError> {
+        let manager = self.manager().await;
+        let results = manager
+            .search(
+                query,
+                ChunkFilter::default(),
+                Some(session_id),
+                Some(limit),
+                None,
+            )
+            .await
+            .map_err(|e| {
+                McpError::internal_error(format!("Knowledge search failed: {}", e), "recall")
+                    .caused_by(&e)
+            })?;
+        Ok(results.iter().map(UnifiedHit::from_knowledge).collect())
+    }
+
+    /// Execute search command. Returns the text listing and the same hits as
+    /// structured JSON with chunk IDs, scores, section paths and char ranges.
     pub async fn execute_search(
         &self,
         query: Option<&str>,
-        source: Option<&str>,
+        filter: ChunkFilter<'_>,
+        limit: Option<usize>,
+        min_relevance: Option<f32>,
+        stitch: bool,
         session_id: &str,
-    ) -> Result<String, McpError> {
+    ) -> Result<(String, Value), McpError> {
         let query = query.ok_or_else(|| {
             McpError::invalid_params(
                 "Missing required parameter: query (required for search command)",
@@ -57,26 +125,44 @@ impl KnowledgeProvider {
             )
         })?;
 
-        let manager = self.knowledge_manager.lock().await;
+        let manager = self.manager().await;
         let results = manager
-            .search(query, source, Some(session_id))
+            .search(query, filter, Some(session_id), limit, min_relevance)
             .await
             .map_err(|e| {
                 McpError::internal_error(format!("Knowledge search failed: {}", e), "knowledge")
+                    .caused_by(&e)
             })?;
 
+        if stitch {
+            let passages = manager
+                .stitch(&results, Some(session_id))
+                .await
+                .map_err(|e| {
+                    McpError::internal_error(format!("Knowledge search failed: {}", e), "knowledge")
+                        .caused_by(&e)
+                })?;
+            return Ok((format_passages_plain(&passages), passages_json(&passages)));
+        }
+        let structured = search_results_json(&results);
         if results.is_empty() {
-            return Ok("No results found".to_string());
+            return Ok(("No results found".to_string(), structured));
         }
 
         let mut output = String::new();
-        for result in results.iter().take(self.max_results) {
+        for result in &results {
             output.push_str(&"=".repeat(50));
             output.push('\n');
             output.push_str(&result.chunk.source_title);
             output.push('\n');
             output.push_str(&result.chunk.source);
             output.push('\n');
+            if !result.alternate_sources.is_empty() {
+                output.push_str(&format!(
+                    "Also at: {}\n",
+                    result.alternate_sources.join(", ")
+                ));
+            }
 
             if result.session_scoped {
                 output.push_str("[SESSION] ");
@@ -86,6 +172,7 @@ impl KnowledgeProvider {
                 output.push_str(&result.chunk.section_path.join(" > "));
                 output.push('\n');
             }
+            output.push_str(&format!("Chunk ID: {}\n", result.chunk.id));
 
             // Show content preview (first 300 chars)
             let content_preview = if result.chunk.content.chars().count() > 300 {
@@ -103,7 +190,39 @@ impl KnowledgeProvider {
             output.push_str(&format!("Relevance: {}%\n\n", score_pct));
         }
 
-        Ok(output)
+        Ok((output, structured))
+    }
+
+    /// Execute get command — a chunk with its neighbours, as text and structured JSON
+    pub async fn execute_get(
+        &self,
+        chunk_id: Option<&str>,
+        context: Option<usize>,
+        session_id: &str,
+    ) -> Result<(String, Value), McpError> {
+        let chunk_id = chunk_id.ok_or_else(|| {
+            McpError::invalid_params(
+                "Missing required parameter: chunk_id (required for get command)",
+                "knowledge",
+            )
+        })?;
+
+        let manager = self.manager().await;
+        let chunk = manager
+            .get_chunk(chunk_id, context.unwrap_or(1), Some(session_id))
+            .await
+            .map_err(|e| {
+                McpError::internal_error(format!("Knowledge get failed: {}", e), "knowledge")
+                    .caused_by(&e)
+            })?
+            .ok_or_else(|| {
+                McpError::invalid_params(format!("Chunk not found: {}", chunk_id), "knowledge")
+            })?;
+
+        let structured = serde_json::to_value(&chunk).map_err(|e| {
+            McpError::internal_error(format!("Failed to serialize chunk: {}", e), "knowledge")
+        })?;
+        Ok((format_chunk_context(&chunk), structured))
     }
 
     /// Execute store command
@@ -126,12 +245,13 @@ impl KnowledgeProvider {
             )
         })?;
 
-        let manager = self.knowledge_manager.lock().await;
+        let manager = self.manager().await;
         let result = manager
             .store_content(key, content, session_id)
             .await
             .map_err(|e| {
                 McpError::internal_error(format!("Knowledge store failed: {}", e), "knowledge")
+                    .caused_by(&e)
             })?;
 
         Ok(format!(
@@ -153,9 +273,10 @@ impl KnowledgeProvider {
             )
         })?;
 
-        let manager = self.knowledge_manager.lock().await;
+        let manager = self.manager().await;
         manager.delete_content(key, session_id).await.map_err(|e| {
             McpError::internal_error(format!("Knowledge delete failed: {}", e), "knowledge")
+                .caused_by(&e)
         })?;
 
         Ok(format!("Deleted stored knowledge '{}'", key))
@@ -170,9 +291,10 @@ impl KnowledgeProvider {
             )
         })?;
 
-        let manager = self.knowledge_manager.lock().await;
+        let manager = self.manager().await;
         let result = manager.read(source).await.map_err(|e| {
             McpError::internal_error(format!("Knowledge read failed: {}", e), "knowledge")
+                .caused_by(&e)
         })?;
 
         let mut output = String::new();
@@ -209,12 +331,13 @@ impl KnowledgeProvider {
             McpError::invalid_params(format!("Invalid regex pattern: {}", e), "knowledge")
         })?;
 
-        let manager = self.knowledge_manager.lock().await;
+        let manager = self.manager().await;
         let results = manager
             .match_content(pattern, source, Some(session_id))
             .await
             .map_err(|e| {
                 McpError::internal_error(format!("Knowledge match failed: {}", e), "knowledge")
+                    .caused_by(&e)
             })?;
 
         if results.is_empty() {
@@ -239,3 +362,34 @@ impl KnowledgeProvider {
         Ok(output)
     }
 }
+
+/// Stitched passages as plain text; passages are shown in full since they
+/// replace the truncated per-chunk previews.
+fn format_passages_plain(passages: &[StitchedPassage]) -> String {
+    if passages.is_empty() {
+        return "No results found".to_string();
+    }
+
+    let mut output = String::new();
+    for passage in passages {
+        output.push_str(&"=".repeat(50));
+        output.push('\n');
+        output.push_str(&passage.source_title);
+        output.push('\n');
+        output.push_str(&passage.source);
+        output.push('\n');
+        output.push_str(&format!(
+            "Chunks {}-{} ({} hits, first chunk ID: {})\n",
+            passage.first_chunk,
+            passage.last_chunk,
+            passage.hits,
+            passage.chunk_ids.first().map(String::as_str).unwrap_or("-")
+        ));
+        output.push_str(&passage.text);
+        output.push('\n');
+
+        let score_pct = (passage.relevance_score * 100.0) as u32;
+        output.push_str(&format!("Relevance: {}%\n\n", score_pct));
+    }
+    output
+}
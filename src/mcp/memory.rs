@@ -22,14 +22,19 @@ use tracing::{debug, warn};
 
 use crate::config::Config;
 use crate::constants::MAX_QUERIES;
+use crate::mcp::confirm;
 use crate::mcp::types::McpError;
-use crate::memory::{MemoryManager, MemoryQuery, MemoryType};
+use crate::memory::manager::MemoryEdit;
+use crate::memory::types::DestructiveOpsConfig;
+use crate::memory::{Memory, MemoryManager, MemoryQuery, MemoryType};
+use crate::search::UnifiedHit;
 
 /// Memory tools provider
 #[derive(Clone)]
 pub struct MemoryProvider {
     memory_manager: Arc<Mutex<MemoryManager>>,
     working_directory: std::path::PathBuf,
+    destructive_ops: DestructiveOpsConfig,
 }
 
 impl MemoryProvider {
@@ -54,6 +59,7 @@ impl MemoryProvider {
                     format!("Failed to initialize memory manager: {}", e),
                     "memory_init",
                 )
+                .caused_by(&e)
             })?;
 
         if let Some(original) = original_dir {
@@ -63,6 +69,7 @@ impl MemoryProvider {
         Ok(Self {
             memory_manager: Arc::new(Mutex::new(manager)),
             working_directory,
+            destructive_ops: config.memory.destructive_ops.clone(),
         })
     }
 
@@ -171,6 +178,22 @@ impl MemoryProvider {
             .and_then(|v| v.as_str())
             .map(|s| crate::memory::types::MemorySource::from(s.to_string()));
 
+        let allow_secrets = arguments
+            .get("allow_secrets")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+
+        let attachments = arguments
+            .get("attachments")
+            .and_then(|v| v.as_array())
+            .map(|arr| {
+                arr.iter()
+                    .filter_map(|v| v.as_str().map(|s| s.trim().to_string()))
+                    .filter(|s| !s.is_empty())
+                    .take(10)
+                    .collect::<Vec<String>>()
+            });
+
         // Use structured logging instead of console output for MCP protocol compliance
         debug!(
             title = %title,
@@ -238,10 +261,16 @@ impl MemoryProvider {
                     tags,
                     related_files,
                     source,
+                    allow_secrets,
+                    attachments,
+                    image: None,
+                    custom_fields: None,
+                    created_by: None,
                 })
                 .await
                 .map_err(|e| {
                     McpError::internal_error(format!("Failed to store memory: {}", e), "memorize")
+                        .caused_by(&e)
                 })?;
 
             // Create requested relationships in the same call so the agent doesn't
@@ -249,6 +278,9 @@ impl MemoryProvider {
             let mut created_rels = 0usize;
             let mut close_targets: Vec<String> = Vec::new();
             for (target_id, rel_type, strength, description) in &related_specs {
+                let Ok(target_id) = manager_guard.resolve_memory_id(target_id).await else {
+                    continue;
+                };
                 if manager_guard
                     .create_relationship(
                         memory.id.clone(),
@@ -317,77 +349,32 @@ impl MemoryProvider {
     }
 
     /// Execute the remember tool
-    pub async fn execute_remember(&self, arguments: &Value) -> Result<String, McpError> {
-        // Parse queries - handle both string and array inputs
-        let queries: Vec<String> = match arguments.get("query") {
-            Some(Value::String(s)) => vec![s.clone()],
-            Some(Value::Array(arr)) => {
-                let queries: Vec<String> = arr
-                    .iter()
-                    .filter_map(|v| v.as_str().map(String::from))
-                    .collect();
-
-                if queries.is_empty() {
-                    return Err(McpError::invalid_params(
-                        "Invalid query array: must contain at least one non-empty string",
-                        "remember",
-                    ));
-                }
-
-                queries
-            }
-            _ => {
-                return Err(McpError::invalid_params(
-					"Missing required parameter 'query': must be a string or array of strings describing what to search for",
-					"remember"
-				));
-            }
+    /// Hybrid memory search for the `recall` tool, as labelled hits in rank order.
+    pub async fn recall_hits(
+        &self,
+        query: &str,
+        limit: usize,
+    ) -> Result<Vec<UnifiedHit>, McpError> {
+        let memory_query = MemoryQuery {
+            limit: Some(limit),
+            ..Default::default()
         };
+        let manager = self.memory_manager.lock().await;
+        let results = manager
+            .remember(query, Some(memory_query))
+            .await
+            .map_err(|e| {
+                McpError::internal_error(format!("Failed to search memories: {}", e), "recall")
+                    .caused_by(&e)
+            })?;
+        Ok(results.iter().map(UnifiedHit::from_memory).collect())
+    }
 
-        // Validate queries
-        if queries.len() > MAX_QUERIES {
-            return Err(McpError::invalid_params(
-				format!("Too many queries: maximum {} queries allowed, got {}. Use fewer, more specific terms.", MAX_QUERIES, queries.len()),
-				"remember"
-			));
-        }
-
-        for (i, query) in queries.iter().enumerate() {
-            // Ensure clean UTF-8 and validate query
-            let clean_query = String::from_utf8_lossy(query.as_bytes()).to_string();
-            let query = clean_query.trim();
-
-            if query.len() < 3 {
-                return Err(McpError::invalid_params(
-                    format!(
-                        "Invalid query {}: must be at least 3 characters long",
-                        i + 1
-                    ),
-                    "remember",
-                ));
-            }
-            if query.len() > 500 {
-                return Err(McpError::invalid_params(
-                    format!(
-                        "Invalid query {}: must be no more than 500 characters long",
-                        i + 1
-                    ),
-                    "remember",
-                ));
-            }
-            if query.is_empty() {
-                return Err(McpError::invalid_params(
-                    format!(
-                        "Invalid query {}: cannot be empty or whitespace only",
-                        i + 1
-                    ),
-                    "remember",
-                ));
-            }
-        }
+    pub async fn execute_remember(&self, arguments: &Value) -> Result<String, McpError> {
+        let queries = parse_queries(arguments, "remember")?;
 
         // Parse filters (absent or empty arrays become None)
-        let memory_types = parse_memory_types(arguments);
+        let memory_types = parse_memory_types(arguments, "memory_types");
         let tags = parse_string_array(arguments, "tags");
         let related_files = parse_string_array(arguments, "related_files");
 
@@ -402,7 +389,14 @@ impl MemoryProvider {
             memory_types,
             tags,
             related_files,
+            exclude_types: parse_memory_types(arguments, "exclude_types"),
+            exclude_tags: parse_string_array(arguments, "exclude_tags"),
+            exclude_files: parse_string_array(arguments, "exclude_files"),
             limit: Some(limit.min(50)),
+            explain: arguments
+                .get("explain")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false),
             ..Default::default()
         };
 
@@ -428,6 +422,7 @@ impl MemoryProvider {
                             format!("Failed to search memories: {}", e),
                             "remember",
                         )
+                        .caused_by(&e)
                     })?
             } else {
                 manager_guard
@@ -438,6 +433,7 @@ impl MemoryProvider {
                             format!("Failed to search memories: {}", e),
                             "remember",
                         )
+                        .caused_by(&e)
                     })?
             }
         };
@@ -526,78 +522,476 @@ impl MemoryProvider {
             );
         }
 
-        // Handle specific memory ID deletion
-        if let Some(memory_id) = arguments.get("memory_id").and_then(|v| v.as_str()) {
-            // Validate memory ID format
-            if memory_id.trim().is_empty() || memory_id.len() > 100 {
-                return Ok("❌ Invalid memory ID format".to_string());
-            }
+        // Resolve the exact set to delete first, so the confirm policy sees its size
+        let (targets, label) =
+            if let Some(memory_id) = arguments.get("memory_id").and_then(|v| v.as_str()) {
+                // Validate memory ID format
+                if memory_id.trim().is_empty() || memory_id.len() > 100 {
+                    return Ok("❌ Invalid memory ID format".to_string());
+                }
+                let manager_guard = self.memory_manager.lock().await;
+                let memory_id = match manager_guard.resolve_memory_id(memory_id).await {
+                    Ok(id) => id,
+                    Err(e) => return Ok(format!("❌ {}", e)),
+                };
+                match manager_guard.get_memory(&memory_id).await {
+                    Ok(Some(memory)) => (vec![memory], format!("Memory ID: {}", memory_id)),
+                    Ok(None) => return Ok(format!("❌ Memory not found: {}", memory_id)),
+                    Err(e) => return Ok(format!("❌ Failed to delete memory: {}", e)),
+                }
+            } else if let Some(query) = arguments.get("query").and_then(|v| v.as_str()) {
+                // Ensure clean UTF-8 query using lossy conversion
+                let clean_query = String::from_utf8_lossy(query.as_bytes()).to_string();
+                let query = clean_query.as_str();
 
-            // Use structured logging instead of console output for MCP protocol compliance
-            debug!(
-                memory_id = %memory_id,
-                "Forgetting memory by ID"
-            );
+                if query.len() < 3 || query.len() > 500 {
+                    return Ok("❌ Query must be between 3 and 500 characters".to_string());
+                }
+                // Parse filters (absent or empty arrays become None)
+                let memory_query = MemoryQuery {
+                    query_text: Some(query.to_string()),
+                    memory_types: parse_memory_types(arguments, "memory_types"),
+                    tags: parse_string_array(arguments, "tags"),
+                    exclude_types: parse_memory_types(arguments, "exclude_types"),
+                    exclude_tags: parse_string_array(arguments, "exclude_tags"),
+                    exclude_files: parse_string_array(arguments, "exclude_files"),
+                    ..Default::default()
+                };
 
-            // Execute deletion - removed timeout to allow operation to complete
-            let res = {
-                let mut manager_guard = self.memory_manager.lock().await;
-                manager_guard.forget(memory_id).await
+                // Use structured logging instead of console output for MCP protocol compliance
+                debug!(
+                    query = %query,
+                    "Forgetting memories matching query"
+                );
+
+                let manager_guard = self.memory_manager.lock().await;
+                match manager_guard.matching_memories(memory_query).await {
+                    Ok(memories) => (memories, format!("Query: \"{}\"", query)),
+                    Err(e) => return Ok(format!("❌ Failed to delete memories: {}", e)),
+                }
+            } else {
+                return Ok("❌ Either 'memory_id' or 'query' must be provided".to_string());
             };
-            match res {
-                Ok(_) => Ok(format!(
-                    "✅ Memory deleted successfully\n\nMemory ID: {}",
-                    memory_id
-                )),
-                Err(e) => {
-                    tracing::warn!("Memory deletion failed: {}", e);
-                    Ok(format!("❌ Failed to delete memory: {}", e))
+
+        if targets.is_empty() {
+            return Ok(format!(
+                "ℹ️  No memories matched — nothing deleted\n\n{}",
+                label
+            ));
+        }
+
+        let ids: Vec<String> = targets.iter().map(|m| m.id.clone()).collect();
+        if ids.len() > self.destructive_ops.confirm_threshold {
+            let presented = arguments.get("confirm_token").and_then(|v| v.as_str());
+            let rejection = match presented {
+                Some(token) => confirm::verify(token, &ids).err(),
+                None => Some(confirm::TokenRejection::Malformed),
+            };
+            if let Some(rejection) = rejection {
+                return Ok(confirmation_request(
+                    &targets,
+                    &label,
+                    presented.map(|_| rejection),
+                    &confirm::issue(&ids, self.destructive_ops.token_ttl_secs),
+                    self.destructive_ops.token_ttl_secs,
+                ));
+            }
+        }
+
+        debug!(count = ids.len(), "Forgetting memories");
+        let res = {
+            let mut manager_guard = self.memory_manager.lock().await;
+            manager_guard.forget_ids(&ids).await
+        };
+        match res {
+            Ok(1) => Ok(format!("✅ Memory deleted successfully\n\n{}", label)),
+            Ok(deleted_count) => Ok(format!(
+                "✅ {} memories deleted successfully\n\n{}",
+                deleted_count, label
+            )),
+            Err(e) => {
+                tracing::warn!("Memory deletion failed: {}", e);
+                Ok(format!("❌ Failed to delete memories: {}", e))
+            }
+        }
+    }
+
+    /// Answer a question from stored memories: hybrid search, then LLM synthesis
+    /// with `[memory-id]` citations.
+    pub async fn execute_ask_memory(&self, arguments: &Value) -> Result<String, McpError> {
+        let queries = parse_queries(arguments, "ask_memory")?;
+
+        let memory_query = MemoryQuery {
+            memory_types: parse_memory_types(arguments, "memory_types"),
+            tags: parse_string_array(arguments, "tags"),
+            related_files: parse_string_array(arguments, "related_files"),
+            ..Default::default()
+        };
+
+        let (answer, results) = {
+            let manager_guard = self.memory_manager.lock().await;
+            manager_guard
+                .answer(&queries, Some(memory_query))
+                .await
+                .map_err(|e| {
+                    McpError::internal_error(
+                        format!("Failed to synthesize answer: {}", e),
+                        "ask_memory",
+                    )
+                    .caused_by(&e)
+                })?
+        };
+
+        let mut output = answer.answer;
+        if !answer.citations.is_empty() {
+            output.push_str("\n\nSources:");
+            for id in &answer.citations {
+                if let Some(r) = results.iter().find(|r| &r.memory.id == id) {
+                    output.push_str(&format!("\n[{}] {}", id, r.memory.title));
                 }
             }
         }
-        // Handle query-based deletion
-        else if let Some(query) = arguments.get("query").and_then(|v| v.as_str()) {
-            // Ensure clean UTF-8 query using lossy conversion
-            let clean_query = String::from_utf8_lossy(query.as_bytes()).to_string();
-            let query = clean_query.as_str();
-
-            if query.len() < 3 || query.len() > 500 {
-                return Ok("❌ Query must be between 3 and 500 characters".to_string());
+        Ok(output)
+    }
+    pub async fn execute_feedback(&self, arguments: &Value) -> Result<String, McpError> {
+        let memory_id = arguments
+            .get("memory_id")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| {
+                McpError::invalid_params("Missing required parameter 'memory_id'", "feedback")
+            })?;
+        let helpful = arguments
+            .get("helpful")
+            .and_then(|v| v.as_bool())
+            .ok_or_else(|| {
+                McpError::invalid_params("Missing required parameter 'helpful'", "feedback")
+            })?;
+        let query = arguments
+            .get("query")
+            .and_then(|v| v.as_str())
+            .map(|q| q.to_string());
+
+        let manager_guard = self.memory_manager.lock().await;
+        let (memory_id, query) = manager_guard
+            .record_feedback(memory_id, helpful, query)
+            .await
+            .map_err(|e| {
+                McpError::internal_error(format!("Failed to record feedback: {}", e), "feedback")
+                    .caused_by(&e)
+            })?;
+        let verdict = if helpful { "helpful" } else { "unhelpful" };
+        Ok(if query.is_empty() {
+            format!("✅ Marked {} as {}", memory_id, verdict)
+        } else {
+            format!("✅ Marked {} as {} for \"{}\"", memory_id, verdict, query)
+        })
+    }
+
+    /// Execute the relate tool: one typed link between two existing memories
+    pub async fn execute_relate(&self, arguments: &Value) -> Result<String, McpError> {
+        let id_arg = |key: &str| {
+            arguments
+                .get(key)
+                .and_then(|v| v.as_str())
+                .map(str::trim)
+                .filter(|id| !id.is_empty() && id.len() <= 100)
+                .ok_or_else(|| {
+                    McpError::invalid_params(
+                        format!("Missing or invalid parameter '{}'", key),
+                        "relate",
+                    )
+                })
+        };
+        let source_id = id_arg("source_id")?;
+        let target_id = id_arg("target_id")?;
+        let relationship_type = arguments
+            .get("relationship_type")
+            .and_then(|v| v.as_str())
+            .map(crate::memory::types::RelationshipType::from)
+            .ok_or_else(|| {
+                McpError::invalid_params("Missing required parameter 'relationship_type'", "relate")
+            })?;
+        let strength = arguments
+            .get("strength")
+            .and_then(|v| v.as_f64())
+            .map(|v| (v as f32).clamp(0.0, 1.0))
+            .unwrap_or(0.8);
+        let description = arguments
+            .get("description")
+            .and_then(|v| v.as_str())
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty())
+            .unwrap_or_else(|| "Linked by agent".to_string());
+
+        let mut manager_guard = self.memory_manager.lock().await;
+        let mut ids = Vec::with_capacity(2);
+        for reference in [source_id, target_id] {
+            let id = manager_guard
+                .resolve_memory_id(reference)
+                .await
+                .map_err(|e| McpError::invalid_params(e.to_string(), "relate"))?;
+            match manager_guard.get_memory(&id).await {
+                Ok(Some(_)) => ids.push(id),
+                Ok(None) => {
+                    return Err(McpError::invalid_params(
+                        format!("Memory not found: {}", reference),
+                        "relate",
+                    ))
+                }
+                Err(e) => {
+                    return Err(McpError::internal_error(
+                        format!("Failed to load memory: {}", e),
+                        "relate",
+                    )
+                    .caused_by(&e))
+                }
             }
-            // Parse filters (absent or empty arrays become None)
-            let memory_types = parse_memory_types(arguments);
-            let tags = parse_string_array(arguments, "tags");
-
-            let memory_query = MemoryQuery {
-                query_text: Some(query.to_string()),
-                memory_types,
-                tags,
-                ..Default::default()
-            };
+        }
+        let target_id = ids.pop().unwrap_or_default();
+        let source_id = ids.pop().unwrap_or_default();
+        if source_id == target_id {
+            return Err(McpError::invalid_params(
+                "A memory cannot be related to itself",
+                "relate",
+            ));
+        }
 
-            // Use structured logging instead of console output for MCP protocol compliance
-            debug!(
-                query = %query,
-                "Forgetting memories matching query"
-            );
+        let relationship = manager_guard
+            .create_relationship(
+                source_id,
+                target_id,
+                relationship_type,
+                strength,
+                description,
+            )
+            .await
+            .map_err(|e| {
+                McpError::internal_error(format!("Failed to create relationship: {}", e), "relate")
+                    .caused_by(&e)
+            })?;
+        Ok(format!(
+            "✅ Linked {} -[{}]-> {} (strength {:.2}, relationship ID: {})",
+            relationship.source_id,
+            relationship.relationship_type,
+            relationship.target_id,
+            relationship.strength,
+            relationship.id
+        ))
+    }
+
+    /// Execute the relationships tool: every link of one memory, both directions
+    pub async fn execute_relationships(&self, arguments: &Value) -> Result<String, McpError> {
+        let reference = arguments
+            .get("memory_id")
+            .and_then(|v| v.as_str())
+            .map(str::trim)
+            .filter(|id| !id.is_empty() && id.len() <= 100)
+            .ok_or_else(|| {
+                McpError::invalid_params(
+                    "Missing or invalid parameter 'memory_id'",
+                    "relationships",
+                )
+            })?;
+
+        let manager_guard = self.memory_manager.lock().await;
+        let memory_id = manager_guard
+            .resolve_memory_id(reference)
+            .await
+            .map_err(|e| McpError::invalid_params(e.to_string(), "relationships"))?;
+        let relationships = manager_guard
+            .get_relationships(&memory_id)
+            .await
+            .map_err(|e| {
+                McpError::internal_error(
+                    format!("Failed to load relationships: {}", e),
+                    "relationships",
+                )
+                .caused_by(&e)
+            })?;
+        if relationships.is_empty() {
+            return Ok(format!("No relationships found for memory {}", memory_id));
+        }
 
-            let res = {
-                let mut manager_guard = self.memory_manager.lock().await;
-                manager_guard.forget_matching(memory_query).await
+        let mut output = format!("{} relationships of {}:\n", relationships.len(), memory_id);
+        for rel in &relationships {
+            let (arrow, other_id) = if rel.source_id == memory_id {
+                ("->", &rel.target_id)
+            } else {
+                ("<-", &rel.source_id)
             };
-            match res {
-                Ok(deleted_count) => Ok(format!(
-                    "✅ {} memories deleted successfully\n\nQuery: \"{}\"",
-                    deleted_count, query
-                )),
-                Err(e) => Ok(format!("❌ Failed to delete memories: {}", e)),
+            // A dangling link (other memory deleted) is still listed, untitled
+            let title = match manager_guard.get_memory(other_id).await {
+                Ok(Some(other)) => format!(" {}", other.title),
+                _ => String::new(),
+            };
+            output.push_str(&format!(
+                "{} {} [{}]{} | strength {:.2} | {} | relationship ID: {}\n",
+                arrow,
+                rel.relationship_type,
+                other_id,
+                title,
+                rel.strength,
+                rel.description,
+                rel.id
+            ));
+        }
+        Ok(output)
+    }
+
+    /// Execute the memory_stats tool: the stats as text and structured JSON
+    pub async fn execute_memory_stats(&self) -> Result<(String, Value), McpError> {
+        let manager_guard = self.memory_manager.lock().await;
+        let stats = manager_guard.get_memory_stats().await.map_err(|e| {
+            McpError::internal_error(
+                format!("Failed to read memory stats: {}", e),
+                "memory_stats",
+            )
+            .caused_by(&e)
+        })?;
+        let structured = serde_json::to_value(&stats).map_err(|e| {
+            McpError::internal_error(
+                format!("Failed to serialize memory stats: {}", e),
+                "memory_stats",
+            )
+        })?;
+        Ok((stats.format(), structured))
+    }
+
+    /// Execute the revise tool: partial in-place update of one memory
+    pub async fn execute_revise(&self, arguments: &Value) -> Result<String, McpError> {
+        let memory_id = arguments
+            .get("memory_id")
+            .and_then(|v| v.as_str())
+            .map(str::trim)
+            .filter(|id| !id.is_empty() && id.len() <= 100)
+            .ok_or_else(|| {
+                McpError::invalid_params("Missing or invalid parameter 'memory_id'", "revise")
+            })?;
+
+        let text = |key: &str| {
+            arguments
+                .get(key)
+                .and_then(|v| v.as_str())
+                .map(|s| String::from_utf8_lossy(s.as_bytes()).to_string())
+        };
+        let title = text("title");
+        let content = text("content");
+        if title.as_ref().is_some_and(|t| t.len() < 5 || t.len() > 200) {
+            return Err(McpError::invalid_params(
+                "Title must be between 5 and 200 characters",
+                "revise",
+            ));
+        }
+        if content
+            .as_ref()
+            .is_some_and(|c| c.len() < 10 || c.len() > 10000)
+        {
+            return Err(McpError::invalid_params(
+                "Content must be between 10 and 10000 characters",
+                "revise",
+            ));
+        }
+
+        // Same limits as memorize: tags up to 50 chars, paths up to 500
+        let list = |key: &str, max_len: usize| -> Vec<String> {
+            parse_string_array(arguments, key)
+                .unwrap_or_default()
+                .iter()
+                .map(|s| s.trim())
+                .filter(|s| !s.is_empty() && s.chars().count() <= max_len)
+                .map(|s| s.to_string())
+                .collect()
+        };
+        let edit = MemoryEdit {
+            title,
+            content,
+            importance: arguments
+                .get("importance")
+                .and_then(|v| v.as_f64())
+                .map(|v| (v as f32).clamp(0.0, 1.0)),
+            add_tags: list("add_tags", 50).into_iter().take(10).collect(),
+            remove_tags: list("remove_tags", 50),
+            add_files: list("add_files", 500).into_iter().take(20).collect(),
+            remove_files: list("remove_files", 500),
+            allow_secrets: arguments
+                .get("allow_secrets")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false),
+        };
+        if edit.is_empty() {
+            return Err(McpError::invalid_params(
+                "Nothing to change: pass at least one of title, content, importance, \
+                 add_tags, remove_tags, add_files or remove_files",
+                "revise",
+            ));
+        }
+        let changed = revised_fields(&edit);
+
+        // Change to working directory so related file paths resolve against its repository
+        let original_dir = std::env::current_dir().map_err(|e| {
+            McpError::internal_error(format!("Failed to get current directory: {}", e), "revise")
+        })?;
+        if let Err(e) = std::env::set_current_dir(&self.working_directory) {
+            return Err(McpError::internal_error(
+                format!("Failed to change to working directory: {}", e),
+                "revise",
+            )
+            .with_details(format!("Path: {}", self.working_directory.display())));
+        }
+
+        let result = {
+            let mut manager_guard = self.memory_manager.lock().await;
+            match manager_guard.resolve_memory_id(memory_id).await {
+                Ok(id) => manager_guard.edit_memory(&id, edit).await,
+                Err(e) => Err(e),
             }
-        } else {
-            Ok("❌ Either 'memory_id' or 'query' must be provided".to_string())
+        };
+
+        if let Err(e) = std::env::set_current_dir(&original_dir) {
+            warn!(
+                error = %e,
+                "Failed to restore original directory"
+            );
+        }
+
+        match result {
+            Ok(Some(memory)) => Ok(format!(
+                "✅ Revised {} ({}). ID and relationships kept; the previous version is in its history.",
+                memory.id,
+                changed.join(", ")
+            )),
+            Ok(None) => Err(McpError::invalid_params(
+                format!("Memory not found: {}", memory_id),
+                "revise",
+            )),
+            Err(e) => Err(
+                McpError::internal_error(format!("Failed to revise memory: {}", e), "revise")
+                    .caused_by(&e),
+            ),
         }
     }
 }
 
+/// Names of the fields `edit` changes, for the revise tool's reply
+fn revised_fields(edit: &MemoryEdit) -> Vec<&'static str> {
+    [
+        ("title", edit.title.is_some()),
+        ("content", edit.content.is_some()),
+        ("importance", edit.importance.is_some()),
+        (
+            "tags",
+            !edit.add_tags.is_empty() || !edit.remove_tags.is_empty(),
+        ),
+        (
+            "related files",
+            !edit.add_files.is_empty() || !edit.remove_files.is_empty(),
+        ),
+    ]
+    .into_iter()
+    .filter_map(|(name, changed)| changed.then_some(name))
+    .collect()
+}
+
 /// Parse a JSON array argument into a non-empty `Vec<String>`, mirroring the
 /// "collect strings, treat empty as absent" pattern the remember/forget filters share.
 fn parse_string_array(arguments: &Value, key: &str) -> Option<Vec<String>> {
@@ -614,10 +1008,43 @@ fn parse_string_array(arguments: &Value, key: &str) -> Option<Vec<String>> {
     }
 }
 
-/// Parse the `memory_types` JSON array into `Vec<MemoryType>`, `None` when absent or empty.
-fn parse_memory_types(arguments: &Value) -> Option<Vec<MemoryType>> {
+/// Preview of a deletion that needs a confirm token, with the token to send back.
+fn confirmation_request(
+    targets: &[Memory],
+    label: &str,
+    rejection: Option<confirm::TokenRejection>,
+    token: &str,
+    ttl_secs: u64,
+) -> String {
+    const PREVIEW: usize = 20;
+    let mut output = String::new();
+    if let Some(rejection) = rejection {
+        output.push_str(&format!("⚠️  Not deleted: {}.\n\n", rejection.as_str()));
+    }
+    output.push_str(&format!(
+        "⚠️  This would delete {} memories ({}):\n",
+        targets.len(),
+        label
+    ));
+    for memory in targets.iter().take(PREVIEW) {
+        output.push_str(&format!("- [{}] {}\n", memory.id, memory.title));
+    }
+    if targets.len() > PREVIEW {
+        output.push_str(&format!("… and {} more\n", targets.len() - PREVIEW));
+    }
+    output.push_str(&format!(
+        "\nConfirm with the user, then repeat this forget call with the same arguments and \
+         confirm_token=\"{}\" (valid {}s).",
+        token, ttl_secs
+    ));
+    output
+}
+
+/// Parse a JSON array of memory types (`memory_types`, `exclude_types`) into
+/// `Vec<MemoryType>`, `None` when absent or empty.
+fn parse_memory_types(arguments: &Value, key: &str) -> Option<Vec<MemoryType>> {
     let types: Vec<MemoryType> = arguments
-        .get("memory_types")?
+        .get(key)?
         .as_array()?
         .iter()
         .filter_map(|v| v.as_str())
@@ -629,3 +1056,78 @@ fn parse_memory_types(arguments: &Value) -> Option<Vec<MemoryType>> {
         Some(types)
     }
 }
+
+/// Parse and validate the `query` argument (a string or an array of 1-5 strings).
+fn parse_queries(arguments: &Value, operation: &str) -> Result<Vec<String>, McpError> {
+    let queries: Vec<String> = match arguments.get("query") {
+        Some(Value::String(s)) => vec![s.clone()],
+        Some(Value::Array(arr)) => {
+            let queries: Vec<String> = arr
+                .iter()
+                .filter_map(|v| v.as_str().map(String::from))
+                .collect();
+
+            if queries.is_empty() {
+                return Err(McpError::invalid_params(
+                    "Invalid query array: must contain at least one non-empty string",
+                    operation,
+                ));
+            }
+
+            queries
+        }
+        _ => {
+            return Err(McpError::invalid_params(
+                "Missing required parameter 'query': must be a string or array of strings describing what to search for",
+                operation,
+            ));
+        }
+    };
+
+    if queries.len() > MAX_QUERIES {
+        return Err(McpError::invalid_params(
+            format!(
+                "Too many queries: maximum {} queries allowed, got {}. Use fewer, more specific terms.",
+                MAX_QUERIES,
+                queries.len()
+            ),
+            operation,
+        ));
+    }
+
+    for (i, query) in queries.iter().enumerate() {
+        // Ensure clean UTF-8 and validate query
+        let clean_query = String::from_utf8_lossy(query.as_bytes()).to_string();
+        let query = clean_query.trim();
+
+        if query.len() < 3 {
+            return Err(McpError::invalid_params(
+                format!(
+                    "Invalid query {}: must be at least 3 characters long",
+                    i + 1
+                ),
+                operation,
+            ));
+        }
+        if query.len() > 500 {
+            return Err(McpError::invalid_params(
+                format!(
+                    "Invalid query {}: must be no more than 500 characters long",
+                    i + 1
+                ),
+                operation,
+            ));
+        }
+        if query.is_empty() {
+            return Err(McpError::invalid_params(
+                format!(
+                    "Invalid query {}: cannot be empty or whitespace only",
+                    i + 1
+                ),
+                operation,
+            ));
+        }
+    }
+
+    Ok(queries)
+}
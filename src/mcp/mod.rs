@@ -12,10 +12,15 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+pub mod auth;
+pub mod confirm;
 pub mod knowledge;
 pub mod logging;
 pub mod memory;
+pub mod progress;
+pub mod roots;
 pub mod server;
 pub mod types;
+pub mod validation;
 
 pub use server::McpServer;
@@ -0,0 +1,73 @@
+// Copyright 2026 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! `notifications/progress` for long-running tool calls.
+//!
+//! A client that wants progress sends `_meta.progressToken` with the call.
+//! Steps reported while the tool runs (the fetch, chunk, embed and store
+//! phases of knowledge indexing) are forwarded to it in order, each with a
+//! higher `progress` than the last. Calls without a token report nothing.
+
+use rmcp::model::{Meta, ProgressNotificationParam};
+use rmcp::{Peer, RoleServer};
+use std::sync::Arc;
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use tracing::debug;
+
+use crate::knowledge::manager::IndexProgress;
+
+/// Progress sink of one tool call
+pub struct ProgressReporter {
+    sender: mpsc::UnboundedSender<String>,
+    forwarder: JoinHandle<()>,
+}
+
+impl ProgressReporter {
+    /// Reporter for a call carrying `meta`; `None` when the client sent no
+    /// progress token
+    pub fn start(meta: &Meta, peer: Peer<RoleServer>) -> Option<Self> {
+        let token = meta.get_progress_token()?;
+        let (sender, mut receiver) = mpsc::unbounded_channel::<String>();
+        // Notifications are sent from a task so reporting never blocks the tool
+        let forwarder = tokio::spawn(async move {
+            let mut progress = 0.0;
+            while let Some(message) = receiver.recv().await {
+                progress += 1.0;
+                let param =
+                    ProgressNotificationParam::new(token.clone(), progress).with_message(message);
+                if let Err(e) = peer.notify_progress(param).await {
+                    debug!("notifications/progress failed: {}", e);
+                    break;
+                }
+            }
+        });
+        Some(Self { sender, forwarder })
+    }
+
+    /// Indexing callback that reports each phase as "Fetching <source>" etc.
+    pub fn index_progress(&self) -> IndexProgress {
+        let sender = self.sender.clone();
+        Arc::new(move |phase, source| {
+            let _ = sender.send(format!("{} {}", phase.label(), source));
+        })
+    }
+
+    /// Wait until every reported step has been sent, so no notification
+    /// follows the tool's result
+    pub async fn finish(self) {
+        drop(self.sender);
+        let _ = self.forwarder.await;
+    }
+}
@@ -0,0 +1,134 @@
+// Copyright 2026 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Workspace selection from MCP client roots.
+//!
+//! Clients that support roots advertise the folders the editor has open. The
+//! server asks for them after the handshake and again on
+//! `notifications/roots/list_changed`, and uses the first one that is a git
+//! repository as its working directory and project, so one configured server
+//! follows whatever workspace is open. Every root also bounds where a tool
+//! call's `project_path` may point.
+
+use std::path::{Path, PathBuf};
+
+/// Local path of a `file://` root URI (percent-decoded); `None` for other schemes.
+pub fn root_path(uri: &str) -> Option<PathBuf> {
+    let rest = uri.strip_prefix("file://")?;
+    // file://host/path: only the local host is meaningful
+    let path = match rest.find('/') {
+        Some(0) => rest,
+        Some(i) if &rest[..i] == "localhost" => &rest[i..],
+        _ => return None,
+    };
+    percent_decode(path).map(PathBuf::from)
+}
+
+fn percent_decode(s: &str) -> Option<String> {
+    let bytes = s.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' {
+            let hex = s.get(i + 1..i + 3)?;
+            out.push(u8::from_str_radix(hex, 16).ok()?);
+            i += 3;
+        } else {
+            out.push(bytes[i]);
+            i += 1;
+        }
+    }
+    String::from_utf8(out).ok()
+}
+
+/// Canonical directories of the given root URIs, skipping any that don't exist
+pub fn root_dirs(uris: &[String]) -> Vec<PathBuf> {
+    uris.iter()
+        .filter_map(|uri| root_path(uri))
+        .filter_map(|path| path.canonicalize().ok())
+        .filter(|path| path.is_dir())
+        .collect()
+}
+
+/// Whether the canonical `path` lies in one of `allowed` (canonical too)
+pub fn is_within(path: &Path, allowed: &[PathBuf]) -> bool {
+    allowed.iter().any(|dir| path.starts_with(dir))
+}
+
+/// Directory to work in for the given root URIs: the first that is a git
+/// repository, else the first existing directory. The flag tells whether it
+/// is a repository (and so has a project ID).
+pub fn select_workspace(uris: &[String]) -> Option<(PathBuf, bool)> {
+    let dirs: Vec<PathBuf> = uris
+        .iter()
+        .filter_map(|uri| root_path(uri))
+        .filter(|path| path.is_dir())
+        .collect();
+    dirs.iter()
+        .find(|path| path.join(".git").exists())
+        .map(|path| (path.clone(), true))
+        .or_else(|| dirs.first().map(|path| (path.clone(), false)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_root_path_decodes_file_uris() {
+        assert_eq!(
+            root_path("file:///home/me/my%20project"),
+            Some(PathBuf::from("/home/me/my project"))
+        );
+        assert_eq!(
+            root_path("file://localhost/srv/repo"),
+            Some(PathBuf::from("/srv/repo"))
+        );
+        assert_eq!(root_path("file://fileserver/share"), None);
+        assert_eq!(root_path("https://example.com/repo"), None);
+        assert_eq!(root_path("file:///bad%zz"), None);
+    }
+
+    #[test]
+    fn test_select_workspace_prefers_git_repos() {
+        let base = std::env::temp_dir().join(format!("octobrain_roots_{}", uuid::Uuid::new_v4()));
+        let plain = base.join("notes");
+        let repo = base.join("repo");
+        std::fs::create_dir_all(&plain).unwrap();
+        std::fs::create_dir_all(repo.join(".git")).unwrap();
+        let uri = |p: &PathBuf| format!("file://{}", p.display());
+
+        let missing = "file:///definitely/not/here".to_string();
+        assert_eq!(
+            select_workspace(&[missing.clone(), uri(&plain), uri(&repo)]),
+            Some((repo.clone(), true))
+        );
+        assert_eq!(
+            select_workspace(&[missing.clone(), uri(&plain)]),
+            Some((plain.clone(), false))
+        );
+        assert_eq!(select_workspace(&[missing]), None);
+        std::fs::remove_dir_all(&base).ok();
+    }
+
+    #[test]
+    fn test_is_within_compares_whole_components() {
+        let allowed = vec![PathBuf::from("/work/repos")];
+        assert!(is_within(Path::new("/work/repos"), &allowed));
+        assert!(is_within(Path::new("/work/repos/api"), &allowed));
+        assert!(!is_within(Path::new("/work/repos-old"), &allowed));
+        assert!(!is_within(Path::new("/etc"), &allowed));
+        assert!(!is_within(Path::new("/work/repos"), &[]));
+    }
+}
@@ -17,35 +17,48 @@
 
 use anyhow::Result;
 use rmcp::{
+    handler::server::tool::ToolCallContext,
     handler::server::{wrapper::Parameters, ServerHandler},
     model::{
-        Implementation, InitializeRequestParams, InitializeResult, ListToolsResult,
-        PaginatedRequestParams, ProtocolVersion, ServerCapabilities, ServerInfo, Tool,
+        CallToolRequestParams, CallToolResult, Content, ErrorCode, Implementation,
+        InitializeRequestParams, InitializeResult, ListToolsResult, PaginatedRequestParams,
+        ProtocolVersion, ServerCapabilities, ServerInfo, Tool,
     },
     schemars::JsonSchema,
-    service::RequestContext,
+    service::{NotificationContext, RequestContext},
     tool, tool_handler, tool_router,
     transport::streamable_http_server::{
         session::local::LocalSessionManager, StreamableHttpService,
     },
-    ErrorData as McpError, RoleServer, ServiceExt,
+    ErrorData as McpError, Peer, RoleServer, ServiceExt,
 };
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
 use std::sync::{Arc, OnceLock};
+use std::time::Instant;
 use tokio::sync::Mutex;
-use tracing::debug;
+use tracing::{debug, info, warn};
 
-/// Tools with project+role stripped — built once.
+/// Tools with project, project_path and role stripped — built once.
 static TOOLS_LOCKED: OnceLock<Vec<Tool>> = OnceLock::new();
 /// Tools with only role stripped — built once.
 static TOOLS_ROLE_ONLY: OnceLock<Vec<Tool>> = OnceLock::new();
 /// Full tools list — built once.
 static TOOLS_FULL: OnceLock<Vec<Tool>> = OnceLock::new();
+/// Compiled input schemas of the full tools list — built once.
+static VALIDATORS: OnceLock<ToolValidators> = OnceLock::new();
 
 fn tools_full() -> &'static Vec<Tool> {
     TOOLS_FULL.get_or_init(|| McpServer::tool_router().list_all())
 }
 
+/// Validation always uses the full schemas: stripped project/role fields are
+/// hidden from the listing but still accepted.
+fn validators() -> &'static ToolValidators {
+    VALIDATORS.get_or_init(|| ToolValidators::new(tools_full()))
+}
+
 fn strip_fields(fields: &[&str]) -> Vec<Tool> {
     tools_full()
         .iter()
@@ -67,7 +80,7 @@ fn strip_fields(fields: &[&str]) -> Vec<Tool> {
 }
 
 fn tools_locked() -> &'static Vec<Tool> {
-    TOOLS_LOCKED.get_or_init(|| strip_fields(&["project", "role"]))
+    TOOLS_LOCKED.get_or_init(|| strip_fields(&["project", "project_path", "role"]))
 }
 
 fn tools_role_only() -> &'static Vec<Tool> {
@@ -75,8 +88,13 @@ fn tools_role_only() -> &'static Vec<Tool> {
 }
 
 use crate::config::Config;
+use crate::knowledge::types::ChunkFilter;
+use crate::mcp::auth::require_key;
 use crate::mcp::knowledge::KnowledgeProvider;
 use crate::mcp::memory::MemoryProvider;
+use crate::mcp::progress::ProgressReporter;
+use crate::mcp::validation::ToolValidators;
+use crate::search::{format_hits, fuse, FusionMethod};
 
 /// Delegates to octolib::utils::path_to_id — single canonical implementation.
 fn derive_project_id(path: &std::path::Path) -> String {
@@ -127,8 +145,14 @@ fn discover_projects(root: &std::path::Path) -> Vec<(String, String)> {
 fn build_instructions(projects: &[(String, String)]) -> String {
     let base = "This server provides memory tools for storing and retrieving AI context. \
                 Use 'memorize' to store information (supports 'related_to' for inline relationships), \
-                'remember' for semantic search, 'forget' to delete memories, \
-                and 'knowledge' to search/index/read/match indexed content. \
+                'remember' for semantic search, 'ask_memory' for a cited answer synthesized from memories, \
+                'revise' to update a memory in place, 'forget' to delete memories, \
+                'relate' and 'relationships' to link memories and list their links, \
+                'feedback' to rate a remember result as helpful or unhelpful, \
+                'memory_stats' for an overview of what the memory store holds, \
+                'knowledge' to search/index/read/match indexed content, \
+                and 'recall' to search memories and knowledge together when unsure which holds the answer. \
+                Memory tools accept 'project_path' to work in another local repository (inside the client's roots or the server's directory). \
                 The 'knowledge' tool's 'source' parameter is always a SINGLE FILE or URL — never a directory.";
 
     if projects.is_empty() {
@@ -153,6 +177,14 @@ pub struct SessionState {
     pub role_locked: bool,
     /// Project is locked (and stripped from schema) when git=true OR no local repos.
     pub project_locked: bool,
+    /// Project came from the handshake; client roots never override it.
+    pub project_from_handshake: bool,
+    /// Client advertised the roots capability in the handshake.
+    pub roots_supported: bool,
+    /// Client root in use instead of the server's working directory.
+    pub workspace: Option<std::path::PathBuf>,
+    /// Every root the client reported, canonicalized.
+    pub roots: Vec<std::path::PathBuf>,
 }
 
 impl Default for SessionState {
@@ -163,16 +195,29 @@ impl Default for SessionState {
             session_id: uuid::Uuid::new_v4().to_string(),
             role_locked: false,
             project_locked: false,
+            project_from_handshake: false,
+            roots_supported: false,
+            workspace: None,
+            roots: Vec::new(),
         }
     }
 }
 
+/// Most `project_path` providers kept open at once; the least recently used
+/// one is closed to make room.
+const MAX_PROJECT_PROVIDERS: usize = 16;
+
+/// `project_path` providers by canonical path and role, with their last use
+type ProjectProviders = HashMap<(PathBuf, Option<String>), (MemoryProvider, Instant)>;
+
 /// MCP Server using rmcp SDK
 #[derive(Clone)]
 pub struct McpServer {
     config: Config,
     working_directory: std::path::PathBuf,
     memory: Arc<Mutex<Option<MemoryProvider>>>,
+    /// Providers opened for a per-call `project_path`
+    project_providers: Arc<Mutex<ProjectProviders>>,
     knowledge: Arc<Mutex<Option<KnowledgeProvider>>>,
     session: Arc<Mutex<SessionState>>,
     instructions: String,
@@ -189,6 +234,7 @@ impl McpServer {
             config,
             working_directory,
             memory: Arc::new(Mutex::new(None)),
+            project_providers: Arc::new(Mutex::new(HashMap::new())),
             knowledge: Arc::new(Mutex::new(None)),
             session: Arc::new(Mutex::new(SessionState::default())),
             instructions,
@@ -197,14 +243,31 @@ impl McpServer {
     }
 
     /// Get memory provider.
+    /// - `project_path` given: cached per repository, see `project_path_provider`;
+    ///   refused when the session's project is locked.
     /// - Locked (handshake received): cached, project/role fixed from session state.
     /// - Unlocked (no handshake): fresh per call, project/role from caller args.
     async fn get_memory_provider(
         &self,
         project: Option<String>,
         role: Option<String>,
+        project_path: Option<String>,
     ) -> Result<MemoryProvider, McpError> {
         let session = self.session.lock().await.clone();
+        if let Some(project_path) = project_path {
+            if session.project_locked {
+                return Err(McpError::invalid_params(
+                    "project_path is not accepted: this session is locked to its project"
+                        .to_string(),
+                    None,
+                ));
+            }
+            return self.project_path_provider(&project_path, role).await;
+        }
+        let working_directory = session
+            .workspace
+            .clone()
+            .unwrap_or_else(|| self.working_directory.clone());
 
         if session.role_locked || session.project_locked {
             // Double-checked lock: cheap path first
@@ -220,24 +283,129 @@ impl McpServer {
             }
             let provider = MemoryProvider::new(
                 &self.config,
-                self.working_directory.clone(),
+                working_directory,
                 session.project,
                 session.role,
             )
             .await
-            .map_err(|e| {
-                McpError::internal_error(format!("Failed to initialize memory: {}", e), None)
-            })?;
+            .map_err(to_rmcp_error)?;
             *guard = Some(provider.clone());
             Ok(provider)
         } else {
             // No handshake — honour per-call project/role from args
-            MemoryProvider::new(&self.config, self.working_directory.clone(), project, role)
+            MemoryProvider::new(&self.config, working_directory, project, role)
                 .await
-                .map_err(|e| {
-                    McpError::internal_error(format!("Failed to initialize memory: {}", e), None)
-                })
+                .map_err(to_rmcp_error)
+        }
+    }
+
+    /// Provider for the repository at `project_path`, so one server can serve
+    /// several repos: memories are scoped to the project ID of that directory
+    /// (as `--project <dir>` resolves it) and git context comes from it. The
+    /// directory must lie in a client root or the server's working directory.
+    /// The role still follows the handshake when it locked one.
+    async fn project_path_provider(
+        &self,
+        project_path: &str,
+        role: Option<String>,
+    ) -> Result<MemoryProvider, McpError> {
+        let path = std::path::Path::new(project_path)
+            .canonicalize()
+            .ok()
+            .filter(|p| p.is_dir())
+            .ok_or_else(|| {
+                McpError::invalid_params(
+                    format!("project_path is not a directory: {}", project_path),
+                    None,
+                )
+            })?;
+        let session = self.session.lock().await.clone();
+        let mut allowed = session.roots.clone();
+        allowed.extend(self.working_directory.canonicalize().ok());
+        if !crate::mcp::roots::is_within(&path, &allowed) {
+            return Err(McpError::invalid_params(
+                format!(
+                    "project_path must be inside the client's roots or the server's directory: {}",
+                    project_path
+                ),
+                None,
+            ));
+        }
+        let role = if session.role_locked {
+            session.role
+        } else {
+            role
+        };
+
+        let key = (path.clone(), role.clone());
+        let mut providers = self.project_providers.lock().await;
+        if let Some((provider, used)) = providers.get_mut(&key) {
+            *used = Instant::now();
+            return Ok(provider.clone());
+        }
+        let project = crate::storage::resolve_project_key(&path.to_string_lossy());
+        debug!(
+            "Opening memory for {} (project={})",
+            path.display(),
+            project
+        );
+        let provider = MemoryProvider::new(&self.config, path, Some(project), role)
+            .await
+            .map_err(to_rmcp_error)?;
+        if providers.len() >= MAX_PROJECT_PROVIDERS {
+            let oldest = providers
+                .iter()
+                .min_by_key(|(_, (_, used))| *used)
+                .map(|(key, _)| key.clone());
+            if let Some(oldest) = oldest {
+                providers.remove(&oldest);
+            }
+        }
+        providers.insert(key, (provider.clone(), Instant::now()));
+        Ok(provider)
+    }
+
+    /// Ask the client for its roots and switch to the workspace they point at.
+    /// A git root also selects its project unless the handshake named one.
+    /// The cached memory provider is dropped so the next call reopens there.
+    async fn apply_roots(&self, peer: Peer<RoleServer>) {
+        let uris: Vec<String> = match peer.list_roots().await {
+            Ok(result) => result.roots.into_iter().map(|r| r.uri).collect(),
+            Err(e) => {
+                debug!("roots/list failed: {}", e);
+                return;
+            }
+        };
+        self.session.lock().await.roots = crate::mcp::roots::root_dirs(&uris);
+        let Some((workspace, is_repo)) = crate::mcp::roots::select_workspace(&uris) else {
+            debug!("No usable local directory among client roots: {:?}", uris);
+            return;
+        };
+
+        {
+            let mut session = self.session.lock().await;
+            if session.workspace.as_ref() == Some(&workspace) {
+                return;
+            }
+            if is_repo && !session.project_from_handshake {
+                session.project = Some(derive_project_id(&workspace));
+                session.project_locked = true;
+            }
+            session.workspace = Some(workspace.clone());
+            info!(
+                "Workspace switched to {} (project={:?})",
+                workspace.display(),
+                session.project
+            );
         }
+        *self.memory.lock().await = None;
+    }
+
+    /// Query roots in the background: `roots/list` needs the client to answer
+    /// while the notification that triggered it is still being handled.
+    fn refresh_roots(&self, peer: Peer<RoleServer>) {
+        let server = self.clone();
+        tokio::spawn(async move { server.apply_roots(peer).await });
     }
 
     /// Get or initialize knowledge provider
@@ -254,9 +422,9 @@ impl McpServer {
             return Ok(provider.clone());
         }
 
-        let provider = KnowledgeProvider::new(&self.config).await.map_err(|e| {
-            McpError::internal_error(format!("Failed to initialize knowledge: {}", e), None)
-        })?;
+        let provider = KnowledgeProvider::new(&self.config)
+            .await
+            .map_err(to_rmcp_error)?;
 
         *guard = Some(provider.clone());
         Ok(provider)
@@ -294,12 +462,24 @@ impl McpServer {
             Default::default(),
         );
 
-        let app = Router::new().nest_service("/mcp", service).layer(
-            CorsLayer::new()
-                .allow_origin(Any)
-                .allow_methods([http::Method::POST, http::Method::GET, http::Method::OPTIONS])
-                .allow_headers(Any),
-        );
+        if self.config.server.api_keys.is_empty() {
+            warn!(
+                "No [server].api_keys configured — any client that can reach {} can read and write memories",
+                addr
+            );
+        }
+        let keys = Arc::new(self.config.server.clone());
+
+        // CORS stays outermost so preflight requests need no key
+        let app = Router::new()
+            .nest_service("/mcp", service)
+            .layer(axum::middleware::from_fn_with_state(keys, require_key))
+            .layer(
+                CorsLayer::new()
+                    .allow_origin(Any)
+                    .allow_methods([http::Method::POST, http::Method::GET, http::Method::OPTIONS])
+                    .allow_headers(Any),
+            );
 
         let listener = tokio::net::TcpListener::bind(&addr)
             .await
@@ -316,11 +496,17 @@ impl McpServer {
 }
 
 /// Convert a provider-layer `McpError` (crate::mcp::types) into the rmcp SDK error type.
+/// The code follows the cause; `data` carries `operation`, `cause` and `retryable`.
 fn to_rmcp_error(e: crate::mcp::types::McpError) -> McpError {
-    McpError::internal_error(
-        e.message,
-        Some(serde_json::to_value(e.operation).unwrap_or_default()),
-    )
+    let mut data = serde_json::json!({
+        "operation": e.operation,
+        "cause": e.cause.as_str(),
+        "retryable": e.retryable,
+    });
+    if let Some(details) = e.details {
+        data["details"] = serde_json::Value::String(details);
+    }
+    McpError::new(ErrorCode(e.cause.code()), e.message, Some(data))
 }
 
 // ============================================================================
@@ -392,8 +578,8 @@ pub enum RelationshipKind {
 }
 
 /// A relationship to create alongside a `memorize` call.
-/// Subsumes what the standalone `relate` tool used to do; one MCP round-trip
-/// stores the memory AND links it to existing memories.
+/// One MCP round-trip stores the memory AND links it to existing memories;
+/// the `relate` tool links memories that already exist.
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct RelationshipSpec {
     /// ID of the target memory to link to
@@ -446,6 +632,9 @@ pub struct MemorizeParams {
     pub project: Option<String>,
     /// Role tag to attach to this memory (e.g. 'developer', 'reviewer').
     pub role: Option<String>,
+    /// Local repository to work in instead of the server's working directory:
+    /// memories are scoped to its project and git context is read from it
+    pub project_path: Option<String>,
     /// Optional: create typed relationships from this new memory to existing
     /// memories in the same call. Subsumes the standalone relate tool.
     /// Most common use: contributing toward a Goal via
@@ -453,6 +642,12 @@ pub struct MemorizeParams {
     /// closing it with `consolidate(goal_id)`.
     #[schemars(length(max = 20))]
     pub related_to: Option<Vec<RelationshipSpec>>,
+    /// Store even if content looks like a credential (API key, token, private key).
+    /// Only set when the user explicitly asked to keep the secret.
+    pub allow_secrets: Option<bool>,
+    /// Paths of small files (diagrams, log excerpts, patches) to attach
+    #[schemars(length(max = 10))]
+    pub attachments: Option<Vec<String>>,
 }
 
 /// Remember tool parameters
@@ -466,16 +661,47 @@ pub struct RememberParams {
     pub tags: Option<Vec<String>>,
     /// Filter by related file paths
     pub related_files: Option<Vec<String>>,
+    /// Skip memories of these categories (e.g. everything but architecture decisions)
+    pub exclude_types: Option<Vec<MemoryType>>,
+    /// Skip memories with any of these tags (e.g. "deprecated")
+    pub exclude_tags: Option<Vec<String>>,
+    /// Skip memories related to any of these file paths
+    pub exclude_files: Option<Vec<String>>,
     /// Max memories to return
     #[schemars(range(min = 1, max = 5))]
     pub limit: Option<usize>,
     /// Minimum relevance score (0.0-1.0)
     #[schemars(range(min = 0.0, max = 1.0))]
     pub min_relevance: Option<f32>,
+    /// Add a score breakdown per result (vector, keyword matches, recency, importance, reranker)
+    pub explain: Option<bool>,
     /// Filter by project key. If omitted, returns memories from all projects.
     pub project: Option<String>,
     /// Filter by role. If omitted, returns memories for all roles.
     pub role: Option<String>,
+    /// Local repository to work in instead of the server's working directory:
+    /// memories are scoped to its project and git context is read from it
+    pub project_path: Option<String>,
+}
+
+/// Ask-memory tool parameters
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct AskMemoryParams {
+    /// Question to answer, or array of 2-5 related phrasings
+    pub query: QueryInput,
+    /// Narrow context to specific memory categories
+    pub memory_types: Option<Vec<MemoryType>>,
+    /// Filter context by tags
+    pub tags: Option<Vec<String>>,
+    /// Filter context by related file paths
+    pub related_files: Option<Vec<String>>,
+    /// Filter by project key. If omitted, uses memories from all projects.
+    pub project: Option<String>,
+    /// Filter by role. If omitted, uses memories for all roles.
+    pub role: Option<String>,
+    /// Local repository to work in instead of the server's working directory:
+    /// memories are scoped to its project and git context is read from it
+    pub project_path: Option<String>,
 }
 
 /// Forget tool parameters
@@ -489,12 +715,158 @@ pub struct ForgetParams {
     pub memory_types: Option<Vec<MemoryType>>,
     /// Filter by tags when using query
     pub tags: Option<Vec<String>>,
-    /// Must be true — deletion is permanent
+    /// Spare memories of these categories when using query
+    pub exclude_types: Option<Vec<MemoryType>>,
+    /// Spare memories with any of these tags when using query
+    pub exclude_tags: Option<Vec<String>>,
+    /// Spare memories related to any of these file paths when using query
+    pub exclude_files: Option<Vec<String>>,
+    /// Must be true to confirm deletion
     pub confirm: bool,
+    /// Token from a previous forget preview; required when the deletion exceeds the configured threshold
+    pub confirm_token: Option<String>,
+    /// Project key filter
+    pub project: Option<String>,
+    /// Role filter
+    pub role: Option<String>,
+    /// Local repository to work in instead of the server's working directory:
+    /// memories are scoped to its project and git context is read from it
+    pub project_path: Option<String>,
+}
+
+/// Feedback tool parameters
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct FeedbackParams {
+    /// ID of the memory being rated (from remember results)
+    pub memory_id: String,
+    /// true if the result was useful for the query, false if it was noise
+    pub helpful: bool,
+    /// Query the memory was returned for. If omitted, the latest search that returned it is used.
+    pub query: Option<String>,
+    /// Project key filter
+    pub project: Option<String>,
+    /// Role filter
+    pub role: Option<String>,
+    /// Local repository to work in instead of the server's working directory:
+    /// memories are scoped to its project and git context is read from it
+    pub project_path: Option<String>,
+}
+
+/// Revise tool parameters
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ReviseParams {
+    /// ID of the memory to update (full ID, unique prefix, or alias)
+    pub memory_id: String,
+    /// New title; omit to keep the current one
+    pub title: Option<String>,
+    /// New content, replacing the old; omit to keep the current one
+    pub content: Option<String>,
+    /// New importance 0.0-1.0
+    #[schemars(range(min = 0.0, max = 1.0))]
+    pub importance: Option<f32>,
+    /// Tags to add
+    #[schemars(length(max = 10))]
+    pub add_tags: Option<Vec<String>>,
+    /// Tags to remove
+    pub remove_tags: Option<Vec<String>>,
+    /// Related file paths to add
+    #[schemars(length(max = 20))]
+    pub add_files: Option<Vec<String>>,
+    /// Related file paths to remove
+    pub remove_files: Option<Vec<String>>,
+    /// Store even if the new title or content looks like a credential.
+    /// Only set when the user explicitly asked to keep the secret.
+    pub allow_secrets: Option<bool>,
+    /// Project key filter
+    pub project: Option<String>,
+    /// Role filter
+    pub role: Option<String>,
+    /// Local repository to work in instead of the server's working directory:
+    /// memories are scoped to its project and git context is read from it
+    pub project_path: Option<String>,
+}
+
+/// Relate tool parameters
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct RelateParams {
+    /// ID of the memory the relationship starts from
+    pub source_id: String,
+    /// ID of the memory it points to
+    pub target_id: String,
+    /// Relationship type, read as "source <type> target"
+    pub relationship_type: RelationshipKind,
+    /// Relationship strength 0.0-1.0 (default 0.8 if omitted)
+    #[schemars(range(min = 0.0, max = 1.0))]
+    pub strength: Option<f32>,
+    /// Optional human description of why these memories are related
+    #[schemars(length(max = 200))]
+    pub description: Option<String>,
+    /// Project key filter
+    pub project: Option<String>,
+    /// Role filter
+    pub role: Option<String>,
+    /// Local repository to work in instead of the server's working directory:
+    /// memories are scoped to its project and git context is read from it
+    pub project_path: Option<String>,
+}
+
+/// Relationships tool parameters
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct RelationshipsParams {
+    /// ID of the memory whose relationships to list
+    pub memory_id: String,
+    /// Project key filter
+    pub project: Option<String>,
+    /// Role filter
+    pub role: Option<String>,
+    /// Local repository to work in instead of the server's working directory:
+    /// memories are scoped to its project and git context is read from it
+    pub project_path: Option<String>,
+}
+
+/// Memory stats tool parameters
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct MemoryStatsParams {
     /// Project key filter
     pub project: Option<String>,
     /// Role filter
     pub role: Option<String>,
+    /// Local repository to work in instead of the server's working directory:
+    /// memories are scoped to its project and git context is read from it
+    pub project_path: Option<String>,
+}
+
+/// How the recall tool merges memory and knowledge rankings
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum RecallFusion {
+    /// Reciprocal Rank Fusion over each store's own ranking (default)
+    Rrf,
+    /// Per-store normalized relevance scores, scaled by memory_weight
+    Weighted,
+}
+
+/// Recall tool parameters
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct RecallParams {
+    /// What to look for, in natural language
+    #[schemars(length(min = 3, max = 500))]
+    pub query: String,
+    /// Max results across memories and knowledge (default 10)
+    #[schemars(range(min = 1, max = 50))]
+    pub limit: Option<usize>,
+    /// How to merge the two rankings (default rrf)
+    pub fusion: Option<RecallFusion>,
+    /// Share of weight given to memories; knowledge gets the rest (default 0.5)
+    #[schemars(range(min = 0.0, max = 1.0))]
+    pub memory_weight: Option<f32>,
+    /// Filter memories by project key. If omitted, searches all projects.
+    pub project: Option<String>,
+    /// Filter memories by role. If omitted, searches all roles.
+    pub role: Option<String>,
+    /// Local repository to work in instead of the server's working directory:
+    /// memories are scoped to its project and git context is read from it
+    pub project_path: Option<String>,
 }
 
 /// Command for the knowledge tool
@@ -511,6 +883,8 @@ pub enum KnowledgeAction {
     Read,
     /// Search indexed content by regex pattern (like grep)
     Match,
+    /// Fetch a search hit's full chunk plus neighbouring chunks from the same source
+    Get,
 }
 
 /// Knowledge tool parameters
@@ -521,10 +895,13 @@ pub struct KnowledgeParams {
     /// [search] What to search for, in natural language (required for search)
     #[schemars(length(min = 3, max = 500))]
     pub query: Option<String>,
-    /// [search] Source filter — a SINGLE URL or local FILE path to auto-index and search within. MUST point to one specific file (e.g. /path/to/notes.md, https://example.com/page) — directories are NOT supported and will be rejected. Supports http/https URLs, file:///path, or /absolute/path. File types: .html, .txt, .md, .pdf, .docx. Omit to search across ALL previously indexed sources.
+    /// [search] Source filter — a SINGLE URL or local FILE path to auto-index and search within. MUST point to one specific file (e.g. /path/to/notes.md, https://example.com/page) — directories are NOT supported and will be rejected. Supports http/https URLs, file:///path, or /absolute/path. File types: .html, .txt, .md, .pdf, .docx, plus OpenAPI/Swagger specs (JSON or YAML, indexed per endpoint). Omit to search across ALL previously indexed sources.
     /// [read] A SINGLE URL or local FILE path to read full content from. MUST point to one specific file — directories are NOT supported. Supports http/https URLs, file:///path, or /absolute/path. File types: .html, .txt, .md, .pdf, .docx.
     /// [match] Source filter — a SINGLE URL or local FILE path. MUST point to one specific file — directories are NOT supported. Omit to match across ALL indexed sources.
     pub source: Option<String>,
+    /// [search] Only search sources indexed into this collection (e.g. "rust-docs", see `octobrain knowledge index --collection`). Combines with source.
+    #[schemars(length(min = 1))]
+    pub collection: Option<String>,
     /// [store/delete] Unique identifier key for the content. Error if key already exists on store — delete first to replace.
     pub key: Option<String>,
     /// [store] Raw text content to store and index (required for store)
@@ -532,6 +909,19 @@ pub struct KnowledgeParams {
     /// [match] Regex pattern to search for in indexed content (e.g., "error_code" or "timeout|retry")
     #[schemars(length(min = 1))]
     pub pattern: Option<String>,
+    /// [search] Maximum number of results (default: configured knowledge.max_results)
+    #[schemars(range(min = 1, max = 100))]
+    pub limit: Option<usize>,
+    /// [search] Drop results with relevance below this score (0.0-1.0)
+    #[schemars(range(min = 0.0, max = 1.0))]
+    pub min_relevance: Option<f32>,
+    /// [search] Merge nearby hits from the same source into continuous, de-duplicated passages
+    pub stitch: Option<bool>,
+    /// [get] Chunk ID from a search result (required for get)
+    pub chunk_id: Option<String>,
+    /// [get] Neighbouring chunks to include on each side (default 1)
+    #[schemars(range(max = 20))]
+    pub context: Option<usize>,
 }
 
 // ============================================================================
@@ -549,7 +939,11 @@ impl McpServer {
         Parameters(params): Parameters<MemorizeParams>,
     ) -> Result<String, McpError> {
         let provider = self
-            .get_memory_provider(params.project.clone(), params.role.clone())
+            .get_memory_provider(
+                params.project.clone(),
+                params.role.clone(),
+                params.project_path.clone(),
+            )
             .await?;
         let args = serde_json::to_value(&params).map_err(|e| {
             McpError::internal_error(format!("Failed to serialize params: {}", e), None)
@@ -562,14 +956,18 @@ impl McpServer {
 
     #[tool(
         name = "remember",
-        description = "Semantic search over stored memories. Call before memorize to avoid duplicates, and at task start to load context. Results include 1-hop graph neighbors automatically. Prefer 2-5 related query terms for broader coverage. Results show [CONFIRMED]/[INFERRED] trust labels."
+        description = "Semantic search over stored memories. Call before memorize to avoid duplicates, and at task start to load context. Results include 1-hop graph neighbors automatically. Prefer 2-5 related query terms for broader coverage. Results show [CONFIRMED]/[INFERRED] trust labels. Queries may carry filter clauses, e.g. 'type:bug_fix tag:auth -tag:old importance>0.6 after:2w \"token refresh\"'."
     )]
     async fn remember(
         &self,
         Parameters(params): Parameters<RememberParams>,
     ) -> Result<String, McpError> {
         let provider = self
-            .get_memory_provider(params.project.clone(), params.role.clone())
+            .get_memory_provider(
+                params.project.clone(),
+                params.role.clone(),
+                params.project_path.clone(),
+            )
             .await?;
         let args = serde_json::to_value(&params).map_err(|e| {
             McpError::internal_error(format!("Failed to serialize params: {}", e), None)
@@ -580,16 +978,44 @@ impl McpServer {
             .map_err(to_rmcp_error)
     }
 
+    #[tool(
+        name = "ask_memory",
+        description = "Answer a question from stored memories. Runs the same search as remember, then an LLM writes a short answer citing memory IDs in [brackets]. Use when you need a conclusion rather than raw memories; use remember when you need full memory content."
+    )]
+    async fn ask_memory(
+        &self,
+        Parameters(params): Parameters<AskMemoryParams>,
+    ) -> Result<String, McpError> {
+        let provider = self
+            .get_memory_provider(
+                params.project.clone(),
+                params.role.clone(),
+                params.project_path.clone(),
+            )
+            .await?;
+        let args = serde_json::to_value(&params).map_err(|e| {
+            McpError::internal_error(format!("Failed to serialize params: {}", e), None)
+        })?;
+        provider
+            .execute_ask_memory(&args)
+            .await
+            .map_err(to_rmcp_error)
+    }
+
     #[tool(
         name = "forget",
-        description = "Permanently delete memories. Irreversible — requires confirm=true. Use memory_id for single deletion, or query+filters for bulk removal. Don't forget memories just because they're old — importance decay handles that. Only delete when information is wrong or superseded."
+        description = "Delete memories. Requires confirm=true. Deletions larger than the configured threshold first return a preview and a confirm_token; show it to the user, then repeat the identical call with confirm_token to delete. The most recent deletions are kept in a small recovery buffer and can be restored by the user with `octobrain memory undo`. Use memory_id for single deletion, or query+filters for bulk removal. Don't forget memories just because they're old — importance decay handles that. Only delete when information is wrong or superseded."
     )]
     async fn forget(
         &self,
         Parameters(params): Parameters<ForgetParams>,
     ) -> Result<String, McpError> {
         let provider = self
-            .get_memory_provider(params.project.clone(), params.role.clone())
+            .get_memory_provider(
+                params.project.clone(),
+                params.role.clone(),
+                params.project_path.clone(),
+            )
             .await?;
         let args = serde_json::to_value(&params).map_err(|e| {
             McpError::internal_error(format!("Failed to serialize params: {}", e), None)
@@ -597,55 +1023,268 @@ impl McpServer {
         provider.execute_forget(&args).await.map_err(to_rmcp_error)
     }
 
+    #[tool(
+        name = "feedback",
+        description = "Rate a remember result as helpful or unhelpful for the query that returned it. Future searches with similar queries rank helpful memories higher and unhelpful ones lower. Use sparingly: rate a result when it clearly solved or clearly misled the task, not every result."
+    )]
+    async fn feedback(
+        &self,
+        Parameters(params): Parameters<FeedbackParams>,
+    ) -> Result<String, McpError> {
+        let provider = self
+            .get_memory_provider(
+                params.project.clone(),
+                params.role.clone(),
+                params.project_path.clone(),
+            )
+            .await?;
+        let args = serde_json::to_value(&params).map_err(|e| {
+            McpError::internal_error(format!("Failed to serialize params: {}", e), None)
+        })?;
+        provider
+            .execute_feedback(&args)
+            .await
+            .map_err(to_rmcp_error)
+    }
+
+    #[tool(
+        name = "revise",
+        description = "Update an existing memory in place when its information changed: fix the content, retitle it, adjust importance, or add/remove tags and related files. Only pass the fields to change. Unlike forget + memorize, the memory keeps its ID, relationships and access history, and the previous version is saved so the user can roll it back."
+    )]
+    async fn revise(
+        &self,
+        Parameters(params): Parameters<ReviseParams>,
+    ) -> Result<String, McpError> {
+        let provider = self
+            .get_memory_provider(
+                params.project.clone(),
+                params.role.clone(),
+                params.project_path.clone(),
+            )
+            .await?;
+        let args = serde_json::to_value(&params).map_err(|e| {
+            McpError::internal_error(format!("Failed to serialize params: {}", e), None)
+        })?;
+        provider.execute_revise(&args).await.map_err(to_rmcp_error)
+    }
+
+    #[tool(
+        name = "relate",
+        description = "Link two existing memories with a typed relationship (related_to, depends_on, supersedes, similar, conflicts, implements, extends, achieves), read as 'source <type> target'. Use it to curate the memory graph: mark a decision that supersedes an older one, or connect a fix to the design it implements. When storing a new memory, prefer memorize's related_to instead."
+    )]
+    async fn relate(
+        &self,
+        Parameters(params): Parameters<RelateParams>,
+    ) -> Result<String, McpError> {
+        let provider = self
+            .get_memory_provider(
+                params.project.clone(),
+                params.role.clone(),
+                params.project_path.clone(),
+            )
+            .await?;
+        let args = serde_json::to_value(&params).map_err(|e| {
+            McpError::internal_error(format!("Failed to serialize params: {}", e), None)
+        })?;
+        provider.execute_relate(&args).await.map_err(to_rmcp_error)
+    }
+
+    #[tool(
+        name = "relationships",
+        description = "List every relationship of one memory, outgoing (->) and incoming (<-), with the type, the other memory's ID and title, strength and description. Includes links created automatically by auto-linking."
+    )]
+    async fn relationships(
+        &self,
+        Parameters(params): Parameters<RelationshipsParams>,
+    ) -> Result<String, McpError> {
+        let provider = self
+            .get_memory_provider(
+                params.project.clone(),
+                params.role.clone(),
+                params.project_path.clone(),
+            )
+            .await?;
+        let args = serde_json::to_value(&params).map_err(|e| {
+            McpError::internal_error(format!("Failed to serialize params: {}", e), None)
+        })?;
+        provider
+            .execute_relationships(&args)
+            .await
+            .map_err(to_rmcp_error)
+    }
+
+    #[tool(
+        name = "memory_stats",
+        description = "Overview of the memory store before querying it: memory counts by type, tag distribution, relationship count, database size, and a decay summary (average stored vs. decayed importance, importance histogram, memories due for cleanup). Returned as text plus structured JSON. Use it to pick useful memory_types and tags filters for remember, or to check whether anything relevant is stored at all."
+    )]
+    async fn memory_stats(
+        &self,
+        Parameters(params): Parameters<MemoryStatsParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let provider = self
+            .get_memory_provider(
+                params.project.clone(),
+                params.role.clone(),
+                params.project_path.clone(),
+            )
+            .await?;
+        let (output, structured) = provider
+            .execute_memory_stats()
+            .await
+            .map_err(to_rmcp_error)?;
+        let mut result = CallToolResult::success(vec![Content::text(output)]);
+        result.structured_content = Some(structured);
+        Ok(result)
+    }
+
+    #[tool(
+        name = "recall",
+        description = "Search memories and the knowledge base at once when you don't know which holds the answer. Runs memory hybrid search and knowledge search concurrently and fuses both rankings (fusion='rrf' by rank, default; 'weighted' by normalized score, tuned with memory_weight). Each result is labelled [memory] with a memory ID or [knowledge] with a chunk ID — follow up with remember or knowledge get for full content. If one store fails, results from the other are returned with a note."
+    )]
+    async fn recall(
+        &self,
+        Parameters(params): Parameters<RecallParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let limit = params.limit.unwrap_or(10);
+        let session_id = self.session.lock().await.session_id.clone();
+        let (memories, knowledge) = tokio::join!(
+            async {
+                self.get_memory_provider(
+                    params.project.clone(),
+                    params.role.clone(),
+                    params.project_path.clone(),
+                )
+                .await?
+                .recall_hits(&params.query, limit)
+                .await
+                .map_err(to_rmcp_error)
+            },
+            async {
+                self.get_or_init_knowledge()
+                    .await?
+                    .recall_hits(&params.query, limit, &session_id)
+                    .await
+                    .map_err(to_rmcp_error)
+            }
+        );
+        let (memories, knowledge, unavailable) = match (memories, knowledge) {
+            (Err(e), Err(_)) => return Err(e),
+            (Ok(m), Ok(k)) => (m, k, None),
+            (Ok(m), Err(e)) => (m, Vec::new(), Some(("knowledge", e))),
+            (Err(e), Ok(k)) => (Vec::new(), k, Some(("memory", e))),
+        };
+
+        let method = match params.fusion.unwrap_or(RecallFusion::Rrf) {
+            RecallFusion::Rrf => FusionMethod::Rrf,
+            RecallFusion::Weighted => FusionMethod::Weighted,
+        };
+        let hits = fuse(
+            memories,
+            knowledge,
+            method,
+            params.memory_weight.unwrap_or(0.5),
+            limit,
+        );
+
+        let mut output = format_hits(&hits);
+        let mut structured = serde_json::json!({ "fusion": method, "results": hits });
+        if let Some((store, e)) = unavailable {
+            debug!("recall: {} search failed: {}", store, e.message);
+            output.push_str(&format!(
+                "\n\nNote: {} search failed, showing {} only: {}",
+                store,
+                if store == "memory" {
+                    "knowledge"
+                } else {
+                    "memories"
+                },
+                e.message
+            ));
+            structured["unavailable"] = serde_json::json!({ "store": store, "error": e.message });
+        }
+        let mut result = CallToolResult::success(vec![Content::text(output)]);
+        result.structured_content = Some(structured);
+        Ok(result)
+    }
+
     #[tool(
         name = "knowledge",
-        description = "Knowledge base with five commands. The 'source' parameter (when used) ALWAYS refers to a SINGLE FILE or URL — never a directory; passing a directory path is an error. 'search': semantic search across indexed content — provide source (single URL or file) to auto-index on-the-fly, omit to search all indexed sources. 'store': save raw text under a unique key (session-scoped, auto-cleaned) — error if key exists, delete first to replace. 'delete': remove stored content by key. 'read': fetch and return the FULL text content of a single URL or file — use ONLY as a last resort when search results are insufficient; prefer 'search' for targeted retrieval. 'match': search indexed content by regex pattern (like grep) — returns matching lines only; prefer 'search' for semantic queries, use 'match' for exact string/regex patterns. Supported file types: .html, .txt, .md, .pdf, .docx."
+        description = "Knowledge base with six commands. The 'source' parameter (when used) ALWAYS refers to a SINGLE FILE or URL — never a directory; passing a directory path is an error. 'search': semantic search across indexed content — provide source (single URL or file) to auto-index on-the-fly, collection to search only a named set of indexed sources, omit both to search all indexed sources. 'store': save raw text under a unique key (session-scoped, auto-cleaned) — error if key exists, delete first to replace. 'delete': remove stored content by key. 'read': fetch and return the FULL text content of a single URL or file — use ONLY as a last resort when search results are insufficient; prefer 'search' for targeted retrieval. 'match': search indexed content by regex pattern (like grep) — returns matching lines only; prefer 'search' for semantic queries, use 'match' for exact string/regex patterns. 'get': fetch the full chunk behind a search hit by chunk_id, plus 'context' neighbouring chunks on each side — use when a search preview is too short to act on. Supported file types: .html, .txt, .md, .pdf, .docx, and OpenAPI/Swagger specs in JSON or YAML (one chunk per endpoint)."
     )]
     async fn knowledge(
         &self,
         Parameters(params): Parameters<KnowledgeParams>,
-    ) -> Result<String, McpError> {
-        let provider = self.get_or_init_knowledge().await?;
+        context: RequestContext<RoleServer>,
+    ) -> Result<CallToolResult, McpError> {
+        // Fetch/chunk/embed/store phases of on-demand indexing, when asked for
+        let reporter = ProgressReporter::start(&context.meta, context.peer.clone());
+        let provider = self
+            .get_or_init_knowledge()
+            .await?
+            .with_progress(reporter.as_ref().map(ProgressReporter::index_progress));
         let session = self.session.lock().await;
         let session_id = session.session_id.clone();
         drop(session);
 
-        match params.command {
-            KnowledgeAction::Search => {
-                provider
-                    .execute_search(
-                        params.query.as_deref(),
-                        params.source.as_deref(),
-                        &session_id,
-                    )
-                    .await
-            }
-            KnowledgeAction::Store => {
-                provider
-                    .execute_store(
-                        params.key.as_deref(),
-                        params.content.as_deref(),
-                        &session_id,
-                    )
-                    .await
-            }
-            KnowledgeAction::Delete => {
-                provider
-                    .execute_delete(params.key.as_deref(), &session_id)
-                    .await
-            }
-            KnowledgeAction::Read => provider.execute_read(params.source.as_deref()).await,
-            KnowledgeAction::Match => {
-                provider
-                    .execute_match(
-                        params.pattern.as_deref(),
-                        params.source.as_deref(),
-                        &session_id,
-                    )
-                    .await
-            }
+        let text = |text: String| CallToolResult::success(vec![Content::text(text)]);
+        let result = match params.command {
+            // Search also returns its hits as structured content (IDs, scores, ranges)
+            KnowledgeAction::Search => provider
+                .execute_search(
+                    params.query.as_deref(),
+                    ChunkFilter {
+                        source: params.source.as_deref(),
+                        collection: params.collection.as_deref(),
+                    },
+                    params.limit,
+                    params.min_relevance,
+                    params.stitch.unwrap_or(false),
+                    &session_id,
+                )
+                .await
+                .map(|(output, structured)| {
+                    let mut result = text(output);
+                    result.structured_content = Some(structured);
+                    result
+                }),
+            KnowledgeAction::Store => provider
+                .execute_store(
+                    params.key.as_deref(),
+                    params.content.as_deref(),
+                    &session_id,
+                )
+                .await
+                .map(text),
+            KnowledgeAction::Delete => provider
+                .execute_delete(params.key.as_deref(), &session_id)
+                .await
+                .map(text),
+            KnowledgeAction::Read => provider
+                .execute_read(params.source.as_deref())
+                .await
+                .map(text),
+            KnowledgeAction::Match => provider
+                .execute_match(
+                    params.pattern.as_deref(),
+                    params.source.as_deref(),
+                    &session_id,
+                )
+                .await
+                .map(text),
+            KnowledgeAction::Get => provider
+                .execute_get(params.chunk_id.as_deref(), params.context, &session_id)
+                .await
+                .map(|(output, structured)| {
+                    let mut result = text(output);
+                    result.structured_content = Some(structured);
+                    result
+                }),
+        }
+        .map_err(to_rmcp_error);
+        if let Some(reporter) = reporter {
+            reporter.finish().await;
         }
-        .map_err(to_rmcp_error)
+        result
     }
 }
 
@@ -668,6 +1307,18 @@ impl ServerHandler for McpServer {
             .with_instructions(self.instructions.clone())
     }
 
+    /// Validate arguments against the tool's input schema, then dispatch
+    async fn call_tool(
+        &self,
+        request: CallToolRequestParams,
+        context: RequestContext<RoleServer>,
+    ) -> Result<CallToolResult, McpError> {
+        let arguments = request.arguments.clone().map(serde_json::Value::Object);
+        validators().validate(&request.name, arguments.as_ref())?;
+        let tcc = ToolCallContext::new(self, request, context);
+        Self::tool_router().call(tcc).await
+    }
+
     /// Return tool list with project/role stripped from schemas when session context is known
     async fn list_tools(
         &self,
@@ -718,6 +1369,7 @@ impl ServerHandler for McpServer {
                 let mut session = self.session.lock().await;
                 let should_lock_project = git || !self.has_local_projects;
                 session.project = if should_lock_project { project } else { None };
+                session.project_from_handshake = session.project.is_some();
                 session.role = role;
                 if let Some(sid) = session_id {
                     session.session_id = sid;
@@ -734,10 +1386,84 @@ impl ServerHandler for McpServer {
             }
         }
 
+        self.session.lock().await.roots_supported = request.capabilities.roots.is_some();
+
         // Store peer info and return server info (default behavior)
         if context.peer.peer_info().is_none() {
             context.peer.set_peer_info(request);
         }
         Ok(self.get_info())
     }
+
+    async fn on_initialized(&self, context: NotificationContext<RoleServer>) {
+        if self.session.lock().await.roots_supported {
+            self.refresh_roots(context.peer);
+        }
+    }
+
+    async fn on_roots_list_changed(&self, context: NotificationContext<RoleServer>) {
+        self.refresh_roots(context.peer);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn server(dir: &std::path::Path) -> McpServer {
+        let config: Config =
+            toml::from_str(include_str!("../../config-templates/default.toml")).unwrap();
+        McpServer::new(config, dir.to_path_buf())
+    }
+
+    #[test]
+    fn test_every_tool_is_classified_for_read_only_keys() {
+        // Tools a read-only key must not call; every other tool has to be
+        // listed in `auth::READ_TOOLS`
+        const WRITE_TOOLS: &[&str] = &["memorize", "revise", "forget", "relate", "feedback"];
+        for tool in tools_full() {
+            let name: &str = &tool.name;
+            assert!(
+                crate::mcp::auth::READ_TOOLS.contains(&name) != WRITE_TOOLS.contains(&name),
+                "tool '{}' must be either a read tool or a write tool",
+                name
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_project_path_is_refused_in_locked_session() {
+        let dir = std::env::temp_dir().join(format!("octobrain_mcp_{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(dir.join("other")).unwrap();
+        let server = server(&dir);
+        server.session.lock().await.project_locked = true;
+
+        let err = server
+            .get_memory_provider(None, None, Some(dir.join("other").display().to_string()))
+            .await
+            .err()
+            .unwrap();
+        assert!(err.message.contains("locked"));
+        assert!(server.project_providers.lock().await.is_empty());
+        assert!(tools_locked().iter().all(|tool| {
+            tool.input_schema
+                .get("properties")
+                .and_then(|p| p.as_object())
+                .is_none_or(|p| !p.contains_key("project_path"))
+        }));
+    }
+
+    #[tokio::test]
+    async fn test_project_path_outside_roots_is_refused() {
+        let dir = std::env::temp_dir().join(format!("octobrain_mcp_{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let server = server(&dir);
+
+        let err = server
+            .get_memory_provider(None, None, Some(std::env::temp_dir().display().to_string()))
+            .await
+            .err()
+            .unwrap();
+        assert!(err.message.contains("inside"));
+    }
 }
@@ -12,13 +12,99 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+/// What kind of failure an error came from, reported to MCP clients as
+/// `data.cause` so they can decide whether to retry without parsing messages.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorCause {
+    /// The arguments were wrong; retrying the same call cannot succeed
+    Validation,
+    /// The embedding provider failed or timed out
+    EmbeddingProvider,
+    /// LanceDB or the filesystem failed, or another process holds the write lock
+    Storage,
+    /// An HTTP fetch (knowledge sources, LLM calls) failed
+    Network,
+    /// Anything else
+    Internal,
+}
+
+impl ErrorCause {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Validation => "validation",
+            Self::EmbeddingProvider => "embedding_provider",
+            Self::Storage => "storage",
+            Self::Network => "network",
+            Self::Internal => "internal",
+        }
+    }
+
+    /// JSON-RPC error code: the standard codes for bad arguments and internal
+    /// failures, and implementation-defined server codes for the rest.
+    pub fn code(&self) -> i32 {
+        match self {
+            Self::Validation => -32602,
+            Self::EmbeddingProvider => -32010,
+            Self::Storage => -32011,
+            Self::Network => -32012,
+            Self::Internal => -32603,
+        }
+    }
+
+    /// Classify `error` by the first recognized error type in its chain.
+    /// Returns the cause and whether the same call may succeed if retried.
+    pub fn classify(error: &anyhow::Error) -> (Self, bool) {
+        for cause in error.chain() {
+            if let Some(e) = cause.downcast_ref::<crate::embedding::EmbeddingError>() {
+                return (Self::EmbeddingProvider, e.retryable);
+            }
+            if cause
+                .downcast_ref::<crate::memory::write_lock::WriteLockBusy>()
+                .is_some()
+            {
+                return (Self::Storage, true);
+            }
+            if let Some(e) = cause.downcast_ref::<lancedb::Error>() {
+                let retryable = match e {
+                    lancedb::Error::Timeout { .. } | lancedb::Error::ObjectStore { .. } => true,
+                    lancedb::Error::Lance { source } => {
+                        source.to_string().to_lowercase().contains("conflict")
+                    }
+                    _ => false,
+                };
+                return (Self::Storage, retryable);
+            }
+            if let Some(e) = cause.downcast_ref::<std::io::Error>() {
+                let retryable = matches!(
+                    e.kind(),
+                    std::io::ErrorKind::TimedOut
+                        | std::io::ErrorKind::Interrupted
+                        | std::io::ErrorKind::WouldBlock
+                );
+                return (Self::Storage, retryable);
+            }
+            if let Some(e) = cause.downcast_ref::<reqwest::Error>() {
+                let retryable = e.is_timeout()
+                    || e.is_connect()
+                    || e.status()
+                        .is_some_and(|s| s.is_server_error() || s.as_u16() == 429);
+                return (Self::Network, retryable);
+            }
+        }
+        (Self::Internal, false)
+    }
+}
+
 /// Error type for memory and knowledge provider operations.
-/// Carries a human-readable message, the operation name for context, and optional details.
+/// Carries a human-readable message, the operation name for context, optional
+/// details, and the cause/retry hint surfaced to clients in the error data.
 #[derive(Debug, Clone)]
 pub struct McpError {
     pub message: String,
     pub operation: String,
     pub details: Option<String>,
+    pub cause: ErrorCause,
+    pub retryable: bool,
 }
 
 impl McpError {
@@ -27,6 +113,8 @@ impl McpError {
             message: message.into(),
             operation: operation.into(),
             details: None,
+            cause: ErrorCause::Validation,
+            retryable: false,
         }
     }
 
@@ -35,6 +123,8 @@ impl McpError {
             message: message.into(),
             operation: operation.into(),
             details: None,
+            cause: ErrorCause::Internal,
+            retryable: false,
         }
     }
 
@@ -42,11 +132,17 @@ impl McpError {
         self.details = Some(details.into());
         self
     }
+
+    /// Take cause and retry hint from the underlying error.
+    pub fn caused_by(mut self, error: &anyhow::Error) -> Self {
+        (self.cause, self.retryable) = ErrorCause::classify(error);
+        self
+    }
 }
 
 impl From<anyhow::Error> for McpError {
     fn from(error: anyhow::Error) -> Self {
-        McpError::internal_error(error.to_string(), "unknown_operation")
+        McpError::internal_error(error.to_string(), "unknown_operation").caused_by(&error)
     }
 }
 
@@ -57,3 +153,49 @@ impl std::fmt::Display for McpError {
 }
 
 impl std::error::Error for McpError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Context;
+
+    #[test]
+    fn test_classify_by_error_chain() {
+        let busy: anyhow::Error = crate::memory::write_lock::WriteLockBusy {
+            path: "write.lock".into(),
+            waited: std::time::Duration::from_secs(30),
+        }
+        .into();
+        let busy = busy.context("Failed to store memory");
+        assert_eq!(ErrorCause::classify(&busy), (ErrorCause::Storage, true));
+
+        let io: anyhow::Error =
+            std::io::Error::new(std::io::ErrorKind::PermissionDenied, "read-only").into();
+        assert_eq!(ErrorCause::classify(&io), (ErrorCause::Storage, false));
+
+        let plain = anyhow::anyhow!("something odd");
+        assert_eq!(ErrorCause::classify(&plain), (ErrorCause::Internal, false));
+    }
+
+    #[test]
+    fn test_embedding_errors_carry_retry_hint() {
+        let result: Result<(), crate::embedding::EmbeddingError> =
+            Err(crate::embedding::EmbeddingError {
+                message: "429 Too Many Requests".to_string(),
+                retryable: true,
+            });
+        let error = result.context("Failed to search memories").unwrap_err();
+        let mcp = McpError::internal_error(error.to_string(), "remember").caused_by(&error);
+        assert_eq!(mcp.cause, ErrorCause::EmbeddingProvider);
+        assert!(mcp.retryable);
+        assert_eq!(mcp.cause.as_str(), "embedding_provider");
+    }
+
+    #[test]
+    fn test_invalid_params_is_validation() {
+        let e = McpError::invalid_params("Missing required parameter 'title'", "memorize");
+        assert_eq!(e.cause, ErrorCause::Validation);
+        assert!(!e.retryable);
+        assert_eq!(e.cause.code(), -32602);
+    }
+}
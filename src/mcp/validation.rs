@@ -0,0 +1,331 @@
+// Copyright 2026 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Up-front validation of `tools/call` arguments against each tool's input schema.
+//!
+//! Without it, a bad argument surfaces as a serde error about the whole params
+//! struct, or deep inside a handler. Validating first lets us answer with
+//! `invalid_params` naming every offending field, which agents can act on.
+
+use jsonschema::error::ValidationErrorKind;
+use jsonschema::Validator;
+use rmcp::model::Tool;
+use rmcp::ErrorData as McpError;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+
+/// Enum value of `#[serde(other)]` catch-all variants. serde maps any unknown
+/// string onto it, so the schema must accept any string there too.
+const CATCH_ALL_VARIANT: &str = "other";
+
+/// One schema violation, addressed by argument path (`tags[2]`, `related_to[0].strength`).
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldError {
+    pub field: String,
+    pub message: String,
+}
+
+/// Compiled input schemas keyed by tool name.
+pub struct ToolValidators {
+    validators: HashMap<String, Validator>,
+}
+
+impl ToolValidators {
+    /// Compile every tool's input schema. A schema that fails to compile is
+    /// logged and its tool left unvalidated rather than unusable.
+    pub fn new(tools: &[Tool]) -> Self {
+        let mut validators = HashMap::new();
+        for tool in tools {
+            let mut schema = Value::Object(tool.input_schema.as_ref().clone());
+            accept_catch_all(&mut schema);
+            match jsonschema::validator_for(&schema) {
+                Ok(validator) => {
+                    validators.insert(tool.name.to_string(), validator);
+                }
+                Err(e) => tracing::warn!("Input schema of tool '{}' is invalid: {}", tool.name, e),
+            }
+        }
+        Self { validators }
+    }
+
+    /// Violations of `tool`'s schema in `arguments` (missing arguments count as `{}`).
+    /// Unknown tools yield no errors; the router reports them itself.
+    pub fn check(&self, tool: &str, arguments: Option<&Value>) -> Vec<FieldError> {
+        let Some(validator) = self.validators.get(tool) else {
+            return Vec::new();
+        };
+        let empty = json!({});
+        let instance = arguments.unwrap_or(&empty);
+        let mut errors: Vec<FieldError> = validator
+            .iter_errors(instance)
+            .map(|e| FieldError {
+                field: field_name(e.instance_path.as_str(), &e.kind),
+                message: e.to_string(),
+            })
+            .collect();
+        errors.dedup();
+        errors
+    }
+
+    /// `Err(invalid_params)` listing every violation, or `Ok` when `arguments` fit.
+    pub fn validate(&self, tool: &str, arguments: Option<&Value>) -> Result<(), McpError> {
+        let errors = self.check(tool, arguments);
+        if errors.is_empty() {
+            return Ok(());
+        }
+        let summary = errors
+            .iter()
+            .map(|e| format!("{}: {}", e.field, e.message))
+            .collect::<Vec<_>>()
+            .join("; ");
+        Err(McpError::invalid_params(
+            format!("Invalid arguments for '{}': {}", tool, summary),
+            Some(json!({
+                "tool": tool,
+                "cause": "validation",
+                "retryable": false,
+                "errors": errors
+                    .iter()
+                    .map(|e| json!({ "field": e.field, "message": e.message }))
+                    .collect::<Vec<_>>(),
+            })),
+        ))
+    }
+}
+
+/// schemars renders an enum with a documented catch-all variant as
+/// `oneOf: [{enum: [...]}, {const: "other"}]`. Widen the catch-all branch to
+/// any string and switch to `anyOf`, since known values now match both branches.
+fn accept_catch_all(schema: &mut Value) {
+    match schema {
+        Value::Object(object) => {
+            let has_catch_all =
+                object
+                    .get("oneOf")
+                    .and_then(|v| v.as_array())
+                    .is_some_and(|variants| {
+                        variants.iter().any(|v| {
+                            v.get("const").and_then(|c| c.as_str()) == Some(CATCH_ALL_VARIANT)
+                        })
+                    });
+            if has_catch_all {
+                if let Some(Value::Array(mut variants)) = object.remove("oneOf") {
+                    for variant in variants.iter_mut() {
+                        if variant.get("const").and_then(|c| c.as_str()) == Some(CATCH_ALL_VARIANT)
+                        {
+                            *variant = json!({ "type": "string" });
+                        }
+                    }
+                    object.insert("anyOf".to_string(), Value::Array(variants));
+                }
+            }
+            object.values_mut().for_each(accept_catch_all);
+        }
+        Value::Array(items) => items.iter_mut().for_each(accept_catch_all),
+        _ => {}
+    }
+}
+
+/// Turn a JSON pointer (`/related_to/0/strength`) into `related_to[0].strength`.
+/// Missing required properties are reported on the property itself.
+fn field_name(pointer: &str, kind: &ValidationErrorKind) -> String {
+    let mut field = String::new();
+    for segment in pointer.split('/').skip(1) {
+        let segment = segment.replace("~1", "/").replace("~0", "~");
+        if segment.parse::<usize>().is_ok() {
+            field.push_str(&format!("[{}]", segment));
+        } else {
+            if !field.is_empty() {
+                field.push('.');
+            }
+            field.push_str(&segment);
+        }
+    }
+    if let ValidationErrorKind::Required { property } = kind {
+        let property = property.as_str().unwrap_or_default();
+        if !field.is_empty() {
+            field.push('.');
+        }
+        field.push_str(property);
+    }
+    if field.is_empty() {
+        "arguments".to_string()
+    } else {
+        field
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mcp::server::{
+        ForgetParams, KnowledgeParams, MemorizeParams, RecallParams, RelateParams,
+    };
+    use rmcp::handler::server::common::schema_for_type;
+
+    fn validators() -> ToolValidators {
+        ToolValidators::new(&[
+            Tool::new("memorize", "", schema_for_type::<MemorizeParams>()),
+            Tool::new("forget", "", schema_for_type::<ForgetParams>()),
+            Tool::new("knowledge", "", schema_for_type::<KnowledgeParams>()),
+            Tool::new("recall", "", schema_for_type::<RecallParams>()),
+            Tool::new("relate", "", schema_for_type::<RelateParams>()),
+        ])
+    }
+
+    #[test]
+    fn test_valid_arguments_pass() {
+        let args = json!({
+            "title": "Use LanceDB",
+            "content": "Vectors live in LanceDB.",
+            "importance": 0.8,
+            "tags": ["storage"],
+            "related_to": [{ "target_id": "abc", "relationship_type": "depends_on" }]
+        });
+        assert!(validators().validate("memorize", Some(&args)).is_ok());
+    }
+
+    #[test]
+    fn test_missing_required_field_is_named() {
+        let args = json!({ "title": "No content" });
+        let errors = validators().check("memorize", Some(&args));
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].field, "content");
+
+        let errors = validators().check("forget", None);
+        assert!(errors.iter().any(|e| e.field == "confirm"));
+    }
+
+    #[test]
+    fn test_nested_fields_are_addressed() {
+        let args = json!({
+            "title": "t",
+            "content": "c",
+            "importance": 3.0,
+            "tags": ["ok", 7],
+            "related_to": [{ "target_id": "abc", "relationship_type": "depends_on", "strength": 2 }]
+        });
+        let fields: Vec<String> = validators()
+            .check("memorize", Some(&args))
+            .into_iter()
+            .map(|e| e.field)
+            .collect();
+        assert!(fields.contains(&"importance".to_string()));
+        assert!(fields.contains(&"tags[1]".to_string()));
+        assert!(fields.contains(&"related_to[0].strength".to_string()));
+    }
+
+    #[test]
+    fn test_error_is_invalid_params_with_field_data() {
+        let args = json!({ "title": "t", "content": "c", "importance": "high" });
+        let err = validators().validate("memorize", Some(&args)).unwrap_err();
+        assert_eq!(err.code, rmcp::model::ErrorCode::INVALID_PARAMS);
+        assert!(err.message.contains("importance"));
+        let data = err.data.unwrap();
+        assert_eq!(data["errors"][0]["field"], "importance");
+    }
+
+    #[test]
+    fn test_forget_exclusions_are_validated() {
+        let args = json!({
+            "query": "old auth notes",
+            "confirm": true,
+            "exclude_tags": ["keep"],
+            "exclude_types": ["decision"],
+            "exclude_files": ["src/auth.rs"]
+        });
+        assert!(validators().validate("forget", Some(&args)).is_ok());
+
+        let args = json!({ "query": "old auth notes", "confirm": true, "exclude_tags": "keep" });
+        let errors = validators().check("forget", Some(&args));
+        assert!(errors.iter().any(|e| e.field == "exclude_tags"));
+    }
+
+    #[test]
+    fn test_catch_all_memory_type_is_accepted() {
+        let args = json!({ "title": "t", "content": "c", "memory_type": "goal" });
+        assert!(validators().validate("memorize", Some(&args)).is_ok());
+    }
+
+    #[test]
+    fn test_unknown_tool_is_left_to_router() {
+        assert!(validators().check("nope", Some(&json!({}))).is_empty());
+    }
+
+    #[test]
+    fn test_unknown_relationship_type_is_rejected() {
+        let args = json!({
+            "title": "t",
+            "content": "c",
+            "related_to": [{ "target_id": "abc", "relationship_type": "blocks" }]
+        });
+        let errors = validators().check("memorize", Some(&args));
+        assert!(errors
+            .iter()
+            .any(|e| e.field == "related_to[0].relationship_type"));
+    }
+
+    #[test]
+    fn test_relate_requires_both_ends_and_known_type() {
+        let ok = json!({
+            "source_id": "abc",
+            "target_id": "def",
+            "relationship_type": "supersedes",
+            "strength": 0.9
+        });
+        assert!(validators().validate("relate", Some(&ok)).is_ok());
+
+        let bad = json!({ "source_id": "abc", "relationship_type": "blocks", "strength": 2 });
+        let fields: Vec<String> = validators()
+            .check("relate", Some(&bad))
+            .into_iter()
+            .map(|e| e.field)
+            .collect();
+        assert!(fields.contains(&"target_id".to_string()));
+        assert!(fields.contains(&"relationship_type".to_string()));
+        assert!(fields.contains(&"strength".to_string()));
+    }
+
+    #[test]
+    fn test_knowledge_search_limit_and_min_relevance_ranges() {
+        let ok =
+            json!({ "command": "search", "query": "retries", "limit": 3, "min_relevance": 0.4 });
+        assert!(validators().validate("knowledge", Some(&ok)).is_ok());
+
+        let bad =
+            json!({ "command": "search", "query": "retries", "limit": 0, "min_relevance": 1.5 });
+        let fields: Vec<String> = validators()
+            .check("knowledge", Some(&bad))
+            .into_iter()
+            .map(|e| e.field)
+            .collect();
+        assert!(fields.contains(&"limit".to_string()));
+        assert!(fields.contains(&"min_relevance".to_string()));
+    }
+
+    #[test]
+    fn test_recall_fusion_and_weight() {
+        let ok = json!({ "query": "retry policy", "fusion": "weighted", "memory_weight": 0.7 });
+        assert!(validators().validate("recall", Some(&ok)).is_ok());
+
+        let bad = json!({ "query": "retry policy", "fusion": "borda", "memory_weight": 2 });
+        let fields: Vec<String> = validators()
+            .check("recall", Some(&bad))
+            .into_iter()
+            .map(|e| e.field)
+            .collect();
+        assert!(fields.contains(&"fusion".to_string()));
+        assert!(fields.contains(&"memory_weight".to_string()));
+    }
+}
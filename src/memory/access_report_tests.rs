@@ -0,0 +1,58 @@
+// Copyright 2026 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#[cfg(test)]
+mod tests {
+    use super::super::manager::{top_queries, weekly_access_counts};
+    use super::super::types::AccessLogEntry;
+    use chrono::{Duration, TimeZone, Utc};
+
+    fn entry(memory_id: &str, days_ago: i64, now: chrono::DateTime<Utc>) -> AccessLogEntry {
+        AccessLogEntry {
+            memory_id: memory_id.to_string(),
+            query: "q".to_string(),
+            accessed_at: now - Duration::days(days_ago),
+        }
+    }
+
+    #[test]
+    fn test_weekly_buckets_oldest_first_without_gaps() {
+        // Wednesday
+        let now = Utc.with_ymd_and_hms(2026, 3, 18, 12, 0, 0).unwrap();
+        let log = vec![
+            entry("a", 0, now),
+            entry("a", 1, now),
+            entry("b", 2, now),
+            // previous week skipped on purpose; two weeks back:
+            entry("c", 15, now),
+        ];
+        let weeks = weekly_access_counts(&log, now, 3);
+        assert_eq!(weeks.len(), 3);
+        assert_eq!(
+            weeks[2].week_start,
+            chrono::NaiveDate::from_ymd_opt(2026, 3, 16).unwrap()
+        );
+        assert_eq!(weeks[2].accesses, 3);
+        assert_eq!(weeks[2].distinct_memories, 2);
+        assert_eq!(weeks[1].accesses, 0);
+        assert_eq!(weeks[0].accesses, 1);
+    }
+
+    #[test]
+    fn test_top_queries_sorted_by_count() {
+        let queries = ["auth", "db", "auth", "cache", "db", "auth"];
+        let top = top_queries(&queries, 2);
+        assert_eq!(top, vec![("auth".to_string(), 3), ("db".to_string(), 2)]);
+    }
+}
@@ -0,0 +1,124 @@
+// Copyright 2026 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Short IDs and named aliases for memories.
+//!
+//! Anywhere a memory ID is accepted, a unique ID prefix (like a git
+//! abbreviated hash) or an alias set with `memory alias set` works too.
+//! Aliases are per project and live in `aliases.json` next to the database.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+
+/// Shortest prefix shown or accepted as a short ID: the first UUID group.
+pub const MIN_SHORT_ID_LEN: usize = 8;
+
+/// Whether `reference` can only be an ID or ID prefix (hex digits and hyphens).
+pub fn is_id_fragment(reference: &str) -> bool {
+    !reference.is_empty() && reference.chars().all(|c| c.is_ascii_hexdigit() || c == '-')
+}
+
+/// Check an alias name: 1-64 of `[A-Za-z0-9._-]`, and not something that
+/// could be read as an ID prefix.
+pub fn validate_alias(alias: &str) -> Result<()> {
+    if alias.is_empty() || alias.len() > 64 {
+        anyhow::bail!("Alias must be 1-64 characters");
+    }
+    if !alias
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-'))
+    {
+        anyhow::bail!("Alias may only contain letters, digits, '.', '_' and '-'");
+    }
+    if is_id_fragment(alias) {
+        anyhow::bail!(
+            "Alias '{}' looks like an ID prefix; include a letter after 'f' or another symbol",
+            alias
+        );
+    }
+    Ok(())
+}
+
+/// Shortest unique prefix (at least `min_len` characters) for every ID,
+/// computed from each ID's longest common prefix with its sorted neighbours.
+pub fn shortest_unique_prefixes(ids: &[String], min_len: usize) -> HashMap<String, String> {
+    let mut sorted: Vec<&str> = ids.iter().map(|s| s.as_str()).collect();
+    sorted.sort_unstable();
+    sorted.dedup();
+    let common = |a: &str, b: &str| a.bytes().zip(b.bytes()).take_while(|(x, y)| x == y).count();
+    sorted
+        .iter()
+        .enumerate()
+        .map(|(i, id)| {
+            let before = i.checked_sub(1).map_or(0, |j| common(id, sorted[j]));
+            let after = sorted.get(i + 1).map_or(0, |next| common(id, next));
+            let len = (before.max(after) + 1).max(min_len).min(id.len());
+            (id.to_string(), id[..len].to_string())
+        })
+        .collect()
+}
+
+/// Aliases of every project, persisted as JSON (`storage::get_aliases_path`).
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct AliasBook {
+    /// project label → alias → memory ID
+    pub projects: HashMap<String, BTreeMap<String, String>>,
+}
+
+impl AliasBook {
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let raw = std::fs::read_to_string(path)
+            .with_context(|| format!("Cannot read aliases '{}'", path.display()))?;
+        serde_json::from_str(&raw)
+            .with_context(|| format!("Aliases file '{}' is corrupt", path.display()))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        std::fs::write(path, serde_json::to_string_pretty(self)?)
+            .with_context(|| format!("Cannot write aliases '{}'", path.display()))
+    }
+
+    pub fn get(&self, project: &str, alias: &str) -> Option<&str> {
+        self.projects.get(project)?.get(alias).map(|s| s.as_str())
+    }
+
+    /// Point `alias` at `memory_id`; returns the ID it pointed at before.
+    pub fn set(&mut self, project: &str, alias: &str, memory_id: &str) -> Option<String> {
+        self.projects
+            .entry(project.to_string())
+            .or_default()
+            .insert(alias.to_string(), memory_id.to_string())
+    }
+
+    pub fn remove(&mut self, project: &str, alias: &str) -> Option<String> {
+        self.projects.get_mut(project)?.remove(alias)
+    }
+
+    pub fn list(&self, project: &str) -> Vec<(String, String)> {
+        self.projects
+            .get(project)
+            .map(|aliases| {
+                aliases
+                    .iter()
+                    .map(|(a, id)| (a.clone(), id.clone()))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
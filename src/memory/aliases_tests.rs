@@ -0,0 +1,111 @@
+// Copyright 2026 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#[cfg(test)]
+mod tests {
+    use super::super::aliases::{
+        is_id_fragment, shortest_unique_prefixes, validate_alias, AliasBook, MIN_SHORT_ID_LEN,
+    };
+
+    fn temp_path() -> std::path::PathBuf {
+        std::env::temp_dir().join(format!(
+            "octobrain_aliases_test_{}.json",
+            uuid::Uuid::new_v4()
+        ))
+    }
+
+    #[test]
+    fn test_short_ids_respect_minimum_length() {
+        let ids = vec![
+            "1a2b3c4d-0000-0000-0000-000000000000".to_string(),
+            "9f8e7d6c-0000-0000-0000-000000000000".to_string(),
+        ];
+        let prefixes = shortest_unique_prefixes(&ids, MIN_SHORT_ID_LEN);
+        assert_eq!(prefixes[&ids[0]], "1a2b3c4d");
+        assert_eq!(prefixes[&ids[1]], "9f8e7d6c");
+    }
+
+    #[test]
+    fn test_short_ids_grow_past_shared_prefix() {
+        let ids = vec![
+            "abcdef01-2345-0000-0000-000000000000".to_string(),
+            "abcdef01-2399-0000-0000-000000000000".to_string(),
+            "abcdef02-0000-0000-0000-000000000000".to_string(),
+        ];
+        let prefixes = shortest_unique_prefixes(&ids, MIN_SHORT_ID_LEN);
+        assert_eq!(prefixes[&ids[0]], "abcdef01-234");
+        assert_eq!(prefixes[&ids[1]], "abcdef01-239");
+        assert_eq!(prefixes[&ids[2]], "abcdef02");
+        for (id, short) in &prefixes {
+            let hits = ids.iter().filter(|other| other.starts_with(short)).count();
+            assert_eq!(hits, 1, "prefix {} of {} is not unique", short, id);
+        }
+    }
+
+    #[test]
+    fn test_id_fragment_detection() {
+        assert!(is_id_fragment("1a2b3c4d"));
+        assert!(is_id_fragment("1a2b3c4d-00"));
+        assert!(!is_id_fragment("auth-design"));
+        assert!(!is_id_fragment(""));
+    }
+
+    #[test]
+    fn test_alias_validation() {
+        assert!(validate_alias("auth-design").is_ok());
+        assert!(validate_alias("v2.schema_notes").is_ok());
+        assert!(validate_alias("").is_err());
+        assert!(validate_alias("has space").is_err());
+        assert!(validate_alias(&"x".repeat(65)).is_err());
+        // Would be read as an ID prefix
+        assert!(validate_alias("deadbeef").is_err());
+        assert!(validate_alias("cafe-face").is_err());
+    }
+
+    #[test]
+    fn test_alias_book_is_per_project_and_round_trips() {
+        let path = temp_path();
+
+        let mut book = AliasBook::load(&path).unwrap();
+        assert!(book.list("proj").is_empty());
+        assert_eq!(book.set("proj", "auth-design", "id-1"), None);
+        assert_eq!(
+            book.set("proj", "auth-design", "id-2"),
+            Some("id-1".to_string())
+        );
+        book.set("other", "auth-design", "id-3");
+        book.save(&path).unwrap();
+
+        let mut loaded = AliasBook::load(&path).unwrap();
+        assert_eq!(loaded.get("proj", "auth-design"), Some("id-2"));
+        assert_eq!(loaded.get("other", "auth-design"), Some("id-3"));
+        assert_eq!(loaded.get("default", "auth-design"), None);
+
+        assert_eq!(
+            loaded.remove("proj", "auth-design"),
+            Some("id-2".to_string())
+        );
+        assert!(loaded.list("proj").is_empty());
+        assert_eq!(loaded.list("other").len(), 1);
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn test_corrupt_alias_book_is_an_error() {
+        let path = temp_path();
+        std::fs::write(&path, "{not json").unwrap();
+        assert!(AliasBook::load(&path).is_err());
+        std::fs::remove_file(&path).ok();
+    }
+}
@@ -0,0 +1,107 @@
+// Copyright 2026 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Answer synthesis over retrieved memories.
+//!
+//! Takes the top hybrid search results, hands them to the configured LLM and
+//! returns a short answer with `[memory-id]` citations instead of raw memories.
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+use crate::config::AnswerConfig;
+use crate::memory::llm::complete;
+use crate::memory::types::MemorySearchResult;
+
+/// Per-memory content cap inside the prompt. Keeps the context bounded even
+/// when a handful of memories carry 10k-char bodies.
+const MAX_MEMORY_CHARS: usize = 2000;
+
+const SYSTEM_PROMPT: &str = "You answer questions using ONLY the numbered memories provided. \
+Be concise (a few sentences). After every claim, cite the supporting memory by its full ID, \
+copied exactly, in square brackets, e.g. [3f2a9c1e-8b4d-4e2f-9a6b-1c7d5e0f2a3b]. If the \
+memories do not contain the answer, say so plainly instead of guessing.";
+
+/// Synthesized answer plus the memory IDs it cites.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MemoryAnswer {
+    pub answer: String,
+    /// IDs of context memories actually cited in the answer, in citation order
+    pub citations: Vec<String>,
+    /// IDs of all memories passed to the model as context
+    pub context_ids: Vec<String>,
+}
+
+/// Build the user prompt: question followed by the context memories.
+pub fn build_answer_prompt(question: &str, results: &[MemorySearchResult]) -> String {
+    let mut prompt = format!("Question: {}\n\nMemories:\n", question);
+    for result in results {
+        let memory = &result.memory;
+        let content: String = memory.content.chars().take(MAX_MEMORY_CHARS).collect();
+        prompt.push_str(&format!(
+            "\n[{}] ({}) {}\n{}\n",
+            memory.id, memory.memory_type, memory.title, content
+        ));
+    }
+    prompt.push_str("\nAnswer the question citing memory IDs in square brackets.");
+    prompt
+}
+
+/// Extract the context IDs cited in `answer`, deduplicated, in order of first appearance.
+/// Unknown IDs (hallucinated citations) are dropped.
+pub fn extract_citations(answer: &str, context_ids: &[String]) -> Vec<String> {
+    let mut positions: Vec<(usize, &String)> = context_ids
+        .iter()
+        .filter_map(|id| answer.find(&format!("[{}]", id)).map(|pos| (pos, id)))
+        .collect();
+    positions.sort_by_key(|(pos, _)| *pos);
+    positions.into_iter().map(|(_, id)| id.clone()).collect()
+}
+
+/// Ask the configured LLM to answer `question` from the top `results`.
+pub async fn synthesize_answer(
+    config: &AnswerConfig,
+    question: &str,
+    results: &[MemorySearchResult],
+) -> Result<MemoryAnswer> {
+    let context: Vec<MemorySearchResult> = results
+        .iter()
+        .take(config.max_context_memories.max(1))
+        .cloned()
+        .collect();
+    let context_ids: Vec<String> = context.iter().map(|r| r.memory.id.clone()).collect();
+
+    if context.is_empty() {
+        return Ok(MemoryAnswer {
+            answer: "No relevant memories found to answer this question.".to_string(),
+            citations: Vec::new(),
+            context_ids,
+        });
+    }
+
+    let answer = complete(
+        config,
+        SYSTEM_PROMPT,
+        &build_answer_prompt(question, &context),
+        "Answer synthesis",
+    )
+    .await?;
+    let citations = extract_citations(&answer, &context_ids);
+
+    Ok(MemoryAnswer {
+        answer,
+        citations,
+        context_ids,
+    })
+}
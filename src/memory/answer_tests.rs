@@ -0,0 +1,69 @@
+// Copyright 2026 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#[cfg(test)]
+mod tests {
+    use super::super::answer::{build_answer_prompt, extract_citations};
+    use super::super::types::{Memory, MemorySearchResult, MemoryType};
+
+    fn result(title: &str, content: &str) -> MemorySearchResult {
+        MemorySearchResult {
+            memory: Memory::new(
+                MemoryType::Architecture,
+                title.to_string(),
+                content.to_string(),
+                None,
+            ),
+            relevance_score: 0.9,
+            selection_reason: String::new(),
+            signals: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_prompt_contains_question_and_memory_ids() {
+        let results = vec![
+            result("Auth flow", "JWT tokens are issued by the gateway"),
+            result("Storage", "LanceDB holds all memories"),
+        ];
+        let prompt = build_answer_prompt("How does auth work?", &results);
+        assert!(prompt.starts_with("Question: How does auth work?"));
+        for r in &results {
+            assert!(prompt.contains(&format!("[{}]", r.memory.id)));
+        }
+        assert!(prompt.contains("JWT tokens are issued by the gateway"));
+    }
+
+    #[test]
+    fn test_prompt_truncates_long_content() {
+        let long = "x".repeat(5000);
+        let prompt = build_answer_prompt("q?", &[result("Long", &long)]);
+        assert!(!prompt.contains(&long));
+        assert!(prompt.contains(&"x".repeat(2000)));
+    }
+
+    #[test]
+    fn test_citations_in_order_and_deduplicated() {
+        let ids = vec!["aaa".to_string(), "bbb".to_string(), "ccc".to_string()];
+        let answer = "Tokens come from the gateway [bbb]. Stored in LanceDB [aaa] [bbb].";
+        assert_eq!(extract_citations(answer, &ids), vec!["bbb", "aaa"]);
+    }
+
+    #[test]
+    fn test_citations_ignore_unknown_ids() {
+        let ids = vec!["aaa".to_string()];
+        let answer = "Something [zzz] and nothing else.";
+        assert!(extract_citations(answer, &ids).is_empty());
+    }
+}
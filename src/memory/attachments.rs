@@ -0,0 +1,130 @@
+// Copyright 2026 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! File attachments for memories.
+//!
+//! Blobs are copied to `attachments/<project>/<memory_id>/<file_name>` under the
+//! system storage dir and referenced from `MemoryMetadata::attachments`. UTF-8
+//! files also contribute (truncated) text to the memory's searchable text.
+
+use anyhow::{Context, Result};
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+
+use super::types::MemoryAttachment;
+
+/// Cap on extracted text per attachment, so a large log doesn't drown the memory's
+/// own content in the embedding.
+pub const MAX_EXTRACTED_CHARS: usize = 4000;
+
+/// An attachment read from disk but not yet written to storage.
+#[derive(Debug, Clone)]
+pub struct PendingAttachment {
+    pub file_name: String,
+    pub bytes: Vec<u8>,
+    pub extracted_text: Option<String>,
+}
+
+/// Extract searchable text from an attachment: UTF-8 content without NUL bytes,
+/// truncated to `MAX_EXTRACTED_CHARS`. Binary files (images, archives) yield `None`.
+pub fn extract_text(bytes: &[u8]) -> Option<String> {
+    if bytes.contains(&0) {
+        return None;
+    }
+    let text = std::str::from_utf8(bytes).ok()?.trim();
+    if text.is_empty() {
+        return None;
+    }
+    Some(text.chars().take(MAX_EXTRACTED_CHARS).collect())
+}
+
+/// Read `path` for attaching, enforcing the `max_bytes` size limit.
+pub fn read_attachment(path: &Path, max_bytes: u64) -> Result<PendingAttachment> {
+    let file_meta = std::fs::metadata(path)
+        .with_context(|| format!("Cannot read attachment '{}'", path.display()))?;
+    if !file_meta.is_file() {
+        return Err(anyhow::anyhow!(
+            "Attachment '{}' is not a regular file",
+            path.display()
+        ));
+    }
+    if file_meta.len() > max_bytes {
+        return Err(anyhow::anyhow!(
+            "Attachment '{}' is {} KiB; limit is {} KiB (max_attachment_size_kb)",
+            path.display(),
+            file_meta.len() / 1024,
+            max_bytes / 1024
+        ));
+    }
+    let file_name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .ok_or_else(|| anyhow::anyhow!("Attachment path '{}' has no file name", path.display()))?;
+    let bytes = std::fs::read(path)
+        .with_context(|| format!("Cannot read attachment '{}'", path.display()))?;
+    let extracted_text = extract_text(&bytes);
+    Ok(PendingAttachment {
+        file_name,
+        bytes,
+        extracted_text,
+    })
+}
+
+/// Directory holding the blobs of one memory.
+fn memory_dir(root: &Path, project_label: &str, memory_id: &str) -> PathBuf {
+    root.join(project_label).join(memory_id)
+}
+
+/// Write `pending` under `root` for `memory_id` and return its metadata record.
+pub fn write_attachment(
+    root: &Path,
+    project_label: &str,
+    memory_id: &str,
+    pending: PendingAttachment,
+) -> Result<MemoryAttachment> {
+    let dir = memory_dir(root, project_label, memory_id);
+    std::fs::create_dir_all(&dir)
+        .with_context(|| format!("Cannot create attachment dir '{}'", dir.display()))?;
+    std::fs::write(dir.join(&pending.file_name), &pending.bytes)
+        .with_context(|| format!("Cannot store attachment '{}'", pending.file_name))?;
+
+    Ok(MemoryAttachment {
+        path: format!("{}/{}/{}", project_label, memory_id, pending.file_name),
+        size_bytes: pending.bytes.len() as u64,
+        sha256: format!("{:x}", Sha256::digest(&pending.bytes)),
+        file_name: pending.file_name,
+        extracted_text: pending.extracted_text,
+    })
+}
+
+/// Absolute path of a stored attachment blob.
+pub fn resolve_path(root: &Path, attachment: &MemoryAttachment) -> PathBuf {
+    root.join(&attachment.path)
+}
+
+/// Remove stored blobs for `memory_ids`. Best-effort: missing dirs are ignored.
+pub fn remove_attachments(root: &Path, project_label: &str, memory_ids: &[String]) {
+    for memory_id in memory_ids {
+        let dir = memory_dir(root, project_label, memory_id);
+        if dir.exists() {
+            if let Err(e) = std::fs::remove_dir_all(&dir) {
+                tracing::warn!(
+                    "Failed to remove attachments for memory '{}': {}",
+                    memory_id,
+                    e
+                );
+            }
+        }
+    }
+}
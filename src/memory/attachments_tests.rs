@@ -0,0 +1,101 @@
+// Copyright 2026 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#[cfg(test)]
+mod tests {
+    use super::super::attachments::{
+        extract_text, read_attachment, remove_attachments, resolve_path, write_attachment,
+        MAX_EXTRACTED_CHARS,
+    };
+    use super::super::types::{Memory, MemoryType};
+
+    fn scratch_dir() -> std::path::PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("octobrain_attach_test_{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_extract_text_only_for_utf8() {
+        assert_eq!(
+            extract_text(b"  panic at line 42\n"),
+            Some("panic at line 42".to_string())
+        );
+        assert_eq!(extract_text(&[0x89, b'P', b'N', b'G', 0, 1]), None);
+        assert_eq!(extract_text(b"   "), None);
+        let long = "a".repeat(MAX_EXTRACTED_CHARS + 10);
+        assert_eq!(
+            extract_text(long.as_bytes()).map(|t| t.len()),
+            Some(MAX_EXTRACTED_CHARS)
+        );
+    }
+
+    #[test]
+    fn test_read_attachment_enforces_size_limit() {
+        let dir = scratch_dir();
+        let file = dir.join("big.log");
+        std::fs::write(&file, vec![b'x'; 2048]).unwrap();
+        assert!(read_attachment(&file, 1024).is_err());
+        let pending = read_attachment(&file, 4096).unwrap();
+        assert_eq!(pending.file_name, "big.log");
+        assert_eq!(pending.bytes.len(), 2048);
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_write_resolve_and_remove() {
+        let dir = scratch_dir();
+        let src = dir.join("fix.patch");
+        std::fs::write(&src, "--- a/x\n+++ b/x\n").unwrap();
+        let root = dir.join("store");
+
+        let pending = read_attachment(&src, 1024).unwrap();
+        let attachment = write_attachment(&root, "proj", "mem-1", pending).unwrap();
+        assert_eq!(attachment.path, "proj/mem-1/fix.patch");
+        assert_eq!(attachment.size_bytes, 16);
+        assert_eq!(attachment.sha256.len(), 64);
+
+        let stored = resolve_path(&root, &attachment);
+        assert_eq!(
+            std::fs::read_to_string(&stored).unwrap(),
+            "--- a/x\n+++ b/x\n"
+        );
+
+        remove_attachments(&root, "proj", &["mem-1".to_string()]);
+        assert!(!stored.exists());
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_searchable_text_includes_attachment_text() {
+        let dir = scratch_dir();
+        let src = dir.join("trace.txt");
+        std::fs::write(&src, "ConnectionResetError in pool worker").unwrap();
+        let pending = read_attachment(&src, 1024).unwrap();
+        let attachment = write_attachment(&dir, "proj", "mem-2", pending).unwrap();
+
+        let mut memory = Memory::new(
+            MemoryType::BugFix,
+            "Pool crash".to_string(),
+            "Worker pool dies under load".to_string(),
+            None,
+        );
+        memory.metadata.attachments.push(attachment);
+        let text = memory.get_searchable_text();
+        assert!(text.contains("trace.txt"));
+        assert!(text.contains("ConnectionResetError in pool worker"));
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
@@ -0,0 +1,48 @@
+// Copyright 2026 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#[cfg(test)]
+mod tests {
+    use super::super::store::build_scalar_predicate_test;
+    use super::super::types::{MemoryConfig, MemoryQuery};
+
+    #[test]
+    fn test_predicate_with_author() {
+        let query = MemoryQuery {
+            created_by: Some("o'neil@example.com".to_string()),
+            ..Default::default()
+        };
+        let pred = build_scalar_predicate_test(Some("proj123"), None, &query);
+        assert!(
+            pred.contains("created_by = 'o''neil@example.com'"),
+            "Expected escaped author filter, got: {}",
+            pred
+        );
+    }
+
+    #[test]
+    fn test_author_defaults_to_empty() {
+        assert!(MemoryConfig::default().author.is_empty());
+
+        // Configs written before the field existed still load
+        let legacy: String = toml::to_string(&MemoryConfig::default())
+            .unwrap()
+            .lines()
+            .filter(|l| !l.starts_with("author"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let config: MemoryConfig = toml::from_str(&legacy).unwrap();
+        assert!(config.author.is_empty());
+    }
+}
@@ -14,8 +14,91 @@
 
 #[cfg(test)]
 mod tests {
-    use super::super::types::{MemoryConfig, MemoryGraph, RelationshipType};
-    use std::collections::HashMap;
+    use super::super::backend::{BackendOptions, StorageBackend};
+    use super::super::ephemeral_store::EphemeralStore;
+    use super::super::manager::plan_auto_links;
+    use super::super::types::{
+        Memory, MemoryConfig, MemoryGraph, MemorySource, MemoryType, RelationshipType,
+    };
+    use crate::embedding::{EmbeddingProvider, InputType};
+    use async_trait::async_trait;
+    use std::collections::{HashMap, HashSet};
+
+    const DIM: usize = 64;
+
+    /// Bag-of-words embedding: texts sharing words point the same way.
+    struct WordHashEmbedding;
+
+    fn embed(text: &str) -> Vec<f32> {
+        let mut v = vec![0.0f32; DIM];
+        for word in text.to_lowercase().split(|c: char| !c.is_alphanumeric()) {
+            if word.is_empty() {
+                continue;
+            }
+            let hash = word
+                .bytes()
+                .fold(5381usize, |h, b| h.wrapping_mul(33) ^ b as usize);
+            v[hash % DIM] += 1.0;
+        }
+        let norm = v.iter().map(|x| x * x).sum::<f32>().sqrt().max(1e-6);
+        v.iter().map(|x| x / norm).collect()
+    }
+
+    #[async_trait]
+    impl EmbeddingProvider for WordHashEmbedding {
+        async fn generate_embedding(&self, text: &str) -> anyhow::Result<Vec<f32>> {
+            Ok(embed(text))
+        }
+
+        async fn generate_embeddings_batch(
+            &self,
+            texts: Vec<String>,
+            _input_type: InputType,
+        ) -> anyhow::Result<Vec<Vec<f32>>> {
+            Ok(texts.iter().map(|t| embed(t)).collect())
+        }
+
+        fn get_dimension(&self) -> usize {
+            DIM
+        }
+    }
+
+    fn store() -> EphemeralStore {
+        // Plain vector scoring, so relevance is the cosine similarity
+        let mut search = crate::config::SearchConfig::default();
+        search.hybrid.enabled = false;
+        EphemeralStore::new(BackendOptions {
+            db_dir: std::path::PathBuf::new(),
+            project_key: Some("p1".to_string()),
+            role: None,
+            embedding_provider: Box::new(WordHashEmbedding),
+            config: MemoryConfig::default(),
+            main_config: crate::config::Config {
+                embedding: Default::default(),
+                search,
+                memory: MemoryConfig::default(),
+                knowledge: Default::default(),
+                vision: Default::default(),
+                maintenance: Default::default(),
+                server: Default::default(),
+                storage: Default::default(),
+            },
+            reranker_integration: None,
+        })
+    }
+
+    /// Full-importance, user-confirmed memory: relevance is not scaled down.
+    fn memory(title: &str, content: &str) -> Memory {
+        let mut memory = Memory::new(
+            MemoryType::Code,
+            title.to_string(),
+            content.to_string(),
+            None,
+        );
+        memory.metadata.importance = 1.0;
+        memory.metadata.source = MemorySource::UserConfirmed;
+        memory
+    }
 
     #[test]
     fn test_auto_link_config_defaults() {
@@ -112,7 +195,7 @@ mod tests {
 
     #[test]
     fn test_memory_graph_with_data() {
-        use super::super::types::{Memory, MemoryRelationship, MemoryType};
+        use super::super::types::MemoryRelationship;
         use chrono::Utc;
 
         let mut graph = MemoryGraph {
@@ -149,4 +232,68 @@ mod tests {
             "auto_linked"
         );
     }
+
+    #[tokio::test]
+    async fn test_plan_links_similar_memories_both_ways() {
+        let store = store();
+        let runtime = memory("Tokio runtime", "async tasks run on the tokio runtime");
+        let tuning = memory(
+            "Tokio runtime tuning",
+            "async tasks run on the tokio runtime",
+        );
+        let baking = memory("Banana bread", "mash ripe bananas before baking");
+        for m in [&runtime, &tuning, &baking] {
+            store.store_memory(m).await.unwrap();
+        }
+        let config = MemoryConfig::default();
+
+        let planned = plan_auto_links(&store, &config, &runtime, 0.5, &HashSet::new())
+            .await
+            .unwrap();
+        let pairs: Vec<(&str, &str)> = planned
+            .iter()
+            .map(|r| (r.source_id.as_str(), r.target_id.as_str()))
+            .collect();
+        assert_eq!(
+            pairs,
+            vec![
+                (runtime.id.as_str(), tuning.id.as_str()),
+                (tuning.id.as_str(), runtime.id.as_str()),
+            ]
+        );
+        assert!(planned
+            .iter()
+            .all(|r| r.relationship_type == RelationshipType::AutoLinked));
+        // Planning stores nothing
+        assert!(store.get_all_relationships().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_plan_skips_pairs_already_linked_in_either_direction() {
+        let store = store();
+        let first = memory(
+            "Retry policy",
+            "retry failed uploads with exponential backoff",
+        );
+        let second = memory(
+            "Upload retries",
+            "retry failed uploads with exponential backoff",
+        );
+        store.store_memory(&first).await.unwrap();
+        store.store_memory(&second).await.unwrap();
+        let config = MemoryConfig::default();
+
+        let linked: HashSet<(String, String)> = [(second.id.clone(), first.id.clone())]
+            .into_iter()
+            .collect();
+        let planned = plan_auto_links(&store, &config, &first, 0.5, &linked)
+            .await
+            .unwrap();
+        assert!(planned.is_empty());
+
+        let planned = plan_auto_links(&store, &config, &first, 0.5, &HashSet::new())
+            .await
+            .unwrap();
+        assert!(planned.iter().any(|r| r.target_id == second.id));
+    }
 }
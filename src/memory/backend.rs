@@ -0,0 +1,512 @@
+// Copyright 2026 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Pluggable storage for memories and their relationships.
+//!
+//! `MemoryManager` talks to its store only through [`StorageBackend`], so the
+//! LanceDB store can be swapped for another engine via `[memory].backend`.
+//! Every backend is scoped to one project/role at construction, exactly like
+//! `MemoryStore`: reads honour the scope (None = all), writes use it.
+
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use super::ephemeral_store::EphemeralStore;
+use super::reranker_integration::RerankerIntegration;
+use super::store::MemoryStore;
+use super::types::{
+    AccessLogEntry, Memory, MemoryConfig, MemoryQuery, MemoryRelationship, MemoryRevision,
+    MemorySearchResult, MemoryState,
+};
+use crate::embedding::EmbeddingProvider;
+use crate::vector_optimizer::{IndexRebuild, TableCompaction};
+
+/// Storage engine selected by `[memory].backend`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BackendKind {
+    /// LanceDB directory (default)
+    LanceDb,
+    /// Single SQLite file with sqlite-vec for vectors and FTS5 for keywords
+    Sqlite,
+    /// Shared Postgres server with pgvector; also holds knowledge chunks
+    Postgres,
+    /// Process memory only; nothing is written to disk (`--ephemeral`)
+    Ephemeral,
+}
+
+impl BackendKind {
+    pub fn parse(raw: &str) -> Result<Self> {
+        match raw.trim().to_ascii_lowercase().as_str() {
+            "lancedb" | "lance" => Ok(Self::LanceDb),
+            "sqlite" => Ok(Self::Sqlite),
+            "postgres" | "postgresql" => Ok(Self::Postgres),
+            "ephemeral" | "in-memory" => Ok(Self::Ephemeral),
+            other => Err(anyhow::anyhow!(
+                "Unknown memory backend '{}': expected lancedb, sqlite, postgres or ephemeral",
+                other
+            )),
+        }
+    }
+}
+
+/// Memory and relationship storage used by `MemoryManager`.
+#[async_trait]
+pub trait StorageBackend: Send + Sync {
+    /// True when no project key is set (global/unscoped context).
+    fn has_no_project_key(&self) -> bool;
+
+    /// project_key used for writes, "default" when unscoped.
+    fn project_label(&self) -> &str;
+
+    /// Insert or replace a memory, embedding its searchable text.
+    async fn store_memory(&self, memory: &Memory) -> Result<()>;
+
+    /// Insert or replace a memory with a precomputed embedding (columnar
+    /// import). Errors when its length differs from `embedding_dimension`.
+    async fn store_memory_with_embedding(&self, memory: &Memory, embedding: Vec<f32>)
+        -> Result<()>;
+
+    /// Insert or replace several memories (bulk import). Backends that can
+    /// embed and write them together override this; the default stores them
+    /// one by one.
+    async fn store_memories(&self, memories: &[Memory]) -> Result<()> {
+        for memory in memories {
+            self.store_memory(memory).await?;
+        }
+        Ok(())
+    }
+
+    /// Insert or replace several memories with precomputed embeddings (bulk
+    /// import). Backends that can write them together override this; the
+    /// default stores them one by one.
+    async fn store_memories_with_embeddings(&self, rows: &[(Memory, Vec<f32>)]) -> Result<()> {
+        for (memory, embedding) in rows {
+            self.store_memory_with_embedding(memory, embedding.clone())
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// Insert or replace `rows` together (imports), embedding the memories
+    /// given without an embedding. Returns how many were embedded. Backends
+    /// that can embed everything before one write override this, so the
+    /// batch is stored whole or not at all; the default embeds and stores the
+    /// missing ones, then stores the rest.
+    async fn store_memories_batch(&self, rows: &[(Memory, Option<Vec<f32>>)]) -> Result<usize> {
+        let mut to_embed = Vec::new();
+        let mut embedded = Vec::new();
+        for (memory, embedding) in rows {
+            match embedding {
+                Some(embedding) => embedded.push((memory.clone(), embedding.clone())),
+                None => to_embed.push(memory.clone()),
+            }
+        }
+        self.store_memories(&to_embed).await?;
+        self.store_memories_with_embeddings(&embedded).await?;
+        Ok(to_embed.len())
+    }
+
+    /// Length of the vectors produced by the store's embedding model.
+    async fn embedding_dimension(&self) -> Result<usize>;
+
+    /// Stored embeddings of every memory in scope, by memory ID.
+    async fn get_all_embeddings(&self) -> Result<HashMap<String, Vec<f32>>>;
+
+    async fn update_memory(&self, memory: &Memory) -> Result<()> {
+        self.store_memory(memory).await
+    }
+
+    async fn delete_memory(&self, memory_id: &str) -> Result<()> {
+        self.delete_memories(&[memory_id.to_string()]).await?;
+        Ok(())
+    }
+
+    /// Delete several memories (and their relationships) as one operation,
+    /// keeping them in the recovery buffer. Returns the number deleted.
+    async fn delete_memories(&self, memory_ids: &[String]) -> Result<usize>;
+
+    /// Memories in the recovery buffer with their deletion time, newest first.
+    async fn list_recoverable(&self) -> Result<Vec<(Memory, String)>>;
+
+    /// Restore the memories removed by the last `operations` delete calls.
+    async fn restore_last_deletions(&self, operations: usize) -> Result<Vec<Memory>>;
+
+    /// Restore these memories from the recovery buffer (their latest copy, when
+    /// deleted more than once), whichever operation removed them.
+    async fn restore_deleted(&self, memory_ids: &[String]) -> Result<Vec<Memory>>;
+
+    /// Permanently drop recovery-buffer entries deleted before `deleted_before`
+    /// (all of them when `None`), with their attachment blobs. Returns entries purged.
+    async fn purge_deleted(&self, deleted_before: Option<DateTime<Utc>>) -> Result<usize>;
+
+    /// Periodic index/compaction upkeep; a no-op for engines that need none.
+    async fn run_maintenance(&self) -> Result<()> {
+        Ok(())
+    }
+
+    /// Force a vector index rebuild; `None` for engines without a rebuildable index.
+    async fn rebuild_vector_index(&self) -> Result<Option<IndexRebuild>> {
+        Ok(None)
+    }
+
+    /// Compact versioned table files and prune versions older than `keep`;
+    /// empty for engines that manage their own storage.
+    async fn compact(&self, _keep: chrono::Duration) -> Result<Vec<TableCompaction>> {
+        Ok(Vec::new())
+    }
+
+    async fn get_memory(&self, memory_id: &str) -> Result<Option<Memory>>;
+
+    /// IDs in scope starting with `prefix` (short-ID lookup), at most `limit`.
+    async fn memory_ids_with_prefix(&self, prefix: &str, limit: usize) -> Result<Vec<String>> {
+        Ok(self
+            .get_all_memories()
+            .await?
+            .into_iter()
+            .map(|m| m.id)
+            .filter(|id| id.starts_with(prefix))
+            .take(limit)
+            .collect())
+    }
+
+    /// Vector (or hybrid, when enabled) search with filters; records accesses.
+    async fn search_memories(&self, query: &MemoryQuery) -> Result<Vec<MemorySearchResult>>;
+
+    /// Access-log entries recorded at or after `since` in this scope.
+    async fn get_access_log(&self, since: DateTime<Utc>) -> Result<Vec<AccessLogEntry>>;
+
+    /// Change lifecycle state and importance without re-embedding.
+    async fn update_state_and_importance(
+        &self,
+        id: &str,
+        new_state: MemoryState,
+        new_importance: f32,
+    ) -> Result<()>;
+
+    /// Working-state memories created on or after `since`.
+    async fn get_recent_working_memories(&self, since: DateTime<Utc>) -> Result<Vec<Memory>>;
+
+    /// Saved revisions of a memory, oldest first.
+    async fn get_revisions(&self, memory_id: &str) -> Result<Vec<MemoryRevision>>;
+
+    /// Append a revision row (`store_revision` numbers it).
+    async fn append_revision(&self, revision: &MemoryRevision) -> Result<()>;
+
+    /// Save `memory` as its next revision before an edit overwrites it;
+    /// returns the revision number.
+    async fn store_revision(&self, memory: &Memory) -> Result<u32> {
+        let latest = self
+            .get_revisions(&memory.id)
+            .await?
+            .iter()
+            .map(|r| r.revision)
+            .max()
+            .unwrap_or(0);
+        let revision = MemoryRevision {
+            memory_id: memory.id.clone(),
+            revision: latest + 1,
+            replaced_at: Utc::now(),
+            memory: memory.clone(),
+        };
+        self.append_revision(&revision).await?;
+        Ok(revision.revision)
+    }
+
+    async fn store_relationship(&self, relationship: &MemoryRelationship) -> Result<()>;
+
+    /// Insert or replace several relationships (auto-linking, consolidation,
+    /// import). Backends that can write them in one transaction override
+    /// this; the default stores them one by one.
+    async fn store_relationships(&self, relationships: &[MemoryRelationship]) -> Result<()> {
+        for relationship in relationships {
+            self.store_relationship(relationship).await?;
+        }
+        Ok(())
+    }
+
+    /// Relationships where the memory is either source or target.
+    async fn get_memory_relationships(&self, memory_id: &str) -> Result<Vec<MemoryRelationship>>;
+
+    async fn get_all_relationships(&self) -> Result<Vec<MemoryRelationship>>;
+
+    async fn delete_auto_linked_relationships(&self, memory_id: &str) -> Result<()>;
+
+    /// Delete relationships by ID within the project; returns how many were removed.
+    async fn delete_relationships(&self, relationship_ids: &[String]) -> Result<usize>;
+
+    async fn get_memory_count(&self) -> Result<usize>;
+
+    /// Distinct project keys and non-empty roles, sorted.
+    async fn get_distinct_projects_and_roles(&self) -> Result<(Vec<String>, Vec<String>)>;
+
+    /// Every memory in scope, without touching access counters.
+    async fn get_all_memories(&self) -> Result<Vec<Memory>>;
+
+    /// Memories with non-empty related_files (stale reference cleanup).
+    async fn get_memories_with_files(&self) -> Result<Vec<Memory>>;
+
+    /// Apply `auto_cleanup_days`; returns the number of memories removed.
+    async fn cleanup_old_memories(&self) -> Result<usize>;
+
+    /// Delete this project's memories, relationships and access log.
+    async fn clear_all_memory_data(&self) -> Result<usize>;
+
+    fn enable_reranker(&self, model: Option<String>);
+
+    fn disable_reranker(&self);
+}
+
+/// Everything a backend needs to open its store.
+pub struct BackendOptions {
+    /// Database directory (`storage::get_memory_database_path`)
+    pub db_dir: std::path::PathBuf,
+    pub project_key: Option<String>,
+    pub role: Option<String>,
+    pub embedding_provider: Box<dyn EmbeddingProvider>,
+    pub config: MemoryConfig,
+    pub main_config: crate::config::Config,
+    pub reranker_integration: Option<RerankerIntegration>,
+}
+
+/// Open the backend named by `[memory].backend`.
+pub async fn open_backend(options: BackendOptions) -> Result<Arc<dyn StorageBackend>> {
+    match BackendKind::parse(&options.config.backend)? {
+        BackendKind::LanceDb => {
+            let store = MemoryStore::new(
+                options.db_dir.to_string_lossy().as_ref(),
+                options.project_key,
+                options.role,
+                options.embedding_provider,
+                options.config,
+                options.main_config,
+                options.reranker_integration,
+            )
+            .await?;
+            Ok(Arc::new(store))
+        }
+        BackendKind::Sqlite => open_sqlite(options).await,
+        BackendKind::Postgres => open_postgres(options).await,
+        BackendKind::Ephemeral => Ok(Arc::new(EphemeralStore::shared(options))),
+    }
+}
+
+#[cfg(feature = "sqlite")]
+async fn open_sqlite(options: BackendOptions) -> Result<Arc<dyn StorageBackend>> {
+    let path = options.db_dir.join(super::sqlite_store::SQLITE_FILE);
+    Ok(Arc::new(
+        super::sqlite_store::SqliteStore::open(&path, options).await?,
+    ))
+}
+
+#[cfg(not(feature = "sqlite"))]
+async fn open_sqlite(_options: BackendOptions) -> Result<Arc<dyn StorageBackend>> {
+    Err(anyhow::anyhow!(
+        "SQLite backend support is not compiled in. Rebuild with --features sqlite, \
+         or set [memory].backend = \"lancedb\""
+    ))
+}
+
+#[cfg(feature = "postgres")]
+async fn open_postgres(options: BackendOptions) -> Result<Arc<dyn StorageBackend>> {
+    Ok(Arc::new(
+        super::postgres_store::PostgresStore::open(options).await?,
+    ))
+}
+
+#[cfg(not(feature = "postgres"))]
+async fn open_postgres(_options: BackendOptions) -> Result<Arc<dyn StorageBackend>> {
+    Err(anyhow::anyhow!(
+        "Postgres backend support is not compiled in. Rebuild with --features postgres, \
+         or set [memory].backend = \"lancedb\""
+    ))
+}
+
+#[async_trait]
+impl StorageBackend for MemoryStore {
+    fn has_no_project_key(&self) -> bool {
+        MemoryStore::has_no_project_key(self)
+    }
+
+    fn project_label(&self) -> &str {
+        MemoryStore::project_label(self)
+    }
+
+    async fn store_memory(&self, memory: &Memory) -> Result<()> {
+        MemoryStore::store_memory(self, memory).await
+    }
+
+    async fn store_memory_with_embedding(
+        &self,
+        memory: &Memory,
+        embedding: Vec<f32>,
+    ) -> Result<()> {
+        MemoryStore::store_memory_with_embedding(self, memory, embedding).await
+    }
+
+    async fn store_memories(&self, memories: &[Memory]) -> Result<()> {
+        MemoryStore::store_memories(self, memories).await
+    }
+
+    async fn store_memories_with_embeddings(&self, rows: &[(Memory, Vec<f32>)]) -> Result<()> {
+        MemoryStore::store_memories_with_embeddings(self, rows).await
+    }
+
+    async fn store_memories_batch(&self, rows: &[(Memory, Option<Vec<f32>>)]) -> Result<usize> {
+        MemoryStore::store_memories_batch(self, rows).await
+    }
+
+    async fn embedding_dimension(&self) -> Result<usize> {
+        Ok(MemoryStore::embedding_dimension(self))
+    }
+
+    async fn get_all_embeddings(&self) -> Result<HashMap<String, Vec<f32>>> {
+        MemoryStore::get_all_embeddings(self).await
+    }
+
+    async fn update_memory(&self, memory: &Memory) -> Result<()> {
+        MemoryStore::update_memory(self, memory).await
+    }
+
+    async fn delete_memory(&self, memory_id: &str) -> Result<()> {
+        MemoryStore::delete_memory(self, memory_id).await
+    }
+
+    async fn delete_memories(&self, memory_ids: &[String]) -> Result<usize> {
+        MemoryStore::delete_memories(self, memory_ids).await
+    }
+
+    async fn list_recoverable(&self) -> Result<Vec<(Memory, String)>> {
+        MemoryStore::list_recoverable(self).await
+    }
+
+    async fn restore_last_deletions(&self, operations: usize) -> Result<Vec<Memory>> {
+        MemoryStore::restore_last_deletions(self, operations).await
+    }
+
+    async fn restore_deleted(&self, memory_ids: &[String]) -> Result<Vec<Memory>> {
+        MemoryStore::restore_deleted(self, memory_ids).await
+    }
+
+    async fn purge_deleted(&self, deleted_before: Option<DateTime<Utc>>) -> Result<usize> {
+        MemoryStore::purge_deleted(self, deleted_before).await
+    }
+
+    async fn run_maintenance(&self) -> Result<()> {
+        MemoryStore::run_maintenance(self).await
+    }
+
+    async fn rebuild_vector_index(&self) -> Result<Option<IndexRebuild>> {
+        Ok(Some(MemoryStore::rebuild_vector_index(self).await?))
+    }
+
+    async fn compact(&self, keep: chrono::Duration) -> Result<Vec<TableCompaction>> {
+        MemoryStore::compact(self, keep).await
+    }
+
+    async fn get_memory(&self, memory_id: &str) -> Result<Option<Memory>> {
+        MemoryStore::get_memory(self, memory_id).await
+    }
+
+    async fn memory_ids_with_prefix(&self, prefix: &str, limit: usize) -> Result<Vec<String>> {
+        MemoryStore::memory_ids_with_prefix(self, prefix, limit).await
+    }
+
+    async fn search_memories(&self, query: &MemoryQuery) -> Result<Vec<MemorySearchResult>> {
+        MemoryStore::search_memories(self, query).await
+    }
+
+    async fn get_access_log(&self, since: DateTime<Utc>) -> Result<Vec<AccessLogEntry>> {
+        MemoryStore::get_access_log(self, since).await
+    }
+
+    async fn update_state_and_importance(
+        &self,
+        id: &str,
+        new_state: MemoryState,
+        new_importance: f32,
+    ) -> Result<()> {
+        MemoryStore::update_state_and_importance(self, id, new_state, new_importance).await
+    }
+
+    async fn get_recent_working_memories(&self, since: DateTime<Utc>) -> Result<Vec<Memory>> {
+        MemoryStore::get_recent_working_memories(self, since).await
+    }
+
+    async fn get_revisions(&self, memory_id: &str) -> Result<Vec<MemoryRevision>> {
+        MemoryStore::get_revisions(self, memory_id).await
+    }
+
+    async fn append_revision(&self, revision: &MemoryRevision) -> Result<()> {
+        MemoryStore::append_revision(self, revision).await
+    }
+
+    async fn store_relationship(&self, relationship: &MemoryRelationship) -> Result<()> {
+        MemoryStore::store_relationship(self, relationship).await
+    }
+
+    async fn store_relationships(&self, relationships: &[MemoryRelationship]) -> Result<()> {
+        MemoryStore::store_relationships(self, relationships).await
+    }
+
+    async fn get_memory_relationships(&self, memory_id: &str) -> Result<Vec<MemoryRelationship>> {
+        MemoryStore::get_memory_relationships(self, memory_id).await
+    }
+
+    async fn get_all_relationships(&self) -> Result<Vec<MemoryRelationship>> {
+        MemoryStore::get_all_relationships(self).await
+    }
+
+    async fn delete_auto_linked_relationships(&self, memory_id: &str) -> Result<()> {
+        MemoryStore::delete_auto_linked_relationships(self, memory_id).await
+    }
+
+    async fn delete_relationships(&self, relationship_ids: &[String]) -> Result<usize> {
+        MemoryStore::delete_relationships(self, relationship_ids).await
+    }
+
+    async fn get_memory_count(&self) -> Result<usize> {
+        MemoryStore::get_memory_count(self).await
+    }
+
+    async fn get_distinct_projects_and_roles(&self) -> Result<(Vec<String>, Vec<String>)> {
+        MemoryStore::get_distinct_projects_and_roles(self).await
+    }
+
+    async fn get_all_memories(&self) -> Result<Vec<Memory>> {
+        MemoryStore::get_all_memories(self).await
+    }
+
+    async fn get_memories_with_files(&self) -> Result<Vec<Memory>> {
+        MemoryStore::get_memories_with_files(self).await
+    }
+
+    async fn cleanup_old_memories(&self) -> Result<usize> {
+        MemoryStore::cleanup_old_memories(self).await
+    }
+
+    async fn clear_all_memory_data(&self) -> Result<usize> {
+        MemoryStore::clear_all_memory_data(self).await
+    }
+
+    fn enable_reranker(&self, model: Option<String>) {
+        MemoryStore::enable_reranker(self, model)
+    }
+
+    fn disable_reranker(&self) {
+        MemoryStore::disable_reranker(self)
+    }
+}
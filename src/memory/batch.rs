@@ -0,0 +1,146 @@
+// Copyright 2026 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Memories created in bulk from a file (`memory import-batch`).
+//!
+//! JSONL files hold one object per line; YAML files a top-level list of
+//! mappings. Both use the keys `title`, `content`, `type`, `importance`,
+//! `tags` and `files`; `tags` and `files` may also be one comma-separated
+//! string. Every entry is validated before anything is stored.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Deserializer};
+use std::path::Path;
+
+/// One memory to create
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BatchEntry {
+    pub title: String,
+    pub content: String,
+    #[serde(rename = "type", default = "default_type")]
+    pub memory_type: String,
+    #[serde(default)]
+    pub importance: Option<f32>,
+    #[serde(default, deserialize_with = "list_or_csv")]
+    pub tags: Vec<String>,
+    #[serde(default, deserialize_with = "list_or_csv")]
+    pub files: Vec<String>,
+}
+
+fn default_type() -> String {
+    "fact".to_string()
+}
+
+/// A list, or one comma-separated string (`tags: net, http`)
+fn list_or_csv<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> std::result::Result<Vec<String>, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum ListOrCsv {
+        List(Vec<String>),
+        Csv(String),
+    }
+    Ok(match ListOrCsv::deserialize(deserializer)? {
+        ListOrCsv::List(items) => items,
+        ListOrCsv::Csv(text) => text
+            .split(',')
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty())
+            .collect(),
+    })
+}
+
+impl BatchEntry {
+    /// Same limits as `memory memorize`
+    fn validate(&self) -> Result<()> {
+        if self.title.len() < 5 || self.title.len() > 200 {
+            anyhow::bail!("title must be between 5 and 200 characters");
+        }
+        if self.content.len() < 10 || self.content.len() > 10000 {
+            anyhow::bail!("content must be between 10 and 10000 characters");
+        }
+        if let Some(importance) = self.importance {
+            if !(0.0..=1.0).contains(&importance) {
+                anyhow::bail!("importance must be between 0.0 and 1.0");
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Parse and validate a batch file, picking the format from its extension
+/// (`.jsonl`, `.yaml` or `.yml`).
+pub fn read_batch(path: &Path) -> Result<Vec<BatchEntry>> {
+    let text = std::fs::read_to_string(path)
+        .with_context(|| format!("Cannot read batch file '{}'", path.display()))?;
+    let extension = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+    let entries = match extension.as_str() {
+        "jsonl" | "ndjson" => parse_jsonl(&text),
+        "yaml" | "yml" => parse_yaml(&text),
+        _ => anyhow::bail!(
+            "Unsupported batch file '{}': expected .jsonl, .yaml or .yml",
+            path.display()
+        ),
+    }
+    .with_context(|| format!("Invalid batch file '{}'", path.display()))?;
+    if entries.is_empty() {
+        anyhow::bail!("Batch file '{}' holds no memories", path.display());
+    }
+    Ok(entries)
+}
+
+/// One entry per non-blank line
+pub fn parse_jsonl(text: &str) -> Result<Vec<BatchEntry>> {
+    let mut entries = Vec::new();
+    for (number, line) in text.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let entry: BatchEntry =
+            serde_json::from_str(line).with_context(|| format!("line {}", number + 1))?;
+        entry
+            .validate()
+            .with_context(|| format!("line {}", number + 1))?;
+        entries.push(entry);
+    }
+    Ok(entries)
+}
+
+/// Keys of a `--format yaml` memory listing that a new memory has no use
+/// for; they are dropped so a listing can be imported as it is
+const LISTING_KEYS: [&str; 4] = ["id", "created", "score", "why"];
+
+/// A top-level YAML list of mappings
+pub fn parse_yaml(text: &str) -> Result<Vec<BatchEntry>> {
+    let items: Option<Vec<serde_yaml::Mapping>> = serde_yaml::from_str(text)?;
+    let mut entries = Vec::new();
+    for (number, mut item) in items.unwrap_or_default().into_iter().enumerate() {
+        for key in LISTING_KEYS {
+            item.remove(key);
+        }
+        let entry: BatchEntry = serde_yaml::from_value(serde_yaml::Value::Mapping(item))
+            .with_context(|| format!("item {}", number + 1))?;
+        entry
+            .validate()
+            .with_context(|| format!("item {}", number + 1))?;
+        entries.push(entry);
+    }
+    Ok(entries)
+}
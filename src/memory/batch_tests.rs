@@ -0,0 +1,133 @@
+// Copyright 2026 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#[cfg(test)]
+mod tests {
+    use super::super::batch::{parse_jsonl, parse_yaml, read_batch};
+
+    #[test]
+    fn test_parse_jsonl_entries_with_defaults() {
+        let text = r#"{"title": "Use sqlite", "content": "Single file, no server.", "type": "decision", "importance": 0.8, "tags": ["db"], "files": ["src/db.rs"]}
+
+{"title": "Retry policy", "content": "Back off exponentially."}
+"#;
+        let entries = parse_jsonl(text).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].memory_type, "decision");
+        assert_eq!(entries[0].importance, Some(0.8));
+        assert_eq!(entries[0].tags, vec!["db"]);
+        assert_eq!(entries[0].files, vec!["src/db.rs"]);
+        assert_eq!(entries[1].memory_type, "fact");
+        assert_eq!(entries[1].importance, None);
+        assert!(entries[1].tags.is_empty());
+    }
+
+    #[test]
+    fn test_parse_jsonl_reports_line_of_bad_entry() {
+        let text = "{\"title\": \"Use sqlite\", \"content\": \"Single file, no server.\"}\n\
+                    {\"title\": \"Tiny\", \"content\": \"Back off exponentially.\"}\n";
+        let error = format!("{:#}", parse_jsonl(text).unwrap_err());
+        assert!(error.contains("line 2"), "{}", error);
+        assert!(error.contains("title"), "{}", error);
+
+        let unknown =
+            "{\"title\": \"Use sqlite\", \"content\": \"Single file.\", \"author\": \"me\"}";
+        assert!(parse_jsonl(unknown).is_err());
+    }
+
+    #[test]
+    fn test_parse_yaml_list_of_mappings() {
+        let text = r#"---
+# Decisions from the design review
+- title: Use sqlite
+  type: decision
+  importance: 0.8
+  tags: [db, "infra"]
+  files:
+    - src/db.rs
+    - 'src/main.rs'
+  content: |
+    Single file, no server.
+
+    # Revisit
+    When we need concurrent writers.
+
+- title: "Retry: policy"
+  content: >
+    Back off
+    exponentially.
+  tags: net, http
+"#;
+        let entries = parse_yaml(text).unwrap();
+        assert_eq!(entries.len(), 2);
+        let first = &entries[0];
+        assert_eq!(first.title, "Use sqlite");
+        assert_eq!(first.memory_type, "decision");
+        assert_eq!(first.importance, Some(0.8));
+        assert_eq!(first.tags, vec!["db", "infra"]);
+        assert_eq!(first.files, vec!["src/db.rs", "src/main.rs"]);
+        assert_eq!(
+            first.content,
+            "Single file, no server.\n\n# Revisit\nWhen we need concurrent writers.\n"
+        );
+        let second = &entries[1];
+        assert_eq!(second.title, "Retry: policy");
+        assert_eq!(second.memory_type, "fact");
+        assert_eq!(second.content, "Back off exponentially.\n");
+        assert_eq!(second.tags, vec!["net", "http"]);
+    }
+
+    #[test]
+    fn test_parse_yaml_rejects_bad_items() {
+        let not_a_list = "title: Use sqlite\ncontent: Single file, no server.\n";
+        assert!(parse_yaml(not_a_list).is_err());
+
+        let missing = "- title: Use sqlite\n  tags: [db]\n";
+        let error = format!("{:#}", parse_yaml(missing).unwrap_err());
+        assert!(error.contains("missing field `content`"), "{}", error);
+
+        let unknown = "- title: Use sqlite\n  content: Single file, no server.\n  owner: me\n";
+        let error = format!("{:#}", parse_yaml(unknown).unwrap_err());
+        assert!(error.contains("unknown field `owner`"), "{}", error);
+
+        let importance =
+            "- title: Use sqlite\n  content: Single file, no server.\n  importance: 3\n";
+        let error = format!("{:#}", parse_yaml(importance).unwrap_err());
+        assert!(error.contains("item 1"), "{}", error);
+
+        assert!(parse_yaml("# nothing yet\n").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_read_batch_picks_format_from_extension() {
+        let dir = std::env::temp_dir().join(format!("octobrain-batch-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let yaml = dir.join("memories.yml");
+        std::fs::write(
+            &yaml,
+            "- title: Use sqlite\n  content: Single file, no server.\n",
+        )
+        .unwrap();
+        assert_eq!(read_batch(&yaml).unwrap()[0].title, "Use sqlite");
+
+        let empty = dir.join("empty.jsonl");
+        std::fs::write(&empty, "\n").unwrap();
+        assert!(read_batch(&empty).is_err());
+
+        let csv = dir.join("memories.csv");
+        std::fs::write(&csv, "title,content\n").unwrap();
+        assert!(read_batch(&csv).is_err());
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
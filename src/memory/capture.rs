@@ -0,0 +1,317 @@
+// Copyright 2026 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Command-output capture (`memory capture -- <command...>`).
+//!
+//! The command runs with its output echoed to the terminal as usual while
+//! stdout and stderr are recorded. Only the head and tail of each stream are
+//! buffered; when the result is condensed into a memory, colour codes and
+//! progress-bar redraws are stripped and the middle of long output is dropped,
+//! keeping more of the end, where errors usually are.
+
+use anyhow::{Context, Result};
+use std::collections::{HashMap, VecDeque};
+use std::process::Stdio;
+use std::sync::OnceLock;
+use std::time::Instant;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+
+/// Tag added to captured command memories.
+pub const CAPTURE_TAG: &str = "capture";
+
+/// Default character budget for the stored output.
+pub const DEFAULT_MAX_OUTPUT_CHARS: usize = 8000;
+
+/// Bytes buffered from the start and from the end of each stream.
+const BUFFERED_BYTES: usize = 64 * 1024;
+
+/// Share of a stream's budget given to its first lines; the rest goes to the end.
+const HEAD_SHARE: f32 = 0.3;
+
+/// One output stream: its first and last bytes plus totals.
+#[derive(Debug, Default)]
+pub struct StreamCapture {
+    head: Vec<u8>,
+    tail: VecDeque<u8>,
+    total_bytes: usize,
+    total_lines: usize,
+}
+
+impl StreamCapture {
+    pub fn push(&mut self, bytes: &[u8]) {
+        self.total_bytes += bytes.len();
+        self.total_lines += bytes.iter().filter(|&&b| b == b'\n').count();
+        let room = BUFFERED_BYTES.saturating_sub(self.head.len());
+        let (head, rest) = bytes.split_at(room.min(bytes.len()));
+        self.head.extend_from_slice(head);
+        self.tail.extend(rest);
+        let excess = self.tail.len().saturating_sub(BUFFERED_BYTES);
+        self.tail.drain(..excess);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.total_bytes == 0
+    }
+
+    /// Whether bytes between head and tail were never buffered.
+    fn has_gap(&self) -> bool {
+        self.head.len() + self.tail.len() < self.total_bytes
+    }
+
+    /// Cleaned text, keeping the first and last lines within `max_chars` and
+    /// noting how many lines were left out.
+    pub fn condense(&self, max_chars: usize) -> String {
+        let tail: Vec<u8> = self.tail.iter().copied().collect();
+        let (head, tail) = if self.has_gap() {
+            (
+                clean(&String::from_utf8_lossy(&self.head)),
+                clean(&String::from_utf8_lossy(&tail)),
+            )
+        } else {
+            let whole = clean(&String::from_utf8_lossy(
+                &[self.head.as_slice(), &tail].concat(),
+            ));
+            if whole.chars().count() <= max_chars {
+                return whole.trim_end().to_string();
+            }
+            // Both budgets together are smaller than `whole`, so the ends never overlap
+            (whole.clone(), whole)
+        };
+
+        let head_budget = (max_chars as f32 * HEAD_SHARE) as usize;
+        let tail_budget = max_chars.saturating_sub(head_budget);
+        let head_lines = take_lines(head.lines(), head_budget);
+        let mut tail_lines = take_lines(tail.lines().rev(), tail_budget);
+        tail_lines.reverse();
+
+        let total_lines = self.total_lines.max(1);
+        let omitted = total_lines.saturating_sub(head_lines.len() + tail_lines.len());
+        let mut text = head_lines.join("\n");
+        text.push_str(&format!("\n... [{} lines omitted] ...\n", omitted));
+        text.push_str(&tail_lines.join("\n"));
+        text
+    }
+}
+
+/// Lines from `lines` in order until `budget` characters are used.
+fn take_lines<'a>(lines: impl Iterator<Item = &'a str>, budget: usize) -> Vec<&'a str> {
+    let mut used = 0;
+    let mut taken = Vec::new();
+    for line in lines {
+        used += line.chars().count() + 1;
+        if used > budget {
+            break;
+        }
+        taken.push(line);
+    }
+    taken
+}
+
+/// CSI sequences (colours, cursor moves) and OSC sequences (titles, links)
+const ANSI_PATTERN: &str = r"\x1b\[[0-9;?]*[ -/]*[@-~]|\x1b\][^\x07]*\x07";
+
+fn ansi_regex() -> Option<&'static regex::Regex> {
+    static ANSI: OnceLock<Option<regex::Regex>> = OnceLock::new();
+    ANSI.get_or_init(|| regex::Regex::new(ANSI_PATTERN).ok())
+        .as_ref()
+}
+
+/// Strip ANSI escape sequences and keep only the final redraw of
+/// carriage-return progress lines.
+pub fn clean(text: &str) -> String {
+    let stripped = match ansi_regex() {
+        Some(ansi) => ansi.replace_all(text, ""),
+        None => text.into(),
+    };
+    let mut out = String::with_capacity(stripped.len());
+    for line in stripped.split_inclusive('\n') {
+        let (body, newline) = match line.strip_suffix('\n') {
+            Some(body) => (body.strip_suffix('\r').unwrap_or(body), "\n"),
+            None => (line, ""),
+        };
+        out.push_str(body.rsplit('\r').next().unwrap_or(body));
+        out.push_str(newline);
+    }
+    out
+}
+
+/// Result of running a captured command.
+#[derive(Debug)]
+pub struct CapturedRun {
+    pub command_line: String,
+    /// Exit code, None when the process was killed by a signal
+    pub exit_code: Option<i32>,
+    pub duration_ms: u128,
+    pub stdout: StreamCapture,
+    pub stderr: StreamCapture,
+}
+
+impl CapturedRun {
+    pub fn success(&self) -> bool {
+        self.exit_code == Some(0)
+    }
+
+    fn status_text(&self) -> String {
+        match self.exit_code {
+            Some(code) => format!("exit {}", code),
+            None => "killed by signal".to_string(),
+        }
+    }
+
+    /// Default memory title: the command line and its outcome, within 200 chars.
+    pub fn title(&self) -> String {
+        let status = format!(" ({})", self.status_text());
+        let budget = 200 - "$ ".len() - status.chars().count();
+        let command = if self.command_line.chars().count() > budget {
+            let mut cut: String = self.command_line.chars().take(budget - 1).collect();
+            cut.push('…');
+            cut
+        } else {
+            self.command_line.clone()
+        };
+        format!("$ {}{}", command, status)
+    }
+
+    /// Memory content: command, outcome and condensed output of both streams,
+    /// splitting `max_chars` between them by size.
+    pub fn content(&self, max_chars: usize) -> String {
+        let (out_budget, err_budget) = match (self.stdout.is_empty(), self.stderr.is_empty()) {
+            (true, _) => (0, max_chars),
+            (_, true) => (max_chars, 0),
+            _ => {
+                let total = (self.stdout.total_bytes + self.stderr.total_bytes) as f32;
+                let share = (self.stdout.total_bytes as f32 / total).clamp(0.25, 0.75);
+                let out = (max_chars as f32 * share) as usize;
+                (out, max_chars - out)
+            }
+        };
+
+        let mut content = format!(
+            "$ {}\nResult: {} after {:.1}s\n",
+            self.command_line,
+            self.status_text(),
+            self.duration_ms as f64 / 1000.0
+        );
+        for (name, stream, budget) in [
+            ("stdout", &self.stdout, out_budget),
+            ("stderr", &self.stderr, err_budget),
+        ] {
+            if !stream.is_empty() {
+                content.push_str(&format!(
+                    "\n--- {} ---\n{}\n",
+                    name,
+                    stream.condense(budget)
+                ));
+            }
+        }
+        if self.stdout.is_empty() && self.stderr.is_empty() {
+            content.push_str("\n(no output)\n");
+        }
+        content.trim_end().to_string()
+    }
+
+    /// Metadata stored in the memory's custom fields.
+    pub fn custom_fields(&self) -> HashMap<String, String> {
+        let mut fields = HashMap::from([
+            ("command".to_string(), self.command_line.clone()),
+            ("duration_ms".to_string(), self.duration_ms.to_string()),
+        ]);
+        if let Some(code) = self.exit_code {
+            fields.insert("exit_code".to_string(), code.to_string());
+        }
+        if let Ok(cwd) = std::env::current_dir() {
+            fields.insert("cwd".to_string(), cwd.display().to_string());
+        }
+        fields
+    }
+}
+
+/// Quote one argument for display when it contains shell-significant characters.
+fn shell_quote(arg: &str) -> String {
+    let plain = !arg.is_empty()
+        && arg
+            .chars()
+            .all(|c| c.is_alphanumeric() || "-_./=:,@+%".contains(c));
+    if plain {
+        arg.to_string()
+    } else {
+        format!("'{}'", arg.replace('\'', r"'\''"))
+    }
+}
+
+/// Display form of `argv`, copy-pasteable into a POSIX shell.
+pub fn command_line(argv: &[String]) -> String {
+    argv.iter()
+        .map(|a| shell_quote(a))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Copy `reader` to `echo` as it arrives while recording it.
+async fn tee(
+    mut reader: impl AsyncRead + Unpin,
+    mut echo: impl AsyncWrite + Unpin,
+) -> std::io::Result<StreamCapture> {
+    let mut capture = StreamCapture::default();
+    let mut buf = vec![0u8; 8192];
+    loop {
+        let n = reader.read(&mut buf).await?;
+        if n == 0 {
+            break;
+        }
+        // A closed terminal must not abort the capture
+        if echo.write_all(&buf[..n]).await.is_ok() {
+            echo.flush().await.ok();
+        }
+        capture.push(&buf[..n]);
+    }
+    Ok(capture)
+}
+
+/// Run `argv` with inherited stdin, echoing and capturing stdout and stderr.
+pub async fn run_command(argv: &[String]) -> Result<CapturedRun> {
+    let (program, args) = argv
+        .split_first()
+        .ok_or_else(|| anyhow::anyhow!("No command given: use `memory capture -- <command...>`"))?;
+    let started = Instant::now();
+    let mut child = tokio::process::Command::new(program)
+        .args(args)
+        .stdin(Stdio::inherit())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .with_context(|| format!("Failed to start '{}'", program))?;
+
+    let stdout = child
+        .stdout
+        .take()
+        .ok_or_else(|| anyhow::anyhow!("stdout was not captured"))?;
+    let stderr = child
+        .stderr
+        .take()
+        .ok_or_else(|| anyhow::anyhow!("stderr was not captured"))?;
+    let (stdout, stderr, status) = tokio::join!(
+        tee(stdout, tokio::io::stdout()),
+        tee(stderr, tokio::io::stderr()),
+        child.wait()
+    );
+
+    Ok(CapturedRun {
+        command_line: command_line(argv),
+        exit_code: status?.code(),
+        duration_ms: started.elapsed().as_millis(),
+        stdout: stdout?,
+        stderr: stderr?,
+    })
+}
@@ -0,0 +1,106 @@
+// Copyright 2026 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#[cfg(test)]
+mod tests {
+    use super::super::capture::{clean, command_line, CapturedRun, StreamCapture};
+
+    fn stream(text: &str) -> StreamCapture {
+        let mut capture = StreamCapture::default();
+        capture.push(text.as_bytes());
+        capture
+    }
+
+    #[test]
+    fn test_clean_strips_colours_and_progress_redraws() {
+        let raw = "\x1b[1;31merror\x1b[0m: boom\r\n 10%\r 50%\r100% done\n";
+        assert_eq!(clean(raw), "error: boom\n100% done\n");
+    }
+
+    #[test]
+    fn test_short_output_is_kept_whole() {
+        assert_eq!(
+            stream("line one\nline two\n").condense(100),
+            "line one\nline two"
+        );
+    }
+
+    #[test]
+    fn test_long_output_keeps_head_and_more_tail() {
+        let text: String = (0..1000).map(|i| format!("line {}\n", i)).collect();
+        let condensed = stream(&text).condense(1000);
+        assert!(condensed.len() <= 1100);
+        assert!(condensed.starts_with("line 0\n"));
+        assert!(condensed.ends_with("line 999"));
+        assert!(condensed.contains("lines omitted"));
+        let head = condensed.split("...").next().unwrap().lines().count();
+        let tail = condensed.rsplit("...").next().unwrap().lines().count();
+        assert!(tail > head);
+    }
+
+    #[test]
+    fn test_streams_beyond_buffer_still_count_lines() {
+        let mut capture = StreamCapture::default();
+        for i in 0..20_000 {
+            capture.push(format!("chunk {}\n", i).as_bytes());
+        }
+        let condensed = capture.condense(500);
+        assert!(condensed.starts_with("chunk 0\n"));
+        assert!(condensed.ends_with("chunk 19999"));
+        let omitted: usize = condensed
+            .split("... [")
+            .nth(1)
+            .and_then(|rest| rest.split(' ').next())
+            .and_then(|n| n.parse().ok())
+            .unwrap();
+        assert!(omitted > 19_900);
+    }
+
+    #[test]
+    fn test_command_line_quotes_shell_characters() {
+        let argv = ["grep", "-r", "two words", "it's"].map(String::from);
+        assert_eq!(command_line(&argv), r"grep -r 'two words' 'it'\''s'");
+    }
+
+    #[test]
+    fn test_content_title_and_metadata() {
+        let run = CapturedRun {
+            command_line: "cargo build".to_string(),
+            exit_code: Some(101),
+            duration_ms: 2500,
+            stdout: StreamCapture::default(),
+            stderr: stream("error[E0308]: mismatched types\n"),
+        };
+        assert_eq!(run.title(), "$ cargo build (exit 101)");
+        let content = run.content(1000);
+        assert!(content.starts_with("$ cargo build\nResult: exit 101 after 2.5s"));
+        assert!(content.contains("--- stderr ---\nerror[E0308]"));
+        assert!(!content.contains("--- stdout ---"));
+        let fields = run.custom_fields();
+        assert_eq!(fields["command"], "cargo build");
+        assert_eq!(fields["exit_code"], "101");
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_run_command_captures_both_streams() {
+        let argv = ["sh", "-c", "echo out; echo err >&2; exit 3"].map(String::from);
+        let run = super::super::capture::run_command(&argv).await.unwrap();
+        assert_eq!(run.exit_code, Some(3));
+        assert!(!run.success());
+        let content = run.content(1000);
+        assert!(content.contains("--- stdout ---\nout"));
+        assert!(content.contains("--- stderr ---\nerr"));
+    }
+}
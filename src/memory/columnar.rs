@@ -0,0 +1,376 @@
+// Copyright 2026 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Columnar export/import of memories as Arrow IPC files (`memory export
+//! --format arrow`). One row per memory with its embedding as a fixed-size
+//! float list, so the store can be loaded straight into DuckDB, pandas or
+//! polars (`pyarrow.feather.read_table`, `read_ipc`) and converted to Parquet
+//! there. Imports validate the schema and the embedding dimension before
+//! anything is stored.
+
+use anyhow::{Context, Result};
+use arrow::ipc::reader::FileReader;
+use arrow::ipc::writer::FileWriter;
+use arrow_array::builder::{FixedSizeListBuilder, Float32Builder, ListBuilder, StringBuilder};
+use arrow_array::{
+    Array, ArrayRef, FixedSizeListArray, Float32Array, RecordBatch, StringArray,
+    TimestampMillisecondArray, UInt32Array,
+};
+use arrow_schema::{DataType, Field, Schema, TimeUnit};
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use std::io::{Read, Seek, Write};
+use std::sync::Arc;
+
+use super::types::{Memory, MemoryDecay, MemoryMetadata, MemorySource, MemoryState, MemoryType};
+use crate::arrow_helpers::{
+    embedding_dimension, f32_column, list_column, string_column, timestamp_ms_column,
+};
+
+/// Schema version stored in the file metadata; bumped on incompatible changes.
+pub const COLUMNAR_VERSION: &str = "1";
+const VERSION_KEY: &str = "octobrain.columnar_version";
+
+/// Rows per record batch when writing.
+const BATCH_ROWS: usize = 1024;
+
+fn timestamp_type() -> DataType {
+    DataType::Timestamp(TimeUnit::Millisecond, Some("UTC".into()))
+}
+
+fn string_list_type() -> DataType {
+    DataType::List(Arc::new(Field::new("item", DataType::Utf8, true)))
+}
+
+/// Schema of an export whose embeddings have `dimension` floats.
+pub fn columnar_schema(dimension: usize) -> Schema {
+    Schema::new(vec![
+        Field::new("id", DataType::Utf8, false),
+        Field::new("memory_type", DataType::Utf8, false),
+        Field::new("title", DataType::Utf8, false),
+        Field::new("content", DataType::Utf8, false),
+        Field::new("created_at", timestamp_type(), false),
+        Field::new("updated_at", timestamp_type(), false),
+        Field::new("importance", DataType::Float32, false),
+        Field::new("confidence", DataType::Float32, false),
+        Field::new("tags", string_list_type(), false),
+        Field::new("related_files", string_list_type(), false),
+        Field::new("git_commit", DataType::Utf8, true),
+        Field::new("source", DataType::Utf8, false),
+        Field::new("state", DataType::Utf8, false),
+        Field::new("access_count", DataType::UInt32, false),
+        Field::new("last_accessed", timestamp_type(), false),
+        Field::new("decay_rate", DataType::Float32, false),
+        // Attachment descriptors and custom fields as JSON text
+        Field::new("attachments", DataType::Utf8, false),
+        Field::new("custom_fields", DataType::Utf8, false),
+        Field::new("created_by", DataType::Utf8, true),
+        Field::new(
+            "embedding",
+            DataType::FixedSizeList(
+                Arc::new(Field::new("item", DataType::Float32, true)),
+                dimension as i32,
+            ),
+            true,
+        ),
+    ])
+    .with_metadata(HashMap::from([(
+        VERSION_KEY.to_string(),
+        COLUMNAR_VERSION.to_string(),
+    )]))
+}
+
+fn string_list(values: impl Iterator<Item = Vec<String>>) -> ArrayRef {
+    let mut builder = ListBuilder::new(StringBuilder::new());
+    for list in values {
+        for value in list {
+            builder.values().append_value(value);
+        }
+        builder.append(true);
+    }
+    Arc::new(builder.finish())
+}
+
+fn timestamps(values: impl Iterator<Item = DateTime<Utc>>) -> ArrayRef {
+    Arc::new(
+        TimestampMillisecondArray::from(values.map(|t| t.timestamp_millis()).collect::<Vec<_>>())
+            .with_timezone("UTC"),
+    )
+}
+
+fn to_batch(
+    schema: &Arc<Schema>,
+    memories: &[Memory],
+    embeddings: &HashMap<String, Vec<f32>>,
+    dimension: usize,
+) -> Result<RecordBatch> {
+    let text = |f: fn(&Memory) -> String| -> ArrayRef {
+        Arc::new(StringArray::from(
+            memories.iter().map(f).collect::<Vec<_>>(),
+        ))
+    };
+
+    let mut embedding = FixedSizeListBuilder::new(Float32Builder::new(), dimension as i32);
+    for memory in memories {
+        match embeddings.get(&memory.id) {
+            Some(vector) if vector.len() == dimension => {
+                embedding.values().append_slice(vector);
+                embedding.append(true);
+            }
+            // Not stored (or from another model): left null, re-embedded on import
+            _ => {
+                embedding.values().append_nulls(dimension);
+                embedding.append(false);
+            }
+        }
+    }
+
+    let mut attachments = Vec::with_capacity(memories.len());
+    let mut custom_fields = Vec::with_capacity(memories.len());
+    for memory in memories {
+        attachments.push(serde_json::to_string(&memory.metadata.attachments)?);
+        custom_fields.push(serde_json::to_string(&memory.metadata.custom_fields)?);
+    }
+
+    let columns: Vec<ArrayRef> = vec![
+        text(|m| m.id.clone()),
+        text(|m| m.memory_type.to_string()),
+        text(|m| m.title.clone()),
+        text(|m| m.content.clone()),
+        timestamps(memories.iter().map(|m| m.created_at)),
+        timestamps(memories.iter().map(|m| m.updated_at)),
+        Arc::new(Float32Array::from_iter_values(
+            memories.iter().map(|m| m.metadata.importance),
+        )),
+        Arc::new(Float32Array::from_iter_values(
+            memories.iter().map(|m| m.metadata.confidence),
+        )),
+        string_list(memories.iter().map(|m| m.metadata.tags.clone())),
+        string_list(memories.iter().map(|m| m.metadata.related_files.clone())),
+        Arc::new(StringArray::from(
+            memories
+                .iter()
+                .map(|m| m.metadata.git_commit.clone())
+                .collect::<Vec<_>>(),
+        )),
+        text(|m| m.metadata.source.to_string()),
+        text(|m| m.metadata.state.to_string()),
+        Arc::new(UInt32Array::from_iter_values(
+            memories.iter().map(|m| m.metadata.decay.access_count),
+        )),
+        timestamps(memories.iter().map(|m| m.metadata.decay.last_accessed)),
+        Arc::new(Float32Array::from_iter_values(
+            memories.iter().map(|m| m.metadata.decay.decay_rate),
+        )),
+        Arc::new(StringArray::from(attachments)),
+        Arc::new(StringArray::from(custom_fields)),
+        Arc::new(StringArray::from(
+            memories
+                .iter()
+                .map(|m| m.metadata.created_by.clone())
+                .collect::<Vec<_>>(),
+        )),
+        Arc::new(embedding.finish()),
+    ];
+    Ok(RecordBatch::try_new(schema.clone(), columns)?)
+}
+
+/// Write `memories` and their stored `embeddings` as an Arrow IPC file.
+/// Memories without an embedding of `dimension` floats get a null vector.
+pub fn write_arrow<W: Write>(
+    writer: W,
+    memories: &[Memory],
+    embeddings: &HashMap<String, Vec<f32>>,
+    dimension: usize,
+) -> Result<()> {
+    let schema = Arc::new(columnar_schema(dimension));
+    let mut writer = FileWriter::try_new(writer, &schema)?;
+    for chunk in memories.chunks(BATCH_ROWS) {
+        writer.write(&to_batch(&schema, chunk, embeddings, dimension)?)?;
+    }
+    writer.finish()?;
+    Ok(())
+}
+
+/// Memories read back from a columnar export
+#[derive(Debug, Default)]
+pub struct ColumnarImport {
+    /// Each memory with its embedding; `None` rows must be re-embedded
+    pub memories: Vec<(Memory, Option<Vec<f32>>)>,
+    /// Embedding length declared by the file
+    pub dimension: usize,
+}
+
+/// Check that `schema` has every exported column with the exported type.
+pub fn validate_schema(schema: &Schema) -> Result<usize> {
+    if let Some(version) = schema.metadata().get(VERSION_KEY) {
+        if version != COLUMNAR_VERSION {
+            anyhow::bail!(
+                "Unsupported columnar export version {} (expected {})",
+                version,
+                COLUMNAR_VERSION
+            );
+        }
+    }
+    let dimension = embedding_dimension(schema)
+        .filter(|d| *d > 0)
+        .context("Missing or invalid 'embedding' column; expected a fixed-size list of floats")?;
+
+    let problems: Vec<String> = columnar_schema(dimension)
+        .fields()
+        .iter()
+        .filter_map(|expected| match schema.field_with_name(expected.name()) {
+            Err(_) => Some(format!("missing column '{}'", expected.name())),
+            Ok(found) if found.data_type() != expected.data_type() => Some(format!(
+                "column '{}' is {}, expected {}",
+                expected.name(),
+                found.data_type(),
+                expected.data_type()
+            )),
+            Ok(_) => None,
+        })
+        .collect();
+    if !problems.is_empty() {
+        anyhow::bail!("Not an octobrain memory export: {}", problems.join("; "));
+    }
+    Ok(dimension)
+}
+
+fn string_list_at(column: &arrow_array::ListArray, row: usize) -> Result<Vec<String>> {
+    let values = column.value(row);
+    let values = values
+        .as_any()
+        .downcast_ref::<StringArray>()
+        .context("list column has an unexpected Arrow type")?;
+    Ok(values.iter().flatten().map(str::to_string).collect())
+}
+
+fn time_at(column: &TimestampMillisecondArray, row: usize) -> Result<DateTime<Utc>> {
+    DateTime::from_timestamp_millis(column.value(row))
+        .with_context(|| format!("timestamp out of range in row {}", row))
+}
+
+fn optional_text(column: &StringArray, row: usize) -> Option<String> {
+    (!column.is_null(row))
+        .then(|| column.value(row).to_string())
+        .filter(|s| !s.is_empty())
+}
+
+fn from_batch(batch: &RecordBatch, out: &mut Vec<(Memory, Option<Vec<f32>>)>) -> Result<()> {
+    let id = string_column(batch, "id")?;
+    let memory_type = string_column(batch, "memory_type")?;
+    let title = string_column(batch, "title")?;
+    let content = string_column(batch, "content")?;
+    let created_at = timestamp_ms_column(batch, "created_at")?;
+    let updated_at = timestamp_ms_column(batch, "updated_at")?;
+    let importance = f32_column(batch, "importance")?;
+    let confidence = f32_column(batch, "confidence")?;
+    let tags = list_column(batch, "tags")?;
+    let related_files = list_column(batch, "related_files")?;
+    let git_commit = string_column(batch, "git_commit")?;
+    let source = string_column(batch, "source")?;
+    let state = string_column(batch, "state")?;
+    let access_count = batch
+        .column_by_name("access_count")
+        .and_then(|c| c.as_any().downcast_ref::<UInt32Array>())
+        .context("column 'access_count' has an unexpected Arrow type")?;
+    let last_accessed = timestamp_ms_column(batch, "last_accessed")?;
+    let decay_rate = f32_column(batch, "decay_rate")?;
+    let attachments = string_column(batch, "attachments")?;
+    let custom_fields = string_column(batch, "custom_fields")?;
+    let created_by = string_column(batch, "created_by")?;
+    let embedding = batch
+        .column_by_name("embedding")
+        .and_then(|c| c.as_any().downcast_ref::<FixedSizeListArray>())
+        .context("column 'embedding' has an unexpected Arrow type")?;
+
+    for row in 0..batch.num_rows() {
+        if id.is_null(row) || id.value(row).is_empty() {
+            anyhow::bail!("Row {} has no memory ID", row);
+        }
+        let memory_id = id.value(row).to_string();
+        let mut decay = MemoryDecay::new(importance.value(row));
+        decay.access_count = access_count.value(row);
+        decay.last_accessed = time_at(last_accessed, row)?;
+        decay.decay_rate = decay_rate.value(row);
+
+        let memory = Memory {
+            id: memory_id.clone(),
+            memory_type: MemoryType::from(memory_type.value(row).to_string()),
+            title: title.value(row).to_string(),
+            content: content.value(row).to_string(),
+            created_at: time_at(created_at, row)?,
+            updated_at: time_at(updated_at, row)?,
+            metadata: MemoryMetadata {
+                git_commit: optional_text(git_commit, row),
+                related_files: string_list_at(related_files, row)?,
+                tags: string_list_at(tags, row)?,
+                importance: importance.value(row),
+                confidence: confidence.value(row),
+                created_by: optional_text(created_by, row),
+                custom_fields: serde_json::from_str(custom_fields.value(row)).with_context(
+                    || format!("Invalid custom_fields JSON for memory {}", memory_id),
+                )?,
+                decay,
+                source: MemorySource::from(source.value(row).to_string()),
+                state: MemoryState::from(state.value(row).to_string()),
+                attachments: serde_json::from_str(attachments.value(row)).with_context(|| {
+                    format!("Invalid attachments JSON for memory {}", memory_id)
+                })?,
+            },
+            relevance_score: None,
+        };
+
+        let vector = if embedding.is_null(row) {
+            None
+        } else {
+            let values = embedding.value(row);
+            let values = values
+                .as_any()
+                .downcast_ref::<Float32Array>()
+                .context("embedding values are not 32-bit floats")?;
+            if values.null_count() > 0 {
+                anyhow::bail!("Embedding of memory {} contains nulls", memory_id);
+            }
+            Some(values.values().to_vec())
+        };
+        out.push((memory, vector));
+    }
+    Ok(())
+}
+
+/// Read and validate an Arrow IPC file written by [`write_arrow`].
+pub fn read_arrow<R: Read + Seek>(reader: R) -> Result<ColumnarImport> {
+    let reader = FileReader::try_new(reader, None).context("Not an Arrow IPC file")?;
+    let dimension = validate_schema(&reader.schema())?;
+    let mut import = ColumnarImport {
+        memories: Vec::new(),
+        dimension,
+    };
+    for batch in reader {
+        from_batch(&batch?, &mut import.memories)?;
+    }
+    Ok(import)
+}
+
+/// Outcome of `memory import --format arrow`.
+#[derive(Debug, Default)]
+pub struct ColumnarImportReport {
+    /// Rows in the file
+    pub rows: usize,
+    /// Memories written (zero on a dry run)
+    pub stored: usize,
+    /// Stored memories whose embedding was missing and had to be regenerated
+    pub reembedded: usize,
+}
@@ -0,0 +1,125 @@
+// Copyright 2026 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#[cfg(test)]
+mod tests {
+    use super::super::columnar::{columnar_schema, read_arrow, validate_schema, write_arrow};
+    use super::super::types::{Memory, MemoryState, MemoryType};
+    use arrow_schema::{DataType, Field, Schema};
+    use std::collections::HashMap;
+    use std::io::Cursor;
+
+    fn memory(title: &str) -> Memory {
+        let mut m = Memory::new(
+            MemoryType::Decision,
+            title.to_string(),
+            format!("{} content", title),
+            None,
+        );
+        m.metadata.tags = vec!["db".to_string(), "infra".to_string()];
+        m.metadata.related_files = vec!["src/main.rs".to_string()];
+        m.metadata.git_commit = Some("abc123".to_string());
+        m.metadata
+            .custom_fields
+            .insert("k".to_string(), "v".to_string());
+        m.metadata.decay.access_count = 7;
+        m.metadata.state = MemoryState::Consolidated;
+        m
+    }
+
+    #[test]
+    fn test_round_trip_keeps_memories_and_embeddings() {
+        let with_vector = memory("Use sqlite");
+        let without_vector = memory("No vector");
+        let embeddings = HashMap::from([(with_vector.id.clone(), vec![0.1, 0.2, 0.3])]);
+
+        let mut buffer = Cursor::new(Vec::new());
+        write_arrow(
+            &mut buffer,
+            &[with_vector.clone(), without_vector.clone()],
+            &embeddings,
+            3,
+        )
+        .unwrap();
+        buffer.set_position(0);
+        let import = read_arrow(buffer).unwrap();
+
+        assert_eq!(import.dimension, 3);
+        assert_eq!(import.memories.len(), 2);
+        let (read, vector) = &import.memories[0];
+        assert_eq!(read.id, with_vector.id);
+        assert_eq!(read.title, "Use sqlite");
+        assert_eq!(read.memory_type, MemoryType::Decision);
+        assert_eq!(read.metadata.tags, with_vector.metadata.tags);
+        assert_eq!(read.metadata.related_files, vec!["src/main.rs"]);
+        assert_eq!(read.metadata.git_commit.as_deref(), Some("abc123"));
+        assert_eq!(read.metadata.custom_fields.get("k").unwrap(), "v");
+        assert_eq!(read.metadata.decay.access_count, 7);
+        assert_eq!(read.metadata.state, MemoryState::Consolidated);
+        assert_eq!(
+            read.created_at.timestamp_millis(),
+            with_vector.created_at.timestamp_millis()
+        );
+        assert_eq!(vector.as_deref(), Some(&[0.1, 0.2, 0.3][..]));
+
+        // Missing embeddings come back as None, to be re-embedded
+        assert_eq!(import.memories[1].0.id, without_vector.id);
+        assert!(import.memories[1].1.is_none());
+    }
+
+    #[test]
+    fn test_wrong_dimension_vectors_are_exported_as_null() {
+        let m = memory("Other model");
+        let embeddings = HashMap::from([(m.id.clone(), vec![1.0; 5])]);
+        let mut buffer = Cursor::new(Vec::new());
+        write_arrow(&mut buffer, &[m], &embeddings, 3).unwrap();
+        buffer.set_position(0);
+        assert!(read_arrow(buffer).unwrap().memories[0].1.is_none());
+    }
+
+    #[test]
+    fn test_validate_schema_reports_mismatches() {
+        assert_eq!(validate_schema(&columnar_schema(8)).unwrap(), 8);
+
+        let fields: Vec<Field> = columnar_schema(8)
+            .fields()
+            .iter()
+            .filter(|f| f.name() != "title")
+            .map(|f| {
+                if f.name() == "importance" {
+                    Field::new("importance", DataType::Float64, false)
+                } else {
+                    f.as_ref().clone()
+                }
+            })
+            .collect();
+        let error = validate_schema(&Schema::new(fields))
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("missing column 'title'"), "{}", error);
+        assert!(
+            error.contains("column 'importance' is Float64"),
+            "{}",
+            error
+        );
+
+        let no_embedding = Schema::new(vec![Field::new("id", DataType::Utf8, false)]);
+        assert!(validate_schema(&no_embedding).is_err());
+    }
+
+    #[test]
+    fn test_read_rejects_non_arrow_files() {
+        assert!(read_arrow(Cursor::new(b"not arrow".to_vec())).is_err());
+    }
+}
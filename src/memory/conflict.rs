@@ -0,0 +1,190 @@
+// Copyright 2026 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Conflict resolution shared by every merge path (sync, share import).
+//!
+//! When the same memory arrives with different content, the incoming copy is
+//! taken as-is if the local copy was not edited since the last merge (it is a
+//! plain successor). Only when both sides changed is it a conflict, settled by
+//! the configured `ConflictStrategy`.
+
+use anyhow::Result;
+use std::io::{self, BufRead, Write};
+
+use crate::memory::types::Memory;
+
+/// How to settle a real conflict (both copies edited since the last merge).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConflictStrategy {
+    /// Keep whichever copy has the newer `updated_at`
+    LastWriteWins,
+    /// Keep the local copy and store the incoming one under a new ID, linked
+    /// to it with a `Conflicts` relationship
+    KeepBoth,
+    /// Ask on the terminal; falls back to `KeepBoth` where nobody can answer
+    Interactive,
+}
+
+impl ConflictStrategy {
+    pub fn parse(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "last-write-wins" | "lww" => Ok(Self::LastWriteWins),
+            "keep-both" => Ok(Self::KeepBoth),
+            "interactive" => Ok(Self::Interactive),
+            other => Err(anyhow::anyhow!(
+                "Unknown conflict strategy '{}'; expected last-write-wins, keep-both or interactive",
+                other
+            )),
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::LastWriteWins => "last-write-wins",
+            Self::KeepBoth => "keep-both",
+            Self::Interactive => "interactive",
+        }
+    }
+}
+
+/// What to do with a local/incoming pair.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Resolution {
+    KeepLocal,
+    TakeIncoming,
+    KeepBoth,
+}
+
+/// What a merge did to the store.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MergeOutcome {
+    /// No local copy existed
+    Inserted,
+    /// The incoming copy replaced the local one
+    Replaced { conflict: bool },
+    /// The local copy was kept
+    KeptLocal { conflict: bool },
+    /// Both kept; the incoming copy was stored under `copy_id`
+    KeptBoth { copy_id: String },
+}
+
+impl MergeOutcome {
+    pub fn is_conflict(&self) -> bool {
+        match self {
+            Self::Inserted => false,
+            Self::Replaced { conflict } | Self::KeptLocal { conflict } => *conflict,
+            Self::KeptBoth { .. } => true,
+        }
+    }
+
+    /// True when the store changed.
+    pub fn is_applied(&self) -> bool {
+        !matches!(self, Self::KeptLocal { .. })
+    }
+}
+
+/// Chooses a resolution for a real conflict (the `Interactive` strategy).
+/// `Send` so merges holding a prompt can run on the multi-threaded runtime.
+pub trait ConflictPrompt: Send {
+    fn choose(&mut self, local: &Memory, incoming: &Memory) -> Result<Resolution>;
+}
+
+/// True when both copies carry the same user-visible content.
+pub fn same_content(a: &Memory, b: &Memory) -> bool {
+    a.memory_type == b.memory_type
+        && a.title == b.title
+        && a.content == b.content
+        && a.metadata.tags == b.metadata.tags
+        && a.metadata.related_files == b.metadata.related_files
+}
+
+/// True when the pair is a real conflict: content differs and the local copy
+/// was edited since the last merge.
+pub fn is_conflict(local: &Memory, incoming: &Memory, local_changed: bool) -> bool {
+    local_changed && !same_content(local, incoming)
+}
+
+/// Decide between `local` and `incoming`. `prompt` is consulted only for real
+/// conflicts under `Interactive`; without one, both copies are kept.
+pub fn resolve(
+    strategy: ConflictStrategy,
+    local: &Memory,
+    incoming: &Memory,
+    local_changed: bool,
+    prompt: Option<&mut (dyn ConflictPrompt + '_)>,
+) -> Result<Resolution> {
+    let incoming_newer = incoming.updated_at > local.updated_at;
+    if same_content(local, incoming) {
+        // Metadata-only difference (importance, access stats): newest wins
+        return Ok(if incoming_newer {
+            Resolution::TakeIncoming
+        } else {
+            Resolution::KeepLocal
+        });
+    }
+    if !local_changed {
+        return Ok(Resolution::TakeIncoming);
+    }
+    match strategy {
+        ConflictStrategy::LastWriteWins if incoming_newer => Ok(Resolution::TakeIncoming),
+        ConflictStrategy::LastWriteWins => Ok(Resolution::KeepLocal),
+        ConflictStrategy::KeepBoth => Ok(Resolution::KeepBoth),
+        ConflictStrategy::Interactive => match prompt {
+            Some(prompt) => prompt.choose(local, incoming),
+            None => Ok(Resolution::KeepBoth),
+        },
+    }
+}
+
+/// Terminal prompt for `Interactive` resolution.
+pub struct StdinConflictPrompt;
+
+fn preview(text: &str) -> String {
+    let flat = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    if flat.chars().count() > 200 {
+        format!("{}…", flat.chars().take(200).collect::<String>())
+    } else {
+        flat
+    }
+}
+
+impl ConflictPrompt for StdinConflictPrompt {
+    fn choose(&mut self, local: &Memory, incoming: &Memory) -> Result<Resolution> {
+        println!("\n⚠️  Conflict on memory {}", local.id);
+        for (label, memory) in [("local", local), ("incoming", incoming)] {
+            println!(
+                "  [{}] {} — updated {}\n      {}",
+                label,
+                memory.title,
+                memory.updated_at.format("%Y-%m-%d %H:%M:%S"),
+                preview(&memory.content)
+            );
+        }
+        let stdin = io::stdin();
+        loop {
+            print!("Keep [l]ocal, [i]ncoming or [b]oth? ");
+            io::stdout().flush()?;
+            let mut answer = String::new();
+            if stdin.lock().read_line(&mut answer)? == 0 {
+                return Ok(Resolution::KeepBoth);
+            }
+            match answer.trim().to_lowercase().as_str() {
+                "l" | "local" => return Ok(Resolution::KeepLocal),
+                "i" | "incoming" => return Ok(Resolution::TakeIncoming),
+                "b" | "both" => return Ok(Resolution::KeepBoth),
+                _ => continue,
+            }
+        }
+    }
+}
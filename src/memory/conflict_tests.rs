@@ -0,0 +1,179 @@
+// Copyright 2026 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#[cfg(test)]
+mod tests {
+    use super::super::conflict::{
+        is_conflict, resolve, ConflictPrompt, ConflictStrategy, MergeOutcome, Resolution,
+    };
+    use super::super::types::{Memory, MemoryType};
+    use anyhow::Result;
+    use chrono::Duration;
+
+    struct FixedPrompt(Resolution, usize);
+
+    impl ConflictPrompt for FixedPrompt {
+        fn choose(&mut self, _local: &Memory, _incoming: &Memory) -> Result<Resolution> {
+            self.1 += 1;
+            Ok(self.0)
+        }
+    }
+
+    /// Local and incoming copies of one memory with different content; the
+    /// incoming copy is newer unless `incoming_newer` is false.
+    fn pair(incoming_newer: bool) -> (Memory, Memory) {
+        let local = Memory::new(
+            MemoryType::Decision,
+            "Vector store".to_string(),
+            "Use LanceDB".to_string(),
+            None,
+        );
+        let mut incoming = local.clone();
+        incoming.content = "Use pgvector".to_string();
+        let offset = Duration::seconds(if incoming_newer { 60 } else { -60 });
+        incoming.updated_at = local.updated_at + offset;
+        (local, incoming)
+    }
+
+    #[test]
+    fn test_parse_strategy() {
+        assert_eq!(
+            ConflictStrategy::parse("last-write-wins").unwrap(),
+            ConflictStrategy::LastWriteWins
+        );
+        assert_eq!(
+            ConflictStrategy::parse("LWW").unwrap(),
+            ConflictStrategy::LastWriteWins
+        );
+        assert_eq!(
+            ConflictStrategy::parse("keep-both").unwrap(),
+            ConflictStrategy::KeepBoth
+        );
+        assert_eq!(
+            ConflictStrategy::parse("interactive").unwrap(),
+            ConflictStrategy::Interactive
+        );
+        assert!(ConflictStrategy::parse("merge").is_err());
+        for strategy in [
+            ConflictStrategy::LastWriteWins,
+            ConflictStrategy::KeepBoth,
+            ConflictStrategy::Interactive,
+        ] {
+            assert_eq!(
+                ConflictStrategy::parse(strategy.as_str()).unwrap(),
+                strategy
+            );
+        }
+    }
+
+    #[test]
+    fn test_unchanged_local_takes_incoming_under_any_strategy() {
+        let (local, incoming) = pair(false);
+        assert!(!is_conflict(&local, &incoming, false));
+        for strategy in [ConflictStrategy::KeepBoth, ConflictStrategy::Interactive] {
+            let mut prompt = FixedPrompt(Resolution::KeepLocal, 0);
+            assert_eq!(
+                resolve(strategy, &local, &incoming, false, Some(&mut prompt)).unwrap(),
+                Resolution::TakeIncoming
+            );
+            assert_eq!(prompt.1, 0);
+        }
+    }
+
+    #[test]
+    fn test_same_content_keeps_newest_without_conflict() {
+        let (local, mut incoming) = pair(true);
+        incoming.content = local.content.clone();
+        assert!(!is_conflict(&local, &incoming, true));
+        assert_eq!(
+            resolve(ConflictStrategy::KeepBoth, &local, &incoming, true, None).unwrap(),
+            Resolution::TakeIncoming
+        );
+        assert_eq!(
+            resolve(ConflictStrategy::KeepBoth, &incoming, &local, true, None).unwrap(),
+            Resolution::KeepLocal
+        );
+    }
+
+    #[test]
+    fn test_last_write_wins_compares_updated_at() {
+        let (local, incoming) = pair(true);
+        assert!(is_conflict(&local, &incoming, true));
+        assert_eq!(
+            resolve(
+                ConflictStrategy::LastWriteWins,
+                &local,
+                &incoming,
+                true,
+                None
+            )
+            .unwrap(),
+            Resolution::TakeIncoming
+        );
+        let (local, incoming) = pair(false);
+        assert_eq!(
+            resolve(
+                ConflictStrategy::LastWriteWins,
+                &local,
+                &incoming,
+                true,
+                None
+            )
+            .unwrap(),
+            Resolution::KeepLocal
+        );
+    }
+
+    #[test]
+    fn test_keep_both_and_interactive() {
+        let (local, incoming) = pair(true);
+        assert_eq!(
+            resolve(ConflictStrategy::KeepBoth, &local, &incoming, true, None).unwrap(),
+            Resolution::KeepBoth
+        );
+
+        let mut prompt = FixedPrompt(Resolution::KeepLocal, 0);
+        assert_eq!(
+            resolve(
+                ConflictStrategy::Interactive,
+                &local,
+                &incoming,
+                true,
+                Some(&mut prompt)
+            )
+            .unwrap(),
+            Resolution::KeepLocal
+        );
+        assert_eq!(prompt.1, 1);
+
+        // Nobody to ask: keep both rather than losing either edit
+        assert_eq!(
+            resolve(ConflictStrategy::Interactive, &local, &incoming, true, None).unwrap(),
+            Resolution::KeepBoth
+        );
+    }
+
+    #[test]
+    fn test_merge_outcome_flags() {
+        assert!(!MergeOutcome::Inserted.is_conflict());
+        assert!(MergeOutcome::Inserted.is_applied());
+        assert!(!MergeOutcome::KeptLocal { conflict: false }.is_applied());
+        assert!(MergeOutcome::KeptLocal { conflict: true }.is_conflict());
+        assert!(MergeOutcome::Replaced { conflict: false }.is_applied());
+        let both = MergeOutcome::KeptBoth {
+            copy_id: "copy".to_string(),
+        };
+        assert!(both.is_conflict() && both.is_applied());
+    }
+}
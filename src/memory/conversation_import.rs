@@ -0,0 +1,328 @@
+// Copyright 2026 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Import facts and decisions from ChatGPT / Claude conversation exports.
+//!
+//! Both exports are a `conversations.json` array. ChatGPT stores messages as a
+//! node `mapping` (tree of edits/regenerations); Claude stores a flat
+//! `chat_messages` list. Only what the *user* said is mined: statements of
+//! preference, decisions and explicit "remember this" facts.
+
+use anyhow::{Context, Result};
+use serde_json::Value;
+
+use crate::config::AnswerConfig;
+use crate::memory::llm::complete;
+use crate::memory::types::MemoryType;
+
+/// Tag added to every imported memory (plus the export format name).
+pub const IMPORTED_TAG: &str = "imported";
+
+/// Sentences shorter/longer than this are ignored by the heuristic extractor.
+const MIN_FACT_CHARS: usize = 15;
+const MAX_FACT_CHARS: usize = 500;
+
+/// Cap on user text sent to the LLM per conversation.
+const MAX_PROMPT_CHARS: usize = 24_000;
+
+const DECISION_MARKERS: &[&str] = &[
+    "we decided",
+    "i decided",
+    "we agreed",
+    "let's go with",
+    "we'll go with",
+    "we will use",
+    "we're going with",
+    "decision:",
+];
+
+const PREFERENCE_MARKERS: &[&str] = &[
+    "i prefer",
+    "i always",
+    "i never",
+    "i like to",
+    "i don't like",
+    "from now on",
+    "please always",
+    "please never",
+    "don't ever",
+];
+
+const FACT_MARKERS: &[&str] = &[
+    "remember that",
+    "please remember",
+    "keep in mind",
+    "note that",
+    "my name is",
+    "i work at",
+    "i work on",
+    "our stack",
+    "we use",
+    "our team",
+];
+
+const EXTRACT_PROMPT: &str = "Below are messages a user wrote in a conversation with an AI \
+assistant. List durable facts about the user or their projects, their preferences, and \
+decisions they made or confirmed. Ignore questions, small talk and anything only relevant \
+to that one conversation. Output one item per line starting with `DECISION:`, \
+`PREFERENCE:` or `FACT:` followed by a self-contained sentence. Output nothing else. If \
+there are none, output `NONE`.";
+
+/// Supported export formats.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConversationFormat {
+    ChatGpt,
+    Claude,
+}
+
+impl ConversationFormat {
+    pub fn parse(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "chatgpt" | "openai" => Ok(Self::ChatGpt),
+            "claude" | "anthropic" => Ok(Self::Claude),
+            other => Err(anyhow::anyhow!(
+                "Unknown import format '{}'; expected chatgpt or claude",
+                other
+            )),
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::ChatGpt => "chatgpt",
+            Self::Claude => "claude",
+        }
+    }
+}
+
+/// A conversation reduced to what the user wrote.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportedConversation {
+    pub id: String,
+    pub title: String,
+    pub created_at: Option<String>,
+    pub user_messages: Vec<String>,
+}
+
+/// Outcome of a conversation import.
+#[derive(Debug, Default)]
+pub struct ConversationImportReport {
+    /// Conversations with at least one user message
+    pub conversations: usize,
+    /// (conversation title, fact) pairs found; stored unless it was a dry run
+    pub facts: Vec<(String, ImportedFact)>,
+    /// Number of memories written
+    pub stored: usize,
+    /// Facts that failed to store, with the reason (e.g. secret detection)
+    pub skipped: Vec<String>,
+}
+
+/// A fact or decision extracted from a conversation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportedFact {
+    pub memory_type: MemoryType,
+    pub text: String,
+}
+
+fn str_field(value: &Value, key: &str) -> Option<String> {
+    value.get(key).and_then(|v| v.as_str()).map(str::to_string)
+}
+
+/// ChatGPT `create_time` is a float unix timestamp.
+fn unix_to_rfc3339(value: Option<&Value>) -> Option<String> {
+    let secs = value?.as_f64()?;
+    chrono::DateTime::from_timestamp(secs as i64, 0).map(|d| d.to_rfc3339())
+}
+
+fn parse_chatgpt_conversation(conv: &Value) -> Option<ImportedConversation> {
+    let mapping = conv.get("mapping")?.as_object()?;
+    let mut messages: Vec<(f64, String)> = mapping
+        .values()
+        .filter_map(|node| {
+            let message = node.get("message")?;
+            let role = message.get("author")?.get("role")?.as_str()?;
+            if role != "user" {
+                return None;
+            }
+            let text = message
+                .get("content")?
+                .get("parts")?
+                .as_array()?
+                .iter()
+                .filter_map(|p| p.as_str())
+                .collect::<Vec<_>>()
+                .join("\n");
+            let order = message
+                .get("create_time")
+                .and_then(|t| t.as_f64())
+                .unwrap_or(0.0);
+            (!text.trim().is_empty()).then_some((order, text))
+        })
+        .collect();
+    messages.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+    Some(ImportedConversation {
+        id: str_field(conv, "conversation_id")
+            .or_else(|| str_field(conv, "id"))
+            .unwrap_or_default(),
+        title: str_field(conv, "title").unwrap_or_default(),
+        created_at: unix_to_rfc3339(conv.get("create_time")),
+        user_messages: messages.into_iter().map(|(_, text)| text).collect(),
+    })
+}
+
+fn parse_claude_conversation(conv: &Value) -> Option<ImportedConversation> {
+    let messages = conv.get("chat_messages")?.as_array()?;
+    let user_messages = messages
+        .iter()
+        .filter(|m| m.get("sender").and_then(|s| s.as_str()) == Some("human"))
+        .filter_map(|m| {
+            let text = str_field(m, "text")
+                .filter(|t| !t.trim().is_empty())
+                .or_else(|| {
+                    // Newer exports carry the text only inside typed content blocks
+                    let blocks = m.get("content")?.as_array()?;
+                    let joined = blocks
+                        .iter()
+                        .filter(|b| b.get("type").and_then(|t| t.as_str()) == Some("text"))
+                        .filter_map(|b| b.get("text").and_then(|t| t.as_str()))
+                        .collect::<Vec<_>>()
+                        .join("\n");
+                    (!joined.trim().is_empty()).then_some(joined)
+                })?;
+            Some(text)
+        })
+        .collect();
+
+    Some(ImportedConversation {
+        id: str_field(conv, "uuid").unwrap_or_default(),
+        title: str_field(conv, "name").unwrap_or_default(),
+        created_at: str_field(conv, "created_at"),
+        user_messages,
+    })
+}
+
+/// Parse an export file's JSON into conversations. Accepts the top-level array
+/// or a single conversation object.
+pub fn parse_export(json: &str, format: ConversationFormat) -> Result<Vec<ImportedConversation>> {
+    let root: Value = serde_json::from_str(json).context("Export is not valid JSON")?;
+    let items = match root {
+        Value::Array(items) => items,
+        single @ Value::Object(_) => vec![single],
+        _ => {
+            return Err(anyhow::anyhow!(
+                "Export must be a JSON array of conversations"
+            ))
+        }
+    };
+    Ok(items
+        .iter()
+        .filter_map(|conv| match format {
+            ConversationFormat::ChatGpt => parse_chatgpt_conversation(conv),
+            ConversationFormat::Claude => parse_claude_conversation(conv),
+        })
+        .filter(|conv| !conv.user_messages.is_empty())
+        .collect())
+}
+
+/// Split a message into rough sentences (on `.`, `!`, `?` and newlines).
+fn sentences(text: &str) -> Vec<String> {
+    let mut out = Vec::new();
+    let mut current = String::new();
+    for c in text.chars() {
+        if c == '\n' {
+            out.push(std::mem::take(&mut current));
+            continue;
+        }
+        current.push(c);
+        if matches!(c, '.' | '!' | '?') {
+            out.push(std::mem::take(&mut current));
+        }
+    }
+    out.push(current);
+    out.into_iter()
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .collect()
+}
+
+/// Keyword-based extraction of decisions, preferences and stated facts from the
+/// user's messages. Questions are skipped; duplicates are dropped.
+pub fn extract_facts_heuristic(conversation: &ImportedConversation) -> Vec<ImportedFact> {
+    let mut facts: Vec<ImportedFact> = Vec::new();
+    for message in &conversation.user_messages {
+        for sentence in sentences(message) {
+            let len = sentence.chars().count();
+            if !(MIN_FACT_CHARS..=MAX_FACT_CHARS).contains(&len) || sentence.ends_with('?') {
+                continue;
+            }
+            let lower = sentence.to_lowercase();
+            let memory_type = if DECISION_MARKERS.iter().any(|m| lower.contains(m)) {
+                MemoryType::Decision
+            } else if PREFERENCE_MARKERS.iter().any(|m| lower.contains(m)) {
+                MemoryType::UserPreference
+            } else if FACT_MARKERS.iter().any(|m| lower.contains(m)) {
+                MemoryType::Insight
+            } else {
+                continue;
+            };
+            if !facts.iter().any(|f| f.text == sentence) {
+                facts.push(ImportedFact {
+                    memory_type,
+                    text: sentence,
+                });
+            }
+        }
+    }
+    facts
+}
+
+/// Parse `DECISION:` / `PREFERENCE:` / `FACT:` lines from an LLM reply.
+pub fn parse_fact_lines(reply: &str) -> Vec<ImportedFact> {
+    reply
+        .lines()
+        .filter_map(|line| {
+            let line = line.trim().trim_start_matches(['-', '*', ' ']);
+            let (memory_type, text) = if let Some(text) = line.strip_prefix("DECISION:") {
+                (MemoryType::Decision, text)
+            } else if let Some(text) = line.strip_prefix("PREFERENCE:") {
+                (MemoryType::UserPreference, text)
+            } else if let Some(text) = line.strip_prefix("FACT:") {
+                (MemoryType::Insight, text)
+            } else {
+                return None;
+            };
+            let text = text.trim();
+            (!text.is_empty()).then(|| ImportedFact {
+                memory_type,
+                text: text.to_string(),
+            })
+        })
+        .collect()
+}
+
+/// Ask the `[search.answer]` model to extract facts from one conversation.
+pub async fn extract_facts_llm(
+    config: &AnswerConfig,
+    conversation: &ImportedConversation,
+) -> Result<Vec<ImportedFact>> {
+    let mut prompt = format!("Conversation: {}\n", conversation.title);
+    for message in &conversation.user_messages {
+        prompt.push_str("\nUSER: ");
+        prompt.push_str(message);
+    }
+    let prompt: String = prompt.chars().take(MAX_PROMPT_CHARS).collect();
+    let reply = complete(config, EXTRACT_PROMPT, &prompt, "Conversation import").await?;
+    Ok(parse_fact_lines(&reply))
+}
@@ -0,0 +1,101 @@
+// Copyright 2026 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#[cfg(test)]
+mod tests {
+    use super::super::conversation_import::{
+        extract_facts_heuristic, parse_export, parse_fact_lines, ConversationFormat,
+    };
+    use super::super::types::MemoryType;
+
+    const CHATGPT: &str = r#"[{
+        "title": "Backend setup",
+        "conversation_id": "c-1",
+        "create_time": 1700000000.5,
+        "mapping": {
+            "b": {"message": {"author": {"role": "user"}, "create_time": 2.0,
+                  "content": {"content_type": "text", "parts": ["We decided to use Postgres for billing. Why is it slow?"]}}},
+            "a": {"message": {"author": {"role": "user"}, "create_time": 1.0,
+                  "content": {"content_type": "text", "parts": ["I prefer tabs over spaces in Go files."]}}},
+            "c": {"message": {"author": {"role": "assistant"}, "create_time": 3.0,
+                  "content": {"content_type": "text", "parts": ["We decided nothing, I am the assistant."]}}},
+            "root": {"message": null}
+        }
+    }]"#;
+
+    const CLAUDE: &str = r#"[{
+        "uuid": "u-9",
+        "name": "Infra chat",
+        "created_at": "2025-01-02T10:00:00Z",
+        "chat_messages": [
+            {"sender": "human", "text": "Remember that staging runs on k3s."},
+            {"sender": "assistant", "text": "Noted."},
+            {"sender": "human", "text": "", "content": [{"type": "text", "text": "Please never force-push to main."}]}
+        ]
+    }]"#;
+
+    #[test]
+    fn test_parse_chatgpt_user_messages_in_order() {
+        let convs = parse_export(CHATGPT, ConversationFormat::ChatGpt).unwrap();
+        assert_eq!(convs.len(), 1);
+        assert_eq!(convs[0].id, "c-1");
+        assert_eq!(convs[0].user_messages.len(), 2);
+        assert!(convs[0].user_messages[0].starts_with("I prefer tabs"));
+        assert!(convs[0]
+            .created_at
+            .as_deref()
+            .unwrap()
+            .starts_with("2023-11-14"));
+    }
+
+    #[test]
+    fn test_parse_claude_including_content_blocks() {
+        let convs = parse_export(CLAUDE, ConversationFormat::Claude).unwrap();
+        assert_eq!(convs[0].title, "Infra chat");
+        assert_eq!(
+            convs[0].user_messages,
+            vec![
+                "Remember that staging runs on k3s.",
+                "Please never force-push to main."
+            ]
+        );
+    }
+
+    #[test]
+    fn test_heuristic_classifies_and_skips_questions() {
+        let convs = parse_export(CHATGPT, ConversationFormat::ChatGpt).unwrap();
+        let facts = extract_facts_heuristic(&convs[0]);
+        assert_eq!(facts.len(), 2);
+        assert_eq!(facts[0].memory_type, MemoryType::UserPreference);
+        assert_eq!(facts[1].memory_type, MemoryType::Decision);
+        assert_eq!(facts[1].text, "We decided to use Postgres for billing.");
+
+        let convs = parse_export(CLAUDE, ConversationFormat::Claude).unwrap();
+        let facts = extract_facts_heuristic(&convs[0]);
+        assert_eq!(facts[0].memory_type, MemoryType::Insight);
+        assert_eq!(facts[1].memory_type, MemoryType::UserPreference);
+    }
+
+    #[test]
+    fn test_parse_fact_lines_and_format() {
+        let facts = parse_fact_lines("FACT: Uses k3s.\n- DECISION: Adopt Postgres.\nNONE");
+        assert_eq!(facts.len(), 2);
+        assert_eq!(facts[1].memory_type, MemoryType::Decision);
+        assert!(ConversationFormat::parse("gemini").is_err());
+        assert_eq!(
+            ConversationFormat::parse("Claude").unwrap(),
+            ConversationFormat::Claude
+        );
+    }
+}
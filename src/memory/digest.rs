@@ -0,0 +1,242 @@
+// Copyright 2026 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Periodic digest (`memory digest`): memories created or updated in a time
+//! window and the notable relationships added in it, as Markdown ready for a
+//! weekly review or a team channel. Optionally prefixed with an LLM summary.
+
+use anyhow::Result;
+use chrono::{DateTime, Duration, Utc};
+use serde::Serialize;
+use std::collections::HashMap;
+
+use crate::config::AnswerConfig;
+use crate::memory::llm::complete;
+use crate::memory::types::{Memory, MemoryRelationship, RelationshipType};
+
+/// Auto-links at or above this strength are listed; weaker ones are noise.
+pub const NOTABLE_AUTO_LINK_STRENGTH: f32 = 0.8;
+
+const DIGEST_PROMPT: &str = "Below is a digest of a team's knowledge base: memories \
+added or updated in a period and new links between them. Write a short summary for a \
+weekly review in 3-6 bullet points: the main themes, key decisions, and anything that \
+supersedes or conflicts with earlier knowledge. Use plain Markdown bullets and nothing else.";
+
+/// Parse a window like `7d`, `12h` or `2w`; a bare number means days.
+pub fn parse_window(window: &str) -> Result<Duration> {
+    let window = window.trim();
+    let (digits, unit) = match window.char_indices().last() {
+        Some((i, c)) if c.is_ascii_alphabetic() => (&window[..i], c.to_ascii_lowercase()),
+        _ => (window, 'd'),
+    };
+    let amount: i64 = digits
+        .parse()
+        .map_err(|_| anyhow::anyhow!("Invalid window '{}'; expected e.g. 12h, 7d or 2w", window))?;
+    if amount <= 0 {
+        anyhow::bail!("Window must be positive, got '{}'", window);
+    }
+    match unit {
+        'h' => Ok(Duration::hours(amount)),
+        'd' => Ok(Duration::days(amount)),
+        'w' => Ok(Duration::weeks(amount)),
+        _ => anyhow::bail!("Unknown unit '{}' in '{}'; use h, d or w", unit, window),
+    }
+}
+
+/// A memory listed in the digest
+#[derive(Debug, Clone, Serialize)]
+pub struct DigestMemory {
+    pub id: String,
+    pub memory_type: String,
+    pub title: String,
+    pub importance: f32,
+    pub at: DateTime<Utc>,
+}
+
+/// A relationship listed in the digest
+#[derive(Debug, Clone, Serialize)]
+pub struct DigestLink {
+    pub source_id: String,
+    pub source_title: String,
+    pub relationship_type: String,
+    pub target_id: String,
+    pub target_title: String,
+    pub strength: f32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Digest {
+    pub since: DateTime<Utc>,
+    pub until: DateTime<Utc>,
+    /// Created in the window, grouped by type, most important first
+    pub created: Vec<DigestMemory>,
+    /// Created earlier but edited in the window, most recent first
+    pub updated: Vec<DigestMemory>,
+    /// Explicit links and strong auto-links added in the window
+    pub links: Vec<DigestLink>,
+    /// LLM summary of the above (`--llm`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
+}
+
+fn digest_memory(memory: &Memory, at: DateTime<Utc>) -> DigestMemory {
+    DigestMemory {
+        id: memory.id.clone(),
+        memory_type: memory.memory_type.to_string(),
+        title: memory.title.clone(),
+        importance: memory.metadata.importance,
+        at,
+    }
+}
+
+/// Explicit relationships always matter; auto-links only when strong.
+fn is_notable(relationship: &MemoryRelationship) -> bool {
+    !matches!(relationship.relationship_type, RelationshipType::AutoLinked)
+        || relationship.strength >= NOTABLE_AUTO_LINK_STRENGTH
+}
+
+/// Collect what changed between `since` and `until`.
+pub fn build_digest(
+    memories: &[Memory],
+    relationships: &[MemoryRelationship],
+    since: DateTime<Utc>,
+    until: DateTime<Utc>,
+) -> Digest {
+    let in_window = |at: DateTime<Utc>| at > since && at <= until;
+
+    let mut created: Vec<DigestMemory> = memories
+        .iter()
+        .filter(|m| in_window(m.created_at))
+        .map(|m| digest_memory(m, m.created_at))
+        .collect();
+    created.sort_by(|a, b| {
+        a.memory_type
+            .cmp(&b.memory_type)
+            .then(b.importance.total_cmp(&a.importance))
+    });
+
+    let mut updated: Vec<DigestMemory> = memories
+        .iter()
+        .filter(|m| m.created_at <= since && in_window(m.updated_at))
+        .map(|m| digest_memory(m, m.updated_at))
+        .collect();
+    updated.sort_by(|a, b| b.at.cmp(&a.at));
+
+    let titles: HashMap<&str, &str> = memories
+        .iter()
+        .map(|m| (m.id.as_str(), m.title.as_str()))
+        .collect();
+    let title_of = |id: &str| titles.get(id).copied().unwrap_or(id).to_string();
+    let mut added: Vec<&MemoryRelationship> = relationships
+        .iter()
+        .filter(|r| in_window(r.created_at) && is_notable(r))
+        .collect();
+    added.sort_by(|a, b| b.strength.total_cmp(&a.strength));
+    let links = added
+        .into_iter()
+        .map(|r| DigestLink {
+            source_id: r.source_id.clone(),
+            source_title: title_of(&r.source_id),
+            relationship_type: r.relationship_type.to_string(),
+            target_id: r.target_id.clone(),
+            target_title: title_of(&r.target_id),
+            strength: r.strength,
+        })
+        .collect();
+
+    Digest {
+        since,
+        until,
+        created,
+        updated,
+        links,
+        summary: None,
+    }
+}
+
+impl Digest {
+    pub fn is_empty(&self) -> bool {
+        self.created.is_empty() && self.updated.is_empty() && self.links.is_empty()
+    }
+
+    /// Render as Markdown
+    pub fn format_markdown(&self) -> String {
+        let mut output = format!(
+            "# Memory digest {} – {}\n\n",
+            self.since.format("%Y-%m-%d"),
+            self.until.format("%Y-%m-%d")
+        );
+        output.push_str(&format!(
+            "{} new, {} updated, {} new links\n",
+            self.created.len(),
+            self.updated.len(),
+            self.links.len()
+        ));
+        if let Some(summary) = &self.summary {
+            output.push_str(&format!("\n## Summary\n\n{}\n", summary));
+        }
+        if self.is_empty() {
+            output.push_str("\nNothing changed in this period.\n");
+            return output;
+        }
+
+        if !self.created.is_empty() {
+            output.push_str("\n## New memories\n");
+            let mut current_type = "";
+            for m in &self.created {
+                if m.memory_type != current_type {
+                    current_type = &m.memory_type;
+                    output.push_str(&format!("\n### {}\n\n", current_type));
+                }
+                output.push_str(&format!(
+                    "- {} (`{}`, importance {:.2})\n",
+                    m.title, m.id, m.importance
+                ));
+            }
+        }
+        if !self.updated.is_empty() {
+            output.push_str("\n## Updated memories\n\n");
+            for m in &self.updated {
+                output.push_str(&format!(
+                    "- [{}] {} (`{}`, {})\n",
+                    m.memory_type,
+                    m.title,
+                    m.id,
+                    m.at.format("%Y-%m-%d")
+                ));
+            }
+        }
+        if !self.links.is_empty() {
+            output.push_str("\n## New links\n\n");
+            for l in &self.links {
+                output.push_str(&format!(
+                    "- {} —{}→ {} ({:.2})\n",
+                    l.source_title, l.relationship_type, l.target_title, l.strength
+                ));
+            }
+        }
+        output
+    }
+}
+
+/// Ask the configured LLM for a short summary of the digest.
+pub async fn summarize_digest(config: &AnswerConfig, digest: &Digest) -> Result<String> {
+    complete(
+        config,
+        DIGEST_PROMPT,
+        &digest.format_markdown(),
+        "Digest summarization",
+    )
+    .await
+}
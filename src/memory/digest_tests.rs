@@ -0,0 +1,108 @@
+// Copyright 2026 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#[cfg(test)]
+mod tests {
+    use super::super::digest::{build_digest, parse_window};
+    use super::super::types::{Memory, MemoryRelationship, MemoryType, RelationshipType};
+    use chrono::{Duration, Utc};
+
+    fn memory(memory_type: MemoryType, title: &str, age_days: i64) -> Memory {
+        let mut m = Memory::new(memory_type, title.to_string(), String::new(), None);
+        m.created_at = Utc::now() - Duration::days(age_days);
+        m.updated_at = m.created_at;
+        m
+    }
+
+    fn link(
+        source: &Memory,
+        target: &Memory,
+        relationship_type: RelationshipType,
+        strength: f32,
+        age_days: i64,
+    ) -> MemoryRelationship {
+        MemoryRelationship {
+            id: uuid::Uuid::new_v4().to_string(),
+            source_id: source.id.clone(),
+            target_id: target.id.clone(),
+            relationship_type,
+            strength,
+            description: String::new(),
+            created_at: Utc::now() - Duration::days(age_days),
+        }
+    }
+
+    #[test]
+    fn test_parse_window() {
+        assert_eq!(parse_window("7d").unwrap(), Duration::days(7));
+        assert_eq!(parse_window("12h").unwrap(), Duration::hours(12));
+        assert_eq!(parse_window("2W").unwrap(), Duration::weeks(2));
+        assert_eq!(parse_window("3").unwrap(), Duration::days(3));
+        assert!(parse_window("0d").is_err());
+        assert!(parse_window("7m").is_err());
+        assert!(parse_window("week").is_err());
+        assert!(parse_window("").is_err());
+    }
+
+    #[test]
+    fn test_digest_splits_created_updated_and_links() {
+        let old = memory(MemoryType::Architecture, "Old design", 30);
+        let mut edited = memory(MemoryType::Learning, "Edited note", 30);
+        edited.updated_at = Utc::now() - Duration::days(1);
+        let decision = memory(MemoryType::Decision, "Use sqlite", 2);
+        let minor = {
+            let mut m = memory(MemoryType::Decision, "Minor call", 3);
+            m.metadata.importance = 0.1;
+            m
+        };
+        let memories = vec![old.clone(), edited.clone(), decision.clone(), minor.clone()];
+        let relationships = vec![
+            link(&decision, &old, RelationshipType::Supersedes, 0.6, 1),
+            link(&decision, &minor, RelationshipType::AutoLinked, 0.5, 1),
+            link(&minor, &old, RelationshipType::AutoLinked, 0.9, 1),
+            link(&edited, &old, RelationshipType::RelatedTo, 0.9, 20),
+        ];
+
+        let now = Utc::now();
+        let digest = build_digest(&memories, &relationships, now - Duration::days(7), now);
+
+        let created: Vec<&str> = digest.created.iter().map(|m| m.title.as_str()).collect();
+        assert_eq!(created, vec!["Use sqlite", "Minor call"]);
+        assert_eq!(digest.updated.len(), 1);
+        assert_eq!(digest.updated[0].id, edited.id);
+
+        // Weak auto-links and links outside the window are left out
+        assert_eq!(digest.links.len(), 2);
+        assert_eq!(digest.links[0].relationship_type, "auto_linked");
+        assert_eq!(digest.links[1].relationship_type, "supersedes");
+        assert_eq!(digest.links[1].source_title, "Use sqlite");
+        assert_eq!(digest.links[1].target_title, "Old design");
+
+        let markdown = digest.format_markdown();
+        assert!(markdown.contains("2 new, 1 updated, 2 new links"));
+        assert!(markdown.contains("### decision"));
+        assert!(markdown.contains("Use sqlite —supersedes→ Old design"));
+    }
+
+    #[test]
+    fn test_empty_digest() {
+        let now = Utc::now();
+        let old = memory(MemoryType::Code, "Ancient", 100);
+        let digest = build_digest(&[old], &[], now - Duration::days(7), now);
+        assert!(digest.is_empty());
+        assert!(digest
+            .format_markdown()
+            .contains("Nothing changed in this period."));
+    }
+}
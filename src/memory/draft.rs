@@ -0,0 +1,164 @@
+// Copyright 2026 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Memories written in an editor (`memory memorize --edit`).
+//!
+//! The draft is a Markdown file with YAML-style front matter holding the
+//! title, type, tags, importance and related files, pre-filled from the
+//! command line; the body below it is the content. Saving an empty draft
+//! aborts.
+
+use anyhow::{Context, Result};
+use std::path::Path;
+
+/// Fields of a memory being drafted
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Draft {
+    pub title: String,
+    pub memory_type: String,
+    pub importance: Option<f32>,
+    pub tags: Vec<String>,
+    pub files: Vec<String>,
+    pub content: String,
+}
+
+impl Draft {
+    /// The draft as the file handed to the editor
+    pub fn render(&self) -> String {
+        let importance = self.importance.map(|i| i.to_string()).unwrap_or_default();
+        format!(
+            "---\n\
+             # Lines starting with '#' are ignored. Save an empty file to abort.\n\
+             title: {}\n\
+             type: {}\n\
+             tags: {}\n\
+             importance: {}\n\
+             files: {}\n\
+             ---\n\
+             {}",
+            self.title,
+            self.memory_type,
+            self.tags.join(", "),
+            importance,
+            self.files.join(", "),
+            self.content
+        )
+    }
+
+    /// Read an edited draft. Without front matter the whole text is the
+    /// content and the other fields stay as in `self`. `None` when the file
+    /// was emptied, or both title and content were left blank.
+    pub fn parse(&self, text: &str) -> Result<Option<Draft>> {
+        if text.trim().is_empty() {
+            return Ok(None);
+        }
+        let mut draft = self.clone();
+        let body = match split_front_matter(text) {
+            Some((front, body)) => {
+                for line in front.lines().map(str::trim) {
+                    if line.is_empty() || line.starts_with('#') {
+                        continue;
+                    }
+                    let (key, value) = line.split_once(':').with_context(|| {
+                        format!("Front matter line '{}' is not 'key: value'", line)
+                    })?;
+                    let value = value.trim();
+                    match key.trim() {
+                        "title" => draft.title = value.to_string(),
+                        "type" => draft.memory_type = value.to_string(),
+                        "tags" => draft.tags = split_list(value),
+                        "files" => draft.files = split_list(value),
+                        "importance" if value.is_empty() => draft.importance = None,
+                        "importance" => {
+                            draft.importance = Some(value.parse().with_context(|| {
+                                format!("importance '{}' is not a number", value)
+                            })?)
+                        }
+                        other => anyhow::bail!(
+                            "Unknown front matter key '{}'; expected title, type, tags, importance or files",
+                            other
+                        ),
+                    }
+                }
+                body
+            }
+            None => text,
+        };
+        draft.content = body.trim().to_string();
+        if draft.content.is_empty() && draft.title.is_empty() {
+            return Ok(None);
+        }
+        Ok(Some(draft))
+    }
+}
+
+/// (front matter, body) when `text` opens with a `---` fenced block
+fn split_front_matter(text: &str) -> Option<(&str, &str)> {
+    let rest = text
+        .strip_prefix("---\n")
+        .or_else(|| text.strip_prefix("---\r\n"))?;
+    let mut offset = 0;
+    for line in rest.split_inclusive('\n') {
+        if line.trim_end() == "---" {
+            return Some((&rest[..offset], &rest[offset + line.len()..]));
+        }
+        offset += line.len();
+    }
+    None
+}
+
+fn split_list(value: &str) -> Vec<String> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+/// Editor command: `$VISUAL`, then `$EDITOR`, then `vi`
+fn editor_command() -> String {
+    ["VISUAL", "EDITOR"]
+        .iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .find(|cmd| !cmd.trim().is_empty())
+        .unwrap_or_else(|| "vi".to_string())
+}
+
+/// Open `draft` in the user's editor and return the saved text. The editor
+/// command may carry arguments (`code --wait`).
+pub fn edit(draft: &Draft, dir: &Path) -> Result<String> {
+    let path = dir.join(format!("octobrain-memory-{}.md", uuid::Uuid::new_v4()));
+    std::fs::write(&path, draft.render())
+        .with_context(|| format!("Cannot write draft '{}'", path.display()))?;
+    let command = editor_command();
+    let mut parts = command.split_whitespace();
+    let program = parts.next().unwrap_or("vi");
+    let status = std::process::Command::new(program)
+        .args(parts)
+        .arg(&path)
+        .status()
+        .with_context(|| format!("Cannot start editor '{}'", command));
+    let text = std::fs::read_to_string(&path);
+    std::fs::remove_file(&path).ok();
+    let status = status?;
+    if !status.success() {
+        anyhow::bail!(
+            "Editor '{}' exited with {}; nothing stored",
+            command,
+            status
+        );
+    }
+    Ok(text?)
+}
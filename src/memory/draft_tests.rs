@@ -0,0 +1,95 @@
+// Copyright 2026 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#[cfg(test)]
+mod tests {
+    use super::super::draft::Draft;
+
+    fn draft() -> Draft {
+        Draft {
+            title: "Use sqlite".to_string(),
+            memory_type: "architecture".to_string(),
+            importance: Some(0.8),
+            tags: vec!["db".to_string(), "infra".to_string()],
+            files: vec!["src/main.rs".to_string()],
+            content: "Single file, no server.".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_render_parse_round_trip() {
+        let original = draft();
+        let parsed = Draft::default().parse(&original.render()).unwrap();
+        assert_eq!(parsed, Some(original));
+    }
+
+    #[test]
+    fn test_parse_overrides_prefilled_fields() {
+        let text = "---\n\
+                    # comment\n\
+                    title: Switch to postgres\n\
+                    type: decision\n\
+                    tags: db , scaling,\n\
+                    importance:\n\
+                    files:\n\
+                    ---\n\
+                    \n\
+                    Need concurrent writers.\n";
+        let parsed = draft().parse(text).unwrap().unwrap();
+        assert_eq!(parsed.title, "Switch to postgres");
+        assert_eq!(parsed.memory_type, "decision");
+        assert_eq!(parsed.tags, vec!["db", "scaling"]);
+        assert_eq!(parsed.importance, None);
+        assert!(parsed.files.is_empty());
+        assert_eq!(parsed.content, "Need concurrent writers.");
+    }
+
+    #[test]
+    fn test_parse_without_front_matter_keeps_fields() {
+        let parsed = draft().parse("Just the body\n").unwrap().unwrap();
+        assert_eq!(parsed.title, "Use sqlite");
+        assert_eq!(parsed.tags, vec!["db", "infra"]);
+        assert_eq!(parsed.content, "Just the body");
+    }
+
+    #[test]
+    fn test_parse_handles_crlf() {
+        let text = "---\r\ntitle: Windows note\r\ntype: fact\r\n---\r\nBody\r\n";
+        let parsed = Draft::default().parse(text).unwrap().unwrap();
+        assert_eq!(parsed.title, "Windows note");
+        assert_eq!(parsed.memory_type, "fact");
+        assert_eq!(parsed.content, "Body");
+    }
+
+    #[test]
+    fn test_empty_draft_aborts() {
+        assert_eq!(draft().parse("").unwrap(), None);
+        assert_eq!(draft().parse("  \n").unwrap(), None);
+        let blank = Draft::default().render();
+        assert_eq!(Draft::default().parse(&blank).unwrap(), None);
+    }
+
+    #[test]
+    fn test_parse_rejects_bad_front_matter() {
+        let unknown = "---\nauthor: me\n---\nBody";
+        let error = draft().parse(unknown).unwrap_err().to_string();
+        assert!(error.contains("author"), "{}", error);
+
+        let importance = "---\nimportance: high\n---\nBody";
+        assert!(draft().parse(importance).is_err());
+
+        let no_colon = "---\njust words\n---\nBody";
+        assert!(draft().parse(no_colon).is_err());
+    }
+}
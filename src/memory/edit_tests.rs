@@ -0,0 +1,66 @@
+// Copyright 2026 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#[cfg(test)]
+mod tests {
+    use super::super::manager::MemoryEdit;
+    use super::super::types::{Memory, MemoryMetadata, MemoryType};
+
+    fn memory() -> Memory {
+        Memory::new(
+            MemoryType::Architecture,
+            "Cache layout".to_string(),
+            "Entries are keyed by content hash.".to_string(),
+            Some(MemoryMetadata {
+                importance: 0.5,
+                tags: vec!["cache".to_string(), "old".to_string()],
+                related_files: vec!["src/cache.rs".to_string()],
+                ..Default::default()
+            }),
+        )
+    }
+
+    #[test]
+    fn test_revised_metadata_applies_partial_changes() {
+        let edit = MemoryEdit {
+            importance: Some(0.9),
+            add_tags: vec!["storage".to_string(), "cache".to_string()],
+            remove_tags: vec!["old".to_string()],
+            add_files: vec!["src/store.rs".to_string()],
+            remove_files: vec!["src/cache.rs".to_string()],
+            ..Default::default()
+        };
+        let metadata = edit.revised_metadata(&memory());
+        assert_eq!(metadata.tags, vec!["cache", "storage"]);
+        assert_eq!(metadata.related_files, vec!["src/store.rs"]);
+        assert_eq!(metadata.importance, 0.9);
+        assert_eq!(metadata.decay.base_importance, 0.9);
+    }
+
+    #[test]
+    fn test_empty_edit_keeps_metadata() {
+        let edit = MemoryEdit::default();
+        assert!(edit.is_empty());
+        let original = memory();
+        let metadata = edit.revised_metadata(&original);
+        assert_eq!(metadata.tags, original.metadata.tags);
+        assert_eq!(metadata.related_files, original.metadata.related_files);
+        assert_eq!(metadata.importance, original.metadata.importance);
+        assert!(!MemoryEdit {
+            add_tags: vec!["x".to_string()],
+            ..Default::default()
+        }
+        .is_empty());
+    }
+}
@@ -0,0 +1,805 @@
+// Copyright 2026 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! In-process storage backend (`[memory].backend = "ephemeral"`, `--ephemeral`).
+//!
+//! Nothing touches the filesystem: memories, relationships, the recovery buffer
+//! and the access log live in one process-wide state that disappears on exit.
+//! Meant for integration tests and throwaway agent sessions. Search is an
+//! exact scan with the same scoring as the other backends.
+
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, MutexGuard, OnceLock};
+
+use super::backend::{BackendOptions, StorageBackend};
+use super::reranker_integration::RerankerIntegration;
+use super::search_scoring::{
+    document_terms, keyword_terms, score_hybrid, score_vector_hits, KeywordIndex,
+};
+use super::types::{
+    AccessLogEntry, Memory, MemoryConfig, MemoryQuery, MemoryRelationship, MemoryRevision,
+    MemorySearchResult, MemoryState, RelationshipType,
+};
+use crate::embedding::{EmbeddingProvider, InputType};
+
+/// A stored memory with its scope and embedding.
+#[derive(Clone)]
+struct Entry {
+    memory: Memory,
+    project_key: String,
+    role: String,
+    embedding: Vec<f32>,
+}
+
+/// A deleted memory kept for `restore_last_deletions`.
+struct Deleted {
+    entry: Entry,
+    deleted_at: DateTime<Utc>,
+    /// Delete call that removed it; restore undoes whole calls
+    operation: u64,
+}
+
+struct LoggedAccess {
+    entry: AccessLogEntry,
+    project_key: String,
+    role: Option<String>,
+}
+
+/// Everything the backend holds; shared by every store of the process.
+#[derive(Default)]
+pub struct EphemeralState {
+    memories: HashMap<String, Entry>,
+    /// Relationships by ID, with the project they were written under
+    relationships: HashMap<String, (MemoryRelationship, String)>,
+    recovery: Vec<Deleted>,
+    access_log: Vec<LoggedAccess>,
+    /// Saved revisions with the project they were written under
+    revisions: Vec<(MemoryRevision, String)>,
+    /// BM25 statistics over every stored memory; maintained by `insert`/`remove`
+    keywords: KeywordIndex,
+    operations: u64,
+}
+
+impl EphemeralState {
+    fn insert(&mut self, entry: Entry) {
+        self.keywords.add(&document_terms(&entry.memory));
+        if let Some(replaced) = self.memories.insert(entry.memory.id.clone(), entry) {
+            self.keywords.remove(&document_terms(&replaced.memory));
+        }
+    }
+
+    fn remove(&mut self, id: &str) -> Option<Entry> {
+        let entry = self.memories.remove(id)?;
+        self.keywords.remove(&document_terms(&entry.memory));
+        Some(entry)
+    }
+}
+
+static SHARED_STATE: OnceLock<Arc<Mutex<EphemeralState>>> = OnceLock::new();
+
+/// Cosine distance, or None when the dimensions differ (other embedding model).
+pub(crate) fn cosine_distance(a: &[f32], b: &[f32]) -> Option<f32> {
+    if a.len() != b.len() || a.is_empty() {
+        return None;
+    }
+    let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+    let norm_a = a.iter().map(|x| x * x).sum::<f32>().sqrt();
+    let norm_b = b.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if norm_a == 0.0 || norm_b == 0.0 {
+        return Some(1.0);
+    }
+    Some(1.0 - dot / (norm_a * norm_b))
+}
+
+/// In-memory memory store
+pub struct EphemeralStore {
+    state: Arc<Mutex<EphemeralState>>,
+    embedding_provider: Box<dyn EmbeddingProvider>,
+    config: MemoryConfig,
+    main_config: crate::config::Config,
+    reranker_integration: Mutex<Option<RerankerIntegration>>,
+    project_key: Option<String>,
+    role: Option<String>,
+    /// `project_key` used for writes ("default" when unscoped)
+    label: String,
+}
+
+impl EphemeralStore {
+    /// Store over the process-wide state, so every manager of this process
+    /// sees the same memories, as they would with a shared database.
+    pub fn shared(options: BackendOptions) -> Self {
+        let state = SHARED_STATE.get_or_init(Default::default).clone();
+        Self::with_state(state, options)
+    }
+
+    /// Store over its own empty state.
+    #[cfg(test)]
+    pub fn new(options: BackendOptions) -> Self {
+        Self::with_state(Default::default(), options)
+    }
+
+    fn with_state(state: Arc<Mutex<EphemeralState>>, options: BackendOptions) -> Self {
+        let label = options
+            .project_key
+            .clone()
+            .unwrap_or_else(|| "default".to_string());
+        Self {
+            state,
+            embedding_provider: options.embedding_provider,
+            config: options.config,
+            main_config: options.main_config,
+            reranker_integration: Mutex::new(options.reranker_integration),
+            project_key: options.project_key,
+            role: options.role,
+            label,
+        }
+    }
+
+    fn state(&self) -> Result<MutexGuard<'_, EphemeralState>> {
+        self.state
+            .lock()
+            .map_err(|_| anyhow::anyhow!("Ephemeral store lock poisoned"))
+    }
+
+    fn current_importance(&self, memory: &Memory) -> f32 {
+        memory.get_current_importance(
+            self.config.decay_enabled,
+            self.config.min_importance_threshold,
+            self.config.decay_half_life_days,
+            self.config.access_boost_factor,
+        )
+    }
+
+    /// Project scope only (no role), as `MemoryStore` uses for ID lookups.
+    fn in_project(&self, project_key: &str) -> bool {
+        self.project_key
+            .as_deref()
+            .is_none_or(|key| key == project_key)
+    }
+
+    /// Project and role scope.
+    fn in_scope(&self, entry: &Entry) -> bool {
+        self.in_project(&entry.project_key)
+            && self.role.as_deref().is_none_or(|role| role == entry.role)
+    }
+
+    /// Scope plus the scalar filters of `query`.
+    fn matches(&self, entry: &Entry, query: &MemoryQuery) -> bool {
+        let memory = &entry.memory;
+        let metadata = &memory.metadata;
+        self.in_scope(entry)
+            && query
+                .memory_types
+                .as_ref()
+                .filter(|t| !t.is_empty())
+                .is_none_or(|types| types.contains(&memory.memory_type))
+            && query
+                .exclude_types
+                .as_ref()
+                .is_none_or(|types| !types.contains(&memory.memory_type))
+            && query
+                .min_importance
+                .is_none_or(|min| metadata.importance >= min)
+            && query
+                .min_confidence
+                .is_none_or(|min| metadata.confidence >= min)
+            && query
+                .git_commit
+                .as_ref()
+                .is_none_or(|c| metadata.git_commit.as_ref() == Some(c))
+            && query
+                .created_by
+                .as_ref()
+                .is_none_or(|c| metadata.created_by.as_ref() == Some(c))
+            && query
+                .created_after
+                .is_none_or(|after| memory.created_at >= after)
+            && query
+                .created_before
+                .is_none_or(|before| memory.created_at <= before)
+    }
+
+    /// Memories in scope that satisfy `keep`.
+    fn select(&self, keep: impl Fn(&Entry) -> bool) -> Result<Vec<Memory>> {
+        Ok(self
+            .state()?
+            .memories
+            .values()
+            .filter(|e| self.in_scope(e) && keep(e))
+            .map(|e| e.memory.clone())
+            .collect())
+    }
+
+    async fn embed(&self, text: &str, input_type: InputType) -> Result<Vec<f32>> {
+        crate::embedding::generate_embedding(
+            text,
+            self.embedding_provider.as_ref(),
+            self.main_config.embedding.timeout_secs,
+            input_type,
+        )
+        .await
+    }
+
+    /// Nearest memories matching `query` by cosine distance, closest first.
+    fn nearest(
+        &self,
+        embedding: &[f32],
+        query: &MemoryQuery,
+        limit: usize,
+    ) -> Result<Vec<(Memory, f32)>> {
+        let state = self.state()?;
+        let mut hits: Vec<(Memory, f32)> = state
+            .memories
+            .values()
+            .filter(|e| self.matches(e, query))
+            .filter_map(|e| Some((e.memory.clone(), cosine_distance(embedding, &e.embedding)?)))
+            .collect();
+        hits.sort_by(|a, b| a.1.total_cmp(&b.1));
+        hits.truncate(limit);
+        Ok(hits)
+    }
+
+    /// IDs of memories matching `query` that share words with `query_text`,
+    /// best BM25 score first.
+    fn keyword_matches(
+        &self,
+        query_text: &str,
+        query: &MemoryQuery,
+        limit: usize,
+    ) -> Result<Vec<String>> {
+        let terms = keyword_terms(query_text);
+        if terms.is_empty() {
+            return Ok(Vec::new());
+        }
+        let state = self.state()?;
+        let mut hits: Vec<(f32, &str)> = state
+            .memories
+            .values()
+            .filter(|e| self.matches(e, query))
+            .filter_map(|e| {
+                let score = state.keywords.score(&terms, &document_terms(&e.memory));
+                (score > 0.0).then_some((score, e.memory.id.as_str()))
+            })
+            .collect();
+        hits.sort_by(|a, b| b.0.total_cmp(&a.0).then_with(|| a.1.cmp(b.1)));
+        Ok(hits
+            .into_iter()
+            .take(limit)
+            .map(|(_, id)| id.to_string())
+            .collect())
+    }
+
+    /// Vector search weighted by temporal importance and trust, like `MemoryStore`.
+    async fn vector_search(&self, query: &MemoryQuery) -> Result<Vec<MemorySearchResult>> {
+        let limit = query
+            .limit
+            .unwrap_or(self.config.max_search_results)
+            .min(self.config.max_search_results);
+
+        let hits = match &query.query_text {
+            Some(query_text) => {
+                let embedding = self.embed(query_text, InputType::Query).await?;
+                // Over-fetch to absorb post-filter losses
+                self.nearest(&embedding, query, limit * 2)?
+                    .into_iter()
+                    .map(|(memory, distance)| (memory, Some(distance)))
+                    .collect()
+            }
+            None => self
+                .select(|e| self.matches(e, query))?
+                .into_iter()
+                .map(|memory| (memory, None))
+                .collect(),
+        };
+        Ok(score_vector_hits(
+            hits,
+            query,
+            |m| self.current_importance(m),
+            limit,
+        ))
+    }
+
+    /// Vector + keyword ranks fused with RRF, then blended with recency and
+    /// importance using the `[search.hybrid]` weights.
+    async fn hybrid_search(
+        &self,
+        query: &MemoryQuery,
+        query_text: &str,
+        limit: usize,
+    ) -> Result<Vec<MemorySearchResult>> {
+        let embedding = self.embed(query_text, InputType::Query).await?;
+        let vector_hits = self.nearest(&embedding, query, limit)?;
+        let keyword_ids = self.keyword_matches(query_text, query, limit)?;
+
+        let vector_ids: Vec<String> = vector_hits.iter().map(|(m, _)| m.id.clone()).collect();
+        let mut candidates: Vec<Memory> = vector_hits.into_iter().map(|(m, _)| m).collect();
+        let missing: Vec<&String> = keyword_ids
+            .iter()
+            .filter(|id| !vector_ids.contains(id))
+            .collect();
+        if !missing.is_empty() {
+            candidates.extend(self.select(|e| missing.contains(&&e.memory.id))?);
+        }
+
+        Ok(score_hybrid(
+            candidates,
+            &vector_ids,
+            &keyword_ids,
+            query,
+            &self.main_config.search.hybrid,
+            |m| self.current_importance(m),
+            limit,
+        ))
+    }
+
+    /// Bump access stats and log text queries for returned memories.
+    fn record_accesses(&self, results: &[MemorySearchResult], query_text: Option<&str>) {
+        if results.is_empty() {
+            return;
+        }
+        let Ok(mut state) = self.state() else {
+            return;
+        };
+        let now = Utc::now();
+        for result in results {
+            let id = &result.memory.id;
+            if let Some(entry) = state
+                .memories
+                .get_mut(id)
+                .filter(|e| e.project_key == self.label)
+            {
+                entry.memory.metadata.decay.access_count += 1;
+                entry.memory.metadata.decay.last_accessed = now;
+            }
+            if let Some(query_text) = query_text {
+                state.access_log.push(LoggedAccess {
+                    entry: AccessLogEntry {
+                        memory_id: id.clone(),
+                        query: query_text.to_string(),
+                        accessed_at: now,
+                    },
+                    project_key: self.label.clone(),
+                    role: self.role.clone(),
+                });
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl StorageBackend for EphemeralStore {
+    fn has_no_project_key(&self) -> bool {
+        self.project_key.is_none()
+    }
+
+    fn project_label(&self) -> &str {
+        &self.label
+    }
+
+    async fn store_memory(&self, memory: &Memory) -> Result<()> {
+        let searchable_text = memory.get_searchable_text();
+        if searchable_text.trim().is_empty() {
+            return Err(anyhow::anyhow!(
+                "Cannot generate embedding: searchable text is empty. Title: '{}', Content: '{}'",
+                memory.title,
+                memory.content
+            ));
+        }
+        let embedding = self.embed(&searchable_text, InputType::Document).await?;
+        self.store_memory_with_embedding(memory, embedding).await
+    }
+
+    async fn store_memory_with_embedding(
+        &self,
+        memory: &Memory,
+        embedding: Vec<f32>,
+    ) -> Result<()> {
+        self.state()?.insert(Entry {
+            memory: memory.clone(),
+            project_key: self.label.clone(),
+            role: self.role.clone().unwrap_or_default(),
+            embedding,
+        });
+        Ok(())
+    }
+
+    async fn embedding_dimension(&self) -> Result<usize> {
+        Ok(self.embed("dimension probe", InputType::Query).await?.len())
+    }
+
+    async fn get_all_embeddings(&self) -> Result<HashMap<String, Vec<f32>>> {
+        Ok(self
+            .state()?
+            .memories
+            .values()
+            .filter(|e| self.in_scope(e))
+            .map(|e| (e.memory.id.clone(), e.embedding.clone()))
+            .collect())
+    }
+
+    async fn delete_memories(&self, memory_ids: &[String]) -> Result<usize> {
+        if memory_ids.is_empty() {
+            return Ok(0);
+        }
+        let mut state = self.state()?;
+        state.operations += 1;
+        let operation = state.operations;
+        let deleted_at = Utc::now();
+
+        let mut deleted = 0;
+        for id in memory_ids {
+            if state
+                .memories
+                .get(id)
+                .is_none_or(|e| e.project_key != self.label)
+            {
+                continue;
+            }
+            if let Some(entry) = state.remove(id) {
+                deleted += 1;
+                if self.config.undo_buffer_size > 0 {
+                    state.recovery.push(Deleted {
+                        entry,
+                        deleted_at,
+                        operation,
+                    });
+                }
+            }
+        }
+        // Keep only the newest `undo_buffer_size` entries for this project
+        let kept = state
+            .recovery
+            .iter()
+            .filter(|d| d.entry.project_key == self.label)
+            .count();
+        let mut excess = kept.saturating_sub(self.config.undo_buffer_size);
+        state.recovery.retain(|d| {
+            if excess > 0 && d.entry.project_key == self.label {
+                excess -= 1;
+                return false;
+            }
+            true
+        });
+        state.relationships.retain(|_, (r, project_key)| {
+            *project_key != self.label
+                || !(memory_ids.contains(&r.source_id) || memory_ids.contains(&r.target_id))
+        });
+        Ok(deleted)
+    }
+
+    async fn list_recoverable(&self) -> Result<Vec<(Memory, String)>> {
+        let state = self.state()?;
+        Ok(state
+            .recovery
+            .iter()
+            .rev()
+            .filter(|d| d.entry.project_key == self.label)
+            .map(|d| (d.entry.memory.clone(), d.deleted_at.to_rfc3339()))
+            .collect())
+    }
+
+    async fn restore_last_deletions(&self, operations: usize) -> Result<Vec<Memory>> {
+        let mut state = self.state()?;
+        let mut undone: Vec<u64> = state
+            .recovery
+            .iter()
+            .filter(|d| d.entry.project_key == self.label)
+            .map(|d| d.operation)
+            .collect();
+        undone.sort_unstable_by(|a, b| b.cmp(a));
+        undone.dedup();
+        undone.truncate(operations.max(1));
+
+        let (restore, keep): (Vec<Deleted>, Vec<Deleted>) = std::mem::take(&mut state.recovery)
+            .into_iter()
+            .partition(|d| d.entry.project_key == self.label && undone.contains(&d.operation));
+        state.recovery = keep;
+        // Oldest first, so a memory deleted twice comes back as its latest copy
+        let mut restored: Vec<Memory> = Vec::with_capacity(restore.len());
+        for deleted in restore {
+            restored.retain(|m| m.id != deleted.entry.memory.id);
+            restored.push(deleted.entry.memory.clone());
+            state.insert(deleted.entry);
+        }
+        Ok(restored)
+    }
+
+    async fn restore_deleted(&self, memory_ids: &[String]) -> Result<Vec<Memory>> {
+        let mut state = self.state()?;
+        let (restore, keep): (Vec<Deleted>, Vec<Deleted>) = std::mem::take(&mut state.recovery)
+            .into_iter()
+            .partition(|d| {
+                d.entry.project_key == self.label && memory_ids.contains(&d.entry.memory.id)
+            });
+        state.recovery = keep;
+        // Oldest first, so a memory deleted twice comes back as its latest copy
+        let mut restored: Vec<Memory> = Vec::with_capacity(restore.len());
+        for deleted in restore {
+            restored.retain(|m| m.id != deleted.entry.memory.id);
+            restored.push(deleted.entry.memory.clone());
+            state.insert(deleted.entry);
+        }
+        Ok(restored)
+    }
+
+    async fn purge_deleted(&self, deleted_before: Option<DateTime<Utc>>) -> Result<usize> {
+        let mut state = self.state()?;
+        let before = state.recovery.len();
+        state.recovery.retain(|d| {
+            d.entry.project_key != self.label
+                || deleted_before.is_some_and(|cutoff| d.deleted_at >= cutoff)
+        });
+        Ok(before - state.recovery.len())
+    }
+
+    async fn get_memory(&self, memory_id: &str) -> Result<Option<Memory>> {
+        Ok(self
+            .state()?
+            .memories
+            .get(memory_id)
+            .filter(|e| self.in_project(&e.project_key))
+            .map(|e| e.memory.clone()))
+    }
+
+    async fn search_memories(&self, query: &MemoryQuery) -> Result<Vec<MemorySearchResult>> {
+        let reranker = self
+            .reranker_integration
+            .lock()
+            .ok()
+            .and_then(|g| g.as_ref().filter(|r| r.config.enabled).cloned());
+        let reranker_query = reranker.and_then(|r| {
+            query
+                .query_text
+                .as_deref()
+                .filter(|t| !t.trim().is_empty())
+                .map(|t| (r, t.to_string()))
+        });
+        // Give the reranker a wider candidate set to reorder
+        let top_k = self.main_config.search.reranker.top_k_candidates;
+        let widen = reranker_query.is_some() && top_k > 1;
+
+        let candidates = match query.query_text.as_deref() {
+            Some(text) if self.main_config.search.hybrid.enabled => {
+                let limit = if widen {
+                    top_k
+                } else {
+                    query.limit.unwrap_or(self.config.max_search_results)
+                };
+                self.hybrid_search(query, text, limit).await?
+            }
+            _ if widen => {
+                let mut extended = query.clone();
+                extended.limit = Some(top_k);
+                self.vector_search(&extended).await?
+            }
+            _ => self.vector_search(query).await?,
+        };
+
+        let results = match reranker_query {
+            Some((reranker, text)) => reranker.rerank_memories(&text, candidates).await?,
+            None => candidates,
+        };
+        self.record_accesses(&results, query.query_text.as_deref());
+        Ok(results)
+    }
+
+    async fn get_access_log(&self, since: DateTime<Utc>) -> Result<Vec<AccessLogEntry>> {
+        let state = self.state()?;
+        Ok(state
+            .access_log
+            .iter()
+            .filter(|a| {
+                self.in_project(&a.project_key)
+                    && self
+                        .role
+                        .as_ref()
+                        .is_none_or(|role| a.role.as_ref() == Some(role))
+                    && a.entry.accessed_at >= since
+            })
+            .map(|a| a.entry.clone())
+            .collect())
+    }
+
+    async fn get_revisions(&self, memory_id: &str) -> Result<Vec<MemoryRevision>> {
+        let mut revisions: Vec<MemoryRevision> = self
+            .state()?
+            .revisions
+            .iter()
+            .filter(|(r, project_key)| r.memory_id == memory_id && self.in_project(project_key))
+            .map(|(r, _)| r.clone())
+            .collect();
+        revisions.sort_by_key(|r| r.revision);
+        Ok(revisions)
+    }
+
+    async fn append_revision(&self, revision: &MemoryRevision) -> Result<()> {
+        self.state()?
+            .revisions
+            .push((revision.clone(), self.label.clone()));
+        Ok(())
+    }
+
+    async fn update_state_and_importance(
+        &self,
+        id: &str,
+        new_state: MemoryState,
+        new_importance: f32,
+    ) -> Result<()> {
+        let mut state = self.state()?;
+        if let Some(entry) = state
+            .memories
+            .get_mut(id)
+            .filter(|e| e.project_key == self.label)
+        {
+            entry.memory.metadata.state = new_state;
+            entry.memory.metadata.importance = new_importance.clamp(0.0, 1.0);
+        }
+        Ok(())
+    }
+
+    async fn get_recent_working_memories(&self, since: DateTime<Utc>) -> Result<Vec<Memory>> {
+        self.select(|e| {
+            e.memory.metadata.state == MemoryState::Working && e.memory.created_at >= since
+        })
+    }
+
+    async fn store_relationship(&self, relationship: &MemoryRelationship) -> Result<()> {
+        self.state()?.relationships.insert(
+            relationship.id.clone(),
+            (relationship.clone(), self.label.clone()),
+        );
+        Ok(())
+    }
+
+    async fn get_memory_relationships(&self, memory_id: &str) -> Result<Vec<MemoryRelationship>> {
+        Ok(self
+            .state()?
+            .relationships
+            .values()
+            .filter(|(r, project_key)| {
+                self.in_project(project_key)
+                    && (r.source_id == memory_id || r.target_id == memory_id)
+            })
+            .map(|(r, _)| r.clone())
+            .collect())
+    }
+
+    async fn get_all_relationships(&self) -> Result<Vec<MemoryRelationship>> {
+        Ok(self
+            .state()?
+            .relationships
+            .values()
+            .filter(|(_, project_key)| self.in_project(project_key))
+            .map(|(r, _)| r.clone())
+            .collect())
+    }
+
+    async fn delete_auto_linked_relationships(&self, memory_id: &str) -> Result<()> {
+        self.state()?.relationships.retain(|_, (r, project_key)| {
+            !(*project_key == self.label
+                && matches!(r.relationship_type, RelationshipType::AutoLinked)
+                && (r.source_id == memory_id || r.target_id == memory_id))
+        });
+        Ok(())
+    }
+
+    async fn delete_relationships(&self, relationship_ids: &[String]) -> Result<usize> {
+        let mut state = self.state()?;
+        let before = state.relationships.len();
+        state.relationships.retain(|_, (r, project_key)| {
+            !(*project_key == self.label && relationship_ids.contains(&r.id))
+        });
+        Ok(before - state.relationships.len())
+    }
+
+    async fn get_memory_count(&self) -> Result<usize> {
+        Ok(self
+            .state()?
+            .memories
+            .values()
+            .filter(|e| self.in_project(&e.project_key))
+            .count())
+    }
+
+    async fn get_distinct_projects_and_roles(&self) -> Result<(Vec<String>, Vec<String>)> {
+        let state = self.state()?;
+        let in_project = state
+            .memories
+            .values()
+            .filter(|e| self.in_project(&e.project_key));
+        let mut projects: Vec<String> = in_project.clone().map(|e| e.project_key.clone()).collect();
+        let mut roles: Vec<String> = in_project
+            .filter(|e| !e.role.is_empty())
+            .map(|e| e.role.clone())
+            .collect();
+        projects.sort();
+        projects.dedup();
+        roles.sort();
+        roles.dedup();
+        Ok((projects, roles))
+    }
+
+    async fn get_all_memories(&self) -> Result<Vec<Memory>> {
+        self.select(|_| true)
+    }
+
+    async fn get_memories_with_files(&self) -> Result<Vec<Memory>> {
+        Ok(self
+            .state()?
+            .memories
+            .values()
+            .filter(|e| {
+                self.in_project(&e.project_key) && !e.memory.metadata.related_files.is_empty()
+            })
+            .map(|e| e.memory.clone())
+            .collect())
+    }
+
+    async fn cleanup_old_memories(&self) -> Result<usize> {
+        let Some(cleanup_days) = self.config.auto_cleanup_days else {
+            return Ok(0);
+        };
+        let cutoff = Utc::now() - chrono::Duration::days(cleanup_days as i64);
+        let ids: Vec<String> = self
+            .state()?
+            .memories
+            .values()
+            .filter(|e| {
+                e.project_key == self.label
+                    && e.memory.created_at < cutoff
+                    && e.memory.metadata.importance < self.config.cleanup_min_importance
+            })
+            .map(|e| e.memory.id.clone())
+            .collect();
+        // Through the recovery buffer, so a cleanup can be undone from the trash
+        self.delete_memories(&ids).await
+    }
+
+    async fn clear_all_memory_data(&self) -> Result<usize> {
+        let mut state = self.state()?;
+        let before = state.memories.len() + state.relationships.len();
+        let cleared: Vec<String> = state
+            .memories
+            .values()
+            .filter(|e| e.project_key == self.label)
+            .map(|e| e.memory.id.clone())
+            .collect();
+        for id in &cleared {
+            state.remove(id);
+        }
+        state
+            .relationships
+            .retain(|_, (_, project_key)| *project_key != self.label);
+        state.access_log.retain(|a| a.project_key != self.label);
+        state
+            .revisions
+            .retain(|(_, project_key)| *project_key != self.label);
+        Ok(before - state.memories.len() - state.relationships.len())
+    }
+
+    fn enable_reranker(&self, model: Option<String>) {
+        super::reranker_integration::enable_reranker(
+            &self.reranker_integration,
+            &self.main_config.search.reranker,
+            model,
+        );
+    }
+
+    fn disable_reranker(&self) {
+        super::reranker_integration::disable_reranker(&self.reranker_integration);
+    }
+}
@@ -0,0 +1,325 @@
+// Copyright 2026 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#[cfg(test)]
+mod tests {
+    use super::super::backend::{BackendKind, BackendOptions, StorageBackend};
+    use super::super::ephemeral_store::EphemeralStore;
+    use super::super::types::{
+        KeywordMatch, Memory, MemoryConfig, MemoryQuery, MemoryRelationship, MemoryState,
+        MemoryType, RelationshipType, SearchSignal,
+    };
+    use crate::embedding::{EmbeddingProvider, InputType};
+    use async_trait::async_trait;
+
+    const DIM: usize = 64;
+
+    /// Bag-of-words embedding: texts sharing words point the same way.
+    struct WordHashEmbedding;
+
+    fn embed(text: &str) -> Vec<f32> {
+        let mut v = vec![0.0f32; DIM];
+        for word in text.to_lowercase().split(|c: char| !c.is_alphanumeric()) {
+            if word.is_empty() {
+                continue;
+            }
+            let hash = word
+                .bytes()
+                .fold(5381usize, |h, b| h.wrapping_mul(33) ^ b as usize);
+            v[hash % DIM] += 1.0;
+        }
+        let norm = v.iter().map(|x| x * x).sum::<f32>().sqrt().max(1e-6);
+        v.iter().map(|x| x / norm).collect()
+    }
+
+    #[async_trait]
+    impl EmbeddingProvider for WordHashEmbedding {
+        async fn generate_embedding(&self, text: &str) -> anyhow::Result<Vec<f32>> {
+            Ok(embed(text))
+        }
+
+        async fn generate_embeddings_batch(
+            &self,
+            texts: Vec<String>,
+            _input_type: InputType,
+        ) -> anyhow::Result<Vec<Vec<f32>>> {
+            Ok(texts.iter().map(|t| embed(t)).collect())
+        }
+
+        fn get_dimension(&self) -> usize {
+            DIM
+        }
+    }
+
+    fn options(project: &str, hybrid: bool) -> BackendOptions {
+        let mut search = crate::config::SearchConfig::default();
+        search.hybrid.enabled = hybrid;
+        BackendOptions {
+            db_dir: std::path::PathBuf::new(),
+            project_key: Some(project.to_string()),
+            role: None,
+            embedding_provider: Box::new(WordHashEmbedding),
+            config: MemoryConfig::default(),
+            main_config: crate::config::Config {
+                embedding: Default::default(),
+                search,
+                memory: MemoryConfig::default(),
+                knowledge: Default::default(),
+                vision: Default::default(),
+                maintenance: Default::default(),
+                server: Default::default(),
+                storage: Default::default(),
+            },
+            reranker_integration: None,
+        }
+    }
+
+    fn memory(title: &str, content: &str) -> Memory {
+        Memory::new(
+            MemoryType::Architecture,
+            title.to_string(),
+            content.to_string(),
+            None,
+        )
+    }
+
+    fn search(text: &str) -> MemoryQuery {
+        MemoryQuery {
+            query_text: Some(text.to_string()),
+            limit: Some(5),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_backend_kind_parses_ephemeral() {
+        assert_eq!(
+            BackendKind::parse("ephemeral").unwrap(),
+            BackendKind::Ephemeral
+        );
+        assert_eq!(
+            BackendKind::parse("In-Memory").unwrap(),
+            BackendKind::Ephemeral
+        );
+    }
+
+    #[tokio::test]
+    async fn test_store_and_search() {
+        let store = EphemeralStore::new(options("p1", false));
+        let db = memory("Database choice", "We keep vectors in process memory");
+        let auth = memory("Auth flow", "Tokens are refreshed by the gateway");
+        store.store_memory(&db).await.unwrap();
+        store.store_memory(&auth).await.unwrap();
+        assert_eq!(store.get_memory_count().await.unwrap(), 2);
+
+        let results = store
+            .search_memories(&search("vectors memory"))
+            .await
+            .unwrap();
+        assert_eq!(results[0].memory.id, db.id);
+        let fetched = store.get_memory(&db.id).await.unwrap().unwrap();
+        assert_eq!(fetched.metadata.decay.access_count, 1);
+        let log = store
+            .get_access_log(chrono::Utc::now() - chrono::Duration::minutes(1))
+            .await
+            .unwrap();
+        assert!(log.iter().any(|e| e.memory_id == db.id));
+    }
+
+    #[tokio::test]
+    async fn test_store_memories_stores_every_entry() {
+        let store = EphemeralStore::new(options("p1", false));
+        let batch = vec![
+            memory("Database choice", "We keep vectors in process memory"),
+            memory("Auth flow", "Tokens are refreshed by the gateway"),
+        ];
+        store.store_memories(&batch).await.unwrap();
+        assert_eq!(store.get_memory_count().await.unwrap(), 2);
+        let results = store
+            .search_memories(&search("gateway tokens"))
+            .await
+            .unwrap();
+        assert_eq!(results[0].memory.id, batch[1].id);
+    }
+
+    #[tokio::test]
+    async fn test_hybrid_search_uses_keywords() {
+        let store = EphemeralStore::new(options("p1", true));
+        let retry = memory("Retry policy", "Clients back off exponentially");
+        let cache = memory("Cache layer", "Responses are cached for an hour");
+        store.store_memory(&retry).await.unwrap();
+        store.store_memory(&cache).await.unwrap();
+
+        let results = store.search_memories(&search("cached")).await.unwrap();
+        assert_eq!(results[0].memory.id, cache.id);
+        assert!(results[0].selection_reason.starts_with("Hybrid"));
+    }
+
+    #[tokio::test]
+    async fn test_explain_reports_signals() {
+        let store = EphemeralStore::new(options("p1", true));
+        let cache = memory("Cache layer", "Responses are cached, cached for an hour");
+        store.store_memory(&cache).await.unwrap();
+
+        let results = store.search_memories(&search("cached")).await.unwrap();
+        assert!(results[0].signals.is_empty());
+
+        let query = MemoryQuery {
+            explain: true,
+            ..search("cached responses")
+        };
+        let results = store.search_memories(&query).await.unwrap();
+        let signals = &results[0].signals;
+        assert!(matches!(
+            signals[0],
+            SearchSignal::Fusion {
+                vector_rank: Some(1),
+                keyword_rank: Some(1),
+                ..
+            }
+        ));
+        assert!(signals.contains(&SearchSignal::Keyword {
+            matches: vec![
+                KeywordMatch {
+                    term: "cached".to_string(),
+                    count: 2,
+                },
+                KeywordMatch {
+                    term: "responses".to_string(),
+                    count: 1,
+                },
+            ],
+        }));
+    }
+
+    #[tokio::test]
+    async fn test_delete_and_restore_round_trip() {
+        let store = EphemeralStore::new(options("p1", false));
+        let a = memory("First", "alpha content");
+        let b = memory("Second", "beta content");
+        store.store_memory(&a).await.unwrap();
+        store.store_memory(&b).await.unwrap();
+        store
+            .store_relationship(&MemoryRelationship {
+                id: "rel".to_string(),
+                source_id: a.id.clone(),
+                target_id: b.id.clone(),
+                relationship_type: RelationshipType::RelatedTo,
+                strength: 0.5,
+                description: String::new(),
+                created_at: chrono::Utc::now(),
+            })
+            .await
+            .unwrap();
+
+        store.delete_memory(&a.id).await.unwrap();
+        store.delete_memory(&b.id).await.unwrap();
+        assert_eq!(store.get_memory_count().await.unwrap(), 0);
+        assert!(store.get_all_relationships().await.unwrap().is_empty());
+        assert_eq!(store.list_recoverable().await.unwrap().len(), 2);
+
+        // One operation back restores only the last delete
+        let restored = store.restore_last_deletions(1).await.unwrap();
+        assert_eq!(restored.len(), 1);
+        assert_eq!(restored[0].id, b.id);
+        let restored = store.restore_last_deletions(1).await.unwrap();
+        assert_eq!(restored[0].id, a.id);
+        assert!(store.list_recoverable().await.unwrap().is_empty());
+        let results = store.search_memories(&search("alpha")).await.unwrap();
+        assert_eq!(results[0].memory.id, a.id);
+    }
+
+    #[tokio::test]
+    async fn test_state_update_and_clear() {
+        let store = EphemeralStore::new(options("p1", false));
+        let note = memory("Scoped", "only in p1");
+        store.store_memory(&note).await.unwrap();
+        store
+            .update_state_and_importance(&note.id, MemoryState::Archived, 0.2)
+            .await
+            .unwrap();
+        let fetched = store.get_memory(&note.id).await.unwrap().unwrap();
+        assert_eq!(fetched.metadata.state, MemoryState::Archived);
+        assert!(store
+            .get_recent_working_memories(chrono::Utc::now() - chrono::Duration::hours(1))
+            .await
+            .unwrap()
+            .is_empty());
+        assert_eq!(store.clear_all_memory_data().await.unwrap(), 1);
+        assert_eq!(store.get_memory_count().await.unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_shared_state_is_scoped_by_project() {
+        let key = |n: &str| format!("{}-{}", n, uuid::Uuid::new_v4());
+        let (p1, p2) = (key("p1"), key("p2"));
+        let first = EphemeralStore::shared(options(&p1, false));
+        let note = memory("Shared", "visible to every store of this project");
+        first.store_memory(&note).await.unwrap();
+
+        // A second store of the same project sees the memory, another project does not
+        let same = EphemeralStore::shared(options(&p1, false));
+        assert!(same.get_memory(&note.id).await.unwrap().is_some());
+        let other = EphemeralStore::shared(options(&p2, false));
+        assert!(other.get_memory(&note.id).await.unwrap().is_none());
+        assert_eq!(other.get_memory_count().await.unwrap(), 0);
+        assert_eq!(
+            other
+                .delete_memories(std::slice::from_ref(&note.id))
+                .await
+                .unwrap(),
+            0
+        );
+    }
+
+    #[tokio::test]
+    async fn test_revisions_are_numbered_per_memory() {
+        let store = EphemeralStore::new(options("p1", false));
+        let mut note = memory("Queue", "First draft");
+        let other = memory("Other", "Unrelated");
+        store.store_memory(&note).await.unwrap();
+        assert!(store.get_revisions(&note.id).await.unwrap().is_empty());
+
+        assert_eq!(store.store_revision(&note).await.unwrap(), 1);
+        note.content = "Second draft".to_string();
+        assert_eq!(store.store_revision(&note).await.unwrap(), 2);
+        assert_eq!(store.store_revision(&other).await.unwrap(), 1);
+
+        let revisions = store.get_revisions(&note.id).await.unwrap();
+        let numbers: Vec<u32> = revisions.iter().map(|r| r.revision).collect();
+        assert_eq!(numbers, vec![1, 2]);
+        assert_eq!(revisions[0].memory.content, "First draft");
+        assert_eq!(revisions[1].memory.content, "Second draft");
+    }
+
+    #[tokio::test]
+    async fn test_cleanup_goes_to_trash() {
+        let store = EphemeralStore::new(options("p1", false));
+        let mut stale = memory("Stale", "long forgotten detail");
+        stale.created_at = chrono::Utc::now() - chrono::Duration::days(400);
+        stale.metadata.importance = 0.05;
+        let fresh = memory("Fresh", "current detail");
+        store.store_memory(&stale).await.unwrap();
+        store.store_memory(&fresh).await.unwrap();
+
+        assert_eq!(store.cleanup_old_memories().await.unwrap(), 1);
+        assert!(store.get_memory(&stale.id).await.unwrap().is_none());
+        let restored = store
+            .restore_deleted(std::slice::from_ref(&stale.id))
+            .await
+            .unwrap();
+        assert_eq!(restored[0].id, stale.id);
+        assert_eq!(store.get_memory_count().await.unwrap(), 2);
+    }
+}
@@ -0,0 +1,75 @@
+// Copyright 2026 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#[cfg(test)]
+mod tests {
+    use super::super::store::{build_scalar_predicate_test, matches_json_filters};
+    use super::super::types::{Memory, MemoryQuery, MemoryType};
+
+    fn memory(tags: &[&str], files: &[&str]) -> Memory {
+        let mut m = Memory::new(
+            MemoryType::Code,
+            "Exclusion subject".to_string(),
+            "Memory used to exercise exclusion filters".to_string(),
+            None,
+        );
+        m.metadata.tags = tags.iter().map(|t| t.to_string()).collect();
+        m.metadata.related_files = files.iter().map(|f| f.to_string()).collect();
+        m
+    }
+
+    #[test]
+    fn test_predicate_excludes_types() {
+        let query = MemoryQuery {
+            exclude_types: Some(vec![MemoryType::BugFix, MemoryType::Testing]),
+            ..Default::default()
+        };
+        let pred = build_scalar_predicate_test(Some("proj123"), None, &query);
+        assert!(
+            pred.contains("memory_type NOT IN ('bug_fix', 'testing')"),
+            "Expected type exclusion, got: {}",
+            pred
+        );
+    }
+
+    #[test]
+    fn test_excluded_tag_rejects_memory() {
+        let query = MemoryQuery {
+            exclude_tags: Some(vec!["wip".to_string()]),
+            ..Default::default()
+        };
+        assert!(!matches_json_filters(
+            &memory(&["auth", "wip"], &[]),
+            &query
+        ));
+        assert!(matches_json_filters(&memory(&["auth"], &[]), &query));
+    }
+
+    #[test]
+    fn test_exclusion_wins_over_inclusion() {
+        let query = MemoryQuery {
+            tags: Some(vec!["auth".to_string()]),
+            exclude_files: Some(vec!["src/legacy.rs".to_string()]),
+            ..Default::default()
+        };
+        assert!(!matches_json_filters(
+            &memory(&["auth"], &["src/legacy.rs"]),
+            &query
+        ));
+        assert!(matches_json_filters(
+            &memory(&["auth"], &["src/auth.rs"]),
+            &query
+        ));
+    }
+}
@@ -0,0 +1,351 @@
+// Copyright 2026 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Memory export formats.
+
+use crate::memory::types::{Memory, MemoryGraph, MemoryRelationship, MemoryType};
+use serde_json::json;
+use std::collections::{HashMap, HashSet, VecDeque};
+
+/// Self-contained page for `memory graph export --format html`; the graph is
+/// spliced in as JSON and laid out client-side, so it works offline.
+const GRAPH_TEMPLATE: &str = include_str!("graph_template.html");
+
+/// Memory types exported to Anki when no `--type` filter is given.
+pub const ANKI_DEFAULT_TYPES: &[MemoryType] = &[MemoryType::Learning, MemoryType::Insight];
+
+/// Escape text for an HTML-enabled Anki field: HTML entities, tabs (the column
+/// separator) and newlines (which would start a new note).
+fn anki_field(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('\t', "    ")
+        .replace("\r\n", "\n")
+        .replace('\n', "<br>")
+}
+
+/// Anki tags are space-separated, so spaces inside a tag become underscores.
+fn anki_tags(tags: &[String]) -> String {
+    tags.iter()
+        .map(|t| t.trim().replace(char::is_whitespace, "_"))
+        .filter(|t| !t.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Render memories as an Anki "Notes in Plain Text" import file:
+/// title → front, content → back, tags preserved (plus the memory type).
+pub fn to_anki(memories: &[Memory], deck: &str) -> String {
+    let mut out = String::from("#separator:tab\n#html:true\n");
+    out.push_str(&format!("#deck:{}\n", deck));
+    out.push_str("#tags column:3\n");
+    for memory in memories {
+        let mut tags = memory.metadata.tags.clone();
+        tags.push(memory.memory_type.to_string());
+        out.push_str(&format!(
+            "{}\t{}\t{}\n",
+            anki_field(&memory.title),
+            anki_field(&memory.content),
+            anki_tags(&tags)
+        ));
+    }
+    out
+}
+
+/// Graph memories ordered by creation time, so every export is stable.
+fn sorted_memories(graph: &MemoryGraph) -> Vec<&Memory> {
+    let mut memories: Vec<&Memory> = graph.memories.values().collect();
+    memories.sort_by(|a, b| a.created_at.cmp(&b.created_at).then(a.id.cmp(&b.id)));
+    memories
+}
+
+/// Relationships whose both ends are in the graph; file formats cannot
+/// reference nodes they do not declare.
+fn graph_edges(graph: &MemoryGraph) -> impl Iterator<Item = &MemoryRelationship> {
+    graph.relationships.iter().filter(|r| {
+        graph.memories.contains_key(&r.source_id) && graph.memories.contains_key(&r.target_id)
+    })
+}
+
+/// Graph as the `{root, nodes, edges}` JSON consumed by the HTML page.
+/// Nodes are ordered by creation time so the initial layout is stable.
+pub fn graph_to_json(graph: &MemoryGraph) -> serde_json::Value {
+    let memories = sorted_memories(graph);
+    let nodes: Vec<_> = memories
+        .iter()
+        .map(|m| {
+            json!({
+                "id": m.id,
+                "title": m.title,
+                "type": m.memory_type.to_string(),
+                "importance": m.metadata.importance,
+                "tags": m.metadata.tags,
+                "created_at": m.created_at.to_rfc3339(),
+                "content": m.content,
+            })
+        })
+        .collect();
+    let edges: Vec<_> = graph
+        .relationships
+        .iter()
+        .map(|r| {
+            json!({
+                "source": r.source_id,
+                "target": r.target_id,
+                "type": r.relationship_type.to_string(),
+                "strength": r.strength,
+                "description": r.description,
+            })
+        })
+        .collect();
+    json!({ "root": graph.root, "nodes": nodes, "edges": edges })
+}
+
+/// Render the graph as one interactive HTML file: force-directed layout, nodes
+/// coloured by memory type, edges labelled with their relationship.
+pub fn to_graph_html(graph: &MemoryGraph, title: &str) -> String {
+    // `<` only occurs inside JSON strings, where `\u003c` is equivalent; this
+    // keeps memory content from closing the <script> element.
+    let data = graph_to_json(graph).to_string().replace('<', "\\u003c");
+    let title = title
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;");
+    GRAPH_TEMPLATE
+        .replace("__OCTOBRAIN_TITLE__", &title)
+        .replace("__OCTOBRAIN_GRAPH__", &data)
+}
+
+/// Quote text as a Graphviz string.
+fn dot_string(text: &str) -> String {
+    let escaped = text
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace("\r\n", "\n")
+        .replace('\n', "\\n");
+    format!("\"{}\"", escaped)
+}
+
+/// Render the graph as Graphviz DOT: one box per memory labelled with its
+/// title and type, edges labelled with their relationship.
+pub fn to_graph_dot(graph: &MemoryGraph) -> String {
+    let mut out = String::from("digraph octobrain {\n    rankdir=LR;\n");
+    out.push_str("    node [shape=box, style=rounded];\n");
+    for memory in sorted_memories(graph) {
+        let label = format!("{}\n({})", memory.title, memory.memory_type);
+        let emphasis = if memory.id == graph.root {
+            ", penwidth=2"
+        } else {
+            ""
+        };
+        out.push_str(&format!(
+            "    {} [label={}{}];\n",
+            dot_string(&memory.id),
+            dot_string(&label),
+            emphasis
+        ));
+    }
+    for edge in graph_edges(graph) {
+        out.push_str(&format!(
+            "    {} -> {} [label={}];\n",
+            dot_string(&edge.source_id),
+            dot_string(&edge.target_id),
+            dot_string(&edge.relationship_type.to_string())
+        ));
+    }
+    out.push_str("}\n");
+    out
+}
+
+/// Mermaid labels are double-quoted; quotes and markup become entities.
+fn mermaid_label(text: &str) -> String {
+    text.replace('&', "#amp;")
+        .replace('"', "#quot;")
+        .replace('<', "#lt;")
+        .replace('>', "#gt;")
+        .replace(['\r', '\n'], " ")
+}
+
+/// Render the graph as a Mermaid flowchart (Obsidian, GitHub and most
+/// Markdown tools draw it inside a `mermaid` code block). Memory IDs are not
+/// valid Mermaid identifiers, so nodes are numbered `m0`, `m1`, ...
+pub fn to_graph_mermaid(graph: &MemoryGraph) -> String {
+    let memories = sorted_memories(graph);
+    let node_ids: HashMap<&str, String> = memories
+        .iter()
+        .enumerate()
+        .map(|(i, m)| (m.id.as_str(), format!("m{}", i)))
+        .collect();
+    let mut out = String::from("flowchart LR\n");
+    for memory in &memories {
+        out.push_str(&format!(
+            "    {}[\"{} ({})\"]\n",
+            node_ids[memory.id.as_str()],
+            mermaid_label(&memory.title),
+            memory.memory_type
+        ));
+    }
+    for edge in graph_edges(graph) {
+        out.push_str(&format!(
+            "    {} -->|\"{}\"| {}\n",
+            node_ids[edge.source_id.as_str()],
+            mermaid_label(&edge.relationship_type.to_string()),
+            node_ids[edge.target_id.as_str()]
+        ));
+    }
+    if let Some(root) = node_ids.get(graph.root.as_str()) {
+        out.push_str(&format!("    style {} stroke-width:3px\n", root));
+    }
+    out
+}
+
+fn xml_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+/// Render the graph as GraphML (Gephi, yEd, Cytoscape), with memory and
+/// relationship fields as typed node and edge attributes.
+pub fn to_graphml(graph: &MemoryGraph) -> String {
+    let mut out = String::from(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+         <graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">\n",
+    );
+    for (id, target, kind) in [
+        ("title", "node", "string"),
+        ("type", "node", "string"),
+        ("importance", "node", "double"),
+        ("tags", "node", "string"),
+        ("created_at", "node", "string"),
+        ("content", "node", "string"),
+        ("relationship", "edge", "string"),
+        ("strength", "edge", "double"),
+        ("description", "edge", "string"),
+    ] {
+        out.push_str(&format!(
+            "  <key id=\"{id}\" for=\"{target}\" attr.name=\"{id}\" attr.type=\"{kind}\"/>\n"
+        ));
+    }
+    out.push_str("  <graph id=\"octobrain\" edgedefault=\"directed\">\n");
+    let data = |key: &str, value: &str| {
+        format!("      <data key=\"{}\">{}</data>\n", key, xml_escape(value))
+    };
+    for memory in sorted_memories(graph) {
+        out.push_str(&format!("    <node id=\"{}\">\n", xml_escape(&memory.id)));
+        out.push_str(&data("title", &memory.title));
+        out.push_str(&data("type", &memory.memory_type.to_string()));
+        out.push_str(&data("importance", &memory.metadata.importance.to_string()));
+        out.push_str(&data("tags", &memory.metadata.tags.join(",")));
+        out.push_str(&data("created_at", &memory.created_at.to_rfc3339()));
+        out.push_str(&data("content", &memory.content));
+        out.push_str("    </node>\n");
+    }
+    for edge in graph_edges(graph) {
+        out.push_str(&format!(
+            "    <edge id=\"{}\" source=\"{}\" target=\"{}\">\n",
+            xml_escape(&edge.id),
+            xml_escape(&edge.source_id),
+            xml_escape(&edge.target_id)
+        ));
+        out.push_str(&data("relationship", &edge.relationship_type.to_string()));
+        out.push_str(&data("strength", &edge.strength.to_string()));
+        out.push_str(&data("description", &edge.description));
+        out.push_str("    </edge>\n");
+    }
+    out.push_str("  </graph>\n</graphml>\n");
+    out
+}
+
+/// A relationship seen from one end: the memory at the other end and whether
+/// the relationship points away from this one.
+type TreeEdge<'a> = (&'a MemoryRelationship, &'a str, bool);
+
+/// Render the graph as an indented tree from its root (`memory graph`). Each
+/// memory appears once, under the neighbour through which it is the fewest
+/// hops from the root; `→` marks relationships from the parent, `←` those
+/// pointing at it.
+pub fn to_graph_tree(graph: &MemoryGraph) -> String {
+    let Some(root) = graph.memories.get(&graph.root) else {
+        return String::new();
+    };
+    let mut neighbours: HashMap<&str, Vec<TreeEdge>> = HashMap::new();
+    for edge in graph_edges(graph) {
+        neighbours
+            .entry(edge.source_id.as_str())
+            .or_default()
+            .push((edge, edge.target_id.as_str(), true));
+        neighbours
+            .entry(edge.target_id.as_str())
+            .or_default()
+            .push((edge, edge.source_id.as_str(), false));
+    }
+
+    // Breadth-first, so every memory hangs under a shortest path
+    let mut children: HashMap<&str, Vec<TreeEdge>> = HashMap::new();
+    let mut seen = HashSet::from([root.id.as_str()]);
+    let mut queue = VecDeque::from([root.id.as_str()]);
+    while let Some(id) = queue.pop_front() {
+        let mut next = neighbours.remove(id).unwrap_or_default();
+        next.sort_by(|a, b| {
+            let title = |id: &str| graph.memories[id].title.as_str();
+            title(a.1).cmp(title(b.1)).then(a.1.cmp(b.1))
+        });
+        for (edge, other, outgoing) in next {
+            if seen.insert(other) {
+                children
+                    .entry(id)
+                    .or_default()
+                    .push((edge, other, outgoing));
+                queue.push_back(other);
+            }
+        }
+    }
+
+    let mut out = format!("{}\n", tree_label(root));
+    push_subtree(graph, &children, &root.id, "", &mut out);
+    out
+}
+
+fn tree_label(memory: &Memory) -> String {
+    format!("{} ({}) [{}]", memory.title, memory.memory_type, memory.id)
+}
+
+fn push_subtree(
+    graph: &MemoryGraph,
+    children: &HashMap<&str, Vec<TreeEdge>>,
+    id: &str,
+    prefix: &str,
+    out: &mut String,
+) {
+    let Some(edges) = children.get(id) else {
+        return;
+    };
+    for (i, (edge, child, outgoing)) in edges.iter().enumerate() {
+        let last = i + 1 == edges.len();
+        out.push_str(&format!(
+            "{}{}{} {} {}\n",
+            prefix,
+            if last { "└── " } else { "├── " },
+            if *outgoing { "→" } else { "←" },
+            edge.relationship_type,
+            tree_label(&graph.memories[*child])
+        ));
+        let nested = format!("{}{}", prefix, if last { "    " } else { "│   " });
+        push_subtree(graph, children, child, &nested, out);
+    }
+}
@@ -0,0 +1,191 @@
+// Copyright 2026 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#[cfg(test)]
+mod tests {
+    use super::super::export::{
+        graph_to_json, to_anki, to_graph_dot, to_graph_html, to_graph_mermaid, to_graph_tree,
+        to_graphml,
+    };
+    use super::super::types::{
+        Memory, MemoryGraph, MemoryRelationship, MemoryType, RelationshipType,
+    };
+
+    #[test]
+    fn test_anki_headers_and_note_line() {
+        let mut memory = Memory::new(
+            MemoryType::Learning,
+            "Borrow checker & closures".to_string(),
+            "Closures capture by reference.\nUse `move` for threads.\tDone".to_string(),
+            None,
+        );
+        memory.metadata.tags = vec!["rust".to_string(), "hard parts".to_string()];
+
+        let deck = to_anki(&[memory], "Octobrain::Rust");
+        let lines: Vec<&str> = deck.lines().collect();
+        assert_eq!(lines[0], "#separator:tab");
+        assert_eq!(lines[2], "#deck:Octobrain::Rust");
+        assert_eq!(lines.len(), 5);
+
+        let fields: Vec<&str> = lines[4].split('\t').collect();
+        assert_eq!(fields.len(), 3);
+        assert_eq!(fields[0], "Borrow checker &amp; closures");
+        assert_eq!(
+            fields[1],
+            "Closures capture by reference.<br>Use `move` for threads.    Done"
+        );
+        assert_eq!(fields[2], "rust hard_parts learning");
+    }
+
+    fn graph() -> MemoryGraph {
+        let decision = Memory::new(
+            MemoryType::Decision,
+            "Use LanceDB".to_string(),
+            "Embedded </script><script>alert(1)</script> store".to_string(),
+            None,
+        );
+        let mut bug = Memory::new(
+            MemoryType::BugFix,
+            "Index rebuild race".to_string(),
+            "Fixed with a write lock".to_string(),
+            None,
+        );
+        bug.created_at = decision.created_at + chrono::Duration::seconds(1);
+        let relationship = MemoryRelationship {
+            id: "rel".to_string(),
+            source_id: bug.id.clone(),
+            target_id: decision.id.clone(),
+            relationship_type: RelationshipType::DependsOn,
+            strength: 0.8,
+            description: String::new(),
+            created_at: bug.created_at,
+        };
+        MemoryGraph {
+            root: decision.id.clone(),
+            memories: [decision, bug]
+                .into_iter()
+                .map(|m| (m.id.clone(), m))
+                .collect(),
+            relationships: vec![relationship],
+        }
+    }
+
+    #[test]
+    fn test_graph_json_nodes_and_edges() {
+        let json = graph_to_json(&graph());
+        let nodes = json["nodes"].as_array().unwrap();
+        assert_eq!(nodes.len(), 2);
+        assert_eq!(nodes[0]["type"], "decision");
+        assert_eq!(nodes[1]["type"], "bug_fix");
+        assert_eq!(json["root"], nodes[0]["id"]);
+        assert_eq!(json["edges"][0]["type"], "depends_on");
+        assert_eq!(json["edges"][0]["source"], nodes[1]["id"]);
+    }
+
+    #[test]
+    fn test_graph_html_embeds_data_safely() {
+        let graph = graph();
+        let html = to_graph_html(&graph, "Graph <of> things");
+        assert!(html.contains("<title>Graph &lt;of&gt; things</title>"));
+        assert!(!html.contains("__OCTOBRAIN_"));
+        // Memory content must not be able to terminate the data element
+        assert_eq!(html.matches("</script>").count(), 2);
+        assert!(html.contains("\\u003c/script>"));
+
+        let start = html.find(r#"id="graph-data">"#).unwrap() + r#"id="graph-data">"#.len();
+        let end = start + html[start..].find("</script>").unwrap();
+        let embedded: serde_json::Value = serde_json::from_str(&html[start..end]).unwrap();
+        assert_eq!(embedded, graph_to_json(&graph));
+    }
+
+    #[test]
+    fn test_graph_dot_quotes_labels() {
+        let mut graph = graph();
+        graph.memories.get_mut(&graph.root).unwrap().title = "Use \"LanceDB\"".to_string();
+        let dot = to_graph_dot(&graph);
+        assert!(dot.starts_with("digraph octobrain {"));
+        assert!(dot.contains(r#"label="Use \"LanceDB\"\n(decision)", penwidth=2"#));
+        let edge = &graph.relationships[0];
+        assert!(dot.contains(&format!(
+            "\"{}\" -> \"{}\" [label=\"depends_on\"];",
+            edge.source_id, edge.target_id
+        )));
+        assert!(dot.trim_end().ends_with('}'));
+    }
+
+    #[test]
+    fn test_graph_mermaid_numbers_nodes() {
+        let mut graph = graph();
+        // Edges to memories outside the graph are dropped
+        let mut dangling = graph.relationships[0].clone();
+        dangling.target_id = "elsewhere".to_string();
+        graph.relationships.push(dangling);
+
+        let mermaid = to_graph_mermaid(&graph);
+        let lines: Vec<&str> = mermaid.lines().collect();
+        assert_eq!(lines[0], "flowchart LR");
+        assert_eq!(lines[1], r#"    m0["Use LanceDB (decision)"]"#);
+        assert_eq!(lines[2], r#"    m1["Index rebuild race (bug_fix)"]"#);
+        assert_eq!(lines[3], r#"    m1 -->|"depends_on"| m0"#);
+        assert_eq!(lines[4], "    style m0 stroke-width:3px");
+        assert_eq!(lines.len(), 5);
+    }
+
+    #[test]
+    fn test_graphml_escapes_content() {
+        let graphml = to_graphml(&graph());
+        assert!(graphml.starts_with("<?xml"));
+        assert_eq!(graphml.matches("<node id=").count(), 2);
+        assert_eq!(graphml.matches("<edge id=\"rel\"").count(), 1);
+        assert!(graphml.contains("&lt;/script&gt;&lt;script&gt;alert(1)"));
+        assert!(!graphml.contains("</script>"));
+        assert!(graphml.contains(r#"<data key="relationship">depends_on</data>"#));
+    }
+
+    #[test]
+    fn test_graph_tree_hangs_memories_under_shortest_path() {
+        let mut graph = graph();
+        let root = graph.root.clone();
+        let bug = graph.relationships[0].source_id.clone();
+        let note = Memory::new(
+            MemoryType::Learning,
+            "Lock files".to_string(),
+            String::new(),
+            None,
+        );
+        // root ← bug, bug → note, and a second path root → note
+        for (id, source, target) in [("r2", &bug, &note.id), ("r3", &root, &note.id)] {
+            let mut relationship = graph.relationships[0].clone();
+            relationship.id = id.to_string();
+            relationship.source_id = source.clone();
+            relationship.target_id = target.clone();
+            relationship.relationship_type = RelationshipType::RelatedTo;
+            graph.relationships.push(relationship);
+        }
+        graph.memories.insert(note.id.clone(), note.clone());
+
+        let tree = to_graph_tree(&graph);
+        let lines: Vec<&str> = tree.lines().collect();
+        assert_eq!(lines.len(), 3, "{}", tree);
+        assert_eq!(lines[0], format!("Use LanceDB (decision) [{}]", root));
+        assert_eq!(
+            lines[1],
+            format!("├── ← depends_on Index rebuild race (bug_fix) [{}]", bug)
+        );
+        assert_eq!(
+            lines[2],
+            format!("└── → related_to Lock files (learning) [{}]", note.id)
+        );
+    }
+}
@@ -0,0 +1,160 @@
+// Copyright 2026 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Relevance feedback on search results.
+//!
+//! `memory feedback <id> --helpful|--unhelpful` records a vote for a memory
+//! against the query that surfaced it. Later searches scale each result's
+//! relevance by the net votes, weighted by how much the voted query shares
+//! words with the current one. Votes are per project and live in
+//! `feedback.json` next to the database.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+use super::search_scoring::keyword_terms;
+use super::types::{sort_by_relevance_desc, MemorySearchResult, SearchSignal};
+
+/// Votes kept per memory; the oldest are dropped first.
+pub const MAX_ENTRIES_PER_MEMORY: usize = 50;
+
+/// Relevance change per net vote on an identical query.
+const STEP_PER_VOTE: f32 = 0.1;
+
+/// Weight of a vote whose query shares no words with the current one.
+const UNRELATED_QUERY_WEIGHT: f32 = 0.25;
+
+/// Bounds of the relevance multiplier.
+const MIN_FACTOR: f32 = 0.5;
+const MAX_FACTOR: f32 = 1.5;
+
+/// One vote on a memory
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeedbackEntry {
+    /// Query the memory was returned for; empty when unknown
+    pub query: String,
+    pub helpful: bool,
+    pub at: DateTime<Utc>,
+}
+
+/// Feedback of every project, persisted as JSON (`storage::get_feedback_path`).
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct FeedbackBook {
+    /// project label → memory ID → votes, oldest first
+    pub projects: HashMap<String, HashMap<String, Vec<FeedbackEntry>>>,
+}
+
+impl FeedbackBook {
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let raw = std::fs::read_to_string(path)
+            .with_context(|| format!("Cannot read feedback '{}'", path.display()))?;
+        serde_json::from_str(&raw)
+            .with_context(|| format!("Feedback file '{}' is corrupt", path.display()))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        std::fs::write(path, serde_json::to_string_pretty(self)?)
+            .with_context(|| format!("Cannot write feedback '{}'", path.display()))
+    }
+
+    /// Record a vote, dropping the oldest beyond `MAX_ENTRIES_PER_MEMORY`
+    pub fn record(&mut self, project: &str, memory_id: &str, entry: FeedbackEntry) {
+        let entries = self
+            .projects
+            .entry(project.to_string())
+            .or_default()
+            .entry(memory_id.to_string())
+            .or_default();
+        entries.push(entry);
+        if entries.len() > MAX_ENTRIES_PER_MEMORY {
+            let excess = entries.len() - MAX_ENTRIES_PER_MEMORY;
+            entries.drain(..excess);
+        }
+    }
+
+    pub fn entries(&self, project: &str, memory_id: &str) -> &[FeedbackEntry] {
+        self.projects
+            .get(project)
+            .and_then(|memories| memories.get(memory_id))
+            .map(|e| e.as_slice())
+            .unwrap_or(&[])
+    }
+}
+
+/// Share of words the two queries have in common (Jaccard), or `None` when
+/// either has no words.
+fn query_overlap(a: &HashSet<String>, b: &str) -> Option<f32> {
+    let b: HashSet<String> = keyword_terms(b).into_iter().collect();
+    if a.is_empty() || b.is_empty() {
+        return None;
+    }
+    let shared = a.intersection(&b).count() as f32;
+    Some(shared / a.union(&b).count() as f32)
+}
+
+/// Relevance multiplier from `entries` for `query`: each helpful vote adds and
+/// each unhelpful vote subtracts `STEP_PER_VOTE`, scaled by query overlap (at
+/// least `UNRELATED_QUERY_WEIGHT`). Bounded to `[MIN_FACTOR, MAX_FACTOR]`.
+pub fn feedback_factor(entries: &[FeedbackEntry], query: &str) -> f32 {
+    let terms: HashSet<String> = keyword_terms(query).into_iter().collect();
+    let net: f32 = entries
+        .iter()
+        .map(|e| {
+            let weight = query_overlap(&terms, &e.query)
+                .unwrap_or(0.0)
+                .max(UNRELATED_QUERY_WEIGHT);
+            if e.helpful {
+                weight
+            } else {
+                -weight
+            }
+        })
+        .sum();
+    (1.0 + STEP_PER_VOTE * net).clamp(MIN_FACTOR, MAX_FACTOR)
+}
+
+/// Scale each result by its feedback for `query` and re-sort by relevance
+pub fn apply_feedback(
+    results: &mut [MemorySearchResult],
+    book: &FeedbackBook,
+    project: &str,
+    query: &str,
+) {
+    let mut changed = false;
+    for result in results.iter_mut() {
+        let entries = book.entries(project, &result.memory.id);
+        if entries.is_empty() {
+            continue;
+        }
+        let factor = feedback_factor(entries, query);
+        if (factor - 1.0).abs() < f32::EPSILON {
+            continue;
+        }
+        result.relevance_score = (result.relevance_score * factor).min(1.0);
+        result.selection_reason = format!("{} (feedback ×{:.2})", result.selection_reason, factor);
+        if !result.signals.is_empty() {
+            result.signals.push(SearchSignal::Feedback { factor });
+        }
+        changed = true;
+    }
+    if changed {
+        sort_by_relevance_desc(results);
+    }
+}
@@ -0,0 +1,117 @@
+// Copyright 2026 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#[cfg(test)]
+mod tests {
+    use super::super::feedback::{
+        apply_feedback, feedback_factor, FeedbackBook, FeedbackEntry, MAX_ENTRIES_PER_MEMORY,
+    };
+    use super::super::types::{Memory, MemorySearchResult, MemoryType};
+
+    fn vote(query: &str, helpful: bool) -> FeedbackEntry {
+        FeedbackEntry {
+            query: query.to_string(),
+            helpful,
+            at: chrono::Utc::now(),
+        }
+    }
+
+    fn result(title: &str, score: f32) -> MemorySearchResult {
+        MemorySearchResult {
+            memory: Memory::new(
+                MemoryType::Learning,
+                title.to_string(),
+                "content".to_string(),
+                None,
+            ),
+            relevance_score: score,
+            selection_reason: "Semantic match".to_string(),
+            signals: Vec::new(),
+        }
+    }
+
+    fn temp_path() -> std::path::PathBuf {
+        std::env::temp_dir().join(format!(
+            "octobrain_feedback_test_{}.json",
+            uuid::Uuid::new_v4()
+        ))
+    }
+
+    #[test]
+    fn test_factor_follows_votes_and_query_similarity() {
+        assert_eq!(feedback_factor(&[], "auth tokens"), 1.0);
+
+        let helpful = [vote("auth tokens", true)];
+        let same = feedback_factor(&helpful, "Auth tokens");
+        let related = feedback_factor(&helpful, "auth flow");
+        let unrelated = feedback_factor(&helpful, "deploy schedule");
+        assert!((same - 1.1).abs() < 1e-6);
+        assert!(same > related && related > unrelated && unrelated > 1.0);
+
+        let unhelpful = [vote("auth tokens", false)];
+        assert!((feedback_factor(&unhelpful, "auth tokens") - 0.9).abs() < 1e-6);
+        // Opposite votes on the same query cancel out
+        let mixed = [vote("auth tokens", true), vote("auth tokens", false)];
+        assert!((feedback_factor(&mixed, "auth tokens") - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_factor_is_bounded() {
+        let many_up: Vec<_> = (0..20).map(|_| vote("cache", true)).collect();
+        let many_down: Vec<_> = (0..20).map(|_| vote("cache", false)).collect();
+        assert_eq!(feedback_factor(&many_up, "cache"), 1.5);
+        assert_eq!(feedback_factor(&many_down, "cache"), 0.5);
+    }
+
+    #[test]
+    fn test_apply_feedback_reorders_results() {
+        let mut results = vec![result("noisy", 0.8), result("useful", 0.75)];
+        let mut book = FeedbackBook::default();
+        for _ in 0..3 {
+            book.record("proj", &results[0].memory.id, vote("retry policy", false));
+        }
+        book.record("proj", &results[1].memory.id, vote("retry policy", true));
+
+        // Other projects' feedback is ignored
+        let mut untouched = results.clone();
+        apply_feedback(&mut untouched, &book, "other", "retry policy");
+        assert_eq!(untouched[0].memory.title, "noisy");
+
+        apply_feedback(&mut results, &book, "proj", "retry policy");
+        assert_eq!(results[0].memory.title, "useful");
+        assert!(results[0].selection_reason.contains("feedback"));
+        assert!(results[1].relevance_score < 0.8);
+    }
+
+    #[test]
+    fn test_book_caps_entries_and_round_trips() {
+        let path = temp_path();
+        let mut book = FeedbackBook::load(&path).unwrap();
+        for i in 0..MAX_ENTRIES_PER_MEMORY + 5 {
+            book.record("proj", "id-1", vote(&format!("query {}", i), true));
+        }
+        book.save(&path).unwrap();
+
+        let loaded = FeedbackBook::load(&path).unwrap();
+        let entries = loaded.entries("proj", "id-1");
+        assert_eq!(entries.len(), MAX_ENTRIES_PER_MEMORY);
+        assert_eq!(entries[0].query, "query 5");
+        assert!(loaded.entries("other", "id-1").is_empty());
+        std::fs::remove_file(&path).ok();
+
+        std::fs::write(&path, "{not json").unwrap();
+        assert!(FeedbackBook::load(&path).is_err());
+        std::fs::remove_file(&path).ok();
+    }
+}
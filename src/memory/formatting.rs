@@ -14,7 +14,287 @@
 
 // Shared memory formatting functions for CLI and MCP
 
-use crate::memory::MemorySearchResult;
+use serde::Serialize;
+
+use crate::memory::manager::MemoryStats;
+use crate::memory::types::SearchSignal;
+use crate::memory::{Memory, MemorySearchResult};
+
+/// Tabular output formats (`--format csv|yaml|md`) for piping results into
+/// spreadsheets or pasting them into docs
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TableFormat {
+    Csv,
+    Yaml,
+    /// Markdown table
+    Markdown,
+}
+
+impl TableFormat {
+    /// `None` for the formats that are not tabular (text, json, compact, ...)
+    pub fn parse(format: &str) -> Option<Self> {
+        match format {
+            "csv" => Some(Self::Csv),
+            "yaml" | "yml" => Some(Self::Yaml),
+            "md" => Some(Self::Markdown),
+            _ => None,
+        }
+    }
+}
+
+/// Named columns of text cells
+#[derive(Debug, Clone, PartialEq)]
+pub struct Table {
+    pub headers: Vec<&'static str>,
+    pub rows: Vec<Vec<String>>,
+    /// Typed form of the rows for YAML, where text cells would lose numbers
+    /// and lists
+    records: Option<serde_yaml::Value>,
+}
+
+impl Table {
+    pub fn new(headers: Vec<&'static str>) -> Self {
+        Self {
+            headers,
+            rows: Vec::new(),
+            records: None,
+        }
+    }
+
+    /// Render YAML from `records` instead of the text cells
+    fn with_records<T: Serialize>(mut self, records: &[T]) -> Self {
+        self.records = serde_yaml::to_value(records).ok();
+        self
+    }
+
+    pub fn push(&mut self, row: Vec<String>) {
+        debug_assert_eq!(row.len(), self.headers.len());
+        self.rows.push(row);
+    }
+
+    pub fn render(&self, format: TableFormat) -> String {
+        match format {
+            TableFormat::Csv => self.to_csv(),
+            TableFormat::Yaml => self.to_yaml(),
+            TableFormat::Markdown => self.to_markdown(),
+        }
+    }
+
+    /// RFC 4180: fields with commas, quotes or line breaks are quoted
+    pub fn to_csv(&self) -> String {
+        let line = |cells: &mut dyn Iterator<Item = &str>| {
+            cells
+                .map(|cell| {
+                    if cell.contains([',', '"', '\n', '\r']) {
+                        format!("\"{}\"", cell.replace('"', "\"\""))
+                    } else {
+                        cell.to_string()
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join(",")
+        };
+        let mut output = line(&mut self.headers.iter().copied());
+        output.push_str("\r\n");
+        for row in &self.rows {
+            output.push_str(&line(&mut row.iter().map(String::as_str)));
+            output.push_str("\r\n");
+        }
+        output
+    }
+
+    /// A list with one mapping per row; memory listings read back as a
+    /// `memory import-batch` file
+    pub fn to_yaml(&self) -> String {
+        let yaml = match &self.records {
+            Some(records) => serde_yaml::to_string(records),
+            None => serde_yaml::to_string(
+                &self
+                    .rows
+                    .iter()
+                    .map(|row| {
+                        self.headers
+                            .iter()
+                            .zip(row)
+                            .map(|(header, cell)| ((*header).into(), cell.as_str().into()))
+                            .collect::<serde_yaml::Mapping>()
+                    })
+                    .collect::<Vec<_>>(),
+            ),
+        };
+        yaml.unwrap_or_default()
+    }
+
+    /// Pipes are escaped and line breaks become `<br>` so every row stays on one line
+    pub fn to_markdown(&self) -> String {
+        let line = |cells: Vec<String>| format!("| {} |\n", cells.join(" | "));
+        let mut output = line(self.headers.iter().map(|h| h.to_string()).collect());
+        output.push_str(&line(
+            self.headers.iter().map(|_| "---".to_string()).collect(),
+        ));
+        for row in &self.rows {
+            output.push_str(&line(
+                row.iter()
+                    .map(|cell| {
+                        cell.replace('|', "\\|")
+                            .replace("\r\n", "<br>")
+                            .replace('\n', "<br>")
+                    })
+                    .collect(),
+            ));
+        }
+        output
+    }
+}
+
+const MEMORY_COLUMNS: [&str; 8] = [
+    "id",
+    "type",
+    "title",
+    "importance",
+    "created",
+    "tags",
+    "files",
+    "content",
+];
+
+fn memory_cells(memory: &Memory) -> Vec<String> {
+    vec![
+        memory.id.clone(),
+        memory.memory_type.to_string(),
+        memory.title.clone(),
+        format!("{:.2}", memory.metadata.importance),
+        memory.created_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+        memory.metadata.tags.join(", "),
+        memory.metadata.related_files.join(", "),
+        memory.content.clone(),
+    ]
+}
+
+/// YAML form of a memory row, keyed like a batch entry (`memory::batch`)
+#[derive(Serialize)]
+struct MemoryRecord<'a> {
+    id: &'a str,
+    #[serde(rename = "type")]
+    memory_type: String,
+    title: &'a str,
+    importance: f32,
+    created: String,
+    tags: &'a [String],
+    files: &'a [String],
+    content: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    score: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    why: Option<&'a str>,
+}
+
+impl<'a> MemoryRecord<'a> {
+    fn new(memory: &'a Memory) -> Self {
+        Self {
+            id: &memory.id,
+            memory_type: memory.memory_type.to_string(),
+            title: &memory.title,
+            importance: memory.metadata.importance,
+            created: memory.created_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+            tags: &memory.metadata.tags,
+            files: &memory.metadata.related_files,
+            content: &memory.content,
+            score: None,
+            why: None,
+        }
+    }
+}
+
+/// One row per memory
+pub fn memories_table(memories: &[Memory]) -> Table {
+    let mut table = Table::new(MEMORY_COLUMNS.to_vec());
+    for memory in memories {
+        table.push(memory_cells(memory));
+    }
+    let records: Vec<MemoryRecord> = memories.iter().map(MemoryRecord::new).collect();
+    table.with_records(&records)
+}
+
+/// One row per search result, with its score and why it was selected
+pub fn search_results_table(results: &[MemorySearchResult]) -> Table {
+    let mut headers = MEMORY_COLUMNS.to_vec();
+    headers.extend(["score", "why"]);
+    let mut table = Table::new(headers);
+    for result in results {
+        let mut row = memory_cells(&result.memory);
+        row.push(format!("{:.2}", result.relevance_score));
+        row.push(result.selection_reason.clone());
+        table.push(row);
+    }
+    let records: Vec<MemoryRecord> = results
+        .iter()
+        .map(|result| MemoryRecord {
+            score: Some(result.relevance_score),
+            why: Some(&result.selection_reason),
+            ..MemoryRecord::new(&result.memory)
+        })
+        .collect();
+    table.with_records(&records)
+}
+
+/// Memory statistics as metric/value rows
+pub fn stats_table(stats: &MemoryStats) -> Table {
+    let mut table = Table::new(vec!["metric", "value"]);
+    let mut add = |metric: String, value: String| table.push(vec![metric, value]);
+    add("total_memories".into(), stats.total_memories.to_string());
+    add("recent_memories".into(), stats.recent_count.to_string());
+    add("relationships".into(), stats.relationship_count.to_string());
+    if let Some(bytes) = stats.db_size_bytes {
+        add("db_size_bytes".into(), bytes.to_string());
+    }
+    if let Some(commit) = &stats.git_commit {
+        add("git_commit".into(), commit.clone());
+    }
+    if !stats.projects.is_empty() {
+        add("projects".into(), stats.projects.join(", "));
+    }
+    if !stats.roles.is_empty() {
+        add("roles".into(), stats.roles.join(", "));
+    }
+    let mut types: Vec<_> = stats.type_counts.iter().collect();
+    types.sort_by(|a, b| b.1.cmp(a.1).then(a.0.cmp(b.0)));
+    for (memory_type, count) in types {
+        add(format!("type.{}", memory_type), count.to_string());
+    }
+    for (tag, count) in stats.top_tags(10) {
+        add(format!("tag.{}", tag), count.to_string());
+    }
+    let importance = &stats.importance;
+    for (i, count) in importance.buckets.iter().enumerate() {
+        add(
+            format!(
+                "importance.{:.1}-{:.1}",
+                i as f32 / 10.0,
+                (i + 1) as f32 / 10.0
+            ),
+            count.to_string(),
+        );
+    }
+    add(
+        "importance.average_base".into(),
+        format!("{:.2}", importance.average_base),
+    );
+    add(
+        "importance.average_current".into(),
+        format!("{:.2}", importance.average_current),
+    );
+    add("cleanup.due".into(), importance.due_for_cleanup.to_string());
+    add(
+        "cleanup.below_threshold_not_yet_due".into(),
+        importance.below_threshold_not_yet_due.to_string(),
+    );
+    add(
+        "cleanup.near_threshold".into(),
+        importance.near_threshold.to_string(),
+    );
+    table
+}
 
 /// Format memory search results as text (token-efficient, for MCP)
 pub fn format_memories_as_text(results: &[MemorySearchResult]) -> String {
@@ -43,6 +323,10 @@ pub fn format_memories_as_text(results: &[MemorySearchResult]) -> String {
             result.memory.created_at.format("%Y-%m-%d %H:%M:%S UTC")
         ));
 
+        if let Some(author) = &result.memory.metadata.created_by {
+            output.push_str(&format!("Author: {}\n", author));
+        }
+
         if !result.memory.metadata.tags.is_empty() {
             output.push_str(&format!(
                 "Tags: {}\n",
@@ -69,12 +353,80 @@ pub fn format_memories_as_text(results: &[MemorySearchResult]) -> String {
             output.push('\n');
         }
 
-        output.push_str(&format!("Why: {}\n\n", result.selection_reason));
+        output.push_str(&format!("Why: {}\n", result.selection_reason));
+        if !result.signals.is_empty() {
+            output.push_str("Score breakdown:\n");
+            for line in explain_signals(&result.signals) {
+                output.push_str(&format!("  - {}\n", line));
+            }
+        }
+        output.push('\n');
     }
 
     output
 }
 
+/// One line per score signal (`remember --explain`)
+pub fn explain_signals(signals: &[SearchSignal]) -> Vec<String> {
+    signals
+        .iter()
+        .map(|signal| match signal {
+            SearchSignal::Vector { similarity } => format!("vector: similarity {:.3}", similarity),
+            SearchSignal::Fusion {
+                score,
+                weight,
+                vector_rank,
+                keyword_rank,
+            } => {
+                let rank = |r: &Option<usize>| r.map_or("-".to_string(), |r| format!("#{}", r));
+                let ranks = if vector_rank.is_some() || keyword_rank.is_some() {
+                    format!(
+                        " (vector {}, keyword {})",
+                        rank(vector_rank),
+                        rank(keyword_rank)
+                    )
+                } else {
+                    String::new()
+                };
+                format!("fusion: {:.3} × weight {:.2}{}", score, weight, ranks)
+            }
+            SearchSignal::Keyword { matches } if matches.is_empty() => {
+                "keyword: no query words matched".to_string()
+            }
+            SearchSignal::Keyword { matches } => format!(
+                "keyword: {}",
+                matches
+                    .iter()
+                    .map(|m| format!("{} ×{}", m.term, m.count))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            SearchSignal::Recency { score, weight } => {
+                format!("recency: {:.3} × weight {:.2}", score, weight)
+            }
+            SearchSignal::Importance {
+                stored,
+                current,
+                weight,
+            } => {
+                let applied = match weight {
+                    Some(weight) => format!("× weight {:.2}", weight),
+                    None => "multiplies the score".to_string(),
+                };
+                format!(
+                    "importance: {:.3} (stored {:.2}, after decay) {}",
+                    current, stored, applied
+                )
+            }
+            SearchSignal::Trust { multiplier } => format!("trust: ×{:.2}", multiplier),
+            SearchSignal::Reranker { score, previous } => {
+                format!("reranker: {:.3} (replaced {:.3})", score, previous)
+            }
+            SearchSignal::Feedback { factor } => format!("feedback: ×{:.2}", factor),
+        })
+        .collect()
+}
+
 /// Format memory search results as markdown
 pub fn format_memories_as_markdown(results: &[MemorySearchResult]) -> String {
     if results.is_empty() {
@@ -135,6 +487,10 @@ pub fn format_memories_as_markdown(results: &[MemorySearchResult]) -> String {
 
 /// Format plain Memory objects for CLI (used by recent, by-type, etc.)
 pub fn format_plain_memories_for_cli(memories: &[crate::memory::Memory], format: &str) {
+    if let Some(table_format) = TableFormat::parse(format) {
+        print!("{}", memories_table(memories).render(table_format));
+        return;
+    }
     match format {
         "json" => {
             println!("{}", serde_json::to_string_pretty(memories).unwrap());
@@ -147,11 +503,12 @@ pub fn format_plain_memories_for_cli(memories: &[crate::memory::Memory], format:
                     memory: m.clone(),
                     relevance_score: 1.0, // No relevance score for plain memories
                     selection_reason: "Listed by query".to_string(),
+                    signals: Vec::new(),
                 })
                 .collect();
             print!("{}", format_memories_as_text(&fake_results));
         }
-        "md" | "markdown" => {
+        "markdown" => {
             // Convert to search results format for consistent markdown formatting
             let fake_results: Vec<MemorySearchResult> = memories
                 .iter()
@@ -159,6 +516,7 @@ pub fn format_plain_memories_for_cli(memories: &[crate::memory::Memory], format:
                     memory: m.clone(),
                     relevance_score: 1.0, // No relevance score for plain memories
                     selection_reason: "Listed by query".to_string(),
+                    signals: Vec::new(),
                 })
                 .collect();
             print!("{}", format_memories_as_markdown(&fake_results));
@@ -192,6 +550,10 @@ pub fn format_plain_memories_for_cli(memories: &[crate::memory::Memory], format:
 
 /// Format memory search results for CLI (with emojis and formatting)
 pub fn format_memories_for_cli(results: &[MemorySearchResult], format: &str) {
+    if let Some(table_format) = TableFormat::parse(format) {
+        print!("{}", search_results_table(results).render(table_format));
+        return;
+    }
     match format {
         "json" => {
             println!("{}", serde_json::to_string_pretty(results).unwrap());
@@ -200,7 +562,7 @@ pub fn format_memories_for_cli(results: &[MemorySearchResult], format: &str) {
             // Use token-efficient text format
             print!("{}", format_memories_as_text(results));
         }
-        "md" | "markdown" => {
+        "markdown" => {
             // Use markdown format
             print!("{}", format_memories_as_markdown(results));
         }
@@ -0,0 +1,187 @@
+// Copyright 2026 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#[cfg(test)]
+mod tests {
+    use super::super::batch::parse_yaml;
+    use super::super::formatting::{
+        explain_signals, memories_table, search_results_table, Table, TableFormat,
+    };
+    use super::super::types::{KeywordMatch, Memory, MemorySearchResult, MemoryType, SearchSignal};
+
+    fn table() -> Table {
+        let mut table = Table::new(vec!["title", "note"]);
+        table.push(vec!["Use sqlite".to_string(), "one, two".to_string()]);
+        table.push(vec![
+            "Say \"hi\"".to_string(),
+            "line one\nline | two".to_string(),
+        ]);
+        table
+    }
+
+    #[test]
+    fn test_parse_table_formats() {
+        assert_eq!(TableFormat::parse("csv"), Some(TableFormat::Csv));
+        assert_eq!(TableFormat::parse("yaml"), Some(TableFormat::Yaml));
+        assert_eq!(TableFormat::parse("yml"), Some(TableFormat::Yaml));
+        assert_eq!(TableFormat::parse("md"), Some(TableFormat::Markdown));
+        assert_eq!(TableFormat::parse("json"), None);
+        assert_eq!(TableFormat::parse("markdown"), None);
+    }
+
+    #[test]
+    fn test_csv_quotes_special_fields() {
+        assert_eq!(
+            table().to_csv(),
+            "title,note\r\n\
+             Use sqlite,\"one, two\"\r\n\
+             \"Say \"\"hi\"\"\",\"line one\nline | two\"\r\n"
+        );
+    }
+
+    #[test]
+    fn test_yaml_cells_read_back_as_strings() {
+        let cells = |table: &Table| -> Vec<Vec<String>> {
+            let rows: Vec<serde_yaml::Mapping> = serde_yaml::from_str(&table.to_yaml()).unwrap();
+            rows.iter()
+                .map(|row| {
+                    table
+                        .headers
+                        .iter()
+                        .map(|h| row.get(*h).and_then(|v| v.as_str()).unwrap().to_string())
+                        .collect()
+                })
+                .collect()
+        };
+        assert_eq!(cells(&table()), table().rows);
+
+        let mut reserved = Table::new(vec!["value"]);
+        reserved.push(vec!["yes".to_string()]);
+        reserved.push(vec!["0.80".to_string()]);
+        reserved.push(vec![String::new()]);
+        assert_eq!(cells(&reserved), reserved.rows);
+        assert_eq!(Table::new(vec!["value"]).to_yaml(), "[]\n");
+    }
+
+    #[test]
+    fn test_yaml_listing_imports_as_batch() {
+        let mut memory = Memory::new(
+            MemoryType::Decision,
+            "Use sqlite: single file".to_string(),
+            "No server to run.\nRevisit for concurrent writers.".to_string(),
+            None,
+        );
+        memory.metadata.importance = 0.8;
+        memory.metadata.tags = vec!["db".to_string(), "yes".to_string()];
+        memory.metadata.related_files = vec!["src/db.rs".to_string()];
+        let results = [MemorySearchResult {
+            memory: memory.clone(),
+            relevance_score: 0.9,
+            selection_reason: "semantic match".to_string(),
+            signals: Vec::new(),
+        }];
+
+        for yaml in [
+            memories_table(std::slice::from_ref(&memory)).to_yaml(),
+            search_results_table(&results).to_yaml(),
+        ] {
+            let entries = parse_yaml(&yaml).unwrap();
+            assert_eq!(entries.len(), 1, "{}", yaml);
+            let entry = &entries[0];
+            assert_eq!(entry.title, memory.title);
+            assert_eq!(entry.content, memory.content);
+            assert_eq!(entry.memory_type, "decision");
+            assert_eq!(entry.importance, Some(0.8));
+            assert_eq!(entry.tags, memory.metadata.tags);
+            assert_eq!(entry.files, memory.metadata.related_files);
+        }
+    }
+
+    #[test]
+    fn test_markdown_rows_stay_on_one_line() {
+        assert_eq!(
+            table().to_markdown(),
+            "| title | note |\n\
+             | --- | --- |\n\
+             | Use sqlite | one, two |\n\
+             | Say \"hi\" | line one<br>line \\| two |\n"
+        );
+    }
+
+    #[test]
+    fn test_memory_tables_have_a_row_per_memory() {
+        let mut memory = Memory::new(
+            MemoryType::Decision,
+            "Use sqlite".to_string(),
+            "Single file".to_string(),
+            None,
+        );
+        memory.metadata.tags = vec!["db".to_string(), "infra".to_string()];
+        let table = memories_table(std::slice::from_ref(&memory));
+        assert_eq!(table.rows.len(), 1);
+        let column = |name: &str| table.headers.iter().position(|h| *h == name).unwrap();
+        assert_eq!(table.rows[0][column("id")], memory.id);
+        assert_eq!(table.rows[0][column("tags")], "db, infra");
+
+        let results = search_results_table(&[MemorySearchResult {
+            memory,
+            relevance_score: 0.876,
+            selection_reason: "semantic match".to_string(),
+            signals: Vec::new(),
+        }]);
+        assert_eq!(results.headers.last(), Some(&"why"));
+        assert_eq!(results.rows[0][results.headers.len() - 2], "0.88");
+        assert_eq!(
+            results.render(TableFormat::Csv).lines().count(),
+            2,
+            "header and one row"
+        );
+    }
+
+    #[test]
+    fn test_explain_lines_per_signal() {
+        let lines = explain_signals(&[
+            SearchSignal::Fusion {
+                score: 0.5,
+                weight: 0.6,
+                vector_rank: Some(2),
+                keyword_rank: None,
+            },
+            SearchSignal::Keyword {
+                matches: vec![KeywordMatch {
+                    term: "token".to_string(),
+                    count: 3,
+                }],
+            },
+            SearchSignal::Importance {
+                stored: 0.8,
+                current: 0.6,
+                weight: None,
+            },
+            SearchSignal::Reranker {
+                score: 0.9,
+                previous: 0.4,
+            },
+        ]);
+        assert_eq!(
+            lines,
+            vec![
+                "fusion: 0.500 × weight 0.60 (vector #2, keyword -)",
+                "keyword: token ×3",
+                "importance: 0.600 (stored 0.80, after decay) multiplies the score",
+                "reranker: 0.900 (replaced 0.400)",
+            ]
+        );
+    }
+}
@@ -101,6 +101,22 @@ impl GitUtils {
         }
     }
 
+    /// Get the configured `user.email`, used to attribute memories to their author
+    pub fn get_user_email() -> Option<String> {
+        let output = Command::new("git")
+            .args(["config", "user.email"])
+            .output()
+            .ok()?;
+
+        if output.status.success() {
+            let email = String::from_utf8(output.stdout).ok()?;
+            let email = email.trim();
+            (!email.is_empty()).then(|| email.to_string())
+        } else {
+            None
+        }
+    }
+
     /// Get the Git repository root directory
     pub fn get_repository_root() -> Option<String> {
         let output = Command::new("git")
@@ -0,0 +1,85 @@
+// Copyright 2026 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#[cfg(test)]
+mod tests {
+    use super::super::manager::activity_heatmap;
+    use chrono::{NaiveDate, TimeZone, Utc};
+
+    fn at(y: i32, m: u32, d: u32) -> chrono::DateTime<Utc> {
+        Utc.with_ymd_and_hms(y, m, d, 12, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn test_matrix_is_monday_aligned_and_bounded() {
+        // Wednesday
+        let today = NaiveDate::from_ymd_opt(2026, 3, 18).unwrap();
+        let heatmap = activity_heatmap(&[], &[], today, 1);
+
+        assert_eq!(heatmap.from, NaiveDate::from_ymd_opt(2026, 2, 19).unwrap());
+        assert_eq!(
+            heatmap.first_week,
+            NaiveDate::from_ymd_opt(2026, 2, 16).unwrap()
+        );
+        assert_eq!(heatmap.created.len(), 5);
+        // Mon-Wed of the first week precede the window; Thu-Sun of the last week follow today
+        assert_eq!(heatmap.created[0][..3], [None, None, None]);
+        assert_eq!(heatmap.created[0][3], Some(0));
+        assert_eq!(heatmap.created[4][2], Some(0));
+        assert_eq!(heatmap.created[4][3..], [None, None, None, None]);
+    }
+
+    #[test]
+    fn test_counts_land_on_their_day_and_out_of_window_is_dropped() {
+        let today = NaiveDate::from_ymd_opt(2026, 3, 18).unwrap();
+        let created = [
+            at(2026, 3, 18),
+            at(2026, 3, 18),
+            at(2026, 3, 2),
+            at(2025, 1, 1),
+        ];
+        let accessed = [at(2026, 2, 19)];
+        let heatmap = activity_heatmap(&created, &accessed, today, 1);
+
+        assert_eq!(heatmap.created[4][2], Some(2));
+        assert_eq!(heatmap.created[2][0], Some(1));
+        assert_eq!(heatmap.accessed[0][3], Some(1));
+        let total: usize = heatmap.created.iter().flatten().flatten().sum();
+        assert_eq!(total, 3);
+    }
+
+    #[test]
+    fn test_terminal_rendering() {
+        let today = NaiveDate::from_ymd_opt(2026, 3, 18).unwrap();
+        let created = [at(2026, 3, 18), at(2026, 3, 18), at(2026, 3, 16)];
+        let text = activity_heatmap(&created, &[], today, 1).format();
+
+        assert!(text.contains("Created: 3 over 2 active days"));
+        assert!(text.contains("Accessed: 0 over 0 active days"));
+        // Month starts are labelled; the partial first week yields to March
+        assert!(text.contains("Mar"));
+        assert!(!text.contains("Feb"));
+        // Busiest day gets the darkest block, half as busy a lighter one
+        let monday = text
+            .lines()
+            .find(|l| l.trim_start().starts_with("Mon"))
+            .unwrap();
+        assert!(monday.ends_with('▒'));
+        let wednesday = text
+            .lines()
+            .find(|l| l.trim_start().starts_with("Wed"))
+            .unwrap();
+        assert!(wednesday.ends_with('█'));
+    }
+}
@@ -14,6 +14,7 @@
 
 #[cfg(test)]
 mod tests {
+    use super::super::search_scoring::{keyword_terms, KeywordIndex};
     use super::super::store::MemoryStore;
     use super::super::types::{Memory, MemoryType};
     use chrono::{Duration, Utc};
@@ -114,4 +115,56 @@ mod tests {
             );
         }
     }
+
+    fn corpus(documents: &[&str]) -> (KeywordIndex, Vec<Vec<String>>) {
+        let mut index = KeywordIndex::default();
+        let documents: Vec<Vec<String>> = documents.iter().map(|d| keyword_terms(d)).collect();
+        for terms in &documents {
+            index.add(terms);
+        }
+        (index, documents)
+    }
+
+    #[test]
+    fn test_bm25_weighs_rare_terms_over_common_ones() {
+        let (index, docs) = corpus(&[
+            "the service uses the cache",
+            "the service retries the request",
+            "the gateway rotates tokens",
+            "the service logs the request",
+        ]);
+        let query = keyword_terms("the tokens");
+        // A single rare match beats several repeats of a word in every document
+        assert!(index.score(&query, &docs[2]) > index.score(&query, &docs[0]));
+        assert!(index.score(&query, &docs[0]) > 0.0);
+        assert_eq!(index.score(&keyword_terms("missing"), &docs[0]), 0.0);
+    }
+
+    #[test]
+    fn test_bm25_saturates_repeats_and_favours_short_documents() {
+        let (index, docs) = corpus(&[
+            "cache",
+            "cache cache cache cache cache cache cache cache",
+            "cache invalidation happens when the upstream schema changes overnight",
+            "unrelated",
+        ]);
+        let query = keyword_terms("cache");
+        let once = index.score(&query, &docs[0]);
+        let repeated = index.score(&query, &docs[1]);
+        assert!(repeated < 8.0 * once);
+        assert!(once > index.score(&query, &docs[2]));
+    }
+
+    #[test]
+    fn test_keyword_index_removal_restores_statistics() {
+        let (mut index, docs) = corpus(&["alpha beta", "beta gamma"]);
+        let query = keyword_terms("alpha");
+        let before = index.score(&query, &docs[0]);
+
+        let extra = keyword_terms("alpha delta");
+        index.add(&extra);
+        assert!(index.score(&query, &docs[0]) < before);
+        index.remove(&extra);
+        assert!((index.score(&query, &docs[0]) - before).abs() < 1e-6);
+    }
 }
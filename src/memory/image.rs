@@ -0,0 +1,113 @@
+// Copyright 2026 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Image memories: caption an image with a vision LLM so it can be embedded and
+//! searched like any text memory. The image itself is stored as an attachment.
+
+use anyhow::Result;
+use base64::Engine;
+use octolib::llm::{
+    ChatCompletionParams, ImageAttachment, ImageData, Message, ProviderFactory, SourceType,
+};
+use std::path::Path;
+
+use crate::config::VisionConfig;
+
+/// Tag added to memories created from an image.
+pub const IMAGE_TAG: &str = "image";
+
+const CAPTION_PROMPT: &str = "Describe this image for a developer's knowledge base. \
+If it is a diagram, list the components and how they connect. If it is a whiteboard \
+or screenshot, transcribe the readable text and summarize the key points. Be factual \
+and concise; do not speculate beyond what is visible.";
+
+/// MIME type for a supported image file, based on its extension.
+pub fn image_media_type(path: &Path) -> Option<&'static str> {
+    let ext = path.extension()?.to_str()?.to_ascii_lowercase();
+    match ext.as_str() {
+        "png" => Some("image/png"),
+        "jpg" | "jpeg" => Some("image/jpeg"),
+        "gif" => Some("image/gif"),
+        "webp" => Some("image/webp"),
+        _ => None,
+    }
+}
+
+/// Build the caption request prompt, adding the memory title as a hint when given.
+pub fn build_caption_prompt(title: Option<&str>) -> String {
+    match title {
+        Some(title) if !title.trim().is_empty() => {
+            format!(
+                "{}\n\nThe user titled this image: \"{}\".",
+                CAPTION_PROMPT, title
+            )
+        }
+        _ => CAPTION_PROMPT.to_string(),
+    }
+}
+
+/// Ask the configured vision model to caption the image at `path` (contents in `bytes`).
+pub async fn caption_image(
+    config: &VisionConfig,
+    path: &Path,
+    bytes: &[u8],
+    title: Option<&str>,
+) -> Result<String> {
+    let media_type = image_media_type(path).ok_or_else(|| {
+        anyhow::anyhow!(
+            "Unsupported image type '{}'; expected png, jpg, gif or webp",
+            path.display()
+        )
+    })?;
+
+    let (provider, model) = ProviderFactory::get_provider_for_model(&config.model)?;
+    if !provider.supports_vision(&model) {
+        // Capability tables lag behind new models, so only warn and let the provider decide
+        tracing::warn!(
+            "Model '{}' is not known to accept image input; set [vision].model to a vision-capable model if captioning fails",
+            config.model
+        );
+    }
+
+    let mut message = Message::user(&build_caption_prompt(title));
+    message.images = Some(vec![ImageAttachment {
+        data: ImageData::Base64(base64::engine::general_purpose::STANDARD.encode(bytes)),
+        media_type: media_type.to_string(),
+        source_type: SourceType::File(path.to_path_buf()),
+        dimensions: None,
+        size_bytes: Some(bytes.len() as u64),
+    }]);
+    let messages = vec![message];
+    let params = ChatCompletionParams::new(&messages, &model, 0.2, 1.0, 50, config.max_tokens);
+
+    let completion = provider.chat_completion(params);
+    let response = if config.timeout_secs == 0 {
+        completion.await?
+    } else {
+        tokio::time::timeout(
+            std::time::Duration::from_secs(config.timeout_secs),
+            completion,
+        )
+        .await
+        .map_err(|_| {
+            anyhow::anyhow!("Image captioning timed out after {}s", config.timeout_secs)
+        })??
+    };
+
+    let caption = response.content.trim().to_string();
+    if caption.is_empty() {
+        return Err(anyhow::anyhow!("Vision model returned an empty caption"));
+    }
+    Ok(caption)
+}
@@ -0,0 +1,35 @@
+// Copyright 2026 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#[cfg(test)]
+mod tests {
+    use super::super::image::{build_caption_prompt, image_media_type};
+    use std::path::Path;
+
+    #[test]
+    fn test_image_media_type_by_extension() {
+        assert_eq!(image_media_type(Path::new("arch.png")), Some("image/png"));
+        assert_eq!(image_media_type(Path::new("board.JPG")), Some("image/jpeg"));
+        assert_eq!(image_media_type(Path::new("a/b.webp")), Some("image/webp"));
+        assert_eq!(image_media_type(Path::new("notes.txt")), None);
+        assert_eq!(image_media_type(Path::new("no_extension")), None);
+    }
+
+    #[test]
+    fn test_caption_prompt_includes_title_hint() {
+        let prompt = build_caption_prompt(Some("Payment service topology"));
+        assert!(prompt.contains("\"Payment service topology\""));
+        assert_eq!(build_caption_prompt(Some("  ")), build_caption_prompt(None));
+    }
+}
@@ -0,0 +1,96 @@
+// Copyright 2026 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#[cfg(test)]
+mod tests {
+    use super::super::manager::importance_distribution;
+    use super::super::types::{Memory, MemoryConfig, MemoryType};
+    use chrono::{Duration, Utc};
+
+    fn memory(importance: f32, age_days: i64) -> Memory {
+        let mut m = Memory::new(
+            MemoryType::Code,
+            "Histogram subject".to_string(),
+            "Memory used to exercise the importance histogram".to_string(),
+            None,
+        );
+        m.metadata.importance = importance;
+        m.created_at = Utc::now() - Duration::days(age_days);
+        m
+    }
+
+    #[test]
+    fn test_buckets_current_importance() {
+        let config = MemoryConfig {
+            decay_enabled: false,
+            ..MemoryConfig::default()
+        };
+        let memories = vec![
+            memory(0.05, 0),
+            memory(0.55, 0),
+            memory(0.59, 0),
+            memory(1.0, 0),
+        ];
+        let dist = importance_distribution(&memories, &config, Utc::now());
+        assert_eq!(dist.buckets.len(), 10);
+        assert_eq!(dist.buckets[0], 1);
+        assert_eq!(dist.buckets[5], 2);
+        // 1.0 lands in the top bucket rather than past it
+        assert_eq!(dist.buckets[9], 1);
+    }
+
+    #[test]
+    fn test_averages_without_decay_match_stored_importance() {
+        let config = MemoryConfig {
+            decay_enabled: false,
+            ..MemoryConfig::default()
+        };
+        let dist = importance_distribution(&[memory(0.2, 0), memory(0.6, 0)], &config, Utc::now());
+        assert!((dist.average_base - 0.4).abs() < 1e-6);
+        assert!((dist.average_current - 0.4).abs() < 1e-6);
+
+        let empty = importance_distribution(&[], &config, Utc::now());
+        assert_eq!(empty.average_base, 0.0);
+    }
+
+    #[test]
+    fn test_cleanup_counts_follow_age_and_threshold() {
+        let config = MemoryConfig {
+            auto_cleanup_days: Some(365),
+            cleanup_min_importance: 0.1,
+            ..MemoryConfig::default()
+        };
+        let memories = vec![
+            memory(0.05, 400), // due
+            memory(0.05, 30),  // below threshold, too young
+            memory(0.15, 400), // near threshold
+            memory(0.8, 400),  // safe
+        ];
+        let dist = importance_distribution(&memories, &config, Utc::now());
+        assert_eq!(dist.due_for_cleanup, 1);
+        assert_eq!(dist.below_threshold_not_yet_due, 1);
+        assert_eq!(dist.near_threshold, 1);
+    }
+
+    #[test]
+    fn test_nothing_due_when_cleanup_disabled() {
+        let config = MemoryConfig {
+            auto_cleanup_days: None,
+            ..MemoryConfig::default()
+        };
+        let dist = importance_distribution(&[memory(0.05, 1000)], &config, Utc::now());
+        assert_eq!(dist.due_for_cleanup, 0);
+        assert_eq!(dist.below_threshold_not_yet_due, 1);
+    }
+}
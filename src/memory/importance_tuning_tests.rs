@@ -0,0 +1,109 @@
+// Copyright 2026 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#[cfg(test)]
+mod tests {
+    use super::super::manager::{claim_importance_tuning, usage_adjusted_importance};
+    use super::super::types::{Memory, MemoryConfig, MemoryType};
+    use chrono::{Duration, Utc};
+
+    fn memory(importance: f32, access_count: u32, last_accessed_days_ago: i64) -> Memory {
+        let mut m = Memory::new(
+            MemoryType::Code,
+            "Tuning subject".to_string(),
+            "Memory used to exercise importance tuning".to_string(),
+            None,
+        );
+        m.metadata.importance = importance;
+        m.metadata.decay.base_importance = importance;
+        m.metadata.decay.access_count = access_count;
+        m.metadata.decay.last_accessed = Utc::now() - Duration::days(last_accessed_days_ago);
+        m
+    }
+
+    #[test]
+    fn test_frequently_retrieved_memory_is_raised() {
+        let config = MemoryConfig::default();
+        let now = Utc::now();
+        let since = now - Duration::days(2);
+        let m = memory(0.5, 10, 1);
+        let adjusted = usage_adjusted_importance(&m, &config, since, now).unwrap();
+        assert!((adjusted - 0.55).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_raise_capped_at_one() {
+        let config = MemoryConfig::default();
+        let now = Utc::now();
+        let m = memory(1.0, 10, 0);
+        assert!(usage_adjusted_importance(&m, &config, now - Duration::days(1), now).is_none());
+    }
+
+    #[test]
+    fn test_few_accesses_not_raised() {
+        let config = MemoryConfig::default();
+        let now = Utc::now();
+        let m = memory(0.5, 1, 0);
+        assert!(usage_adjusted_importance(&m, &config, now - Duration::days(1), now).is_none());
+    }
+
+    #[test]
+    fn test_idle_memory_is_lowered_to_floor() {
+        let config = MemoryConfig::default();
+        let now = Utc::now();
+        let since = now - Duration::days(1);
+
+        let m = memory(0.5, 0, 90);
+        let adjusted = usage_adjusted_importance(&m, &config, since, now).unwrap();
+        assert!((adjusted - 0.45).abs() < 0.001);
+
+        let near_floor = memory(config.min_importance_threshold + 0.01, 0, 90);
+        let adjusted = usage_adjusted_importance(&near_floor, &config, since, now).unwrap();
+        assert!((adjusted - config.min_importance_threshold).abs() < 0.001);
+
+        let below_floor = memory(0.01, 0, 90);
+        assert!(usage_adjusted_importance(&below_floor, &config, since, now).is_none());
+    }
+
+    #[test]
+    fn test_tuning_runs_once_per_interval() {
+        let dir = std::env::temp_dir().join(format!("octobrain_tuning_{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let marker = dir.join(".importance_tuning_default");
+        let now = Utc::now();
+
+        let since = claim_importance_tuning(&marker, 24, now).unwrap().unwrap();
+        assert_eq!(since, now - Duration::hours(24));
+        // Recorded before the scan: a restart within the interval skips the pass
+        assert!(
+            claim_importance_tuning(&marker, 24, now + Duration::hours(1))
+                .unwrap()
+                .is_none()
+        );
+        let later = now + Duration::hours(25);
+        assert_eq!(
+            claim_importance_tuning(&marker, 24, later).unwrap(),
+            Some(now)
+        );
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_tuning_skipped_when_marker_cannot_be_written() {
+        let marker = std::env::temp_dir()
+            .join(format!("octobrain_missing_{}", uuid::Uuid::new_v4()))
+            .join(".importance_tuning_default");
+        assert!(claim_importance_tuning(&marker, 24, Utc::now()).is_err());
+    }
+}
@@ -0,0 +1,114 @@
+// Copyright 2026 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! JSONL backup of memories and relationships (`memory export --format jsonl`).
+//!
+//! One JSON object per line: a header with the format version and embedding
+//! dimension, then every memory with its stored embedding, then every
+//! relationship. `memory import --format jsonl` streams the file back under
+//! the original IDs, reusing exported embeddings and regenerating only the
+//! missing ones (or those of another model).
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::io::Write;
+
+use super::types::{Memory, MemoryRelationship};
+
+/// Format version written in the header; newer files are refused.
+pub const JSONL_VERSION: u32 = 1;
+
+/// One line of a JSONL backup
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum JsonlRecord {
+    Header {
+        version: u32,
+        /// Embedding length of the exporting model
+        dimension: usize,
+        exported_at: DateTime<Utc>,
+    },
+    Memory {
+        memory: Memory,
+        /// Absent when the backend could not read it back
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        embedding: Option<Vec<f32>>,
+    },
+    Relationship {
+        relationship: MemoryRelationship,
+    },
+}
+
+/// Write a header, `memories` (with their `embeddings`) and `relationships`.
+pub fn write_jsonl<W: Write>(
+    mut writer: W,
+    memories: &[Memory],
+    relationships: &[MemoryRelationship],
+    embeddings: &HashMap<String, Vec<f32>>,
+    dimension: usize,
+) -> Result<()> {
+    let mut write_line = |record: &JsonlRecord| -> Result<()> {
+        serde_json::to_writer(&mut writer, record)?;
+        writer.write_all(b"\n")?;
+        Ok(())
+    };
+    write_line(&JsonlRecord::Header {
+        version: JSONL_VERSION,
+        dimension,
+        exported_at: Utc::now(),
+    })?;
+    for memory in memories {
+        write_line(&JsonlRecord::Memory {
+            memory: memory.clone(),
+            embedding: embeddings.get(&memory.id).cloned(),
+        })?;
+    }
+    for relationship in relationships {
+        write_line(&JsonlRecord::Relationship {
+            relationship: relationship.clone(),
+        })?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+/// Parse one line; `None` for blank lines. Headers of newer versions are errors.
+pub fn parse_line(line: &str) -> Result<Option<JsonlRecord>> {
+    if line.trim().is_empty() {
+        return Ok(None);
+    }
+    let record: JsonlRecord = serde_json::from_str(line).context("Invalid JSONL record")?;
+    if let JsonlRecord::Header { version, .. } = &record {
+        if *version > JSONL_VERSION {
+            anyhow::bail!(
+                "Backup format version {} is newer than supported ({}); upgrade octobrain",
+                version,
+                JSONL_VERSION
+            );
+        }
+    }
+    Ok(Some(record))
+}
+
+/// Outcome of `memory import --format jsonl`.
+#[derive(Debug, Default)]
+pub struct JsonlImportReport {
+    /// Memories read (and stored unless it was a dry run)
+    pub memories: usize,
+    pub relationships: usize,
+    /// Stored memories whose embedding was missing or of another model
+    pub reembedded: usize,
+}
@@ -0,0 +1,184 @@
+// Copyright 2026 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#[cfg(test)]
+mod tests {
+    use super::super::backend::{BackendOptions, StorageBackend};
+    use super::super::ephemeral_store::EphemeralStore;
+    use super::super::jsonl::{parse_line, write_jsonl, JsonlRecord, JSONL_VERSION};
+    use super::super::manager::import_jsonl_into;
+    use super::super::types::{
+        Memory, MemoryConfig, MemoryRelationship, MemoryType, RelationshipType,
+    };
+    use crate::embedding::{EmbeddingProvider, InputType};
+    use async_trait::async_trait;
+    use chrono::Utc;
+    use std::collections::HashMap;
+    use std::path::PathBuf;
+
+    const DIM: usize = 4;
+
+    /// Constant embedding; these tests never rank results.
+    struct FlatEmbedding;
+
+    #[async_trait]
+    impl EmbeddingProvider for FlatEmbedding {
+        async fn generate_embedding(&self, _text: &str) -> anyhow::Result<Vec<f32>> {
+            Ok(vec![0.5; DIM])
+        }
+
+        async fn generate_embeddings_batch(
+            &self,
+            texts: Vec<String>,
+            _input_type: InputType,
+        ) -> anyhow::Result<Vec<Vec<f32>>> {
+            Ok(vec![vec![0.5; DIM]; texts.len()])
+        }
+
+        fn get_dimension(&self) -> usize {
+            DIM
+        }
+    }
+
+    fn store() -> EphemeralStore {
+        EphemeralStore::new(BackendOptions {
+            db_dir: PathBuf::new(),
+            project_key: Some("p1".to_string()),
+            role: None,
+            embedding_provider: Box::new(FlatEmbedding),
+            config: MemoryConfig::default(),
+            main_config: crate::config::Config {
+                embedding: Default::default(),
+                search: Default::default(),
+                memory: MemoryConfig::default(),
+                knowledge: Default::default(),
+                vision: Default::default(),
+                maintenance: Default::default(),
+                server: Default::default(),
+                storage: Default::default(),
+            },
+            reranker_integration: None,
+        })
+    }
+
+    /// Backup of two memories, the first with an embedding, and a link
+    /// between them, followed by `trailer`
+    fn backup(trailer: &str) -> PathBuf {
+        let a = Memory::new(MemoryType::Decision, "A".into(), "first".into(), None);
+        let b = Memory::new(MemoryType::Learning, "B".into(), "second".into(), None);
+        let link = MemoryRelationship {
+            id: "rel-1".to_string(),
+            source_id: a.id.clone(),
+            target_id: b.id.clone(),
+            relationship_type: RelationshipType::RelatedTo,
+            strength: 0.7,
+            description: "see also".to_string(),
+            created_at: Utc::now(),
+        };
+        let embeddings = HashMap::from([(a.id.clone(), vec![0.1; DIM])]);
+        let mut out = Vec::new();
+        write_jsonl(&mut out, &[a, b], &[link], &embeddings, DIM).unwrap();
+        out.extend_from_slice(trailer.as_bytes());
+
+        let path =
+            std::env::temp_dir().join(format!("octobrain_backup_{}.jsonl", uuid::Uuid::new_v4()));
+        std::fs::write(&path, out).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_round_trip_preserves_ids_and_embeddings() {
+        let a = Memory::new(MemoryType::Decision, "A".into(), "first".into(), None);
+        let b = Memory::new(MemoryType::Learning, "B".into(), "second".into(), None);
+        let link = MemoryRelationship {
+            id: "rel-1".to_string(),
+            source_id: a.id.clone(),
+            target_id: b.id.clone(),
+            relationship_type: RelationshipType::RelatedTo,
+            strength: 0.7,
+            description: "see also".to_string(),
+            created_at: Utc::now(),
+        };
+        let embeddings = HashMap::from([(a.id.clone(), vec![0.5, -0.5])]);
+
+        let mut out = Vec::new();
+        write_jsonl(&mut out, &[a.clone(), b.clone()], &[link], &embeddings, 2).unwrap();
+        let text = String::from_utf8(out).unwrap();
+        let records: Vec<JsonlRecord> = text
+            .lines()
+            .filter_map(|line| parse_line(line).unwrap())
+            .collect();
+        assert_eq!(records.len(), 4);
+
+        match &records[0] {
+            JsonlRecord::Header {
+                version, dimension, ..
+            } => assert_eq!((*version, *dimension), (JSONL_VERSION, 2)),
+            other => panic!("expected header, got {:?}", other),
+        }
+        match &records[1] {
+            JsonlRecord::Memory { memory, embedding } => {
+                assert_eq!(memory.id, a.id);
+                assert_eq!(embedding.as_deref(), Some(&[0.5, -0.5][..]));
+            }
+            other => panic!("expected memory, got {:?}", other),
+        }
+        match &records[2] {
+            JsonlRecord::Memory { memory, embedding } => {
+                assert_eq!(memory.id, b.id);
+                assert!(embedding.is_none());
+            }
+            other => panic!("expected memory, got {:?}", other),
+        }
+        match &records[3] {
+            JsonlRecord::Relationship { relationship } => {
+                assert_eq!(relationship.id, "rel-1");
+                assert_eq!(relationship.source_id, a.id);
+            }
+            other => panic!("expected relationship, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_line_skips_blanks_and_rejects_newer_versions() {
+        assert!(parse_line("   ").unwrap().is_none());
+        assert!(parse_line("{\"kind\":\"unknown\"}").is_err());
+        let newer = format!(
+            "{{\"kind\":\"header\",\"version\":{},\"dimension\":3,\"exported_at\":\"2026-01-01T00:00:00Z\"}}",
+            JSONL_VERSION + 1
+        );
+        assert!(parse_line(&newer).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_import_reuses_fitting_embeddings() {
+        let store = store();
+        let report = import_jsonl_into(&store, &backup(""), false).await.unwrap();
+        assert_eq!((report.memories, report.relationships), (2, 1));
+        assert_eq!(report.reembedded, 1);
+        assert_eq!(store.get_memory_count().await.unwrap(), 2);
+        assert_eq!(store.get_all_relationships().await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_malformed_last_line_imports_nothing() {
+        let store = store();
+        let err = import_jsonl_into(&store, &backup("{\"kind\":\"memory\"\n"), false)
+            .await
+            .unwrap_err();
+        assert!(format!("{:#}", err).contains("Line 5"));
+        assert_eq!(store.get_memory_count().await.unwrap(), 0);
+        assert!(store.get_all_relationships().await.unwrap().is_empty());
+    }
+}
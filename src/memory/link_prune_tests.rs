@@ -0,0 +1,58 @@
+// Copyright 2026 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#[cfg(test)]
+mod tests {
+    use super::super::manager::weak_relationships;
+    use super::super::types::{MemoryRelationship, RelationshipType};
+
+    fn rel(id: &str, relationship_type: RelationshipType, strength: f32) -> MemoryRelationship {
+        MemoryRelationship {
+            id: id.to_string(),
+            source_id: format!("{}-src", id),
+            target_id: format!("{}-dst", id),
+            relationship_type,
+            strength,
+            description: String::new(),
+            created_at: chrono::Utc::now(),
+        }
+    }
+
+    fn ids(rels: &[MemoryRelationship]) -> Vec<&str> {
+        rels.iter().map(|r| r.id.as_str()).collect()
+    }
+
+    #[test]
+    fn test_keeps_only_edges_below_threshold_weakest_first() {
+        let rels = vec![
+            rel("a", RelationshipType::AutoLinked, 0.79),
+            rel("b", RelationshipType::AutoLinked, 0.95),
+            rel("c", RelationshipType::RelatedTo, 0.5),
+            rel("d", RelationshipType::AutoLinked, 0.8),
+        ];
+        let weak = weak_relationships(rels, 0.8, None);
+        assert_eq!(ids(&weak), vec!["c", "a"]);
+    }
+
+    #[test]
+    fn test_type_filter_spares_other_relationships() {
+        let rels = vec![
+            rel("a", RelationshipType::AutoLinked, 0.7),
+            rel("c", RelationshipType::RelatedTo, 0.5),
+            rel("g", RelationshipType::Achieves, 0.3),
+        ];
+        let weak = weak_relationships(rels, 0.8, Some(&RelationshipType::from("auto_linked")));
+        assert_eq!(ids(&weak), vec!["a"]);
+    }
+}
@@ -0,0 +1,55 @@
+// Copyright 2026 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Single-shot text completion against the `[search.answer]` model, shared by
+//! answer synthesis, transcript summarization and conversation import.
+
+use anyhow::Result;
+use octolib::llm::{ChatCompletionParams, Message, ProviderFactory};
+
+use crate::config::AnswerConfig;
+
+/// Send one system + user exchange and return the trimmed reply text.
+/// `operation` names the caller in the timeout error.
+pub async fn complete(
+    config: &AnswerConfig,
+    system: &str,
+    user: &str,
+    operation: &str,
+) -> Result<String> {
+    let (provider, model) = ProviderFactory::get_provider_for_model(&config.model)?;
+    let messages = vec![Message::system(system), Message::user(user)];
+    let params = ChatCompletionParams::new(
+        &messages,
+        &model,
+        config.temperature,
+        1.0,
+        50,
+        config.max_tokens,
+    );
+
+    let completion = provider.chat_completion(params);
+    let response = if config.timeout_secs == 0 {
+        completion.await?
+    } else {
+        tokio::time::timeout(
+            std::time::Duration::from_secs(config.timeout_secs),
+            completion,
+        )
+        .await
+        .map_err(|_| anyhow::anyhow!("{} timed out after {}s", operation, config.timeout_secs))??
+    };
+
+    Ok(response.content.trim().to_string())
+}
@@ -12,23 +12,54 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use chrono::{Duration, Utc};
-use std::collections::HashSet;
-use std::path::PathBuf;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
 use tokio::sync::Mutex as AsyncMutex;
 use tokio::task::JoinHandle;
 
+use super::aliases::{
+    is_id_fragment, shortest_unique_prefixes, validate_alias, AliasBook, MIN_SHORT_ID_LEN,
+};
+use super::answer::MemoryAnswer;
+use super::attachments::{read_attachment, resolve_path, write_attachment};
+use super::backend::{open_backend, BackendKind, BackendOptions, StorageBackend};
+use super::batch::BatchEntry;
+use super::columnar::{read_arrow, write_arrow, ColumnarImportReport};
+use super::conflict::{
+    is_conflict, resolve, ConflictPrompt, ConflictStrategy, MergeOutcome, Resolution,
+};
+use super::conversation_import::{
+    extract_facts_heuristic, extract_facts_llm, parse_export, ConversationFormat,
+    ConversationImportReport, IMPORTED_TAG,
+};
+use super::digest::{build_digest, summarize_digest, Digest};
+use super::feedback::{apply_feedback, FeedbackBook, FeedbackEntry};
 use super::git_utils::{FileFate, GitUtils, RenameMap};
-use super::store::MemoryStore;
+use super::image::{caption_image, IMAGE_TAG};
+use super::jsonl::{parse_line, write_jsonl, JsonlImportReport, JsonlRecord};
+use super::query_syntax::{parse_query, QueryExpr};
+use super::redaction::{Redactor, REDACTED_TAG};
+use super::review::{due_reviews, is_reviewable, DueReview, ReviewBook, ReviewCard, MAX_GRADE};
+use super::secrets::{describe_findings, detect_secrets};
+use super::session_import::{extract_session_items, load_sessions, SessionFormat, SESSION_TAG};
+use super::share::{
+    key_fingerprint, load_or_create_key, same_key, sign_bundle, verify_bundle, ShareBundle,
+    ShareImportReport, SharePayload, SharedMemory, BUNDLE_VERSION, SHARE_TAG,
+};
+use super::sync::{new_relationships, SyncApplied};
 use super::types::{
-    Memory, MemoryConfig, MemoryMetadata, MemoryQuery, MemoryRelationship, MemorySearchResult,
-    MemorySource, MemoryState, MemoryType, RelationshipType,
+    Memory, MemoryAttachment, MemoryConfig, MemoryMetadata, MemoryQuery, MemoryRelationship,
+    MemoryRevision, MemorySearchResult, MemorySource, MemoryState, MemoryType, RelationshipType,
 };
-use crate::config::Config;
-use crate::embedding::{create_embedding_provider_from_parts, parse_provider_model};
+use crate::config::{AnswerConfig, Config, VisionConfig};
+use crate::embedding::{
+    create_embedding_provider_from_parts, parse_provider_model, resolve_model_spec,
+};
+use crate::vector_optimizer::{IndexRebuild, TableCompaction};
 
 /// How often (in memorize calls) to run LanceDB maintenance.
 /// 250 is small enough that the unindexed delta never gets large enough to
@@ -38,7 +69,9 @@ use crate::embedding::{create_embedding_provider_from_parts, parse_provider_mode
 const MAINTENANCE_EVERY_N_WRITES: usize = 250;
 
 /// Parameters for the memorize() call — groups the optional fields to stay under clippy's arg limit.
+/// Start from [`MemorizeParams::new`] and set the fields you need; more may be added.
 #[derive(Debug)]
+#[non_exhaustive]
 pub struct MemorizeParams {
     pub memory_type: MemoryType,
     pub title: String,
@@ -47,21 +80,190 @@ pub struct MemorizeParams {
     pub tags: Option<Vec<String>>,
     pub related_files: Option<Vec<String>>,
     pub source: Option<MemorySource>,
+    /// Store content even when it looks like a credential (see `secrets::detect_secrets`)
+    pub allow_secrets: bool,
+    /// Paths of small files to attach (copied into the attachments storage dir)
+    pub attachments: Option<Vec<String>>,
+    /// Image to caption with the vision model; the caption is appended to `content`
+    /// and the image is stored as an attachment
+    pub image: Option<String>,
+    /// Free-form string metadata persisted with the memory
+    pub custom_fields: Option<HashMap<String, String>>,
+    /// Author to record instead of the configured one (e.g. a shared bundle's sender)
+    pub created_by: Option<String>,
+}
+
+impl MemorizeParams {
+    /// A memory with default importance, no tags and auto-detected files.
+    pub fn new(
+        memory_type: MemoryType,
+        title: impl Into<String>,
+        content: impl Into<String>,
+    ) -> Self {
+        Self {
+            memory_type,
+            title: title.into(),
+            content: content.into(),
+            importance: None,
+            tags: None,
+            related_files: None,
+            source: None,
+            allow_secrets: false,
+            attachments: None,
+            image: None,
+            custom_fields: None,
+            created_by: None,
+        }
+    }
+}
+
+/// Partial changes to an existing memory for `edit_memory`; unset fields are kept.
+#[derive(Debug, Default)]
+pub struct MemoryEdit {
+    pub title: Option<String>,
+    pub content: Option<String>,
+    pub importance: Option<f32>,
+    pub add_tags: Vec<String>,
+    pub remove_tags: Vec<String>,
+    pub add_files: Vec<String>,
+    pub remove_files: Vec<String>,
+    /// Store new title/content even when it looks like a credential
+    pub allow_secrets: bool,
+}
+
+impl MemoryEdit {
+    /// Whether the edit changes nothing
+    pub fn is_empty(&self) -> bool {
+        self.title.is_none()
+            && self.content.is_none()
+            && self.importance.is_none()
+            && self.add_tags.is_empty()
+            && self.remove_tags.is_empty()
+            && self.add_files.is_empty()
+            && self.remove_files.is_empty()
+    }
+
+    /// Metadata of `memory` with the tag, file and importance changes applied.
+    /// Removals run after additions, so a value in both lists ends up removed.
+    pub fn revised_metadata(&self, memory: &Memory) -> MemoryMetadata {
+        let mut revised = memory.clone();
+        for tag in &self.add_tags {
+            revised.add_tag(tag.clone());
+        }
+        for file in &self.add_files {
+            revised.add_related_file(file.clone());
+        }
+        for tag in &self.remove_tags {
+            revised.remove_tag(tag);
+        }
+        for file in &self.remove_files {
+            revised.remove_related_file(file);
+        }
+        if let Some(importance) = self.importance {
+            revised.metadata.importance = importance.clamp(0.0, 1.0);
+            revised.metadata.decay.base_importance = revised.metadata.importance;
+        }
+        revised.metadata
+    }
+}
+/// New base importance for `memory` from its access history, or `None` when unchanged.
+///
+/// Raised by one step when it was retrieved after `since` and has at least
+/// `importance_tuning_min_accesses` lifetime accesses; lowered by one step when it
+/// has not been retrieved for `importance_tuning_idle_days`. Bounded to
+/// `[min_importance_threshold, 1.0]`.
+pub(crate) fn usage_adjusted_importance(
+    memory: &Memory,
+    config: &MemoryConfig,
+    since: chrono::DateTime<Utc>,
+    now: chrono::DateTime<Utc>,
+) -> Option<f32> {
+    let decay = &memory.metadata.decay;
+    let current = memory.metadata.importance;
+    let step = config.importance_tuning_step.abs();
+
+    let hot =
+        decay.last_accessed > since && decay.access_count >= config.importance_tuning_min_accesses;
+    let idle = (now - decay.last_accessed).num_days() >= config.importance_tuning_idle_days as i64;
+
+    let target = if hot {
+        (current + step).min(1.0)
+    } else if idle {
+        (current - step).max(config.min_importance_threshold.min(current))
+    } else {
+        return None;
+    };
+
+    if (target - current).abs() < f32::EPSILON {
+        None
+    } else {
+        Some(target)
+    }
+}
+
+/// Start of the access window for an importance-tuning pass, or `None` while the
+/// last pass recorded in `marker` is younger than `interval_hours`.
+///
+/// A due pass is recorded before it scans anything, so a pass that fails or is
+/// interrupted is not retried on every start-up; a marker that cannot be written
+/// skips the pass rather than leaving it ungated.
+pub(crate) fn claim_importance_tuning(
+    marker: &Path,
+    interval_hours: u32,
+    now: chrono::DateTime<Utc>,
+) -> Result<Option<chrono::DateTime<Utc>>> {
+    let interval_hours = interval_hours.max(1) as i64;
+    let last_run = std::fs::read_to_string(marker)
+        .ok()
+        .and_then(|raw| chrono::DateTime::parse_from_rfc3339(raw.trim()).ok())
+        .map(|d| d.with_timezone(&Utc));
+    if last_run.is_some_and(|last| (now - last).num_hours() < interval_hours) {
+        return Ok(None);
+    }
+    std::fs::write(marker, now.to_rfc3339())
+        .with_context(|| format!("Cannot record importance tuning in '{}'", marker.display()))?;
+    Ok(Some(
+        last_run.unwrap_or_else(|| now - Duration::hours(interval_hours)),
+    ))
 }
+
+/// Relationships with strength below `below`, restricted to `relationship_type`
+/// when given, weakest first.
+pub(crate) fn weak_relationships(
+    relationships: Vec<MemoryRelationship>,
+    below: f32,
+    relationship_type: Option<&RelationshipType>,
+) -> Vec<MemoryRelationship> {
+    let wanted = relationship_type.map(|t| t.to_string());
+    let mut weak: Vec<MemoryRelationship> = relationships
+        .into_iter()
+        .filter(|r| r.strength < below)
+        .filter(|r| {
+            wanted
+                .as_deref()
+                .is_none_or(|t| r.relationship_type.to_string() == t)
+        })
+        .collect();
+    weak.sort_by(|a, b| a.strength.total_cmp(&b.strength));
+    weak
+}
+
 /// High-level memory management interface
 pub struct MemoryManager {
     /// Wrapped in Arc so fire-and-forget background tasks (currently:
     /// post-memorize auto-linking) can hold their own clone of the store
-    /// for as long as they need. All MemoryStore mutating methods now
-    /// take `&self` via internal LanceDB `Table` mutability, so no top-
-    /// level lock is needed — concurrent reads + writes are safe.
-    store: Arc<MemoryStore>,
+    /// for as long as they need. All backend mutating methods take `&self`
+    /// (LanceDB `Table` mutability, or the backend's own connection lock), so
+    /// no top-level lock is needed — concurrent reads + writes are safe.
+    store: Arc<dyn StorageBackend>,
     config: MemoryConfig,
     /// Path to the stale-check marker file for incremental git scanning
     stale_check_marker: PathBuf,
     /// Path to the sleep-consolidation marker file; stores last-run RFC3339 timestamp.
     /// Lazy auto-consolidation is gated by `(now - last_run) >= interval_hours`.
     sleep_consolidation_marker: PathBuf,
+    /// Path to the importance-tuning marker file; stores last-run RFC3339 timestamp.
+    importance_tuning_marker: PathBuf,
     /// JoinHandles for in-flight fire-and-forget auto-link tasks. memorize
     /// pushes here when spawning; consolidate_goal drains (awaits) before
     /// running so a goal-close never races against in-flight auto-links of
@@ -78,6 +280,26 @@ pub struct MemoryManager {
     /// overlapping maintenance runs and (b) can await it from
     /// consolidate_goal so retrieval there sees a fully-merged index.
     pending_maintenance: Arc<AsyncMutex<Option<JoinHandle<()>>>>,
+    /// LLM settings for `answer()` synthesis
+    answer_config: AnswerConfig,
+    /// Vision LLM settings for captioning image memories
+    vision_config: VisionConfig,
+    /// Compiled PII redaction rules; `None` when `redaction_enabled` is off
+    redactor: Option<Redactor>,
+    /// Root of the attachment blob store (`storage::get_attachments_dir`);
+    /// `None` for ephemeral sessions, which cannot keep attachments
+    attachments_root: Option<PathBuf>,
+    /// Author recorded on new memories: `[memory].author`, else `git config user.email`
+    author: Option<String>,
+    /// Alias book path (`storage::get_aliases_path`); `None` for ephemeral sessions
+    aliases_path: Option<PathBuf>,
+    /// Search feedback path (`storage::get_feedback_path`); `None` for ephemeral sessions
+    feedback_path: Option<PathBuf>,
+    /// Review cards path (`storage::get_reviews_path`); `None` for ephemeral sessions
+    reviews_path: Option<PathBuf>,
+    /// Local database directory, for the size in `get_memory_stats`; `None` when the
+    /// backend keeps nothing on local disk (ephemeral, Postgres)
+    db_dir: Option<PathBuf>,
 }
 
 impl MemoryManager {
@@ -88,7 +310,15 @@ impl MemoryManager {
         role: Option<String>,
     ) -> Result<Self> {
         // Use memory config from main config (loaded from config file)
-        let memory_config = config.memory.clone();
+        let mut memory_config = config.memory.clone();
+        let backend_kind = BackendKind::parse(&memory_config.backend)?;
+        let ephemeral = backend_kind == BackendKind::Ephemeral;
+        if ephemeral {
+            // These passes keep their progress in marker files next to the database
+            memory_config.stale_ref_cleanup_enabled = false;
+            memory_config.sleep_consolidation_enabled = false;
+            memory_config.importance_tuning_enabled = false;
+        }
 
         // Create reranker integration if enabled
         let reranker_integration = if config.search.reranker.enabled {
@@ -101,39 +331,78 @@ impl MemoryManager {
             None
         };
 
-        // Use shared memory database path (single DB for all projects)
-        let db_path = crate::storage::get_memory_database_path()?;
+        // Use shared memory database path (single DB for all projects);
+        // ephemeral sessions have none
+        let db_path = if ephemeral {
+            PathBuf::new()
+        } else {
+            crate::storage::get_memory_database_path()?
+        };
 
         // Marker files: {db_dir}/.{kind}_{project_key}
         let project_label = project_key.as_deref().unwrap_or("default");
         let stale_check_marker = db_path.join(format!(".stale_check_{}", project_label));
         let sleep_consolidation_marker =
             db_path.join(format!(".sleep_consolidation_{}", project_label));
+        let importance_tuning_marker =
+            db_path.join(format!(".importance_tuning_{}", project_label));
 
         // Create embedding provider using model from config
-        let model_string = &config.embedding.model;
-        let (provider, model) = parse_provider_model(model_string)?;
+        let model_string = resolve_model_spec(&config.embedding.model)?;
+        let (provider, model) = parse_provider_model(&model_string)?;
         let embedding_provider = create_embedding_provider_from_parts(&provider, &model).await?;
 
-        let store = MemoryStore::new(
-            db_path.to_string_lossy().as_ref(),
+        let db_dir = (!ephemeral
+            && backend_kind != BackendKind::Postgres
+            && !(backend_kind == BackendKind::LanceDb && config.storage.is_remote()))
+        .then(|| db_path.clone());
+        let store = open_backend(BackendOptions {
+            db_dir: db_path,
             project_key,
             role,
             embedding_provider,
-            memory_config.clone(),
-            config.clone(),
+            config: memory_config.clone(),
+            main_config: config.clone(),
             reranker_integration,
-        )
+        })
         .await?;
 
+        let redactor = if memory_config.redaction_enabled {
+            Some(Redactor::new(&memory_config.redaction_patterns)?)
+        } else {
+            None
+        };
+
+        let author = Some(memory_config.author.trim().to_string())
+            .filter(|a| !a.is_empty())
+            .or_else(GitUtils::get_user_email);
+
         let mut manager = Self {
-            store: Arc::new(store),
+            store,
             config: memory_config,
             stale_check_marker,
             sleep_consolidation_marker,
+            importance_tuning_marker,
             pending_auto_links: Arc::new(AsyncMutex::new(Vec::new())),
             memorize_counter: Arc::new(AtomicUsize::new(0)),
             pending_maintenance: Arc::new(AsyncMutex::new(None)),
+            answer_config: config.search.answer.clone(),
+            vision_config: config.vision.clone(),
+            redactor,
+            attachments_root: (!ephemeral)
+                .then(crate::storage::get_attachments_dir)
+                .transpose()?,
+            author,
+            aliases_path: (!ephemeral)
+                .then(crate::storage::get_aliases_path)
+                .transpose()?,
+            feedback_path: (!ephemeral)
+                .then(crate::storage::get_feedback_path)
+                .transpose()?,
+            reviews_path: (!ephemeral)
+                .then(crate::storage::get_reviews_path)
+                .transpose()?,
+            db_dir,
         };
 
         // Lazy cleanup of stale file references on init (like knowledge session cleanup)
@@ -146,6 +415,9 @@ impl MemoryManager {
         if manager.config.sleep_consolidation_enabled {
             manager.maybe_sleep_consolidate().await.ok();
         }
+        if manager.config.importance_tuning_enabled {
+            manager.maybe_tune_importance().await.ok();
+        }
 
         Ok(manager)
     }
@@ -193,6 +465,43 @@ impl MemoryManager {
         Ok(())
     }
 
+    /// Run a usage-driven importance-tuning pass when the marker says one is due
+    /// (at most once per `importance_tuning_interval_hours`, see `claim_importance_tuning`).
+    /// Memories retrieved since the previous pass (with enough lifetime accesses)
+    /// gain one step of base importance; memories idle for `importance_tuning_idle_days`
+    /// lose one step. Returns the number of memories adjusted.
+    pub async fn maybe_tune_importance(&mut self) -> Result<usize> {
+        let now = Utc::now();
+        let Some(since) = claim_importance_tuning(
+            &self.importance_tuning_marker,
+            self.config.importance_tuning_interval_hours,
+            now,
+        )?
+        else {
+            return Ok(0);
+        };
+
+        let memories = self
+            .store
+            .get_recent_working_memories(chrono::DateTime::UNIX_EPOCH)
+            .await?;
+        let mut adjusted = 0;
+        for memory in &memories {
+            if let Some(new_importance) =
+                usage_adjusted_importance(memory, &self.config, since, now)
+            {
+                self.store
+                    .update_state_and_importance(&memory.id, MemoryState::Working, new_importance)
+                    .await?;
+                adjusted += 1;
+            }
+        }
+        if adjusted > 0 {
+            tracing::info!("Importance tuning: adjusted {} memories", adjusted);
+        }
+        Ok(adjusted)
+    }
+
     /// Read the last commit we scanned for stale references.
     fn read_stale_check_marker(&self) -> Option<String> {
         std::fs::read_to_string(&self.stale_check_marker)
@@ -401,13 +710,64 @@ impl MemoryManager {
         let MemorizeParams {
             memory_type,
             title,
-            content,
+            mut content,
             importance,
             tags,
             related_files,
             source,
+            allow_secrets,
+            attachments,
+            image,
+            custom_fields,
+            created_by,
         } = params;
 
+        let has_attachments = attachments.as_ref().is_some_and(|a| !a.is_empty());
+        if self.attachments_root.is_none() && (has_attachments || image.is_some()) {
+            return Err(anyhow::anyhow!(
+                "Attachments and images need on-disk storage, which ephemeral mode does not have"
+            ));
+        }
+
+        let max_attachment_bytes = self.config.max_attachment_size_kb.saturating_mul(1024);
+        let mut pending_attachments = attachments
+            .unwrap_or_default()
+            .iter()
+            .map(|path| read_attachment(Path::new(path), max_attachment_bytes))
+            .collect::<Result<Vec<_>>>()?;
+
+        let is_image = image.is_some();
+        if let Some(image_path) = image {
+            let path = Path::new(&image_path);
+            let pending = read_attachment(path, max_attachment_bytes)?;
+            let caption =
+                caption_image(&self.vision_config, path, &pending.bytes, Some(&title)).await?;
+            content = if content.trim().is_empty() {
+                caption
+            } else {
+                format!("{}\n\n{}", content.trim_end(), caption)
+            };
+            pending_attachments.insert(0, pending);
+        }
+
+        if !allow_secrets {
+            let mut scanned = format!("{}\n{}", title, content);
+            for pending in &pending_attachments {
+                if let Some(text) = &pending.extracted_text {
+                    scanned.push('\n');
+                    scanned.push_str(text);
+                }
+            }
+            let findings = detect_secrets(&scanned);
+            if !findings.is_empty() {
+                return Err(anyhow::anyhow!(
+                    "Refusing to store memory: content looks like it contains secrets: {}. \
+                     Remove them or pass allow_secrets to store anyway.",
+                    describe_findings(&findings)
+                ));
+            }
+        }
+
         // Initialize metadata with all values at once to satisfy clippy
         let mut metadata = MemoryMetadata {
             git_commit: GitUtils::get_current_commit(),
@@ -415,9 +775,15 @@ impl MemoryManager {
             tags: tags.unwrap_or_default(),
             related_files: Vec::new(),
             source: source.unwrap_or_default(),
+            custom_fields: custom_fields.unwrap_or_default(),
+            created_by: created_by.or_else(|| self.author.clone()),
             ..Default::default()
         };
 
+        if is_image && !metadata.tags.iter().any(|t| t == IMAGE_TAG) {
+            metadata.tags.push(IMAGE_TAG.to_string());
+        }
+
         // Add related files (convert to relative paths if possible)
         if let Some(files) = related_files {
             metadata.related_files = files
@@ -433,7 +799,16 @@ impl MemoryManager {
             }
         }
 
-        let memory = Memory::new(memory_type, title, content, Some(metadata));
+        let (title, content) = self.redact_text(title, content, &mut metadata.tags);
+        let mut memory = Memory::new(memory_type, title, content, Some(metadata));
+
+        if let Some(root) = &self.attachments_root {
+            for pending in pending_attachments {
+                let attachment =
+                    write_attachment(root, self.store.project_label(), &memory.id, pending)?;
+                memory.metadata.attachments.push(attachment);
+            }
+        }
 
         // Store the memory — caller waits only for this.
         self.store.store_memory(&memory).await?;
@@ -471,6 +846,54 @@ impl MemoryManager {
         Ok(memory)
     }
 
+    /// Absolute path of a stored attachment blob (None in ephemeral mode).
+    pub fn attachment_path(&self, attachment: &MemoryAttachment) -> Option<PathBuf> {
+        self.attachments_root
+            .as_ref()
+            .map(|root| resolve_path(root, attachment))
+    }
+
+    /// Apply PII redaction (when enabled) to a new memory's title and content,
+    /// tagging it `redacted` if anything was masked.
+    fn redact_text(
+        &self,
+        title: String,
+        content: String,
+        tags: &mut Vec<String>,
+    ) -> (String, String) {
+        let (title, content) = self.redact_optional(Some(title), Some(content), tags);
+        (title.unwrap_or_default(), content.unwrap_or_default())
+    }
+
+    /// Redaction for optional title/content (updates pass only changed fields).
+    fn redact_optional(
+        &self,
+        title: Option<String>,
+        content: Option<String>,
+        tags: &mut Vec<String>,
+    ) -> (Option<String>, Option<String>) {
+        let Some(redactor) = &self.redactor else {
+            return (title, content);
+        };
+        let mut replaced = 0;
+        let mut apply = |text: Option<String>| {
+            text.map(|t| {
+                let (masked, n) = redactor.redact(&t);
+                replaced += n;
+                masked
+            })
+        };
+        let title = apply(title);
+        let content = apply(content);
+        if replaced > 0 {
+            tracing::info!("Redacted {} sensitive value(s) before storage", replaced);
+            if !tags.iter().any(|t| t == REDACTED_TAG) {
+                tags.push(REDACTED_TAG.to_string());
+            }
+        }
+        (title, content)
+    }
+
     /// Await all in-flight fire-and-forget auto-link tasks and drain the
     /// handle list. Called by `consolidate_goal` (and any other operation
     /// that depends on the relationship graph being fully built) so we
@@ -543,10 +966,800 @@ impl MemoryManager {
         query: &str,
         filters: Option<MemoryQuery>,
     ) -> Result<Vec<MemorySearchResult>> {
-        let mut search_query = filters.unwrap_or_default();
-        search_query.query_text = Some(query.to_string());
+        let search_query = with_query_text(filters.unwrap_or_default(), query)?;
+        let mut results = self.store.search_memories(&search_query).await?;
+        self.rerank_by_feedback(&mut results, query)?;
+        Ok(results)
+    }
 
-        self.store.search_memories(&search_query).await
+    /// Re-rank `results` by the recorded feedback for `query`
+    fn rerank_by_feedback(&self, results: &mut [MemorySearchResult], query: &str) -> Result<()> {
+        if let Some(path) = &self.feedback_path {
+            let book = FeedbackBook::load(path)?;
+            apply_feedback(results, &book, self.store.project_label(), query);
+        }
+        Ok(())
+    }
+
+    /// Record whether a memory was a helpful result. `query` defaults to the
+    /// most recent search (within the last day) that returned the memory.
+    /// Returns the resolved memory ID and the query the vote was recorded for.
+    pub async fn record_feedback(
+        &self,
+        memory_ref: &str,
+        helpful: bool,
+        query: Option<String>,
+    ) -> Result<(String, String)> {
+        let path = self.feedback_path.as_ref().ok_or_else(|| {
+            anyhow::anyhow!("Feedback needs persistent storage; unavailable with --ephemeral")
+        })?;
+        let memory_id = self.resolve_memory_id(memory_ref).await?;
+        if self.store.get_memory(&memory_id).await?.is_none() {
+            anyhow::bail!("Memory not found: {}", memory_ref);
+        }
+        let query = match query.map(|q| q.trim().to_string()) {
+            Some(q) if !q.is_empty() => q,
+            _ => self
+                .store
+                .get_access_log(Utc::now() - Duration::days(1))
+                .await?
+                .into_iter()
+                .filter(|e| e.memory_id == memory_id)
+                .max_by_key(|e| e.accessed_at)
+                .map(|e| e.query)
+                .unwrap_or_default(),
+        };
+
+        let mut book = FeedbackBook::load(path)?;
+        book.record(
+            self.store.project_label(),
+            &memory_id,
+            FeedbackEntry {
+                query: query.clone(),
+                helpful,
+                at: Utc::now(),
+            },
+        );
+        book.save(path)?;
+        Ok((memory_id, query))
+    }
+
+    /// Ingest a WebVTT/SRT meeting transcript: extract decisions and learnings
+    /// (keyword heuristics, or per-segment LLM summaries when `summarize`) and store
+    /// each as a memory with its time range and attendees in `custom_fields`.
+    pub async fn ingest_transcript(
+        &mut self,
+        path: &Path,
+        summarize: bool,
+        tags: Option<Vec<String>>,
+        allow_secrets: bool,
+    ) -> Result<Vec<Memory>> {
+        use super::transcript::{
+            attendees, extract_items_heuristic, parse_transcript, segment_cues, summarize_segment,
+            SEGMENT_CHARS, TRANSCRIPT_TAG,
+        };
+
+        let raw = std::fs::read_to_string(path)
+            .map_err(|e| anyhow::anyhow!("Cannot read transcript '{}': {}", path.display(), e))?;
+        let cues = parse_transcript(&raw);
+        if cues.is_empty() {
+            return Err(anyhow::anyhow!(
+                "No cues found in '{}'; expected a WebVTT or SRT file",
+                path.display()
+            ));
+        }
+        let file_name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| path.display().to_string());
+        let all_attendees = attendees(&cues);
+
+        let items = if summarize {
+            let mut items = Vec::new();
+            for segment in segment_cues(&cues, SEGMENT_CHARS) {
+                items.extend(summarize_segment(&self.answer_config, &segment).await?);
+            }
+            items
+        } else {
+            extract_items_heuristic(&cues)
+        };
+
+        let mut base_tags = tags.unwrap_or_default();
+        if !base_tags.iter().any(|t| t == TRANSCRIPT_TAG) {
+            base_tags.push(TRANSCRIPT_TAG.to_string());
+        }
+
+        let mut stored = Vec::with_capacity(items.len());
+        for item in items {
+            let summary: String = item.text.chars().take(80).collect();
+            let label = match item.memory_type {
+                MemoryType::Decision => "Decision",
+                _ => "Learning",
+            };
+            let title = format!("{}: {}", label, summary);
+            let mut content = format!(
+                "{}\n\nFrom transcript {} [{} – {}]",
+                item.text, file_name, item.start, item.end
+            );
+            if !item.speakers.is_empty() {
+                content.push_str(&format!("\nSpeakers: {}", item.speakers.join(", ")));
+            }
+            if !all_attendees.is_empty() {
+                content.push_str(&format!("\nAttendees: {}", all_attendees.join(", ")));
+            }
+
+            let mut custom_fields = HashMap::new();
+            custom_fields.insert("transcript".to_string(), file_name.clone());
+            custom_fields.insert("transcript_start".to_string(), item.start.clone());
+            custom_fields.insert("transcript_end".to_string(), item.end.clone());
+            custom_fields.insert("attendees".to_string(), all_attendees.join(", "));
+            if !item.speakers.is_empty() {
+                custom_fields.insert("speakers".to_string(), item.speakers.join(", "));
+            }
+
+            let memory = self
+                .memorize(MemorizeParams {
+                    memory_type: item.memory_type,
+                    title,
+                    content,
+                    importance: None,
+                    tags: Some(base_tags.clone()),
+                    related_files: None,
+                    source: None,
+                    allow_secrets,
+                    attachments: None,
+                    image: None,
+                    custom_fields: Some(custom_fields),
+                    created_by: None,
+                })
+                .await?;
+            stored.push(memory);
+        }
+        Ok(stored)
+    }
+
+    /// Import facts, preferences and decisions from a ChatGPT/Claude conversation
+    /// export. Each fact becomes an `Imported` memory referencing its conversation
+    /// in `custom_fields`. With `dry_run` nothing is stored.
+    pub async fn import_conversations(
+        &mut self,
+        path: &Path,
+        format: ConversationFormat,
+        use_llm: bool,
+        dry_run: bool,
+    ) -> Result<ConversationImportReport> {
+        let raw = std::fs::read_to_string(path)
+            .map_err(|e| anyhow::anyhow!("Cannot read export '{}': {}", path.display(), e))?;
+        let conversations = parse_export(&raw, format)?;
+
+        let mut report = ConversationImportReport {
+            conversations: conversations.len(),
+            ..Default::default()
+        };
+        for conversation in &conversations {
+            let facts = if use_llm {
+                extract_facts_llm(&self.answer_config, conversation).await?
+            } else {
+                extract_facts_heuristic(conversation)
+            };
+
+            for fact in facts {
+                if !dry_run {
+                    let title: String = fact.text.chars().take(80).collect();
+                    let mut custom_fields = HashMap::new();
+                    custom_fields.insert("conversation_source".to_string(), format.as_str().into());
+                    custom_fields.insert("conversation_id".to_string(), conversation.id.clone());
+                    custom_fields
+                        .insert("conversation_title".to_string(), conversation.title.clone());
+                    if let Some(created_at) = &conversation.created_at {
+                        custom_fields.insert("conversation_date".to_string(), created_at.clone());
+                    }
+                    let result = self
+                        .memorize(MemorizeParams {
+                            memory_type: fact.memory_type.clone(),
+                            title,
+                            content: format!(
+                                "{}\n\nFrom {} conversation \"{}\"",
+                                fact.text,
+                                format.as_str(),
+                                conversation.title
+                            ),
+                            importance: None,
+                            tags: Some(vec![IMPORTED_TAG.to_string(), format.as_str().to_string()]),
+                            related_files: None,
+                            source: Some(MemorySource::Imported),
+                            allow_secrets: false,
+                            attachments: None,
+                            image: None,
+                            custom_fields: Some(custom_fields),
+                            created_by: None,
+                        })
+                        .await;
+                    match result {
+                        Ok(_) => report.stored += 1,
+                        Err(e) => report.skipped.push(format!("{}: {}", fact.text, e)),
+                    }
+                }
+                report.facts.push((conversation.title.clone(), fact));
+            }
+        }
+        Ok(report)
+    }
+
+    /// Import decisions, fixes and failed tool results from coding-agent session
+    /// logs (a file or a directory of them). Memories are tagged with the session
+    /// and repo so past sessions become searchable context.
+    pub async fn import_agent_sessions(
+        &mut self,
+        path: &Path,
+        format: SessionFormat,
+        dry_run: bool,
+    ) -> Result<ConversationImportReport> {
+        let sessions = load_sessions(path, format)?;
+        let mut report = ConversationImportReport {
+            conversations: sessions.len(),
+            ..Default::default()
+        };
+
+        for session in &sessions {
+            let mut tags = vec![
+                SESSION_TAG.to_string(),
+                format.as_str().to_string(),
+                format!("session:{}", session.id),
+            ];
+            if let Some(repo) = session.repo_name() {
+                tags.push(format!("repo:{}", repo));
+            }
+
+            for item in extract_session_items(session) {
+                if !dry_run {
+                    let first_line = item.text.lines().next().unwrap_or_default();
+                    let title: String = first_line.chars().take(80).collect();
+                    let mut custom_fields = HashMap::new();
+                    custom_fields.insert("session_id".to_string(), session.id.clone());
+                    custom_fields.insert("session_format".to_string(), format.as_str().into());
+                    if let Some(repo) = &session.repo {
+                        custom_fields.insert("repo".to_string(), repo.clone());
+                    }
+                    let result = self
+                        .memorize(MemorizeParams {
+                            memory_type: item.memory_type.clone(),
+                            title,
+                            content: format!(
+                                "{}\n\nFrom {} session {}",
+                                item.text,
+                                format.as_str(),
+                                session.id
+                            ),
+                            importance: None,
+                            tags: Some(tags.clone()),
+                            related_files: None,
+                            source: Some(MemorySource::Imported),
+                            allow_secrets: false,
+                            attachments: None,
+                            image: None,
+                            custom_fields: Some(custom_fields),
+                            created_by: None,
+                        })
+                        .await;
+                    match result {
+                        Ok(_) => report.stored += 1,
+                        Err(e) => report.skipped.push(format!("{}: {}", first_line, e)),
+                    }
+                }
+                report.facts.push((session.id.clone(), item));
+            }
+        }
+        Ok(report)
+    }
+
+    /// All memories in scope matching any of `types` and any of `tags` (empty
+    /// filters match everything), oldest first. Does not count as an access.
+    pub async fn export_memories(
+        &self,
+        types: &[MemoryType],
+        tags: &[String],
+    ) -> Result<Vec<Memory>> {
+        let mut memories: Vec<Memory> = self
+            .store
+            .get_all_memories()
+            .await?
+            .into_iter()
+            .filter(|m| types.is_empty() || types.contains(&m.memory_type))
+            .filter(|m| tags.is_empty() || m.metadata.tags.iter().any(|t| tags.contains(t)))
+            .collect();
+        memories.sort_by_key(|m| m.created_at);
+        Ok(memories)
+    }
+
+    /// Write the memories matching `types`/`tags`, with their stored
+    /// embeddings, to `path` as an Arrow IPC file. Returns how many were written.
+    pub async fn export_arrow(
+        &self,
+        types: &[MemoryType],
+        tags: &[String],
+        path: &Path,
+    ) -> Result<usize> {
+        let memories = self.export_memories(types, tags).await?;
+        let embeddings = self.store.get_all_embeddings().await?;
+        let dimension = self.store.embedding_dimension().await?;
+        let file = std::fs::File::create(path)
+            .with_context(|| format!("Cannot create '{}'", path.display()))?;
+        write_arrow(
+            std::io::BufWriter::new(file),
+            &memories,
+            &embeddings,
+            dimension,
+        )?;
+        Ok(memories.len())
+    }
+
+    /// Store the memories of an Arrow IPC export under their original IDs.
+    /// The file is validated first: its schema must match and its embeddings
+    /// must have the dimension of the configured model. Rows exported without
+    /// an embedding are re-embedded. With `dry_run` nothing is stored.
+    pub async fn import_arrow(&self, path: &Path, dry_run: bool) -> Result<ColumnarImportReport> {
+        let file = std::fs::File::open(path)
+            .with_context(|| format!("Cannot read export '{}'", path.display()))?;
+        let import = read_arrow(std::io::BufReader::new(file))
+            .with_context(|| format!("Invalid columnar export '{}'", path.display()))?;
+
+        let has_embeddings = import.memories.iter().any(|(_, e)| e.is_some());
+        if has_embeddings {
+            let expected = self.store.embedding_dimension().await?;
+            if import.dimension != expected {
+                return Err(anyhow::anyhow!(
+                    "Export has {}-dimension embeddings but the configured model produces {}; \
+                     import it with the model it was exported with",
+                    import.dimension,
+                    expected
+                ));
+            }
+        }
+
+        let mut report = ColumnarImportReport {
+            rows: import.memories.len(),
+            ..Default::default()
+        };
+        if dry_run {
+            return Ok(report);
+        }
+        report.reembedded = self.store.store_memories_batch(&import.memories).await?;
+        report.stored = import.memories.len();
+        Ok(report)
+    }
+
+    /// Write the memories matching `types`/`tags`, their embeddings and the
+    /// relationships between them as JSONL (see `memory::jsonl`). Returns the
+    /// number of memories and relationships written.
+    pub async fn export_jsonl<W: std::io::Write + Send>(
+        &self,
+        types: &[MemoryType],
+        tags: &[String],
+        writer: W,
+    ) -> Result<(usize, usize)> {
+        let memories = self.export_memories(types, tags).await?;
+        let ids: HashSet<&str> = memories.iter().map(|m| m.id.as_str()).collect();
+        let relationships: Vec<MemoryRelationship> = self
+            .store
+            .get_all_relationships()
+            .await?
+            .into_iter()
+            .filter(|r| ids.contains(r.source_id.as_str()) && ids.contains(r.target_id.as_str()))
+            .collect();
+        let embeddings = self.store.get_all_embeddings().await?;
+        let dimension = self.store.embedding_dimension().await?;
+        write_jsonl(writer, &memories, &relationships, &embeddings, dimension)?;
+        Ok((memories.len(), relationships.len()))
+    }
+
+    /// Restore a JSONL backup, keeping memory and relationship IDs (see
+    /// [`import_jsonl_into`]). With `dry_run` it is only parsed and counted.
+    pub async fn import_jsonl(&self, path: &Path, dry_run: bool) -> Result<JsonlImportReport> {
+        import_jsonl_into(self.store.as_ref(), path, dry_run).await
+    }
+
+    /// Create the memories of a batch file (`memory import-batch`). Every entry
+    /// is checked for secrets before anything is stored; then `chunk_size`
+    /// memories at a time are embedded and written together by the backend.
+    /// Files are kept as given instead of being guessed from the working tree.
+    /// `progress` receives (stored, total) after each chunk.
+    pub async fn memorize_batch(
+        &mut self,
+        entries: Vec<BatchEntry>,
+        chunk_size: usize,
+        allow_secrets: bool,
+        progress: &mut (dyn FnMut(usize, usize) + Send),
+    ) -> Result<Vec<Memory>> {
+        if !allow_secrets {
+            for entry in &entries {
+                let findings = detect_secrets(&format!("{}\n{}", entry.title, entry.content));
+                if !findings.is_empty() {
+                    return Err(anyhow::anyhow!(
+                        "Refusing to import batch: memory '{}' looks like it contains secrets: {}. \
+                         Remove them or pass allow_secrets to store anyway.",
+                        entry.title,
+                        describe_findings(&findings)
+                    ));
+                }
+            }
+        }
+
+        let git_commit = GitUtils::get_current_commit();
+        let memories: Vec<Memory> = entries
+            .into_iter()
+            .map(|entry| {
+                let mut metadata = MemoryMetadata {
+                    git_commit: git_commit.clone(),
+                    importance: entry.importance.unwrap_or(self.config.default_importance),
+                    tags: entry.tags,
+                    related_files: entry
+                        .files
+                        .into_iter()
+                        .map(|file| GitUtils::get_relative_path(&file).unwrap_or(file))
+                        .collect(),
+                    source: MemorySource::Imported,
+                    created_by: self.author.clone(),
+                    ..Default::default()
+                };
+                let (title, content) =
+                    self.redact_text(entry.title, entry.content, &mut metadata.tags);
+                Memory::new(
+                    MemoryType::from(entry.memory_type),
+                    title,
+                    content,
+                    Some(metadata),
+                )
+            })
+            .collect();
+
+        let total = memories.len();
+        let mut stored = 0;
+        for chunk in memories.chunks(chunk_size.max(1)) {
+            self.store.store_memories(chunk).await?;
+            stored += chunk.len();
+            progress(stored, total);
+        }
+
+        let before = self.memorize_counter.fetch_add(total, Ordering::Relaxed);
+        if (before + total) / MAINTENANCE_EVERY_N_WRITES > before / MAINTENANCE_EVERY_N_WRITES {
+            self.spawn_maintenance_if_idle();
+        }
+
+        // One background task links the whole batch, like memorize does per memory
+        if self.config.auto_linking_enabled {
+            let store = self.store.clone();
+            let config = self.config.clone();
+            let ids: Vec<String> = memories.iter().map(|m| m.id.clone()).collect();
+            let handle = tokio::spawn(async move {
+                for memory_id in ids {
+                    if let Err(e) =
+                        auto_link_memory_impl(store.clone(), config.clone(), &memory_id).await
+                    {
+                        tracing::warn!(
+                            "auto-link for imported memory '{}' failed: {}",
+                            memory_id,
+                            e
+                        );
+                    }
+                }
+            });
+            self.pending_auto_links.lock().await.push(handle);
+        }
+
+        Ok(memories)
+    }
+
+    /// Build a bundle of the memories matching `types`/`tags`, signed with the
+    /// local share key (generated on first use).
+    pub async fn export_share_bundle(
+        &self,
+        types: &[MemoryType],
+        tags: &[String],
+        sender: String,
+    ) -> Result<(ShareBundle, usize)> {
+        let memories = self.export_memories(types, tags).await?;
+        let key = load_or_create_key(&crate::storage::get_share_key_path()?)?;
+        let payload = SharePayload {
+            version: BUNDLE_VERSION,
+            sender,
+            created_at: Utc::now(),
+            memories: memories.iter().map(SharedMemory::from).collect(),
+        };
+        Ok((sign_bundle(&key, &payload)?, payload.memories.len()))
+    }
+
+    /// Conflict strategy for merges: `requested` (e.g. `--on-conflict`) or the
+    /// configured `[memory].conflict_strategy`.
+    pub fn conflict_strategy(&self, requested: Option<&str>) -> Result<ConflictStrategy> {
+        ConflictStrategy::parse(requested.unwrap_or(&self.config.conflict_strategy))
+    }
+
+    /// Verify a shared bundle and store its memories attributed to the sender
+    /// (`shared_by` / `shared_key` custom fields and a `from:<sender>` tag).
+    /// The signer must be `trusted_fingerprint` when given, else one of
+    /// `[memory].trusted_share_keys`; with neither, any signer is accepted
+    /// with a warning.
+    /// Memories already imported from the same key are merged through
+    /// `merge_memory`: unchanged copies are skipped, newer versions replace
+    /// untouched local copies, and locally edited ones follow `strategy`.
+    pub async fn import_share_bundle(
+        &mut self,
+        bundle: &ShareBundle,
+        trusted_fingerprint: Option<&str>,
+        strategy: ConflictStrategy,
+        mut prompt: Option<&mut dyn ConflictPrompt>,
+    ) -> Result<ShareImportReport> {
+        let trusted = match trusted_fingerprint {
+            Some(fingerprint) => vec![fingerprint.to_string()],
+            None => self.config.trusted_share_keys.clone(),
+        };
+        let payload = verify_bundle(bundle, &trusted)?;
+        let fingerprint = key_fingerprint(&hex::decode(&bundle.public_key)?);
+        if trusted.is_empty() {
+            tracing::warn!(
+                "Importing a bundle from unknown signer {} ('{}'); no trusted share keys are configured",
+                fingerprint,
+                payload.sender
+            );
+        }
+
+        // Divergent copies kept by an earlier merge carry `conflict_of`; match originals only
+        let already_imported: HashMap<String, Memory> = self
+            .store
+            .get_all_memories()
+            .await?
+            .into_iter()
+            .filter(|m| {
+                m.metadata
+                    .custom_fields
+                    .get("shared_key")
+                    .is_some_and(|key| same_key(key, &fingerprint))
+            })
+            .filter(|m| !m.metadata.custom_fields.contains_key("conflict_of"))
+            .filter_map(|m| {
+                let shared_id = m.metadata.custom_fields.get("shared_id")?.clone();
+                Some((shared_id, m))
+            })
+            .collect();
+
+        let mut report = ShareImportReport {
+            sender: payload.sender.clone(),
+            fingerprint: fingerprint.clone(),
+            trusted: !trusted.is_empty(),
+            ..Default::default()
+        };
+        for shared in payload.memories {
+            let mut tags = shared.tags.clone();
+            for tag in [SHARE_TAG.to_string(), format!("from:{}", payload.sender)] {
+                if !tags.contains(&tag) {
+                    tags.push(tag);
+                }
+            }
+
+            if let Some(local) = already_imported.get(&shared.id) {
+                let mut incoming = local.clone();
+                incoming.memory_type = shared.memory_type;
+                incoming.title = shared.title;
+                incoming.content = shared.content;
+                incoming.metadata.importance = shared.importance;
+                incoming.metadata.tags = tags;
+                incoming.metadata.related_files = shared.related_files;
+                incoming.updated_at = shared.updated_at.unwrap_or(shared.created_at);
+                // Imported copies start with updated_at <= created_at; later is a local edit
+                let local_changed = local.updated_at > local.created_at;
+
+                let outcome = self
+                    .merge_memory(
+                        Some(local),
+                        incoming,
+                        local_changed,
+                        strategy,
+                        prompt.as_deref_mut(),
+                    )
+                    .await?;
+                match outcome {
+                    MergeOutcome::KeptLocal { conflict: false } => report.duplicates += 1,
+                    MergeOutcome::Replaced { conflict: false } => report.updated += 1,
+                    _ => report.conflicts += 1,
+                }
+                continue;
+            }
+
+            let mut custom_fields = HashMap::new();
+            custom_fields.insert("shared_by".to_string(), payload.sender.clone());
+            custom_fields.insert("shared_key".to_string(), fingerprint.clone());
+            custom_fields.insert("shared_id".to_string(), shared.id.clone());
+            custom_fields.insert(
+                "shared_created_at".to_string(),
+                shared.created_at.to_rfc3339(),
+            );
+
+            let result = self
+                .memorize(MemorizeParams {
+                    memory_type: shared.memory_type,
+                    title: shared.title.clone(),
+                    content: shared.content,
+                    importance: Some(shared.importance),
+                    tags: Some(tags),
+                    related_files: Some(shared.related_files),
+                    source: Some(MemorySource::Imported),
+                    allow_secrets: false,
+                    attachments: None,
+                    image: None,
+                    custom_fields: Some(custom_fields),
+                    created_by: Some(payload.sender.clone()),
+                })
+                .await;
+            match result {
+                Ok(_) => report.stored += 1,
+                Err(e) => report.skipped.push(format!("{}: {}", shared.title, e)),
+            }
+        }
+        Ok(report)
+    }
+
+    /// Memories in scope updated after `since` (all when `None`), oldest first.
+    /// Used by sync to find changes to replicate; does not count as an access.
+    pub async fn changed_since(&self, since: Option<chrono::DateTime<Utc>>) -> Result<Vec<Memory>> {
+        let mut memories: Vec<Memory> = self
+            .store
+            .get_all_memories()
+            .await?
+            .into_iter()
+            .filter(|m| since.is_none_or(|since| m.updated_at > since))
+            .collect();
+        memories.sort_by_key(|m| m.updated_at);
+        Ok(memories)
+    }
+
+    /// Relationships in scope created after `since` (all when `None`), for sync
+    pub async fn relationships_since(
+        &self,
+        since: Option<chrono::DateTime<Utc>>,
+    ) -> Result<Vec<MemoryRelationship>> {
+        Ok(self
+            .store
+            .get_all_relationships()
+            .await?
+            .into_iter()
+            .filter(|r| since.is_none_or(|since| r.created_at > since))
+            .collect())
+    }
+
+    /// Store relationships replicated from another machine that are new here
+    /// and link two memories present here; returns how many were added.
+    /// Call after `apply_synced` so the memories they link are in place.
+    pub async fn apply_synced_relationships(
+        &self,
+        relationships: Vec<MemoryRelationship>,
+    ) -> Result<usize> {
+        if relationships.is_empty() {
+            return Ok(0);
+        }
+        let existing = self.store.get_all_relationships().await?;
+        let memory_ids: HashSet<String> = self
+            .store
+            .get_all_memories()
+            .await?
+            .into_iter()
+            .map(|m| m.id)
+            .collect();
+        let added = new_relationships(&existing, relationships, &memory_ids);
+        self.store.store_relationships(&added).await?;
+        Ok(added.len())
+    }
+
+    /// Upsert memories replicated from another machine, keeping ids and timestamps.
+    /// Local copies edited after `base` (the last sync) are conflicts and follow
+    /// `strategy`; see `merge_memory`.
+    pub async fn apply_synced(
+        &mut self,
+        memories: Vec<Memory>,
+        base: Option<chrono::DateTime<Utc>>,
+        strategy: ConflictStrategy,
+        mut prompt: Option<&mut dyn ConflictPrompt>,
+    ) -> Result<SyncApplied> {
+        let local: HashMap<String, Memory> = self
+            .store
+            .get_all_memories()
+            .await?
+            .into_iter()
+            .map(|m| (m.id.clone(), m))
+            .collect();
+
+        let mut result = SyncApplied::default();
+        for memory in memories {
+            let existing = local.get(&memory.id);
+            let local_changed =
+                existing.is_some_and(|l| base.is_none_or(|base| l.updated_at > base));
+            let outcome = self
+                .merge_memory(
+                    existing,
+                    memory,
+                    local_changed,
+                    strategy,
+                    prompt.as_deref_mut(),
+                )
+                .await?;
+            if outcome.is_conflict() {
+                result.conflicts += 1;
+            }
+            if outcome.is_applied() {
+                result.applied += 1;
+            } else {
+                result.skipped += 1;
+            }
+        }
+        Ok(result)
+    }
+
+    /// Merge one incoming copy of a memory into the store — the single place
+    /// sync and import choose between a local and an incoming version (rules in
+    /// `conflict::resolve`). `local_changed` says whether the local copy was
+    /// edited since the last merge.
+    pub async fn merge_memory(
+        &mut self,
+        local: Option<&Memory>,
+        incoming: Memory,
+        local_changed: bool,
+        strategy: ConflictStrategy,
+        prompt: Option<&mut (dyn ConflictPrompt + '_)>,
+    ) -> Result<MergeOutcome> {
+        let Some(local) = local else {
+            self.store.store_memory(&incoming).await?;
+            return Ok(MergeOutcome::Inserted);
+        };
+        let conflict = is_conflict(local, &incoming, local_changed);
+
+        match resolve(strategy, local, &incoming, local_changed, prompt)? {
+            Resolution::TakeIncoming => {
+                self.store.store_revision(local).await?;
+                self.store.store_memory(&incoming).await?;
+                Ok(MergeOutcome::Replaced { conflict })
+            }
+            Resolution::KeepLocal => {
+                if conflict && incoming.updated_at >= local.updated_at {
+                    // Re-stamp the chosen older copy so the choice replicates onward
+                    let mut kept = local.clone();
+                    kept.updated_at = Utc::now();
+                    self.store.store_memory(&kept).await?;
+                }
+                Ok(MergeOutcome::KeptLocal { conflict })
+            }
+            Resolution::KeepBoth => {
+                let mut copy = incoming;
+                copy.id = uuid::Uuid::new_v4().to_string();
+                copy.updated_at = Utc::now();
+                copy.metadata
+                    .custom_fields
+                    .insert("conflict_of".to_string(), local.id.clone());
+                self.store.store_memory(&copy).await?;
+                self.create_relationship(
+                    copy.id.clone(),
+                    local.id.clone(),
+                    RelationshipType::Conflicts,
+                    1.0,
+                    "Divergent copy kept during merge".to_string(),
+                )
+                .await?;
+                Ok(MergeOutcome::KeptBoth { copy_id: copy.id })
+            }
+        }
+    }
+
+    /// Search memories and synthesize a short cited answer from the top results.
+    /// Returns the answer together with the memories it was built from.
+    pub async fn answer(
+        &self,
+        queries: &[String],
+        filters: Option<MemoryQuery>,
+    ) -> Result<(MemoryAnswer, Vec<MemorySearchResult>)> {
+        let results = self.remember_multi(queries, filters).await?;
+        let question = queries.join("; ");
+        let answer =
+            super::answer::synthesize_answer(&self.answer_config, &question, &results).await?;
+        Ok((answer, results))
     }
 
     /// Remember (search) memories based on multiple queries with relevance-based merging
@@ -573,10 +1786,9 @@ impl MemoryManager {
 
         // Search with each query
         for query in queries {
-            let mut search_query = base_filters.clone();
-            search_query.query_text = Some(query.clone());
-
-            let results = self.store.search_memories(&search_query).await?;
+            let search_query = with_query_text(base_filters.clone(), query)?;
+            let mut results = self.store.search_memories(&search_query).await?;
+            self.rerank_by_feedback(&mut results, query)?;
 
             for result in results {
                 let memory_id = result.memory.id.clone();
@@ -639,15 +1851,99 @@ impl MemoryManager {
 
     /// Forget memories matching criteria
     pub async fn forget_matching(&mut self, query: MemoryQuery) -> Result<usize> {
+        let ids: Vec<String> = self
+            .matching_memories(query)
+            .await?
+            .into_iter()
+            .map(|m| m.id)
+            .collect();
+        self.forget_ids(&ids).await
+    }
+
+    /// Memories `forget_matching` would delete for `query`, without deleting them.
+    pub async fn matching_memories(&self, query: MemoryQuery) -> Result<Vec<Memory>> {
         let search_results = self.store.search_memories(&query).await?;
-        let mut deleted_count = 0;
+        Ok(search_results.into_iter().map(|r| r.memory).collect())
+    }
+
+    /// Forget exactly these memories as one operation. Returns the number deleted.
+    pub async fn forget_ids(&mut self, ids: &[String]) -> Result<usize> {
+        let deleted = self.store.delete_memories(ids).await?;
+        self.purge_expired_trash().await?;
+        Ok(deleted)
+    }
+
+    /// Restore the memories removed by the last `operations` forget calls from the
+    /// recovery buffer. Auto-links are rebuilt for restored memories; explicit
+    /// relationships are not recoverable.
+    pub async fn undo_forget(&mut self, operations: usize) -> Result<Vec<Memory>> {
+        let restored = self.store.restore_last_deletions(operations).await?;
+        if self.config.auto_linking_enabled {
+            for memory in &restored {
+                self.auto_link_memory(&memory.id).await.ok();
+            }
+        }
+        Ok(restored)
+    }
+
+    /// Memories currently held in the recovery buffer with their deletion time.
+    pub async fn list_recoverable(&self) -> Result<Vec<(Memory, String)>> {
+        self.store.list_recoverable().await
+    }
+
+    /// Restore trashed memories by ID or unique ID prefix, whichever forget or
+    /// cleanup removed them. Auto-links are rebuilt; explicit relationships are
+    /// not recoverable. Errors on references matching nothing or several entries.
+    pub async fn restore_from_trash(&mut self, references: &[String]) -> Result<Vec<Memory>> {
+        // A memory deleted more than once has several entries
+        let mut trashed: Vec<String> = self
+            .store
+            .list_recoverable()
+            .await?
+            .into_iter()
+            .map(|(memory, _)| memory.id)
+            .collect();
+        trashed.sort();
+        trashed.dedup();
+        let mut ids: Vec<String> = Vec::with_capacity(references.len());
+        for reference in references {
+            let reference = reference.trim();
+            let matches: Vec<&String> = trashed
+                .iter()
+                .filter(|id| id.starts_with(reference))
+                .collect();
+            match matches.as_slice() {
+                [id] => ids.push((*id).clone()),
+                [] => anyhow::bail!("Memory '{}' is not in the trash", reference),
+                _ => anyhow::bail!(
+                    "Memory ID prefix '{}' is ambiguous in the trash; use more characters",
+                    reference
+                ),
+            }
+        }
 
-        for result in search_results {
-            self.store.delete_memory(&result.memory.id).await?;
-            deleted_count += 1;
+        let restored = self.store.restore_deleted(&ids).await?;
+        if self.config.auto_linking_enabled {
+            for memory in &restored {
+                self.auto_link_memory(&memory.id).await.ok();
+            }
+        }
+        Ok(restored)
+    }
+
+    /// Permanently delete trash entries older than `trash_retention_days`
+    /// (none when it is 0). Returns entries purged.
+    pub async fn purge_expired_trash(&self) -> Result<usize> {
+        if self.config.trash_retention_days == 0 {
+            return Ok(0);
         }
+        let cutoff = Utc::now() - chrono::Duration::days(self.config.trash_retention_days as i64);
+        self.store.purge_deleted(Some(cutoff)).await
+    }
 
-        Ok(deleted_count)
+    /// Permanently delete everything in the trash. Returns entries purged.
+    pub async fn empty_trash(&self) -> Result<usize> {
+        self.store.purge_deleted(None).await
     }
     /// Update an existing memory
     pub async fn update_memory(
@@ -658,8 +1954,17 @@ impl MemoryManager {
         metadata_updates: Option<MemoryMetadata>,
     ) -> Result<Option<Memory>> {
         if let Some(mut memory) = self.store.get_memory(memory_id).await? {
+            // Keep the version being replaced for `memory history` / `rollback`
+            self.store.store_revision(&memory).await?;
+
             // Update Git commit to current
             let current_commit = GitUtils::get_current_commit();
+            let mut metadata_updates = metadata_updates;
+            let tags = match metadata_updates.as_mut() {
+                Some(meta) => &mut meta.tags,
+                None => &mut memory.metadata.tags,
+            };
+            let (title, content) = self.redact_optional(title, content, tags);
             if let Some(mut meta) = metadata_updates {
                 meta.git_commit = current_commit.clone();
                 memory.update(title, content, Some(meta));
@@ -686,18 +1991,251 @@ impl MemoryManager {
         }
     }
 
+    /// Apply a partial `edit` to a memory in place: ID, relationships and access
+    /// history are kept and the replaced version is saved as a revision.
+    /// Returns `None` when the memory does not exist.
+    pub async fn edit_memory(
+        &mut self,
+        memory_id: &str,
+        mut edit: MemoryEdit,
+    ) -> Result<Option<Memory>> {
+        let Some(memory) = self.store.get_memory(memory_id).await? else {
+            return Ok(None);
+        };
+
+        if !edit.allow_secrets {
+            let scanned = [edit.title.as_deref(), edit.content.as_deref()]
+                .into_iter()
+                .flatten()
+                .collect::<Vec<_>>()
+                .join("\n");
+            let findings = detect_secrets(&scanned);
+            if !findings.is_empty() {
+                return Err(anyhow::anyhow!(
+                    "Refusing to update memory: content looks like it contains secrets: {}. \
+                     Remove them or pass allow_secrets to store anyway.",
+                    describe_findings(&findings)
+                ));
+            }
+        }
+
+        // Stored file paths are repository-relative, like in memorize()
+        for files in [&mut edit.add_files, &mut edit.remove_files] {
+            *files = files
+                .drain(..)
+                .map(|file| GitUtils::get_relative_path(&file).unwrap_or(file))
+                .collect();
+        }
+
+        let metadata = edit.revised_metadata(&memory);
+        self.update_memory(memory_id, edit.title, edit.content, Some(metadata))
+            .await
+    }
+
+    /// Earlier versions of a memory saved by updates, oldest first.
+    pub async fn memory_history(&self, memory_id: &str) -> Result<Vec<MemoryRevision>> {
+        self.store.get_revisions(memory_id).await
+    }
+
+    /// Restore a memory to the content of `revision`. The version being replaced
+    /// is saved as a new revision first, so a rollback can itself be undone.
+    /// Access statistics and the ID stay as they are. Returns the restored memory
+    /// and the revision number the replaced version was saved under, or `None`
+    /// when the memory does not exist.
+    pub async fn rollback_memory(
+        &mut self,
+        memory_id: &str,
+        revision: u32,
+    ) -> Result<Option<(Memory, u32)>> {
+        let Some(current) = self.store.get_memory(memory_id).await? else {
+            return Ok(None);
+        };
+        let revisions = self.store.get_revisions(memory_id).await?;
+        let Some(target) = revisions.iter().find(|r| r.revision == revision) else {
+            return Err(match revisions.last() {
+                Some(latest) => anyhow::anyhow!(
+                    "Memory '{}' has no revision {} (revisions 1-{})",
+                    memory_id,
+                    revision,
+                    latest.revision
+                ),
+                None => anyhow::anyhow!("Memory '{}' has no saved revisions", memory_id),
+            });
+        };
+
+        let saved_as = self.store.store_revision(&current).await?;
+        let mut restored = target.memory.clone();
+        restored.id = current.id.clone();
+        restored.created_at = current.created_at;
+        restored.updated_at = Utc::now();
+        restored.metadata.decay = current.metadata.decay.clone();
+        restored.relevance_score = None;
+        self.store.update_memory(&restored).await?;
+
+        if self.config.auto_linking_enabled {
+            self.store
+                .delete_auto_linked_relationships(memory_id)
+                .await?;
+            self.auto_link_memory(memory_id).await?;
+        }
+        Ok(Some((restored, saved_as)))
+    }
+
     /// Get memory by ID
     pub async fn get_memory(&self, memory_id: &str) -> Result<Option<Memory>> {
         self.store.get_memory(memory_id).await
     }
 
+    /// Turn an alias or a unique ID prefix into a full memory ID.
+    /// Full IDs and references that match nothing are returned unchanged, so
+    /// the caller's usual "not found" handling applies.
+    pub async fn resolve_memory_id(&self, reference: &str) -> Result<String> {
+        let reference = reference.trim();
+        if let Some(path) = &self.aliases_path {
+            let book = AliasBook::load(path)?;
+            if let Some(id) = book.get(self.store.project_label(), reference) {
+                return Ok(id.to_string());
+            }
+        }
+        if reference.len() >= 36 || !is_id_fragment(reference) || reference.len() < 4 {
+            return Ok(reference.to_string());
+        }
+        let matches = self.store.memory_ids_with_prefix(reference, 2).await?;
+        match matches.as_slice() {
+            [id] => Ok(id.clone()),
+            [] => Ok(reference.to_string()),
+            _ => anyhow::bail!(
+                "Memory ID prefix '{}' is ambiguous; use more characters",
+                reference
+            ),
+        }
+    }
+
+    /// Shortest unique prefix of each ID among all memories in scope
+    pub async fn short_ids(&self, ids: &[String]) -> Result<HashMap<String, String>> {
+        let all: Vec<String> = self
+            .store
+            .get_all_memories()
+            .await?
+            .into_iter()
+            .map(|m| m.id)
+            .collect();
+        let prefixes = shortest_unique_prefixes(&all, MIN_SHORT_ID_LEN);
+        Ok(ids
+            .iter()
+            .map(|id| {
+                let short = prefixes.get(id).cloned().unwrap_or_else(|| id.clone());
+                (id.clone(), short)
+            })
+            .collect())
+    }
+
+    fn aliases_path(&self) -> Result<&PathBuf> {
+        self.aliases_path.as_ref().ok_or_else(|| {
+            anyhow::anyhow!("Aliases need persistent storage; unavailable with --ephemeral")
+        })
+    }
+
+    /// Point `alias` at a memory (given by ID, prefix or another alias).
+    /// Returns the resolved memory ID.
+    pub async fn set_alias(&self, alias: &str, memory_ref: &str) -> Result<String> {
+        validate_alias(alias)?;
+        let path = self.aliases_path()?;
+        let memory_id = self.resolve_memory_id(memory_ref).await?;
+        if self.store.get_memory(&memory_id).await?.is_none() {
+            anyhow::bail!("Memory not found: {}", memory_ref);
+        }
+        let mut book = AliasBook::load(path)?;
+        book.set(self.store.project_label(), alias, &memory_id);
+        book.save(path)?;
+        Ok(memory_id)
+    }
+
+    /// Remove an alias; returns the memory ID it pointed at, if it existed
+    pub fn remove_alias(&self, alias: &str) -> Result<Option<String>> {
+        let path = self.aliases_path()?;
+        let mut book = AliasBook::load(path)?;
+        let removed = book.remove(self.store.project_label(), alias);
+        if removed.is_some() {
+            book.save(path)?;
+        }
+        Ok(removed)
+    }
+
+    /// Aliases of the current project as `(alias, memory_id)`, sorted by alias
+    pub fn list_aliases(&self) -> Result<Vec<(String, String)>> {
+        match &self.aliases_path {
+            Some(path) => Ok(AliasBook::load(path)?.list(self.store.project_label())),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    /// Learning and Insight memories due for spaced-repetition review, most
+    /// overdue first
+    pub async fn due_for_review(&self, limit: usize) -> Result<Vec<DueReview>> {
+        let Some(path) = &self.reviews_path else {
+            return Ok(Vec::new());
+        };
+        let book = ReviewBook::load(path)?;
+        let memories = self.store.get_all_memories().await?;
+        let mut due = due_reviews(memories, &book, self.store.project_label(), Utc::now());
+        due.truncate(limit);
+        Ok(due)
+    }
+
+    /// Record a 0-5 recall grade for a reviewable memory and return its new
+    /// schedule. Does not count as an access or touch the memory itself.
+    pub async fn grade_review(&self, memory_ref: &str, grade: u8) -> Result<(String, ReviewCard)> {
+        if grade > MAX_GRADE {
+            anyhow::bail!("Grade must be between 0 and {}", MAX_GRADE);
+        }
+        let path = self.reviews_path.as_ref().ok_or_else(|| {
+            anyhow::anyhow!("Reviews need persistent storage; unavailable with --ephemeral")
+        })?;
+        let memory_id = self.resolve_memory_id(memory_ref).await?;
+        let memory = self
+            .store
+            .get_memory(&memory_id)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("Memory not found: {}", memory_ref))?;
+        if !is_reviewable(&memory.memory_type) {
+            anyhow::bail!(
+                "Only learning and insight memories are reviewed; {} is {}",
+                memory_id,
+                memory.memory_type
+            );
+        }
+
+        let project = self.store.project_label();
+        let mut book = ReviewBook::load(path)?;
+        let mut card = book
+            .get(project, &memory_id)
+            .cloned()
+            .unwrap_or_else(|| ReviewCard::new(memory.created_at));
+        card.grade(grade, Utc::now());
+        book.set(project, &memory_id, card.clone());
+        book.save(path)?;
+        Ok((memory_id, card))
+    }
+
     /// Get recent memories
     pub async fn get_recent_memories(&self, limit: usize) -> Result<Vec<Memory>> {
+        self.get_recent_memories_filtered(limit, MemoryQuery::default())
+            .await
+    }
+
+    /// Get recent memories matching the filters of `filters` (type, author,
+    /// exclusions); its limit and sort order are replaced
+    pub async fn get_recent_memories_filtered(
+        &self,
+        limit: usize,
+        filters: MemoryQuery,
+    ) -> Result<Vec<Memory>> {
         let query = MemoryQuery {
             limit: Some(limit),
             sort_by: Some(super::types::MemorySortBy::CreatedAt),
             sort_order: Some(super::types::SortOrder::Descending),
-            ..Default::default()
+            ..filters
         };
 
         let results = self.store.search_memories(&query).await?;
@@ -775,29 +2313,172 @@ impl MemoryManager {
     /// Get memory statistics
     pub async fn get_memory_stats(&self) -> Result<MemoryStats> {
         let total_count = self.store.get_memory_count().await?;
+        let memories = self.store.get_all_memories().await?;
 
-        // Get count by type (simplified - would need custom queries for exact counts)
-        let recent_memories = self.get_recent_memories(100).await?;
-        let mut type_counts = std::collections::HashMap::new();
-
-        for memory in &recent_memories {
+        let mut type_counts = HashMap::new();
+        let mut tag_counts = HashMap::new();
+        for memory in &memories {
             *type_counts
                 .entry(memory.memory_type.to_string())
                 .or_insert(0) += 1;
+            for tag in &memory.metadata.tags {
+                *tag_counts.entry(tag.clone()).or_insert(0) += 1;
+            }
         }
 
         let (projects, roles) = self.store.get_distinct_projects_and_roles().await?;
+        let importance = importance_distribution(&memories, &self.config, Utc::now());
 
         Ok(MemoryStats {
             total_memories: total_count,
             type_counts,
-            recent_count: recent_memories.len().min(10),
+            tag_counts,
+            relationship_count: self.store.get_all_relationships().await?.len(),
+            db_size_bytes: self.db_dir.as_deref().map(crate::storage::dir_size),
+            recent_count: memories.len().min(10),
             git_commit: GitUtils::get_current_commit(),
             projects,
             roles,
+            importance,
+        })
+    }
+
+    /// Build the access analytics report: most/least accessed memories (by persisted
+    /// access_count), weekly access trend from the access log over the last `weeks`
+    /// weeks, and the queries that surfaced each top memory.
+    pub async fn get_access_report(&self, limit: usize, weeks: u32) -> Result<AccessReport> {
+        let now = Utc::now();
+        let weeks = weeks.max(1);
+        let since = now - Duration::weeks(weeks as i64);
+
+        let memories = self.store.get_all_memories().await?;
+        let log = self.store.get_access_log(since).await?;
+
+        let mut queries_by_memory: std::collections::HashMap<&str, Vec<&str>> =
+            std::collections::HashMap::new();
+        for entry in &log {
+            queries_by_memory
+                .entry(entry.memory_id.as_str())
+                .or_default()
+                .push(entry.query.as_str());
+        }
+
+        let to_entry = |m: &Memory| AccessReportEntry {
+            memory_id: m.id.clone(),
+            title: m.title.clone(),
+            access_count: m.metadata.decay.access_count,
+            last_accessed: m.metadata.decay.last_accessed,
+            top_queries: top_queries(
+                queries_by_memory
+                    .get(m.id.as_str())
+                    .map(|v| v.as_slice())
+                    .unwrap_or(&[]),
+                3,
+            ),
+        };
+
+        let mut by_count: Vec<&Memory> = memories.iter().collect();
+        by_count.sort_by(|a, b| {
+            b.metadata
+                .decay
+                .access_count
+                .cmp(&a.metadata.decay.access_count)
+                .then(
+                    b.metadata
+                        .decay
+                        .last_accessed
+                        .cmp(&a.metadata.decay.last_accessed),
+                )
+        });
+        let most_accessed: Vec<AccessReportEntry> =
+            by_count.iter().take(limit).map(|m| to_entry(m)).collect();
+        let least_accessed: Vec<AccessReportEntry> = by_count
+            .iter()
+            .rev()
+            .take(limit)
+            .map(|m| to_entry(m))
+            .collect();
+
+        Ok(AccessReport {
+            total_memories: memories.len(),
+            logged_accesses: log.len(),
+            weeks,
+            most_accessed,
+            least_accessed,
+            weekly: weekly_access_counts(&log, now, weeks),
+        })
+    }
+
+    /// Memories worth a second look: base importance at least `min_importance`,
+    /// current importance decayed by at least `min_decay` (fraction of base), and
+    /// not accessed for `idle_days`. Most decayed first.
+    pub async fn get_stale_report(
+        &self,
+        idle_days: u32,
+        min_importance: f32,
+        min_decay: f32,
+        limit: usize,
+    ) -> Result<StaleReport> {
+        let memories = self.store.get_all_memories().await?;
+        let mut entries = stale_entries(
+            &memories,
+            &self.config,
+            Utc::now(),
+            idle_days,
+            min_importance,
+            min_decay,
+        );
+        let total_stale = entries.len();
+        entries.truncate(limit);
+        Ok(StaleReport {
+            idle_days,
+            min_importance,
+            min_decay,
+            total_stale,
+            entries,
         })
     }
 
+    /// Digest of memories created or updated and notable links added over the
+    /// last `window`; with `llm`, prefixed by a `[search.answer]` summary.
+    pub async fn digest(&self, window: Duration, llm: bool) -> Result<Digest> {
+        let until = Utc::now();
+        let memories = self.store.get_all_memories().await?;
+        let relationships = self.store.get_all_relationships().await?;
+        let mut digest = build_digest(&memories, &relationships, until - window, until);
+        if llm && !digest.is_empty() {
+            digest.summary = Some(summarize_digest(&self.answer_config, &digest).await?);
+        }
+        Ok(digest)
+    }
+
+    /// Calendar heatmap of memories created (in scope) and accessed (from the
+    /// access log) per day over the last `months` months.
+    pub async fn get_activity_heatmap(&self, months: u32) -> Result<ActivityHeatmap> {
+        let today = Utc::now().date_naive();
+        let since = today
+            .checked_sub_months(chrono::Months::new(months.max(1)))
+            .unwrap_or(today)
+            .and_hms_opt(0, 0, 0)
+            .unwrap_or_default()
+            .and_utc();
+        let created: Vec<_> = self
+            .store
+            .get_all_memories()
+            .await?
+            .iter()
+            .map(|m| m.created_at)
+            .collect();
+        let accessed: Vec<_> = self
+            .store
+            .get_access_log(since)
+            .await?
+            .iter()
+            .map(|e| e.accessed_at)
+            .collect();
+        Ok(activity_heatmap(&created, &accessed, today, months))
+    }
+
     /// Create a relationship between two memories
     pub async fn create_relationship(
         &mut self,
@@ -826,6 +2507,26 @@ impl MemoryManager {
         self.store.get_memory_relationships(memory_id).await
     }
 
+    /// Remove relationships weaker than `below` (optionally only of one type),
+    /// returning what was cut. With `dry_run` nothing is deleted.
+    pub async fn prune_relationships(
+        &mut self,
+        below: f32,
+        relationship_type: Option<RelationshipType>,
+        dry_run: bool,
+    ) -> Result<Vec<MemoryRelationship>> {
+        let weak = weak_relationships(
+            self.store.get_all_relationships().await?,
+            below,
+            relationship_type.as_ref(),
+        );
+        if !dry_run {
+            let ids: Vec<String> = weak.iter().map(|r| r.id.clone()).collect();
+            self.store.delete_relationships(&ids).await?;
+        }
+        Ok(weak)
+    }
+
     /// Get related memories through relationships
     pub async fn get_related_memories(&self, memory_id: &str) -> Result<Vec<Memory>> {
         let relationships = self.get_relationships(memory_id).await?;
@@ -964,6 +2665,7 @@ impl MemoryManager {
             let mut meta = MemoryMetadata {
                 importance: consolidated_importance,
                 source: goal.metadata.source.clone(),
+                created_by: goal.metadata.created_by.clone(),
                 ..Default::default()
             };
             meta.tags.push("consolidated".to_string());
@@ -983,8 +2685,9 @@ impl MemoryManager {
         // already exists (the agent put it there), but recording it from the manager
         // path keeps the CLI mode complete. Duplicate is acceptable — relationships
         // are not deduped, and the additional row is informative.
+        let mut links = Vec::with_capacity(sources.len() + 1);
         if parent_id_ref.is_none() {
-            let closes = MemoryRelationship {
+            links.push(MemoryRelationship {
                 id: uuid::Uuid::new_v4().to_string(),
                 source_id: parent.id.clone(),
                 target_id: goal_id.to_string(),
@@ -992,13 +2695,12 @@ impl MemoryManager {
                 strength: 1.0,
                 description: format!("Closes goal via consolidation of {} sources", sources.len()),
                 created_at: Utc::now(),
-            };
-            self.store.store_relationship(&closes).await?;
+            });
         }
 
         // Provenance: link parent → each source so the chain is queryable.
         for src in &sources {
-            let link = MemoryRelationship {
+            links.push(MemoryRelationship {
                 id: uuid::Uuid::new_v4().to_string(),
                 source_id: parent.id.clone(),
                 target_id: src.id.clone(),
@@ -1006,9 +2708,9 @@ impl MemoryManager {
                 strength: 0.9,
                 description: "Source absorbed by consolidation".to_string(),
                 created_at: Utc::now(),
-            };
-            self.store.store_relationship(&link).await?;
+            });
         }
+        self.store.store_relationships(&links).await?;
 
         // Archive sources: state → Consolidated, importance *= 0.2 via partial UPDATE.
         for src in &sources {
@@ -1118,8 +2820,9 @@ impl MemoryManager {
             );
             self.store.store_memory(&goal).await?;
 
-            for member_id in &cluster {
-                let achieves = MemoryRelationship {
+            let achieves: Vec<MemoryRelationship> = cluster
+                .iter()
+                .map(|member_id| MemoryRelationship {
                     id: uuid::Uuid::new_v4().to_string(),
                     source_id: member_id.clone(),
                     target_id: goal.id.clone(),
@@ -1127,9 +2830,9 @@ impl MemoryManager {
                     strength: 1.0,
                     description: "Sleep consolidation cluster member".to_string(),
                     created_at: now,
-                };
-                self.store.store_relationship(&achieves).await?;
-            }
+                })
+                .collect();
+            self.store.store_relationships(&achieves).await?;
 
             match self.consolidate_goal(&goal.id, None, None).await {
                 Ok(m) => consolidated.push(m),
@@ -1151,14 +2854,73 @@ impl MemoryManager {
     pub async fn auto_link_memory(&self, memory_id: &str) -> Result<Vec<MemoryRelationship>> {
         auto_link_memory_impl(self.store.clone(), self.config.clone(), memory_id).await
     }
+
+    /// Auto-link `memory_id`, or every memory in scope when `None`, at `threshold`
+    /// (default `auto_link_threshold`). Runs even when automatic linking on write
+    /// is disabled. Memories that are already related in either direction are not
+    /// linked again, so repeated runs only add what is new. With `dry_run` nothing
+    /// is stored. Returns the relationships created (or that would be).
+    pub async fn auto_link(
+        &self,
+        memory_id: Option<&str>,
+        threshold: Option<f32>,
+        dry_run: bool,
+    ) -> Result<Vec<MemoryRelationship>> {
+        let threshold = threshold.unwrap_or(self.config.auto_link_threshold);
+        if !(0.0..=1.0).contains(&threshold) {
+            anyhow::bail!("Threshold must be between 0.0 and 1.0, got {}", threshold);
+        }
+        let (memories, existing) = match memory_id {
+            Some(id) => {
+                let memory = self
+                    .store
+                    .get_memory(id)
+                    .await?
+                    .ok_or_else(|| anyhow::anyhow!("Memory not found: {}", id))?;
+                let existing = self.store.get_memory_relationships(id).await?;
+                (vec![memory], existing)
+            }
+            None => (
+                self.store.get_all_memories().await?,
+                self.store.get_all_relationships().await?,
+            ),
+        };
+        let mut linked: HashSet<(String, String)> = existing
+            .into_iter()
+            .map(|r| (r.source_id, r.target_id))
+            .collect();
+
+        let mut created = Vec::new();
+        for memory in &memories {
+            let relationships = plan_auto_links(
+                self.store.as_ref(),
+                &self.config,
+                memory,
+                threshold,
+                &linked,
+            )
+            .await?;
+            for relationship in relationships {
+                linked.insert((
+                    relationship.source_id.clone(),
+                    relationship.target_id.clone(),
+                ));
+                created.push(relationship);
+            }
+        }
+        if !dry_run {
+            self.store.store_relationships(&created).await?;
+        }
+        Ok(created)
+    }
 }
 
 /// Free-function implementation of auto-linking so it can be spawned on a tokio
-/// task that doesn't borrow `self`. Takes an `Arc<MemoryStore>` (cheap clone)
+/// task that doesn't borrow `self`. Takes an `Arc<dyn StorageBackend>` (cheap clone)
 /// and a snapshot of the `MemoryConfig`. Both the method version above and the
 /// fire-and-forget path in `memorize` delegate here, so behavior stays consistent.
 pub(crate) async fn auto_link_memory_impl(
-    store: Arc<MemoryStore>,
+    store: Arc<dyn StorageBackend>,
     config: MemoryConfig,
     memory_id: &str,
 ) -> Result<Vec<MemoryRelationship>> {
@@ -1166,35 +2928,109 @@ pub(crate) async fn auto_link_memory_impl(
         return Ok(Vec::new());
     }
 
-    // 1. Get the memory
     let memory = store
         .get_memory(memory_id)
         .await?
         .ok_or_else(|| anyhow::anyhow!("Memory not found: {}", memory_id))?;
+    let relationships = plan_auto_links(
+        store.as_ref(),
+        &config,
+        &memory,
+        config.auto_link_threshold,
+        &HashSet::new(),
+    )
+    .await?;
+    store.store_relationships(&relationships).await?;
+    Ok(relationships)
+}
+
+/// Restore a JSONL backup into `store`, keeping memory and relationship IDs.
+/// Exported embeddings are reused when they fit the configured model; missing
+/// or mismatched ones are regenerated. The whole file is parsed before anything
+/// is stored, so a bad line leaves the store untouched; the memories are then
+/// written as one batch, followed by the relationships.
+pub(crate) async fn import_jsonl_into(
+    store: &dyn StorageBackend,
+    path: &Path,
+    dry_run: bool,
+) -> Result<JsonlImportReport> {
+    use std::io::BufRead;
+
+    let file = std::fs::File::open(path)
+        .with_context(|| format!("Cannot read backup '{}'", path.display()))?;
+    let mut memories = Vec::new();
+    let mut relationships = Vec::new();
+    for (number, line) in std::io::BufReader::new(file).lines().enumerate() {
+        let record = parse_line(&line?)
+            .with_context(|| format!("Line {} of '{}'", number + 1, path.display()))?;
+        match record {
+            None | Some(JsonlRecord::Header { .. }) => {}
+            Some(JsonlRecord::Memory { memory, embedding }) => memories.push((memory, embedding)),
+            Some(JsonlRecord::Relationship { relationship }) => relationships.push(relationship),
+        }
+    }
+
+    let mut report = JsonlImportReport {
+        memories: memories.len(),
+        relationships: relationships.len(),
+        ..Default::default()
+    };
+    if dry_run {
+        return Ok(report);
+    }
+    if memories.iter().any(|(_, embedding)| embedding.is_some()) {
+        let expected = store.embedding_dimension().await?;
+        for (_, embedding) in &mut memories {
+            if embedding.as_ref().is_some_and(|e| e.len() != expected) {
+                *embedding = None;
+            }
+        }
+    }
+    report.reembedded = store.store_memories_batch(&memories).await?;
+    store.store_relationships(&relationships).await?;
+    Ok(report)
+}
+
+/// Relationships auto-linking would add for `memory`, without storing them:
+/// AutoLinked edges to memories at least `threshold` similar (both directions
+/// when `bidirectional_links`), then RelatedTo edges to memories sharing a
+/// related file that still exists. Pairs in `linked` (either order) are skipped.
+pub(crate) async fn plan_auto_links(
+    store: &dyn StorageBackend,
+    config: &MemoryConfig,
+    memory: &Memory,
+    threshold: f32,
+    linked: &HashSet<(String, String)>,
+) -> Result<Vec<MemoryRelationship>> {
+    let memory_id = memory.id.as_str();
+    let is_linked = |other: &str| {
+        linked.contains(&(memory_id.to_string(), other.to_string()))
+            || linked.contains(&(other.to_string(), memory_id.to_string()))
+    };
 
-    // 2. Search for similar memories with high threshold
+    // 1. Search for similar memories with high threshold
     let query = MemoryQuery {
         query_text: Some(memory.get_searchable_text()),
         limit: Some(config.max_auto_links_per_memory * 2), // Get more candidates
-        min_relevance: Some(config.auto_link_threshold),
+        min_relevance: Some(threshold),
         ..Default::default()
     };
 
     let similar = store.search_memories(&query).await?;
 
-    // 3. Create bidirectional similarity relationships
+    // 2. Create bidirectional similarity relationships
     let mut relationships = Vec::new();
     let mut link_count = 0;
 
     for result in similar.iter() {
-        if result.memory.id == memory_id {
+        if result.memory.id == memory_id || is_linked(&result.memory.id) {
             continue;
         }
         if link_count >= config.max_auto_links_per_memory {
             break;
         }
 
-        let forward_rel = MemoryRelationship {
+        relationships.push(MemoryRelationship {
             id: uuid::Uuid::new_v4().to_string(),
             source_id: memory_id.to_string(),
             target_id: result.memory.id.clone(),
@@ -1202,12 +3038,10 @@ pub(crate) async fn auto_link_memory_impl(
             strength: result.relevance_score,
             description: format!("Auto-linked (similarity: {:.2})", result.relevance_score),
             created_at: Utc::now(),
-        };
-        store.store_relationship(&forward_rel).await?;
-        relationships.push(forward_rel);
+        });
 
         if config.bidirectional_links {
-            let backward_rel = MemoryRelationship {
+            relationships.push(MemoryRelationship {
                 id: uuid::Uuid::new_v4().to_string(),
                 source_id: result.memory.id.clone(),
                 target_id: memory_id.to_string(),
@@ -1215,14 +3049,12 @@ pub(crate) async fn auto_link_memory_impl(
                 strength: result.relevance_score,
                 description: format!("Auto-linked (similarity: {:.2})", result.relevance_score),
                 created_at: Utc::now(),
-            };
-            store.store_relationship(&backward_rel).await?;
-            relationships.push(backward_rel);
+            });
         }
         link_count += 1;
     }
 
-    // 4. File-based relationships: link memories that share related files —
+    // 3. File-based relationships: link memories that share related files —
     //    only files still alive on disk, to avoid linking through dead refs.
     let live_files: Vec<String> = memory
         .metadata
@@ -1239,7 +3071,7 @@ pub(crate) async fn auto_link_memory_impl(
         };
         let file_related = store.search_memories(&file_query).await?;
         for result in file_related {
-            if result.memory.id == memory_id {
+            if result.memory.id == memory_id || is_linked(&result.memory.id) {
                 continue;
             }
             if relationships.iter().any(|r: &MemoryRelationship| {
@@ -1247,7 +3079,7 @@ pub(crate) async fn auto_link_memory_impl(
             }) {
                 continue;
             }
-            let file_rel = MemoryRelationship {
+            relationships.push(MemoryRelationship {
                 id: uuid::Uuid::new_v4().to_string(),
                 source_id: memory_id.to_string(),
                 target_id: result.memory.id.clone(),
@@ -1255,9 +3087,7 @@ pub(crate) async fn auto_link_memory_impl(
                 strength: 0.7,
                 description: "Shares related files".to_string(),
                 created_at: Utc::now(),
-            };
-            store.store_relationship(&file_rel).await?;
-            relationships.push(file_rel);
+            });
         }
     }
 
@@ -1323,8 +3153,36 @@ impl MemoryManager {
         Ok(graph)
     }
 
+    /// The whole graph of memories matching `types`/`tags` (empty = all), with
+    /// the relationships between them. `root` is left empty.
+    pub async fn get_full_graph(
+        &self,
+        types: &[MemoryType],
+        tags: &[String],
+    ) -> Result<super::types::MemoryGraph> {
+        let memories: HashMap<String, Memory> = self
+            .export_memories(types, tags)
+            .await?
+            .into_iter()
+            .map(|m| (m.id.clone(), m))
+            .collect();
+        let relationships = self
+            .store
+            .get_all_relationships()
+            .await?
+            .into_iter()
+            .filter(|r| memories.contains_key(&r.source_id) && memories.contains_key(&r.target_id))
+            .collect();
+        Ok(super::types::MemoryGraph {
+            root: String::new(),
+            memories,
+            relationships,
+        })
+    }
+
     /// Clean up old memories and stale file references
     pub async fn cleanup(&mut self) -> Result<usize> {
+        self.purge_expired_trash().await?;
         let mut total = self.store.cleanup_old_memories().await?;
         if self.config.stale_ref_cleanup_enabled {
             total += self.cleanup_stale_references().await?;
@@ -1332,6 +3190,60 @@ impl MemoryManager {
         Ok(total)
     }
 
+    /// Optimize indexes and compact storage now, after any pass already in flight.
+    pub async fn optimize(&self) -> Result<()> {
+        self.drain_pending_maintenance().await;
+        self.store.run_maintenance().await
+    }
+
+    /// Re-embed the memories whose stored vector is missing or has another
+    /// length than the configured model produces (`migrate embeddings` on
+    /// backends without a fixed vector size). With `dry_run` they are only
+    /// counted. Returns (memories checked, memories re-embedded or to re-embed).
+    pub async fn reembed_mismatched(
+        &self,
+        dry_run: bool,
+        progress: &mut (dyn FnMut(usize, usize) + Send),
+    ) -> Result<(usize, usize)> {
+        let dimension = self.store.embedding_dimension().await?;
+        let embeddings = self.store.get_all_embeddings().await?;
+        let memories = self.store.get_all_memories().await?;
+        let checked = memories.len();
+        let stale: Vec<Memory> = memories
+            .into_iter()
+            .filter(|m| embeddings.get(&m.id).is_none_or(|e| e.len() != dimension))
+            .collect();
+        if dry_run {
+            return Ok((checked, stale.len()));
+        }
+        for (done, memory) in stale.iter().enumerate() {
+            self.store.store_memory(memory).await?;
+            progress(done + 1, stale.len());
+        }
+        Ok((checked, stale.len()))
+    }
+
+    /// Rebuild the memories vector index after any maintenance pass in flight;
+    /// `None` when the backend has no such index
+    pub async fn rebuild_vector_index(&self) -> Result<Option<IndexRebuild>> {
+        self.drain_pending_maintenance().await;
+        self.store.rebuild_vector_index().await
+    }
+
+    /// Compact the memory tables and prune their versions older than `keep`
+    /// (`storage optimize`); empty when the backend keeps no table files
+    pub async fn compact_storage(&self, keep: Duration) -> Result<Vec<TableCompaction>> {
+        self.drain_pending_maintenance().await;
+        self.store.compact(keep).await
+    }
+
+    /// Project keys that have memories in the store (all of them when unscoped).
+    pub async fn list_projects(&self) -> Result<Vec<String>> {
+        let (mut projects, _) = self.store.get_distinct_projects_and_roles().await?;
+        projects.sort();
+        Ok(projects)
+    }
+
     /// Clear all memory data (DANGEROUS: deletes all memories and relationships)
     pub async fn clear_all(&mut self) -> Result<usize> {
         self.store.clear_all_memory_data().await
@@ -1359,6 +3271,37 @@ impl MemoryManager {
         }
     }
 
+    /// Change a memory's type in place, keeping its ID, relationships and history
+    pub async fn set_memory_type(
+        &mut self,
+        memory_id: &str,
+        memory_type: MemoryType,
+    ) -> Result<bool> {
+        if let Some(mut memory) = self.store.get_memory(memory_id).await? {
+            memory.memory_type = memory_type;
+            memory.updated_at = Utc::now();
+            self.store.update_memory(&memory).await?;
+            Ok(true)
+        } else {
+            Ok(false)
+        }
+    }
+
+    /// Set a memory's confidence (0.0-1.0)
+    pub async fn set_confidence(&mut self, memory_id: &str, confidence: f32) -> Result<bool> {
+        if !(0.0..=1.0).contains(&confidence) {
+            anyhow::bail!("Confidence must be between 0.0 and 1.0");
+        }
+        if let Some(mut memory) = self.store.get_memory(memory_id).await? {
+            memory.metadata.confidence = confidence;
+            memory.updated_at = Utc::now();
+            self.store.update_memory(&memory).await?;
+            Ok(true)
+        } else {
+            Ok(false)
+        }
+    }
+
     /// Add related file to memory
     pub async fn add_related_file(&mut self, memory_id: &str, file_path: String) -> Result<bool> {
         if let Some(mut memory) = self.store.get_memory(memory_id).await? {
@@ -1429,23 +3372,562 @@ pub(crate) fn build_clusters(
     clusters
 }
 
+/// Set the search text of `filters` from a user query, splitting off any
+/// `keyword:`/`tag:`/`type:`/`file:`/`after:`/`before:` clauses and
+/// `importance>0.6` style comparisons into `filter_expr`. With only
+/// clauses and no free text, the required keywords become the search text,
+/// or the search turns into a filter-only scan when there are none.
+pub(crate) fn with_query_text(mut filters: MemoryQuery, query: &str) -> Result<MemoryQuery> {
+    let parsed = parse_query(query)?;
+    let Some(expr) = parsed.filter else {
+        filters.query_text = Some(query.to_string());
+        return Ok(filters);
+    };
+    let text = if parsed.text.trim().is_empty() {
+        expr.required_keywords().join(" ")
+    } else {
+        parsed.text
+    };
+    filters.query_text = Some(text).filter(|t| !t.trim().is_empty());
+    filters.filter_expr = Some(match filters.filter_expr.take() {
+        Some(existing) => QueryExpr::And(vec![existing, expr]),
+        None => expr,
+    });
+    Ok(filters)
+}
+
+/// Most frequent queries in `queries`, count-descending (ties alphabetical).
+pub(crate) fn top_queries(queries: &[&str], n: usize) -> Vec<(String, usize)> {
+    let mut counts: std::collections::HashMap<&str, usize> = std::collections::HashMap::new();
+    for q in queries {
+        *counts.entry(q).or_insert(0) += 1;
+    }
+    let mut sorted: Vec<(String, usize)> = counts
+        .into_iter()
+        .map(|(q, c)| (q.to_string(), c))
+        .collect();
+    sorted.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+    sorted.truncate(n);
+    sorted
+}
+
+/// Bucket access-log entries into the last `weeks` ISO weeks (Monday start), oldest first.
+/// Weeks with no accesses are kept so the trend has no gaps.
+pub(crate) fn weekly_access_counts(
+    log: &[super::types::AccessLogEntry],
+    now: chrono::DateTime<Utc>,
+    weeks: u32,
+) -> Vec<WeeklyAccess> {
+    use chrono::Datelike;
+    let week_start =
+        |d: chrono::NaiveDate| d - Duration::days(d.weekday().num_days_from_monday() as i64);
+    let current = week_start(now.date_naive());
+
+    (0..weeks as i64)
+        .rev()
+        .map(|i| {
+            let start = current - Duration::weeks(i);
+            let in_week: Vec<&super::types::AccessLogEntry> = log
+                .iter()
+                .filter(|e| week_start(e.accessed_at.date_naive()) == start)
+                .collect();
+            let distinct: HashSet<&str> = in_week.iter().map(|e| e.memory_id.as_str()).collect();
+            WeeklyAccess {
+                week_start: start,
+                accesses: in_week.len(),
+                distinct_memories: distinct.len(),
+            }
+        })
+        .collect()
+}
+
+/// Select stale Working memories (see `MemoryManager::get_stale_report`),
+/// sorted by decayed fraction, then by base importance.
+pub(crate) fn stale_entries(
+    memories: &[Memory],
+    config: &MemoryConfig,
+    now: chrono::DateTime<Utc>,
+    idle_days: u32,
+    min_importance: f32,
+    min_decay: f32,
+) -> Vec<StaleEntry> {
+    let mut entries: Vec<StaleEntry> = memories
+        .iter()
+        .filter(|m| m.metadata.state == MemoryState::Working)
+        .filter_map(|m| {
+            let base = m.metadata.importance;
+            let days_idle = (now - m.metadata.decay.last_accessed).num_days();
+            if base < min_importance || base <= 0.0 || days_idle < idle_days as i64 {
+                return None;
+            }
+            let current = m.get_current_importance(
+                config.decay_enabled,
+                config.min_importance_threshold,
+                config.decay_half_life_days,
+                config.access_boost_factor,
+            );
+            let decayed = (1.0 - current / base).max(0.0);
+            (decayed >= min_decay).then(|| StaleEntry {
+                memory_id: m.id.clone(),
+                title: m.title.clone(),
+                memory_type: m.memory_type.to_string(),
+                base_importance: base,
+                current_importance: current,
+                decayed,
+                last_accessed: m.metadata.decay.last_accessed,
+                days_idle,
+            })
+        })
+        .collect();
+    entries.sort_by(|a, b| {
+        b.decayed
+            .total_cmp(&a.decayed)
+            .then(b.base_importance.total_cmp(&a.base_importance))
+    });
+    entries
+}
+
+/// One memory row of the stale report
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct StaleEntry {
+    pub memory_id: String,
+    pub title: String,
+    pub memory_type: String,
+    pub base_importance: f32,
+    pub current_importance: f32,
+    /// Share of base importance lost to decay (0.0-1.0)
+    pub decayed: f32,
+    pub last_accessed: chrono::DateTime<Utc>,
+    pub days_idle: i64,
+}
+
+/// Important memories that decayed while nobody used them (`memory report stale`)
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct StaleReport {
+    pub idle_days: u32,
+    pub min_importance: f32,
+    pub min_decay: f32,
+    /// Matches before the listing limit was applied
+    pub total_stale: usize,
+    pub entries: Vec<StaleEntry>,
+}
+
+impl StaleReport {
+    /// Format report as human-readable string
+    pub fn format(&self) -> String {
+        let mut output = "Stale Memory Report:\n".to_string();
+        output.push_str(&format!(
+            "  Importance >= {:.2}, decayed >= {:.0}%, idle >= {} days: {} memories\n",
+            self.min_importance,
+            self.min_decay * 100.0,
+            self.idle_days,
+            self.total_stale
+        ));
+        if self.entries.is_empty() {
+            output.push_str("\n  Nothing to review.\n");
+            return output;
+        }
+        output.push('\n');
+        for entry in &self.entries {
+            output.push_str(&format!(
+                "    {:.2} → {:.2}  [{}] {}  ({})  idle {} days\n",
+                entry.base_importance,
+                entry.current_importance,
+                entry.memory_type,
+                entry.title,
+                entry.memory_id,
+                entry.days_idle
+            ));
+        }
+        output.push_str(
+            "\n  Refresh one that still holds: octobrain memory update <id> --importance <score>\n",
+        );
+        output
+            .push_str("  Drop one that no longer does: octobrain memory forget --memory-id <id>\n");
+        output
+    }
+}
+
+/// Daily counts of memories created and accessed over the last `months` months,
+/// as a calendar matrix: one row per Monday-start week, one column per weekday.
+/// Days outside the window (before its start, after today) are `None`.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct ActivityHeatmap {
+    pub months: u32,
+    /// Monday of the first row
+    pub first_week: chrono::NaiveDate,
+    pub from: chrono::NaiveDate,
+    pub to: chrono::NaiveDate,
+    pub created: Vec<[Option<usize>; 7]>,
+    pub accessed: Vec<[Option<usize>; 7]>,
+}
+
+/// Bucket creation and access timestamps into the calendar matrix ending on
+/// `today` and starting `months` months earlier.
+pub(crate) fn activity_heatmap(
+    created: &[chrono::DateTime<Utc>],
+    accessed: &[chrono::DateTime<Utc>],
+    today: chrono::NaiveDate,
+    months: u32,
+) -> ActivityHeatmap {
+    use chrono::Datelike;
+    let months = months.max(1);
+    let from = today
+        .checked_sub_months(chrono::Months::new(months))
+        .unwrap_or(today)
+        + Duration::days(1);
+    let first_week = from - Duration::days(from.weekday().num_days_from_monday() as i64);
+    let weeks = ((today - first_week).num_days() / 7 + 1) as usize;
+
+    let matrix = |stamps: &[chrono::DateTime<Utc>]| {
+        let mut rows: Vec<[Option<usize>; 7]> = (0..weeks)
+            .map(|w| {
+                std::array::from_fn(|d| {
+                    let day = first_week + Duration::days((w * 7 + d) as i64);
+                    (day >= from && day <= today).then_some(0)
+                })
+            })
+            .collect();
+        for stamp in stamps {
+            let offset = (stamp.date_naive() - first_week).num_days();
+            if offset < 0 {
+                continue;
+            }
+            let (w, d) = (offset as usize / 7, offset as usize % 7);
+            if let Some(Some(count)) = rows.get_mut(w).map(|row| &mut row[d]) {
+                *count += 1;
+            }
+        }
+        rows
+    };
+
+    ActivityHeatmap {
+        months,
+        first_week,
+        from,
+        to: today,
+        created: matrix(created),
+        accessed: matrix(accessed),
+    }
+}
+
+impl ActivityHeatmap {
+    /// Render both calendars as terminal blocks, GitHub-style
+    pub fn format(&self) -> String {
+        let mut output = format!(
+            "Activity ({} to {}):\n",
+            self.from.format("%Y-%m-%d"),
+            self.to.format("%Y-%m-%d")
+        );
+        output.push_str(&self.format_grid("Created", &self.created));
+        output.push_str(&self.format_grid("Accessed", &self.accessed));
+        output.push_str("  Less · ░ ▒ ▓ █ More\n");
+        output
+    }
+
+    fn format_grid(&self, label: &str, rows: &[[Option<usize>; 7]]) -> String {
+        use chrono::Datelike;
+        let counts = || rows.iter().flatten().flatten();
+        let total: usize = counts().sum();
+        let max = counts().copied().max().unwrap_or(0);
+        let active = counts().filter(|c| **c > 0).count();
+        let mut output = format!("\n  {}: {} over {} active days\n", label, total, active);
+
+        // Month names above the week where each month starts, two columns per week.
+        // Placed right to left so a month start wins over the first column's label.
+        let mut months = vec![' '; rows.len() * 2 + 3];
+        for w in (0..rows.len()).rev() {
+            let monday = self.first_week + Duration::days(w as i64 * 7);
+            let sunday = monday + Duration::days(6);
+            if w == 0 || sunday.month() != monday.month() || monday.day() == 1 {
+                let name = if w == 0 { monday } else { sunday }
+                    .format("%b")
+                    .to_string();
+                let col = w * 2;
+                if months[col..].iter().take(4).all(|c| *c == ' ') {
+                    for (i, ch) in name.chars().enumerate() {
+                        months[col + i] = ch;
+                    }
+                }
+            }
+        }
+        output.push_str(&format!(
+            "        {}\n",
+            months.iter().collect::<String>().trim_end()
+        ));
+
+        for (d, day) in ["Mon", "", "Wed", "", "Fri", "", "Sun"].iter().enumerate() {
+            let cells: String = rows
+                .iter()
+                .map(|row| match row[d] {
+                    None => "  ",
+                    Some(0) => "· ",
+                    Some(c) => match (c * 4).div_ceil(max.max(1)) {
+                        1 => "░ ",
+                        2 => "▒ ",
+                        3 => "▓ ",
+                        _ => "█ ",
+                    },
+                })
+                .collect();
+            output.push_str(&format!("    {:<3} {}\n", day, cells.trim_end()));
+        }
+        output
+    }
+}
+
+/// Access counts for one week of the access report
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct WeeklyAccess {
+    pub week_start: chrono::NaiveDate,
+    pub accesses: usize,
+    pub distinct_memories: usize,
+}
+
+/// One memory row of the access report
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct AccessReportEntry {
+    pub memory_id: String,
+    pub title: String,
+    pub access_count: u32,
+    pub last_accessed: chrono::DateTime<Utc>,
+    /// Queries that retrieved this memory within the report window, with counts
+    pub top_queries: Vec<(String, usize)>,
+}
+
+/// Access analytics report (`memory report access`)
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct AccessReport {
+    pub total_memories: usize,
+    pub logged_accesses: usize,
+    pub weeks: u32,
+    pub most_accessed: Vec<AccessReportEntry>,
+    pub least_accessed: Vec<AccessReportEntry>,
+    pub weekly: Vec<WeeklyAccess>,
+}
+
+impl AccessReport {
+    /// Format report as human-readable string
+    pub fn format(&self) -> String {
+        let mut output = "Memory Access Report:\n".to_string();
+        output.push_str(&format!("  Total memories: {}\n", self.total_memories));
+        output.push_str(&format!(
+            "  Logged accesses (last {} weeks): {}\n",
+            self.weeks, self.logged_accesses
+        ));
+
+        output.push_str("\n  Most accessed:\n");
+        for entry in &self.most_accessed {
+            output.push_str(&format_access_entry(entry));
+        }
+
+        output.push_str("\n  Least accessed:\n");
+        for entry in &self.least_accessed {
+            output.push_str(&format_access_entry(entry));
+        }
+
+        output.push_str("\n  Weekly trend:\n");
+        let max = self.weekly.iter().map(|w| w.accesses).max().unwrap_or(0);
+        for week in &self.weekly {
+            let bar_len = (week.accesses * 30).checked_div(max).unwrap_or(0);
+            output.push_str(&format!(
+                "    {}  {:>5} accesses  {:>4} memories  {}\n",
+                week.week_start,
+                week.accesses,
+                week.distinct_memories,
+                "█".repeat(bar_len)
+            ));
+        }
+
+        output
+    }
+}
+
+fn format_access_entry(entry: &AccessReportEntry) -> String {
+    let mut line = format!(
+        "    {:>5}×  {}  ({})  last: {}\n",
+        entry.access_count,
+        entry.title,
+        entry.memory_id,
+        entry.last_accessed.format("%Y-%m-%d")
+    );
+    if !entry.top_queries.is_empty() {
+        let queries: Vec<String> = entry
+            .top_queries
+            .iter()
+            .map(|(q, c)| format!("\"{}\" ({})", q, c))
+            .collect();
+        line.push_str(&format!("           queries: {}\n", queries.join(", ")));
+    }
+    line
+}
+
+/// Width of the "near the cleanup threshold" band above `cleanup_min_importance`.
+const CLEANUP_MARGIN: f32 = 0.1;
+
+/// Buckets in the importance histogram, each 0.1 wide.
+const IMPORTANCE_BUCKETS: usize = 10;
+
+/// Current (decayed) importance histogram plus what the next cleanup would see.
+/// Cleanup itself compares the stored base importance, so the cleanup counts do too.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct ImportanceDistribution {
+    /// Memory counts per 0.1-wide bucket of current importance, lowest first
+    pub buckets: Vec<usize>,
+    pub cleanup_min_importance: f32,
+    /// `None` when automatic cleanup is disabled
+    pub cleanup_after_days: Option<u32>,
+    /// Removed by the next cleanup run: old enough and below the threshold
+    pub due_for_cleanup: usize,
+    /// Below the threshold, removed once they pass the age cutoff
+    pub below_threshold_not_yet_due: usize,
+    /// Base importance within `CLEANUP_MARGIN` above the threshold
+    pub near_threshold: usize,
+    /// Mean stored importance, before decay
+    pub average_base: f32,
+    /// Mean current importance, after decay and access boosts
+    pub average_current: f32,
+}
+
+/// Bucket current importance and count memories against the cleanup rule.
+pub(crate) fn importance_distribution(
+    memories: &[Memory],
+    config: &MemoryConfig,
+    now: chrono::DateTime<Utc>,
+) -> ImportanceDistribution {
+    let threshold = config.cleanup_min_importance;
+    let cutoff = config
+        .auto_cleanup_days
+        .map(|days| now - Duration::days(days as i64));
+    let mut distribution = ImportanceDistribution {
+        buckets: vec![0; IMPORTANCE_BUCKETS],
+        cleanup_min_importance: threshold,
+        cleanup_after_days: config.auto_cleanup_days,
+        due_for_cleanup: 0,
+        below_threshold_not_yet_due: 0,
+        near_threshold: 0,
+        average_base: 0.0,
+        average_current: 0.0,
+    };
+    for memory in memories {
+        let current = memory.get_current_importance(
+            config.decay_enabled,
+            config.min_importance_threshold,
+            config.decay_half_life_days,
+            config.access_boost_factor,
+        );
+        let bucket = ((current.clamp(0.0, 1.0) * IMPORTANCE_BUCKETS as f32) as usize)
+            .min(IMPORTANCE_BUCKETS - 1);
+        distribution.buckets[bucket] += 1;
+        distribution.average_current += current;
+
+        let base = memory.metadata.importance;
+        distribution.average_base += base;
+        if base < threshold {
+            match cutoff {
+                Some(cutoff) if memory.created_at < cutoff => distribution.due_for_cleanup += 1,
+                _ => distribution.below_threshold_not_yet_due += 1,
+            }
+        } else if base < threshold + CLEANUP_MARGIN {
+            distribution.near_threshold += 1;
+        }
+    }
+    if !memories.is_empty() {
+        distribution.average_base /= memories.len() as f32;
+        distribution.average_current /= memories.len() as f32;
+    }
+    distribution
+}
+
+impl ImportanceDistribution {
+    /// Format histogram as human-readable lines
+    pub fn format(&self) -> String {
+        let mut output = format!(
+            "  Average importance: {:.2} stored, {:.2} after decay\n",
+            self.average_base, self.average_current
+        );
+        output.push_str("  Current importance:\n");
+        let max = self.buckets.iter().copied().max().unwrap_or(0);
+        let width = 1.0 / self.buckets.len() as f32;
+        for (i, count) in self.buckets.iter().enumerate() {
+            let bar_len = (count * 30).checked_div(max).unwrap_or(0);
+            output.push_str(&format!(
+                "    {:.1}-{:.1}  {:>5}  {}\n",
+                i as f32 * width,
+                (i + 1) as f32 * width,
+                count,
+                "█".repeat(bar_len)
+            ));
+        }
+        match self.cleanup_after_days {
+            Some(days) => output.push_str(&format!(
+                "  Cleanup (importance < {:.2}, older than {} days):\n",
+                self.cleanup_min_importance, days
+            )),
+            None => output.push_str(&format!(
+                "  Cleanup (importance < {:.2}, automatic cleanup disabled):\n",
+                self.cleanup_min_importance
+            )),
+        }
+        output.push_str(&format!(
+            "    Removed by next cleanup: {}\n",
+            self.due_for_cleanup
+        ));
+        output.push_str(&format!(
+            "    Below threshold, not yet old enough: {}\n",
+            self.below_threshold_not_yet_due
+        ));
+        output.push_str(&format!(
+            "    Within {:.1} above threshold: {}\n",
+            CLEANUP_MARGIN, self.near_threshold
+        ));
+        output
+    }
+}
+
 /// Memory statistics
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, serde::Serialize)]
 pub struct MemoryStats {
     pub total_memories: usize,
     pub type_counts: std::collections::HashMap<String, usize>,
+    /// Memories per tag
+    pub tag_counts: std::collections::HashMap<String, usize>,
+    pub relationship_count: usize,
+    /// Size of the local database directory, shared by all projects; `None` for
+    /// ephemeral and Postgres storage
+    pub db_size_bytes: Option<u64>,
     pub recent_count: usize,
     pub git_commit: Option<String>,
     pub projects: Vec<String>,
     pub roles: Vec<String>,
+    pub importance: ImportanceDistribution,
 }
 
 impl MemoryStats {
+    /// The `limit` most used tags, most used first
+    pub fn top_tags(&self, limit: usize) -> Vec<(&str, usize)> {
+        let mut tags: Vec<_> = self
+            .tag_counts
+            .iter()
+            .map(|(tag, count)| (tag.as_str(), *count))
+            .collect();
+        tags.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+        tags.truncate(limit);
+        tags
+    }
+
     /// Format stats as human-readable string
     pub fn format(&self) -> String {
         let mut output = "Memory Statistics:\n".to_string();
         output.push_str(&format!("  Total memories: {}\n", self.total_memories));
         output.push_str(&format!("  Recent memories: {}\n", self.recent_count));
+        output.push_str(&format!("  Relationships: {}\n", self.relationship_count));
+        if let Some(bytes) = self.db_size_bytes {
+            output.push_str(&format!(
+                "  Database size: {:.1} MiB\n",
+                bytes as f64 / (1024.0 * 1024.0)
+            ));
+        }
 
         if let Some(ref commit) = self.git_commit {
             output.push_str(&format!("  Current commit: {}\n", commit));
@@ -1474,6 +3956,14 @@ impl MemoryStats {
             }
         }
 
+        if !self.tag_counts.is_empty() {
+            output.push_str("  Top tags:\n");
+            for (tag, count) in self.top_tags(10) {
+                output.push_str(&format!("    {}: {}\n", tag, count));
+            }
+        }
+
+        output.push_str(&self.importance.format());
         output
     }
 }
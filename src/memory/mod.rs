@@ -15,14 +15,45 @@
 // Memory module for AI context and conversation state management
 // Uses LanceDB for vector storage and semantic search capabilities
 
+pub mod aliases;
+pub mod answer;
+pub mod attachments;
+pub mod backend;
+pub mod batch;
+pub mod capture;
+pub mod columnar;
+pub mod conflict;
+pub mod conversation_import;
 #[cfg(test)]
 mod decay_tests;
+pub mod digest;
+pub mod draft;
+pub mod ephemeral_store;
+pub mod export;
+pub mod feedback;
 pub mod formatting;
 pub mod git_utils;
+pub mod image;
+pub mod jsonl;
+pub mod llm;
 pub mod manager;
+#[cfg(feature = "postgres")]
+pub mod postgres_store;
+pub mod query_syntax;
+pub mod redaction;
 pub mod reranker_integration;
+pub mod review;
+pub(crate) mod search_scoring;
+pub mod secrets;
+pub mod session_import;
+pub mod share;
+#[cfg(feature = "sqlite")]
+pub mod sqlite_store;
 pub mod store;
+pub mod sync;
+pub mod transcript;
 pub mod types;
+pub mod write_lock;
 
 #[cfg(test)]
 mod hybrid_tests;
@@ -42,6 +73,114 @@ mod goal_tests;
 #[cfg(test)]
 mod sleep_tests;
 
+#[cfg(test)]
+mod answer_tests;
+
+#[cfg(test)]
+mod importance_tuning_tests;
+
+#[cfg(test)]
+mod access_report_tests;
+
+#[cfg(test)]
+mod stale_report_tests;
+
+#[cfg(test)]
+mod redaction_tests;
+
+#[cfg(test)]
+mod secrets_tests;
+
+#[cfg(test)]
+mod attachments_tests;
+
+#[cfg(test)]
+mod image_tests;
+
+#[cfg(test)]
+mod transcript_tests;
+
+#[cfg(test)]
+mod conversation_import_tests;
+
+#[cfg(test)]
+mod session_import_tests;
+
+#[cfg(test)]
+mod export_tests;
+
+#[cfg(test)]
+mod share_tests;
+
+#[cfg(test)]
+mod author_tests;
+
+#[cfg(test)]
+mod exclusion_filter_tests;
+
+#[cfg(test)]
+mod query_syntax_tests;
+
+#[cfg(test)]
+mod aliases_tests;
+
+#[cfg(test)]
+mod link_prune_tests;
+
+#[cfg(test)]
+mod feedback_tests;
+
+#[cfg(test)]
+mod digest_tests;
+
+#[cfg(test)]
+mod review_tests;
+
+#[cfg(test)]
+mod columnar_tests;
+
+#[cfg(test)]
+mod jsonl_tests;
+
+#[cfg(test)]
+mod sync_tests;
+
+#[cfg(test)]
+mod conflict_tests;
+
+#[cfg(test)]
+mod write_lock_tests;
+
+#[cfg(test)]
+mod heatmap_tests;
+
+#[cfg(test)]
+mod importance_histogram_tests;
+
+#[cfg(all(test, feature = "sqlite"))]
+mod sqlite_store_tests;
+
+#[cfg(test)]
+mod ephemeral_store_tests;
+
+#[cfg(test)]
+mod store_tests;
+
+#[cfg(test)]
+mod capture_tests;
+
+#[cfg(test)]
+mod draft_tests;
+
+#[cfg(test)]
+mod batch_tests;
+
+#[cfg(test)]
+mod formatting_tests;
+
+#[cfg(test)]
+mod edit_tests;
+
 // Re-export the main types and interfaces
 pub use formatting::{format_memories_as_text, format_memories_for_cli};
 pub use manager::MemoryManager;
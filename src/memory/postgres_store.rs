@@ -0,0 +1,1110 @@
+// Copyright 2026 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Shared Postgres storage backend (`[memory].backend = "postgres"`).
+//!
+//! Memories, relationships, the recovery buffer and the access log live in
+//! ordinary tables so several users and servers can work against one store.
+//! Embeddings are pgvector `vector` values ranked by exact cosine distance
+//! (`<=>`); the column is left unsized so projects with different embedding
+//! models can share a database, which rules out an ANN index. Keyword search
+//! uses a generated `tsvector` column. Scoring mirrors `MemoryStore`.
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use deadpool_postgres::Pool;
+use pgvector::Vector;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use tokio_postgres::Row;
+
+use super::backend::{BackendOptions, StorageBackend};
+use super::reranker_integration::RerankerIntegration;
+use super::search_scoring::{keyword_terms, score_hybrid, score_vector_hits};
+use super::types::{
+    AccessLogEntry, Memory, MemoryConfig, MemoryDecay, MemoryMetadata, MemoryQuery,
+    MemoryRelationship, MemoryRevision, MemorySearchResult, MemorySource, MemoryState, MemoryType,
+    RelationshipType,
+};
+use crate::embedding::{EmbeddingProvider, InputType};
+use crate::postgres::{any_terms_tsquery, param_refs, Filter, Param};
+
+/// Memory columns in the order `memory_from_row` reads them.
+const MEMORY_COLUMNS: &str = "id, project_key, role, memory_type, title, content, created_at, \
+     updated_at, importance, confidence, tags, related_files, git_commit, source, access_count, \
+     last_accessed, state, attachments, custom_fields, created_by, decay_rate";
+
+/// Number of entries in `MEMORY_COLUMNS`; extra selected columns follow them.
+const MEMORY_COLUMN_COUNT: usize = 21;
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS memories (
+    id TEXT PRIMARY KEY,
+    project_key TEXT NOT NULL,
+    role TEXT NOT NULL DEFAULT '',
+    memory_type TEXT NOT NULL,
+    title TEXT NOT NULL,
+    content TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL,
+    importance REAL NOT NULL,
+    confidence REAL NOT NULL,
+    tags TEXT NOT NULL,
+    related_files TEXT NOT NULL,
+    git_commit TEXT,
+    source TEXT NOT NULL,
+    access_count INTEGER NOT NULL DEFAULT 0,
+    last_accessed TIMESTAMPTZ NOT NULL,
+    state TEXT NOT NULL,
+    attachments TEXT NOT NULL,
+    custom_fields TEXT NOT NULL,
+    created_by TEXT NOT NULL DEFAULT '',
+    decay_rate REAL NOT NULL DEFAULT 1.0,
+    embedding vector NOT NULL,
+    search_text tsvector GENERATED ALWAYS AS (to_tsvector('simple', title || ' ' || content)) STORED
+);
+CREATE INDEX IF NOT EXISTS memories_scope ON memories (project_key, role);
+CREATE INDEX IF NOT EXISTS memories_search ON memories USING GIN (search_text);
+
+CREATE TABLE IF NOT EXISTS memory_relationships (
+    id TEXT PRIMARY KEY,
+    source_id TEXT NOT NULL,
+    target_id TEXT NOT NULL,
+    project_key TEXT NOT NULL,
+    relationship_type TEXT NOT NULL,
+    strength REAL NOT NULL,
+    description TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL
+);
+CREATE INDEX IF NOT EXISTS memory_relationships_source ON memory_relationships (source_id);
+CREATE INDEX IF NOT EXISTS memory_relationships_target ON memory_relationships (target_id);
+
+CREATE TABLE IF NOT EXISTS memory_recovery (
+    seq BIGSERIAL PRIMARY KEY,
+    id TEXT NOT NULL,
+    project_key TEXT NOT NULL,
+    role TEXT NOT NULL,
+    memory_type TEXT NOT NULL,
+    title TEXT NOT NULL,
+    content TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL,
+    importance REAL NOT NULL,
+    confidence REAL NOT NULL,
+    tags TEXT NOT NULL,
+    related_files TEXT NOT NULL,
+    git_commit TEXT,
+    source TEXT NOT NULL,
+    access_count INTEGER NOT NULL,
+    last_accessed TIMESTAMPTZ NOT NULL,
+    state TEXT NOT NULL,
+    attachments TEXT NOT NULL,
+    custom_fields TEXT NOT NULL,
+    created_by TEXT NOT NULL,
+    decay_rate REAL NOT NULL DEFAULT 1.0,
+    embedding vector NOT NULL,
+    deleted_at TIMESTAMPTZ NOT NULL
+);
+CREATE INDEX IF NOT EXISTS memory_recovery_project ON memory_recovery (project_key, deleted_at);
+
+CREATE TABLE IF NOT EXISTS memory_access_log (
+    id TEXT PRIMARY KEY,
+    project_key TEXT NOT NULL,
+    role TEXT,
+    memory_id TEXT NOT NULL,
+    query TEXT NOT NULL,
+    accessed_at TIMESTAMPTZ NOT NULL
+);
+CREATE INDEX IF NOT EXISTS memory_access_log_project ON memory_access_log (project_key, accessed_at);
+
+CREATE TABLE IF NOT EXISTS memory_revisions (
+    id TEXT PRIMARY KEY,
+    project_key TEXT NOT NULL,
+    memory_id TEXT NOT NULL,
+    revision INTEGER NOT NULL,
+    replaced_at TIMESTAMPTZ NOT NULL,
+    memory TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS memory_revisions_memory ON memory_revisions (memory_id);
+
+-- Columns added after the first release, missing from older databases
+ALTER TABLE memories ADD COLUMN IF NOT EXISTS decay_rate REAL NOT NULL DEFAULT 1.0;
+ALTER TABLE memory_recovery ADD COLUMN IF NOT EXISTS decay_rate REAL NOT NULL DEFAULT 1.0;
+";
+
+/// Build a memory from the first `MEMORY_COLUMN_COUNT` columns of `row`.
+fn memory_from_row(row: &Row) -> Result<Memory> {
+    let importance: f32 = row.try_get(8)?;
+    let created_at: DateTime<Utc> = row.try_get(6)?;
+    let mut decay = MemoryDecay::new(importance);
+    decay.access_count = row.try_get::<_, i32>(14)?.max(0) as u32;
+    decay.last_accessed = row.try_get(15)?;
+    decay.decay_rate = row.try_get(20)?;
+    let created_by: String = row.try_get(19)?;
+
+    Ok(Memory {
+        id: row.try_get(0)?,
+        memory_type: MemoryType::from(row.try_get::<_, String>(3)?),
+        title: row.try_get(4)?,
+        content: row.try_get(5)?,
+        created_at,
+        updated_at: row.try_get(7)?,
+        metadata: MemoryMetadata {
+            git_commit: row.try_get(12)?,
+            created_by: (!created_by.is_empty()).then_some(created_by),
+            importance,
+            confidence: row.try_get(9)?,
+            tags: serde_json::from_str(row.try_get(10)?).unwrap_or_default(),
+            related_files: serde_json::from_str(row.try_get(11)?).unwrap_or_default(),
+            source: MemorySource::from(row.try_get::<_, String>(13)?),
+            decay,
+            state: MemoryState::from(row.try_get::<_, String>(16)?),
+            attachments: serde_json::from_str(row.try_get(17)?).unwrap_or_default(),
+            custom_fields: serde_json::from_str(row.try_get(18)?).unwrap_or_default(),
+        },
+        relevance_score: None,
+    })
+}
+
+fn relationship_from_row(row: &Row) -> Result<MemoryRelationship> {
+    Ok(MemoryRelationship {
+        id: row.try_get(0)?,
+        source_id: row.try_get(1)?,
+        target_id: row.try_get(2)?,
+        relationship_type: RelationshipType::from(row.try_get::<_, &str>(3)?),
+        strength: row.try_get(4)?,
+        description: row.try_get(5)?,
+        created_at: row.try_get(6)?,
+    })
+}
+
+/// `col = EXCLUDED.col` for every memory column but `id`, plus the embedding.
+fn memory_upsert_set() -> String {
+    MEMORY_COLUMNS
+        .split(", ")
+        .skip(1)
+        .chain(std::iter::once("embedding"))
+        .map(|c| format!("{c} = EXCLUDED.{c}", c = c.trim()))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Postgres-backed memory store
+pub struct PostgresStore {
+    pool: Pool,
+    embedding_provider: Box<dyn EmbeddingProvider>,
+    config: MemoryConfig,
+    main_config: crate::config::Config,
+    reranker_integration: Mutex<Option<RerankerIntegration>>,
+    project_key: Option<String>,
+    role: Option<String>,
+    /// `project_key` used for writes ("default" when unscoped)
+    label: String,
+}
+
+impl PostgresStore {
+    /// Connect to `[memory].postgres_url` and create the tables if missing.
+    pub async fn open(options: BackendOptions) -> Result<Self> {
+        let url = crate::postgres::resolve_url(&options.config)?;
+        let pool = crate::postgres::connect(&url)?;
+        crate::postgres::ensure_schema(&pool, SCHEMA).await?;
+
+        let label = options
+            .project_key
+            .clone()
+            .unwrap_or_else(|| "default".to_string());
+        Ok(Self {
+            pool,
+            embedding_provider: options.embedding_provider,
+            config: options.config,
+            main_config: options.main_config,
+            reranker_integration: Mutex::new(options.reranker_integration),
+            project_key: options.project_key,
+            role: options.role,
+            label,
+        })
+    }
+
+    async fn client(&self) -> Result<deadpool_postgres::Client> {
+        self.pool
+            .get()
+            .await
+            .context("Failed to get a Postgres connection")
+    }
+
+    fn current_importance(&self, memory: &Memory) -> f32 {
+        memory.get_current_importance(
+            self.config.decay_enabled,
+            self.config.min_importance_threshold,
+            self.config.decay_half_life_days,
+            self.config.access_boost_factor,
+        )
+    }
+
+    /// Project/role scope, plus the scalar filters of `query` when given.
+    fn filter(&self, query: Option<&MemoryQuery>) -> Filter {
+        let mut filter = Filter::default();
+        if let Some(key) = self.project_key.as_deref() {
+            filter.push("project_key = ?", key.to_string());
+        }
+        if let Some(role) = self.role.as_deref() {
+            filter.push("role = ?", role.to_string());
+        }
+        let Some(query) = query else {
+            return filter;
+        };
+        if let Some(types) = query.memory_types.as_ref().filter(|t| !t.is_empty()) {
+            let types: Vec<String> = types.iter().map(|t| t.to_string()).collect();
+            filter.push("memory_type = ANY(?)", types);
+        }
+        if let Some(types) = query.exclude_types.as_ref().filter(|t| !t.is_empty()) {
+            let types: Vec<String> = types.iter().map(|t| t.to_string()).collect();
+            filter.push("memory_type <> ALL(?)", types);
+        }
+        if let Some(min_importance) = query.min_importance {
+            filter.push("importance >= ?", min_importance);
+        }
+        if let Some(min_confidence) = query.min_confidence {
+            filter.push("confidence >= ?", min_confidence);
+        }
+        if let Some(git_commit) = &query.git_commit {
+            filter.push("git_commit = ?", git_commit.clone());
+        }
+        if let Some(created_by) = &query.created_by {
+            filter.push("created_by = ?", created_by.clone());
+        }
+        if let Some(created_after) = query.created_after {
+            filter.push("created_at >= ?", created_after);
+        }
+        if let Some(created_before) = query.created_before {
+            filter.push("created_at <= ?", created_before);
+        }
+        filter
+    }
+
+    /// Project scope only (no role), as `MemoryStore` uses for ID lookups.
+    fn project_filter(&self) -> Filter {
+        let mut filter = Filter::default();
+        if let Some(key) = self.project_key.as_deref() {
+            filter.push("project_key = ?", key.to_string());
+        }
+        filter
+    }
+
+    /// Every memory matching `filter`.
+    async fn select_memories(&self, filter: Filter) -> Result<Vec<Memory>> {
+        let sql = format!(
+            "SELECT {} FROM memories WHERE {}",
+            MEMORY_COLUMNS,
+            filter.sql()
+        );
+        let rows = self.client().await?.query(&sql, &filter.refs()).await?;
+        rows.iter().map(memory_from_row).collect()
+    }
+
+    async fn embed(&self, text: &str, input_type: InputType) -> Result<Vec<f32>> {
+        crate::embedding::generate_embedding(
+            text,
+            self.embedding_provider.as_ref(),
+            self.main_config.embedding.timeout_secs,
+            input_type,
+        )
+        .await
+    }
+
+    /// Nearest memories to `embedding` by cosine distance, closest first.
+    async fn nearest(
+        &self,
+        embedding: Vec<f32>,
+        mut filter: Filter,
+        limit: usize,
+    ) -> Result<Vec<(Memory, f32)>> {
+        let dims = filter.bind(embedding.len() as i32);
+        let vector = filter.bind(Vector::from(embedding));
+        let limit = filter.bind(limit as i64);
+        let sql = format!(
+            "SELECT {}, (embedding <=> {vector})::REAL AS distance FROM memories \
+             WHERE vector_dims(embedding) = {dims} AND {} ORDER BY distance LIMIT {limit}",
+            MEMORY_COLUMNS,
+            filter.sql()
+        );
+        let rows = self.client().await?.query(&sql, &filter.refs()).await?;
+        rows.iter()
+            .map(|row| Ok((memory_from_row(row)?, row.try_get(MEMORY_COLUMN_COUNT)?)))
+            .collect()
+    }
+
+    /// IDs of the best full-text matches for `query_text`, best first.
+    async fn keyword_matches(
+        &self,
+        query_text: &str,
+        mut filter: Filter,
+        limit: usize,
+    ) -> Result<Vec<String>> {
+        // Bare words only, so user text cannot inject tsquery operators
+        let Some(tsquery) = any_terms_tsquery(&keyword_terms(query_text)) else {
+            return Ok(Vec::new());
+        };
+        let tsquery = filter.bind(tsquery);
+        let limit = filter.bind(limit as i64);
+        let sql = format!(
+            "SELECT id FROM memories \
+             WHERE search_text @@ to_tsquery('simple', {tsquery}) AND {} \
+             ORDER BY ts_rank(search_text, to_tsquery('simple', {tsquery})) DESC LIMIT {limit}",
+            filter.sql()
+        );
+        let rows = self.client().await?.query(&sql, &filter.refs()).await?;
+        rows.iter()
+            .map(|row| Ok(row.try_get::<_, String>(0)?))
+            .collect()
+    }
+
+    /// Vector search weighted by temporal importance and trust, like `MemoryStore`.
+    async fn vector_search(&self, query: &MemoryQuery) -> Result<Vec<MemorySearchResult>> {
+        let limit = query
+            .limit
+            .unwrap_or(self.config.max_search_results)
+            .min(self.config.max_search_results);
+        let filter = self.filter(Some(query));
+
+        let hits = match &query.query_text {
+            Some(query_text) => {
+                let embedding = self.embed(query_text, InputType::Query).await?;
+                // Over-fetch to absorb post-filter losses
+                self.nearest(embedding, filter, limit * 2)
+                    .await?
+                    .into_iter()
+                    .map(|(memory, distance)| (memory, Some(distance)))
+                    .collect()
+            }
+            None => self
+                .select_memories(filter)
+                .await?
+                .into_iter()
+                .map(|memory| (memory, None))
+                .collect(),
+        };
+        Ok(score_vector_hits(
+            hits,
+            query,
+            |m| self.current_importance(m),
+            limit,
+        ))
+    }
+
+    /// Vector + full-text ranks fused with RRF, then blended with recency and
+    /// importance using the `[search.hybrid]` weights.
+    async fn hybrid_search(
+        &self,
+        query: &MemoryQuery,
+        query_text: &str,
+        limit: usize,
+    ) -> Result<Vec<MemorySearchResult>> {
+        let embedding = self.embed(query_text, InputType::Query).await?;
+        let vector_hits = self
+            .nearest(embedding, self.filter(Some(query)), limit)
+            .await?;
+        let keyword_ids = self
+            .keyword_matches(query_text, self.filter(Some(query)), limit)
+            .await?;
+
+        let vector_ids: Vec<String> = vector_hits.iter().map(|(m, _)| m.id.clone()).collect();
+        let mut candidates: Vec<Memory> = vector_hits.into_iter().map(|(m, _)| m).collect();
+        let missing: Vec<String> = keyword_ids
+            .iter()
+            .filter(|id| !vector_ids.contains(id))
+            .cloned()
+            .collect();
+        if !missing.is_empty() {
+            let mut filter = Filter::default();
+            filter.push("id = ANY(?)", missing);
+            candidates.extend(self.select_memories(filter).await?);
+        }
+
+        Ok(score_hybrid(
+            candidates,
+            &vector_ids,
+            &keyword_ids,
+            query,
+            &self.main_config.search.hybrid,
+            |m| self.current_importance(m),
+            limit,
+        ))
+    }
+
+    /// Bump access stats and log text queries for returned memories. Best-effort.
+    async fn record_accesses_best_effort(
+        &self,
+        results: &[MemorySearchResult],
+        query_text: Option<&str>,
+    ) {
+        if results.is_empty() {
+            return;
+        }
+        let ids: Vec<String> = results.iter().map(|r| r.memory.id.clone()).collect();
+        let outcome: Result<()> = async {
+            let now = Utc::now();
+            let mut client = self.client().await?;
+            let tx = client.transaction().await?;
+            tx.execute(
+                "UPDATE memories SET access_count = access_count + 1, last_accessed = $1 \
+                 WHERE id = ANY($2) AND project_key = $3",
+                &[&now, &ids, &self.label],
+            )
+            .await?;
+            if let Some(query_text) = query_text {
+                for id in &ids {
+                    tx.execute(
+                        "INSERT INTO memory_access_log \
+                         (id, project_key, role, memory_id, query, accessed_at) \
+                         VALUES ($1, $2, $3, $4, $5, $6)",
+                        &[
+                            &uuid::Uuid::new_v4().to_string(),
+                            &self.label,
+                            &self.role,
+                            id,
+                            &query_text,
+                            &now,
+                        ],
+                    )
+                    .await?;
+                }
+            }
+            tx.commit().await?;
+            Ok(())
+        }
+        .await;
+        if let Err(e) = outcome {
+            tracing::warn!("record_accesses failed (search still succeeded): {}", e);
+        }
+    }
+
+    async fn select_relationships(&self, filter: Filter) -> Result<Vec<MemoryRelationship>> {
+        let sql = format!(
+            "SELECT id, source_id, target_id, relationship_type, strength, description, \
+             created_at FROM memory_relationships WHERE {}",
+            filter.sql()
+        );
+        let rows = self.client().await?.query(&sql, &filter.refs()).await?;
+        rows.iter().map(relationship_from_row).collect()
+    }
+
+    /// Best-effort removal of attachment blobs for permanently deleted memories.
+    fn remove_attachment_blobs(&self, memory_ids: &[String]) {
+        if memory_ids.is_empty() {
+            return;
+        }
+        match crate::storage::get_attachments_dir() {
+            Ok(root) => super::attachments::remove_attachments(&root, &self.label, memory_ids),
+            Err(e) => tracing::warn!("Cannot resolve attachments dir: {}", e),
+        }
+    }
+}
+
+#[async_trait]
+impl StorageBackend for PostgresStore {
+    fn has_no_project_key(&self) -> bool {
+        self.project_key.is_none()
+    }
+
+    fn project_label(&self) -> &str {
+        &self.label
+    }
+
+    async fn store_memory(&self, memory: &Memory) -> Result<()> {
+        let searchable_text = memory.get_searchable_text();
+        if searchable_text.trim().is_empty() {
+            return Err(anyhow::anyhow!(
+                "Cannot generate embedding: searchable text is empty. Title: '{}', Content: '{}'",
+                memory.title,
+                memory.content
+            ));
+        }
+        let embedding = self.embed(&searchable_text, InputType::Document).await?;
+        self.store_memory_with_embedding(memory, embedding).await
+    }
+
+    async fn store_memory_with_embedding(
+        &self,
+        memory: &Memory,
+        embedding: Vec<f32>,
+    ) -> Result<()> {
+        let values: Vec<Param> = vec![
+            Box::new(memory.id.clone()),
+            Box::new(self.label.clone()),
+            Box::new(self.role.clone().unwrap_or_default()),
+            Box::new(memory.memory_type.to_string()),
+            Box::new(memory.title.clone()),
+            Box::new(memory.content.clone()),
+            Box::new(memory.created_at),
+            Box::new(memory.updated_at),
+            Box::new(memory.metadata.importance),
+            Box::new(memory.metadata.confidence),
+            Box::new(serde_json::to_string(&memory.metadata.tags)?),
+            Box::new(serde_json::to_string(&memory.metadata.related_files)?),
+            Box::new(memory.metadata.git_commit.clone()),
+            Box::new(memory.metadata.source.to_string()),
+            Box::new(memory.metadata.decay.access_count.min(i32::MAX as u32) as i32),
+            Box::new(memory.metadata.decay.last_accessed),
+            Box::new(memory.metadata.state.to_string()),
+            Box::new(serde_json::to_string(&memory.metadata.attachments)?),
+            Box::new(serde_json::to_string(&memory.metadata.custom_fields)?),
+            Box::new(memory.metadata.created_by.clone().unwrap_or_default()),
+            Box::new(memory.metadata.decay.decay_rate),
+            Box::new(Vector::from(embedding)),
+        ];
+        let placeholders = (1..=values.len())
+            .map(|i| format!("${}", i))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let sql = format!(
+            "INSERT INTO memories ({}, embedding) VALUES ({}) ON CONFLICT (id) DO UPDATE SET {}",
+            MEMORY_COLUMNS,
+            placeholders,
+            memory_upsert_set()
+        );
+        self.client()
+            .await?
+            .execute(&sql, &param_refs(&values))
+            .await?;
+        Ok(())
+    }
+
+    async fn embedding_dimension(&self) -> Result<usize> {
+        Ok(self.embed("dimension probe", InputType::Query).await?.len())
+    }
+
+    async fn get_all_embeddings(&self) -> Result<HashMap<String, Vec<f32>>> {
+        let filter = self.filter(None);
+        let sql = format!("SELECT id, embedding FROM memories WHERE {}", filter.sql());
+        let rows = self.client().await?.query(&sql, &filter.refs()).await?;
+        rows.iter()
+            .map(|row| {
+                let vector: Vector = row.try_get(1)?;
+                Ok((row.try_get(0)?, vector.to_vec()))
+            })
+            .collect()
+    }
+
+    async fn delete_memories(&self, memory_ids: &[String]) -> Result<usize> {
+        if memory_ids.is_empty() {
+            return Ok(0);
+        }
+        let ids = memory_ids.to_vec();
+        let mut client = self.client().await?;
+        let tx = client.transaction().await?;
+
+        let expired = if self.config.undo_buffer_size > 0 {
+            tx.execute(
+                &format!(
+                    "INSERT INTO memory_recovery ({cols}, embedding, deleted_at) \
+                     SELECT {cols}, embedding, $1 FROM memories \
+                     WHERE id = ANY($2) AND project_key = $3",
+                    cols = MEMORY_COLUMNS
+                ),
+                &[&Utc::now(), &ids, &self.label],
+            )
+            .await?;
+            // Keep only the newest `undo_buffer_size` rows for this project
+            let rows = tx
+                .query(
+                    "DELETE FROM memory_recovery WHERE seq IN (\
+                     SELECT seq FROM memory_recovery WHERE project_key = $1 \
+                     ORDER BY deleted_at DESC, seq DESC OFFSET $2) RETURNING id",
+                    &[&self.label, &(self.config.undo_buffer_size as i64)],
+                )
+                .await?;
+            rows.iter()
+                .map(|row| Ok(row.try_get::<_, String>(0)?))
+                .collect::<Result<Vec<_>>>()?
+        } else {
+            ids.clone()
+        };
+
+        let deleted = tx
+            .execute(
+                "DELETE FROM memories WHERE id = ANY($1) AND project_key = $2",
+                &[&ids, &self.label],
+            )
+            .await?;
+        tx.execute(
+            "DELETE FROM memory_relationships \
+             WHERE (source_id = ANY($1) OR target_id = ANY($1)) AND project_key = $2",
+            &[&ids, &self.label],
+        )
+        .await?;
+        tx.commit().await?;
+
+        // Expired entries can no longer be undone, so their blobs go too
+        self.remove_attachment_blobs(&expired);
+        Ok(deleted as usize)
+    }
+
+    async fn list_recoverable(&self) -> Result<Vec<(Memory, String)>> {
+        let rows = self
+            .client()
+            .await?
+            .query(
+                &format!(
+                    "SELECT {}, deleted_at FROM memory_recovery WHERE project_key = $1 \
+                     ORDER BY deleted_at DESC, seq DESC",
+                    MEMORY_COLUMNS
+                ),
+                &[&self.label],
+            )
+            .await?;
+        rows.iter()
+            .map(|row| {
+                let deleted_at: DateTime<Utc> = row.try_get(MEMORY_COLUMN_COUNT)?;
+                Ok((memory_from_row(row)?, deleted_at.to_rfc3339()))
+            })
+            .collect()
+    }
+
+    async fn restore_last_deletions(&self, operations: usize) -> Result<Vec<Memory>> {
+        let mut client = self.client().await?;
+        let tx = client.transaction().await?;
+        let stamps: Vec<DateTime<Utc>> = tx
+            .query(
+                "SELECT DISTINCT deleted_at FROM memory_recovery WHERE project_key = $1 \
+                 ORDER BY deleted_at DESC LIMIT $2",
+                &[&self.label, &(operations.max(1) as i64)],
+            )
+            .await?
+            .iter()
+            .map(|row| Ok(row.try_get(0)?))
+            .collect::<Result<_>>()?;
+        if stamps.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        // A memory deleted twice in the window comes back as its latest copy
+        let rows = tx
+            .query(
+                &format!(
+                    "INSERT INTO memories ({cols}, embedding) \
+                     SELECT DISTINCT ON (id) {cols}, embedding FROM memory_recovery \
+                     WHERE project_key = $1 AND deleted_at = ANY($2) ORDER BY id, seq DESC \
+                     ON CONFLICT (id) DO UPDATE SET {} RETURNING {cols}",
+                    memory_upsert_set(),
+                    cols = MEMORY_COLUMNS
+                ),
+                &[&self.label, &stamps],
+            )
+            .await?;
+        let restored = rows
+            .iter()
+            .map(memory_from_row)
+            .collect::<Result<Vec<_>>>()?;
+        tx.execute(
+            "DELETE FROM memory_recovery WHERE project_key = $1 AND deleted_at = ANY($2)",
+            &[&self.label, &stamps],
+        )
+        .await?;
+        tx.commit().await?;
+        Ok(restored)
+    }
+
+    async fn restore_deleted(&self, memory_ids: &[String]) -> Result<Vec<Memory>> {
+        if memory_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+        let ids = memory_ids.to_vec();
+        let mut client = self.client().await?;
+        let tx = client.transaction().await?;
+        // A memory deleted more than once comes back as its latest copy
+        let rows = tx
+            .query(
+                &format!(
+                    "INSERT INTO memories ({cols}, embedding) \
+                     SELECT DISTINCT ON (id) {cols}, embedding FROM memory_recovery \
+                     WHERE project_key = $1 AND id = ANY($2) ORDER BY id, seq DESC \
+                     ON CONFLICT (id) DO UPDATE SET {} RETURNING {cols}",
+                    memory_upsert_set(),
+                    cols = MEMORY_COLUMNS
+                ),
+                &[&self.label, &ids],
+            )
+            .await?;
+        let restored = rows
+            .iter()
+            .map(memory_from_row)
+            .collect::<Result<Vec<_>>>()?;
+        tx.execute(
+            "DELETE FROM memory_recovery WHERE project_key = $1 AND id = ANY($2)",
+            &[&self.label, &ids],
+        )
+        .await?;
+        tx.commit().await?;
+        Ok(restored)
+    }
+
+    async fn purge_deleted(&self, deleted_before: Option<DateTime<Utc>>) -> Result<usize> {
+        let rows = self
+            .client()
+            .await?
+            .query(
+                "DELETE FROM memory_recovery WHERE project_key = $1 \
+                 AND ($2::timestamptz IS NULL OR deleted_at < $2) RETURNING id",
+                &[&self.label, &deleted_before],
+            )
+            .await?;
+        let purged = rows
+            .iter()
+            .map(|row| Ok(row.try_get::<_, String>(0)?))
+            .collect::<Result<Vec<_>>>()?;
+        self.remove_attachment_blobs(&purged);
+        Ok(purged.len())
+    }
+
+    async fn run_maintenance(&self) -> Result<()> {
+        self.client()
+            .await?
+            .batch_execute("ANALYZE memories; ANALYZE memory_relationships;")
+            .await?;
+        Ok(())
+    }
+
+    async fn get_memory(&self, memory_id: &str) -> Result<Option<Memory>> {
+        let mut filter = self.project_filter();
+        filter.push("id = ?", memory_id.to_string());
+        Ok(self.select_memories(filter).await?.into_iter().next())
+    }
+
+    async fn search_memories(&self, query: &MemoryQuery) -> Result<Vec<MemorySearchResult>> {
+        let reranker = self
+            .reranker_integration
+            .lock()
+            .ok()
+            .and_then(|g| g.as_ref().filter(|r| r.config.enabled).cloned());
+        let reranker_query = reranker.and_then(|r| {
+            query
+                .query_text
+                .as_deref()
+                .filter(|t| !t.trim().is_empty())
+                .map(|t| (r, t.to_string()))
+        });
+        // Give the reranker a wider candidate set to reorder
+        let top_k = self.main_config.search.reranker.top_k_candidates;
+        let widen = reranker_query.is_some() && top_k > 1;
+
+        let candidates = match query.query_text.as_deref() {
+            Some(text) if self.main_config.search.hybrid.enabled => {
+                let limit = if widen {
+                    top_k
+                } else {
+                    query.limit.unwrap_or(self.config.max_search_results)
+                };
+                self.hybrid_search(query, text, limit).await?
+            }
+            _ if widen => {
+                let mut extended = query.clone();
+                extended.limit = Some(top_k);
+                self.vector_search(&extended).await?
+            }
+            _ => self.vector_search(query).await?,
+        };
+
+        let results = match reranker_query {
+            Some((reranker, text)) => reranker.rerank_memories(&text, candidates).await?,
+            None => candidates,
+        };
+        self.record_accesses_best_effort(&results, query.query_text.as_deref())
+            .await;
+        Ok(results)
+    }
+
+    async fn get_access_log(&self, since: DateTime<Utc>) -> Result<Vec<AccessLogEntry>> {
+        let mut filter = self.filter(None);
+        filter.push("accessed_at >= ?", since);
+        let sql = format!(
+            "SELECT memory_id, query, accessed_at FROM memory_access_log WHERE {}",
+            filter.sql()
+        );
+        let rows = self.client().await?.query(&sql, &filter.refs()).await?;
+        rows.iter()
+            .map(|row| {
+                Ok(AccessLogEntry {
+                    memory_id: row.try_get(0)?,
+                    query: row.try_get(1)?,
+                    accessed_at: row.try_get(2)?,
+                })
+            })
+            .collect()
+    }
+
+    async fn update_state_and_importance(
+        &self,
+        id: &str,
+        new_state: MemoryState,
+        new_importance: f32,
+    ) -> Result<()> {
+        self.client()
+            .await?
+            .execute(
+                "UPDATE memories SET state = $1, importance = $2 WHERE id = $3 AND project_key = $4",
+                &[
+                    &new_state.to_string(),
+                    &new_importance.clamp(0.0, 1.0),
+                    &id,
+                    &self.label,
+                ],
+            )
+            .await
+            .context("partial update of state/importance failed")?;
+        Ok(())
+    }
+
+    async fn get_recent_working_memories(&self, since: DateTime<Utc>) -> Result<Vec<Memory>> {
+        let mut filter = self.filter(None);
+        filter.push("state = ?", MemoryState::Working.to_string());
+        filter.push("created_at >= ?", since);
+        self.select_memories(filter).await
+    }
+
+    async fn get_revisions(&self, memory_id: &str) -> Result<Vec<MemoryRevision>> {
+        let mut filter = Filter::default();
+        filter.push("memory_id = ?", memory_id.to_string());
+        if let Some(key) = self.project_key.as_deref() {
+            filter.push("project_key = ?", key.to_string());
+        }
+        let sql = format!(
+            "SELECT memory_id, revision, replaced_at, memory FROM memory_revisions \
+             WHERE {} ORDER BY revision",
+            filter.sql()
+        );
+        let rows = self.client().await?.query(&sql, &filter.refs()).await?;
+        rows.iter()
+            .map(|row| {
+                Ok(MemoryRevision {
+                    memory_id: row.try_get(0)?,
+                    revision: row.try_get::<_, i32>(1)?.max(0) as u32,
+                    replaced_at: row.try_get(2)?,
+                    memory: serde_json::from_str(row.try_get(3)?)
+                        .context("Corrupt memory revision")?,
+                })
+            })
+            .collect()
+    }
+
+    async fn append_revision(&self, revision: &MemoryRevision) -> Result<()> {
+        self.client()
+            .await?
+            .execute(
+                "INSERT INTO memory_revisions \
+                 (id, project_key, memory_id, revision, replaced_at, memory) \
+                 VALUES ($1, $2, $3, $4, $5, $6)",
+                &[
+                    &uuid::Uuid::new_v4().to_string(),
+                    &self.label,
+                    &revision.memory_id,
+                    &(revision.revision as i32),
+                    &revision.replaced_at,
+                    &serde_json::to_string(&revision.memory)?,
+                ],
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn store_relationship(&self, relationship: &MemoryRelationship) -> Result<()> {
+        self.store_relationships(std::slice::from_ref(relationship))
+            .await
+    }
+
+    async fn store_relationships(&self, relationships: &[MemoryRelationship]) -> Result<()> {
+        if relationships.is_empty() {
+            return Ok(());
+        }
+        let mut client = self.client().await?;
+        let tx = client.transaction().await?;
+        for relationship in relationships {
+            tx.execute(
+                "INSERT INTO memory_relationships \
+                 (id, source_id, target_id, project_key, relationship_type, strength, \
+                 description, created_at) VALUES ($1, $2, $3, $4, $5, $6, $7, $8) \
+                 ON CONFLICT (id) DO UPDATE SET source_id = EXCLUDED.source_id, \
+                 target_id = EXCLUDED.target_id, project_key = EXCLUDED.project_key, \
+                 relationship_type = EXCLUDED.relationship_type, strength = EXCLUDED.strength, \
+                 description = EXCLUDED.description, created_at = EXCLUDED.created_at",
+                &[
+                    &relationship.id,
+                    &relationship.source_id,
+                    &relationship.target_id,
+                    &self.label,
+                    &relationship.relationship_type.to_string(),
+                    &relationship.strength,
+                    &relationship.description,
+                    &relationship.created_at,
+                ],
+            )
+            .await?;
+        }
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn get_memory_relationships(&self, memory_id: &str) -> Result<Vec<MemoryRelationship>> {
+        let mut filter = self.project_filter();
+        filter.push("(source_id = ? OR target_id = ?)", memory_id.to_string());
+        self.select_relationships(filter).await
+    }
+
+    async fn get_all_relationships(&self) -> Result<Vec<MemoryRelationship>> {
+        self.select_relationships(self.project_filter()).await
+    }
+
+    async fn delete_auto_linked_relationships(&self, memory_id: &str) -> Result<()> {
+        self.client()
+            .await?
+            .execute(
+                "DELETE FROM memory_relationships WHERE (source_id = $1 OR target_id = $1) \
+                 AND relationship_type = $2 AND project_key = $3",
+                &[
+                    &memory_id,
+                    &RelationshipType::AutoLinked.to_string(),
+                    &self.label,
+                ],
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn delete_relationships(&self, relationship_ids: &[String]) -> Result<usize> {
+        if relationship_ids.is_empty() {
+            return Ok(0);
+        }
+        let ids = relationship_ids.to_vec();
+        let deleted = self
+            .client()
+            .await?
+            .execute(
+                "DELETE FROM memory_relationships WHERE id = ANY($1) AND project_key = $2",
+                &[&ids, &self.label],
+            )
+            .await?;
+        Ok(deleted as usize)
+    }
+
+    async fn get_memory_count(&self) -> Result<usize> {
+        let filter = self.project_filter();
+        let sql = format!("SELECT COUNT(*) FROM memories WHERE {}", filter.sql());
+        let row = self.client().await?.query_one(&sql, &filter.refs()).await?;
+        Ok(row.try_get::<_, i64>(0)? as usize)
+    }
+
+    async fn get_distinct_projects_and_roles(&self) -> Result<(Vec<String>, Vec<String>)> {
+        let filter = self.project_filter();
+        let condition = filter.sql();
+        let client = self.client().await?;
+        let projects = client
+            .query(
+                &format!(
+                    "SELECT DISTINCT project_key FROM memories WHERE {} ORDER BY project_key",
+                    condition
+                ),
+                &filter.refs(),
+            )
+            .await?
+            .iter()
+            .map(|row| Ok(row.try_get(0)?))
+            .collect::<Result<Vec<String>>>()?;
+        let roles = client
+            .query(
+                &format!(
+                    "SELECT DISTINCT role FROM memories WHERE {} AND role != '' ORDER BY role",
+                    condition
+                ),
+                &filter.refs(),
+            )
+            .await?
+            .iter()
+            .map(|row| Ok(row.try_get(0)?))
+            .collect::<Result<Vec<String>>>()?;
+        Ok((projects, roles))
+    }
+
+    async fn get_all_memories(&self) -> Result<Vec<Memory>> {
+        self.select_memories(self.filter(None)).await
+    }
+
+    async fn get_memories_with_files(&self) -> Result<Vec<Memory>> {
+        let mut filter = self.project_filter();
+        filter.push_raw("related_files != '[]'");
+        self.select_memories(filter).await
+    }
+
+    async fn cleanup_old_memories(&self) -> Result<usize> {
+        let Some(cleanup_days) = self.config.auto_cleanup_days else {
+            return Ok(0);
+        };
+        let cutoff = Utc::now() - chrono::Duration::days(cleanup_days as i64);
+        let ids = self
+            .client()
+            .await?
+            .query(
+                "SELECT id FROM memories WHERE project_key = $1 AND created_at < $2 \
+                 AND importance < $3",
+                &[&self.label, &cutoff, &self.config.cleanup_min_importance],
+            )
+            .await?
+            .iter()
+            .map(|row| Ok(row.try_get::<_, String>(0)?))
+            .collect::<Result<Vec<_>>>()?;
+        // Through the recovery buffer, so a cleanup can be undone from the trash
+        self.delete_memories(&ids).await
+    }
+
+    async fn clear_all_memory_data(&self) -> Result<usize> {
+        let mut client = self.client().await?;
+        let tx = client.transaction().await?;
+        let memories = tx
+            .execute(
+                "DELETE FROM memories WHERE project_key = $1",
+                &[&self.label],
+            )
+            .await?;
+        let relationships = tx
+            .execute(
+                "DELETE FROM memory_relationships WHERE project_key = $1",
+                &[&self.label],
+            )
+            .await?;
+        tx.execute(
+            "DELETE FROM memory_access_log WHERE project_key = $1",
+            &[&self.label],
+        )
+        .await?;
+        tx.execute(
+            "DELETE FROM memory_revisions WHERE project_key = $1",
+            &[&self.label],
+        )
+        .await?;
+        tx.commit().await?;
+        Ok((memories + relationships) as usize)
+    }
+
+    fn enable_reranker(&self, model: Option<String>) {
+        super::reranker_integration::enable_reranker(
+            &self.reranker_integration,
+            &self.main_config.search.reranker,
+            model,
+        );
+    }
+
+    fn disable_reranker(&self) {
+        super::reranker_integration::disable_reranker(&self.reranker_integration);
+    }
+}
@@ -0,0 +1,464 @@
+// Copyright 2026 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Field-prefixed boolean filters inside a search query.
+//!
+//! `keyword:(auth AND NOT oauth) tag:backend jwt rotation` splits into a filter
+//! tree (`keyword:` / `tag:` / `type:` / `file:` clauses, all required) and the
+//! remaining free text, which still drives semantic and BM25 ranking. Inside a
+//! clause's parentheses `AND`, `OR`, `NOT` and grouping are supported; adjacent
+//! terms are ANDed. A clause can be negated with a leading `NOT` or `-`.
+//!
+//! Keywords match whole words of the title and content, case-insensitively, so
+//! `NOT oauth` does not exclude a memory that only mentions `auth`. Queries with
+//! no field prefix are passed through untouched.
+//!
+//! `after:` and `before:` bound the creation date (`after:2w`,
+//! `before:2026-01-01`), and `importance>0.6` or `confidence<=0.5` compare
+//! scores with `<`, `<=`, `>`, `>=` or `=`, so
+//! `type:bug_fix tag:auth -tag:old importance>0.6 "token refresh"` needs no flags.
+//! A comparison without a number (`importance=high`) or a quote left open in
+//! free text is just text; only a malformed field clause is an error.
+
+use anyhow::{bail, Result};
+use chrono::{DateTime, NaiveDate, NaiveTime, Utc};
+
+use super::digest::parse_window;
+use super::search_scoring::keyword_terms;
+use super::types::{Memory, MemoryType};
+
+/// Boolean filter over a memory's words, tags, type and related files
+#[derive(Debug, Clone, PartialEq)]
+pub enum QueryExpr {
+    /// Whole word, or consecutive words for a quoted phrase
+    Keyword(String),
+    Tag(String),
+    Type(MemoryType),
+    /// Related file path, or a suffix of one (`file:auth.rs`)
+    File(String),
+    Importance(Comparison, f32),
+    Confidence(Comparison, f32),
+    CreatedAfter(DateTime<Utc>),
+    CreatedBefore(DateTime<Utc>),
+    And(Vec<QueryExpr>),
+    Or(Vec<QueryExpr>),
+    Not(Box<QueryExpr>),
+}
+
+/// Operator of a score comparison (`importance>0.6`)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Comparison {
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    Eq,
+}
+
+impl Comparison {
+    fn holds(self, left: f32, right: f32) -> bool {
+        match self {
+            Self::Lt => left < right,
+            Self::Le => left <= right,
+            Self::Gt => left > right,
+            Self::Ge => left >= right,
+            Self::Eq => (left - right).abs() < f32::EPSILON,
+        }
+    }
+}
+
+/// `importance>0.6` / `confidence<=0.5` as a clause; `None` for other words,
+/// including a comparison whose value is not a number
+fn parse_comparison(word: &str) -> Option<QueryExpr> {
+    let at = word.find(['<', '>', '='])?;
+    let (name, rest) = word.split_at(at);
+    let (op, value) = [
+        ("<=", Comparison::Le),
+        (">=", Comparison::Ge),
+        ("<", Comparison::Lt),
+        (">", Comparison::Gt),
+        ("=", Comparison::Eq),
+    ]
+    .iter()
+    .find_map(|(symbol, op)| rest.strip_prefix(symbol).map(|value| (*op, value)))?;
+    let leaf: fn(Comparison, f32) -> QueryExpr = match name.to_lowercase().as_str() {
+        "importance" => QueryExpr::Importance,
+        "confidence" => QueryExpr::Confidence,
+        _ => return None,
+    };
+    value.parse::<f32>().ok().map(|value| leaf(op, value))
+}
+
+/// Memory type named in a `type:` clause. `MemoryType::from` turns unknown
+/// names into `Insight`, so a typo would silently match the wrong memories.
+fn parse_memory_type(value: &str) -> Result<MemoryType> {
+    let memory_type = MemoryType::from(value.to_string());
+    if memory_type == MemoryType::Insight && !value.eq_ignore_ascii_case("insight") {
+        let names: Vec<String> = MemoryType::ALL.iter().map(|t| t.to_string()).collect();
+        bail!(
+            "Unknown memory type '{}' in query; valid types: {}",
+            value,
+            names.join(", ")
+        );
+    }
+    Ok(memory_type)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Field {
+    Keyword,
+    Tag,
+    Type,
+    File,
+    After,
+    Before,
+}
+
+impl Field {
+    fn parse(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "keyword" | "kw" => Some(Self::Keyword),
+            "tag" => Some(Self::Tag),
+            "type" => Some(Self::Type),
+            "file" => Some(Self::File),
+            "after" => Some(Self::After),
+            "before" => Some(Self::Before),
+            _ => None,
+        }
+    }
+
+    fn leaf(self, value: &str) -> Result<QueryExpr> {
+        Ok(match self {
+            Self::Keyword => QueryExpr::Keyword(value.to_lowercase()),
+            Self::Tag => QueryExpr::Tag(value.to_string()),
+            Self::Type => QueryExpr::Type(parse_memory_type(value)?),
+            Self::File => QueryExpr::File(value.to_string()),
+            Self::After => QueryExpr::CreatedAfter(parse_date_bound(value, Utc::now())?),
+            Self::Before => QueryExpr::CreatedBefore(parse_date_bound(value, Utc::now())?),
+        })
+    }
+}
+
+impl QueryExpr {
+    /// Whether `memory` satisfies the expression
+    pub fn matches(&self, memory: &Memory) -> bool {
+        let words = format!(
+            " {} ",
+            keyword_terms(&format!("{} {}", memory.title, memory.content)).join(" ")
+        );
+        self.eval(memory, &words)
+    }
+
+    fn eval(&self, memory: &Memory, words: &str) -> bool {
+        match self {
+            Self::Keyword(phrase) => {
+                let terms = keyword_terms(phrase);
+                !terms.is_empty() && words.contains(&format!(" {} ", terms.join(" ")))
+            }
+            Self::Tag(tag) => memory
+                .metadata
+                .tags
+                .iter()
+                .any(|t| t.eq_ignore_ascii_case(tag)),
+            Self::Type(memory_type) => memory.memory_type == *memory_type,
+            Self::File(file) => memory
+                .metadata
+                .related_files
+                .iter()
+                .any(|f| f == file || f.ends_with(&format!("/{}", file))),
+            Self::Importance(op, value) => op.holds(memory.metadata.importance, *value),
+            Self::Confidence(op, value) => op.holds(memory.metadata.confidence, *value),
+            Self::CreatedAfter(at) => memory.created_at >= *at,
+            Self::CreatedBefore(at) => memory.created_at <= *at,
+            Self::And(items) => items.iter().all(|e| e.eval(memory, words)),
+            Self::Or(items) => items.iter().any(|e| e.eval(memory, words)),
+            Self::Not(inner) => !inner.eval(memory, words),
+        }
+    }
+
+    /// Keywords every matching memory contains, used as search text when the
+    /// query has no free text of its own and to prefilter through the FTS index.
+    /// An `OR` contributes only the keywords all its branches require; a `NOT`
+    /// contributes none.
+    pub fn required_keywords(&self) -> Vec<String> {
+        match self {
+            Self::Keyword(k) => vec![k.clone()],
+            Self::And(items) => items.iter().flat_map(Self::required_keywords).collect(),
+            Self::Or(items) => {
+                let mut branches = items.iter().map(Self::required_keywords);
+                let first = branches.next().unwrap_or_default();
+                branches.fold(first, |common, branch| {
+                    common.into_iter().filter(|k| branch.contains(k)).collect()
+                })
+            }
+            _ => Vec::new(),
+        }
+    }
+}
+
+/// A search query split into free text and a filter
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParsedQuery {
+    /// Words outside any field clause; empty when there are none
+    pub text: String,
+    /// All field clauses ANDed; `None` when the query had none
+    pub filter: Option<QueryExpr>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Field(Field),
+    /// A complete `importance>0.6` style clause
+    Compare(QueryExpr),
+    Word(String),
+    Quoted(String),
+    /// Text after a `"` that is never closed
+    Unterminated(String),
+    Open,
+    Close,
+    Minus,
+}
+
+fn tokenize(input: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut chars = input.chars().peekable();
+    while let Some(&c) = chars.peek() {
+        match c {
+            c if c.is_whitespace() => {
+                chars.next();
+            }
+            '(' => {
+                chars.next();
+                tokens.push(Token::Open);
+            }
+            ')' => {
+                chars.next();
+                tokens.push(Token::Close);
+            }
+            '-' if tokens.last().is_none_or(|t| !matches!(t, Token::Field(_))) => {
+                chars.next();
+                tokens.push(Token::Minus);
+            }
+            '"' => {
+                chars.next();
+                let mut quoted = String::new();
+                let token = loop {
+                    match chars.next() {
+                        Some('"') => break Token::Quoted(quoted),
+                        Some(ch) => quoted.push(ch),
+                        None => break Token::Unterminated(quoted),
+                    }
+                };
+                tokens.push(token);
+            }
+            _ => {
+                let mut word = String::new();
+                while let Some(&ch) = chars.peek() {
+                    if ch.is_whitespace() || ch == '(' || ch == ')' || ch == '"' {
+                        break;
+                    }
+                    chars.next();
+                    if ch == ':' {
+                        if let Some(field) = Field::parse(&word) {
+                            tokens.push(Token::Field(field));
+                            word.clear();
+                            break;
+                        }
+                    }
+                    word.push(ch);
+                }
+                if let Some(clause) = parse_comparison(&word) {
+                    tokens.push(Token::Compare(clause));
+                } else if !word.is_empty() {
+                    tokens.push(Token::Word(word));
+                }
+            }
+        }
+    }
+    tokens
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn advance(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn peek_operator(&self, op: &str) -> bool {
+        matches!(self.peek(), Some(Token::Word(w)) if w == op)
+    }
+
+    /// Top level: field clauses (optionally negated) and free text
+    fn parse_query(&mut self) -> Result<ParsedQuery> {
+        let mut text = Vec::new();
+        let mut clauses = Vec::new();
+        while let Some(token) = self.peek().cloned() {
+            let negated = match token {
+                Token::Minus => {
+                    self.advance();
+                    true
+                }
+                Token::Word(ref w)
+                    if w == "NOT"
+                        && matches!(
+                            self.tokens.get(self.pos + 1),
+                            Some(Token::Field(_) | Token::Compare(_))
+                        ) =>
+                {
+                    self.advance();
+                    true
+                }
+                _ => false,
+            };
+            let clause = match self.advance() {
+                Some(Token::Field(field)) => self.parse_field_value(field)?,
+                Some(Token::Compare(clause)) => clause,
+                Some(Token::Word(w)) | Some(Token::Quoted(w)) | Some(Token::Unterminated(w)) => {
+                    text.push(if negated { format!("-{}", w) } else { w });
+                    continue;
+                }
+                // Stray punctuation in free text is not part of any clause
+                Some(Token::Open) | Some(Token::Close) | Some(Token::Minus) | None => continue,
+            };
+            clauses.push(if negated {
+                QueryExpr::Not(Box::new(clause))
+            } else {
+                clause
+            });
+        }
+        let filter = match clauses.len() {
+            0 => None,
+            1 => clauses.pop(),
+            _ => Some(QueryExpr::And(clauses)),
+        };
+        Ok(ParsedQuery {
+            text: text.join(" "),
+            filter,
+        })
+    }
+
+    fn parse_field_value(&mut self, field: Field) -> Result<QueryExpr> {
+        match self.advance() {
+            Some(Token::Open) => {
+                let expr = self.parse_or(field)?;
+                match self.advance() {
+                    Some(Token::Close) => Ok(expr),
+                    _ => bail!("Missing ')' in query"),
+                }
+            }
+            Some(Token::Word(w)) | Some(Token::Quoted(w)) => field.leaf(&w),
+            Some(Token::Unterminated(_)) => bail!("Unterminated quote in query"),
+            _ => bail!("Expected a value after a field prefix in query"),
+        }
+    }
+
+    fn parse_or(&mut self, field: Field) -> Result<QueryExpr> {
+        let mut items = vec![self.parse_and(field)?];
+        while self.peek_operator("OR") {
+            self.advance();
+            items.push(self.parse_and(field)?);
+        }
+        Ok(if items.len() == 1 {
+            items.remove(0)
+        } else {
+            QueryExpr::Or(items)
+        })
+    }
+
+    fn parse_and(&mut self, field: Field) -> Result<QueryExpr> {
+        let mut items = vec![self.parse_unary(field)?];
+        loop {
+            if self.peek_operator("AND") {
+                self.advance();
+            } else if matches!(self.peek(), None | Some(Token::Close)) || self.peek_operator("OR") {
+                break;
+            }
+            items.push(self.parse_unary(field)?);
+        }
+        Ok(if items.len() == 1 {
+            items.remove(0)
+        } else {
+            QueryExpr::And(items)
+        })
+    }
+
+    fn parse_unary(&mut self, field: Field) -> Result<QueryExpr> {
+        match self.advance() {
+            Some(Token::Word(w)) if w == "NOT" => {
+                Ok(QueryExpr::Not(Box::new(self.parse_unary(field)?)))
+            }
+            Some(Token::Minus) => Ok(QueryExpr::Not(Box::new(self.parse_unary(field)?))),
+            Some(Token::Open) => {
+                let expr = self.parse_or(field)?;
+                match self.advance() {
+                    Some(Token::Close) => Ok(expr),
+                    _ => bail!("Missing ')' in query"),
+                }
+            }
+            Some(Token::Word(w)) if w == "AND" || w == "OR" => {
+                bail!("'{}' needs a term on both sides", w)
+            }
+            Some(Token::Word(w)) | Some(Token::Quoted(w)) => field.leaf(&w),
+            Some(Token::Compare(clause)) => Ok(clause),
+            Some(Token::Unterminated(_)) => bail!("Unterminated quote in query"),
+            Some(Token::Field(_)) => bail!("Field prefixes cannot be nested inside '(...)'"),
+            Some(Token::Close) => bail!("Unexpected ')' in query"),
+            None => bail!("Query ends where a term was expected"),
+        }
+    }
+}
+
+/// Split `input` into free text and a filter tree.
+pub fn parse_query(input: &str) -> Result<ParsedQuery> {
+    let tokens = tokenize(input);
+    if !tokens
+        .iter()
+        .any(|t| matches!(t, Token::Field(_) | Token::Compare(_)))
+    {
+        return Ok(ParsedQuery {
+            text: input.to_string(),
+            filter: None,
+        });
+    }
+    Parser { tokens, pos: 0 }.parse_query()
+}
+
+/// Parse a creation-date bound (`--after`/`--before`): an RFC 3339 timestamp,
+/// an ISO date (midnight UTC), or a window back from `now` like `3d` or `2w`.
+pub fn parse_date_bound(value: &str, now: DateTime<Utc>) -> Result<DateTime<Utc>> {
+    let value = value.trim();
+    if let Ok(at) = DateTime::parse_from_rfc3339(value) {
+        return Ok(at.with_timezone(&Utc));
+    }
+    if let Ok(date) = NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+        return Ok(date.and_time(NaiveTime::MIN).and_utc());
+    }
+    match parse_window(value) {
+        Ok(window) => Ok(now - window),
+        Err(_) => bail!(
+            "Invalid date '{}'; expected YYYY-MM-DD, an RFC 3339 timestamp, or a relative window like 3d or 2w",
+            value
+        ),
+    }
+}
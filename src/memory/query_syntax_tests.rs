@@ -0,0 +1,221 @@
+// Copyright 2026 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#[cfg(test)]
+mod tests {
+    use super::super::manager::with_query_text;
+    use super::super::query_syntax::{parse_date_bound, parse_query, QueryExpr};
+    use super::super::types::{Memory, MemoryQuery, MemoryType};
+
+    fn memory(title: &str, content: &str, tags: &[&str]) -> Memory {
+        let mut m = Memory::new(
+            MemoryType::Code,
+            title.to_string(),
+            content.to_string(),
+            None,
+        );
+        m.metadata.tags = tags.iter().map(|t| t.to_string()).collect();
+        m
+    }
+
+    #[test]
+    fn test_plain_query_passes_through() {
+        let parsed = parse_query("how do we rotate jwt keys?").unwrap();
+        assert_eq!(parsed.text, "how do we rotate jwt keys?");
+        assert!(parsed.filter.is_none());
+    }
+
+    #[test]
+    fn test_clauses_split_from_free_text() {
+        let parsed =
+            parse_query("keyword:(auth AND NOT oauth) tag:backend token rotation").unwrap();
+        assert_eq!(parsed.text, "token rotation");
+        assert_eq!(
+            parsed.filter,
+            Some(QueryExpr::And(vec![
+                QueryExpr::And(vec![
+                    QueryExpr::Keyword("auth".to_string()),
+                    QueryExpr::Not(Box::new(QueryExpr::Keyword("oauth".to_string()))),
+                ]),
+                QueryExpr::Tag("backend".to_string()),
+            ]))
+        );
+    }
+
+    #[test]
+    fn test_keywords_match_whole_words() {
+        let expr = parse_query("keyword:(auth AND NOT oauth)")
+            .unwrap()
+            .filter
+            .unwrap();
+        assert!(expr.matches(&memory("Auth middleware", "Checks session cookies", &[])));
+        assert!(!expr.matches(&memory("Auth middleware", "Falls back to OAuth", &[])));
+        assert!(!expr.matches(&memory("Authorization", "Role checks", &[])));
+    }
+
+    #[test]
+    fn test_or_phrase_and_negated_clause() {
+        let expr = parse_query("keyword:(\"rate limit\" OR throttle) -tag:wip")
+            .unwrap()
+            .filter
+            .unwrap();
+        assert!(expr.matches(&memory("API gateway", "Apply a rate limit per key", &[])));
+        assert!(expr.matches(&memory("Throttle", "Slow clients down", &["backend"])));
+        assert!(!expr.matches(&memory("Throttle", "Slow clients down", &["wip"])));
+        assert!(!expr.matches(&memory("API gateway", "rate is limited", &[])));
+    }
+
+    #[test]
+    fn test_type_clause() {
+        let expr = parse_query("type:decision").unwrap().filter.unwrap();
+        let mut decision = memory("Use Postgres", "Chosen for pgvector", &[]);
+        decision.memory_type = MemoryType::Decision;
+        assert!(expr.matches(&decision));
+        assert!(!expr.matches(&memory("Use Postgres", "Chosen for pgvector", &[])));
+    }
+
+    #[test]
+    fn test_unknown_type_rejected() {
+        let err = parse_query("type:bugfx tag:auth").unwrap_err().to_string();
+        assert!(err.contains("'bugfx'"));
+        assert!(err.contains("bug_fix") && err.contains("insight"));
+        assert!(parse_query("type:Insight").is_ok());
+        assert!(parse_query("type:bug").is_ok());
+    }
+
+    #[test]
+    fn test_malformed_queries_rejected() {
+        assert!(parse_query("keyword:(auth AND").is_err());
+        assert!(parse_query("keyword:(auth OR)").is_err());
+        assert!(parse_query("tag:\"unterminated").is_err());
+        assert!(parse_query("keyword:(tag:x)").is_err());
+    }
+
+    #[test]
+    fn test_malformed_free_text_passes_through() {
+        for input in [
+            "importance=high",
+            "why does \"token refresh fail",
+            "confidence>",
+        ] {
+            let parsed = parse_query(input).unwrap();
+            assert_eq!(parsed.text, input);
+            assert!(parsed.filter.is_none());
+        }
+
+        let parsed = parse_query("tag:auth importance=high \"token refresh").unwrap();
+        assert_eq!(parsed.text, "importance=high token refresh");
+        assert_eq!(parsed.filter, Some(QueryExpr::Tag("auth".to_string())));
+    }
+
+    #[test]
+    fn test_unknown_key_value_words_are_text() {
+        let parsed = parse_query("retries=3 timeout<=30s owner:alice tag:backend").unwrap();
+        assert_eq!(parsed.text, "retries=3 timeout<=30s owner:alice");
+        assert_eq!(parsed.filter, Some(QueryExpr::Tag("backend".to_string())));
+
+        let parsed = parse_query("env=prod region:eu").unwrap();
+        assert_eq!(parsed.text, "env=prod region:eu");
+        assert!(parsed.filter.is_none());
+    }
+
+    #[test]
+    fn test_required_keywords_become_search_text() {
+        let query =
+            with_query_text(MemoryQuery::default(), "keyword:(auth AND NOT oauth)").unwrap();
+        assert_eq!(query.query_text.as_deref(), Some("auth"));
+        assert!(query.filter_expr.is_some());
+
+        let query = with_query_text(MemoryQuery::default(), "tag:backend").unwrap();
+        assert!(query.query_text.is_none());
+    }
+
+    #[test]
+    fn test_or_branches_require_only_shared_keywords() {
+        let required = |q: &str| parse_query(q).unwrap().filter.unwrap().required_keywords();
+        assert!(required("keyword:(auth OR NOT jwt)").is_empty());
+        assert!(required("keyword:(\"rate limit\" OR throttle)").is_empty());
+        assert_eq!(required("keyword:(auth OR (auth AND jwt))"), vec!["auth"]);
+        assert_eq!(
+            required("keyword:auth keyword:(jwt OR oauth)"),
+            vec!["auth"]
+        );
+
+        // A memory with neither keyword still matches through the NOT branch
+        let expr = parse_query("keyword:(auth OR NOT jwt)")
+            .unwrap()
+            .filter
+            .unwrap();
+        assert!(expr.matches(&memory("Cache", "Evict stale rows", &[])));
+        assert!(!expr.matches(&memory("Tokens", "Sign with jwt", &[])));
+    }
+
+    #[test]
+    fn test_parse_date_bound() {
+        use chrono::{Duration, TimeZone, Utc};
+        let now = Utc.with_ymd_and_hms(2026, 3, 15, 12, 0, 0).unwrap();
+        assert_eq!(
+            parse_date_bound("2026-01-02", now).unwrap(),
+            Utc.with_ymd_and_hms(2026, 1, 2, 0, 0, 0).unwrap()
+        );
+        assert_eq!(
+            parse_date_bound("2026-01-02T10:30:00+02:00", now).unwrap(),
+            Utc.with_ymd_and_hms(2026, 1, 2, 8, 30, 0).unwrap()
+        );
+        assert_eq!(
+            parse_date_bound("3d", now).unwrap(),
+            now - Duration::days(3)
+        );
+        assert_eq!(
+            parse_date_bound(" 2w ", now).unwrap(),
+            now - Duration::weeks(2)
+        );
+        assert!(parse_date_bound("yesterday", now).is_err());
+        assert!(parse_date_bound("2026-13-01", now).is_err());
+    }
+
+    #[test]
+    fn test_comparison_and_date_clauses() {
+        let parsed =
+            parse_query("type:bug_fix tag:auth -tag:old importance>0.6 \"token refresh\"").unwrap();
+        assert_eq!(parsed.text, "token refresh");
+        let expr = parsed.filter.unwrap();
+
+        let mut fix = memory("Refresh race", "Token refresh raced", &["auth"]);
+        fix.memory_type = MemoryType::BugFix;
+        fix.metadata.importance = 0.8;
+        assert!(expr.matches(&fix));
+
+        fix.metadata.importance = 0.6;
+        assert!(!expr.matches(&fix));
+        fix.metadata.importance = 0.8;
+        fix.metadata.tags.push("old".to_string());
+        assert!(!expr.matches(&fix));
+
+        let expr = parse_query("confidence<=0.5 after:2020-01-01 before:1d")
+            .unwrap()
+            .filter
+            .unwrap();
+        let mut guess = memory("Maybe cache", "Possibly a cache bug", &[]);
+        guess.metadata.confidence = 0.5;
+        guess.created_at -= chrono::Duration::days(2);
+        assert!(expr.matches(&guess));
+        guess.created_at = chrono::Utc::now();
+        assert!(!expr.matches(&guess));
+
+        // Unknown names and non-numbers stay free text; a bad date is an error
+        assert_eq!(parse_query("a>b").unwrap().text, "a>b");
+        assert!(parse_query("after:someday").is_err());
+    }
+}
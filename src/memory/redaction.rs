@@ -0,0 +1,78 @@
+// Copyright 2026 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Opt-in PII redaction applied to memory title/content before embedding and storage.
+
+use anyhow::{Context, Result};
+use regex::Regex;
+
+/// Tag added to memories whose text was altered by redaction (audit trail).
+pub const REDACTED_TAG: &str = "redacted";
+
+/// Built-in (kind, pattern) pairs. Kinds become the `[REDACTED_<KIND>]` placeholder.
+const BUILTIN_PATTERNS: &[(&str, &str)] = &[
+    ("EMAIL", r"[A-Za-z0-9._%+-]+@[A-Za-z0-9.-]+\.[A-Za-z]{2,}"),
+    (
+        "PHONE",
+        r"(?:\+\d{1,3}[\s.-]?)?(?:\(\d{2,4}\)|\d{2,4})[\s.-]\d{3,4}[\s.-]\d{3,4}\b",
+    ),
+    ("API_KEY", r"\bAKIA[0-9A-Z]{16}\b"),
+    ("API_KEY", r"\bgh[pousr]_[A-Za-z0-9]{36,}\b"),
+    ("API_KEY", r"\bxox[abprs]-[A-Za-z0-9-]{10,}\b"),
+    ("API_KEY", r"\b[sprk]k_(?:live|test)_[A-Za-z0-9]{16,}\b"),
+    ("API_KEY", r"\bsk-[A-Za-z0-9_-]{20,}\b"),
+];
+
+/// Compiled redaction rules: built-ins plus user patterns from `redaction_patterns`.
+pub struct Redactor {
+    rules: Vec<(String, Regex)>,
+}
+
+impl Redactor {
+    /// Compile built-in rules and `custom_patterns`. Custom matches use `[REDACTED]`.
+    pub fn new(custom_patterns: &[String]) -> Result<Self> {
+        let mut rules = Vec::with_capacity(BUILTIN_PATTERNS.len() + custom_patterns.len());
+        for (kind, pattern) in BUILTIN_PATTERNS {
+            rules.push((
+                format!("[REDACTED_{}]", kind),
+                Regex::new(pattern).context("invalid built-in redaction pattern")?,
+            ));
+        }
+        for pattern in custom_patterns {
+            rules.push((
+                "[REDACTED]".to_string(),
+                Regex::new(pattern)
+                    .with_context(|| format!("invalid redaction pattern '{}'", pattern))?,
+            ));
+        }
+        Ok(Self { rules })
+    }
+
+    /// Mask every rule match in `text`. Returns the masked text and the number of
+    /// replacements made.
+    pub fn redact(&self, text: &str) -> (String, usize) {
+        let mut output = text.to_string();
+        let mut replaced = 0;
+        for (placeholder, regex) in &self.rules {
+            let hits = regex.find_iter(&output).count();
+            if hits > 0 {
+                replaced += hits;
+                output = regex
+                    .replace_all(&output, placeholder.as_str())
+                    .into_owned();
+            }
+        }
+        (output, replaced)
+    }
+}
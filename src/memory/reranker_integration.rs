@@ -43,8 +43,9 @@
 //! ```
 
 use crate::config::RerankerConfig;
-use crate::memory::types::MemorySearchResult;
+use crate::memory::types::{MemorySearchResult, SearchSignal};
 use anyhow::Result;
+use std::sync::Mutex;
 
 /// Reranker integration wrapper
 #[derive(Clone)]
@@ -116,7 +117,14 @@ impl RerankerIntegration {
         for rerank_result in rerank_response.results {
             if let Some(original) = results.get_mut(rerank_result.index) {
                 // Update relevance score with reranker score (convert f64 to f32)
-                original.relevance_score = rerank_result.relevance_score as f32;
+                let score = rerank_result.relevance_score as f32;
+                if !original.signals.is_empty() {
+                    original.signals.push(SearchSignal::Reranker {
+                        score,
+                        previous: original.relevance_score,
+                    });
+                }
+                original.relevance_score = score;
                 reranked_results.push(original.clone());
             }
         }
@@ -124,3 +132,40 @@ impl RerankerIntegration {
         Ok(reranked_results)
     }
 }
+
+/// Turn reranking on in a store's reranker slot, creating the reranker from
+/// `defaults` when the slot is empty. `model` overrides the configured model.
+pub fn enable_reranker(
+    slot: &Mutex<Option<RerankerIntegration>>,
+    defaults: &RerankerConfig,
+    model: Option<String>,
+) {
+    let mut guard = match slot.lock() {
+        Ok(g) => g,
+        Err(p) => p.into_inner(), // recover from poisoning — config-only data
+    };
+    if let Some(ref mut reranker) = *guard {
+        if let Some(m) = model {
+            reranker.config.model = m;
+        }
+        reranker.config.enabled = true;
+    } else {
+        let mut config = defaults.clone();
+        config.enabled = true;
+        if let Some(m) = model {
+            config.model = m;
+        }
+        *guard = Some(RerankerIntegration::new(config));
+    }
+}
+
+/// Turn reranking off in a store's reranker slot.
+pub fn disable_reranker(slot: &Mutex<Option<RerankerIntegration>>) {
+    let mut guard = match slot.lock() {
+        Ok(g) => g,
+        Err(p) => p.into_inner(),
+    };
+    if let Some(ref mut reranker) = *guard {
+        reranker.config.enabled = false;
+    }
+}
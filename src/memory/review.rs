@@ -0,0 +1,158 @@
+// Copyright 2026 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Spaced-repetition review of Learning and Insight memories (`memory review`).
+//!
+//! Each memory gets an SM-2 card: a review is due one day after creation, and
+//! every grade (0-5) moves the next review further out or, below 3, back to
+//! tomorrow. Cards are per project and live in `reviews.json` next to the
+//! database; memories never reviewed have no card until their first grade.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+
+use super::types::{Memory, MemoryState, MemoryType};
+
+/// Highest grade: perfect recall. Grades below `PASSING_GRADE` restart the card.
+pub const MAX_GRADE: u8 = 5;
+pub const PASSING_GRADE: u8 = 3;
+
+const INITIAL_EASE: f32 = 2.5;
+const MIN_EASE: f32 = 1.3;
+
+/// Memory types scheduled for review
+pub fn is_reviewable(memory_type: &MemoryType) -> bool {
+    matches!(memory_type, MemoryType::Learning | MemoryType::Insight)
+}
+
+/// SM-2 schedule of one memory
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReviewCard {
+    pub ease: f32,
+    pub interval_days: u32,
+    /// Passing grades in a row
+    pub repetitions: u32,
+    pub next_review_at: DateTime<Utc>,
+    #[serde(default)]
+    pub last_reviewed_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub last_grade: Option<u8>,
+}
+
+impl ReviewCard {
+    /// Card of a memory not reviewed yet: first due a day after creation
+    pub fn new(created_at: DateTime<Utc>) -> Self {
+        Self {
+            ease: INITIAL_EASE,
+            interval_days: 0,
+            repetitions: 0,
+            next_review_at: created_at + Duration::days(1),
+            last_reviewed_at: None,
+            last_grade: None,
+        }
+    }
+
+    /// Apply a 0-5 grade at `now` (SM-2): passing grades grow the interval
+    /// 1 → 6 → interval × ease days, failing ones reset it to one day.
+    pub fn grade(&mut self, grade: u8, now: DateTime<Utc>) {
+        let grade = grade.min(MAX_GRADE);
+        if grade < PASSING_GRADE {
+            self.repetitions = 0;
+            self.interval_days = 1;
+        } else {
+            self.repetitions += 1;
+            self.interval_days = match self.repetitions {
+                1 => 1,
+                2 => 6,
+                _ => ((self.interval_days.max(1) as f32) * self.ease).round() as u32,
+            };
+        }
+        let miss = (MAX_GRADE - grade) as f32;
+        self.ease = (self.ease + 0.1 - miss * (0.08 + miss * 0.02)).max(MIN_EASE);
+        self.next_review_at = now + Duration::days(self.interval_days as i64);
+        self.last_reviewed_at = Some(now);
+        self.last_grade = Some(grade);
+    }
+
+    pub fn is_due(&self, now: DateTime<Utc>) -> bool {
+        self.next_review_at <= now
+    }
+}
+
+/// Review cards of every project, persisted as JSON (`storage::get_reviews_path`).
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct ReviewBook {
+    /// project label → memory ID → card
+    pub projects: HashMap<String, HashMap<String, ReviewCard>>,
+}
+
+impl ReviewBook {
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let raw = std::fs::read_to_string(path)
+            .with_context(|| format!("Cannot read reviews '{}'", path.display()))?;
+        serde_json::from_str(&raw)
+            .with_context(|| format!("Reviews file '{}' is corrupt", path.display()))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        std::fs::write(path, serde_json::to_string_pretty(self)?)
+            .with_context(|| format!("Cannot write reviews '{}'", path.display()))
+    }
+
+    pub fn get(&self, project: &str, memory_id: &str) -> Option<&ReviewCard> {
+        self.projects.get(project)?.get(memory_id)
+    }
+
+    pub fn set(&mut self, project: &str, memory_id: &str, card: ReviewCard) {
+        self.projects
+            .entry(project.to_string())
+            .or_default()
+            .insert(memory_id.to_string(), card);
+    }
+}
+
+/// A memory due for review with its current card
+#[derive(Debug, Clone)]
+pub struct DueReview {
+    pub memory: Memory,
+    pub card: ReviewCard,
+}
+
+/// Reviewable working memories whose card is due at `now`, most overdue first.
+pub fn due_reviews(
+    memories: Vec<Memory>,
+    book: &ReviewBook,
+    project: &str,
+    now: DateTime<Utc>,
+) -> Vec<DueReview> {
+    let mut due: Vec<DueReview> = memories
+        .into_iter()
+        .filter(|m| is_reviewable(&m.memory_type) && m.metadata.state == MemoryState::Working)
+        .filter_map(|memory| {
+            let card = book
+                .get(project, &memory.id)
+                .cloned()
+                .unwrap_or_else(|| ReviewCard::new(memory.created_at));
+            card.is_due(now).then_some(DueReview { memory, card })
+        })
+        .collect();
+    due.sort_by_key(|d| d.card.next_review_at);
+    due
+}
@@ -0,0 +1,116 @@
+// Copyright 2026 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#[cfg(test)]
+mod tests {
+    use super::super::review::{due_reviews, ReviewBook, ReviewCard};
+    use super::super::types::{Memory, MemoryState, MemoryType};
+    use chrono::{Duration, Utc};
+
+    fn memory(memory_type: MemoryType, age_days: i64) -> Memory {
+        let mut m = Memory::new(memory_type, "title".to_string(), String::new(), None);
+        m.created_at = Utc::now() - Duration::days(age_days);
+        m
+    }
+
+    #[test]
+    fn test_passing_grades_grow_interval() {
+        let now = Utc::now();
+        let mut card = ReviewCard::new(now);
+        assert_eq!(card.next_review_at, now + Duration::days(1));
+
+        card.grade(5, now);
+        assert_eq!((card.repetitions, card.interval_days), (1, 1));
+        card.grade(5, now);
+        assert_eq!(card.interval_days, 6);
+        let ease = card.ease;
+        card.grade(4, now);
+        assert_eq!(card.interval_days, (6.0 * ease).round() as u32);
+        assert_eq!(
+            card.next_review_at,
+            now + Duration::days(card.interval_days as i64)
+        );
+        assert!(!card.is_due(now));
+    }
+
+    #[test]
+    fn test_failing_grade_resets_and_ease_has_floor() {
+        let now = Utc::now();
+        let mut card = ReviewCard::new(now);
+        card.grade(5, now);
+        card.grade(5, now);
+        card.grade(1, now);
+        assert_eq!((card.repetitions, card.interval_days), (0, 1));
+        assert_eq!(card.last_grade, Some(1));
+
+        for _ in 0..20 {
+            card.grade(0, now);
+        }
+        assert!((card.ease - 1.3).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_due_reviews_only_reviewable_working_memories() {
+        let now = Utc::now();
+        let old_learning = memory(MemoryType::Learning, 10);
+        let older_insight = memory(MemoryType::Insight, 20);
+        let fresh_learning = memory(MemoryType::Learning, 0);
+        let decision = memory(MemoryType::Decision, 10);
+        let mut archived = memory(MemoryType::Learning, 10);
+        archived.metadata.state = MemoryState::Archived;
+        let graded = memory(MemoryType::Learning, 30);
+
+        let mut book = ReviewBook::default();
+        let mut card = ReviewCard::new(graded.created_at);
+        card.grade(5, now);
+        book.set("proj", &graded.id, card);
+
+        let memories = vec![
+            old_learning.clone(),
+            older_insight.clone(),
+            fresh_learning,
+            decision,
+            archived,
+            graded.clone(),
+        ];
+        let due = due_reviews(memories.clone(), &book, "proj", now);
+        let ids: Vec<&str> = due.iter().map(|d| d.memory.id.as_str()).collect();
+        assert_eq!(
+            ids,
+            vec![older_insight.id.as_str(), old_learning.id.as_str()]
+        );
+
+        // Another project's cards do not apply
+        let due = due_reviews(memories, &book, "other", now);
+        assert_eq!(due.first().unwrap().memory.id, graded.id);
+    }
+
+    #[test]
+    fn test_review_book_round_trips() {
+        let path = std::env::temp_dir().join(format!(
+            "octobrain_reviews_test_{}.json",
+            uuid::Uuid::new_v4()
+        ));
+        let mut book = ReviewBook::load(&path).unwrap();
+        let mut card = ReviewCard::new(Utc::now());
+        card.grade(3, Utc::now());
+        book.set("proj", "id-1", card.clone());
+        book.save(&path).unwrap();
+
+        let loaded = ReviewBook::load(&path).unwrap();
+        assert_eq!(loaded.get("proj", "id-1"), Some(&card));
+        assert_eq!(loaded.get("proj", "id-2"), None);
+        std::fs::remove_file(&path).ok();
+    }
+}
@@ -0,0 +1,305 @@
+// Copyright 2026 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Result scoring shared by the non-LanceDB storage backends.
+//!
+//! Those engines only fetch candidates (nearest vectors, keyword matches);
+//! turning them into ranked `MemorySearchResult`s follows `MemoryStore` exactly
+//! so a query scores the same whichever backend holds the memories.
+
+use std::collections::{HashMap, HashSet};
+
+use super::store::{
+    generate_selection_reason, matches_json_filters, sort_search_results, MemoryStore, RRF_K,
+};
+use super::types::{
+    sort_by_relevance_desc, KeywordMatch, Memory, MemoryQuery, MemorySearchResult, SearchSignal,
+};
+use crate::config::HybridSearchConfig;
+
+/// Alphanumeric words of `text`. Safe to splice into FTS5 or tsquery syntax.
+pub(crate) fn keyword_terms(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|t| !t.is_empty())
+        .map(|t| t.to_lowercase())
+        .collect()
+}
+
+/// BM25 term-frequency saturation and length normalization (the usual
+/// Lucene / FTS5 defaults).
+const BM25_K1: f32 = 1.2;
+const BM25_B: f32 = 0.75;
+
+/// Words a memory is keyword-searchable by: title, content and tags, the
+/// columns `MemoryStore` indexes for full-text search.
+pub(crate) fn document_terms(memory: &Memory) -> Vec<String> {
+    keyword_terms(&format!(
+        "{} {} {}",
+        memory.title,
+        memory.content,
+        memory.metadata.tags.join(" ")
+    ))
+}
+
+/// Corpus statistics for BM25: how many documents contain each term and their
+/// total length. Kept up to date as documents are added and removed, so a query
+/// never has to rescan the corpus to weigh its terms.
+#[derive(Debug, Default)]
+pub(crate) struct KeywordIndex {
+    documents: usize,
+    total_terms: usize,
+    document_frequency: HashMap<String, usize>,
+}
+
+impl KeywordIndex {
+    /// Count a document made of `terms` (as from `document_terms`).
+    pub fn add(&mut self, terms: &[String]) {
+        self.documents += 1;
+        self.total_terms += terms.len();
+        for term in distinct(terms) {
+            *self.document_frequency.entry(term.clone()).or_default() += 1;
+        }
+    }
+
+    /// Forget a document previously passed to `add` with the same `terms`.
+    pub fn remove(&mut self, terms: &[String]) {
+        self.documents = self.documents.saturating_sub(1);
+        self.total_terms = self.total_terms.saturating_sub(terms.len());
+        for term in distinct(terms) {
+            if let Some(count) = self.document_frequency.get_mut(term) {
+                *count -= 1;
+                if *count == 0 {
+                    self.document_frequency.remove(term);
+                }
+            }
+        }
+    }
+
+    /// BM25 score of a document for `query_terms`; 0 when it shares none.
+    /// Rare terms weigh more than common ones, and repeats saturate.
+    pub fn score(&self, query_terms: &[String], document: &[String]) -> f32 {
+        if self.documents == 0 || document.is_empty() {
+            return 0.0;
+        }
+        let n = self.documents as f32;
+        let average_length = self.total_terms as f32 / n;
+        let length_norm = 1.0 - BM25_B + BM25_B * document.len() as f32 / average_length.max(1.0);
+        distinct(query_terms)
+            .into_iter()
+            .map(|term| {
+                let tf = document.iter().filter(|t| *t == term).count() as f32;
+                if tf == 0.0 {
+                    return 0.0;
+                }
+                let df = self.document_frequency.get(term).copied().unwrap_or(0) as f32;
+                // Lucene's +1 keeps the weight of terms in most documents positive
+                let idf = ((n - df + 0.5) / (df + 0.5) + 1.0).ln();
+                idf * tf * (BM25_K1 + 1.0) / (tf + BM25_K1 * length_norm)
+            })
+            .sum()
+    }
+}
+
+fn distinct(terms: &[String]) -> HashSet<&String> {
+    terms.iter().collect()
+}
+
+/// Words of `query_text` that occur in `memory`, in query order, with counts.
+pub(crate) fn keyword_matches(query_text: &str, memory: &Memory) -> Vec<KeywordMatch> {
+    let document = document_terms(memory);
+    let mut seen = HashSet::new();
+    keyword_terms(query_text)
+        .into_iter()
+        .filter(|term| seen.insert(term.clone()))
+        .filter_map(|term| {
+            let count = document.iter().filter(|t| **t == term).count();
+            (count > 0).then_some(KeywordMatch { term, count })
+        })
+        .collect()
+}
+
+/// Signals of a vector score: similarity × current importance × trust, or
+/// current importance alone for filter-only rows (`similarity` = `None`).
+pub(crate) fn vector_signals(
+    similarity: Option<f32>,
+    memory: &Memory,
+    current_importance: f32,
+) -> Vec<SearchSignal> {
+    let importance = SearchSignal::Importance {
+        stored: memory.metadata.importance,
+        current: current_importance,
+        weight: None,
+    };
+    match similarity {
+        Some(similarity) => vec![
+            SearchSignal::Vector { similarity },
+            importance,
+            SearchSignal::Trust {
+                multiplier: memory.metadata.source.trust_multiplier(),
+            },
+        ],
+        None => vec![importance],
+    }
+}
+
+/// Signals of a hybrid score: weighted fusion, recency and importance, times
+/// trust. `recency` and `importance` are (score, weight) pairs.
+pub(crate) fn hybrid_signals(
+    memory: &Memory,
+    query_text: &str,
+    fusion: SearchSignal,
+    recency: (f32, f32),
+    importance: (f32, f32),
+) -> Vec<SearchSignal> {
+    vec![
+        fusion,
+        SearchSignal::Keyword {
+            matches: keyword_matches(query_text, memory),
+        },
+        SearchSignal::Recency {
+            score: recency.0,
+            weight: recency.1,
+        },
+        SearchSignal::Importance {
+            stored: memory.metadata.importance,
+            current: importance.0,
+            weight: Some(importance.1),
+        },
+        SearchSignal::Trust {
+            multiplier: memory.metadata.source.trust_multiplier(),
+        },
+    ]
+}
+
+/// Rank vector hits (memory, cosine distance) by similarity × current
+/// importance × trust, or filter-only rows (no distance) by current importance.
+pub(crate) fn score_vector_hits(
+    hits: Vec<(Memory, Option<f32>)>,
+    query: &MemoryQuery,
+    current_importance: impl Fn(&Memory) -> f32,
+    limit: usize,
+) -> Vec<MemorySearchResult> {
+    let min_relevance = query.min_relevance.unwrap_or(0.0);
+    let mut results = Vec::new();
+    for (memory, distance) in hits {
+        if !matches_json_filters(&memory, query) {
+            continue;
+        }
+        let relevance_score = match distance {
+            Some(distance) => {
+                (1.0 - distance)
+                    * current_importance(&memory)
+                    * memory.metadata.source.trust_multiplier()
+            }
+            None => current_importance(&memory),
+        };
+        if relevance_score >= min_relevance {
+            let signals = if query.explain {
+                vector_signals(
+                    distance.map(|d| 1.0 - d),
+                    &memory,
+                    current_importance(&memory),
+                )
+            } else {
+                Vec::new()
+            };
+            results.push(MemorySearchResult {
+                memory,
+                relevance_score,
+                selection_reason: generate_selection_reason(query, relevance_score),
+                signals,
+            });
+        }
+    }
+    sort_search_results(&mut results, query, &current_importance);
+    results.truncate(limit);
+    results
+}
+
+/// Fuse the vector and keyword rankings (memory IDs, best first) with RRF,
+/// normalized by its 2/k maximum, and blend with recency and importance under
+/// the `[search.hybrid]` weights. `candidates` holds every ranked memory once.
+pub(crate) fn score_hybrid(
+    candidates: Vec<Memory>,
+    vector_ranking: &[String],
+    keyword_ranking: &[String],
+    query: &MemoryQuery,
+    hybrid: &HybridSearchConfig,
+    current_importance: impl Fn(&Memory) -> f32,
+    limit: usize,
+) -> Vec<MemorySearchResult> {
+    let mut rrf: HashMap<&str, f32> = HashMap::new();
+    for ranking in [vector_ranking, keyword_ranking] {
+        for (rank, id) in ranking.iter().enumerate() {
+            *rrf.entry(id.as_str()).or_default() += 1.0 / (RRF_K + rank as f32);
+        }
+    }
+
+    let max_rrf_score = 2.0 / RRF_K;
+    let min_relevance = query.min_relevance.unwrap_or(0.0);
+    let mut results = Vec::new();
+    for memory in candidates {
+        if !matches_json_filters(&memory, query) {
+            continue;
+        }
+        let rrf_score =
+            (rrf.get(memory.id.as_str()).copied().unwrap_or(0.0) / max_rrf_score).min(1.0);
+        let recency_score =
+            MemoryStore::calculate_recency_score(&memory, hybrid.recency_decay_days);
+        let importance_score = current_importance(&memory);
+        let final_score = (hybrid.default_vector_weight * rrf_score
+            + hybrid.default_recency_weight * recency_score
+            + hybrid.default_importance_weight * importance_score)
+            * memory.metadata.source.trust_multiplier();
+
+        if final_score >= min_relevance {
+            let selection_reason = format!(
+                "Hybrid: rrf={:.2}, recency={:.2}, importance={:.2}, final={:.2}",
+                rrf_score, recency_score, importance_score, final_score
+            );
+            let signals = if query.explain {
+                let rank = |ranking: &[String]| {
+                    ranking
+                        .iter()
+                        .position(|id| *id == memory.id)
+                        .map(|r| r + 1)
+                };
+                hybrid_signals(
+                    &memory,
+                    query.query_text.as_deref().unwrap_or_default(),
+                    SearchSignal::Fusion {
+                        score: rrf_score,
+                        weight: hybrid.default_vector_weight,
+                        vector_rank: rank(vector_ranking),
+                        keyword_rank: rank(keyword_ranking),
+                    },
+                    (recency_score, hybrid.default_recency_weight),
+                    (importance_score, hybrid.default_importance_weight),
+                )
+            } else {
+                Vec::new()
+            };
+            results.push(MemorySearchResult {
+                memory,
+                relevance_score: final_score,
+                selection_reason,
+                signals,
+            });
+        }
+    }
+
+    sort_by_relevance_desc(&mut results);
+    results.truncate(limit);
+    results
+}
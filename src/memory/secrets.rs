@@ -0,0 +1,146 @@
+// Copyright 2026 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Secret detection for memorize input.
+//!
+//! Unlike redaction (opt-in masking), this is a guard: memorize refuses content
+//! that looks like a credential unless the caller explicitly allows it.
+
+use regex::Regex;
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+/// Known credential formats as (kind, pattern) pairs.
+const CREDENTIAL_PATTERNS: &[(&str, &str)] = &[
+    ("aws_access_key", r"\b(?:AKIA|ASIA)[0-9A-Z]{16}\b"),
+    (
+        "aws_secret_key",
+        r#"(?i)aws_secret_access_key\s*[:=]\s*["']?[A-Za-z0-9/+=]{40}"#,
+    ),
+    ("github_token", r"\bgh[pousr]_[A-Za-z0-9]{36,}\b"),
+    ("github_token", r"\bgithub_pat_[A-Za-z0-9_]{22,}\b"),
+    (
+        "private_key",
+        r"-----BEGIN (?:[A-Z0-9]+ )*PRIVATE KEY(?: BLOCK)?-----",
+    ),
+    ("slack_token", r"\bxox[abprs]-[A-Za-z0-9-]{10,}\b"),
+    ("stripe_key", r"\b[sr]k_live_[A-Za-z0-9]{16,}\b"),
+    ("google_api_key", r"\bAIza[0-9A-Za-z_-]{35}\b"),
+    ("api_key", r"\bsk-[A-Za-z0-9_-]{20,}\b"),
+];
+
+/// Candidate tokens for the entropy check: long runs of base64/url-safe characters.
+const TOKEN_PATTERN: &str = r"[A-Za-z0-9+/_-]{32,}={0,2}";
+
+/// Shannon entropy (bits per char) above which a candidate token is treated as a secret.
+/// Hex digests (max 4.0) and English identifiers stay well below this.
+const ENTROPY_THRESHOLD: f64 = 4.3;
+
+/// A suspected secret found in memorize input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SecretFinding {
+    /// Credential kind, e.g. `aws_access_key` or `high_entropy_string`
+    pub kind: &'static str,
+    /// Masked preview of the match (first 4 chars) for error messages
+    pub preview: String,
+}
+
+fn compiled_patterns() -> &'static [(&'static str, Regex)] {
+    static PATTERNS: OnceLock<Vec<(&'static str, Regex)>> = OnceLock::new();
+    PATTERNS.get_or_init(|| {
+        CREDENTIAL_PATTERNS
+            .iter()
+            .filter_map(|(kind, pattern)| Regex::new(pattern).ok().map(|re| (*kind, re)))
+            .collect()
+    })
+}
+
+fn token_regex() -> Option<&'static Regex> {
+    static TOKEN: OnceLock<Option<Regex>> = OnceLock::new();
+    TOKEN
+        .get_or_init(|| Regex::new(TOKEN_PATTERN).ok())
+        .as_ref()
+}
+
+/// Shannon entropy of `s` in bits per character.
+pub fn shannon_entropy(s: &str) -> f64 {
+    let len = s.chars().count();
+    if len == 0 {
+        return 0.0;
+    }
+    let mut counts: HashMap<char, usize> = HashMap::new();
+    for c in s.chars() {
+        *counts.entry(c).or_insert(0) += 1;
+    }
+    counts
+        .values()
+        .map(|&n| {
+            let p = n as f64 / len as f64;
+            -p * p.log2()
+        })
+        .sum()
+}
+
+fn preview(matched: &str) -> String {
+    let head: String = matched.chars().take(4).collect();
+    format!("{}…", head)
+}
+
+/// Scan `text` for known credential formats and high-entropy tokens.
+pub fn detect_secrets(text: &str) -> Vec<SecretFinding> {
+    let mut findings = Vec::new();
+    let mut covered: Vec<(usize, usize)> = Vec::new();
+
+    for (kind, regex) in compiled_patterns() {
+        for m in regex.find_iter(text) {
+            covered.push((m.start(), m.end()));
+            findings.push(SecretFinding {
+                kind,
+                preview: preview(m.as_str()),
+            });
+        }
+    }
+
+    if let Some(token_re) = token_regex() {
+        for m in token_re.find_iter(text) {
+            if covered
+                .iter()
+                .any(|(start, end)| m.start() < *end && *start < m.end())
+            {
+                continue;
+            }
+            let token = m.as_str();
+            // Require mixed letters and digits so long words/paths don't trip the check
+            let has_digit = token.chars().any(|c| c.is_ascii_digit());
+            let has_alpha = token.chars().any(|c| c.is_ascii_alphabetic());
+            if has_digit && has_alpha && shannon_entropy(token) >= ENTROPY_THRESHOLD {
+                findings.push(SecretFinding {
+                    kind: "high_entropy_string",
+                    preview: preview(token),
+                });
+            }
+        }
+    }
+
+    findings
+}
+
+/// One-line summary of findings for error messages, e.g. `aws_access_key (AKIA…)`.
+pub fn describe_findings(findings: &[SecretFinding]) -> String {
+    findings
+        .iter()
+        .map(|f| format!("{} ({})", f.kind, f.preview))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
@@ -0,0 +1,387 @@
+// Copyright 2026 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Import coding-agent session logs (Claude Code, Continue, Cursor) as memories.
+//!
+//! Sessions are normalized into a flat list of turns (user text, assistant text,
+//! tool results). Decisions and fixes are mined from the text; failed tool
+//! results become BugFix memories so the error and its context are searchable.
+
+use anyhow::{Context, Result};
+use serde_json::Value;
+use std::path::Path;
+
+use crate::memory::conversation_import::ImportedFact;
+use crate::memory::types::MemoryType;
+
+/// Tag added to every memory imported from an agent session.
+pub const SESSION_TAG: &str = "agent-session";
+
+const MIN_ITEM_CHARS: usize = 20;
+const MAX_ITEM_CHARS: usize = 600;
+
+const DECISION_MARKERS: &[&str] = &[
+    "we decided",
+    "i decided",
+    "let's go with",
+    "we'll go with",
+    "i'll go with",
+    "decided to",
+    "going with",
+    "we agreed",
+];
+
+const FIX_MARKERS: &[&str] = &[
+    "root cause",
+    "the issue was",
+    "the problem was",
+    "the bug was",
+    "fixed by",
+    "fixed the",
+    "this fixes",
+    "resolved by",
+    "the fix is",
+];
+
+/// Supported agent log formats.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SessionFormat {
+    /// Claude Code `~/.claude/projects/<project>/<session>.jsonl`
+    ClaudeCode,
+    /// Continue session JSON (`~/.continue/sessions/<id>.json`)
+    Continue,
+    /// Cursor chat exports: JSON/JSONL with `role`/`content` or `type`/`text` messages
+    Cursor,
+}
+
+impl SessionFormat {
+    pub fn parse(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "claude-code" | "claude_code" | "claudecode" => Ok(Self::ClaudeCode),
+            "continue" => Ok(Self::Continue),
+            "cursor" => Ok(Self::Cursor),
+            other => Err(anyhow::anyhow!(
+                "Unknown session format '{}'; expected claude-code, continue or cursor",
+                other
+            )),
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::ClaudeCode => "claude-code",
+            Self::Continue => "continue",
+            Self::Cursor => "cursor",
+        }
+    }
+}
+
+/// One normalized turn of an agent session.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SessionTurn {
+    User(String),
+    Assistant(String),
+    ToolResult {
+        tool: String,
+        output: String,
+        is_error: bool,
+    },
+}
+
+/// A parsed agent session.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AgentSession {
+    pub id: String,
+    /// Repository / workspace directory the session ran in, when recorded
+    pub repo: Option<String>,
+    pub turns: Vec<SessionTurn>,
+}
+
+impl AgentSession {
+    /// Short repo name (last path component) for tagging.
+    pub fn repo_name(&self) -> Option<String> {
+        let repo = self.repo.as_deref()?;
+        Path::new(repo)
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+    }
+}
+
+/// Text of a message `content` that is either a string or a list of typed blocks.
+fn content_text(content: &Value) -> String {
+    match content {
+        Value::String(s) => s.clone(),
+        Value::Array(blocks) => blocks
+            .iter()
+            .filter_map(|b| match b {
+                Value::String(s) => Some(s.as_str()),
+                _ if b.get("type").and_then(|t| t.as_str()) == Some("text") => {
+                    b.get("text").and_then(|t| t.as_str())
+                }
+                _ => None,
+            })
+            .collect::<Vec<_>>()
+            .join("\n"),
+        _ => String::new(),
+    }
+}
+
+fn str_field(value: &Value, key: &str) -> Option<String> {
+    value.get(key).and_then(|v| v.as_str()).map(str::to_string)
+}
+
+/// Parse one Claude Code JSONL transcript. Tool names are resolved from the
+/// preceding `tool_use` blocks by id.
+pub fn parse_claude_code(jsonl: &str, fallback_id: &str) -> AgentSession {
+    let mut session = AgentSession {
+        id: fallback_id.to_string(),
+        repo: None,
+        turns: Vec::new(),
+    };
+    let mut tool_names: std::collections::HashMap<String, String> = Default::default();
+
+    for line in jsonl.lines().filter(|l| !l.trim().is_empty()) {
+        let Ok(entry) = serde_json::from_str::<Value>(line) else {
+            continue;
+        };
+        if let Some(id) = str_field(&entry, "sessionId") {
+            session.id = id;
+        }
+        if session.repo.is_none() {
+            session.repo = str_field(&entry, "cwd");
+        }
+        let Some(message) = entry.get("message") else {
+            continue;
+        };
+        let role = str_field(message, "role").or_else(|| str_field(&entry, "type"));
+        let content = message.get("content").cloned().unwrap_or(Value::Null);
+
+        if let Value::Array(blocks) = &content {
+            for block in blocks {
+                match block.get("type").and_then(|t| t.as_str()) {
+                    Some("tool_use") => {
+                        if let (Some(id), Some(name)) =
+                            (str_field(block, "id"), str_field(block, "name"))
+                        {
+                            tool_names.insert(id, name);
+                        }
+                    }
+                    Some("tool_result") => {
+                        let tool = str_field(block, "tool_use_id")
+                            .and_then(|id| tool_names.get(&id).cloned())
+                            .unwrap_or_else(|| "tool".to_string());
+                        session.turns.push(SessionTurn::ToolResult {
+                            tool,
+                            output: content_text(block.get("content").unwrap_or(&Value::Null)),
+                            is_error: block
+                                .get("is_error")
+                                .and_then(|v| v.as_bool())
+                                .unwrap_or(false),
+                        });
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        let text = content_text(&content);
+        if text.trim().is_empty() {
+            continue;
+        }
+        match role.as_deref() {
+            Some("user") => session.turns.push(SessionTurn::User(text)),
+            Some("assistant") => session.turns.push(SessionTurn::Assistant(text)),
+            _ => {}
+        }
+    }
+    session
+}
+
+/// Parse a Continue session JSON file.
+pub fn parse_continue(json: &str, fallback_id: &str) -> Result<AgentSession> {
+    let root: Value = serde_json::from_str(json).context("Continue session is not valid JSON")?;
+    let turns = root
+        .get("history")
+        .and_then(|h| h.as_array())
+        .map(|items| {
+            items
+                .iter()
+                .filter_map(|item| {
+                    let message = item.get("message").unwrap_or(item);
+                    let text = content_text(message.get("content")?);
+                    if text.trim().is_empty() {
+                        return None;
+                    }
+                    match message.get("role")?.as_str()? {
+                        "user" => Some(SessionTurn::User(text)),
+                        "assistant" => Some(SessionTurn::Assistant(text)),
+                        "tool" => Some(SessionTurn::ToolResult {
+                            tool: str_field(message, "name").unwrap_or_else(|| "tool".into()),
+                            output: text,
+                            is_error: false,
+                        }),
+                        _ => None,
+                    }
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    Ok(AgentSession {
+        id: str_field(&root, "sessionId").unwrap_or_else(|| fallback_id.to_string()),
+        repo: str_field(&root, "workspaceDirectory"),
+        turns,
+    })
+}
+
+/// Parse a Cursor chat export: a JSON array (or JSONL) of messages using either
+/// `role`/`content` or `type` (`user`/`ai`) / `text` fields.
+pub fn parse_cursor(content: &str, fallback_id: &str) -> AgentSession {
+    let messages: Vec<Value> = match serde_json::from_str::<Value>(content) {
+        Ok(Value::Array(items)) => items,
+        Ok(Value::Object(obj)) => obj
+            .get("messages")
+            .or_else(|| obj.get("bubbles"))
+            .and_then(|m| m.as_array())
+            .cloned()
+            .unwrap_or_default(),
+        _ => content
+            .lines()
+            .filter_map(|l| serde_json::from_str::<Value>(l).ok())
+            .collect(),
+    };
+
+    let turns = messages
+        .iter()
+        .filter_map(|m| {
+            let role = str_field(m, "role").or_else(|| str_field(m, "type"))?;
+            let text = m
+                .get("content")
+                .map(content_text)
+                .filter(|t| !t.trim().is_empty())
+                .or_else(|| str_field(m, "text"))?;
+            match role.as_str() {
+                "user" | "human" => Some(SessionTurn::User(text)),
+                "assistant" | "ai" | "bot" => Some(SessionTurn::Assistant(text)),
+                _ => None,
+            }
+        })
+        .collect();
+
+    AgentSession {
+        id: fallback_id.to_string(),
+        repo: None,
+        turns,
+    }
+}
+
+/// Parse `path` (a session file, or a directory of them) in `format`.
+pub fn load_sessions(path: &Path, format: SessionFormat) -> Result<Vec<AgentSession>> {
+    let files: Vec<std::path::PathBuf> = if path.is_dir() {
+        let mut files: Vec<_> = std::fs::read_dir(path)
+            .with_context(|| format!("Cannot read directory '{}'", path.display()))?
+            .filter_map(|e| e.ok().map(|e| e.path()))
+            .filter(|p| {
+                matches!(
+                    p.extension().and_then(|e| e.to_str()),
+                    Some("jsonl") | Some("json")
+                )
+            })
+            .collect();
+        files.sort();
+        files
+    } else {
+        vec![path.to_path_buf()]
+    };
+
+    let mut sessions = Vec::new();
+    for file in files {
+        let raw = std::fs::read_to_string(&file)
+            .with_context(|| format!("Cannot read session log '{}'", file.display()))?;
+        let fallback_id = file
+            .file_stem()
+            .map(|s| s.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let session = match format {
+            SessionFormat::ClaudeCode => parse_claude_code(&raw, &fallback_id),
+            SessionFormat::Continue => parse_continue(&raw, &fallback_id)?,
+            SessionFormat::Cursor => parse_cursor(&raw, &fallback_id),
+        };
+        if !session.turns.is_empty() {
+            sessions.push(session);
+        }
+    }
+    Ok(sessions)
+}
+
+fn truncate(text: &str) -> String {
+    let trimmed = text.trim();
+    if trimmed.chars().count() <= MAX_ITEM_CHARS {
+        trimmed.to_string()
+    } else {
+        let head: String = trimmed.chars().take(MAX_ITEM_CHARS).collect();
+        format!("{}…", head)
+    }
+}
+
+/// Paragraph-level candidates: decisions and fixes stated in text turns.
+fn text_items(text: &str, allow_fixes: bool, items: &mut Vec<ImportedFact>) {
+    for paragraph in text.split("\n\n") {
+        let paragraph = paragraph.trim();
+        if paragraph.chars().count() < MIN_ITEM_CHARS || paragraph.starts_with("```") {
+            continue;
+        }
+        let lower = paragraph.to_lowercase();
+        let memory_type = if DECISION_MARKERS.iter().any(|m| lower.contains(m)) {
+            MemoryType::Decision
+        } else if allow_fixes && FIX_MARKERS.iter().any(|m| lower.contains(m)) {
+            MemoryType::BugFix
+        } else {
+            continue;
+        };
+        items.push(ImportedFact {
+            memory_type,
+            text: truncate(paragraph),
+        });
+    }
+}
+
+/// Extract decisions, fixes and failed tool results from a session.
+pub fn extract_session_items(session: &AgentSession) -> Vec<ImportedFact> {
+    let mut items: Vec<ImportedFact> = Vec::new();
+    for turn in &session.turns {
+        match turn {
+            SessionTurn::User(text) => text_items(text, false, &mut items),
+            SessionTurn::Assistant(text) => text_items(text, true, &mut items),
+            SessionTurn::ToolResult {
+                tool,
+                output,
+                is_error: true,
+            } if output.trim().chars().count() >= MIN_ITEM_CHARS => {
+                items.push(ImportedFact {
+                    memory_type: MemoryType::BugFix,
+                    text: format!("Tool `{}` failed:\n{}", tool, truncate(output)),
+                });
+            }
+            _ => {}
+        }
+    }
+    let mut unique: Vec<ImportedFact> = Vec::with_capacity(items.len());
+    for item in items {
+        if !unique.iter().any(|u| u.text == item.text) {
+            unique.push(item);
+        }
+    }
+    unique
+}
@@ -0,0 +1,77 @@
+// Copyright 2026 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#[cfg(test)]
+mod tests {
+    use super::super::session_import::{
+        extract_session_items, parse_claude_code, parse_continue, parse_cursor, SessionFormat,
+        SessionTurn,
+    };
+    use super::super::types::MemoryType;
+
+    const CLAUDE_CODE: &str = r#"{"type":"user","sessionId":"s-42","cwd":"/home/dev/octobrain","message":{"role":"user","content":"We decided to keep LanceDB as the only store for now."}}
+{"type":"assistant","sessionId":"s-42","message":{"role":"assistant","content":[{"type":"text","text":"Running the tests."},{"type":"tool_use","id":"tu1","name":"Bash","input":{"command":"cargo test"}}]}}
+{"type":"user","sessionId":"s-42","message":{"role":"user","content":[{"type":"tool_result","tool_use_id":"tu1","is_error":true,"content":"error[E0308]: mismatched types in store.rs"}]}}
+not json
+{"type":"assistant","sessionId":"s-42","message":{"role":"assistant","content":[{"type":"text","text":"The issue was an i32/u32 mismatch in the decay column writer."}]}}"#;
+
+    #[test]
+    fn test_parse_claude_code_session() {
+        let session = parse_claude_code(CLAUDE_CODE, "fallback");
+        assert_eq!(session.id, "s-42");
+        assert_eq!(session.repo_name().as_deref(), Some("octobrain"));
+        assert!(session.turns.contains(&SessionTurn::ToolResult {
+            tool: "Bash".to_string(),
+            output: "error[E0308]: mismatched types in store.rs".to_string(),
+            is_error: true,
+        }));
+    }
+
+    #[test]
+    fn test_extract_decisions_fixes_and_tool_errors() {
+        let items = extract_session_items(&parse_claude_code(CLAUDE_CODE, "x"));
+        let types: Vec<MemoryType> = items.iter().map(|i| i.memory_type.clone()).collect();
+        assert_eq!(
+            types,
+            vec![MemoryType::Decision, MemoryType::BugFix, MemoryType::BugFix]
+        );
+        assert!(items[1].text.starts_with("Tool `Bash` failed:"));
+    }
+
+    #[test]
+    fn test_parse_continue_and_cursor() {
+        let cont = r#"{"sessionId":"c-1","workspaceDirectory":"/src/app","history":[
+            {"message":{"role":"user","content":"Let's go with axum for the HTTP layer."}},
+            {"message":{"role":"assistant","content":[{"type":"text","text":"Sounds good."}]}}]}"#;
+        let session = parse_continue(cont, "f").unwrap();
+        assert_eq!(session.id, "c-1");
+        assert_eq!(session.turns.len(), 2);
+
+        let cursor = r#"[{"type":"user","text":"Why does it hang?"},{"type":"ai","text":"Root cause: the lock is held across an await."}]"#;
+        let session = parse_cursor(cursor, "chat-1");
+        assert_eq!(session.id, "chat-1");
+        let items = extract_session_items(&session);
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].memory_type, MemoryType::BugFix);
+    }
+
+    #[test]
+    fn test_session_format_parse() {
+        assert_eq!(
+            SessionFormat::parse("claude-code").unwrap(),
+            SessionFormat::ClaudeCode
+        );
+        assert!(SessionFormat::parse("chatgpt").is_err());
+    }
+}
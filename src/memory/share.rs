@@ -0,0 +1,211 @@
+// Copyright 2026 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Signed memory bundles for sharing curated knowledge between teammates.
+//!
+//! A bundle is JSON holding the payload (sender + memories) as a string, the
+//! sender's Ed25519 public key and a signature over the payload bytes. Signing
+//! the exact string, rather than re-serialized structs, keeps verification
+//! independent of map ordering.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use ring::rand::SystemRandom;
+use ring::signature::{Ed25519KeyPair, KeyPair, UnparsedPublicKey, ED25519};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::path::Path;
+
+use crate::memory::types::{Memory, MemoryType};
+
+/// Tag added to every memory imported from a bundle.
+pub const SHARE_TAG: &str = "shared";
+
+/// Current bundle payload version.
+pub const BUNDLE_VERSION: u32 = 1;
+
+/// The portable part of a memory carried in a bundle.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SharedMemory {
+    pub id: String,
+    pub memory_type: MemoryType,
+    pub title: String,
+    pub content: String,
+    pub importance: f32,
+    pub tags: Vec<String>,
+    pub related_files: Vec<String>,
+    pub created_at: DateTime<Utc>,
+    /// Absent in bundles written before conflict-aware re-imports
+    #[serde(default)]
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+impl From<&Memory> for SharedMemory {
+    fn from(memory: &Memory) -> Self {
+        Self {
+            id: memory.id.clone(),
+            memory_type: memory.memory_type.clone(),
+            title: memory.title.clone(),
+            content: memory.content.clone(),
+            importance: memory.metadata.importance,
+            tags: memory.metadata.tags.clone(),
+            related_files: memory.metadata.related_files.clone(),
+            created_at: memory.created_at,
+            updated_at: Some(memory.updated_at),
+        }
+    }
+}
+
+/// Signed content of a bundle.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SharePayload {
+    pub version: u32,
+    pub sender: String,
+    pub created_at: DateTime<Utc>,
+    pub memories: Vec<SharedMemory>,
+}
+
+/// On-disk bundle format.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ShareBundle {
+    /// `SharePayload` serialized as JSON; the signed bytes
+    pub payload: String,
+    /// Hex-encoded Ed25519 public key of the sender
+    pub public_key: String,
+    /// Hex-encoded Ed25519 signature over `payload`
+    pub signature: String,
+}
+
+/// Outcome of importing a bundle.
+#[derive(Debug, Default)]
+pub struct ShareImportReport {
+    pub sender: String,
+    pub fingerprint: String,
+    /// Whether the signer was checked against trusted keys; false when none are configured
+    pub trusted: bool,
+    pub stored: usize,
+    /// Memories already imported unchanged from an earlier bundle
+    pub duplicates: usize,
+    /// Earlier imports replaced by a newer version from the sender
+    pub updated: usize,
+    /// Earlier imports edited locally and changed by the sender, settled by the
+    /// conflict strategy
+    pub conflicts: usize,
+    /// Memories that failed to store, with the reason
+    pub skipped: Vec<String>,
+}
+
+/// Load the local signing key from `path`, generating it on first use.
+pub fn load_or_create_key(path: &Path) -> Result<Ed25519KeyPair> {
+    if path.exists() {
+        let bytes = std::fs::read(path)
+            .with_context(|| format!("Cannot read share key '{}'", path.display()))?;
+        return Ed25519KeyPair::from_pkcs8(&bytes)
+            .map_err(|e| anyhow::anyhow!("Invalid share key '{}': {}", path.display(), e));
+    }
+
+    let document = Ed25519KeyPair::generate_pkcs8(&SystemRandom::new())
+        .map_err(|_| anyhow::anyhow!("Failed to generate share key"))?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, document.as_ref())
+        .with_context(|| format!("Cannot write share key '{}'", path.display()))?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o600))?;
+    }
+    Ed25519KeyPair::from_pkcs8(document.as_ref())
+        .map_err(|e| anyhow::anyhow!("Generated share key is invalid: {}", e))
+}
+
+/// Length of the truncated fingerprints recorded on memories imported by older
+/// octobrain versions.
+const LEGACY_FINGERPRINT_LEN: usize = 16;
+
+/// Fingerprint of a public key: its full SHA-256, hex-encoded.
+pub fn key_fingerprint(public_key: &[u8]) -> String {
+    hex::encode(Sha256::digest(public_key))
+}
+
+/// Whether a `shared_key` recorded on an imported memory names the key with
+/// `fingerprint`; older imports recorded only its first 16 hex chars.
+pub fn same_key(recorded: &str, fingerprint: &str) -> bool {
+    recorded == fingerprint
+        || (recorded.len() == LEGACY_FINGERPRINT_LEN && fingerprint.starts_with(recorded))
+}
+
+/// Fingerprint of the local share key at `path`, generating the key if needed.
+pub fn local_key_fingerprint(path: &Path) -> Result<String> {
+    let key = load_or_create_key(path)?;
+    Ok(key_fingerprint(key.public_key().as_ref()))
+}
+
+/// Sender name used when none is given: `$USER` / `$USERNAME`, else "unknown".
+pub fn default_sender() -> String {
+    std::env::var("USER")
+        .or_else(|_| std::env::var("USERNAME"))
+        .ok()
+        .filter(|s| !s.trim().is_empty())
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+/// Serialize and sign `payload` with `key`.
+pub fn sign_bundle(key: &Ed25519KeyPair, payload: &SharePayload) -> Result<ShareBundle> {
+    let payload = serde_json::to_string(payload)?;
+    let signature = key.sign(payload.as_bytes());
+    Ok(ShareBundle {
+        public_key: hex::encode(key.public_key().as_ref()),
+        signature: hex::encode(signature.as_ref()),
+        payload,
+    })
+}
+
+/// Verify the bundle signature against its embedded public key and return the
+/// payload. When `trusted` lists fingerprints, the key must be one of them;
+/// an empty list accepts any signer.
+pub fn verify_bundle(bundle: &ShareBundle, trusted: &[String]) -> Result<SharePayload> {
+    let public_key = hex::decode(&bundle.public_key).context("Bundle public key is not hex")?;
+    let signature = hex::decode(&bundle.signature).context("Bundle signature is not hex")?;
+
+    if !trusted.is_empty() {
+        let actual = key_fingerprint(&public_key);
+        if !trusted
+            .iter()
+            .any(|expected| actual.eq_ignore_ascii_case(expected.trim()))
+        {
+            return Err(anyhow::anyhow!(
+                "Bundle was signed by key {}, which is not a trusted key. Pass --trust or add \
+                 it to [memory].trusted_share_keys after confirming it with the sender",
+                actual
+            ));
+        }
+    }
+
+    UnparsedPublicKey::new(&ED25519, &public_key)
+        .verify(bundle.payload.as_bytes(), &signature)
+        .map_err(|_| anyhow::anyhow!("Bundle signature is invalid; it was modified or forged"))?;
+
+    let payload: SharePayload =
+        serde_json::from_str(&bundle.payload).context("Bundle payload is not valid JSON")?;
+    if payload.version > BUNDLE_VERSION {
+        return Err(anyhow::anyhow!(
+            "Bundle version {} is newer than supported version {}",
+            payload.version,
+            BUNDLE_VERSION
+        ));
+    }
+    Ok(payload)
+}
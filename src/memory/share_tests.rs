@@ -0,0 +1,102 @@
+// Copyright 2026 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#[cfg(test)]
+mod tests {
+    use super::super::share::{
+        key_fingerprint, load_or_create_key, local_key_fingerprint, same_key, sign_bundle,
+        verify_bundle, SharePayload, SharedMemory, BUNDLE_VERSION,
+    };
+    use super::super::types::{Memory, MemoryType};
+    use chrono::Utc;
+
+    fn key_path() -> std::path::PathBuf {
+        std::env::temp_dir()
+            .join(format!("octobrain_share_test_{}", uuid::Uuid::new_v4()))
+            .join("key.pk8")
+    }
+
+    fn payload() -> SharePayload {
+        let memory = Memory::new(
+            MemoryType::Decision,
+            "Use LanceDB".to_string(),
+            "We store vectors in LanceDB for local-first search.".to_string(),
+            None,
+        );
+        SharePayload {
+            version: BUNDLE_VERSION,
+            sender: "alice".to_string(),
+            created_at: Utc::now(),
+            memories: vec![SharedMemory::from(&memory)],
+        }
+    }
+
+    #[test]
+    fn test_sign_and_verify_round_trip() {
+        let key = load_or_create_key(&key_path()).unwrap();
+        let payload = payload();
+
+        let bundle = sign_bundle(&key, &payload).unwrap();
+        let verified = verify_bundle(&bundle, &[]).unwrap();
+        assert_eq!(verified, payload);
+    }
+
+    #[test]
+    fn test_tampered_payload_is_rejected() {
+        let key = load_or_create_key(&key_path()).unwrap();
+        let mut bundle = sign_bundle(&key, &payload()).unwrap();
+
+        bundle.payload = bundle.payload.replace("LanceDB", "Postgres");
+        let err = verify_bundle(&bundle, &[]).unwrap_err();
+        assert!(err.to_string().contains("signature is invalid"));
+    }
+
+    #[test]
+    fn test_trusted_fingerprint_must_match() {
+        let path = key_path();
+        let key = load_or_create_key(&path).unwrap();
+        let bundle = sign_bundle(&key, &payload()).unwrap();
+
+        let fingerprint = local_key_fingerprint(&path).unwrap();
+        assert_eq!(
+            fingerprint,
+            key_fingerprint(&hex::decode(&bundle.public_key).unwrap())
+        );
+        let other = "0".repeat(64);
+        assert!(verify_bundle(&bundle, &[fingerprint.clone()]).is_ok());
+        assert!(verify_bundle(&bundle, &[other.clone(), fingerprint.to_uppercase()]).is_ok());
+        let err = verify_bundle(&bundle, &[other]).unwrap_err();
+        assert!(err.to_string().contains("not a trusted key"));
+        // A truncated fingerprint no longer pins the key
+        assert!(verify_bundle(&bundle, &[fingerprint[..16].to_string()]).is_err());
+    }
+
+    #[test]
+    fn test_legacy_short_fingerprints_name_the_same_key() {
+        let fingerprint = local_key_fingerprint(&key_path()).unwrap();
+        assert!(same_key(&fingerprint, &fingerprint));
+        assert!(same_key(&fingerprint[..16], &fingerprint));
+        assert!(!same_key(&fingerprint[..8], &fingerprint));
+        assert!(!same_key(&"0".repeat(16), &fingerprint));
+    }
+
+    #[test]
+    fn test_key_is_reused_across_loads() {
+        let path = key_path();
+        let first = local_key_fingerprint(&path).unwrap();
+        let second = local_key_fingerprint(&path).unwrap();
+        assert_eq!(first, second);
+        assert_eq!(first.len(), 64);
+    }
+}
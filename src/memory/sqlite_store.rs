@@ -0,0 +1,1446 @@
+// Copyright 2026 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Single-file SQLite storage backend (`[memory].backend = "sqlite"`).
+//!
+//! Memories, relationships, the recovery buffer and the access log live in one
+//! `memories.sqlite3` file. Embeddings are little-endian f32 blobs ranked with
+//! sqlite-vec's exact `vec_distance_cosine`, and keyword search uses an FTS5
+//! index kept in sync by triggers. Scoring mirrors `MemoryStore` so results
+//! are comparable across backends; HyDE query expansion is LanceDB-only.
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use rusqlite::types::Value;
+use rusqlite::{params_from_iter, Connection, Row};
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::{Arc, Mutex, Once};
+
+use super::backend::{BackendOptions, StorageBackend};
+use super::reranker_integration::RerankerIntegration;
+use super::search_scoring::{keyword_terms, score_hybrid, score_vector_hits};
+use super::types::{
+    AccessLogEntry, Memory, MemoryConfig, MemoryDecay, MemoryMetadata, MemoryQuery,
+    MemoryRelationship, MemoryRevision, MemorySearchResult, MemorySource, MemoryState, MemoryType,
+    RelationshipType,
+};
+use crate::embedding::{EmbeddingProvider, InputType};
+
+/// Database file inside the memory database directory.
+pub const SQLITE_FILE: &str = "memories.sqlite3";
+
+/// Memory columns in the order `memory_from_row` reads them.
+const MEMORY_COLUMNS: &str = "id, project_key, role, memory_type, title, content, created_at, \
+     updated_at, importance, confidence, tags, related_files, git_commit, source, access_count, \
+     last_accessed, state, attachments, custom_fields, created_by, decay_rate";
+
+/// Number of entries in `MEMORY_COLUMNS`; extra selected columns follow them.
+const MEMORY_COLUMN_COUNT: usize = 21;
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS memories (
+    seq INTEGER PRIMARY KEY,
+    id TEXT NOT NULL UNIQUE,
+    project_key TEXT NOT NULL,
+    role TEXT NOT NULL DEFAULT '',
+    memory_type TEXT NOT NULL,
+    title TEXT NOT NULL,
+    content TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    importance REAL NOT NULL,
+    confidence REAL NOT NULL,
+    tags TEXT NOT NULL,
+    related_files TEXT NOT NULL,
+    git_commit TEXT,
+    source TEXT NOT NULL,
+    access_count INTEGER NOT NULL DEFAULT 0,
+    last_accessed TEXT NOT NULL,
+    state TEXT NOT NULL,
+    attachments TEXT NOT NULL,
+    custom_fields TEXT NOT NULL,
+    created_by TEXT NOT NULL DEFAULT '',
+    decay_rate REAL NOT NULL DEFAULT 1.0,
+    embedding BLOB NOT NULL
+);
+CREATE INDEX IF NOT EXISTS memories_scope ON memories (project_key, role);
+
+CREATE VIRTUAL TABLE IF NOT EXISTS memories_fts USING fts5(
+    title, content, content='memories', content_rowid='seq'
+);
+CREATE TRIGGER IF NOT EXISTS memories_fts_insert AFTER INSERT ON memories BEGIN
+    INSERT INTO memories_fts (rowid, title, content) VALUES (new.seq, new.title, new.content);
+END;
+CREATE TRIGGER IF NOT EXISTS memories_fts_delete AFTER DELETE ON memories BEGIN
+    INSERT INTO memories_fts (memories_fts, rowid, title, content)
+    VALUES ('delete', old.seq, old.title, old.content);
+END;
+CREATE TRIGGER IF NOT EXISTS memories_fts_update AFTER UPDATE OF title, content ON memories BEGIN
+    INSERT INTO memories_fts (memories_fts, rowid, title, content)
+    VALUES ('delete', old.seq, old.title, old.content);
+    INSERT INTO memories_fts (rowid, title, content) VALUES (new.seq, new.title, new.content);
+END;
+
+CREATE TABLE IF NOT EXISTS memory_relationships (
+    id TEXT PRIMARY KEY,
+    source_id TEXT NOT NULL,
+    target_id TEXT NOT NULL,
+    project_key TEXT NOT NULL,
+    relationship_type TEXT NOT NULL,
+    strength REAL NOT NULL,
+    description TEXT NOT NULL,
+    created_at TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS memory_relationships_source ON memory_relationships (source_id);
+CREATE INDEX IF NOT EXISTS memory_relationships_target ON memory_relationships (target_id);
+
+CREATE TABLE IF NOT EXISTS memory_recovery (
+    id TEXT NOT NULL,
+    project_key TEXT NOT NULL,
+    role TEXT NOT NULL,
+    memory_type TEXT NOT NULL,
+    title TEXT NOT NULL,
+    content TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    importance REAL NOT NULL,
+    confidence REAL NOT NULL,
+    tags TEXT NOT NULL,
+    related_files TEXT NOT NULL,
+    git_commit TEXT,
+    source TEXT NOT NULL,
+    access_count INTEGER NOT NULL,
+    last_accessed TEXT NOT NULL,
+    state TEXT NOT NULL,
+    attachments TEXT NOT NULL,
+    custom_fields TEXT NOT NULL,
+    created_by TEXT NOT NULL,
+    decay_rate REAL NOT NULL DEFAULT 1.0,
+    embedding BLOB NOT NULL,
+    deleted_at TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS memory_access_log (
+    id TEXT PRIMARY KEY,
+    project_key TEXT NOT NULL,
+    role TEXT,
+    memory_id TEXT NOT NULL,
+    query TEXT NOT NULL,
+    accessed_at TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS memory_revisions (
+    id TEXT PRIMARY KEY,
+    project_key TEXT NOT NULL,
+    memory_id TEXT NOT NULL,
+    revision INTEGER NOT NULL,
+    replaced_at TEXT NOT NULL,
+    memory TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS memory_revisions_memory ON memory_revisions (memory_id);
+";
+
+/// Columns added after the first release, as `(table, column, definition)`.
+/// `CREATE TABLE IF NOT EXISTS` leaves older databases without them.
+const ADDED_COLUMNS: [(&str, &str, &str); 2] = [
+    ("memories", "decay_rate", "REAL NOT NULL DEFAULT 1.0"),
+    ("memory_recovery", "decay_rate", "REAL NOT NULL DEFAULT 1.0"),
+];
+
+/// Add the `ADDED_COLUMNS` missing from a database created by an older octobrain.
+fn add_missing_columns(conn: &Connection) -> Result<()> {
+    for (table, column, definition) in ADDED_COLUMNS {
+        let existing = query_rows(
+            conn,
+            &format!("SELECT name FROM pragma_table_info('{}')", table),
+            &[],
+            |row| Ok(row.get::<_, String>(0)?),
+        )?;
+        if !existing.iter().any(|name| name == column) {
+            conn.execute(
+                &format!("ALTER TABLE {} ADD COLUMN {} {}", table, column, definition),
+                [],
+            )?;
+        }
+    }
+    Ok(())
+}
+
+static REGISTER_SQLITE_VEC: Once = Once::new();
+
+/// Load sqlite-vec into every connection opened after this call.
+fn register_sqlite_vec() {
+    REGISTER_SQLITE_VEC.call_once(|| {
+        // SAFETY: sqlite3_vec_init has exactly the signature SQLite expects of an
+        // extension entry point; sqlite-vec only declares it without arguments.
+        unsafe {
+            rusqlite::ffi::sqlite3_auto_extension(Some(std::mem::transmute::<
+                *const (),
+                unsafe extern "C" fn(
+                    *mut rusqlite::ffi::sqlite3,
+                    *mut *mut std::os::raw::c_char,
+                    *const rusqlite::ffi::sqlite3_api_routines,
+                ) -> std::os::raw::c_int,
+            >(
+                sqlite_vec::sqlite3_vec_init as *const (),
+            )));
+        }
+    });
+}
+
+/// WHERE-clause conditions with their positional parameters.
+#[derive(Default)]
+struct Filter {
+    clauses: Vec<String>,
+    params: Vec<Value>,
+}
+
+impl Filter {
+    fn push(&mut self, clause: &str, value: impl Into<Value>) {
+        self.clauses.push(clause.to_string());
+        self.params.push(value.into());
+    }
+
+    fn push_in(&mut self, column: &str, values: impl IntoIterator<Item = Value>) {
+        let start = self.params.len();
+        self.params.extend(values);
+        let placeholders = vec!["?"; self.params.len() - start].join(", ");
+        self.clauses
+            .push(format!("{} IN ({})", column, placeholders));
+    }
+
+    fn push_not_in(&mut self, column: &str, values: impl IntoIterator<Item = Value>) {
+        let start = self.params.len();
+        self.params.extend(values);
+        let placeholders = vec!["?"; self.params.len() - start].join(", ");
+        self.clauses
+            .push(format!("{} NOT IN ({})", column, placeholders));
+    }
+
+    fn sql(&self) -> String {
+        if self.clauses.is_empty() {
+            "1".to_string()
+        } else {
+            self.clauses.join(" AND ")
+        }
+    }
+}
+
+fn placeholders(count: usize) -> String {
+    vec!["?"; count].join(", ")
+}
+
+fn embedding_to_blob(embedding: &[f32]) -> Vec<u8> {
+    embedding.iter().flat_map(|v| v.to_le_bytes()).collect()
+}
+
+fn blob_to_embedding(blob: &[u8]) -> Vec<f32> {
+    blob.chunks_exact(4)
+        .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+        .collect()
+}
+
+fn parse_time(raw: &str) -> Result<DateTime<Utc>> {
+    Ok(DateTime::parse_from_rfc3339(raw)
+        .with_context(|| format!("invalid timestamp '{}'", raw))?
+        .with_timezone(&Utc))
+}
+
+/// Build a memory from the first `MEMORY_COLUMN_COUNT` columns of `row`.
+fn memory_from_row(row: &Row) -> Result<Memory> {
+    let importance = row.get::<_, f64>(8)? as f32;
+    let created_at = parse_time(&row.get::<_, String>(6)?)?;
+    let mut decay = MemoryDecay::new(importance);
+    decay.access_count = row.get::<_, i64>(14)?.max(0) as u32;
+    decay.last_accessed = parse_time(&row.get::<_, String>(15)?).unwrap_or(created_at);
+    decay.decay_rate = row.get::<_, f64>(20)? as f32;
+    let created_by: String = row.get(19)?;
+
+    Ok(Memory {
+        id: row.get(0)?,
+        memory_type: MemoryType::from(row.get::<_, String>(3)?),
+        title: row.get(4)?,
+        content: row.get(5)?,
+        created_at,
+        updated_at: parse_time(&row.get::<_, String>(7)?)?,
+        metadata: MemoryMetadata {
+            git_commit: row.get(12)?,
+            created_by: (!created_by.is_empty()).then_some(created_by),
+            importance,
+            confidence: row.get::<_, f64>(9)? as f32,
+            tags: serde_json::from_str(&row.get::<_, String>(10)?).unwrap_or_default(),
+            related_files: serde_json::from_str(&row.get::<_, String>(11)?).unwrap_or_default(),
+            source: MemorySource::from(row.get::<_, String>(13)?),
+            decay,
+            state: MemoryState::from(row.get::<_, String>(16)?),
+            attachments: serde_json::from_str(&row.get::<_, String>(17)?).unwrap_or_default(),
+            custom_fields: serde_json::from_str(&row.get::<_, String>(18)?).unwrap_or_default(),
+        },
+        relevance_score: None,
+    })
+}
+
+fn relationship_from_row(row: &Row) -> Result<MemoryRelationship> {
+    Ok(MemoryRelationship {
+        id: row.get(0)?,
+        source_id: row.get(1)?,
+        target_id: row.get(2)?,
+        relationship_type: RelationshipType::from(row.get::<_, String>(3)?.as_str()),
+        strength: row.get::<_, f64>(4)? as f32,
+        description: row.get(5)?,
+        created_at: parse_time(&row.get::<_, String>(6)?)?,
+    })
+}
+
+/// Run `sql` and convert every row with `convert`.
+fn query_rows<T>(
+    conn: &Connection,
+    sql: &str,
+    params: &[Value],
+    convert: impl Fn(&Row) -> Result<T>,
+) -> Result<Vec<T>> {
+    let mut stmt = conn.prepare(sql)?;
+    let mut rows = stmt.query(params_from_iter(params.iter()))?;
+    let mut out = Vec::new();
+    while let Some(row) = rows.next()? {
+        out.push(convert(row)?);
+    }
+    Ok(out)
+}
+
+/// Insert or update a memory row; `values` are `MEMORY_COLUMNS` plus the embedding.
+fn upsert_memory_row(conn: &Connection, values: &[Value]) -> Result<()> {
+    let updates = MEMORY_COLUMNS
+        .split(", ")
+        .skip(1)
+        .chain(std::iter::once("embedding"))
+        .map(|c| format!("{c} = excluded.{c}", c = c.trim()))
+        .collect::<Vec<_>>()
+        .join(", ");
+    conn.execute(
+        &format!(
+            "INSERT INTO memories ({}, embedding) VALUES ({}) ON CONFLICT(id) DO UPDATE SET {}",
+            MEMORY_COLUMNS,
+            placeholders(MEMORY_COLUMN_COUNT + 1),
+            updates
+        ),
+        params_from_iter(values.iter()),
+    )?;
+    Ok(())
+}
+
+/// SQLite-backed memory store
+pub struct SqliteStore {
+    conn: Arc<Mutex<Connection>>,
+    embedding_provider: Box<dyn EmbeddingProvider>,
+    config: MemoryConfig,
+    main_config: crate::config::Config,
+    reranker_integration: Mutex<Option<RerankerIntegration>>,
+    project_key: Option<String>,
+    role: Option<String>,
+    /// `project_key` used for writes ("default" when unscoped)
+    label: String,
+}
+
+impl SqliteStore {
+    /// Open (creating if needed) the database at `path`.
+    pub async fn open(path: &Path, options: BackendOptions) -> Result<Self> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        register_sqlite_vec();
+        let conn = Connection::open(path)
+            .with_context(|| format!("Failed to open SQLite database {}", path.display()))?;
+        Self::with_connection(conn, options)
+    }
+
+    /// Database that lives only as long as this store.
+    #[cfg(test)]
+    pub fn open_in_memory(options: BackendOptions) -> Result<Self> {
+        register_sqlite_vec();
+        Self::with_connection(Connection::open_in_memory()?, options)
+    }
+
+    /// Wrap a connection opened after `register_sqlite_vec`, creating the schema if missing.
+    fn with_connection(conn: Connection, options: BackendOptions) -> Result<Self> {
+        // Several MCP servers may share the file; wait for their writes instead of failing
+        conn.busy_timeout(std::time::Duration::from_secs(
+            options.config.write_lock_timeout_secs,
+        ))?;
+        conn.query_row("PRAGMA journal_mode = WAL", [], |_| Ok(()))?;
+        conn.execute_batch(SCHEMA)
+            .context("Failed to initialize SQLite memory schema")?;
+        add_missing_columns(&conn).context("Failed to upgrade SQLite memory schema")?;
+
+        let label = options
+            .project_key
+            .clone()
+            .unwrap_or_else(|| "default".to_string());
+        Ok(Self {
+            conn: Arc::new(Mutex::new(conn)),
+            embedding_provider: options.embedding_provider,
+            config: options.config,
+            main_config: options.main_config,
+            reranker_integration: Mutex::new(options.reranker_integration),
+            project_key: options.project_key,
+            role: options.role,
+            label,
+        })
+    }
+
+    /// Run `f` against the connection on the blocking thread pool.
+    async fn with_conn<T, F>(&self, f: F) -> Result<T>
+    where
+        T: Send + 'static,
+        F: FnOnce(&mut Connection) -> Result<T> + Send + 'static,
+    {
+        let conn = self.conn.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut guard = conn
+                .lock()
+                .map_err(|_| anyhow::anyhow!("SQLite connection lock poisoned"))?;
+            f(&mut guard)
+        })
+        .await
+        .context("SQLite task panicked")?
+    }
+
+    /// `MEMORY_COLUMNS` values of `memory` plus its embedding, for `upsert_memory_row`
+    fn memory_values(&self, memory: &Memory, embedding: &[f32]) -> Result<Vec<Value>> {
+        Ok(vec![
+            memory.id.clone().into(),
+            self.label.clone().into(),
+            self.role.clone().unwrap_or_default().into(),
+            memory.memory_type.to_string().into(),
+            memory.title.clone().into(),
+            memory.content.clone().into(),
+            memory.created_at.to_rfc3339().into(),
+            memory.updated_at.to_rfc3339().into(),
+            f64::from(memory.metadata.importance).into(),
+            f64::from(memory.metadata.confidence).into(),
+            serde_json::to_string(&memory.metadata.tags)?.into(),
+            serde_json::to_string(&memory.metadata.related_files)?.into(),
+            memory.metadata.git_commit.clone().into(),
+            memory.metadata.source.to_string().into(),
+            i64::from(memory.metadata.decay.access_count).into(),
+            memory.metadata.decay.last_accessed.to_rfc3339().into(),
+            memory.metadata.state.to_string().into(),
+            serde_json::to_string(&memory.metadata.attachments)?.into(),
+            serde_json::to_string(&memory.metadata.custom_fields)?.into(),
+            memory
+                .metadata
+                .created_by
+                .clone()
+                .unwrap_or_default()
+                .into(),
+            f64::from(memory.metadata.decay.decay_rate).into(),
+            Value::Blob(embedding_to_blob(embedding)),
+        ])
+    }
+
+    fn current_importance(&self, memory: &Memory) -> f32 {
+        memory.get_current_importance(
+            self.config.decay_enabled,
+            self.config.min_importance_threshold,
+            self.config.decay_half_life_days,
+            self.config.access_boost_factor,
+        )
+    }
+
+    /// Project/role scope, plus the scalar filters of `query` when given.
+    fn filter(&self, query: Option<&MemoryQuery>) -> Filter {
+        let mut filter = Filter::default();
+        if let Some(key) = self.project_key.as_deref() {
+            filter.push("project_key = ?", key.to_string());
+        }
+        if let Some(role) = self.role.as_deref() {
+            filter.push("role = ?", role.to_string());
+        }
+        let Some(query) = query else {
+            return filter;
+        };
+        if let Some(types) = query.memory_types.as_ref().filter(|t| !t.is_empty()) {
+            filter.push_in(
+                "memory_type",
+                types.iter().map(|t| Value::Text(t.to_string())),
+            );
+        }
+        if let Some(types) = query.exclude_types.as_ref().filter(|t| !t.is_empty()) {
+            filter.push_not_in(
+                "memory_type",
+                types.iter().map(|t| Value::Text(t.to_string())),
+            );
+        }
+        if let Some(min_importance) = query.min_importance {
+            filter.push("importance >= ?", f64::from(min_importance));
+        }
+        if let Some(min_confidence) = query.min_confidence {
+            filter.push("confidence >= ?", f64::from(min_confidence));
+        }
+        if let Some(git_commit) = &query.git_commit {
+            filter.push("git_commit = ?", git_commit.clone());
+        }
+        if let Some(created_by) = &query.created_by {
+            filter.push("created_by = ?", created_by.clone());
+        }
+        if let Some(created_after) = query.created_after {
+            filter.push("created_at >= ?", created_after.to_rfc3339());
+        }
+        if let Some(created_before) = query.created_before {
+            filter.push("created_at <= ?", created_before.to_rfc3339());
+        }
+        filter
+    }
+
+    /// Every memory matching `filter` (plus an optional extra condition).
+    async fn select_memories(&self, filter: Filter, extra: Option<&str>) -> Result<Vec<Memory>> {
+        let mut condition = filter.sql();
+        if let Some(extra) = extra {
+            condition = format!("{} AND {}", condition, extra);
+        }
+        let sql = format!(
+            "SELECT {} FROM memories WHERE {}",
+            MEMORY_COLUMNS, condition
+        );
+        self.with_conn(move |conn| query_rows(conn, &sql, &filter.params, memory_from_row))
+            .await
+    }
+
+    async fn embed(&self, text: &str, input_type: InputType) -> Result<Vec<f32>> {
+        crate::embedding::generate_embedding(
+            text,
+            self.embedding_provider.as_ref(),
+            self.main_config.embedding.timeout_secs,
+            input_type,
+        )
+        .await
+    }
+
+    /// Nearest memories to `embedding` by cosine distance, closest first.
+    async fn nearest(
+        &self,
+        embedding: &[f32],
+        filter: Filter,
+        limit: usize,
+    ) -> Result<Vec<(Memory, f32)>> {
+        let sql = format!(
+            "SELECT {}, vec_distance_cosine(embedding, ?) AS distance FROM memories \
+             WHERE length(embedding) = ? AND {} ORDER BY distance LIMIT ?",
+            MEMORY_COLUMNS,
+            filter.sql()
+        );
+        let mut params = vec![
+            Value::Blob(embedding_to_blob(embedding)),
+            Value::Integer((embedding.len() * 4) as i64),
+        ];
+        params.extend(filter.params);
+        params.push(Value::Integer(limit as i64));
+        self.with_conn(move |conn| {
+            query_rows(conn, &sql, &params, |row| {
+                let distance = row.get::<_, f64>(MEMORY_COLUMN_COUNT)? as f32;
+                Ok((memory_from_row(row)?, distance))
+            })
+        })
+        .await
+    }
+
+    /// IDs of the best FTS5 matches for `query_text`, best first.
+    async fn keyword_matches(
+        &self,
+        query_text: &str,
+        filter: Filter,
+        limit: usize,
+    ) -> Result<Vec<String>> {
+        // Quote every token so user text cannot inject FTS5 query syntax
+        let terms: Vec<String> = keyword_terms(query_text)
+            .iter()
+            .map(|t| format!("\"{}\"", t))
+            .collect();
+        if terms.is_empty() {
+            return Ok(Vec::new());
+        }
+        let sql = format!(
+            "SELECT id FROM memories_fts JOIN memories ON memories.seq = memories_fts.rowid \
+             WHERE memories_fts MATCH ? AND {} ORDER BY bm25(memories_fts) LIMIT ?",
+            filter.sql()
+        );
+        let mut params = vec![Value::Text(terms.join(" OR "))];
+        params.extend(filter.params);
+        params.push(Value::Integer(limit as i64));
+        self.with_conn(move |conn| query_rows(conn, &sql, &params, |row| Ok(row.get(0)?)))
+            .await
+    }
+
+    /// Vector search weighted by temporal importance and trust, like `MemoryStore`.
+    async fn vector_search(&self, query: &MemoryQuery) -> Result<Vec<MemorySearchResult>> {
+        let limit = query
+            .limit
+            .unwrap_or(self.config.max_search_results)
+            .min(self.config.max_search_results);
+        let filter = self.filter(Some(query));
+
+        let hits = match &query.query_text {
+            Some(query_text) => {
+                let embedding = self.embed(query_text, InputType::Query).await?;
+                // Over-fetch to absorb post-filter losses
+                self.nearest(&embedding, filter, limit * 2)
+                    .await?
+                    .into_iter()
+                    .map(|(memory, distance)| (memory, Some(distance)))
+                    .collect()
+            }
+            None => self
+                .select_memories(filter, None)
+                .await?
+                .into_iter()
+                .map(|memory| (memory, None))
+                .collect(),
+        };
+        Ok(score_vector_hits(
+            hits,
+            query,
+            |m| self.current_importance(m),
+            limit,
+        ))
+    }
+
+    /// Vector + FTS5 ranks fused with RRF, then blended with recency and
+    /// importance using the `[search.hybrid]` weights.
+    async fn hybrid_search(
+        &self,
+        query: &MemoryQuery,
+        query_text: &str,
+        limit: usize,
+    ) -> Result<Vec<MemorySearchResult>> {
+        let embedding = self.embed(query_text, InputType::Query).await?;
+        let vector_hits = self
+            .nearest(&embedding, self.filter(Some(query)), limit)
+            .await?;
+        let keyword_ids = self
+            .keyword_matches(query_text, self.filter(Some(query)), limit)
+            .await?;
+
+        let vector_ids: Vec<String> = vector_hits.iter().map(|(m, _)| m.id.clone()).collect();
+        let mut candidates: Vec<Memory> = vector_hits.into_iter().map(|(m, _)| m).collect();
+        let missing: Vec<Value> = keyword_ids
+            .iter()
+            .filter(|id| !vector_ids.contains(id))
+            .map(|id| Value::Text(id.clone()))
+            .collect();
+        if !missing.is_empty() {
+            let mut filter = Filter::default();
+            filter.push_in("id", missing);
+            candidates.extend(self.select_memories(filter, None).await?);
+        }
+
+        Ok(score_hybrid(
+            candidates,
+            &vector_ids,
+            &keyword_ids,
+            query,
+            &self.main_config.search.hybrid,
+            |m| self.current_importance(m),
+            limit,
+        ))
+    }
+
+    /// Bump access stats and log text queries for returned memories. Best-effort.
+    async fn record_accesses_best_effort(
+        &self,
+        results: &[MemorySearchResult],
+        query_text: Option<&str>,
+    ) {
+        if results.is_empty() {
+            return;
+        }
+        let ids: Vec<String> = results.iter().map(|r| r.memory.id.clone()).collect();
+        let label = self.label.clone();
+        let role = self.role.clone();
+        let query_text = query_text.map(str::to_string);
+        let outcome = self
+            .with_conn(move |conn| {
+                let now = Utc::now().to_rfc3339();
+                let tx = conn.transaction()?;
+                let mut params: Vec<Value> = vec![now.clone().into()];
+                params.extend(ids.iter().cloned().map(Value::Text));
+                params.push(label.clone().into());
+                tx.execute(
+                    &format!(
+                        "UPDATE memories SET access_count = access_count + 1, last_accessed = ? \
+                         WHERE id IN ({}) AND project_key = ?",
+                        placeholders(ids.len())
+                    ),
+                    params_from_iter(params.iter()),
+                )?;
+                if let Some(query_text) = query_text {
+                    for id in &ids {
+                        tx.execute(
+                            "INSERT INTO memory_access_log \
+                             (id, project_key, role, memory_id, query, accessed_at) \
+                             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                            rusqlite::params![
+                                uuid::Uuid::new_v4().to_string(),
+                                label,
+                                role,
+                                id,
+                                query_text,
+                                now
+                            ],
+                        )?;
+                    }
+                }
+                tx.commit()?;
+                Ok(())
+            })
+            .await;
+        if let Err(e) = outcome {
+            tracing::warn!("record_accesses failed (search still succeeded): {}", e);
+        }
+    }
+
+    async fn select_relationships(&self, filter: Filter) -> Result<Vec<MemoryRelationship>> {
+        let sql = format!(
+            "SELECT id, source_id, target_id, relationship_type, strength, description, \
+             created_at FROM memory_relationships WHERE {}",
+            filter.sql()
+        );
+        self.with_conn(move |conn| query_rows(conn, &sql, &filter.params, relationship_from_row))
+            .await
+    }
+
+    /// Best-effort removal of attachment blobs for permanently deleted memories.
+    fn remove_attachment_blobs(&self, memory_ids: &[String]) {
+        if memory_ids.is_empty() {
+            return;
+        }
+        match crate::storage::get_attachments_dir() {
+            Ok(root) => super::attachments::remove_attachments(&root, &self.label, memory_ids),
+            Err(e) => tracing::warn!("Cannot resolve attachments dir: {}", e),
+        }
+    }
+}
+
+#[async_trait]
+impl StorageBackend for SqliteStore {
+    fn has_no_project_key(&self) -> bool {
+        self.project_key.is_none()
+    }
+
+    fn project_label(&self) -> &str {
+        &self.label
+    }
+
+    async fn store_memory(&self, memory: &Memory) -> Result<()> {
+        let searchable_text = memory.get_searchable_text();
+        if searchable_text.trim().is_empty() {
+            return Err(anyhow::anyhow!(
+                "Cannot generate embedding: searchable text is empty. Title: '{}', Content: '{}'",
+                memory.title,
+                memory.content
+            ));
+        }
+        let embedding = self.embed(&searchable_text, InputType::Document).await?;
+        self.store_memory_with_embedding(memory, embedding).await
+    }
+
+    async fn store_memory_with_embedding(
+        &self,
+        memory: &Memory,
+        embedding: Vec<f32>,
+    ) -> Result<()> {
+        let values = self.memory_values(memory, &embedding)?;
+        self.with_conn(move |conn| upsert_memory_row(conn, &values))
+            .await
+    }
+
+    async fn store_memories_with_embeddings(&self, rows: &[(Memory, Vec<f32>)]) -> Result<()> {
+        let rows = rows
+            .iter()
+            .map(|(memory, embedding)| self.memory_values(memory, embedding))
+            .collect::<Result<Vec<_>>>()?;
+        self.with_conn(move |conn| {
+            let tx = conn.transaction()?;
+            for values in &rows {
+                upsert_memory_row(&tx, values)?;
+            }
+            tx.commit()?;
+            Ok(())
+        })
+        .await
+    }
+
+    async fn store_memories_batch(&self, rows: &[(Memory, Option<Vec<f32>>)]) -> Result<usize> {
+        let mut embedded = Vec::with_capacity(rows.len());
+        let mut generated = 0;
+        for (memory, embedding) in rows {
+            let embedding = match embedding {
+                Some(embedding) => embedding.clone(),
+                None => {
+                    generated += 1;
+                    self.embed(&memory.get_searchable_text(), InputType::Document)
+                        .await?
+                }
+            };
+            embedded.push((memory.clone(), embedding));
+        }
+        self.store_memories_with_embeddings(&embedded).await?;
+        Ok(generated)
+    }
+
+    async fn embedding_dimension(&self) -> Result<usize> {
+        Ok(self.embed("dimension probe", InputType::Query).await?.len())
+    }
+
+    async fn get_all_embeddings(&self) -> Result<HashMap<String, Vec<f32>>> {
+        let filter = self.filter(None);
+        let sql = format!("SELECT id, embedding FROM memories WHERE {}", filter.sql());
+        let rows = self
+            .with_conn(move |conn| {
+                query_rows(conn, &sql, &filter.params, |row| {
+                    Ok((row.get::<_, String>(0)?, row.get::<_, Vec<u8>>(1)?))
+                })
+            })
+            .await?;
+        Ok(rows
+            .into_iter()
+            .map(|(id, blob)| (id, blob_to_embedding(&blob)))
+            .collect())
+    }
+
+    async fn delete_memories(&self, memory_ids: &[String]) -> Result<usize> {
+        if memory_ids.is_empty() {
+            return Ok(0);
+        }
+        let ids = memory_ids.to_vec();
+        let label = self.label.clone();
+        let undo_buffer_size = self.config.undo_buffer_size;
+        let (deleted, expired) = self
+            .with_conn(move |conn| {
+                let tx = conn.transaction()?;
+                let id_list = placeholders(ids.len());
+                let predicate = format!("id IN ({}) AND project_key = ?", id_list);
+                let mut scoped: Vec<Value> = ids.iter().cloned().map(Value::Text).collect();
+                scoped.push(label.clone().into());
+
+                let expired = if undo_buffer_size > 0 {
+                    let mut params = vec![Value::Text(Utc::now().to_rfc3339())];
+                    params.extend(scoped.iter().cloned());
+                    tx.execute(
+                        &format!(
+                            "INSERT INTO memory_recovery ({cols}, embedding, deleted_at) \
+                             SELECT {cols}, embedding, ? FROM memories WHERE {}",
+                            predicate,
+                            cols = MEMORY_COLUMNS
+                        ),
+                        params_from_iter(params.iter()),
+                    )?;
+                    // Keep only the newest `undo_buffer_size` rows for this project
+                    let expired = query_rows(
+                        &tx,
+                        "SELECT id FROM memory_recovery WHERE project_key = ? \
+                         ORDER BY deleted_at DESC, rowid DESC LIMIT -1 OFFSET ?",
+                        &[
+                            label.clone().into(),
+                            Value::Integer(undo_buffer_size as i64),
+                        ],
+                        |row| Ok(row.get::<_, String>(0)?),
+                    )?;
+                    if !expired.is_empty() {
+                        let mut params: Vec<Value> =
+                            expired.iter().cloned().map(Value::Text).collect();
+                        params.push(label.clone().into());
+                        tx.execute(
+                            &format!(
+                                "DELETE FROM memory_recovery WHERE id IN ({}) AND project_key = ?",
+                                placeholders(expired.len())
+                            ),
+                            params_from_iter(params.iter()),
+                        )?;
+                    }
+                    expired
+                } else {
+                    ids.clone()
+                };
+
+                let deleted = tx.execute(
+                    &format!("DELETE FROM memories WHERE {}", predicate),
+                    params_from_iter(scoped.iter()),
+                )?;
+                let mut params: Vec<Value> = ids.iter().cloned().map(Value::Text).collect();
+                params.extend(ids.iter().cloned().map(Value::Text));
+                params.push(label.into());
+                tx.execute(
+                    &format!(
+                        "DELETE FROM memory_relationships \
+                         WHERE (source_id IN ({ids}) OR target_id IN ({ids})) AND project_key = ?",
+                        ids = id_list
+                    ),
+                    params_from_iter(params.iter()),
+                )?;
+                tx.commit()?;
+                Ok((deleted, expired))
+            })
+            .await?;
+        // Expired entries can no longer be undone, so their blobs go too
+        self.remove_attachment_blobs(&expired);
+        Ok(deleted)
+    }
+
+    async fn list_recoverable(&self) -> Result<Vec<(Memory, String)>> {
+        let label = self.label.clone();
+        self.with_conn(move |conn| {
+            query_rows(
+                conn,
+                &format!(
+                    "SELECT {}, deleted_at FROM memory_recovery WHERE project_key = ? \
+                     ORDER BY deleted_at DESC",
+                    MEMORY_COLUMNS
+                ),
+                &[label.into()],
+                |row| {
+                    Ok((
+                        memory_from_row(row)?,
+                        row.get::<_, String>(MEMORY_COLUMN_COUNT)?,
+                    ))
+                },
+            )
+        })
+        .await
+    }
+
+    async fn restore_last_deletions(&self, operations: usize) -> Result<Vec<Memory>> {
+        let label = self.label.clone();
+        self.with_conn(move |conn| {
+            let tx = conn.transaction()?;
+            let stamps: Vec<Value> = query_rows(
+                &tx,
+                "SELECT DISTINCT deleted_at FROM memory_recovery WHERE project_key = ? \
+                 ORDER BY deleted_at DESC LIMIT ?",
+                &[
+                    label.clone().into(),
+                    Value::Integer(operations.max(1) as i64),
+                ],
+                |row| Ok(Value::Text(row.get(0)?)),
+            )?;
+            if stamps.is_empty() {
+                return Ok(Vec::new());
+            }
+            let predicate = format!(
+                "project_key = ? AND deleted_at IN ({})",
+                placeholders(stamps.len())
+            );
+            let mut params = vec![Value::Text(label)];
+            params.extend(stamps);
+
+            let rows = query_rows(
+                &tx,
+                &format!(
+                    "SELECT {}, embedding FROM memory_recovery WHERE {}",
+                    MEMORY_COLUMNS, predicate
+                ),
+                &params,
+                |row| {
+                    let values = (0..=MEMORY_COLUMN_COUNT)
+                        .map(|i| row.get::<_, Value>(i))
+                        .collect::<rusqlite::Result<Vec<_>>>()?;
+                    Ok((memory_from_row(row)?, values))
+                },
+            )?;
+            let mut restored = Vec::with_capacity(rows.len());
+            for (memory, values) in rows {
+                upsert_memory_row(&tx, &values)?;
+                restored.push(memory);
+            }
+            tx.execute(
+                &format!("DELETE FROM memory_recovery WHERE {}", predicate),
+                params_from_iter(params.iter()),
+            )?;
+            tx.commit()?;
+            Ok(restored)
+        })
+        .await
+    }
+
+    async fn restore_deleted(&self, memory_ids: &[String]) -> Result<Vec<Memory>> {
+        if memory_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+        let label = self.label.clone();
+        let ids = memory_ids.to_vec();
+        self.with_conn(move |conn| {
+            let tx = conn.transaction()?;
+            let predicate = format!("project_key = ? AND id IN ({})", placeholders(ids.len()));
+            let mut params = vec![Value::Text(label)];
+            params.extend(ids.into_iter().map(Value::Text));
+
+            // Newest copy first, so a memory deleted twice comes back as its latest copy
+            let rows = query_rows(
+                &tx,
+                &format!(
+                    "SELECT {}, embedding FROM memory_recovery WHERE {} \
+                     ORDER BY deleted_at DESC, rowid DESC",
+                    MEMORY_COLUMNS, predicate
+                ),
+                &params,
+                |row| {
+                    let values = (0..=MEMORY_COLUMN_COUNT)
+                        .map(|i| row.get::<_, Value>(i))
+                        .collect::<rusqlite::Result<Vec<_>>>()?;
+                    Ok((memory_from_row(row)?, values))
+                },
+            )?;
+            let mut restored: Vec<Memory> = Vec::with_capacity(rows.len());
+            for (memory, values) in rows {
+                if restored.iter().any(|m| m.id == memory.id) {
+                    continue;
+                }
+                upsert_memory_row(&tx, &values)?;
+                restored.push(memory);
+            }
+            tx.execute(
+                &format!("DELETE FROM memory_recovery WHERE {}", predicate),
+                params_from_iter(params.iter()),
+            )?;
+            tx.commit()?;
+            Ok(restored)
+        })
+        .await
+    }
+
+    async fn purge_deleted(&self, deleted_before: Option<DateTime<Utc>>) -> Result<usize> {
+        let label = self.label.clone();
+        let purged = self
+            .with_conn(move |conn| {
+                let tx = conn.transaction()?;
+                let entries = query_rows(
+                    &tx,
+                    "SELECT id, deleted_at FROM memory_recovery WHERE project_key = ?",
+                    &[label.clone().into()],
+                    |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)),
+                )?;
+                let stamps: Vec<String> = entries
+                    .iter()
+                    .filter(|(_, deleted_at)| match deleted_before {
+                        Some(cutoff) => parse_time(deleted_at).is_ok_and(|t| t < cutoff),
+                        None => true,
+                    })
+                    .map(|(_, deleted_at)| deleted_at.clone())
+                    .collect();
+                let purged: Vec<String> = entries
+                    .into_iter()
+                    .filter(|(_, deleted_at)| stamps.contains(deleted_at))
+                    .map(|(id, _)| id)
+                    .collect();
+                if !purged.is_empty() {
+                    let mut params = vec![Value::Text(label)];
+                    params.extend(stamps.into_iter().map(Value::Text));
+                    tx.execute(
+                        &format!(
+                            "DELETE FROM memory_recovery \
+                             WHERE project_key = ? AND deleted_at IN ({})",
+                            placeholders(params.len() - 1)
+                        ),
+                        params_from_iter(params.iter()),
+                    )?;
+                }
+                tx.commit()?;
+                Ok(purged)
+            })
+            .await?;
+        self.remove_attachment_blobs(&purged);
+        Ok(purged.len())
+    }
+
+    async fn run_maintenance(&self) -> Result<()> {
+        self.with_conn(|conn| {
+            conn.execute_batch(
+                "INSERT INTO memories_fts (memories_fts) VALUES ('optimize'); PRAGMA optimize;",
+            )?;
+            Ok(())
+        })
+        .await
+    }
+
+    async fn get_memory(&self, memory_id: &str) -> Result<Option<Memory>> {
+        let mut filter = Filter::default();
+        filter.push("id = ?", memory_id.to_string());
+        if let Some(key) = self.project_key.as_deref() {
+            filter.push("project_key = ?", key.to_string());
+        }
+        Ok(self.select_memories(filter, None).await?.into_iter().next())
+    }
+
+    async fn search_memories(&self, query: &MemoryQuery) -> Result<Vec<MemorySearchResult>> {
+        let reranker = self
+            .reranker_integration
+            .lock()
+            .ok()
+            .and_then(|g| g.as_ref().filter(|r| r.config.enabled).cloned());
+        let reranker_query = reranker.and_then(|r| {
+            query
+                .query_text
+                .as_deref()
+                .filter(|t| !t.trim().is_empty())
+                .map(|t| (r, t.to_string()))
+        });
+        // Give the reranker a wider candidate set to reorder
+        let top_k = self.main_config.search.reranker.top_k_candidates;
+        let widen = reranker_query.is_some() && top_k > 1;
+
+        let candidates = match query.query_text.as_deref() {
+            Some(text) if self.main_config.search.hybrid.enabled => {
+                let limit = if widen {
+                    top_k
+                } else {
+                    query.limit.unwrap_or(self.config.max_search_results)
+                };
+                self.hybrid_search(query, text, limit).await?
+            }
+            _ if widen => {
+                let mut extended = query.clone();
+                extended.limit = Some(top_k);
+                self.vector_search(&extended).await?
+            }
+            _ => self.vector_search(query).await?,
+        };
+
+        let results = match reranker_query {
+            Some((reranker, text)) => reranker.rerank_memories(&text, candidates).await?,
+            None => candidates,
+        };
+        self.record_accesses_best_effort(&results, query.query_text.as_deref())
+            .await;
+        Ok(results)
+    }
+
+    async fn get_access_log(&self, since: DateTime<Utc>) -> Result<Vec<AccessLogEntry>> {
+        let mut filter = self.filter(None);
+        filter.push("accessed_at >= ?", since.to_rfc3339());
+        let sql = format!(
+            "SELECT memory_id, query, accessed_at FROM memory_access_log WHERE {}",
+            filter.sql()
+        );
+        let entries = self
+            .with_conn(move |conn| {
+                query_rows(conn, &sql, &filter.params, |row| {
+                    Ok((
+                        row.get::<_, String>(0)?,
+                        row.get::<_, String>(1)?,
+                        row.get::<_, String>(2)?,
+                    ))
+                })
+            })
+            .await?;
+        Ok(entries
+            .into_iter()
+            .filter_map(|(memory_id, query, accessed_at)| {
+                Some(AccessLogEntry {
+                    memory_id,
+                    query,
+                    accessed_at: parse_time(&accessed_at).ok()?,
+                })
+            })
+            .collect())
+    }
+
+    async fn update_state_and_importance(
+        &self,
+        id: &str,
+        new_state: MemoryState,
+        new_importance: f32,
+    ) -> Result<()> {
+        let params: Vec<Value> = vec![
+            new_state.to_string().into(),
+            f64::from(new_importance.clamp(0.0, 1.0)).into(),
+            id.to_string().into(),
+            self.label.clone().into(),
+        ];
+        self.with_conn(move |conn| {
+            conn.execute(
+                "UPDATE memories SET state = ?, importance = ? WHERE id = ? AND project_key = ?",
+                params_from_iter(params.iter()),
+            )
+            .context("partial update of state/importance failed")?;
+            Ok(())
+        })
+        .await
+    }
+
+    async fn get_recent_working_memories(&self, since: DateTime<Utc>) -> Result<Vec<Memory>> {
+        let mut filter = self.filter(None);
+        filter.push("state = ?", MemoryState::Working.to_string());
+        filter.push("created_at >= ?", since.to_rfc3339());
+        self.select_memories(filter, None).await
+    }
+
+    async fn get_revisions(&self, memory_id: &str) -> Result<Vec<MemoryRevision>> {
+        let mut filter = Filter::default();
+        filter.push("memory_id = ?", memory_id.to_string());
+        if let Some(key) = self.project_key.as_deref() {
+            filter.push("project_key = ?", key.to_string());
+        }
+        let sql = format!(
+            "SELECT memory_id, revision, replaced_at, memory FROM memory_revisions \
+             WHERE {} ORDER BY revision",
+            filter.sql()
+        );
+        self.with_conn(move |conn| {
+            query_rows(conn, &sql, &filter.params, |row| {
+                Ok(MemoryRevision {
+                    memory_id: row.get(0)?,
+                    revision: row.get::<_, i64>(1)?.max(0) as u32,
+                    replaced_at: parse_time(&row.get::<_, String>(2)?)?,
+                    memory: serde_json::from_str(&row.get::<_, String>(3)?)
+                        .context("Corrupt memory revision")?,
+                })
+            })
+        })
+        .await
+    }
+
+    async fn append_revision(&self, revision: &MemoryRevision) -> Result<()> {
+        let params: Vec<Value> = vec![
+            uuid::Uuid::new_v4().to_string().into(),
+            self.label.clone().into(),
+            revision.memory_id.clone().into(),
+            i64::from(revision.revision).into(),
+            revision.replaced_at.to_rfc3339().into(),
+            serde_json::to_string(&revision.memory)?.into(),
+        ];
+        self.with_conn(move |conn| {
+            conn.execute(
+                "INSERT INTO memory_revisions \
+                 (id, project_key, memory_id, revision, replaced_at, memory) \
+                 VALUES (?, ?, ?, ?, ?, ?)",
+                params_from_iter(params.iter()),
+            )?;
+            Ok(())
+        })
+        .await
+    }
+
+    async fn store_relationship(&self, relationship: &MemoryRelationship) -> Result<()> {
+        self.store_relationships(std::slice::from_ref(relationship))
+            .await
+    }
+
+    async fn store_relationships(&self, relationships: &[MemoryRelationship]) -> Result<()> {
+        if relationships.is_empty() {
+            return Ok(());
+        }
+        let rows: Vec<Vec<Value>> = relationships
+            .iter()
+            .map(|relationship| {
+                vec![
+                    relationship.id.clone().into(),
+                    relationship.source_id.clone().into(),
+                    relationship.target_id.clone().into(),
+                    self.label.clone().into(),
+                    relationship.relationship_type.to_string().into(),
+                    f64::from(relationship.strength).into(),
+                    relationship.description.clone().into(),
+                    relationship.created_at.to_rfc3339().into(),
+                ]
+            })
+            .collect();
+        self.with_conn(move |conn| {
+            let tx = conn.transaction()?;
+            for params in &rows {
+                tx.execute(
+                    "INSERT OR REPLACE INTO memory_relationships \
+                     (id, source_id, target_id, project_key, relationship_type, strength, \
+                     description, created_at) VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
+                    params_from_iter(params.iter()),
+                )?;
+            }
+            tx.commit()?;
+            Ok(())
+        })
+        .await
+    }
+
+    async fn get_memory_relationships(&self, memory_id: &str) -> Result<Vec<MemoryRelationship>> {
+        let mut filter = Filter::default();
+        filter
+            .clauses
+            .push("(source_id = ? OR target_id = ?)".to_string());
+        filter.params.push(memory_id.to_string().into());
+        filter.params.push(memory_id.to_string().into());
+        if let Some(key) = self.project_key.as_deref() {
+            filter.push("project_key = ?", key.to_string());
+        }
+        self.select_relationships(filter).await
+    }
+
+    async fn get_all_relationships(&self) -> Result<Vec<MemoryRelationship>> {
+        let mut filter = Filter::default();
+        if let Some(key) = self.project_key.as_deref() {
+            filter.push("project_key = ?", key.to_string());
+        }
+        self.select_relationships(filter).await
+    }
+
+    async fn delete_auto_linked_relationships(&self, memory_id: &str) -> Result<()> {
+        let params: Vec<Value> = vec![
+            memory_id.to_string().into(),
+            memory_id.to_string().into(),
+            RelationshipType::AutoLinked.to_string().into(),
+            self.label.clone().into(),
+        ];
+        self.with_conn(move |conn| {
+            conn.execute(
+                "DELETE FROM memory_relationships WHERE (source_id = ? OR target_id = ?) \
+                 AND relationship_type = ? AND project_key = ?",
+                params_from_iter(params.iter()),
+            )?;
+            Ok(())
+        })
+        .await
+    }
+
+    async fn delete_relationships(&self, relationship_ids: &[String]) -> Result<usize> {
+        if relationship_ids.is_empty() {
+            return Ok(0);
+        }
+        let sql = format!(
+            "DELETE FROM memory_relationships WHERE id IN ({}) AND project_key = ?",
+            placeholders(relationship_ids.len())
+        );
+        let mut params: Vec<Value> = relationship_ids.iter().cloned().map(Value::Text).collect();
+        params.push(self.label.clone().into());
+        self.with_conn(move |conn| Ok(conn.execute(&sql, params_from_iter(params.iter()))?))
+            .await
+    }
+
+    async fn get_memory_count(&self) -> Result<usize> {
+        let mut filter = Filter::default();
+        if let Some(key) = self.project_key.as_deref() {
+            filter.push("project_key = ?", key.to_string());
+        }
+        let sql = format!("SELECT COUNT(*) FROM memories WHERE {}", filter.sql());
+        self.with_conn(move |conn| {
+            let count: i64 =
+                conn.query_row(&sql, params_from_iter(filter.params.iter()), |row| {
+                    row.get(0)
+                })?;
+            Ok(count as usize)
+        })
+        .await
+    }
+
+    async fn get_distinct_projects_and_roles(&self) -> Result<(Vec<String>, Vec<String>)> {
+        let mut filter = Filter::default();
+        if let Some(key) = self.project_key.as_deref() {
+            filter.push("project_key = ?", key.to_string());
+        }
+        let condition = filter.sql();
+        self.with_conn(move |conn| {
+            let projects = query_rows(
+                conn,
+                &format!(
+                    "SELECT DISTINCT project_key FROM memories WHERE {} ORDER BY project_key",
+                    condition
+                ),
+                &filter.params,
+                |row| Ok(row.get(0)?),
+            )?;
+            let roles = query_rows(
+                conn,
+                &format!(
+                    "SELECT DISTINCT role FROM memories WHERE {} AND role != '' ORDER BY role",
+                    condition
+                ),
+                &filter.params,
+                |row| Ok(row.get(0)?),
+            )?;
+            Ok((projects, roles))
+        })
+        .await
+    }
+
+    async fn get_all_memories(&self) -> Result<Vec<Memory>> {
+        self.select_memories(self.filter(None), None).await
+    }
+
+    async fn get_memories_with_files(&self) -> Result<Vec<Memory>> {
+        let mut filter = Filter::default();
+        if let Some(key) = self.project_key.as_deref() {
+            filter.push("project_key = ?", key.to_string());
+        }
+        self.select_memories(filter, Some("related_files != '[]'"))
+            .await
+    }
+
+    async fn cleanup_old_memories(&self) -> Result<usize> {
+        let Some(cleanup_days) = self.config.auto_cleanup_days else {
+            return Ok(0);
+        };
+        let cutoff = Utc::now() - chrono::Duration::days(cleanup_days as i64);
+        let params: Vec<Value> = vec![
+            self.label.clone().into(),
+            cutoff.to_rfc3339().into(),
+            f64::from(self.config.cleanup_min_importance).into(),
+        ];
+        let ids = self
+            .with_conn(move |conn| {
+                query_rows(
+                    conn,
+                    "SELECT id FROM memories \
+                     WHERE project_key = ? AND created_at < ? AND importance < ?",
+                    &params,
+                    |row| Ok(row.get::<_, String>(0)?),
+                )
+            })
+            .await?;
+        // Through the recovery buffer, so a cleanup can be undone from the trash
+        self.delete_memories(&ids).await
+    }
+
+    async fn clear_all_memory_data(&self) -> Result<usize> {
+        let label = self.label.clone();
+        self.with_conn(move |conn| {
+            let tx = conn.transaction()?;
+            let memories = tx.execute("DELETE FROM memories WHERE project_key = ?1", [&label])?;
+            let relationships = tx.execute(
+                "DELETE FROM memory_relationships WHERE project_key = ?1",
+                [&label],
+            )?;
+            tx.execute(
+                "DELETE FROM memory_access_log WHERE project_key = ?1",
+                [&label],
+            )?;
+            tx.execute(
+                "DELETE FROM memory_revisions WHERE project_key = ?1",
+                [&label],
+            )?;
+            tx.commit()?;
+            Ok(memories + relationships)
+        })
+        .await
+    }
+
+    fn enable_reranker(&self, model: Option<String>) {
+        super::reranker_integration::enable_reranker(
+            &self.reranker_integration,
+            &self.main_config.search.reranker,
+            model,
+        );
+    }
+
+    fn disable_reranker(&self) {
+        super::reranker_integration::disable_reranker(&self.reranker_integration);
+    }
+}
@@ -0,0 +1,392 @@
+// Copyright 2026 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#[cfg(test)]
+mod tests {
+    use super::super::backend::{BackendOptions, StorageBackend};
+    use super::super::sqlite_store::SqliteStore;
+    use super::super::types::{
+        Memory, MemoryConfig, MemoryQuery, MemoryRelationship, MemoryState, MemoryType,
+        RelationshipType,
+    };
+    use crate::embedding::{EmbeddingProvider, InputType};
+    use async_trait::async_trait;
+
+    const DIM: usize = 64;
+
+    /// Bag-of-words embedding: texts sharing words point the same way.
+    struct WordHashEmbedding;
+
+    fn embed(text: &str) -> Vec<f32> {
+        let mut v = vec![0.0f32; DIM];
+        for word in text.to_lowercase().split(|c: char| !c.is_alphanumeric()) {
+            if word.is_empty() {
+                continue;
+            }
+            let hash = word
+                .bytes()
+                .fold(5381usize, |h, b| h.wrapping_mul(33) ^ b as usize);
+            v[hash % DIM] += 1.0;
+        }
+        let norm = v.iter().map(|x| x * x).sum::<f32>().sqrt().max(1e-6);
+        v.iter().map(|x| x / norm).collect()
+    }
+
+    #[async_trait]
+    impl EmbeddingProvider for WordHashEmbedding {
+        async fn generate_embedding(&self, text: &str) -> anyhow::Result<Vec<f32>> {
+            Ok(embed(text))
+        }
+
+        async fn generate_embeddings_batch(
+            &self,
+            texts: Vec<String>,
+            _input_type: InputType,
+        ) -> anyhow::Result<Vec<Vec<f32>>> {
+            Ok(texts.iter().map(|t| embed(t)).collect())
+        }
+
+        fn get_dimension(&self) -> usize {
+            DIM
+        }
+    }
+
+    fn main_config(hybrid: bool) -> crate::config::Config {
+        let mut search = crate::config::SearchConfig::default();
+        search.hybrid.enabled = hybrid;
+        crate::config::Config {
+            embedding: Default::default(),
+            search,
+            memory: MemoryConfig::default(),
+            knowledge: Default::default(),
+            vision: Default::default(),
+            maintenance: Default::default(),
+            server: Default::default(),
+            storage: Default::default(),
+        }
+    }
+
+    fn store(project: &str, hybrid: bool) -> SqliteStore {
+        SqliteStore::open_in_memory(BackendOptions {
+            db_dir: std::env::temp_dir(),
+            project_key: Some(project.to_string()),
+            role: None,
+            embedding_provider: Box::new(WordHashEmbedding),
+            config: MemoryConfig::default(),
+            main_config: main_config(hybrid),
+            reranker_integration: None,
+        })
+        .unwrap()
+    }
+
+    fn memory(title: &str, content: &str) -> Memory {
+        Memory::new(
+            MemoryType::Architecture,
+            title.to_string(),
+            content.to_string(),
+            None,
+        )
+    }
+
+    fn search(text: &str) -> MemoryQuery {
+        MemoryQuery {
+            query_text: Some(text.to_string()),
+            limit: Some(5),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_store_get_and_vector_search() {
+        let store = store("p1", false);
+        let db = memory("Database choice", "We store vectors in sqlite files");
+        let auth = memory("Auth flow", "Tokens are refreshed by the gateway");
+        store.store_memory(&db).await.unwrap();
+        store.store_memory(&auth).await.unwrap();
+
+        let fetched = store.get_memory(&db.id).await.unwrap().unwrap();
+        assert_eq!(fetched.title, db.title);
+        assert_eq!(fetched.memory_type, MemoryType::Architecture);
+        assert_eq!(store.get_memory_count().await.unwrap(), 2);
+
+        let results = store
+            .search_memories(&search("sqlite vectors"))
+            .await
+            .unwrap();
+        assert_eq!(results[0].memory.id, db.id);
+        // Returned memories get their access recorded
+        let fetched = store.get_memory(&db.id).await.unwrap().unwrap();
+        assert_eq!(fetched.metadata.decay.access_count, 1);
+        let log = store
+            .get_access_log(chrono::Utc::now() - chrono::Duration::minutes(1))
+            .await
+            .unwrap();
+        assert!(log.iter().any(|e| e.memory_id == db.id));
+    }
+
+    #[tokio::test]
+    async fn test_hybrid_search_uses_keywords() {
+        let store = store("p1", true);
+        let retry = memory("Retry policy", "Clients back off exponentially");
+        let cache = memory("Cache layer", "Responses are cached for an hour");
+        store.store_memory(&retry).await.unwrap();
+        store.store_memory(&cache).await.unwrap();
+
+        let results = store.search_memories(&search("cached")).await.unwrap();
+        assert_eq!(results[0].memory.id, cache.id);
+        assert!(results[0].selection_reason.starts_with("Hybrid"));
+    }
+
+    #[tokio::test]
+    async fn test_update_replaces_keyword_index() {
+        let store = store("p1", true);
+        let mut note = memory("Deploy", "Deploys run on fridays");
+        store.store_memory(&note).await.unwrap();
+        note.content = "Deploys run on mondays".to_string();
+        store.update_memory(&note).await.unwrap();
+
+        assert_eq!(store.get_memory_count().await.unwrap(), 1);
+        let results = store.search_memories(&search("mondays")).await.unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].memory.content, note.content);
+    }
+
+    #[tokio::test]
+    async fn test_update_changes_type_and_confidence_in_place() {
+        let store = store("p1", false);
+        let mut note = memory("Retry policy", "Clients back off exponentially");
+        let other = memory("Cache layer", "Responses are cached for an hour");
+        store.store_memory(&note).await.unwrap();
+        store.store_memory(&other).await.unwrap();
+        store
+            .store_relationship(&MemoryRelationship {
+                id: "rel".to_string(),
+                source_id: note.id.clone(),
+                target_id: other.id.clone(),
+                relationship_type: RelationshipType::RelatedTo,
+                strength: 0.5,
+                description: String::new(),
+                created_at: chrono::Utc::now(),
+            })
+            .await
+            .unwrap();
+
+        note.memory_type = MemoryType::Decision;
+        note.metadata.confidence = 0.4;
+        store.update_memory(&note).await.unwrap();
+
+        let fetched = store.get_memory(&note.id).await.unwrap().unwrap();
+        assert_eq!(fetched.memory_type, MemoryType::Decision);
+        assert!((fetched.metadata.confidence - 0.4).abs() < 1e-6);
+        assert_eq!(
+            store
+                .get_memory_relationships(&note.id)
+                .await
+                .unwrap()
+                .len(),
+            1
+        );
+
+        let query = MemoryQuery {
+            memory_types: Some(vec![MemoryType::Decision]),
+            ..search("retry")
+        };
+        let results = store.search_memories(&query).await.unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].memory.id, note.id);
+        let query = MemoryQuery {
+            min_confidence: Some(0.5),
+            ..search("retry")
+        };
+        let results = store.search_memories(&query).await.unwrap();
+        assert!(results.iter().all(|r| r.memory.id != note.id));
+    }
+
+    #[tokio::test]
+    async fn test_delete_relationships_by_id() {
+        let p1 = store("p1", false);
+        let a = memory("First", "alpha content");
+        let b = memory("Second", "beta content");
+        p1.store_memory(&a).await.unwrap();
+        p1.store_memory(&b).await.unwrap();
+        for (id, strength) in [("weak", 0.3), ("strong", 0.9)] {
+            p1.store_relationship(&MemoryRelationship {
+                id: id.to_string(),
+                source_id: a.id.clone(),
+                target_id: b.id.clone(),
+                relationship_type: RelationshipType::AutoLinked,
+                strength,
+                description: String::new(),
+                created_at: chrono::Utc::now(),
+            })
+            .await
+            .unwrap();
+        }
+
+        let removed = p1
+            .delete_relationships(&["weak".to_string(), "missing".to_string()])
+            .await
+            .unwrap();
+        assert_eq!(removed, 1);
+        let left = p1.get_all_relationships().await.unwrap();
+        assert_eq!(left.len(), 1);
+        assert_eq!(left[0].id, "strong");
+        assert_eq!(p1.delete_relationships(&[]).await.unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_delete_and_restore_round_trip() {
+        let store = store("p1", false);
+        let a = memory("First", "alpha content");
+        let b = memory("Second", "beta content");
+        store.store_memory(&a).await.unwrap();
+        store.store_memory(&b).await.unwrap();
+        store
+            .store_relationship(&MemoryRelationship {
+                id: "rel".to_string(),
+                source_id: a.id.clone(),
+                target_id: b.id.clone(),
+                relationship_type: RelationshipType::RelatedTo,
+                strength: 0.5,
+                description: String::new(),
+                created_at: chrono::Utc::now(),
+            })
+            .await
+            .unwrap();
+        assert_eq!(
+            store.get_memory_relationships(&a.id).await.unwrap().len(),
+            1
+        );
+
+        let deleted = store
+            .delete_memories(&[a.id.clone(), b.id.clone()])
+            .await
+            .unwrap();
+        assert_eq!(deleted, 2);
+        assert_eq!(store.get_memory_count().await.unwrap(), 0);
+        assert!(store.get_all_relationships().await.unwrap().is_empty());
+        assert_eq!(store.list_recoverable().await.unwrap().len(), 2);
+
+        let restored = store.restore_last_deletions(1).await.unwrap();
+        assert_eq!(restored.len(), 2);
+        assert_eq!(store.get_memory_count().await.unwrap(), 2);
+        assert!(store.list_recoverable().await.unwrap().is_empty());
+        // Restored rows keep their embeddings and stay searchable
+        let results = store.search_memories(&search("alpha")).await.unwrap();
+        assert_eq!(results[0].memory.id, a.id);
+    }
+
+    #[tokio::test]
+    async fn test_decay_rate_survives_store_and_restore() {
+        let store = store("p1", false);
+        let mut fast = memory("Sprint notes", "Scratch notes for this week");
+        fast.metadata.decay.decay_rate = 4.0;
+        store.store_memory(&fast).await.unwrap();
+        let fetched = store.get_memory(&fast.id).await.unwrap().unwrap();
+        assert_eq!(fetched.metadata.decay.decay_rate, 4.0);
+
+        store.delete_memories(&[fast.id.clone()]).await.unwrap();
+        let restored = store.restore_last_deletions(1).await.unwrap();
+        assert_eq!(restored[0].metadata.decay.decay_rate, 4.0);
+        let fetched = store.get_memory(&fast.id).await.unwrap().unwrap();
+        assert_eq!(fetched.metadata.decay.decay_rate, 4.0);
+    }
+
+    #[tokio::test]
+    async fn test_restore_from_trash_by_id_and_purge() {
+        let store = store("p1", false);
+        let a = memory("First", "alpha content");
+        let b = memory("Second", "beta content");
+        store.store_memory(&a).await.unwrap();
+        store.store_memory(&b).await.unwrap();
+        store.delete_memory(&a.id).await.unwrap();
+        store.delete_memory(&b.id).await.unwrap();
+
+        // Restoring by ID skips the more recent deletion of `b`
+        let restored = store
+            .restore_deleted(std::slice::from_ref(&a.id))
+            .await
+            .unwrap();
+        assert_eq!(restored.len(), 1);
+        assert_eq!(restored[0].id, a.id);
+        assert!(store.get_memory(&a.id).await.unwrap().is_some());
+        let trashed = store.list_recoverable().await.unwrap();
+        assert_eq!(trashed.len(), 1);
+        assert_eq!(trashed[0].0.id, b.id);
+
+        // Nothing is older than a day; emptying removes the rest for good
+        let day_ago = chrono::Utc::now() - chrono::Duration::days(1);
+        assert_eq!(store.purge_deleted(Some(day_ago)).await.unwrap(), 0);
+        assert_eq!(store.purge_deleted(None).await.unwrap(), 1);
+        assert!(store.list_recoverable().await.unwrap().is_empty());
+        assert!(store
+            .restore_deleted(std::slice::from_ref(&b.id))
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_state_update_and_project_scope() {
+        let store = store("p1", false);
+        let note = memory("Scoped", "only in p1");
+        store.store_memory(&note).await.unwrap();
+        store
+            .update_state_and_importance(&note.id, MemoryState::Archived, 0.2)
+            .await
+            .unwrap();
+        let fetched = store.get_memory(&note.id).await.unwrap().unwrap();
+        assert_eq!(fetched.metadata.state, MemoryState::Archived);
+        assert!((fetched.metadata.importance - 0.2).abs() < 1e-6);
+        assert!(store
+            .get_recent_working_memories(chrono::Utc::now() - chrono::Duration::hours(1))
+            .await
+            .unwrap()
+            .is_empty());
+
+        let (projects, _) = store.get_distinct_projects_and_roles().await.unwrap();
+        assert_eq!(projects, vec!["p1".to_string()]);
+        assert_eq!(store.clear_all_memory_data().await.unwrap(), 1);
+        assert_eq!(store.get_memory_count().await.unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_batch_writes_store_every_row() {
+        let store = store("p1", false);
+        let a = memory("Queue", "Jobs are retried three times");
+        let b = memory("Schema", "Tables carry a schema version");
+        let rows = vec![
+            (a.clone(), embed(&a.get_searchable_text())),
+            (b.clone(), embed(&b.get_searchable_text())),
+        ];
+        store.store_memories_with_embeddings(&rows).await.unwrap();
+        assert_eq!(store.get_memory_count().await.unwrap(), 2);
+
+        let link = |id: &str, source: &Memory, target: &Memory| MemoryRelationship {
+            id: id.to_string(),
+            source_id: source.id.clone(),
+            target_id: target.id.clone(),
+            relationship_type: RelationshipType::RelatedTo,
+            strength: 0.5,
+            description: String::new(),
+            created_at: chrono::Utc::now(),
+        };
+        store
+            .store_relationships(&[link("r1", &a, &b), link("r2", &b, &a)])
+            .await
+            .unwrap();
+        store.store_relationships(&[]).await.unwrap();
+        assert_eq!(store.get_all_relationships().await.unwrap().len(), 2);
+    }
+}
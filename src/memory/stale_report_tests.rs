@@ -0,0 +1,72 @@
+// Copyright 2026 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#[cfg(test)]
+mod tests {
+    use super::super::manager::stale_entries;
+    use super::super::types::{Memory, MemoryConfig, MemoryState, MemoryType};
+    use chrono::{Duration, Utc};
+
+    fn memory(title: &str, importance: f32, last_accessed_days_ago: i64) -> Memory {
+        let mut m = Memory::new(
+            MemoryType::Architecture,
+            title.to_string(),
+            "Memory used to exercise the stale report".to_string(),
+            None,
+        );
+        m.metadata.importance = importance;
+        m.metadata.decay.last_accessed = Utc::now() - Duration::days(last_accessed_days_ago);
+        m
+    }
+
+    #[test]
+    fn test_idle_important_memories_are_listed_most_decayed_first() {
+        let config = MemoryConfig::default();
+        let memories = vec![
+            memory("older", 0.9, 300),
+            memory("old", 0.9, 200),
+            memory("recent", 0.9, 10),
+            memory("minor", 0.3, 300),
+        ];
+        let entries = stale_entries(&memories, &config, Utc::now(), 90, 0.7, 0.5);
+        let titles: Vec<&str> = entries.iter().map(|e| e.title.as_str()).collect();
+        assert_eq!(titles, vec!["older", "old"]);
+        assert!(entries[0].decayed > entries[1].decayed);
+        assert!(entries[1].current_importance < 0.45);
+    }
+
+    #[test]
+    fn test_min_decay_filters_lightly_decayed() {
+        let config = MemoryConfig::default();
+        // One half-life idle: decayed by ~50%
+        let memories = vec![memory("half", 0.8, 91)];
+        assert_eq!(
+            stale_entries(&memories, &config, Utc::now(), 30, 0.7, 0.4).len(),
+            1
+        );
+        assert!(stale_entries(&memories, &config, Utc::now(), 30, 0.7, 0.6).is_empty());
+    }
+
+    #[test]
+    fn test_archived_and_undecayed_memories_skipped() {
+        let mut archived = memory("archived", 0.9, 300);
+        archived.metadata.state = MemoryState::Archived;
+        let config = MemoryConfig {
+            decay_enabled: false,
+            ..MemoryConfig::default()
+        };
+        let memories = vec![archived, memory("no decay", 0.9, 300)];
+        assert!(stale_entries(&memories, &config, Utc::now(), 90, 0.7, 0.1).is_empty());
+    }
+}
@@ -14,6 +14,7 @@
 
 use anyhow::{Context, Result};
 use chrono::Utc;
+use std::collections::HashMap;
 use std::sync::Arc;
 
 // Arrow imports
@@ -24,9 +25,9 @@ use arrow_schema::{DataType, Field, Schema};
 use futures::TryStreamExt;
 use lance_index::scalar::FullTextSearchQuery;
 use lancedb::{
-    connect,
     index::Index,
-    query::{ExecutableQuery, QueryBase, QueryExecutionOptions},
+    index::IndexType,
+    query::{ExecutableQuery, QueryBase, QueryExecutionOptions, Select},
     table::{NewColumnTransform, OptimizeAction},
     Connection, DistanceType, Table,
 };
@@ -34,7 +35,29 @@ use lancedb::{
 /// RRF (Reciprocal Rank Fusion) constant k — same value used by knowledge store.
 /// Based on: https://plg.uwaterloo.ca/~gvcormac/cormacksigir09-rrf.pdf
 /// "Experiments indicate that k = 60 was near-optimal"
-const RRF_K: f32 = 60.0;
+pub(crate) const RRF_K: f32 = 60.0;
+
+/// Memory columns with an FTS (BM25) index; hybrid search and `keyword:` filters use them.
+const FTS_COLUMNS: [&str; 3] = ["title", "content", "tags"];
+
+/// Most rows a `keyword:` FTS prefilter may list before the query falls back to a scan.
+const FTS_PREFILTER_LIMIT: usize = 5000;
+
+/// Schema versions of the memory database, applied in place by `apply_migration`.
+const MIGRATIONS: [Migration; 3] = [
+    Migration {
+        version: 1,
+        description: "add memories.state",
+    },
+    Migration {
+        version: 2,
+        description: "add access_count, last_accessed and decay_rate",
+    },
+    Migration {
+        version: 3,
+        description: "add attachments, custom_fields and created_by",
+    },
+];
 
 /// Rocchio query expansion: `alpha * query + (1 - alpha) * centroid`, then L2-normalized.
 ///
@@ -61,11 +84,18 @@ pub(crate) fn rocchio_blend(query: &[f32], centroid: &[f32], alpha: f32) -> Vec<
 }
 
 use super::reranker_integration::RerankerIntegration;
-use super::types::{Memory, MemoryConfig, MemoryQuery, MemoryRelationship, MemorySearchResult};
+use super::search_scoring::{hybrid_signals, vector_signals};
+use super::types::{
+    Memory, MemoryConfig, MemoryQuery, MemoryRelationship, MemoryRevision, MemorySearchResult,
+    SearchSignal,
+};
+use super::write_lock::{WriteGuard, WriteLock};
 use crate::arrow_helpers::{
-    f32_column, f32_column_opt, i32_column_opt, string_column, string_column_opt,
+    embedding_dimension, f32_column, f32_column_opt, i32_column, i32_column_opt, string_column,
+    string_column_opt,
 };
-use crate::embedding::EmbeddingProvider;
+use crate::embedding::{EmbeddingProvider, InputType, ModelGuard};
+use crate::schema::Migration;
 
 /// SQL string escaping for LanceDB predicates is shared across stores; see
 /// [`crate::sql::escape_sql_literal`]. Aliased here to keep predicate call sites terse.
@@ -103,6 +133,17 @@ fn build_scalar_predicate(
         }
     }
 
+    if let Some(ref exclude_types) = query.exclude_types {
+        if !exclude_types.is_empty() {
+            let list = exclude_types
+                .iter()
+                .map(|t| format!("'{}'", t))
+                .collect::<Vec<_>>()
+                .join(", ");
+            parts.push(format!("memory_type NOT IN ({})", list));
+        }
+    }
+
     if let Some(min_importance) = query.min_importance {
         parts.push(format!("importance >= {}", min_importance));
     }
@@ -116,6 +157,10 @@ fn build_scalar_predicate(
         parts.push(format!("git_commit = '{}'", escape_sql(git_commit)));
     }
 
+    if let Some(ref created_by) = query.created_by {
+        parts.push(format!("created_by = '{}'", escape_sql(created_by)));
+    }
+
     if let Some(created_after) = query.created_after {
         parts.push(format!("created_at >= '{}'", created_after.to_rfc3339()));
     }
@@ -127,10 +172,141 @@ fn build_scalar_predicate(
     parts.join(" AND ")
 }
 
+/// Filter on JSON-serialized fields that cannot be pushed down as SQL predicates.
+/// Scalar fields (memory_type, importance, confidence, git_commit, created_at) are
+/// handled by `build_scalar_predicate()` and pushed down via `only_if()`.
+pub(crate) fn matches_json_filters(memory: &Memory, query: &MemoryQuery) -> bool {
+    // tags is stored as a JSON array string — must filter in Rust
+    if let Some(ref tags) = query.tags {
+        if !tags.iter().any(|tag| memory.metadata.tags.contains(tag)) {
+            return false;
+        }
+    }
+
+    // related_files is stored as a JSON array string — must filter in Rust
+    if let Some(ref files) = query.related_files {
+        if !files
+            .iter()
+            .any(|file| memory.metadata.related_files.contains(file))
+        {
+            return false;
+        }
+    }
+
+    if let Some(ref expr) = query.filter_expr {
+        if !expr.matches(memory) {
+            return false;
+        }
+    }
+
+    if let Some(ref tags) = query.exclude_tags {
+        if tags.iter().any(|tag| memory.metadata.tags.contains(tag)) {
+            return false;
+        }
+    }
+
+    if let Some(ref files) = query.exclude_files {
+        if files
+            .iter()
+            .any(|file| memory.metadata.related_files.contains(file))
+        {
+            return false;
+        }
+    }
+
+    true
+}
+
+/// Generate selection reason for search results
+pub(crate) fn generate_selection_reason(query: &MemoryQuery, relevance_score: f32) -> String {
+    let mut reasons = Vec::new();
+
+    if query.query_text.is_some() {
+        reasons.push(format!("Semantic similarity: {:.2}", relevance_score));
+    }
+
+    if query.memory_types.is_some() {
+        reasons.push("Matches memory type filter".to_string());
+    }
+
+    if query.tags.is_some() {
+        reasons.push("Contains matching tags".to_string());
+    }
+
+    if query.related_files.is_some() {
+        reasons.push("Related to specified files".to_string());
+    }
+
+    if query.filter_expr.is_some() {
+        reasons.push("Matches query filter".to_string());
+    }
+
+    if query.exclude_types.is_some()
+        || query.exclude_tags.is_some()
+        || query.exclude_files.is_some()
+    {
+        reasons.push("Passes exclusion filters".to_string());
+    }
+
+    if query.git_commit.is_some() {
+        reasons.push("Matches Git commit filter".to_string());
+    }
+
+    if reasons.is_empty() {
+        "Matches search criteria".to_string()
+    } else {
+        reasons.join(", ")
+    }
+}
+
+/// Order results by `query.sort_by` when set, otherwise by descending relevance.
+pub(crate) fn sort_search_results(
+    results: &mut [MemorySearchResult],
+    query: &MemoryQuery,
+    current_importance: impl Fn(&Memory) -> f32,
+) {
+    if let Some(sort_by) = &query.sort_by {
+        let sort_order = query
+            .sort_order
+            .as_ref()
+            .unwrap_or(&super::types::SortOrder::Descending);
+
+        results.sort_by(|a, b| {
+            let ordering = match sort_by {
+                super::types::MemorySortBy::CreatedAt => {
+                    a.memory.created_at.cmp(&b.memory.created_at)
+                }
+                super::types::MemorySortBy::Importance => {
+                    let a_imp = current_importance(&a.memory);
+                    let b_imp = current_importance(&b.memory);
+                    a_imp
+                        .partial_cmp(&b_imp)
+                        .unwrap_or(std::cmp::Ordering::Equal)
+                }
+            };
+
+            match sort_order {
+                super::types::SortOrder::Ascending => ordering,
+                super::types::SortOrder::Descending => ordering.reverse(),
+            }
+        });
+    } else {
+        super::types::sort_by_relevance_desc(results);
+    }
+}
+
 /// LanceDB-based storage for memories with vector search capabilities
 pub struct MemoryStore {
     memories_table: Table,
     relationships_table: Table,
+    /// Append-only log of (memory, query) pairs for every memory returned by a
+    /// text search. Backs `memory report access`.
+    access_log_table: Table,
+    /// Recovery buffer: the last `undo_buffer_size` deleted memory rows (embedding
+    /// included) plus a `deleted_at` stamp shared by everything removed in one call.
+    recovery_table: Table,
+    /// Earlier versions of edited memories, serialized as JSON (`memory history`).
+    revisions_table: Table,
     schema: Arc<Schema>,
     rel_schema: Arc<Schema>,
     embedding_provider: Box<dyn EmbeddingProvider>,
@@ -145,6 +321,13 @@ pub struct MemoryStore {
     reranker_integration: std::sync::Mutex<Option<RerankerIntegration>>,
     project_key: Option<String>,
     role: Option<String>,
+    /// Cross-process writer lock: several MCP servers may share this database
+    write_lock: WriteLock,
+    /// Refuses writes once the table is stamped with another embedding model
+    model_guard: ModelGuard,
+    /// Every `FTS_COLUMNS` index exists; otherwise keyword filters scan in Rust and
+    /// hybrid search falls back to vector-only
+    fts_ready: bool,
 }
 
 impl MemoryStore {
@@ -156,7 +339,7 @@ impl MemoryStore {
     /// Arrow schema for the `memories` table. Defined once so the writer
     /// (`store_memory_with_embedding`) and the table creator (`init_tables`)
     /// can never drift out of sync.
-    fn memories_schema(vector_dim: usize) -> Arc<Schema> {
+    pub(crate) fn memories_schema(vector_dim: usize) -> Arc<Schema> {
         Arc::new(Schema::new(vec![
             Field::new("id", DataType::Utf8, false),
             Field::new("project_key", DataType::Utf8, false),
@@ -177,9 +360,17 @@ impl MemoryStore {
             // DataFusion SQL-parser versions, and to match what the writer produces below.
             Field::new("access_count", DataType::Int32, false),
             Field::new("last_accessed", DataType::Utf8, false),
+            // Per-memory half-life multiplier (`MemoryDecay::decay_rate`); 1.0 = global rate.
+            Field::new("decay_rate", DataType::Float32, false),
             // Lifecycle state for goal-anchored consolidation. Stores `MemoryState`
             // as a lowercase string ("working" | "consolidated" | "archived").
             Field::new("state", DataType::Utf8, false),
+            // JSON array of `MemoryAttachment` records; blobs live on disk.
+            Field::new("attachments", DataType::Utf8, true),
+            // JSON object of free-form string metadata (`MemoryMetadata::custom_fields`).
+            Field::new("custom_fields", DataType::Utf8, true),
+            // Author (`MemoryMetadata::created_by`); empty when unknown.
+            Field::new("created_by", DataType::Utf8, true),
             Field::new(
                 "embedding",
                 DataType::FixedSizeList(
@@ -205,9 +396,45 @@ impl MemoryStore {
         ]))
     }
 
+    /// Arrow schema for the `memory_recovery` table: the memories schema plus `deleted_at`.
+    fn recovery_schema(memories_schema: &Schema) -> Arc<Schema> {
+        let mut fields: Vec<Field> = memories_schema
+            .fields()
+            .iter()
+            .map(|f| f.as_ref().clone())
+            .collect();
+        fields.push(Field::new("deleted_at", DataType::Utf8, false));
+        Arc::new(Schema::new(fields))
+    }
+
+    /// Arrow schema for the `memory_access_log` table.
+    fn access_log_schema() -> Arc<Schema> {
+        Arc::new(Schema::new(vec![
+            Field::new("id", DataType::Utf8, false),
+            Field::new("project_key", DataType::Utf8, false),
+            Field::new("role", DataType::Utf8, true),
+            Field::new("memory_id", DataType::Utf8, false),
+            Field::new("query", DataType::Utf8, false),
+            Field::new("accessed_at", DataType::Utf8, false),
+        ]))
+    }
+
+    /// Arrow schema for the `memory_revisions` table.
+    fn revisions_schema() -> Arc<Schema> {
+        Arc::new(Schema::new(vec![
+            Field::new("id", DataType::Utf8, false),
+            Field::new("project_key", DataType::Utf8, false),
+            Field::new("memory_id", DataType::Utf8, false),
+            Field::new("revision", DataType::Int32, false),
+            Field::new("replaced_at", DataType::Utf8, false),
+            // The replaced `Memory` as JSON
+            Field::new("memory", DataType::Utf8, false),
+        ]))
+    }
+
     /// project_key used for writes/deletes, falling back to "default" when the
     /// store is unscoped. Centralizes the repeated `unwrap_or("default")`.
-    fn project_label(&self) -> &str {
+    pub(crate) fn project_label(&self) -> &str {
         self.project_key.as_deref().unwrap_or("default")
     }
 
@@ -230,13 +457,7 @@ impl MemoryStore {
         self.memories_table
             .create_index(
                 &["embedding"],
-                Index::IvfPq(
-                    lancedb::index::vector::IvfPqIndexBuilder::default()
-                        .distance_type(params.distance_type)
-                        .num_partitions(params.num_partitions)
-                        .num_sub_vectors(params.num_sub_vectors)
-                        .num_bits(params.num_bits as u32),
-                ),
+                crate::vector_optimizer::ivf_pq_index(&params),
             )
             .execute()
             .await?;
@@ -254,13 +475,26 @@ impl MemoryStore {
         reranker_integration: Option<RerankerIntegration>,
     ) -> Result<Self> {
         let reranker_integration = std::sync::Mutex::new(reranker_integration);
-        let db = connect(db_path).execute().await?;
+        // Zero interval: every read checks for commits made by other processes
+        let db = crate::storage::lancedb_connection(
+            "memory",
+            std::path::Path::new(db_path),
+            &main_config.storage,
+        )
+        .read_consistency_interval(std::time::Duration::ZERO)
+        .execute()
+        .await?;
+        let write_lock = WriteLock::new(
+            std::path::Path::new(db_path),
+            std::time::Duration::from_secs(config.write_lock_timeout_secs),
+        );
 
         // Get vector dimension from the embedding provider by testing with a short text
         let test_embedding = crate::embedding::generate_embedding(
             "test",
             embedding_provider.as_ref(),
             main_config.embedding.timeout_secs,
+            InputType::None,
         )
         .await?;
         let vector_dim = test_embedding.len();
@@ -268,17 +502,36 @@ impl MemoryStore {
         // Build the memories schema once — reused for every write
         let schema = Self::memories_schema(vector_dim);
 
+        // Table creation and migrations race when two servers start together
+        let init_guard = write_lock.acquire().await?;
+
         // Initialize tables (creates them if missing, adds scalar + FTS indexes)
         Self::init_tables(&db, &schema).await?;
 
         // Cache table handles — opened once, reused for the lifetime of this store
         let memories_table = db.open_table("memories").execute().await?;
         let relationships_table = db.open_table("memory_relationships").execute().await?;
+        let access_log_table = db.open_table("memory_access_log").execute().await?;
+        let recovery_table = db.open_table("memory_recovery").execute().await?;
+        let revisions_table = db.open_table("memory_revisions").execute().await?;
+
+        let model_guard = ModelGuard::open(
+            std::path::Path::new(db_path),
+            "memories",
+            &main_config.embedding.model,
+            vector_dim,
+            embedding_dimension(&memories_table.schema().await?),
+        )?;
 
-        // Migrate existing tables that pre-date the access_count / last_accessed columns.
-        // New tables created above already have them; this only adds them where missing.
-        Self::migrate_decay_columns(&memories_table).await?;
-        Self::migrate_state_column(&memories_table).await?;
+        // Upgrade tables created by older versions to the current schema. New tables
+        // created above already have every column, so the steps only add what is
+        // missing.
+        crate::schema::migrate(&db, "memory", &MIGRATIONS, |version| {
+            Self::apply_migration(version, &memories_table, &recovery_table)
+        })
+        .await?;
+        let fts_ready = Self::ensure_fts_indexes(&memories_table).await;
+        drop(init_guard);
 
         // Build relationship schema once — reused for every relationship write
         let rel_schema = Self::relationships_schema();
@@ -286,6 +539,9 @@ impl MemoryStore {
         let store = Self {
             memories_table,
             relationships_table,
+            access_log_table,
+            recovery_table,
+            revisions_table,
             schema,
             rel_schema,
             embedding_provider,
@@ -295,6 +551,9 @@ impl MemoryStore {
             reranker_integration,
             project_key,
             role,
+            write_lock,
+            model_guard,
+            fts_ready,
         };
         // Ensure optimal vector index (only during initialization, not on every store)
         store.ensure_optimal_index().await?;
@@ -302,13 +561,123 @@ impl MemoryStore {
         Ok(store)
     }
 
-    /// Add `access_count` and `last_accessed` columns to pre-existing memory tables that
-    /// were created before the decay-persistence change. New tables already have them
-    /// via the schema in `new()`. Defaults: access_count=0, last_accessed=created_at.
+    /// Create the `FTS_COLUMNS` indexes missing on `table` (tables that pre-date
+    /// tag indexing, or whose index build failed). Returns whether all of them exist;
+    /// failures are logged rather than fatal, since search still works without them.
+    async fn ensure_fts_indexes(table: &Table) -> bool {
+        let indexed: Vec<String> = match table.list_indices().await {
+            Ok(indices) => indices
+                .into_iter()
+                .filter(|idx| matches!(idx.index_type, IndexType::FTS))
+                .flat_map(|idx| idx.columns)
+                .collect(),
+            Err(e) => {
+                tracing::warn!("Cannot list memories indexes: {}", e);
+                return false;
+            }
+        };
+        let mut ready = true;
+        for column in FTS_COLUMNS {
+            if indexed.iter().any(|c| c == column) {
+                continue;
+            }
+            match table
+                .create_index(&[column], Index::FTS(Default::default()))
+                .execute()
+                .await
+            {
+                Ok(()) => tracing::info!("Created FTS index on memories.{}", column),
+                Err(e) => {
+                    tracing::warn!("Cannot create FTS index on memories.{}: {}", column, e);
+                    ready = false;
+                }
+            }
+        }
+        ready
+    }
+
+    /// Narrow `predicate` to the rows whose title, content or tags contain any of
+    /// the required `keyword:` terms of `query`, found through the FTS index instead
+    /// of fetching every row. Only keywords every match contains are pushed down
+    /// (`QueryExpr::required_keywords`); the exact boolean match, `OR` and `NOT`
+    /// included, still runs in Rust on what is left. `predicate` comes back
+    /// unchanged (a scan) without an FTS index, without required keywords, or
+    /// when the index finds nothing (e.g. only stop words) or more than
+    /// `FTS_PREFILTER_LIMIT` rows.
+    async fn push_down_keywords(&self, query: &MemoryQuery, predicate: String) -> Result<String> {
+        let Some(expr) = query.filter_expr.as_ref().filter(|_| self.fts_ready) else {
+            return Ok(predicate);
+        };
+        let keywords = expr.required_keywords();
+        if keywords.is_empty() {
+            return Ok(predicate);
+        }
+
+        let mut fts_query = self
+            .memories_table
+            .query()
+            .full_text_search(FullTextSearchQuery::new(keywords.join(" ")))
+            .select(Select::Columns(vec!["id".to_string()]))
+            .limit(FTS_PREFILTER_LIMIT + 1);
+        if !predicate.is_empty() {
+            fts_query = fts_query.only_if(predicate.clone());
+        }
+        let mut results = match fts_query.execute().await {
+            Ok(results) => results,
+            Err(e) => {
+                tracing::warn!("FTS keyword prefilter failed, scanning instead: {}", e);
+                return Ok(predicate);
+            }
+        };
+        let mut ids = Vec::new();
+        while let Some(batch) = results.try_next().await? {
+            let column = string_column(&batch, "id")?;
+            ids.extend((0..batch.num_rows()).map(|i| format!("'{}'", escape_sql(column.value(i)))));
+        }
+        if ids.is_empty() || ids.len() > FTS_PREFILTER_LIMIT {
+            return Ok(predicate);
+        }
+
+        let id_filter = format!("id IN ({})", ids.join(","));
+        Ok(if predicate.is_empty() {
+            id_filter
+        } else {
+            format!("({}) AND {}", predicate, id_filter)
+        })
+    }
+
+    /// Apply schema `version` of `MIGRATIONS`. The recovery buffer copies whole
+    /// memory rows, so it gets the memory columns too.
+    async fn apply_migration(version: u32, memories: &Table, recovery: &Table) -> Result<()> {
+        match version {
+            1 => Self::migrate_state_column(memories).await,
+            2 => {
+                for table in [memories, recovery] {
+                    Self::migrate_decay_columns(table).await?;
+                }
+                Ok(())
+            }
+            3 => {
+                for table in [memories, recovery] {
+                    Self::migrate_string_column(table, "attachments", "[]").await?;
+                    Self::migrate_string_column(table, "custom_fields", "{}").await?;
+                    Self::migrate_string_column(table, "created_by", "").await?;
+                }
+                Ok(())
+            }
+            _ => anyhow::bail!("Unknown memory schema version {}", version),
+        }
+    }
+
+    /// Add `access_count`, `last_accessed` and `decay_rate` columns to memory and
+    /// recovery tables created before the decay-persistence change. New tables already
+    /// have them via the schema in `new()`. Defaults: access_count=0,
+    /// last_accessed=created_at, decay_rate=1.0.
     async fn migrate_decay_columns(table: &Table) -> Result<()> {
         let schema = table.schema().await?;
         let has_access_count = schema.field_with_name("access_count").is_ok();
         let has_last_accessed = schema.field_with_name("last_accessed").is_ok();
+        let has_decay_rate = schema.field_with_name("decay_rate").is_ok();
 
         let mut transforms: Vec<(String, String)> = Vec::new();
         if !has_access_count {
@@ -317,19 +686,23 @@ impl MemoryStore {
         if !has_last_accessed {
             transforms.push(("last_accessed".to_string(), "created_at".to_string()));
         }
+        if !has_decay_rate {
+            transforms.push(("decay_rate".to_string(), "CAST(1.0 AS FLOAT)".to_string()));
+        }
 
         if transforms.is_empty() {
             return Ok(());
         }
 
         tracing::info!(
-            "Migrating memories table: adding {} decay column(s)",
+            "Migrating {} table: adding {} decay column(s)",
+            table.name(),
             transforms.len()
         );
         table
             .add_columns(NewColumnTransform::SqlExpressions(transforms), None)
             .await
-            .context("Failed to add decay columns to existing memories table")?;
+            .with_context(|| format!("Failed to add decay columns to {} table", table.name()))?;
         Ok(())
     }
 
@@ -355,8 +728,33 @@ impl MemoryStore {
         Ok(())
     }
 
+    /// Add a string metadata column (`attachments`, `custom_fields`, `created_by`) to
+    /// memory and recovery tables created before it existed. Legacy rows get `default`.
+    async fn migrate_string_column(table: &Table, column: &str, default: &str) -> Result<()> {
+        let schema = table.schema().await?;
+        if schema.field_with_name(column).is_ok() {
+            return Ok(());
+        }
+        tracing::info!(
+            "Migrating {} table: adding '{}' column",
+            table.name(),
+            column
+        );
+        table
+            .add_columns(
+                NewColumnTransform::SqlExpressions(vec![(
+                    column.to_string(),
+                    format!("'{}'", default),
+                )]),
+                None,
+            )
+            .await
+            .with_context(|| format!("Failed to add {} column to existing table", column))?;
+        Ok(())
+    }
+
     /// Initialize memory and relationship tables (static — called once from new())
-    async fn init_tables(db: &Connection, schema: &Arc<Schema>) -> Result<()> {
+    pub(crate) async fn init_tables(db: &Connection, schema: &Arc<Schema>) -> Result<()> {
         let table_names = db.table_names().execute().await?;
 
         // Create memories table if it doesn't exist
@@ -418,6 +816,11 @@ impl MemoryStore {
                 .execute()
                 .await
                 .context("Failed to create FTS index on memories.title")?;
+            table
+                .create_index(&["tags"], Index::FTS(Default::default()))
+                .execute()
+                .await
+                .context("Failed to create FTS index on memories.tags")?;
 
             tracing::info!("Created scalar (Bitmap/BTree) and FTS indexes on memories table");
         }
@@ -457,6 +860,34 @@ impl MemoryStore {
             tracing::info!("Created Bitmap indexes on memory_relationships table");
         }
 
+        // Create recovery buffer table if it doesn't exist (also covers DBs that pre-date it)
+        if !table_names.contains(&"memory_recovery".to_string()) {
+            db.create_empty_table("memory_recovery", Self::recovery_schema(schema))
+                .execute()
+                .await?;
+        }
+
+        // Create access log table if it doesn't exist (also covers DBs that pre-date it)
+        if !table_names.contains(&"memory_access_log".to_string()) {
+            db.create_empty_table("memory_access_log", Self::access_log_schema())
+                .execute()
+                .await?;
+
+            let log_table = db.open_table("memory_access_log").execute().await?;
+            log_table
+                .create_index(&["project_key"], Index::Bitmap(Default::default()))
+                .execute()
+                .await
+                .context("Failed to create Bitmap index on memory_access_log.project_key")?;
+        }
+
+        // Create revisions table if it doesn't exist (also covers DBs that pre-date it)
+        if !table_names.contains(&"memory_revisions".to_string()) {
+            db.create_empty_table("memory_revisions", Self::revisions_schema())
+                .execute()
+                .await?;
+        }
+
         Ok(())
     }
 
@@ -478,106 +909,553 @@ impl MemoryStore {
             &searchable_text,
             self.embedding_provider.as_ref(),
             self.main_config.embedding.timeout_secs,
+            InputType::Document,
         )
         .await?;
 
         self.store_memory_with_embedding(memory, embedding).await
     }
 
-    /// Store a memory with a pre-computed embedding (for batch operations)
-    async fn store_memory_with_embedding(
+    /// Store a memory with a pre-computed embedding (batch operations, imports)
+    pub async fn store_memory_with_embedding(
         &self,
         memory: &Memory,
         embedding: Vec<f32>,
     ) -> Result<()> {
-        // Prepare data
-        let tags_json = serde_json::to_string(&memory.metadata.tags)?;
-        let files_json = serde_json::to_string(&memory.metadata.related_files)?;
+        let batch = self.memories_batch(&[(memory, embedding.as_slice())])?;
+        self.upsert_memories(batch).await
+    }
+
+    /// Store several memories with pre-computed embeddings (imports) as one
+    /// Arrow batch in a single upsert, so either all of them are written or none is
+    pub async fn store_memories_with_embeddings(&self, rows: &[(Memory, Vec<f32>)]) -> Result<()> {
+        if rows.is_empty() {
+            return Ok(());
+        }
+        let rows: Vec<(&Memory, &[f32])> = rows.iter().map(|(m, e)| (m, e.as_slice())).collect();
+        let batch = self.memories_batch(&rows)?;
+        self.upsert_memories(batch).await
+    }
+
+    /// Store several memories, embedding them in batches bounded by
+    /// `embedding.batch_size` and `max_tokens_per_batch`, and writing them as
+    /// a single Arrow batch
+    pub async fn store_memories(&self, memories: &[Memory]) -> Result<()> {
+        if memories.is_empty() {
+            return Ok(());
+        }
+        let refs: Vec<&Memory> = memories.iter().collect();
+        let embeddings = self.embed_memories(&refs).await?;
+        let rows: Vec<(&Memory, &[f32])> = memories
+            .iter()
+            .zip(embeddings.iter().map(Vec::as_slice))
+            .collect();
+        let batch = self.memories_batch(&rows)?;
+        self.upsert_memories(batch).await
+    }
+
+    /// Store `rows` (imports) as one Arrow batch in a single upsert. Rows
+    /// without an embedding are embedded first, so nothing is written unless
+    /// every row is ready. Returns how many memories had to be embedded.
+    pub async fn store_memories_batch(&self, rows: &[(Memory, Option<Vec<f32>>)]) -> Result<usize> {
+        if rows.is_empty() {
+            return Ok(0);
+        }
+        let missing: Vec<&Memory> = rows
+            .iter()
+            .filter(|(_, embedding)| embedding.is_none())
+            .map(|(memory, _)| memory)
+            .collect();
+        let mut generated = self.embed_memories(&missing).await?.into_iter();
+        let embeddings: Vec<Vec<f32>> = rows
+            .iter()
+            .map(|(_, embedding)| match embedding {
+                Some(embedding) => Some(embedding.clone()),
+                None => generated.next(),
+            })
+            .collect::<Option<_>>()
+            .context("Embedding provider returned too few vectors")?;
+        let rows_ref: Vec<(&Memory, &[f32])> = rows
+            .iter()
+            .zip(embeddings.iter().map(Vec::as_slice))
+            .map(|((memory, _), embedding)| (memory, embedding))
+            .collect();
+        let batch = self.memories_batch(&rows_ref)?;
+        self.upsert_memories(batch).await?;
+        Ok(missing.len())
+    }
+
+    /// Document embeddings of `memories`' searchable text, in order
+    async fn embed_memories(&self, memories: &[&Memory]) -> Result<Vec<Vec<f32>>> {
+        if memories.is_empty() {
+            return Ok(Vec::new());
+        }
+        let texts: Vec<String> = memories.iter().map(|m| m.get_searchable_text()).collect();
+        if let Some(empty) = memories
+            .iter()
+            .zip(&texts)
+            .find(|(_, text)| text.trim().is_empty())
+        {
+            return Err(anyhow::anyhow!(
+                "Cannot generate embedding: searchable text of memory '{}' is empty",
+                empty.0.id
+            ));
+        }
+
+        let embeddings = crate::embedding::embed_documents(
+            texts,
+            self.embedding_provider.as_ref(),
+            &self.main_config.embedding,
+        )
+        .await?;
+        if embeddings.len() != memories.len() {
+            return Err(anyhow::anyhow!(
+                "Embedding provider returned {} vectors for {} memories",
+                embeddings.len(),
+                memories.len()
+            ));
+        }
+        Ok(embeddings)
+    }
+
+    /// Rows of the memories table for `rows`, stamped with this store's
+    /// project and role
+    fn memories_batch(&self, rows: &[(&Memory, &[f32])]) -> Result<RecordBatch> {
+        let mut vectors = Vec::with_capacity(rows.len() * self.vector_dim);
+        for (memory, embedding) in rows {
+            if embedding.len() != self.vector_dim {
+                return Err(anyhow::anyhow!(
+                    "Embedding of memory '{}' has {} dimensions; this store uses {}",
+                    memory.id,
+                    embedding.len(),
+                    self.vector_dim
+                ));
+            }
+            vectors.extend_from_slice(embedding);
+        }
+        let memories: Vec<&Memory> = rows.iter().map(|(m, _)| *m).collect();
+        let json = |f: fn(&Memory) -> serde_json::Result<String>| -> Result<StringArray> {
+            Ok(StringArray::from(
+                memories
+                    .iter()
+                    .map(|m| f(m))
+                    .collect::<serde_json::Result<Vec<_>>>()?,
+            ))
+        };
+        let strings = |f: fn(&Memory) -> String| {
+            StringArray::from(memories.iter().map(|m| f(m)).collect::<Vec<_>>())
+        };
+        let floats = |f: fn(&Memory) -> f32| {
+            Float32Array::from(memories.iter().map(|m| f(m)).collect::<Vec<_>>())
+        };
 
-        let embedding_values = Float32Array::from(embedding);
         let embedding_array = FixedSizeListArray::new(
             Arc::new(Field::new("item", DataType::Float32, true)),
             self.vector_dim as i32,
-            Arc::new(embedding_values),
+            Arc::new(Float32Array::from(vectors)),
             None,
         );
+        let project = self.project_key.as_deref().unwrap_or("default").to_string();
+        let role = self.role.clone().unwrap_or_default();
 
-        let batch = RecordBatch::try_new(
+        Ok(RecordBatch::try_new(
             self.schema.clone(),
             vec![
-                Arc::new(StringArray::from(vec![memory.id.clone()])),
-                Arc::new(StringArray::from(vec![self
-                    .project_key
-                    .as_deref()
-                    .unwrap_or("default")
-                    .to_string()])),
-                Arc::new(StringArray::from(vec![self
-                    .role
-                    .clone()
-                    .unwrap_or_default()])),
-                Arc::new(StringArray::from(vec![memory.memory_type.to_string()])),
-                Arc::new(StringArray::from(vec![memory.title.clone()])),
-                Arc::new(StringArray::from(vec![memory.content.clone()])),
-                Arc::new(StringArray::from(vec![memory.created_at.to_rfc3339()])),
-                Arc::new(StringArray::from(vec![memory.updated_at.to_rfc3339()])),
-                Arc::new(Float32Array::from(vec![memory.metadata.importance])),
-                Arc::new(Float32Array::from(vec![memory.metadata.confidence])),
-                Arc::new(StringArray::from(vec![tags_json])),
-                Arc::new(StringArray::from(vec![files_json])),
-                Arc::new(StringArray::from(vec![memory.metadata.git_commit.clone()])),
-                Arc::new(StringArray::from(vec![memory.metadata.source.to_string()])),
-                Arc::new(Int32Array::from(vec![
-                    memory.metadata.decay.access_count as i32,
-                ])),
-                Arc::new(StringArray::from(vec![memory
-                    .metadata
-                    .decay
-                    .last_accessed
-                    .to_rfc3339()])),
-                Arc::new(StringArray::from(vec![memory.metadata.state.to_string()])),
+                Arc::new(strings(|m| m.id.clone())),
+                Arc::new(StringArray::from(vec![project; rows.len()])),
+                Arc::new(StringArray::from(vec![role; rows.len()])),
+                Arc::new(strings(|m| m.memory_type.to_string())),
+                Arc::new(strings(|m| m.title.clone())),
+                Arc::new(strings(|m| m.content.clone())),
+                Arc::new(strings(|m| m.created_at.to_rfc3339())),
+                Arc::new(strings(|m| m.updated_at.to_rfc3339())),
+                Arc::new(floats(|m| m.metadata.importance)),
+                Arc::new(floats(|m| m.metadata.confidence)),
+                Arc::new(json(|m| serde_json::to_string(&m.metadata.tags))?),
+                Arc::new(json(|m| serde_json::to_string(&m.metadata.related_files))?),
+                Arc::new(StringArray::from(
+                    memories
+                        .iter()
+                        .map(|m| m.metadata.git_commit.clone())
+                        .collect::<Vec<_>>(),
+                )),
+                Arc::new(strings(|m| m.metadata.source.to_string())),
+                Arc::new(Int32Array::from(
+                    memories
+                        .iter()
+                        .map(|m| m.metadata.decay.access_count as i32)
+                        .collect::<Vec<_>>(),
+                )),
+                Arc::new(strings(|m| m.metadata.decay.last_accessed.to_rfc3339())),
+                Arc::new(floats(|m| m.metadata.decay.decay_rate)),
+                Arc::new(strings(|m| m.metadata.state.to_string())),
+                Arc::new(json(|m| serde_json::to_string(&m.metadata.attachments))?),
+                Arc::new(json(|m| serde_json::to_string(&m.metadata.custom_fields))?),
+                Arc::new(strings(|m| {
+                    m.metadata.created_by.clone().unwrap_or_default()
+                })),
                 Arc::new(embedding_array),
             ],
-        )?;
+        )?)
+    }
+
+    /// Upsert memory rows in one write
+    async fn upsert_memories(&self, batch: RecordBatch) -> Result<()> {
+        // Use merge_insert for atomic upsert (update if exists, insert if not)
+        // Key on "id" which is globally unique (UUID)
+        use arrow::record_batch::RecordBatchIterator;
+        use std::iter::once;
+        let batch_reader = RecordBatchIterator::new(once(Ok(batch)), self.schema.clone());
+        let _guard = self.write_lock.acquire().await?;
+        self.model_guard.check()?;
+        let mut merge = self.memories_table.merge_insert(&["id"]);
+        merge
+            .when_matched_update_all(None)
+            .when_not_matched_insert_all();
+        merge.execute(Box::new(batch_reader)).await?;
+
+        Ok(())
+    }
+
+    /// Update an existing memory
+    pub async fn update_memory(&self, memory: &Memory) -> Result<()> {
+        // store_memory upserts via merge_insert keyed on id, so it handles updates too.
+        self.store_memory(memory).await
+    }
+
+    /// Delete a memory by ID
+    pub async fn delete_memory(&self, memory_id: &str) -> Result<()> {
+        self.delete_memories(&[memory_id.to_string()]).await?;
+        Ok(())
+    }
+
+    /// Delete several memories as one operation. Rows are first copied into the
+    /// recovery buffer under a shared `deleted_at`, so `restore_last_deletions`
+    /// brings the whole batch back together. Returns the number of rows deleted.
+    pub async fn delete_memories(&self, memory_ids: &[String]) -> Result<usize> {
+        if memory_ids.is_empty() {
+            return Ok(0);
+        }
+        let project = escape_sql(self.project_label());
+        let id_list = memory_ids
+            .iter()
+            .map(|id| format!("'{}'", escape_sql(id)))
+            .collect::<Vec<_>>()
+            .join(",");
+        let predicate = format!("id IN ({}) AND project_key = '{}'", id_list, project);
+
+        // Stash, delete and relationship cleanup form one write
+        let _guard = self.write_lock.acquire().await?;
+        let stashed = if self.config.undo_buffer_size > 0 {
+            self.stash_for_recovery(&predicate).await?
+        } else {
+            let count = self
+                .memories_table
+                .count_rows(Some(predicate.clone()))
+                .await?;
+            self.remove_attachment_blobs(memory_ids);
+            count
+        };
+
+        self.memories_table.delete(&predicate).await?;
+
+        // Also delete any relationships involving these memories (scoped to project)
+        self.relationships_table
+            .delete(&format!(
+                "(source_id IN ({ids}) OR target_id IN ({ids})) AND project_key = '{}'",
+                project,
+                ids = id_list
+            ))
+            .await
+            .ok();
+
+        Ok(stashed)
+    }
+
+    /// Copy memory rows matching `predicate` into the recovery buffer, then trim the
+    /// buffer to `undo_buffer_size` rows for this project. Returns rows copied.
+    async fn stash_for_recovery(&self, predicate: &str) -> Result<usize> {
+        let recovery_schema = Self::recovery_schema(&self.schema);
+        let deleted_at = Utc::now().to_rfc3339();
+
+        let mut results = self
+            .memories_table
+            .query()
+            .only_if(predicate)
+            .execute()
+            .await?;
+        let mut batches = Vec::new();
+        let mut copied = 0;
+        while let Some(batch) = results.try_next().await? {
+            if batch.num_rows() == 0 {
+                continue;
+            }
+            let mut columns = Vec::with_capacity(recovery_schema.fields().len());
+            for field in self.schema.fields() {
+                let column = batch
+                    .column_by_name(field.name())
+                    .with_context(|| format!("memories row missing column {}", field.name()))?;
+                columns.push(column.clone());
+            }
+            columns.push(Arc::new(StringArray::from(vec![
+                deleted_at.as_str();
+                batch.num_rows()
+            ])));
+            copied += batch.num_rows();
+            batches.push(RecordBatch::try_new(recovery_schema.clone(), columns)?);
+        }
+        if batches.is_empty() {
+            return Ok(0);
+        }
+
+        use arrow::record_batch::RecordBatchIterator;
+        let batch_reader =
+            RecordBatchIterator::new(batches.into_iter().map(Ok), recovery_schema.clone());
+        self.recovery_table.add(batch_reader).execute().await?;
+
+        // Trim: keep only the newest `undo_buffer_size` rows for this project
+        let mut entries = self.recovery_entries().await?;
+        if entries.len() > self.config.undo_buffer_size {
+            let expired_ids: Vec<String> = entries
+                .split_off(self.config.undo_buffer_size)
+                .into_iter()
+                .map(|(id, _)| id)
+                .collect();
+            let expired: Vec<String> = expired_ids
+                .iter()
+                .map(|id| format!("'{}'", escape_sql(id)))
+                .collect();
+            self.recovery_table
+                .delete(&format!(
+                    "id IN ({}) AND project_key = '{}'",
+                    expired.join(","),
+                    escape_sql(self.project_label())
+                ))
+                .await?;
+            // Expired entries can no longer be undone, so their blobs go too
+            self.remove_attachment_blobs(&expired_ids);
+        }
+
+        Ok(copied)
+    }
+
+    /// Best-effort removal of attachment blobs for permanently deleted memories.
+    fn remove_attachment_blobs(&self, memory_ids: &[String]) {
+        match crate::storage::get_attachments_dir() {
+            Ok(root) => {
+                super::attachments::remove_attachments(&root, self.project_label(), memory_ids)
+            }
+            Err(e) => tracing::warn!("Cannot resolve attachments dir: {}", e),
+        }
+    }
+
+    /// (memory id, deleted_at) pairs in the recovery buffer for this project, newest first.
+    async fn recovery_entries(&self) -> Result<Vec<(String, String)>> {
+        let mut results = self
+            .recovery_table
+            .query()
+            .only_if(format!(
+                "project_key = '{}'",
+                escape_sql(self.project_label())
+            ))
+            .execute()
+            .await?;
+        let mut entries = Vec::new();
+        while let Some(batch) = results.try_next().await? {
+            let ids = string_column(&batch, "id")?;
+            let deleted_ats = string_column(&batch, "deleted_at")?;
+            for i in 0..batch.num_rows() {
+                entries.push((ids.value(i).to_string(), deleted_ats.value(i).to_string()));
+            }
+        }
+        // RFC3339 strings from the same clock sort chronologically
+        entries.sort_by(|a, b| b.1.cmp(&a.1));
+        Ok(entries)
+    }
+
+    /// Memories in the recovery buffer for this project with their deletion time,
+    /// newest deletion first.
+    pub async fn list_recoverable(&self) -> Result<Vec<(Memory, String)>> {
+        let mut results = self
+            .recovery_table
+            .query()
+            .only_if(format!(
+                "project_key = '{}'",
+                escape_sql(self.project_label())
+            ))
+            .execute()
+            .await?;
+        let mut recoverable = Vec::new();
+        while let Some(batch) = results.try_next().await? {
+            if batch.num_rows() == 0 {
+                continue;
+            }
+            let deleted_ats = string_column(&batch, "deleted_at")?;
+            for (i, memory) in Self::batch_to_memories(&batch)?.into_iter().enumerate() {
+                recoverable.push((memory, deleted_ats.value(i).to_string()));
+            }
+        }
+        recoverable.sort_by(|a, b| b.1.cmp(&a.1));
+        Ok(recoverable)
+    }
+
+    /// Restore the memories removed by the last `operations` delete calls (each call
+    /// shares one `deleted_at`). Rows go back into `memories` with their original
+    /// embeddings and are removed from the buffer. Relationships are not restored.
+    pub async fn restore_last_deletions(&self, operations: usize) -> Result<Vec<Memory>> {
+        let _guard = self.write_lock.acquire().await?;
+        let entries = self.recovery_entries().await?;
+        let mut stamps: Vec<&str> = Vec::new();
+        for (_, deleted_at) in &entries {
+            if !stamps.contains(&deleted_at.as_str()) {
+                stamps.push(deleted_at.as_str());
+            }
+        }
+        stamps.truncate(operations.max(1));
+        if stamps.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let stamp_list = stamps
+            .iter()
+            .map(|s| format!("'{}'", escape_sql(s)))
+            .collect::<Vec<_>>()
+            .join(",");
+        let predicate = format!(
+            "deleted_at IN ({}) AND project_key = '{}'",
+            stamp_list,
+            escape_sql(self.project_label())
+        );
+        let restored = self.reinsert_recovery_rows(&predicate).await?;
+        if !restored.is_empty() {
+            self.recovery_table.delete(&predicate).await?;
+        }
+        Ok(restored)
+    }
+
+    /// Restore these memories from the recovery buffer. A memory deleted more than
+    /// once comes back as its latest copy; older copies are dropped with it.
+    pub async fn restore_deleted(&self, memory_ids: &[String]) -> Result<Vec<Memory>> {
+        let _guard = self.write_lock.acquire().await?;
+        // Entries are newest first, so the first stamp seen per ID is its latest copy
+        let mut latest: Vec<(String, String)> = Vec::new();
+        for (id, deleted_at) in self.recovery_entries().await? {
+            if memory_ids.contains(&id) && !latest.iter().any(|(seen, _)| *seen == id) {
+                latest.push((id, deleted_at));
+            }
+        }
+        if latest.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let project = escape_sql(self.project_label());
+        let copies = latest
+            .iter()
+            .map(|(id, deleted_at)| {
+                format!(
+                    "(id = '{}' AND deleted_at = '{}')",
+                    escape_sql(id),
+                    escape_sql(deleted_at)
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(" OR ");
+        let restored = self
+            .reinsert_recovery_rows(&format!("({}) AND project_key = '{}'", copies, project))
+            .await?;
+
+        let id_list = latest
+            .iter()
+            .map(|(id, _)| format!("'{}'", escape_sql(id)))
+            .collect::<Vec<_>>()
+            .join(",");
+        self.recovery_table
+            .delete(&format!(
+                "id IN ({}) AND project_key = '{}'",
+                id_list, project
+            ))
+            .await?;
+        Ok(restored)
+    }
+
+    /// Copy recovery rows matching `predicate` back into `memories` (embeddings
+    /// included) and return them. The caller removes them from the buffer.
+    async fn reinsert_recovery_rows(&self, predicate: &str) -> Result<Vec<Memory>> {
+        let mut results = self
+            .recovery_table
+            .query()
+            .only_if(predicate)
+            .execute()
+            .await?;
+        let mut restored = Vec::new();
+        let mut batches = Vec::new();
+        while let Some(batch) = results.try_next().await? {
+            if batch.num_rows() == 0 {
+                continue;
+            }
+            restored.extend(Self::batch_to_memories(&batch)?);
+            let mut columns = Vec::with_capacity(self.schema.fields().len());
+            for field in self.schema.fields() {
+                let column = batch
+                    .column_by_name(field.name())
+                    .with_context(|| format!("recovery row missing column {}", field.name()))?;
+                columns.push(column.clone());
+            }
+            batches.push(RecordBatch::try_new(self.schema.clone(), columns)?);
+        }
+        if batches.is_empty() {
+            return Ok(Vec::new());
+        }
 
-        // Use merge_insert for atomic upsert (update if exists, insert if not)
-        // Key on "id" which is globally unique (UUID)
         use arrow::record_batch::RecordBatchIterator;
-        use std::iter::once;
-        let batch_reader = RecordBatchIterator::new(once(Ok(batch)), self.schema.clone());
+        let batch_reader =
+            RecordBatchIterator::new(batches.into_iter().map(Ok), self.schema.clone());
         let mut merge = self.memories_table.merge_insert(&["id"]);
         merge
             .when_matched_update_all(None)
             .when_not_matched_insert_all();
         merge.execute(Box::new(batch_reader)).await?;
-
-        Ok(())
-    }
-
-    /// Update an existing memory
-    pub async fn update_memory(&self, memory: &Memory) -> Result<()> {
-        // store_memory upserts via merge_insert keyed on id, so it handles updates too.
-        self.store_memory(memory).await
+        Ok(restored)
     }
 
-    /// Delete a memory by ID
-    pub async fn delete_memory(&self, memory_id: &str) -> Result<()> {
-        let id = escape_sql(memory_id);
-        let project = escape_sql(self.project_label());
-
-        self.memories_table
-            .delete(&format!("id = '{}' AND project_key = '{}'", id, project))
-            .await?;
+    /// Permanently drop recovery entries deleted before `deleted_before` (all when
+    /// `None`) together with their attachment blobs. Returns entries purged.
+    pub async fn purge_deleted(
+        &self,
+        deleted_before: Option<chrono::DateTime<Utc>>,
+    ) -> Result<usize> {
+        let _guard = self.write_lock.acquire().await?;
+        let expired: Vec<(String, String)> = self
+            .recovery_entries()
+            .await?
+            .into_iter()
+            .filter(|(_, deleted_at)| match deleted_before {
+                Some(cutoff) => {
+                    chrono::DateTime::parse_from_rfc3339(deleted_at).is_ok_and(|t| t < cutoff)
+                }
+                None => true,
+            })
+            .collect();
+        if expired.is_empty() {
+            return Ok(0);
+        }
 
-        // Also delete any relationships involving this memory (scoped to project)
-        self.relationships_table
+        let mut stamps: Vec<String> = expired
+            .iter()
+            .map(|(_, deleted_at)| format!("'{}'", escape_sql(deleted_at)))
+            .collect();
+        stamps.sort();
+        stamps.dedup();
+        self.recovery_table
             .delete(&format!(
-                "(source_id = '{}' OR target_id = '{}') AND project_key = '{}'",
-                id, id, project
+                "deleted_at IN ({}) AND project_key = '{}'",
+                stamps.join(","),
+                escape_sql(self.project_label())
             ))
-            .await
-            .ok();
-
-        Ok(())
+            .await?;
+        let ids: Vec<String> = expired.iter().map(|(id, _)| id.clone()).collect();
+        self.remove_attachment_blobs(&ids);
+        Ok(expired.len())
     }
 
     /// Periodic ingest-time maintenance. Combines:
@@ -594,7 +1472,8 @@ impl MemoryStore {
     /// scanned alongside the indexed region. Per LanceDB docs:
     /// <https://lancedb.com/docs/indexing/reindexing/>
     pub async fn run_maintenance(&self) -> Result<()> {
-        self.ensure_optimal_index().await?;
+        let _guard = self.write_lock.acquire().await?;
+        self.build_vector_index_if_needed().await?;
         // OptimizeAction::All = Compact + Index incremental + Prune. The
         // Index part is the one that absorbs the unindexed delta into the
         // existing IVF index without retraining. Compact merges small files.
@@ -604,6 +1483,12 @@ impl MemoryStore {
 
     /// Ensure optimal vector index for memories table (call periodically, not on every store)
     pub async fn ensure_optimal_index(&self) -> Result<()> {
+        let _guard = self.write_lock.acquire().await?;
+        self.build_vector_index_if_needed().await
+    }
+
+    /// Body of `ensure_optimal_index`; the caller holds the write lock.
+    async fn build_vector_index_if_needed(&self) -> Result<()> {
         use crate::vector_optimizer::VectorOptimizer;
 
         let row_count = self.memories_table.count_rows(None).await?;
@@ -644,6 +1529,55 @@ impl MemoryStore {
         Ok(())
     }
 
+    /// Drop and rebuild the memories vector index with parameters sized to
+    /// the current row count, regardless of growth thresholds.
+    pub async fn rebuild_vector_index(&self) -> Result<crate::vector_optimizer::IndexRebuild> {
+        let _guard = self.write_lock.acquire().await?;
+        crate::vector_optimizer::rebuild_vector_index(&self.memories_table, self.vector_dim).await
+    }
+
+    /// Compact every table and prune versions older than `keep`. The tables
+    /// are shared by all projects, so this covers the whole database.
+    pub async fn compact(
+        &self,
+        keep: chrono::Duration,
+    ) -> Result<Vec<crate::vector_optimizer::TableCompaction>> {
+        let _guard = self.write_lock.acquire().await?;
+        let mut compactions = Vec::new();
+        for table in [
+            &self.memories_table,
+            &self.relationships_table,
+            &self.access_log_table,
+            &self.recovery_table,
+            &self.revisions_table,
+        ] {
+            compactions.push(crate::vector_optimizer::compact_table(table, keep).await?);
+        }
+        Ok(compactions)
+    }
+
+    /// IDs in this project starting with `prefix`, at most `limit` of them.
+    /// `prefix` must already be validated as an ID fragment (hex and hyphens).
+    pub async fn memory_ids_with_prefix(&self, prefix: &str, limit: usize) -> Result<Vec<String>> {
+        let mut predicate = format!("id LIKE '{}%'", escape_sql(prefix));
+        if let Some(key) = self.project_key.as_deref() {
+            predicate.push_str(&format!(" AND project_key = '{}'", escape_sql(key)));
+        }
+        let mut results = self
+            .memories_table
+            .query()
+            .only_if(predicate)
+            .limit(limit)
+            .execute()
+            .await?;
+        let mut ids = Vec::new();
+        while let Some(batch) = results.try_next().await? {
+            let column = string_column(&batch, "id")?;
+            ids.extend((0..batch.num_rows()).map(|i| column.value(i).to_string()));
+        }
+        Ok(ids)
+    }
+
     /// Get a memory by ID
     pub async fn get_memory(&self, memory_id: &str) -> Result<Option<Memory>> {
         let id = escape_sql(memory_id);
@@ -660,7 +1594,7 @@ impl MemoryStore {
 
         while let Some(batch) = results.try_next().await? {
             if batch.num_rows() > 0 {
-                let memories = self.batch_to_memories(&batch)?;
+                let memories = Self::batch_to_memories(&batch)?;
                 return Ok(memories.into_iter().next());
             }
         }
@@ -693,7 +1627,11 @@ impl MemoryStore {
         };
 
         // Fetch candidates from the appropriate search path
-        let candidates = if self.main_config.search.hybrid.enabled && query.query_text.is_some() {
+        // Hybrid needs the FTS indexes; without them it degrades to vector search
+        let candidates = if self.main_config.search.hybrid.enabled
+            && self.fts_ready
+            && query.query_text.is_some()
+        {
             // Hybrid path: when reranker is active, fetch more candidates so it has
             // enough material to rerank; otherwise use the normal hybrid limit.
             let mut hybrid_query = self.convert_to_hybrid_query(query);
@@ -715,7 +1653,8 @@ impl MemoryStore {
         } else {
             // Standard vector search, no reranker
             let results = self.vector_search(query).await?;
-            self.record_accesses_best_effort(&results).await;
+            self.record_accesses_best_effort(&results, query.query_text.as_deref())
+                .await;
             return Ok(results);
         };
 
@@ -737,7 +1676,8 @@ impl MemoryStore {
                 candidates
             };
 
-        self.record_accesses_best_effort(&final_results).await;
+        self.record_accesses_best_effort(&final_results, query.query_text.as_deref())
+            .await;
         Ok(final_results)
     }
 
@@ -747,15 +1687,168 @@ impl MemoryStore {
     /// silently missing one access tick.
     ///
     /// Uses LanceDB partial column update so the embedding column is never rewritten —
-    /// no re-embedding cost on the read path.
-    async fn record_accesses_best_effort(&self, results: &[MemorySearchResult]) {
+    /// no re-embedding cost on the read path. Text searches are additionally appended to
+    /// the access log together with the query that surfaced each memory.
+    async fn record_accesses_best_effort(
+        &self,
+        results: &[MemorySearchResult],
+        query_text: Option<&str>,
+    ) {
         if results.is_empty() {
             return;
         }
         let ids: Vec<&str> = results.iter().map(|r| r.memory.id.as_str()).collect();
-        if let Err(e) = self.record_accesses(&ids).await {
+        // Never make a search wait on another process's write; stats are best-effort
+        let guard = match self.write_lock.try_acquire() {
+            Ok(Some(guard)) => guard,
+            Ok(None) => {
+                tracing::debug!("memory database busy, skipping access stats for this search");
+                return;
+            }
+            Err(e) => {
+                tracing::warn!("write lock unavailable (search still succeeded): {}", e);
+                return;
+            }
+        };
+        if let Err(e) = self.record_accesses(&guard, &ids).await {
             tracing::warn!("record_accesses failed (search still succeeded): {}", e);
         }
+        if let Some(query_text) = query_text {
+            if let Err(e) = self.append_access_log(&guard, &ids, query_text).await {
+                tracing::warn!("access log write failed (search still succeeded): {}", e);
+            }
+        }
+    }
+
+    /// Append one access-log row per returned memory; `_guard` proves the write lock is held.
+    async fn append_access_log(
+        &self,
+        _guard: &WriteGuard,
+        ids: &[&str],
+        query_text: &str,
+    ) -> Result<()> {
+        let now = Utc::now().to_rfc3339();
+        let schema = Self::access_log_schema();
+        let batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![
+                Arc::new(StringArray::from(
+                    ids.iter()
+                        .map(|_| uuid::Uuid::new_v4().to_string())
+                        .collect::<Vec<_>>(),
+                )),
+                Arc::new(StringArray::from(vec![self.project_label(); ids.len()])),
+                Arc::new(StringArray::from(vec![self.role.as_deref(); ids.len()])),
+                Arc::new(StringArray::from(ids.to_vec())),
+                Arc::new(StringArray::from(vec![query_text; ids.len()])),
+                Arc::new(StringArray::from(vec![now.as_str(); ids.len()])),
+            ],
+        )?;
+
+        use arrow::record_batch::RecordBatchIterator;
+        use std::iter::once;
+        let batch_reader = RecordBatchIterator::new(once(Ok(batch)), schema);
+        self.access_log_table.add(batch_reader).execute().await?;
+        Ok(())
+    }
+
+    /// Access-log entries recorded at or after `since`, scoped to this store's project/role.
+    pub async fn get_access_log(
+        &self,
+        since: chrono::DateTime<Utc>,
+    ) -> Result<Vec<super::types::AccessLogEntry>> {
+        let mut parts = vec![format!("accessed_at >= '{}'", since.to_rfc3339())];
+        if let Some(key) = self.project_key.as_deref() {
+            parts.push(format!("project_key = '{}'", escape_sql(key)));
+        }
+        if let Some(role) = self.role.as_deref() {
+            parts.push(format!("role = '{}'", escape_sql(role)));
+        }
+
+        let mut results = self
+            .access_log_table
+            .query()
+            .only_if(parts.join(" AND "))
+            .execute()
+            .await?;
+
+        let mut entries = Vec::new();
+        while let Some(batch) = results.try_next().await? {
+            let memory_ids = string_column(&batch, "memory_id")?;
+            let queries = string_column(&batch, "query")?;
+            let accessed_ats = string_column(&batch, "accessed_at")?;
+            for i in 0..batch.num_rows() {
+                let Ok(accessed_at) = chrono::DateTime::parse_from_rfc3339(accessed_ats.value(i))
+                else {
+                    continue;
+                };
+                entries.push(super::types::AccessLogEntry {
+                    memory_id: memory_ids.value(i).to_string(),
+                    query: queries.value(i).to_string(),
+                    accessed_at: accessed_at.with_timezone(&Utc),
+                });
+            }
+        }
+        Ok(entries)
+    }
+
+    /// Saved revisions of `memory_id` in this store's project, oldest first.
+    pub async fn get_revisions(&self, memory_id: &str) -> Result<Vec<MemoryRevision>> {
+        let mut parts = vec![format!("memory_id = '{}'", escape_sql(memory_id))];
+        if let Some(key) = self.project_key.as_deref() {
+            parts.push(format!("project_key = '{}'", escape_sql(key)));
+        }
+
+        let mut results = self
+            .revisions_table
+            .query()
+            .only_if(parts.join(" AND "))
+            .execute()
+            .await?;
+
+        let mut revisions = Vec::new();
+        while let Some(batch) = results.try_next().await? {
+            let numbers = i32_column(&batch, "revision")?;
+            let replaced_ats = string_column(&batch, "replaced_at")?;
+            let memories = string_column(&batch, "memory")?;
+            for i in 0..batch.num_rows() {
+                revisions.push(MemoryRevision {
+                    memory_id: memory_id.to_string(),
+                    revision: numbers.value(i).max(0) as u32,
+                    replaced_at: chrono::DateTime::parse_from_rfc3339(replaced_ats.value(i))?
+                        .with_timezone(&Utc),
+                    memory: serde_json::from_str(memories.value(i))
+                        .context("Corrupt memory revision")?,
+                });
+            }
+        }
+        revisions.sort_by_key(|r| r.revision);
+        Ok(revisions)
+    }
+
+    /// Append one revision row under this store's project.
+    pub async fn append_revision(&self, revision: &MemoryRevision) -> Result<()> {
+        let schema = Self::revisions_schema();
+        let batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![
+                Arc::new(StringArray::from(vec![uuid::Uuid::new_v4().to_string()])),
+                Arc::new(StringArray::from(vec![self.project_label()])),
+                Arc::new(StringArray::from(vec![revision.memory_id.as_str()])),
+                Arc::new(Int32Array::from(vec![revision.revision as i32])),
+                Arc::new(StringArray::from(vec![revision.replaced_at.to_rfc3339()])),
+                Arc::new(StringArray::from(vec![serde_json::to_string(
+                    &revision.memory,
+                )?])),
+            ],
+        )?;
+
+        use arrow::record_batch::RecordBatchIterator;
+        use std::iter::once;
+        let batch_reader = RecordBatchIterator::new(once(Ok(batch)), schema);
+        let _guard = self.write_lock.acquire().await?;
+        self.revisions_table.add(batch_reader).execute().await?;
+        Ok(())
     }
 
     /// Apply a lifecycle transition + importance change to one memory without
@@ -772,6 +1865,7 @@ impl MemoryStore {
         let predicate = format!("id = '{}' AND project_key = '{}'", id_escaped, project);
         let clamped = new_importance.clamp(0.0, 1.0);
 
+        let _guard = self.write_lock.acquire().await?;
         self.memories_table
             .update()
             .only_if(predicate)
@@ -784,8 +1878,9 @@ impl MemoryStore {
     }
 
     /// Bump access_count and last_accessed for the given memory IDs.
-    /// Partial update: embedding column is untouched.
-    async fn record_accesses(&self, ids: &[&str]) -> Result<()> {
+    /// Partial update: embedding column is untouched. Like every other write it
+    /// runs under the write lock, which `_guard` proves the caller holds.
+    async fn record_accesses(&self, _guard: &WriteGuard, ids: &[&str]) -> Result<()> {
         if ids.is_empty() {
             return Ok(());
         }
@@ -825,12 +1920,14 @@ impl MemoryStore {
         // Build scalar filter predicate for pushdown (tags/related_files stay in Rust)
         let predicate =
             build_scalar_predicate(self.project_key.as_deref(), self.role.as_deref(), query);
+        let predicate = self.push_down_keywords(query, predicate).await?;
 
         if let Some(ref query_text) = query.query_text {
             let raw_embedding = crate::embedding::generate_embedding(
                 query_text,
                 self.embedding_provider.as_ref(),
                 self.main_config.embedding.timeout_secs,
+                InputType::Query,
             )
             .await?;
             let query_embedding = self
@@ -857,11 +1954,11 @@ impl MemoryStore {
                     .map(|arr| (0..arr.len()).map(|i| arr.value(i)).collect::<Vec<f32>>())
                     .unwrap_or_default();
 
-                let memories = self.batch_to_memories(&batch)?;
+                let memories = Self::batch_to_memories(&batch)?;
 
                 for (memory, distance) in memories.into_iter().zip(distance_array) {
                     // Only JSON-field filters remain here
-                    if !self.matches_json_filters(&memory, query) {
+                    if !matches_json_filters(&memory, query) {
                         continue;
                     }
 
@@ -872,10 +1969,16 @@ impl MemoryStore {
                     let final_score = vector_similarity * current_importance * trust_multiplier;
 
                     if final_score >= min_relevance {
+                        let signals = if query.explain {
+                            vector_signals(Some(vector_similarity), &memory, current_importance)
+                        } else {
+                            Vec::new()
+                        };
                         results.push(MemorySearchResult {
                             memory,
                             relevance_score: final_score,
-                            selection_reason: self.generate_selection_reason(query, final_score),
+                            selection_reason: generate_selection_reason(query, final_score),
+                            signals,
                         });
                     }
                 }
@@ -893,57 +1996,33 @@ impl MemoryStore {
                     continue;
                 }
 
-                let memories = self.batch_to_memories(&batch)?;
+                let memories = Self::batch_to_memories(&batch)?;
 
                 for memory in memories {
-                    if !self.matches_json_filters(&memory, query) {
+                    if !matches_json_filters(&memory, query) {
                         continue;
                     }
 
                     let relevance_score = self.current_importance(&memory);
 
                     if relevance_score >= min_relevance {
+                        let signals = if query.explain {
+                            vector_signals(None, &memory, relevance_score)
+                        } else {
+                            Vec::new()
+                        };
                         results.push(MemorySearchResult {
                             memory,
                             relevance_score,
-                            selection_reason: self
-                                .generate_selection_reason(query, relevance_score),
+                            selection_reason: generate_selection_reason(query, relevance_score),
+                            signals,
                         });
                     }
                 }
             }
         }
 
-        // Sort
-        if let Some(sort_by) = &query.sort_by {
-            let sort_order = query
-                .sort_order
-                .as_ref()
-                .unwrap_or(&super::types::SortOrder::Descending);
-
-            results.sort_by(|a, b| {
-                let ordering = match sort_by {
-                    super::types::MemorySortBy::CreatedAt => {
-                        a.memory.created_at.cmp(&b.memory.created_at)
-                    }
-                    super::types::MemorySortBy::Importance => {
-                        let a_imp = self.current_importance(&a.memory);
-                        let b_imp = self.current_importance(&b.memory);
-                        a_imp
-                            .partial_cmp(&b_imp)
-                            .unwrap_or(std::cmp::Ordering::Equal)
-                    }
-                };
-
-                match sort_order {
-                    super::types::SortOrder::Ascending => ordering,
-                    super::types::SortOrder::Descending => ordering.reverse(),
-                }
-            });
-        } else {
-            super::types::sort_by_relevance_desc(&mut results);
-        }
-
+        sort_search_results(&mut results, query, |m| self.current_importance(m));
         results.truncate(limit);
         Ok(results)
     }
@@ -1073,6 +2152,7 @@ impl MemoryStore {
             query_text,
             self.embedding_provider.as_ref(),
             self.main_config.embedding.timeout_secs,
+            InputType::Query,
         )
         .await?;
 
@@ -1082,6 +2162,7 @@ impl MemoryStore {
             self.role.as_deref(),
             &query.filters,
         );
+        let predicate = self.push_down_keywords(&query.filters, predicate).await?;
 
         let query_embedding = self
             .expand_query_embedding(raw_embedding, &predicate)
@@ -1122,11 +2203,11 @@ impl MemoryStore {
                 })
                 .unwrap_or_else(|| vec![0.5; batch.num_rows()]);
 
-            let memories = self.batch_to_memories(&batch)?;
+            let memories = Self::batch_to_memories(&batch)?;
 
             for (memory, rrf_score) in memories.into_iter().zip(rrf_scores) {
                 // JSON-field filters (tags, related_files) applied post-fetch
-                if !self.matches_json_filters(&memory, &query.filters) {
+                if !matches_json_filters(&memory, &query.filters) {
                     continue;
                 }
 
@@ -1146,10 +2227,27 @@ impl MemoryStore {
                         "Hybrid: rrf={:.2}, recency={:.2}, importance={:.2}, final={:.2}",
                         rrf_score, recency_score, importance_score, final_score
                     );
+                    let signals = if query.filters.explain {
+                        hybrid_signals(
+                            &memory,
+                            query_text,
+                            SearchSignal::Fusion {
+                                score: rrf_score,
+                                weight: query.vector_weight,
+                                vector_rank: None,
+                                keyword_rank: None,
+                            },
+                            (recency_score, query.recency_weight),
+                            (importance_score, query.importance_weight),
+                        )
+                    } else {
+                        Vec::new()
+                    };
                     results.push(super::types::MemorySearchResult {
                         memory,
                         relevance_score: final_score,
                         selection_reason,
+                        signals,
                     });
                 }
             }
@@ -1189,32 +2287,40 @@ impl MemoryStore {
             if batch.num_rows() == 0 {
                 continue;
             }
-            memories.extend(self.batch_to_memories(&batch)?);
+            memories.extend(Self::batch_to_memories(&batch)?);
         }
         Ok(memories)
     }
 
     /// Store a memory relationship
     pub async fn store_relationship(&self, relationship: &MemoryRelationship) -> Result<()> {
+        self.store_relationships(std::slice::from_ref(relationship))
+            .await
+    }
+
+    /// Store several relationships as one Arrow batch in a single upsert, so
+    /// either all of them are written or none is
+    pub async fn store_relationships(&self, relationships: &[MemoryRelationship]) -> Result<()> {
+        if relationships.is_empty() {
+            return Ok(());
+        }
+        let project = self.project_key.as_deref().unwrap_or("default").to_string();
+        let strings = |f: fn(&MemoryRelationship) -> String| {
+            StringArray::from(relationships.iter().map(f).collect::<Vec<_>>())
+        };
         let batch = RecordBatch::try_new(
             self.rel_schema.clone(),
             vec![
-                Arc::new(StringArray::from(vec![relationship.id.clone()])),
-                Arc::new(StringArray::from(vec![relationship.source_id.clone()])),
-                Arc::new(StringArray::from(vec![relationship.target_id.clone()])),
-                Arc::new(StringArray::from(vec![self
-                    .project_key
-                    .as_deref()
-                    .unwrap_or("default")
-                    .to_string()])),
-                Arc::new(StringArray::from(vec![relationship
-                    .relationship_type
-                    .to_string()])),
-                Arc::new(Float32Array::from(vec![relationship.strength])),
-                Arc::new(StringArray::from(vec![relationship.description.clone()])),
-                Arc::new(StringArray::from(vec![relationship
-                    .created_at
-                    .to_rfc3339()])),
+                Arc::new(strings(|r| r.id.clone())),
+                Arc::new(strings(|r| r.source_id.clone())),
+                Arc::new(strings(|r| r.target_id.clone())),
+                Arc::new(StringArray::from(vec![project; relationships.len()])),
+                Arc::new(strings(|r| r.relationship_type.to_string())),
+                Arc::new(Float32Array::from(
+                    relationships.iter().map(|r| r.strength).collect::<Vec<_>>(),
+                )),
+                Arc::new(strings(|r| r.description.clone())),
+                Arc::new(strings(|r| r.created_at.to_rfc3339())),
             ],
         )?;
 
@@ -1223,6 +2329,7 @@ impl MemoryStore {
         use arrow::record_batch::RecordBatchIterator;
         use std::iter::once;
         let batch_reader = RecordBatchIterator::new(once(Ok(batch)), self.rel_schema.clone());
+        let _guard = self.write_lock.acquire().await?;
         let mut merge = self.relationships_table.merge_insert(&["id"]);
         merge
             .when_matched_update_all(None)
@@ -1267,9 +2374,28 @@ impl MemoryStore {
         Ok(relationships)
     }
 
+    /// Every relationship in this store's project scope (all projects when project_key is None)
+    pub async fn get_all_relationships(&self) -> Result<Vec<MemoryRelationship>> {
+        let mut q = self.relationships_table.query();
+        if let Some(key) = self.project_key.as_deref() {
+            q = q.only_if(format!("project_key = '{}'", escape_sql(key)));
+        }
+        let mut results = q.execute().await?;
+
+        let mut relationships = Vec::new();
+        while let Some(batch) = results.try_next().await? {
+            if batch.num_rows() == 0 {
+                continue;
+            }
+            relationships.extend(self.batch_to_relationships(&batch)?);
+        }
+        Ok(relationships)
+    }
+
     /// Delete all AutoLinked relationships for a memory (used before re-linking on update)
     pub async fn delete_auto_linked_relationships(&self, memory_id: &str) -> Result<()> {
         let id = escape_sql(memory_id);
+        let _guard = self.write_lock.acquire().await?;
         self.relationships_table
             .delete(&format!(
                 "(source_id = '{}' OR target_id = '{}') AND relationship_type = 'auto_linked' AND project_key = '{}'",
@@ -1280,6 +2406,30 @@ impl MemoryStore {
         Ok(())
     }
 
+    /// Delete relationships by ID within this project; returns how many were removed
+    pub async fn delete_relationships(&self, relationship_ids: &[String]) -> Result<usize> {
+        if relationship_ids.is_empty() {
+            return Ok(0);
+        }
+        let id_list = relationship_ids
+            .iter()
+            .map(|id| format!("'{}'", escape_sql(id)))
+            .collect::<Vec<_>>()
+            .join(",");
+        let predicate = format!(
+            "id IN ({}) AND project_key = '{}'",
+            id_list,
+            escape_sql(self.project_label())
+        );
+        let _guard = self.write_lock.acquire().await?;
+        let matched = self
+            .relationships_table
+            .count_rows(Some(predicate.clone()))
+            .await?;
+        self.relationships_table.delete(&predicate).await?;
+        Ok(matched)
+    }
+
     /// Get total count of memories (all projects when project_key is None)
     pub async fn get_memory_count(&self) -> Result<usize> {
         let filter = self
@@ -1330,6 +2480,77 @@ impl MemoryStore {
         Ok((projects, roles))
     }
 
+    /// Fetch every memory in this store's project/role scope without touching access
+    /// counters (unlike `search_memories`). Used by reporting.
+    pub async fn get_all_memories(&self) -> Result<Vec<Memory>> {
+        let mut parts: Vec<String> = Vec::new();
+        if let Some(key) = self.project_key.as_deref() {
+            parts.push(format!("project_key = '{}'", escape_sql(key)));
+        }
+        if let Some(role) = self.role.as_deref() {
+            parts.push(format!("role = '{}'", escape_sql(role)));
+        }
+
+        let mut q = self.memories_table.query();
+        if !parts.is_empty() {
+            q = q.only_if(parts.join(" AND "));
+        }
+        let mut results = q.execute().await?;
+
+        let mut memories = Vec::new();
+        while let Some(batch) = results.try_next().await? {
+            if batch.num_rows() == 0 {
+                continue;
+            }
+            memories.extend(Self::batch_to_memories(&batch)?);
+        }
+        Ok(memories)
+    }
+
+    pub fn embedding_dimension(&self) -> usize {
+        self.vector_dim
+    }
+
+    /// Embeddings of every memory in scope, by memory ID.
+    pub async fn get_all_embeddings(&self) -> Result<HashMap<String, Vec<f32>>> {
+        let mut parts: Vec<String> = Vec::new();
+        if let Some(key) = self.project_key.as_deref() {
+            parts.push(format!("project_key = '{}'", escape_sql(key)));
+        }
+        if let Some(role) = self.role.as_deref() {
+            parts.push(format!("role = '{}'", escape_sql(role)));
+        }
+
+        let mut q = self.memories_table.query();
+        if !parts.is_empty() {
+            q = q.only_if(parts.join(" AND "));
+        }
+        let mut results = q.execute().await?;
+
+        let mut embeddings = HashMap::new();
+        while let Some(batch) = results.try_next().await? {
+            let Some(ids) = batch
+                .column_by_name("id")
+                .and_then(|c| c.as_any().downcast_ref::<StringArray>())
+            else {
+                continue;
+            };
+            let Some(list_arr) = batch
+                .column_by_name("embedding")
+                .and_then(|c| c.as_any().downcast_ref::<FixedSizeListArray>())
+            else {
+                continue;
+            };
+            for i in 0..batch.num_rows() {
+                let vec_arr = list_arr.value(i);
+                if let Some(f32_arr) = vec_arr.as_any().downcast_ref::<Float32Array>() {
+                    embeddings.insert(ids.value(i).to_string(), f32_arr.values().to_vec());
+                }
+            }
+        }
+        Ok(embeddings)
+    }
+
     /// Get all memories that have non-empty related_files (for stale reference cleanup).
     /// Returns (id, related_files, importance) tuples to avoid loading full embeddings.
     pub async fn get_memories_with_files(&self) -> Result<Vec<Memory>> {
@@ -1353,7 +2574,7 @@ impl MemoryStore {
             if batch.num_rows() == 0 {
                 continue;
             }
-            memories.extend(self.batch_to_memories(&batch)?);
+            memories.extend(Self::batch_to_memories(&batch)?);
         }
 
         Ok(memories)
@@ -1372,23 +2593,26 @@ impl MemoryStore {
                 self.config.cleanup_min_importance
             );
 
-            // Count memories to be deleted
-            let mut count_results = self
+            // Collect the expired IDs, then delete through the recovery buffer so a
+            // cleanup can be undone from the trash like a forget
+            let mut results = self
                 .memories_table
                 .query()
-                .only_if(filter.clone())
+                .only_if(filter)
                 .execute()
                 .await?;
-
-            let mut count = 0;
-            while let Some(batch) = count_results.try_next().await? {
-                count += batch.num_rows();
+            let mut ids = Vec::new();
+            while let Some(batch) = results.try_next().await? {
+                let id_column = string_column(&batch, "id")?;
+                ids.extend((0..batch.num_rows()).map(|i| id_column.value(i).to_string()));
             }
-
-            // Delete old memories
-            self.memories_table.delete(&filter).await?;
+            if ids.is_empty() {
+                return Ok(0);
+            }
+            let count = self.delete_memories(&ids).await?;
 
             // Optimize table after deletion (compact files, prune deleted rows)
+            let _guard = self.write_lock.acquire().await?;
             self.memories_table.optimize(OptimizeAction::All).await?;
 
             Ok(count)
@@ -1398,7 +2622,7 @@ impl MemoryStore {
     }
 
     /// Convert RecordBatch to Vec<Memory>
-    fn batch_to_memories(&self, batch: &RecordBatch) -> Result<Vec<Memory>> {
+    pub(crate) fn batch_to_memories(batch: &RecordBatch) -> Result<Vec<Memory>> {
         use chrono::DateTime;
 
         let num_rows = batch.num_rows();
@@ -1421,12 +2645,17 @@ impl MemoryStore {
         let source_array = string_column_opt(batch, "source");
 
         // Decay columns are present on tables migrated by migrate_decay_columns(); fall
-        // back to defaults (count=0, last_accessed=created_at) if absent (e.g. mid-migration).
+        // back to defaults (count=0, last_accessed=created_at, rate=1.0) if absent (e.g.
+        // mid-migration).
         let access_count_array = i32_column_opt(batch, "access_count");
         let last_accessed_array = string_column_opt(batch, "last_accessed");
+        let decay_rate_array = f32_column_opt(batch, "decay_rate");
         // State column is added by migrate_state_column on existing tables; default to
         // Working if absent so legacy rows keep their normal retrieval behavior.
         let state_array = string_column_opt(batch, "state");
+        let attachments_array = string_column_opt(batch, "attachments");
+        let custom_fields_array = string_column_opt(batch, "custom_fields");
+        let created_by_array = string_column_opt(batch, "created_by");
 
         for i in 0..num_rows {
             let memory_type =
@@ -1469,13 +2698,28 @@ impl MemoryStore {
             let mut decay = super::types::MemoryDecay::new(importance);
             decay.access_count = access_count;
             decay.last_accessed = last_accessed;
+            decay.decay_rate = decay_rate_array.map(|a| a.value(i)).unwrap_or(1.0);
 
             let state = state_array
                 .map(|a| super::types::MemoryState::from(a.value(i).to_string()))
                 .unwrap_or_default();
 
+            let attachments = attachments_array
+                .filter(|a| !a.is_null(i))
+                .and_then(|a| serde_json::from_str(a.value(i)).ok())
+                .unwrap_or_default();
+            let custom_fields = custom_fields_array
+                .filter(|a| !a.is_null(i))
+                .and_then(|a| serde_json::from_str(a.value(i)).ok())
+                .unwrap_or_default();
+
+            let created_by = created_by_array
+                .filter(|a| !a.is_null(i) && !a.value(i).is_empty())
+                .map(|a| a.value(i).to_string());
+
             let metadata = super::types::MemoryMetadata {
                 git_commit,
+                created_by,
                 importance,
                 confidence: confidence_array.value(i),
                 tags,
@@ -1483,7 +2727,8 @@ impl MemoryStore {
                 source,
                 decay,
                 state,
-                ..Default::default()
+                attachments,
+                custom_fields,
             };
 
             let memory = Memory {
@@ -1543,30 +2788,6 @@ impl MemoryStore {
         Ok(relationships)
     }
 
-    /// Filter on JSON-serialized fields that cannot be pushed to LanceDB as SQL predicates.
-    /// Scalar fields (memory_type, importance, confidence, git_commit, created_at) are
-    /// handled by `build_scalar_predicate()` and pushed down via `only_if()`.
-    fn matches_json_filters(&self, memory: &Memory, query: &MemoryQuery) -> bool {
-        // tags is stored as a JSON array string — must filter in Rust
-        if let Some(ref tags) = query.tags {
-            if !tags.iter().any(|tag| memory.metadata.tags.contains(tag)) {
-                return false;
-            }
-        }
-
-        // related_files is stored as a JSON array string — must filter in Rust
-        if let Some(ref files) = query.related_files {
-            if !files
-                .iter()
-                .any(|file| memory.metadata.related_files.contains(file))
-            {
-                return false;
-            }
-        }
-
-        true
-    }
-
     /// Clear all memory data for the current project
     pub async fn clear_all_memory_data(&self) -> Result<usize> {
         // Get current counts before deletion (scoped to project)
@@ -1583,6 +2804,8 @@ impl MemoryStore {
 
         let total_deleted = memory_count + relationship_count;
 
+        let _guard = self.write_lock.acquire().await?;
+
         // Delete only this project's memories and relationships
         self.memories_table
             .delete(&format!("project_key = '{}'", project_key))
@@ -1591,6 +2814,14 @@ impl MemoryStore {
         self.relationships_table
             .delete(&format!("project_key = '{}'", project_key))
             .await?;
+
+        self.access_log_table
+            .delete(&format!("project_key = '{}'", project_key))
+            .await?;
+
+        self.revisions_table
+            .delete(&format!("project_key = '{}'", project_key))
+            .await?;
         // Optimize tables after deletion
         self.memories_table.optimize(OptimizeAction::All).await?;
         self.relationships_table
@@ -1600,69 +2831,20 @@ impl MemoryStore {
         Ok(total_deleted)
     }
 
-    /// Generate selection reason for search results
-    fn generate_selection_reason(&self, query: &MemoryQuery, relevance_score: f32) -> String {
-        let mut reasons = Vec::new();
-
-        if query.query_text.is_some() {
-            reasons.push(format!("Semantic similarity: {:.2}", relevance_score));
-        }
-
-        if query.memory_types.is_some() {
-            reasons.push("Matches memory type filter".to_string());
-        }
-
-        if query.tags.is_some() {
-            reasons.push("Contains matching tags".to_string());
-        }
-
-        if query.related_files.is_some() {
-            reasons.push("Related to specified files".to_string());
-        }
-
-        if query.git_commit.is_some() {
-            reasons.push("Matches Git commit filter".to_string());
-        }
-
-        if reasons.is_empty() {
-            "Matches search criteria".to_string()
-        } else {
-            reasons.join(", ")
-        }
-    }
-
     /// Enable reranker with optional model override.
     /// Takes `&self` (mutates via interior `Mutex`) so `MemoryStore` can be
     /// shared as `Arc<Self>` for fire-and-forget tasks.
     pub fn enable_reranker(&self, model: Option<String>) {
-        let mut guard = match self.reranker_integration.lock() {
-            Ok(g) => g,
-            Err(p) => p.into_inner(), // recover from poisoning — config-only data
-        };
-        if let Some(ref mut reranker) = *guard {
-            if let Some(m) = model {
-                reranker.config.model = m;
-            }
-            reranker.config.enabled = true;
-        } else {
-            let mut config = self.main_config.search.reranker.clone();
-            config.enabled = true;
-            if let Some(m) = model {
-                config.model = m;
-            }
-            *guard = Some(RerankerIntegration::new(config));
-        }
+        super::reranker_integration::enable_reranker(
+            &self.reranker_integration,
+            &self.main_config.search.reranker,
+            model,
+        );
     }
 
     /// Disable reranker
     pub fn disable_reranker(&self) {
-        let mut guard = match self.reranker_integration.lock() {
-            Ok(g) => g,
-            Err(p) => p.into_inner(),
-        };
-        if let Some(ref mut reranker) = *guard {
-            reranker.config.enabled = false;
-        }
+        super::reranker_integration::disable_reranker(&self.reranker_integration);
     }
 }
 
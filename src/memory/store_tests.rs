@@ -0,0 +1,150 @@
+// Copyright 2026 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#[cfg(test)]
+mod tests {
+    use super::super::store::MemoryStore;
+    use super::super::types::{Memory, MemoryConfig, MemoryType};
+    use crate::embedding::{EmbeddingProvider, InputType};
+    use async_trait::async_trait;
+
+    const DIM: usize = 16;
+
+    /// Constant embedding; these tests never rank results.
+    struct FlatEmbedding;
+
+    #[async_trait]
+    impl EmbeddingProvider for FlatEmbedding {
+        async fn generate_embedding(&self, _text: &str) -> anyhow::Result<Vec<f32>> {
+            Ok(vec![0.25; DIM])
+        }
+
+        async fn generate_embeddings_batch(
+            &self,
+            texts: Vec<String>,
+            _input_type: InputType,
+        ) -> anyhow::Result<Vec<Vec<f32>>> {
+            Ok(vec![vec![0.25; DIM]; texts.len()])
+        }
+
+        fn get_dimension(&self) -> usize {
+            DIM
+        }
+    }
+
+    async fn store(undo_buffer_size: usize) -> MemoryStore {
+        let dir = std::env::temp_dir().join(format!("octobrain_store_{}", uuid::Uuid::new_v4()));
+        let config = MemoryConfig {
+            undo_buffer_size,
+            ..Default::default()
+        };
+        let main_config = crate::config::Config {
+            embedding: Default::default(),
+            search: Default::default(),
+            memory: config.clone(),
+            knowledge: Default::default(),
+            vision: Default::default(),
+            maintenance: Default::default(),
+            server: Default::default(),
+            storage: Default::default(),
+        };
+        MemoryStore::new(
+            dir.to_str().unwrap(),
+            Some("p1".to_string()),
+            None,
+            Box::new(FlatEmbedding),
+            config,
+            main_config,
+            None,
+        )
+        .await
+        .unwrap()
+    }
+
+    async fn stored(store: &MemoryStore, titles: &[&str]) -> Vec<String> {
+        let mut ids = Vec::new();
+        for title in titles {
+            let memory = Memory::new(
+                MemoryType::Decision,
+                title.to_string(),
+                format!("{} content", title),
+                None,
+            );
+            store.store_memory(&memory).await.unwrap();
+            ids.push(memory.id);
+        }
+        ids
+    }
+
+    fn sorted(mut ids: Vec<String>) -> Vec<String> {
+        ids.sort();
+        ids
+    }
+
+    #[tokio::test]
+    async fn test_undo_restores_each_delete_as_a_batch() {
+        let store = store(10).await;
+        let ids = stored(&store, &["First", "Second", "Third"]).await;
+
+        assert_eq!(store.delete_memories(&ids[..2]).await.unwrap(), 2);
+        assert_eq!(store.delete_memories(&ids[2..]).await.unwrap(), 1);
+        assert_eq!(store.get_memory_count().await.unwrap(), 0);
+        assert_eq!(store.list_recoverable().await.unwrap().len(), 3);
+
+        // Undo goes back one delete call at a time, newest first
+        let restored = store.restore_last_deletions(1).await.unwrap();
+        assert_eq!(restored.len(), 1);
+        assert_eq!(restored[0].id, ids[2]);
+        let restored = store.restore_last_deletions(1).await.unwrap();
+        assert_eq!(
+            sorted(restored.into_iter().map(|m| m.id).collect()),
+            sorted(ids[..2].to_vec())
+        );
+
+        assert_eq!(store.get_memory_count().await.unwrap(), 3);
+        assert!(store.get_memory(&ids[0]).await.unwrap().is_some());
+        assert!(store.list_recoverable().await.unwrap().is_empty());
+        assert!(store.restore_last_deletions(1).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_recovery_buffer_trims_to_undo_buffer_size() {
+        let store = store(3).await;
+        let ids = stored(&store, &["First", "Second", "Third", "Fourth"]).await;
+
+        store.delete_memories(&ids[..2]).await.unwrap();
+        store.delete_memories(&ids[2..]).await.unwrap();
+
+        // Four rows went in; only the newest three stay recoverable
+        let recoverable = store.list_recoverable().await.unwrap();
+        assert_eq!(recoverable.len(), 3);
+        let kept: Vec<&str> = recoverable.iter().map(|(m, _)| m.id.as_str()).collect();
+        assert!(kept.contains(&ids[2].as_str()));
+        assert!(kept.contains(&ids[3].as_str()));
+
+        let restored = store.restore_last_deletions(2).await.unwrap();
+        assert_eq!(restored.len(), 3);
+        assert_eq!(store.get_memory_count().await.unwrap(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_zero_buffer_size_disables_undo() {
+        let store = store(0).await;
+        let ids = stored(&store, &["Only"]).await;
+
+        assert_eq!(store.delete_memories(&ids).await.unwrap(), 1);
+        assert!(store.list_recoverable().await.unwrap().is_empty());
+        assert!(store.restore_last_deletions(1).await.unwrap().is_empty());
+    }
+}
@@ -0,0 +1,654 @@
+// Copyright 2026 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Memory replication between machines.
+//!
+//! `octobrain serve --sync` exposes `GET /sync/changes` and `POST /sync/push`.
+//! `octobrain sync <url>` pulls memories updated since its last pull
+//! watermark, then pushes local memories updated since its last push watermark.
+//! `octobrain sync <dir>` does the same through a snapshot file in a shared
+//! folder instead of a server. Both directions merge through
+//! `MemoryManager::merge_memory`, so a memory edited on both sides since the
+//! last sync is settled by the configured conflict strategy. Relationships
+//! created since the last sync travel with the memories and are added where
+//! both ends exist. Deletions and attachment blobs are not replicated.
+//!
+//! Pushed rows keep the pushing client's timestamps, so the server also
+//! records when it received each one (`SyncReceipts`); a pull returns rows
+//! changed or received after its watermark.
+
+use anyhow::{Context, Result};
+use axum::extract::{DefaultBodyLimit, Query, State};
+use axum::http::{HeaderMap, StatusCode};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use subtle::ConstantTimeEq;
+use tokio::sync::Mutex as AsyncMutex;
+
+use crate::config::{Config, KeyScope};
+use crate::mcp::auth::presented_key;
+use crate::memory::conflict::{ConflictPrompt, ConflictStrategy};
+use crate::memory::manager::MemoryManager;
+use crate::memory::types::{Memory, MemoryRelationship};
+
+/// Environment variable holding the shared sync token for server and client.
+pub const SYNC_TOKEN_ENV: &str = "OCTOBRAIN_SYNC_TOKEN";
+
+/// Largest accepted push body; a first push carries the whole project.
+const MAX_PUSH_BYTES: usize = 256 * 1024 * 1024;
+
+/// Response of `GET /sync/changes`.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SyncChanges {
+    /// Server clock when the changes were read; the client's next pull watermark
+    pub server_time: DateTime<Utc>,
+    pub memories: Vec<Memory>,
+    #[serde(default)]
+    pub relationships: Vec<MemoryRelationship>,
+}
+
+/// Body of `POST /sync/push`.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SyncPush {
+    pub project: Option<String>,
+    /// Server time of the client's pull in this run; server copies changed
+    /// after it were not seen by the client and count as concurrent edits
+    #[serde(default)]
+    pub base: Option<DateTime<Utc>>,
+    pub memories: Vec<Memory>,
+    #[serde(default)]
+    pub relationships: Vec<MemoryRelationship>,
+}
+
+/// Outcome of applying replicated memories to a store.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SyncApplied {
+    /// Memories inserted or replaced
+    pub applied: usize,
+    /// Memories ignored because the local copy was as new or newer
+    pub skipped: usize,
+    /// Memories edited on both sides, settled by the conflict strategy
+    #[serde(default)]
+    pub conflicts: usize,
+    /// Relationships added
+    #[serde(default)]
+    pub relationships: usize,
+}
+
+/// Result of one `octobrain sync` run.
+#[derive(Debug, Default)]
+pub struct SyncReport {
+    pub pulled: SyncApplied,
+    pub pushed: SyncApplied,
+}
+
+/// Watermarks for one (remote, project) pair.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SyncWatermark {
+    pub last_pull: Option<DateTime<Utc>>,
+    pub last_push: Option<DateTime<Utc>>,
+}
+
+/// Client-side sync state, persisted as JSON (`storage::get_sync_state_path`).
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct SyncState {
+    pub remotes: HashMap<String, SyncWatermark>,
+}
+
+impl SyncState {
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let raw = std::fs::read_to_string(path)
+            .with_context(|| format!("Cannot read sync state '{}'", path.display()))?;
+        serde_json::from_str(&raw)
+            .with_context(|| format!("Sync state '{}' is corrupt", path.display()))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        std::fs::write(path, serde_json::to_string_pretty(self)?)
+            .with_context(|| format!("Cannot write sync state '{}'", path.display()))
+    }
+
+    /// State key for a remote URL and project scope.
+    pub fn key(remote: &str, project: Option<&str>) -> String {
+        format!(
+            "{}#{}",
+            remote.trim_end_matches('/'),
+            project.unwrap_or("default")
+        )
+    }
+}
+
+/// Server clock at which pushed memories and relationships were received, by
+/// memory ID / relationship ID, for one project scope.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct ProjectReceipts {
+    #[serde(default)]
+    pub memories: HashMap<String, DateTime<Utc>>,
+    #[serde(default)]
+    pub relationships: HashMap<String, DateTime<Utc>>,
+}
+
+impl ProjectReceipts {
+    /// Note that `memories` and `relationships` were received at `at`
+    pub fn record(
+        &mut self,
+        memories: &[Memory],
+        relationships: &[MemoryRelationship],
+        at: DateTime<Utc>,
+    ) {
+        for memory in memories {
+            self.memories.insert(memory.id.clone(), at);
+        }
+        for relationship in relationships {
+            self.relationships.insert(relationship.id.clone(), at);
+        }
+    }
+
+    /// What a pull with watermark `since` must return: rows edited or created
+    /// after it, or received from another client after it
+    pub fn changes_after(
+        &self,
+        memories: Vec<Memory>,
+        relationships: Vec<MemoryRelationship>,
+        since: Option<DateTime<Utc>>,
+    ) -> (Vec<Memory>, Vec<MemoryRelationship>) {
+        let Some(since) = since else {
+            return (memories, relationships);
+        };
+        let after = |stamp: Option<&DateTime<Utc>>| stamp.is_some_and(|at| *at > since);
+        (
+            memories
+                .into_iter()
+                .filter(|m| m.updated_at > since || after(self.memories.get(&m.id)))
+                .collect(),
+            relationships
+                .into_iter()
+                .filter(|r| r.created_at > since || after(self.relationships.get(&r.id)))
+                .collect(),
+        )
+    }
+}
+
+/// Server-side receive times, persisted as JSON (`storage::get_sync_receipts_path`).
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct SyncReceipts {
+    pub projects: HashMap<String, ProjectReceipts>,
+}
+
+impl SyncReceipts {
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let raw = std::fs::read_to_string(path)
+            .with_context(|| format!("Cannot read sync receipts '{}'", path.display()))?;
+        serde_json::from_str(&raw)
+            .with_context(|| format!("Sync receipts '{}' are corrupt", path.display()))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        std::fs::write(path, serde_json::to_string(self)?)
+            .with_context(|| format!("Cannot write sync receipts '{}'", path.display()))
+    }
+
+    pub fn project(&mut self, project: Option<&str>) -> &mut ProjectReceipts {
+        self.projects
+            .entry(project.unwrap_or("default").to_string())
+            .or_default()
+    }
+}
+
+// ============================================================================
+// Server
+// ============================================================================
+
+#[derive(Debug, Deserialize)]
+struct ChangesParams {
+    since: Option<DateTime<Utc>>,
+    project: Option<String>,
+}
+
+struct SyncServerState {
+    config: Config,
+    token: Option<String>,
+    /// `Interactive` has nobody to ask on the server and keeps both copies
+    strategy: ConflictStrategy,
+    /// One manager per project scope, opened on first use
+    managers: AsyncMutex<HashMap<Option<String>, Arc<AsyncMutex<MemoryManager>>>>,
+    receipts: AsyncMutex<SyncReceipts>,
+    receipts_path: PathBuf,
+}
+
+type HandlerError = (StatusCode, String);
+
+impl SyncServerState {
+    /// Accept the sync token, or a `[server].api_keys` key whose scope covers
+    /// `scope`; anything goes when neither is configured
+    fn authorize(
+        &self,
+        headers: &HeaderMap,
+        scope: KeyScope,
+    ) -> std::result::Result<(), HandlerError> {
+        let keys = &self.config.server;
+        if self.token.is_none() && keys.api_keys.is_empty() {
+            return Ok(());
+        }
+        let presented = presented_key(headers);
+        if let (Some(presented), Some(token)) = (presented, self.token.as_deref()) {
+            if bool::from(presented.as_bytes().ct_eq(token.as_bytes())) {
+                return Ok(());
+            }
+        }
+        match presented.and_then(|k| keys.find_key(k)) {
+            Some(key) if key.scope == KeyScope::ReadWrite || scope == KeyScope::Read => Ok(()),
+            Some(key) => Err((
+                StatusCode::FORBIDDEN,
+                format!("API key '{}' is read-only and cannot push", key.name),
+            )),
+            None => Err((StatusCode::UNAUTHORIZED, "Invalid sync token".to_string())),
+        }
+    }
+
+    async fn manager(
+        &self,
+        project: Option<String>,
+    ) -> std::result::Result<Arc<AsyncMutex<MemoryManager>>, HandlerError> {
+        let mut managers = self.managers.lock().await;
+        if let Some(manager) = managers.get(&project) {
+            return Ok(manager.clone());
+        }
+        let manager = MemoryManager::new(&self.config, project.clone(), None)
+            .await
+            .map_err(internal_error)?;
+        let manager = Arc::new(AsyncMutex::new(manager));
+        managers.insert(project, manager.clone());
+        Ok(manager)
+    }
+}
+
+fn internal_error(e: anyhow::Error) -> HandlerError {
+    (StatusCode::INTERNAL_SERVER_ERROR, e.to_string())
+}
+
+async fn changes_handler(
+    State(state): State<Arc<SyncServerState>>,
+    headers: HeaderMap,
+    Query(params): Query<ChangesParams>,
+) -> std::result::Result<Json<SyncChanges>, HandlerError> {
+    state.authorize(&headers, KeyScope::Read)?;
+    let manager = state.manager(params.project.clone()).await?;
+    let manager = manager.lock().await;
+    let server_time = Utc::now();
+    let memories = manager.changed_since(None).await.map_err(internal_error)?;
+    let relationships = manager
+        .relationships_since(None)
+        .await
+        .map_err(internal_error)?;
+    let (memories, relationships) = state
+        .receipts
+        .lock()
+        .await
+        .project(params.project.as_deref())
+        .changes_after(memories, relationships, params.since);
+    Ok(Json(SyncChanges {
+        server_time,
+        memories,
+        relationships,
+    }))
+}
+
+async fn push_handler(
+    State(state): State<Arc<SyncServerState>>,
+    headers: HeaderMap,
+    Json(push): Json<SyncPush>,
+) -> std::result::Result<Json<SyncApplied>, HandlerError> {
+    state.authorize(&headers, KeyScope::ReadWrite)?;
+    let manager = state.manager(push.project.clone()).await?;
+    let mut manager = manager.lock().await;
+    let mut applied = manager
+        .apply_synced(push.memories.clone(), push.base, state.strategy, None)
+        .await
+        .map_err(internal_error)?;
+    applied.relationships = manager
+        .apply_synced_relationships(push.relationships.clone())
+        .await
+        .map_err(internal_error)?;
+
+    // Recorded while the manager is held, so a pull either saw these rows or
+    // has a watermark older than their receipt
+    let mut receipts = state.receipts.lock().await;
+    receipts.project(push.project.as_deref()).record(
+        &push.memories,
+        &push.relationships,
+        Utc::now(),
+    );
+    receipts
+        .save(&state.receipts_path)
+        .map_err(internal_error)?;
+    Ok(Json(applied))
+}
+
+/// Serve the sync endpoints on `bind_addr` until the process is stopped.
+pub async fn run_sync_server(config: Config, bind_addr: &str, token: Option<String>) -> Result<()> {
+    let addr = bind_addr
+        .parse::<std::net::SocketAddr>()
+        .map_err(|e| anyhow::anyhow!("Invalid bind address '{}': {}", bind_addr, e))?;
+
+    let strategy = ConflictStrategy::parse(&config.memory.conflict_strategy)?;
+    let receipts_path = crate::storage::get_sync_receipts_path()?;
+    let state = Arc::new(SyncServerState {
+        config,
+        token,
+        strategy,
+        managers: AsyncMutex::new(HashMap::new()),
+        receipts: AsyncMutex::new(SyncReceipts::load(&receipts_path)?),
+        receipts_path,
+    });
+    let app = Router::new()
+        .route("/sync/changes", get(changes_handler))
+        .route("/sync/push", post(push_handler))
+        .layer(DefaultBodyLimit::max(MAX_PUSH_BYTES))
+        .with_state(state);
+
+    let listener = tokio::net::TcpListener::bind(&addr)
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to bind to {}: {}", addr, e))?;
+    tracing::info!("Sync server listening on {}", addr);
+
+    axum::serve(listener, app)
+        .await
+        .map_err(|e| anyhow::anyhow!("Sync server error: {}", e))?;
+    Ok(())
+}
+
+// ============================================================================
+// Client
+// ============================================================================
+
+fn with_token(request: reqwest::RequestBuilder, token: Option<&str>) -> reqwest::RequestBuilder {
+    match token {
+        Some(token) => request.bearer_auth(token),
+        None => request,
+    }
+}
+
+async fn read_json<T: serde::de::DeserializeOwned>(response: reqwest::Response) -> Result<T> {
+    let status = response.status();
+    let body = response
+        .text()
+        .await
+        .context("Failed to read sync response")?;
+    if !status.is_success() {
+        anyhow::bail!("Sync server returned {}: {}", status, body);
+    }
+    serde_json::from_str(&body).context("Sync server returned an invalid response")
+}
+
+/// Pull then push changes for `manager`'s project scope against `remote`,
+/// advancing the watermarks stored at `state_path` only after both succeed.
+/// Pulled conflicts are settled by `strategy` (asking `prompt` when interactive).
+pub async fn sync_with_remote(
+    manager: &mut MemoryManager,
+    remote: &str,
+    project: Option<String>,
+    token: Option<&str>,
+    state_path: &Path,
+    strategy: ConflictStrategy,
+    prompt: Option<&mut dyn ConflictPrompt>,
+) -> Result<SyncReport> {
+    let remote = remote.trim_end_matches('/');
+    if !remote.starts_with("http://") && !remote.starts_with("https://") {
+        anyhow::bail!(
+            "Remote must start with http:// or https://, got: {}",
+            remote
+        );
+    }
+
+    let mut state = SyncState::load(state_path)?;
+    let key = SyncState::key(remote, project.as_deref());
+    let watermark = state.remotes.get(&key).cloned().unwrap_or_default();
+
+    let client = reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(120))
+        .user_agent("Octobrain/1.0")
+        .build()?;
+
+    // Pull
+    let mut params: Vec<(&str, String)> = Vec::new();
+    if let Some(since) = watermark.last_pull {
+        params.push(("since", since.to_rfc3339()));
+    }
+    if let Some(project) = &project {
+        params.push(("project", project.clone()));
+    }
+    let url = reqwest::Url::parse_with_params(&format!("{}/sync/changes", remote), &params)?;
+    let response = with_token(client.get(url), token)
+        .send()
+        .await
+        .with_context(|| format!("Failed to reach sync server {}", remote))?;
+    let changes: SyncChanges = read_json(response).await?;
+    let pulled_versions: HashSet<(String, DateTime<Utc>)> = changes
+        .memories
+        .iter()
+        .map(|m| (m.id.clone(), m.updated_at))
+        .collect();
+    let pulled_links: HashSet<String> =
+        changes.relationships.iter().map(|r| r.id.clone()).collect();
+    // Local edits since the last push are the ones the server has not seen
+    let mut pulled = manager
+        .apply_synced(changes.memories, watermark.last_push, strategy, prompt)
+        .await?;
+    pulled.relationships = manager
+        .apply_synced_relationships(changes.relationships)
+        .await?;
+
+    // Push — versions just pulled are not echoed back; re-stamped or kept-both
+    // copies from the merge above are newer and do go out
+    let push_started = Utc::now();
+    let memories: Vec<Memory> = manager
+        .changed_since(watermark.last_push)
+        .await?
+        .into_iter()
+        .filter(|m| !pulled_versions.contains(&(m.id.clone(), m.updated_at)))
+        .collect();
+    let relationships: Vec<MemoryRelationship> = manager
+        .relationships_since(watermark.last_push)
+        .await?
+        .into_iter()
+        .filter(|r| !pulled_links.contains(&r.id))
+        .collect();
+    let pushed = if memories.is_empty() && relationships.is_empty() {
+        SyncApplied::default()
+    } else {
+        let body = serde_json::to_vec(&SyncPush {
+            project,
+            base: Some(changes.server_time),
+            memories,
+            relationships,
+        })?;
+        let response = with_token(client.post(format!("{}/sync/push", remote)), token)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .body(body)
+            .send()
+            .await
+            .with_context(|| format!("Failed to reach sync server {}", remote))?;
+        read_json(response).await?
+    };
+
+    state.remotes.insert(
+        key,
+        SyncWatermark {
+            last_pull: Some(changes.server_time),
+            last_push: Some(push_started),
+        },
+    );
+    state.save(state_path)?;
+
+    Ok(SyncReport { pulled, pushed })
+}
+
+/// Snapshot file of `project` in a sync folder
+pub fn snapshot_file_name(project: Option<&str>) -> String {
+    let label: String = project
+        .unwrap_or("default")
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    format!("octobrain-sync-{}.json", label)
+}
+
+/// Sync `manager`'s project scope through a snapshot file in shared folder
+/// `dir`: merge the file as a pull would, then rewrite it from the local store.
+pub async fn sync_with_path(
+    manager: &mut MemoryManager,
+    dir: &Path,
+    project: Option<String>,
+    state_path: &Path,
+    strategy: ConflictStrategy,
+    prompt: Option<&mut dyn ConflictPrompt>,
+) -> Result<SyncReport> {
+    std::fs::create_dir_all(dir)
+        .with_context(|| format!("Cannot create sync folder '{}'", dir.display()))?;
+    let dir = dir.canonicalize()?;
+    let file = dir.join(snapshot_file_name(project.as_deref()));
+
+    let mut state = SyncState::load(state_path)?;
+    let key = SyncState::key(&dir.to_string_lossy(), project.as_deref());
+    let watermark = state.remotes.get(&key).cloned().unwrap_or_default();
+
+    // Pull
+    let snapshot: Option<SyncChanges> = if file.exists() {
+        let raw = std::fs::read_to_string(&file)
+            .with_context(|| format!("Cannot read sync snapshot '{}'", file.display()))?;
+        Some(
+            serde_json::from_str(&raw)
+                .with_context(|| format!("Sync snapshot '{}' is corrupt", file.display()))?,
+        )
+    } else {
+        None
+    };
+    let (written_at, snapshot_memories, snapshot_links) = match snapshot {
+        Some(s) => (Some(s.server_time), s.memories, s.relationships),
+        None => (None, Vec::new(), Vec::new()),
+    };
+    let file_versions: HashMap<String, DateTime<Utc>> = snapshot_memories
+        .iter()
+        .map(|m| (m.id.clone(), m.updated_at))
+        .collect();
+    let file_links: HashSet<String> = snapshot_links.iter().map(|r| r.id.clone()).collect();
+    // Every copy that differs from ours, whatever its stamp: the writer's clock
+    // says nothing about when its edit reached the folder
+    let local_versions: HashMap<String, DateTime<Utc>> = manager
+        .changed_since(None)
+        .await?
+        .into_iter()
+        .map(|m| (m.id, m.updated_at))
+        .collect();
+    let incoming: Vec<Memory> = snapshot_memories
+        .into_iter()
+        .filter(|m| local_versions.get(&m.id) != Some(&m.updated_at))
+        .collect();
+    let mut pulled = manager
+        .apply_synced(incoming, watermark.last_push, strategy, prompt)
+        .await?;
+    pulled.relationships = manager.apply_synced_relationships(snapshot_links).await?;
+
+    // Push — the merged local state replaces the snapshot
+    let push_started = Utc::now();
+    let memories = manager.changed_since(None).await?;
+    let relationships = manager.relationships_since(None).await?;
+    let changed = memories
+        .iter()
+        .filter(|m| file_versions.get(&m.id) != Some(&m.updated_at))
+        .count();
+    let pushed = SyncApplied {
+        applied: changed,
+        skipped: memories.len() - changed,
+        conflicts: 0,
+        relationships: relationships
+            .iter()
+            .filter(|r| !file_links.contains(&r.id))
+            .count(),
+    };
+    let snapshot = SyncChanges {
+        server_time: push_started,
+        memories,
+        relationships,
+    };
+    // Write beside the file and rename, so readers never see half a snapshot
+    let partial = file.with_extension("json.partial");
+    std::fs::write(&partial, serde_json::to_vec(&snapshot)?)
+        .with_context(|| format!("Cannot write sync snapshot '{}'", partial.display()))?;
+    std::fs::rename(&partial, &file)
+        .with_context(|| format!("Cannot write sync snapshot '{}'", file.display()))?;
+
+    state.remotes.insert(
+        key,
+        SyncWatermark {
+            // The writer's clock, as a server's would be
+            last_pull: written_at.or(watermark.last_pull),
+            last_push: Some(push_started),
+        },
+    );
+    state.save(state_path)?;
+
+    Ok(SyncReport { pulled, pushed })
+}
+
+/// Relationships of `incoming` to add to a store holding `existing` and the
+/// memories `memory_ids`: ones not known by ID or by (source, target, type),
+/// linking two memories that are present
+pub fn new_relationships(
+    existing: &[MemoryRelationship],
+    incoming: Vec<MemoryRelationship>,
+    memory_ids: &HashSet<String>,
+) -> Vec<MemoryRelationship> {
+    let mut ids: HashSet<String> = existing.iter().map(|r| r.id.clone()).collect();
+    let mut links: HashSet<(String, String, String)> = existing
+        .iter()
+        .map(|r| {
+            (
+                r.source_id.clone(),
+                r.target_id.clone(),
+                r.relationship_type.to_string(),
+            )
+        })
+        .collect();
+    incoming
+        .into_iter()
+        .filter(|r| memory_ids.contains(&r.source_id) && memory_ids.contains(&r.target_id))
+        .filter(|r| {
+            ids.insert(r.id.clone())
+                && links.insert((
+                    r.source_id.clone(),
+                    r.target_id.clone(),
+                    r.relationship_type.to_string(),
+                ))
+        })
+        .collect()
+}
@@ -0,0 +1,178 @@
+// Copyright 2026 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#[cfg(test)]
+mod tests {
+    use super::super::conflict::{resolve, ConflictStrategy, Resolution};
+    use super::super::sync::{
+        new_relationships, snapshot_file_name, ProjectReceipts, SyncReceipts, SyncState,
+        SyncWatermark,
+    };
+    use super::super::types::{Memory, MemoryRelationship, MemoryType, RelationshipType};
+    use chrono::{Duration, Utc};
+    use std::collections::HashSet;
+
+    fn memory() -> Memory {
+        Memory::new(
+            MemoryType::Decision,
+            "Sync by watermark".to_string(),
+            "Replicate memories by updated_at.".to_string(),
+            None,
+        )
+    }
+
+    #[test]
+    fn test_pulled_copy_last_write_wins() {
+        let local = memory();
+        let mut newer = local.clone();
+        newer.updated_at = local.updated_at + Duration::seconds(5);
+        let mut older = local.clone();
+        older.updated_at = local.updated_at - Duration::seconds(5);
+
+        let lww = |incoming: &Memory| {
+            resolve(
+                ConflictStrategy::LastWriteWins,
+                &local,
+                incoming,
+                true,
+                None,
+            )
+            .unwrap()
+        };
+        assert_eq!(lww(&newer), Resolution::TakeIncoming);
+        assert_eq!(lww(&older), Resolution::KeepLocal);
+        assert_eq!(lww(&local.clone()), Resolution::KeepLocal);
+    }
+
+    #[test]
+    fn test_state_key_normalizes_remote() {
+        assert_eq!(
+            SyncState::key("http://host:12346/", Some("abc")),
+            SyncState::key("http://host:12346", Some("abc"))
+        );
+        assert_ne!(
+            SyncState::key("http://host:12346", Some("abc")),
+            SyncState::key("http://host:12346", None)
+        );
+    }
+
+    #[test]
+    fn test_state_round_trip() {
+        let path = std::env::temp_dir().join(format!(
+            "octobrain_sync_state_{}.json",
+            uuid::Uuid::new_v4()
+        ));
+        assert!(SyncState::load(&path).unwrap().remotes.is_empty());
+
+        let mut state = SyncState::default();
+        let watermark = SyncWatermark {
+            last_pull: Some(Utc::now()),
+            last_push: None,
+        };
+        state
+            .remotes
+            .insert(SyncState::key("http://a", None), watermark.clone());
+        state.save(&path).unwrap();
+
+        let loaded = SyncState::load(&path).unwrap();
+        assert_eq!(loaded.remotes.get("http://a#default"), Some(&watermark));
+        std::fs::remove_file(&path).ok();
+    }
+
+    fn link(id: &str, source: &str, target: &str) -> MemoryRelationship {
+        MemoryRelationship {
+            id: id.to_string(),
+            source_id: source.to_string(),
+            target_id: target.to_string(),
+            relationship_type: RelationshipType::RelatedTo,
+            strength: 0.8,
+            description: String::new(),
+            created_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_new_relationships_skip_known_and_dangling_links() {
+        let memory_ids: HashSet<String> = ["a", "b", "c"].iter().map(|s| s.to_string()).collect();
+        let existing = vec![link("r1", "a", "b")];
+        let incoming = vec![
+            link("r1", "a", "b"),
+            link("r2", "a", "b"),
+            link("r3", "b", "c"),
+            link("r4", "b", "c"),
+            link("r5", "c", "gone"),
+        ];
+        let added: Vec<String> = new_relationships(&existing, incoming, &memory_ids)
+            .into_iter()
+            .map(|r| r.id)
+            .collect();
+        assert_eq!(added, vec!["r3"]);
+    }
+
+    #[test]
+    fn test_snapshot_file_name_is_path_safe() {
+        assert_eq!(snapshot_file_name(None), "octobrain-sync-default.json");
+        assert_eq!(
+            snapshot_file_name(Some("github.com/acme/api")),
+            "octobrain-sync-github.com_acme_api.json"
+        );
+    }
+
+    #[test]
+    fn test_old_stamped_push_after_pull_is_returned() {
+        // Client B pulls at `pulled`; client A then pushes an edit it made
+        // (and stamped) an hour before that
+        let pulled = Utc::now();
+        let mut edit = memory();
+        edit.updated_at = pulled - Duration::hours(1);
+        let mut old_link = link("r1", &edit.id, "b");
+        old_link.created_at = pulled - Duration::hours(1);
+
+        let mut receipts = ProjectReceipts::default();
+        let (memories, links) =
+            receipts.changes_after(vec![edit.clone()], vec![old_link.clone()], Some(pulled));
+        assert!(memories.is_empty() && links.is_empty());
+
+        receipts.record(
+            std::slice::from_ref(&edit),
+            std::slice::from_ref(&old_link),
+            pulled + Duration::seconds(1),
+        );
+        let (memories, links) =
+            receipts.changes_after(vec![edit.clone()], vec![old_link], Some(pulled));
+        assert_eq!(memories.len(), 1);
+        assert_eq!(links.len(), 1);
+
+        // B's next pull starts from the server time of the one above
+        let (memories, _) =
+            receipts.changes_after(vec![edit], Vec::new(), Some(pulled + Duration::seconds(2)));
+        assert!(memories.is_empty());
+    }
+
+    #[test]
+    fn test_receipts_round_trip_per_project() {
+        let path =
+            std::env::temp_dir().join(format!("octobrain_receipts_{}.json", uuid::Uuid::new_v4()));
+        let mut receipts = SyncReceipts::default();
+        receipts
+            .project(Some("abc"))
+            .record(&[memory()], &[], Utc::now());
+        receipts.save(&path).unwrap();
+
+        let mut loaded = SyncReceipts::load(&path).unwrap();
+        assert_eq!(loaded.project(Some("abc")).memories.len(), 1);
+        assert!(loaded.project(None).memories.is_empty());
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,273 @@
+// Copyright 2026 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Meeting transcript ingestion (WebVTT / SRT).
+//!
+//! Cues are parsed into timestamped, speaker-attributed lines, then turned into
+//! Decision/Learning items either by keyword heuristics or, with summarization,
+//! by asking the `[search.answer]` LLM to list them per segment.
+
+use anyhow::Result;
+
+use crate::config::AnswerConfig;
+use crate::memory::llm::complete;
+use crate::memory::types::MemoryType;
+
+/// Tag added to memories extracted from a transcript.
+pub const TRANSCRIPT_TAG: &str = "transcript";
+
+/// Character budget for one summarization segment.
+pub const SEGMENT_CHARS: usize = 4000;
+
+const DECISION_MARKERS: &[&str] = &[
+    "we decided",
+    "decided to",
+    "decision is",
+    "we agreed",
+    "agreed to",
+    "let's go with",
+    "we will go with",
+    "we'll go with",
+    "action item",
+];
+
+const LEARNING_MARKERS: &[&str] = &[
+    "we learned",
+    "lesson learned",
+    "turns out",
+    "it turned out",
+    "we realized",
+    "root cause",
+    "takeaway",
+];
+
+const SUMMARY_PROMPT: &str = "Below is part of a meeting transcript. List every decision \
+that was made and every learning (lesson, discovered fact, root cause) that was stated. \
+Output one item per line, each starting with `DECISION:` or `LEARNING:` followed by a \
+self-contained sentence. Output nothing else. If there are none, output `NONE`.";
+
+/// One timed caption from a VTT/SRT file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TranscriptCue {
+    pub start: String,
+    pub end: String,
+    pub speaker: Option<String>,
+    pub text: String,
+}
+
+/// Consecutive cues grouped for summarization.
+#[derive(Debug, Clone)]
+pub struct TranscriptSegment {
+    pub start: String,
+    pub end: String,
+    pub speakers: Vec<String>,
+    /// `Speaker: text` lines
+    pub text: String,
+}
+
+/// A Decision or Learning extracted from a transcript.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TranscriptItem {
+    pub memory_type: MemoryType,
+    pub text: String,
+    pub start: String,
+    pub end: String,
+    pub speakers: Vec<String>,
+}
+
+/// Normalize `00:01:02,500` (SRT) / `01:02.500` (VTT short form) to `HH:MM:SS`.
+fn normalize_timestamp(raw: &str) -> String {
+    let main = raw.trim().split(['.', ',']).next().unwrap_or("");
+    let parts: Vec<&str> = main.split(':').collect();
+    match parts.len() {
+        2 => format!("00:{}:{}", parts[0], parts[1]),
+        _ => main.to_string(),
+    }
+}
+
+/// Split `<v Alice>text</v>` or `Alice: text` into (speaker, text).
+fn split_speaker(line: &str) -> (Option<String>, String) {
+    if let Some(rest) = line.strip_prefix("<v ") {
+        if let Some(close) = rest.find('>') {
+            let speaker = rest[..close].trim().to_string();
+            let text = rest[close + 1..].replace("</v>", "");
+            return (Some(speaker), text.trim().to_string());
+        }
+    }
+    if let Some((name, text)) = line.split_once(": ") {
+        let name = name.trim();
+        // Speaker labels are short names, not sentences that happen to contain ": "
+        if !name.is_empty() && name.len() <= 40 && name.split_whitespace().count() <= 4 {
+            return (Some(name.to_string()), text.trim().to_string());
+        }
+    }
+    (None, line.trim().to_string())
+}
+
+/// Parse WebVTT or SRT content into cues. Headers, cue numbers, NOTE blocks and
+/// cue settings are skipped.
+pub fn parse_transcript(content: &str) -> Vec<TranscriptCue> {
+    let normalized = content.replace("\r\n", "\n");
+    let mut cues = Vec::new();
+
+    for block in normalized.split("\n\n") {
+        let mut lines = block.lines().map(str::trim).filter(|l| !l.is_empty());
+        let Some(timing) = lines.by_ref().find(|l| l.contains("-->")) else {
+            continue;
+        };
+        let Some((start, rest)) = timing.split_once("-->") else {
+            continue;
+        };
+        // VTT cue settings (e.g. `align:start`) follow the end timestamp
+        let end = rest.split_whitespace().next().unwrap_or("");
+
+        let mut speaker = None;
+        let mut text_parts = Vec::new();
+        for line in lines {
+            let (line_speaker, text) = split_speaker(line);
+            if speaker.is_none() {
+                speaker = line_speaker;
+            }
+            if !text.is_empty() {
+                text_parts.push(text);
+            }
+        }
+        if text_parts.is_empty() {
+            continue;
+        }
+        cues.push(TranscriptCue {
+            start: normalize_timestamp(start),
+            end: normalize_timestamp(end),
+            speaker,
+            text: text_parts.join(" "),
+        });
+    }
+    cues
+}
+
+/// Distinct speakers in order of first appearance.
+pub fn attendees(cues: &[TranscriptCue]) -> Vec<String> {
+    let mut names: Vec<String> = Vec::new();
+    for cue in cues {
+        if let Some(speaker) = &cue.speaker {
+            if !names.contains(speaker) {
+                names.push(speaker.clone());
+            }
+        }
+    }
+    names
+}
+
+fn cue_line(cue: &TranscriptCue) -> String {
+    match &cue.speaker {
+        Some(speaker) => format!("{}: {}", speaker, cue.text),
+        None => cue.text.clone(),
+    }
+}
+
+fn build_segment(cues: &[TranscriptCue]) -> Option<TranscriptSegment> {
+    let (first, last) = (cues.first()?, cues.last()?);
+    Some(TranscriptSegment {
+        start: first.start.clone(),
+        end: last.end.clone(),
+        speakers: attendees(cues),
+        text: cues.iter().map(cue_line).collect::<Vec<_>>().join("\n"),
+    })
+}
+
+/// Group consecutive cues into segments of at most `max_chars` text.
+pub fn segment_cues(cues: &[TranscriptCue], max_chars: usize) -> Vec<TranscriptSegment> {
+    let mut segments = Vec::new();
+    let mut segment_start = 0;
+    let mut size = 0;
+
+    for (i, cue) in cues.iter().enumerate() {
+        let len = cue_line(cue).len() + 1;
+        if size + len > max_chars && i > segment_start {
+            segments.extend(build_segment(&cues[segment_start..i]));
+            segment_start = i;
+            size = 0;
+        }
+        size += len;
+    }
+    segments.extend(build_segment(&cues[segment_start..]));
+    segments
+}
+
+/// Keyword-based extraction: cues stating a decision or a learning.
+pub fn extract_items_heuristic(cues: &[TranscriptCue]) -> Vec<TranscriptItem> {
+    cues.iter()
+        .filter_map(|cue| {
+            let lower = cue.text.to_lowercase();
+            let memory_type = if DECISION_MARKERS.iter().any(|m| lower.contains(m)) {
+                MemoryType::Decision
+            } else if LEARNING_MARKERS.iter().any(|m| lower.contains(m)) {
+                MemoryType::Learning
+            } else {
+                return None;
+            };
+            Some(TranscriptItem {
+                memory_type,
+                text: cue.text.clone(),
+                start: cue.start.clone(),
+                end: cue.end.clone(),
+                speakers: cue.speaker.iter().cloned().collect(),
+            })
+        })
+        .collect()
+}
+
+/// Parse `DECISION:` / `LEARNING:` lines from an LLM summary of `segment`.
+pub fn parse_summary_items(summary: &str, segment: &TranscriptSegment) -> Vec<TranscriptItem> {
+    summary
+        .lines()
+        .filter_map(|line| {
+            let line = line.trim().trim_start_matches(['-', '*', ' ']);
+            let (memory_type, text) = if let Some(text) = line.strip_prefix("DECISION:") {
+                (MemoryType::Decision, text)
+            } else if let Some(text) = line.strip_prefix("LEARNING:") {
+                (MemoryType::Learning, text)
+            } else {
+                return None;
+            };
+            let text = text.trim();
+            if text.is_empty() {
+                return None;
+            }
+            Some(TranscriptItem {
+                memory_type,
+                text: text.to_string(),
+                start: segment.start.clone(),
+                end: segment.end.clone(),
+                speakers: segment.speakers.clone(),
+            })
+        })
+        .collect()
+}
+
+/// Ask the configured LLM to list decisions and learnings in `segment`.
+pub async fn summarize_segment(
+    config: &AnswerConfig,
+    segment: &TranscriptSegment,
+) -> Result<Vec<TranscriptItem>> {
+    let summary = complete(
+        config,
+        SUMMARY_PROMPT,
+        &segment.text,
+        "Transcript summarization",
+    )
+    .await?;
+
+    Ok(parse_summary_items(&summary, segment))
+}
@@ -0,0 +1,95 @@
+// Copyright 2026 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#[cfg(test)]
+mod tests {
+    use super::super::transcript::{
+        attendees, extract_items_heuristic, parse_summary_items, parse_transcript, segment_cues,
+    };
+    use super::super::types::MemoryType;
+
+    const VTT: &str = "WEBVTT
+
+NOTE recorded by the meeting bot
+
+1
+00:00:01.000 --> 00:00:04.500 align:start
+<v Alice>Welcome everyone, quick sync on the queue.</v>
+
+2
+00:01:10.000 --> 00:01:15.000
+<v Bob>We decided to move retries into the worker.</v>
+
+3
+01:20.000 --> 01:25.000
+<v Alice>Turns out the timeout was the root cause.</v>
+";
+
+    const SRT: &str = "1\r\n00:00:02,000 --> 00:00:05,000\r\nCarol: Let's go with Postgres.\r\n\r\n2\r\n00:00:06,000 --> 00:00:09,000\r\nNothing notable here.\r\n";
+
+    #[test]
+    fn test_parse_vtt_cues_with_speakers() {
+        let cues = parse_transcript(VTT);
+        assert_eq!(cues.len(), 3);
+        assert_eq!(cues[0].start, "00:00:01");
+        assert_eq!(cues[0].end, "00:00:04");
+        assert_eq!(cues[0].speaker.as_deref(), Some("Alice"));
+        assert_eq!(cues[1].text, "We decided to move retries into the worker.");
+        // Short VTT timestamps are padded to HH:MM:SS
+        assert_eq!(cues[2].start, "00:01:20");
+        assert_eq!(attendees(&cues), vec!["Alice", "Bob"]);
+    }
+
+    #[test]
+    fn test_parse_srt_with_colon_speaker() {
+        let cues = parse_transcript(SRT);
+        assert_eq!(cues.len(), 2);
+        assert_eq!(cues[0].speaker.as_deref(), Some("Carol"));
+        assert_eq!(cues[0].text, "Let's go with Postgres.");
+        assert_eq!(cues[1].speaker, None);
+    }
+
+    #[test]
+    fn test_heuristic_extracts_decisions_and_learnings() {
+        let items = extract_items_heuristic(&parse_transcript(VTT));
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].memory_type, MemoryType::Decision);
+        assert_eq!(items[0].speakers, vec!["Bob"]);
+        assert_eq!(items[1].memory_type, MemoryType::Learning);
+        assert_eq!(items[1].start, "00:01:20");
+    }
+
+    #[test]
+    fn test_segments_respect_char_budget() {
+        let cues = parse_transcript(VTT);
+        let segments = segment_cues(&cues, 60);
+        assert_eq!(segments.len(), 3);
+        let whole = segment_cues(&cues, 10_000);
+        assert_eq!(whole.len(), 1);
+        assert_eq!(whole[0].start, "00:00:01");
+        assert_eq!(whole[0].end, "00:01:25");
+        assert!(whole[0].text.contains("Bob: We decided"));
+    }
+
+    #[test]
+    fn test_parse_summary_items() {
+        let segment = &segment_cues(&parse_transcript(VTT), 10_000)[0];
+        let summary = "DECISION: Retries move into the worker.\n- LEARNING: Timeout caused the stalls.\nNONE\nDECISION:";
+        let items = parse_summary_items(summary, segment);
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].memory_type, MemoryType::Decision);
+        assert_eq!(items[1].text, "Timeout caused the stalls.");
+        assert_eq!(items[1].speakers, vec!["Alice", "Bob"]);
+    }
+}
@@ -13,9 +13,12 @@
 // limitations under the License.
 
 use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+use super::query_syntax::QueryExpr;
+
 /// Origin/trust tier of a memory — determines how much weight it gets in retrieval scoring.
 ///
 /// `UserConfirmed` is ground truth: the user explicitly stated, approved, or corrected this.
@@ -130,6 +133,34 @@ pub enum MemoryType {
     Goal,
 }
 
+impl MemoryType {
+    /// Every memory type, in declaration order
+    pub const ALL: [MemoryType; 22] = [
+        MemoryType::Code,
+        MemoryType::Architecture,
+        MemoryType::BugFix,
+        MemoryType::Feature,
+        MemoryType::Documentation,
+        MemoryType::UserPreference,
+        MemoryType::Decision,
+        MemoryType::Learning,
+        MemoryType::Configuration,
+        MemoryType::Testing,
+        MemoryType::Performance,
+        MemoryType::Security,
+        MemoryType::Validation,
+        MemoryType::Research,
+        MemoryType::Workflow,
+        MemoryType::Requirement,
+        MemoryType::Design,
+        MemoryType::Integration,
+        MemoryType::Communication,
+        MemoryType::Process,
+        MemoryType::Insight,
+        MemoryType::Goal,
+    ];
+}
+
 impl std::fmt::Display for MemoryType {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -317,6 +348,25 @@ pub struct MemoryMetadata {
     /// Lifecycle state — Working by default, transitions to Consolidated on goal close.
     #[serde(default)]
     pub state: MemoryState,
+    /// Files attached to this memory (blobs live under the attachments storage dir)
+    #[serde(default)]
+    pub attachments: Vec<MemoryAttachment>,
+}
+
+/// A small file (diagram, log excerpt, patch) attached to a memory.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MemoryAttachment {
+    /// Original file name
+    pub file_name: String,
+    /// Blob path relative to the attachments storage dir
+    pub path: String,
+    /// Size in bytes
+    pub size_bytes: u64,
+    /// SHA-256 of the blob contents (hex)
+    pub sha256: String,
+    /// Text extracted from UTF-8 files (truncated); included in the searchable text
+    #[serde(default)]
+    pub extracted_text: Option<String>,
 }
 
 impl Default for MemoryMetadata {
@@ -332,6 +382,7 @@ impl Default for MemoryMetadata {
             decay: MemoryDecay::new(0.5),
             source: MemorySource::AgentInferred,
             state: MemoryState::Working,
+            attachments: Vec::new(),
         }
     }
 }
@@ -400,13 +451,22 @@ impl Memory {
 
     /// Get searchable text for embedding generation
     pub fn get_searchable_text(&self) -> String {
-        format!(
+        let mut text = format!(
             "{} {} {} {}",
             self.title,
             self.content,
             self.metadata.tags.join(" "),
             self.metadata.related_files.join(" ")
-        )
+        );
+        for attachment in &self.metadata.attachments {
+            text.push(' ');
+            text.push_str(&attachment.file_name);
+            if let Some(extracted) = &attachment.extracted_text {
+                text.push(' ');
+                text.push_str(extracted);
+            }
+        }
+        text
     }
 
     /// Get current importance considering temporal decay.
@@ -480,8 +540,18 @@ pub struct MemoryQuery {
     pub tags: Option<Vec<String>>,
     /// Filter by related files
     pub related_files: Option<Vec<String>>,
+    /// Exclude memories of these types
+    pub exclude_types: Option<Vec<MemoryType>>,
+    /// Exclude memories carrying any of these tags
+    pub exclude_tags: Option<Vec<String>>,
+    /// Exclude memories related to any of these files
+    pub exclude_files: Option<Vec<String>>,
+    /// Boolean filter parsed from field clauses (`tag:`, `importance>0.6`, ...)
+    pub filter_expr: Option<QueryExpr>,
     /// Filter by git commit
     pub git_commit: Option<String>,
+    /// Filter by author (`MemoryMetadata::created_by`)
+    pub created_by: Option<String>,
     /// Filter by minimum importance score
     pub min_importance: Option<f32>,
     /// Filter by minimum confidence score
@@ -497,6 +567,8 @@ pub struct MemoryQuery {
     pub sort_by: Option<MemorySortBy>,
     /// Sort order
     pub sort_order: Option<SortOrder>,
+    /// Record what each result's score is made of in `MemorySearchResult::signals`
+    pub explain: bool,
 }
 
 /// Hybrid search query combining vector RRF fusion with recency and importance signals.
@@ -584,6 +656,48 @@ pub struct MemorySearchResult {
     pub relevance_score: f32,
     /// Explanation of why this memory was selected
     pub selection_reason: String,
+    /// Score breakdown; filled only when the query asked to `explain`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub signals: Vec<SearchSignal>,
+}
+
+/// One contribution to a search result's relevance score
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "signal", rename_all = "snake_case")]
+pub enum SearchSignal {
+    /// Cosine similarity between the query and memory embeddings
+    Vector { similarity: f32 },
+    /// Vector and keyword rankings fused with RRF, normalized to [0, 1].
+    /// Ranks are 1-based and absent when the engine reports only the fused score.
+    Fusion {
+        score: f32,
+        weight: f32,
+        vector_rank: Option<usize>,
+        keyword_rank: Option<usize>,
+    },
+    /// Query words found in the title, content or tags
+    Keyword { matches: Vec<KeywordMatch> },
+    /// `exp(-age / recency_decay_days)`
+    Recency { score: f32, weight: f32 },
+    /// Importance after decay; `weight` is `None` when it multiplies the score
+    Importance {
+        stored: f32,
+        current: f32,
+        weight: Option<f32>,
+    },
+    /// Source trust tier multiplier
+    Trust { multiplier: f32 },
+    /// Cross-encoder score that replaced the `previous` one
+    Reranker { score: f32, previous: f32 },
+    /// Multiplier from recorded helpful / unhelpful votes
+    Feedback { factor: f32 },
+}
+
+/// A query word and how often it occurs in a memory
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct KeywordMatch {
+    pub term: String,
+    pub count: usize,
 }
 
 /// Sort search results by descending relevance score.
@@ -596,6 +710,25 @@ pub(crate) fn sort_by_relevance_desc(results: &mut [MemorySearchResult]) {
     });
 }
 
+/// One access-log row: a memory returned by a text search, and the query that found it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AccessLogEntry {
+    pub memory_id: String,
+    pub query: String,
+    pub accessed_at: DateTime<Utc>,
+}
+
+/// An earlier version of a memory, saved when an edit replaced it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MemoryRevision {
+    pub memory_id: String,
+    /// 1 for the first saved version, increasing with every edit
+    pub revision: u32,
+    /// When this version was replaced
+    pub replaced_at: DateTime<Utc>,
+    pub memory: Memory,
+}
+
 /// Memory relationship between memories
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MemoryRelationship {
@@ -723,8 +856,56 @@ fn default_sleep_consolidation_max_age_days() -> u32 {
     7
 }
 
+fn default_redaction_enabled() -> bool {
+    false
+}
+
+fn default_undo_buffer_size() -> usize {
+    50
+}
+
+fn default_trash_retention_days() -> u32 {
+    30
+}
+
+fn default_write_lock_timeout_secs() -> u64 {
+    30
+}
+
+fn default_max_attachment_size_kb() -> u64 {
+    1024
+}
+
+fn default_backend() -> String {
+    "lancedb".to_string()
+}
+
+fn default_conflict_strategy() -> String {
+    "last-write-wins".to_string()
+}
+
+fn default_importance_tuning_enabled() -> bool {
+    true
+}
+
+fn default_importance_tuning_interval_hours() -> u32 {
+    24
+}
+
+fn default_importance_tuning_step() -> f32 {
+    0.05
+}
+
+fn default_importance_tuning_min_accesses() -> u32 {
+    5
+}
+
+fn default_importance_tuning_idle_days() -> u32 {
+    60
+}
+
 /// Configuration for memory system
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct MemoryConfig {
     /// Maximum number of memories to keep
     pub max_memories: Option<usize>,
@@ -778,6 +959,107 @@ pub struct MemoryConfig {
     /// Only consider Working-state memories created in the last N days.
     #[serde(default = "default_sleep_consolidation_max_age_days")]
     pub sleep_consolidation_max_age_days: u32,
+
+    /// Usage-driven importance tuning: lazily nudges base importance up for memories
+    /// that keep being retrieved and down for ones that never surface. Marker-gated
+    /// like sleep consolidation.
+    #[serde(default = "default_importance_tuning_enabled")]
+    pub importance_tuning_enabled: bool,
+    /// Hours between automatic importance-tuning passes.
+    #[serde(default = "default_importance_tuning_interval_hours")]
+    pub importance_tuning_interval_hours: u32,
+    /// Base-importance change applied per pass (raise or lower).
+    #[serde(default = "default_importance_tuning_step")]
+    pub importance_tuning_step: f32,
+    /// Minimum lifetime access count before a recently retrieved memory is raised.
+    #[serde(default = "default_importance_tuning_min_accesses")]
+    pub importance_tuning_min_accesses: u32,
+    /// Memories not retrieved for this many days are lowered.
+    #[serde(default = "default_importance_tuning_idle_days")]
+    pub importance_tuning_idle_days: u32,
+
+    /// Number of deleted memories kept (with embeddings) for `memory undo`. 0 disables.
+    #[serde(default = "default_undo_buffer_size")]
+    pub undo_buffer_size: usize,
+    /// Days a deleted memory stays in the trash before it is purged for good. 0 keeps
+    /// entries until `undo_buffer_size` pushes them out.
+    #[serde(default = "default_trash_retention_days")]
+    pub trash_retention_days: u32,
+
+    /// Seconds a write waits for another octobrain process (e.g. a second
+    /// editor's MCP server) to release the memory or knowledge write lock.
+    #[serde(default = "default_write_lock_timeout_secs")]
+    pub write_lock_timeout_secs: u64,
+
+    /// Mask emails, phone numbers and API-key shapes in title/content before embedding
+    /// and storage. Redacted memories are tagged `redacted`.
+    #[serde(default = "default_redaction_enabled")]
+    pub redaction_enabled: bool,
+    /// Extra regex patterns to redact (replaced with `[REDACTED]`).
+    #[serde(default)]
+    pub redaction_patterns: Vec<String>,
+
+    /// Largest file accepted as a memory attachment, in KiB.
+    #[serde(default = "default_max_attachment_size_kb")]
+    pub max_attachment_size_kb: u64,
+
+    /// Author recorded on new memories. Empty falls back to `git config user.email`.
+    #[serde(default)]
+    pub author: String,
+
+    /// How sync and share import settle a memory edited on both sides:
+    /// `last-write-wins`, `keep-both` or `interactive`.
+    #[serde(default = "default_conflict_strategy")]
+    pub conflict_strategy: String,
+
+    /// Fingerprints of the share keys whose bundles `memory share import` accepts.
+    /// Empty accepts any signer with a warning.
+    #[serde(default)]
+    pub trusted_share_keys: Vec<String>,
+
+    /// Safeguards for deletions requested by agents over MCP
+    #[serde(default)]
+    pub destructive_ops: DestructiveOpsConfig,
+
+    /// Storage engine for memories: `lancedb`, `sqlite` (needs the `sqlite` feature)
+    /// or `postgres` (needs the `postgres` feature; also holds knowledge chunks).
+    #[serde(default = "default_backend")]
+    pub backend: String,
+
+    /// Connection URL for the `postgres` backend; `$OCTOBRAIN_POSTGRES_URL` wins.
+    #[serde(default)]
+    pub postgres_url: String,
+}
+
+/// Policy for destructive MCP operations (`forget`).
+///
+/// A deletion touching more than `confirm_threshold` memories first returns a
+/// preview and a confirm token; the agent must repeat the call with the token.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct DestructiveOpsConfig {
+    /// Largest deletion allowed without a confirm token. 0 requires one always.
+    #[serde(default = "default_confirm_threshold")]
+    pub confirm_threshold: usize,
+    /// Seconds a confirm token stays valid
+    #[serde(default = "default_confirm_token_ttl_secs")]
+    pub token_ttl_secs: u64,
+}
+
+impl Default for DestructiveOpsConfig {
+    fn default() -> Self {
+        Self {
+            confirm_threshold: default_confirm_threshold(),
+            token_ttl_secs: default_confirm_token_ttl_secs(),
+        }
+    }
+}
+
+fn default_confirm_threshold() -> usize {
+    1
+}
+
+fn default_confirm_token_ttl_secs() -> u64 {
+    300
 }
 
 impl Default for MemoryConfig {
@@ -803,6 +1085,23 @@ impl Default for MemoryConfig {
             sleep_consolidation_threshold: 0.85,
             sleep_consolidation_min_cluster_size: 3,
             sleep_consolidation_max_age_days: 7,
+            importance_tuning_enabled: true,
+            importance_tuning_interval_hours: 24,
+            importance_tuning_step: 0.05,
+            importance_tuning_min_accesses: 5,
+            importance_tuning_idle_days: 60,
+            undo_buffer_size: 50,
+            trash_retention_days: 30,
+            write_lock_timeout_secs: 30,
+            redaction_enabled: false,
+            redaction_patterns: Vec::new(),
+            max_attachment_size_kb: 1024,
+            author: String::new(),
+            conflict_strategy: default_conflict_strategy(),
+            trusted_share_keys: Vec::new(),
+            destructive_ops: DestructiveOpsConfig::default(),
+            backend: default_backend(),
+            postgres_url: String::new(),
         }
     }
 }
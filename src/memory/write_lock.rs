@@ -0,0 +1,130 @@
+// Copyright 2026 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Cross-process single-writer lock for the memory and knowledge databases.
+//!
+//! Every editor spawns its own stdio MCP server, so several processes can open
+//! the same LanceDB directory. Reads are safe, but multi-step writes (stash +
+//! delete, re-indexing a source, index rebuilds, compaction) interleave badly.
+//! Each write takes an exclusive OS file lock on `<db>/write.lock`, retrying
+//! with backoff until the configured timeout. The lock is released when the
+//! guard drops, and by the OS if the process dies, so a crashed server never
+//! wedges the others.
+
+use anyhow::{Context, Result};
+use std::fs::{File, OpenOptions, TryLockError};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+/// Lock file name inside the database directory.
+pub const WRITE_LOCK_FILE: &str = "write.lock";
+
+const INITIAL_BACKOFF: Duration = Duration::from_millis(10);
+const MAX_BACKOFF: Duration = Duration::from_millis(250);
+
+/// Another process held the write lock past the timeout; retrying may succeed.
+#[derive(Debug)]
+pub struct WriteLockBusy {
+    pub path: PathBuf,
+    pub waited: Duration,
+}
+
+impl std::fmt::Display for WriteLockBusy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Database is busy: another octobrain process held '{}' for over {}s. \
+             Retry, or raise [memory].write_lock_timeout_secs",
+            self.path.display(),
+            self.waited.as_secs()
+        )
+    }
+}
+
+impl std::error::Error for WriteLockBusy {}
+
+/// Exclusive writer lock shared by all processes using one database directory.
+#[derive(Debug, Clone)]
+pub struct WriteLock {
+    path: PathBuf,
+    timeout: Duration,
+}
+
+/// Held while writing; dropping it releases the lock.
+#[derive(Debug)]
+pub struct WriteGuard {
+    _file: File,
+}
+
+impl WriteLock {
+    pub fn new(db_dir: &Path, timeout: Duration) -> Self {
+        Self::named(db_dir, WRITE_LOCK_FILE, timeout)
+    }
+
+    /// Lock on `<dir>/<file_name>`, for exclusive jobs other than writes.
+    pub fn named(dir: &Path, file_name: &str, timeout: Duration) -> Self {
+        Self {
+            path: dir.join(file_name),
+            timeout,
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn open(&self) -> Result<File> {
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(&self.path)
+            .with_context(|| format!("Cannot open write lock '{}'", self.path.display()))
+    }
+
+    /// Take the lock without waiting; `None` when another writer holds it.
+    pub fn try_acquire(&self) -> Result<Option<WriteGuard>> {
+        let file = self.open()?;
+        match file.try_lock() {
+            Ok(()) => Ok(Some(WriteGuard { _file: file })),
+            Err(TryLockError::WouldBlock) => Ok(None),
+            Err(TryLockError::Error(e)) => {
+                Err(e).with_context(|| format!("Cannot lock '{}'", self.path.display()))
+            }
+        }
+    }
+
+    /// Take the lock, retrying with exponential backoff until the timeout.
+    pub async fn acquire(&self) -> Result<WriteGuard> {
+        let started = Instant::now();
+        let mut backoff = INITIAL_BACKOFF;
+        loop {
+            if let Some(guard) = self.try_acquire()? {
+                return Ok(guard);
+            }
+            if started.elapsed() >= self.timeout {
+                return Err(WriteLockBusy {
+                    path: self.path().to_path_buf(),
+                    waited: self.timeout,
+                }
+                .into());
+            }
+            tokio::time::sleep(backoff).await;
+            backoff = (backoff * 2).min(MAX_BACKOFF);
+        }
+    }
+}
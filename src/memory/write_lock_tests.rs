@@ -0,0 +1,58 @@
+// Copyright 2026 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#[cfg(test)]
+mod tests {
+    use super::super::write_lock::{WriteLock, WRITE_LOCK_FILE};
+    use std::time::Duration;
+
+    fn lock(timeout_ms: u64) -> WriteLock {
+        let dir =
+            std::env::temp_dir().join(format!("octobrain_write_lock_{}", uuid::Uuid::new_v4()));
+        WriteLock::new(&dir, Duration::from_millis(timeout_ms))
+    }
+
+    #[test]
+    fn test_second_writer_is_refused_until_release() {
+        let first = lock(0);
+        let second = WriteLock::new(first.path().parent().unwrap(), Duration::from_millis(0));
+        assert!(first.path().ends_with(WRITE_LOCK_FILE));
+
+        let guard = first.try_acquire().unwrap().expect("lock is free");
+        assert!(second.try_acquire().unwrap().is_none());
+        drop(guard);
+        assert!(second.try_acquire().unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn test_acquire_times_out_while_held() {
+        let lock = lock(50);
+        let _held = lock.try_acquire().unwrap().unwrap();
+        let err = lock.acquire().await.unwrap_err();
+        assert!(err.to_string().contains("busy"));
+    }
+
+    #[tokio::test]
+    async fn test_acquire_waits_for_release() {
+        let lock = lock(5_000);
+        let held = lock.try_acquire().unwrap().unwrap();
+        let waiter = {
+            let lock = lock.clone();
+            tokio::spawn(async move { lock.acquire().await.map(|_| ()) })
+        };
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        drop(held);
+        waiter.await.unwrap().unwrap();
+    }
+}
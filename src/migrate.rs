@@ -0,0 +1,647 @@
+// Copyright 2026 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Re-embedding after `[embedding].model` changes (`octobrain migrate embeddings`).
+//!
+//! LanceDB tables hold fixed-size vectors and are stamped with the model that
+//! produced them (`ModelGuard`), so a store refuses to open once the model
+//! changes. The migration re-embeds every row into a `<table>_reembed` staging
+//! table in batches, then swaps it in: the original is dropped, recreated empty
+//! with its indexes for the new vector size, and refilled from the staging
+//! table. Rows already staged are skipped, so an interrupted run picks up where
+//! it stopped; the stamp only moves to the new model once the swap is done.
+//!
+//! SQLite and Postgres memories keep one vector per row with no fixed size;
+//! there the memories whose vectors have another length are re-embedded in place.
+
+use anyhow::{Context, Result};
+use arrow::compute::filter_record_batch;
+use arrow::record_batch::RecordBatchIterator;
+use arrow_array::{Array, BooleanArray, FixedSizeListArray, Float32Array, RecordBatch};
+use arrow_schema::{DataType, Field, Schema};
+use futures::TryStreamExt;
+use lancedb::{
+    query::{ExecutableQuery, QueryBase, Select},
+    Connection, Table,
+};
+use serde::Serialize;
+use std::collections::HashSet;
+use std::iter::once;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::arrow_helpers::{embedding_dimension, string_column};
+use crate::config::Config;
+use crate::embedding::{
+    generate_embedding, generate_embeddings_batch, record_model_stamp, recorded_model_stamp,
+    EmbeddingProvider, InputType, ModelStamp,
+};
+use crate::knowledge::store::KnowledgeStore;
+use crate::knowledge::KnowledgeManager;
+use crate::memory::backend::BackendKind;
+use crate::memory::store::MemoryStore;
+use crate::memory::write_lock::WriteLock;
+use crate::memory::MemoryManager;
+
+/// Texts embedded per provider call unless `--batch-size` says otherwise
+pub const DEFAULT_BATCH_SIZE: usize = 32;
+
+/// Suffix of the staging table a migration fills before swapping it in
+const STAGING_SUFFIX: &str = "_reembed";
+
+/// Called after every batch with the table name, rows done and rows in total
+pub type Progress<'a> = &'a mut (dyn FnMut(&str, usize, usize) + Send);
+
+/// What `migrate embeddings` did (or, with `check`, would do) to one table
+#[derive(Debug, Clone, Serialize)]
+pub struct TableMigration {
+    pub table: String,
+    /// Model the stored vectors were built with, when it was recorded
+    pub from: Option<ModelStamp>,
+    pub rows: usize,
+    /// Rows embedded by this run (still to embed, with `check`)
+    pub reembedded: usize,
+    /// A staging table left by an interrupted run was continued
+    pub resumed: bool,
+}
+
+/// Re-embed every store whose vectors were built with another model than
+/// `[embedding].model`. Returns one entry per migrated table; empty when all
+/// stores are up to date. With `check` nothing is written.
+pub async fn migrate_embeddings(
+    config: &Config,
+    batch_size: usize,
+    check: bool,
+    progress: Progress<'_>,
+) -> Result<Vec<TableMigration>> {
+    let backend = BackendKind::parse(&config.memory.backend)?;
+    if backend == BackendKind::Ephemeral {
+        anyhow::bail!("Nothing to migrate: --ephemeral stores are never persisted");
+    }
+    if batch_size == 0 {
+        anyhow::bail!("--batch-size must be at least 1");
+    }
+
+    let provider = crate::embedding::create_embedding_provider(config).await?;
+    let dimension = generate_embedding(
+        "test",
+        provider.as_ref(),
+        config.embedding.timeout_secs,
+        InputType::None,
+    )
+    .await?
+    .len();
+    let target = ModelStamp {
+        model: config.embedding.model.trim().to_string(),
+        dimension,
+    };
+    let run = LanceRun {
+        provider: provider.as_ref(),
+        target: &target,
+        timeout_secs: config.embedding.timeout_secs,
+        batch_size,
+        check,
+    };
+
+    let mut migrated = Vec::new();
+    let memories = match backend {
+        BackendKind::LanceDb => run.migrate(LanceDb::Memory, config, progress).await?,
+        _ => reembed_row_vectors(config, check, progress).await?,
+    };
+    let memories_changed = !memories.is_empty();
+    migrated.extend(memories);
+
+    // Postgres keeps knowledge chunks in its own tables; the others share LanceDB
+    let knowledge_changed = if backend == BackendKind::Postgres {
+        false
+    } else {
+        let knowledge = run.migrate(LanceDb::Knowledge, config, progress).await?;
+        let changed = !knowledge.is_empty();
+        migrated.extend(knowledge);
+        changed
+    };
+
+    if !check {
+        // Swapped tables start without a vector index; build one for the new vectors
+        if memories_changed && backend == BackendKind::LanceDb {
+            let manager = MemoryManager::new(&without_lazy_passes(config), None, None).await?;
+            manager.rebuild_vector_index().await?;
+            manager.optimize().await?;
+        }
+        if knowledge_changed {
+            KnowledgeManager::new(config)
+                .await?
+                .rebuild_vector_index()
+                .await?;
+        }
+    }
+    Ok(migrated)
+}
+
+/// Opening a manager runs lazy passes that embed and write; keep the
+/// migration to re-embedding only
+fn without_lazy_passes(config: &Config) -> Config {
+    let mut config = config.clone();
+    config.memory.stale_ref_cleanup_enabled = false;
+    config.memory.sleep_consolidation_enabled = false;
+    config.memory.importance_tuning_enabled = false;
+    config
+}
+
+/// SQLite/Postgres: re-embed, project by project, the memories whose vector
+/// length differs from the current model's.
+async fn reembed_row_vectors(
+    config: &Config,
+    check: bool,
+    progress: Progress<'_>,
+) -> Result<Vec<TableMigration>> {
+    let config = without_lazy_passes(config);
+    let projects = MemoryManager::new(&config, None, None)
+        .await?
+        .list_projects()
+        .await?;
+    let mut migrated = Vec::new();
+    for project in projects {
+        let manager = MemoryManager::new(&config, Some(project.clone()), None).await?;
+        let label = format!("memories ({})", project);
+        let (rows, stale) = manager
+            .reembed_mismatched(check, &mut |done, total| progress(&label, done, total))
+            .await?;
+        if stale > 0 {
+            migrated.push(TableMigration {
+                table: label,
+                from: None,
+                rows,
+                reembedded: stale,
+                resumed: false,
+            });
+        }
+    }
+    Ok(migrated)
+}
+
+/// A LanceDB database holding stamped vector tables
+#[derive(Debug, Clone, Copy)]
+enum LanceDb {
+    Memory,
+    Knowledge,
+}
+
+/// A table with an `embedding` column and the columns that identify a row
+struct VectorTable {
+    name: &'static str,
+    key: &'static [&'static str],
+}
+
+impl LanceDb {
+    /// Database name, also its directory under `[storage].uri`
+    fn name(self) -> &'static str {
+        match self {
+            Self::Memory => "memory",
+            Self::Knowledge => "knowledge",
+        }
+    }
+
+    fn dir(self) -> Result<PathBuf> {
+        match self {
+            Self::Memory => crate::storage::get_memory_database_path(),
+            Self::Knowledge => crate::storage::get_knowledge_database_path(),
+        }
+    }
+
+    /// Table whose model stamp covers the database
+    fn stamp_table(self) -> &'static str {
+        match self {
+            Self::Memory => "memories",
+            Self::Knowledge => "knowledge_chunks",
+        }
+    }
+
+    fn tables(self) -> &'static [VectorTable] {
+        match self {
+            // The trash keeps whole memory rows, vectors included; one memory
+            // may be in it more than once
+            Self::Memory => &[
+                VectorTable {
+                    name: "memories",
+                    key: &["id"],
+                },
+                VectorTable {
+                    name: "memory_recovery",
+                    key: &["id", "deleted_at"],
+                },
+            ],
+            Self::Knowledge => &[VectorTable {
+                name: "knowledge_chunks",
+                key: &["id"],
+            }],
+        }
+    }
+
+    /// The text each row's vector was generated from
+    fn texts(self, batch: &RecordBatch) -> Result<Vec<String>> {
+        match self {
+            Self::Memory => Ok(MemoryStore::batch_to_memories(batch)?
+                .iter()
+                .map(|memory| memory.get_searchable_text())
+                .collect()),
+            Self::Knowledge => {
+                let content = string_column(batch, "content")?;
+                Ok((0..batch.num_rows())
+                    .map(|i| content.value(i).to_string())
+                    .collect())
+            }
+        }
+    }
+
+    /// Create the missing tables, with their indexes, for `dimension`-sized vectors
+    async fn create_tables(self, db: &Connection, dimension: usize) -> Result<()> {
+        match self {
+            Self::Memory => {
+                MemoryStore::init_tables(db, &MemoryStore::memories_schema(dimension)).await
+            }
+            Self::Knowledge => {
+                KnowledgeStore::initialize_table(db, &KnowledgeStore::build_schema(dimension)).await
+            }
+        }
+    }
+}
+
+/// Settings shared by the LanceDB migrations of one run
+struct LanceRun<'a> {
+    provider: &'a dyn EmbeddingProvider,
+    target: &'a ModelStamp,
+    timeout_secs: u64,
+    batch_size: usize,
+    check: bool,
+}
+
+impl LanceRun<'_> {
+    async fn migrate(
+        &self,
+        kind: LanceDb,
+        config: &Config,
+        progress: Progress<'_>,
+    ) -> Result<Vec<TableMigration>> {
+        let dir = kind.dir()?;
+        if !config.storage.is_remote() && !dir.exists() {
+            return Ok(Vec::new());
+        }
+        let db = crate::storage::lancedb_connection(kind.name(), &dir, &config.storage)
+            .execute()
+            .await?;
+        let names = db.table_names().execute().await?;
+        let exists = |name: &str| names.iter().any(|n| n == name);
+        let resumed = kind.tables().iter().any(|t| exists(&staging_name(t.name)));
+        let recorded = recorded_model_stamp(&dir, kind.stamp_table())?;
+        if !resumed && !self.is_stale(&db, kind, recorded.as_ref()).await? {
+            return Ok(Vec::new());
+        }
+
+        // Holds off writers of the old model while rows are copied
+        let lock = WriteLock::new(
+            &dir,
+            Duration::from_secs(config.memory.write_lock_timeout_secs),
+        );
+        let _guard = if self.check {
+            None
+        } else {
+            Some(lock.acquire().await?)
+        };
+
+        let mut migrated = Vec::new();
+        for table in kind.tables() {
+            let staging = staging_name(table.name);
+            let (rows, reembedded) = if exists(table.name) {
+                self.fill_staging(&db, kind, table, exists(&staging), progress)
+                    .await?
+            } else {
+                (0, 0)
+            };
+            migrated.push(TableMigration {
+                table: table.name.to_string(),
+                from: recorded.clone(),
+                rows,
+                reembedded,
+                resumed: exists(&staging),
+            });
+        }
+        if self.check {
+            return Ok(migrated);
+        }
+
+        // Swap: every staging table is complete, so the originals can go
+        let names = db.table_names().execute().await?;
+        let staged: Vec<&VectorTable> = kind
+            .tables()
+            .iter()
+            .filter(|t| names.contains(&staging_name(t.name)))
+            .collect();
+        for table in &staged {
+            if names.iter().any(|n| n == table.name) {
+                db.drop_table(table.name, &[]).await?;
+            }
+        }
+        kind.create_tables(&db, self.target.dimension).await?;
+        for table in staged {
+            let staging = staging_name(table.name);
+            let target = db.open_table(table.name).execute().await?;
+            copy_rows(&db.open_table(&staging).execute().await?, &target, table).await?;
+            db.drop_table(&staging, &[]).await?;
+        }
+        record_model_stamp(&dir, kind.stamp_table(), self.target.clone())?;
+        Ok(migrated)
+    }
+
+    /// Whether the database holds vectors of another model than the target
+    async fn is_stale(
+        &self,
+        db: &Connection,
+        kind: LanceDb,
+        recorded: Option<&ModelStamp>,
+    ) -> Result<bool> {
+        if let Some(recorded) = recorded {
+            return Ok(recorded != self.target);
+        }
+        // Tables created before stamps were recorded: only the size can tell
+        let table = kind.stamp_table();
+        if !db.table_names().execute().await?.iter().any(|n| n == table) {
+            return Ok(false);
+        }
+        let schema = db.open_table(table).execute().await?.schema().await?;
+        Ok(embedding_dimension(&schema).is_some_and(|d| d != self.target.dimension))
+    }
+
+    /// Embed the rows of `table` missing from its staging table into it.
+    /// Returns (rows in the table, rows embedded now or, with `check`, to embed).
+    async fn fill_staging(
+        &self,
+        db: &Connection,
+        kind: LanceDb,
+        table: &VectorTable,
+        staging_exists: bool,
+        progress: Progress<'_>,
+    ) -> Result<(usize, usize)> {
+        let source = db.open_table(table.name).execute().await?;
+        let total = source.count_rows(None).await?;
+        let staging = staging_name(table.name);
+        let done = if staging_exists {
+            row_keys(&db.open_table(&staging).execute().await?, table.key).await?
+        } else {
+            HashSet::new()
+        };
+        if self.check {
+            return Ok((total, total.saturating_sub(done.len())));
+        }
+
+        let schema = staging_schema(&source.schema().await?, self.target.dimension);
+        let staging = if staging_exists {
+            db.open_table(&staging).execute().await?
+        } else {
+            db.create_empty_table(&staging, schema.clone())
+                .execute()
+                .await?
+        };
+
+        let mut finished = done.len().min(total);
+        let mut embedded = 0;
+        progress(table.name, finished, total);
+        let mut results = source.query().execute().await?;
+        while let Some(batch) = results.try_next().await? {
+            let keys = batch_keys(&batch, table.key)?;
+            let pending = BooleanArray::from(
+                keys.iter()
+                    .map(|key| !done.contains(key))
+                    .collect::<Vec<_>>(),
+            );
+            let batch = filter_record_batch(&batch, &pending)?;
+            let mut offset = 0;
+            while offset < batch.num_rows() {
+                let rows = batch.slice(offset, self.batch_size.min(batch.num_rows() - offset));
+                let vectors = generate_embeddings_batch(
+                    kind.texts(&rows)?,
+                    self.provider,
+                    self.timeout_secs,
+                    InputType::Document,
+                )
+                .await?;
+                let rows = with_embeddings(&rows, &schema, vectors, self.target.dimension)?;
+                let count = rows.num_rows();
+                staging
+                    .add(RecordBatchIterator::new(once(Ok(rows)), schema.clone()))
+                    .execute()
+                    .await?;
+                offset += count;
+                finished += count;
+                embedded += count;
+                progress(table.name, finished, total);
+            }
+        }
+        Ok((total, embedded))
+    }
+}
+
+fn staging_name(table: &str) -> String {
+    format!("{}{}", table, STAGING_SUFFIX)
+}
+
+/// `schema` with its `embedding` column resized to `dimension`
+fn staging_schema(schema: &Schema, dimension: usize) -> Arc<Schema> {
+    let fields: Vec<Field> = schema
+        .fields()
+        .iter()
+        .map(|field| {
+            if field.name() == "embedding" {
+                Field::new(
+                    "embedding",
+                    DataType::FixedSizeList(
+                        Arc::new(Field::new("item", DataType::Float32, true)),
+                        dimension as i32,
+                    ),
+                    field.is_nullable(),
+                )
+            } else {
+                field.as_ref().clone()
+            }
+        })
+        .collect();
+    Arc::new(Schema::new(fields))
+}
+
+/// `batch` laid out as `schema`, with `vectors` as its embedding column
+fn with_embeddings(
+    batch: &RecordBatch,
+    schema: &Arc<Schema>,
+    vectors: Vec<Vec<f32>>,
+    dimension: usize,
+) -> Result<RecordBatch> {
+    if vectors.len() != batch.num_rows() {
+        anyhow::bail!(
+            "Embedding provider returned {} vectors for {} texts",
+            vectors.len(),
+            batch.num_rows()
+        );
+    }
+    if let Some(vector) = vectors.iter().find(|v| v.len() != dimension) {
+        anyhow::bail!(
+            "Embedding provider returned a {}-dimensional vector; expected {}",
+            vector.len(),
+            dimension
+        );
+    }
+    let embedding = FixedSizeListArray::new(
+        Arc::new(Field::new("item", DataType::Float32, true)),
+        dimension as i32,
+        Arc::new(Float32Array::from(vectors.concat())),
+        None,
+    );
+    let mut columns = Vec::with_capacity(schema.fields().len());
+    for field in schema.fields() {
+        if field.name() == "embedding" {
+            columns.push(Arc::new(embedding.clone()) as Arc<dyn Array>);
+        } else {
+            columns.push(
+                batch
+                    .column_by_name(field.name())
+                    .with_context(|| format!("row missing column {}", field.name()))?
+                    .clone(),
+            );
+        }
+    }
+    Ok(RecordBatch::try_new(schema.clone(), columns)?)
+}
+
+/// Identity of each row of `batch`: its `key` columns joined
+fn batch_keys(batch: &RecordBatch, key: &[&str]) -> Result<Vec<String>> {
+    let columns = key
+        .iter()
+        .map(|name| string_column(batch, name))
+        .collect::<Result<Vec<_>>>()?;
+    Ok((0..batch.num_rows())
+        .map(|i| {
+            columns
+                .iter()
+                .map(|column| column.value(i))
+                .collect::<Vec<_>>()
+                .join("\u{1f}")
+        })
+        .collect())
+}
+
+async fn row_keys(table: &Table, key: &[&str]) -> Result<HashSet<String>> {
+    let mut results = table
+        .query()
+        .select(Select::Columns(key.iter().map(|k| k.to_string()).collect()))
+        .execute()
+        .await?;
+    let mut keys = HashSet::new();
+    while let Some(batch) = results.try_next().await? {
+        keys.extend(batch_keys(&batch, key)?);
+    }
+    Ok(keys)
+}
+
+/// Upsert every row of `from` into `to`, keyed so a repeated copy is harmless
+async fn copy_rows(from: &Table, to: &Table, table: &VectorTable) -> Result<()> {
+    let schema = to.schema().await?;
+    let mut results = from.query().execute().await?;
+    while let Some(batch) = results.try_next().await? {
+        if batch.num_rows() == 0 {
+            continue;
+        }
+        let mut columns = Vec::with_capacity(schema.fields().len());
+        for field in schema.fields() {
+            let column = batch
+                .column_by_name(field.name())
+                .with_context(|| format!("{} row missing column {}", table.name, field.name()))?;
+            columns.push(column.clone());
+        }
+        let batch = RecordBatch::try_new(schema.clone(), columns)?;
+        let mut merge = to.merge_insert(table.key);
+        merge
+            .when_matched_update_all(None)
+            .when_not_matched_insert_all();
+        merge
+            .execute(Box::new(RecordBatchIterator::new(
+                once(Ok(batch)),
+                schema.clone(),
+            )))
+            .await?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow_array::StringArray;
+
+    #[test]
+    fn test_with_embeddings_replaces_vector_column() {
+        let item = Arc::new(Field::new("item", DataType::Float32, true));
+        let old = Arc::new(Schema::new(vec![
+            Field::new("id", DataType::Utf8, false),
+            Field::new("embedding", DataType::FixedSizeList(item.clone(), 2), false),
+        ]));
+        let batch = RecordBatch::try_new(
+            old.clone(),
+            vec![
+                Arc::new(StringArray::from(vec!["a", "b"])),
+                Arc::new(FixedSizeListArray::new(
+                    item,
+                    2,
+                    Arc::new(Float32Array::from(vec![0.0; 4])),
+                    None,
+                )),
+            ],
+        )
+        .unwrap();
+
+        let schema = staging_schema(&old, 3);
+        assert_eq!(embedding_dimension(&schema), Some(3));
+        let vectors = vec![vec![1.0, 2.0, 3.0], vec![4.0, 5.0, 6.0]];
+        let out = with_embeddings(&batch, &schema, vectors, 3).unwrap();
+        assert_eq!(batch_keys(&out, &["id"]).unwrap(), vec!["a", "b"]);
+        let embedding = out
+            .column_by_name("embedding")
+            .unwrap()
+            .as_any()
+            .downcast_ref::<FixedSizeListArray>()
+            .unwrap();
+        assert_eq!(embedding.value_length(), 3);
+
+        assert!(with_embeddings(&batch, &schema, vec![vec![1.0; 3]], 3).is_err());
+        assert!(with_embeddings(&batch, &schema, vec![vec![1.0; 2]; 2], 3).is_err());
+    }
+
+    #[test]
+    fn test_batch_keys_join_key_columns() {
+        let batch = RecordBatch::try_new(
+            Arc::new(Schema::new(vec![
+                Field::new("id", DataType::Utf8, false),
+                Field::new("deleted_at", DataType::Utf8, false),
+            ])),
+            vec![
+                Arc::new(StringArray::from(vec!["a", "a"])),
+                Arc::new(StringArray::from(vec!["t1", "t2"])),
+            ],
+        )
+        .unwrap();
+        let keys = batch_keys(&batch, &["id", "deleted_at"]).unwrap();
+        assert_eq!(keys.len(), 2);
+        assert_ne!(keys[0], keys[1]);
+        assert_eq!(batch_keys(&batch, &["id"]).unwrap(), vec!["a", "a"]);
+    }
+}
@@ -0,0 +1,224 @@
+// Copyright 2026 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Schema versions of the LanceDB databases.
+//!
+//! Each database records the migrations applied to it in a `schema_version`
+//! table, one row per version. When a store opens, the migrations above the
+//! recorded version run in order and each is recorded as soon as it
+//! completes, so an interrupted upgrade resumes where it stopped.
+//!
+//! Migrations must be idempotent: databases that pre-date the table start at
+//! version 0 although some changes may already be in place, and new tables
+//! are created with the current schema before the runner sees them.
+
+use anyhow::{Context, Result};
+use arrow::record_batch::RecordBatchIterator;
+use arrow_array::{Int32Array, RecordBatch, StringArray};
+use arrow_schema::{DataType, Field, Schema};
+use chrono::Utc;
+use futures::TryStreamExt;
+use lancedb::query::{ExecutableQuery, QueryBase, Select};
+use lancedb::{Connection, Table};
+use std::future::Future;
+use std::iter::once;
+use std::sync::Arc;
+
+use crate::arrow_helpers::i32_column;
+
+/// Table holding the applied migrations of a database
+pub const SCHEMA_VERSION_TABLE: &str = "schema_version";
+
+/// One in-place change to the tables of a database
+#[derive(Debug, Clone, Copy)]
+pub struct Migration {
+    pub version: u32,
+    pub description: &'static str,
+}
+
+fn version_schema() -> Arc<Schema> {
+    Arc::new(Schema::new(vec![
+        Field::new("version", DataType::Int32, false),
+        Field::new("description", DataType::Utf8, false),
+        Field::new("applied_at", DataType::Utf8, false),
+    ]))
+}
+
+async fn version_table(db: &Connection) -> Result<Table> {
+    let names = db.table_names().execute().await?;
+    if names.iter().any(|n| n == SCHEMA_VERSION_TABLE) {
+        return Ok(db.open_table(SCHEMA_VERSION_TABLE).execute().await?);
+    }
+    Ok(db
+        .create_empty_table(SCHEMA_VERSION_TABLE, version_schema())
+        .execute()
+        .await?)
+}
+
+/// Highest version recorded in the version table; 0 when none is
+async fn recorded_version(table: &Table) -> Result<u32> {
+    let batches: Vec<RecordBatch> = table
+        .query()
+        .select(Select::Columns(vec!["version".to_string()]))
+        .execute()
+        .await?
+        .try_collect()
+        .await?;
+    let mut version = 0;
+    for batch in &batches {
+        let versions = i32_column(batch, "version")?;
+        for i in 0..batch.num_rows() {
+            version = version.max(versions.value(i).max(0) as u32);
+        }
+    }
+    Ok(version)
+}
+
+async fn record(table: &Table, migration: &Migration) -> Result<()> {
+    let schema = version_schema();
+    let batch = RecordBatch::try_new(
+        schema.clone(),
+        vec![
+            Arc::new(Int32Array::from(vec![migration.version as i32])),
+            Arc::new(StringArray::from(vec![migration.description])),
+            Arc::new(StringArray::from(vec![Utc::now().to_rfc3339()])),
+        ],
+    )?;
+    table
+        .add(RecordBatchIterator::new(once(Ok(batch)), schema))
+        .execute()
+        .await?;
+    Ok(())
+}
+
+/// Bring the `label` database to the last of `migrations` (ascending by
+/// version): `apply` runs each one above the recorded version. Fails for a
+/// database written by a newer octobrain. The caller holds the write lock.
+pub async fn migrate<F, Fut>(
+    db: &Connection,
+    label: &str,
+    migrations: &[Migration],
+    mut apply: F,
+) -> Result<u32>
+where
+    F: FnMut(u32) -> Fut,
+    Fut: Future<Output = Result<()>>,
+{
+    let table = version_table(db).await?;
+    let current = recorded_version(&table).await?;
+    let latest = migrations.last().map_or(0, |m| m.version);
+    if current > latest {
+        anyhow::bail!(
+            "The {} database has schema version {}, newer than version {} this octobrain \
+             supports. Upgrade octobrain to open it",
+            label,
+            current,
+            latest
+        );
+    }
+
+    for migration in migrations.iter().filter(|m| m.version > current) {
+        tracing::info!(
+            "Migrating {} database to schema version {}: {}",
+            label,
+            migration.version,
+            migration.description
+        );
+        apply(migration.version).await.with_context(|| {
+            format!(
+                "Schema migration {} of the {} database ({}) failed",
+                migration.version, label, migration.description
+            )
+        })?;
+        record(&table, migration).await?;
+    }
+    Ok(latest)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    async fn current_version(db: &Connection) -> u32 {
+        recorded_version(&version_table(db).await.unwrap())
+            .await
+            .unwrap()
+    }
+
+    const MIGRATIONS: [Migration; 2] = [
+        Migration {
+            version: 1,
+            description: "first",
+        },
+        Migration {
+            version: 2,
+            description: "second",
+        },
+    ];
+
+    async fn test_db() -> Connection {
+        let dir = std::env::temp_dir().join(format!("octobrain_schema_{}", uuid::Uuid::new_v4()));
+        lancedb::connect(dir.to_str().unwrap())
+            .execute()
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_migrations_run_once_in_order() {
+        let db = test_db().await;
+        let applied = Mutex::new(Vec::new());
+        let run = |version| {
+            applied.lock().unwrap().push(version);
+            async { Ok(()) }
+        };
+
+        assert_eq!(
+            migrate(&db, "test", &MIGRATIONS[..1], run).await.unwrap(),
+            1
+        );
+        assert_eq!(migrate(&db, "test", &MIGRATIONS, run).await.unwrap(), 2);
+        assert_eq!(migrate(&db, "test", &MIGRATIONS, run).await.unwrap(), 2);
+        assert_eq!(*applied.lock().unwrap(), vec![1, 2]);
+        assert_eq!(current_version(&db).await, 2);
+    }
+
+    #[tokio::test]
+    async fn test_failed_migration_is_retried_and_newer_database_refused() {
+        let db = test_db().await;
+        let err = migrate(&db, "test", &MIGRATIONS, |version| async move {
+            anyhow::ensure!(version < 2, "boom");
+            Ok(())
+        })
+        .await
+        .unwrap_err();
+        assert!(format!("{:#}", err).contains("boom"));
+        assert_eq!(current_version(&db).await, 1);
+
+        migrate(&db, "test", &MIGRATIONS, |_| async { Ok(()) })
+            .await
+            .unwrap();
+        let err = migrate(&db, "test", &MIGRATIONS[..1], |_| async { Ok(()) })
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("newer"));
+    }
+}
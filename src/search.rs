@@ -0,0 +1,316 @@
+// Copyright 2026 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Unified search across the memory store and the knowledge base.
+//!
+//! The two stores score on different scales (memory blends vector, BM25,
+//! recency and importance; knowledge is plain vector similarity), so raw scores
+//! are not comparable. Results are fused either by weighted min-max normalized
+//! scores or by Reciprocal Rank Fusion over each store's own ranking.
+
+use serde::Serialize;
+
+use crate::knowledge::stitch::chunk_body;
+use crate::knowledge::types::KnowledgeSearchResult;
+use crate::memory::MemorySearchResult;
+
+/// Characters of content shown per hit.
+const SNIPPET_CHARS: usize = 200;
+
+/// Below this spread a list's scores are treated as equal and kept as-is.
+const MIN_SPREAD: f32 = 1e-6;
+
+/// Rank offset of Reciprocal Rank Fusion; damps the lead of the very top ranks.
+pub const RRF_K: f32 = 60.0;
+
+/// How per-store result lists are combined
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FusionMethod {
+    /// Reciprocal Rank Fusion: only each hit's rank within its store counts
+    #[default]
+    Rrf,
+    /// Min-max normalized scores, scaled by the store's weight
+    Weighted,
+}
+
+/// Which store a hit came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HitOrigin {
+    Memory,
+    Knowledge,
+}
+
+impl HitOrigin {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Memory => "memory",
+            Self::Knowledge => "knowledge",
+        }
+    }
+}
+
+/// One result of a unified search, labelled with its origin
+#[derive(Debug, Clone, Serialize)]
+pub struct UnifiedHit {
+    pub origin: HitOrigin,
+    /// Memory ID or knowledge chunk ID
+    pub id: String,
+    pub title: String,
+    /// Memory type, or knowledge source with its section path
+    pub location: String,
+    pub snippet: String,
+    /// Score after per-store normalization, comparable across origins
+    pub score: f32,
+    /// Score as reported by the originating store
+    pub raw_score: f32,
+}
+
+impl UnifiedHit {
+    pub fn from_memory(result: &MemorySearchResult) -> Self {
+        Self {
+            origin: HitOrigin::Memory,
+            id: result.memory.id.clone(),
+            title: result.memory.title.clone(),
+            location: result.memory.memory_type.to_string(),
+            snippet: snippet(&result.memory.content),
+            score: result.relevance_score,
+            raw_score: result.relevance_score,
+        }
+    }
+
+    pub fn from_knowledge(result: &KnowledgeSearchResult) -> Self {
+        let chunk = &result.chunk;
+        let location = if chunk.section_path.is_empty() {
+            chunk.source.clone()
+        } else {
+            format!("{} > {}", chunk.source, chunk.section_path.join(" > "))
+        };
+        Self {
+            origin: HitOrigin::Knowledge,
+            id: chunk.id.clone(),
+            title: chunk.source_title.clone(),
+            location,
+            snippet: snippet(chunk_body(&chunk.content)),
+            score: result.relevance_score,
+            raw_score: result.relevance_score,
+        }
+    }
+}
+
+fn snippet(text: &str) -> String {
+    let flat = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    if flat.chars().count() <= SNIPPET_CHARS {
+        return flat;
+    }
+    let mut cut: String = flat.chars().take(SNIPPET_CHARS).collect();
+    cut.push('…');
+    cut
+}
+
+/// Min-max normalize `hits` in place to 0..1. A list whose scores are all equal
+/// (including a single hit) keeps its raw scores clamped to 0..1, so one weak
+/// hit is not promoted to a perfect match.
+pub fn normalize_scores(hits: &mut [UnifiedHit]) {
+    let (min, max) = hits.iter().fold((f32::MAX, f32::MIN), |(lo, hi), h| {
+        (lo.min(h.raw_score), hi.max(h.raw_score))
+    });
+    let spread = max - min;
+    for hit in hits.iter_mut() {
+        hit.score = if spread > MIN_SPREAD {
+            (hit.raw_score - min) / spread
+        } else {
+            hit.raw_score.clamp(0.0, 1.0)
+        };
+    }
+}
+
+/// Normalize each list, then merge them by normalized score (memory first on
+/// ties) and keep the best `limit`.
+pub fn interleave(
+    memories: Vec<UnifiedHit>,
+    knowledge: Vec<UnifiedHit>,
+    limit: usize,
+) -> Vec<UnifiedHit> {
+    fuse(memories, knowledge, FusionMethod::Weighted, 0.5, limit)
+}
+
+/// Score both lists with `method`, weighting memories by `memory_weight` and
+/// knowledge by the remainder, then merge and keep the best `limit`. The
+/// heavier store's top hit scores 1.0; a store weighted 0 is left out.
+pub fn fuse(
+    mut memories: Vec<UnifiedHit>,
+    mut knowledge: Vec<UnifiedHit>,
+    method: FusionMethod,
+    memory_weight: f32,
+    limit: usize,
+) -> Vec<UnifiedHit> {
+    let memory_weight = memory_weight.clamp(0.0, 1.0);
+    let heaviest = memory_weight.max(1.0 - memory_weight);
+    let mut merged = Vec::with_capacity(memories.len() + knowledge.len());
+    for (hits, weight) in [
+        (&mut memories, memory_weight),
+        (&mut knowledge, 1.0 - memory_weight),
+    ] {
+        if weight <= 0.0 {
+            continue;
+        }
+        let scale = weight / heaviest;
+        match method {
+            FusionMethod::Weighted => {
+                normalize_scores(hits);
+                hits.iter_mut().for_each(|h| h.score *= scale);
+            }
+            FusionMethod::Rrf => {
+                hits.sort_by(|a, b| b.raw_score.total_cmp(&a.raw_score));
+                for (rank, hit) in hits.iter_mut().enumerate() {
+                    hit.score = scale * (RRF_K + 1.0) / (RRF_K + 1.0 + rank as f32);
+                }
+            }
+        }
+        merged.append(hits);
+    }
+    // Stable sort keeps each store's own order among equal scores
+    merged.sort_by(|a, b| {
+        b.score
+            .total_cmp(&a.score)
+            .then_with(|| (a.origin as u8).cmp(&(b.origin as u8)))
+    });
+    merged.truncate(limit);
+    merged
+}
+
+/// Plain-text listing with an origin label on every hit.
+pub fn format_hits(hits: &[UnifiedHit]) -> String {
+    if hits.is_empty() {
+        return "No results found".to_string();
+    }
+    let mut output = format!("Found {} results\n", hits.len());
+    for (i, hit) in hits.iter().enumerate() {
+        output.push_str(&format!(
+            "\n{}. [{}] {} ({:.0}%)\n   {}\n   ID: {}\n   {}\n",
+            i + 1,
+            hit.origin.as_str(),
+            hit.title,
+            hit.score * 100.0,
+            hit.location,
+            hit.id,
+            hit.snippet
+        ));
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hit(origin: HitOrigin, id: &str, raw: f32) -> UnifiedHit {
+        UnifiedHit {
+            origin,
+            id: id.to_string(),
+            title: id.to_string(),
+            location: String::new(),
+            snippet: String::new(),
+            score: raw,
+            raw_score: raw,
+        }
+    }
+
+    fn ids(hits: &[UnifiedHit]) -> Vec<&str> {
+        hits.iter().map(|h| h.id.as_str()).collect()
+    }
+
+    #[test]
+    fn test_normalize_spreads_scores_to_unit_range() {
+        let mut hits = vec![
+            hit(HitOrigin::Memory, "a", 0.25),
+            hit(HitOrigin::Memory, "b", 0.5),
+            hit(HitOrigin::Memory, "c", 0.75),
+        ];
+        normalize_scores(&mut hits);
+        let scores: Vec<f32> = hits.iter().map(|h| h.score).collect();
+        assert_eq!(scores, vec![0.0, 0.5, 1.0]);
+        assert_eq!(hits[2].raw_score, 0.75);
+    }
+
+    #[test]
+    fn test_single_hit_keeps_raw_score() {
+        let mut hits = vec![hit(HitOrigin::Knowledge, "k", 0.3)];
+        normalize_scores(&mut hits);
+        assert_eq!(hits[0].score, 0.3);
+    }
+
+    #[test]
+    fn test_interleave_compares_normalized_scores() {
+        // Memory scores are tiny on their raw scale but still rank first when best
+        let memories = vec![
+            hit(HitOrigin::Memory, "m1", 0.05),
+            hit(HitOrigin::Memory, "m2", 0.01),
+        ];
+        let knowledge = vec![
+            hit(HitOrigin::Knowledge, "k1", 0.9),
+            hit(HitOrigin::Knowledge, "k2", 0.7),
+            hit(HitOrigin::Knowledge, "k3", 0.8),
+        ];
+        let merged = interleave(memories, knowledge, 4);
+        assert_eq!(ids(&merged), vec!["m1", "k1", "k3", "m2"]);
+    }
+
+    #[test]
+    fn test_rrf_alternates_by_rank_regardless_of_raw_scale() {
+        let memories = vec![
+            hit(HitOrigin::Memory, "m1", 0.05),
+            hit(HitOrigin::Memory, "m2", 0.04),
+        ];
+        let knowledge = vec![
+            hit(HitOrigin::Knowledge, "k2", 0.7),
+            hit(HitOrigin::Knowledge, "k1", 0.9),
+        ];
+        let merged = fuse(memories, knowledge, FusionMethod::Rrf, 0.5, 10);
+        assert_eq!(ids(&merged), vec!["m1", "k1", "m2", "k2"]);
+        assert_eq!(merged[0].score, 1.0);
+    }
+
+    #[test]
+    fn test_weights_favour_one_store() {
+        let memories = vec![hit(HitOrigin::Memory, "m1", 0.5)];
+        let knowledge = vec![
+            hit(HitOrigin::Knowledge, "k1", 0.9),
+            hit(HitOrigin::Knowledge, "k2", 0.8),
+        ];
+        let merged = fuse(
+            memories.clone(),
+            knowledge.clone(),
+            FusionMethod::Rrf,
+            0.25,
+            10,
+        );
+        assert_eq!(ids(&merged), vec!["k1", "k2", "m1"]);
+
+        let merged = fuse(memories, knowledge, FusionMethod::Weighted, 1.0, 10);
+        assert_eq!(ids(&merged), vec!["m1"]);
+    }
+
+    #[test]
+    fn test_format_labels_each_origin() {
+        let text = format_hits(&[
+            hit(HitOrigin::Memory, "m1", 1.0),
+            hit(HitOrigin::Knowledge, "k1", 0.5),
+        ]);
+        assert!(text.contains("1. [memory] m1 (100%)"));
+        assert!(text.contains("2. [knowledge] k1 (50%)"));
+    }
+}
@@ -18,10 +18,24 @@ use std::fs;
 use std::path::{Path, PathBuf};
 use std::process::Command;
 
+use crate::config::StorageConfig;
+
 /// Get the system-wide storage directory for Octobrain
 /// Following XDG Base Directory specification on Unix-like systems
 /// and proper conventions on other systems
 pub fn get_system_storage_dir() -> Result<PathBuf> {
+    let base_dir = system_storage_dir_path()?;
+
+    // Create directory if it doesn't exist
+    if !base_dir.exists() {
+        fs::create_dir_all(&base_dir)?;
+    }
+
+    Ok(base_dir)
+}
+
+/// Location of the system-wide storage directory, without creating it
+fn system_storage_dir_path() -> Result<PathBuf> {
     let base_dir = if cfg!(target_os = "macos") {
         // macOS: ~/.local/share/octobrain
         dirs::home_dir()
@@ -46,12 +60,6 @@ pub fn get_system_storage_dir() -> Result<PathBuf> {
                 .join("octobrain")
         }
     };
-
-    // Create directory if it doesn't exist
-    if !base_dir.exists() {
-        fs::create_dir_all(&base_dir)?;
-    }
-
     Ok(base_dir)
 }
 
@@ -83,6 +91,18 @@ pub fn get_project_identifier(project_path: &Path) -> Result<String> {
     Ok(format!("{:x}", result)[..16].to_string()) // Use first 16 chars
 }
 
+/// Project key for a `--project` value. An existing directory resolves to the ID
+/// its memories are stored under (derived the way the MCP server derives it from
+/// the workspace); anything else is taken as a project key as-is.
+pub fn resolve_project_key(value: &str) -> String {
+    let path = Path::new(value);
+    if !path.is_dir() {
+        return value.to_string();
+    }
+    let path = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+    octolib::utils::path_to_id(&path)
+}
+
 /// Try to get the Git remote URL for a project
 fn get_git_remote_url(project_path: &Path) -> Result<String> {
     let output = Command::new("git")
@@ -146,6 +166,86 @@ pub fn get_memory_database_path() -> Result<PathBuf> {
     Ok(system_dir.join("memory"))
 }
 
+/// Get the knowledge database path (LanceDB backend), shared by all projects.
+pub fn get_knowledge_database_path() -> Result<PathBuf> {
+    let system_dir = get_system_storage_dir()?;
+    Ok(system_dir.join("knowledge"))
+}
+
+/// Connection to the LanceDB database `name` (`memory` or `knowledge`): in
+/// the object store of `[storage].uri` when set, otherwise at `local_dir`.
+/// `local_dir` still holds the lock and model stamp files either way.
+pub fn lancedb_connection(
+    name: &str,
+    local_dir: &Path,
+    storage: &StorageConfig,
+) -> lancedb::connection::ConnectBuilder {
+    match storage.remote_uri(name) {
+        Some(uri) => lancedb::connect(&uri).storage_options(storage.options.clone()),
+        None => lancedb::connect(&local_dir.to_string_lossy()),
+    }
+}
+
+/// Total size of the files under `dir`; unreadable entries count as empty.
+pub fn dir_size(dir: &Path) -> u64 {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return 0;
+    };
+    entries
+        .flatten()
+        .map(|entry| match entry.metadata() {
+            Ok(meta) if meta.is_dir() => dir_size(&entry.path()),
+            Ok(meta) => meta.len(),
+            Err(_) => 0,
+        })
+        .sum()
+}
+
+/// Get the directory holding memory attachment blobs.
+/// Layout: `attachments/<project_label>/<memory_id>/<file_name>`.
+pub fn get_attachments_dir() -> Result<PathBuf> {
+    let system_dir = get_system_storage_dir()?;
+    Ok(system_dir.join("attachments"))
+}
+
+/// Get the path of the local Ed25519 key used to sign shared memory bundles.
+/// Created on first `memory share export`.
+pub fn get_share_key_path() -> Result<PathBuf> {
+    let system_dir = get_system_storage_dir()?;
+    Ok(system_dir.join("share_key.pk8"))
+}
+
+/// Get the path of the client-side sync watermarks (`octobrain sync`).
+pub fn get_sync_state_path() -> Result<PathBuf> {
+    let system_dir = get_system_storage_dir()?;
+    Ok(system_dir.join("sync_state.json"))
+}
+
+/// Get the path of the server-side receive times of pushed changes
+/// (`octobrain serve --sync`).
+pub fn get_sync_receipts_path() -> Result<PathBuf> {
+    let system_dir = get_system_storage_dir()?;
+    Ok(system_dir.join("sync_receipts.json"))
+}
+
+/// Get the path of the memory alias book (`memory alias set`).
+pub fn get_aliases_path() -> Result<PathBuf> {
+    let system_dir = get_system_storage_dir()?;
+    Ok(system_dir.join("aliases.json"))
+}
+
+/// Get the path of the search relevance feedback (`memory feedback`).
+pub fn get_feedback_path() -> Result<PathBuf> {
+    let system_dir = get_system_storage_dir()?;
+    Ok(system_dir.join("feedback.json"))
+}
+
+/// Get the path of the spaced-repetition review cards (`memory review`).
+pub fn get_reviews_path() -> Result<PathBuf> {
+    let system_dir = get_system_storage_dir()?;
+    Ok(system_dir.join("reviews.json"))
+}
+
 /// Get the system config file path
 /// Stored directly under ~/.local/share/octobrain/ on all systems
 pub fn get_system_config_path() -> Result<PathBuf> {
@@ -163,3 +263,44 @@ pub fn get_config_path() -> Result<PathBuf> {
         get_system_config_path()
     }
 }
+
+/// Same as `get_config_path`, but never creates the storage directory
+/// (`--ephemeral` runs must not touch the filesystem).
+pub fn find_config_path() -> Result<PathBuf> {
+    if let Ok(env_path) = std::env::var("OCTOBRAIN_CONFIG_PATH") {
+        Ok(PathBuf::from(env_path))
+    } else {
+        Ok(system_storage_dir_path()?.join("config.toml"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_project_key_keeps_keys_and_resolves_paths() {
+        assert_eq!(resolve_project_key("my-project"), "my-project");
+        assert_eq!(resolve_project_key("3f2a9c1b7e4d5a60"), "3f2a9c1b7e4d5a60");
+
+        let dir = std::env::temp_dir().join(format!("octobrain_project_{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        let expected = octolib::utils::path_to_id(&dir.canonicalize().unwrap());
+        assert_eq!(resolve_project_key(dir.to_str().unwrap()), expected);
+        // Relative and absolute spellings of the same directory agree
+        let relative = dir.join("..").join(dir.file_name().unwrap());
+        assert_eq!(resolve_project_key(relative.to_str().unwrap()), expected);
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_dir_size_counts_nested_files() {
+        let dir = std::env::temp_dir().join(format!("octobrain_size_{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(dir.join("data")).unwrap();
+        fs::write(dir.join("a.lance"), [0u8; 100]).unwrap();
+        fs::write(dir.join("data").join("b.lance"), [0u8; 28]).unwrap();
+        assert_eq!(dir_size(&dir), 128);
+        assert_eq!(dir_size(&dir.join("missing")), 0);
+        fs::remove_dir_all(&dir).ok();
+    }
+}
@@ -1,6 +1,11 @@
 // Copyright 2026 Muvon Un Limited
 //
-use lancedb::DistanceType;
+use anyhow::Result;
+use lancedb::index::vector::IvfPqIndexBuilder;
+use lancedb::index::Index;
+use lancedb::table::{CompactionOptions, OptimizeAction, OptimizeOptions};
+use lancedb::{DistanceType, Table};
+use serde::Serialize;
 
 /// Parameters for vector index optimization
 pub struct IndexParams {
@@ -70,3 +75,179 @@ impl VectorOptimizer {
         }
     }
 }
+
+/// IVF-PQ index definition for the `embedding` column
+pub fn ivf_pq_index(params: &IndexParams) -> Index {
+    Index::IvfPq(
+        IvfPqIndexBuilder::default()
+            .distance_type(params.distance_type)
+            .num_partitions(params.num_partitions)
+            .num_sub_vectors(params.num_sub_vectors)
+            .num_bits(params.num_bits as u32),
+    )
+}
+
+/// Outcome of a forced vector index rebuild
+#[derive(Debug, Clone, Serialize)]
+pub struct IndexRebuild {
+    pub table: String,
+    pub rows: usize,
+    /// An existing `embedding` index was dropped
+    pub dropped: bool,
+    /// A new index was built; false when the table is small enough for brute force
+    pub created: bool,
+    pub num_partitions: u32,
+    pub num_sub_vectors: u32,
+}
+
+/// Drop the `embedding` index of `table` and build a new one sized for its
+/// current row count. Callers serialize this with their other writes.
+pub async fn rebuild_vector_index(table: &Table, vector_dim: usize) -> Result<IndexRebuild> {
+    let rows = table.count_rows(None).await?;
+    let existing: Vec<String> = table
+        .list_indices()
+        .await?
+        .into_iter()
+        .filter(|idx| idx.columns == vec!["embedding"])
+        .map(|idx| idx.name)
+        .collect();
+    for name in &existing {
+        table.drop_index(name).await?;
+    }
+
+    let params = VectorOptimizer::calculate_index_params(rows, vector_dim);
+    if params.should_create_index {
+        table
+            .create_index(&["embedding"], ivf_pq_index(&params))
+            .execute()
+            .await?;
+    }
+    Ok(IndexRebuild {
+        table: table.name().to_string(),
+        rows,
+        dropped: !existing.is_empty(),
+        created: params.should_create_index,
+        num_partitions: params.num_partitions,
+        num_sub_vectors: params.num_sub_vectors,
+    })
+}
+
+/// Outcome of compacting one table (`storage optimize`)
+#[derive(Debug, Clone, Serialize)]
+pub struct TableCompaction {
+    pub table: String,
+    /// Small fragments merged away, and the fragments written in their place
+    pub fragments_removed: usize,
+    pub fragments_added: usize,
+    /// Old table versions deleted
+    pub versions_pruned: u64,
+    /// Bytes of data files deleted with those versions
+    pub bytes_reclaimed: u64,
+}
+
+/// Merge the small fragments of `table`, fold unindexed rows into its
+/// indexes, then delete the versions older than `keep` and the files only
+/// they referenced. Callers serialize this with their other writes.
+pub async fn compact_table(table: &Table, keep: chrono::Duration) -> Result<TableCompaction> {
+    let compacted = table
+        .optimize(OptimizeAction::Compact {
+            options: CompactionOptions::default(),
+            remap_options: None,
+        })
+        .await?;
+    table
+        .optimize(OptimizeAction::Index(OptimizeOptions::default()))
+        .await?;
+    let pruned = table
+        .optimize(OptimizeAction::Prune {
+            older_than: Some(keep),
+            delete_unverified: None,
+            error_if_tagged_old_versions: None,
+        })
+        .await?;
+    let compaction = compacted.compaction;
+    let prune = pruned.prune;
+    Ok(TableCompaction {
+        table: table.name().to_string(),
+        fragments_removed: compaction.as_ref().map_or(0, |c| c.fragments_removed),
+        fragments_added: compaction.as_ref().map_or(0, |c| c.fragments_added),
+        versions_pruned: prune.as_ref().map_or(0, |p| p.old_versions),
+        bytes_reclaimed: prune.as_ref().map_or(0, |p| p.bytes_removed),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow::record_batch::RecordBatchIterator;
+    use arrow_array::{FixedSizeListArray, Float32Array, Int32Array, RecordBatch};
+    use arrow_schema::{DataType, Field, Schema};
+    use std::sync::Arc;
+
+    const DIM: usize = 8;
+
+    async fn table(rows: usize) -> Table {
+        let dir = std::env::temp_dir().join(format!("octobrain_index_{}", uuid::Uuid::new_v4()));
+        let db = lancedb::connect(dir.to_str().unwrap())
+            .execute()
+            .await
+            .unwrap();
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("n", DataType::Int32, false),
+            Field::new(
+                "embedding",
+                DataType::FixedSizeList(
+                    Arc::new(Field::new("item", DataType::Float32, true)),
+                    DIM as i32,
+                ),
+                false,
+            ),
+        ]));
+        let values: Vec<f32> = (0..rows * DIM)
+            .map(|i| ((i * 7919) % 1009) as f32 / 1009.0)
+            .collect();
+        let batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![
+                Arc::new(Int32Array::from_iter_values(0..rows as i32)),
+                Arc::new(FixedSizeListArray::new(
+                    Arc::new(Field::new("item", DataType::Float32, true)),
+                    DIM as i32,
+                    Arc::new(Float32Array::from(values)),
+                    None,
+                )),
+            ],
+        )
+        .unwrap();
+        db.create_table(
+            "vectors",
+            RecordBatchIterator::new(std::iter::once(Ok(batch)), schema),
+        )
+        .execute()
+        .await
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_rebuild_replaces_the_index_and_drops_it_below_threshold() {
+        let table = table(1200).await;
+
+        let first = rebuild_vector_index(&table, DIM).await.unwrap();
+        assert_eq!(first.rows, 1200);
+        assert!(first.created);
+        assert!(!first.dropped);
+        assert_eq!(first.num_partitions, 34);
+
+        let second = rebuild_vector_index(&table, DIM).await.unwrap();
+        assert!(second.created && second.dropped);
+        assert_eq!(table.list_indices().await.unwrap().len(), 1);
+
+        // Under the threshold the index goes away and search falls back to brute force
+        table.delete("n >= 100").await.unwrap();
+        let small = rebuild_vector_index(&table, DIM).await.unwrap();
+        assert_eq!(small.rows, 100);
+        assert!(small.dropped);
+        assert!(!small.created);
+        assert!(table.list_indices().await.unwrap().is_empty());
+    }
+}